@@ -0,0 +1,118 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * AlphaShape.hpp
+ *
+ * Alpha shapes (concave hulls) of point clouds projected onto the xy plane,
+ * e.g. to extract building footprints or object outlines.
+ */
+
+#ifndef LVR2_ALGORITHM_ALPHASHAPE_HPP
+#define LVR2_ALGORITHM_ALPHASHAPE_HPP
+
+#include "lvr2/io/vector/PolylineIO.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <array>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Delaunay triangulates the 2D points (see Delaunay2D) and returns all triangles
+ *        with a circumradius of at most alpha as counter clockwise
+ *        triples of point indices. Duplicate points are only used once.
+ *
+ * @param points    The 2D points
+ * @param alpha     Maximum circumradius of a triangle. Pass infinity to
+ *                  get a triangulation of the convex hull.
+ */
+std::vector<std::array<unsigned int, 3>> alphaTriangles2D(const std::vector<Vector2d>& points, double alpha);
+
+/// A boundary loop of a triangulation as a sequence of point indices
+struct BoundaryRing
+{
+    std::vector<unsigned int> indices;
+
+    /// False, if the loop could not be closed (inconsistent triangulation)
+    bool closed = true;
+};
+
+/**
+ * @brief Chains the boundary edges of the triangles (edges whose reverse
+ *        edge is not used by any triangle) to rings.
+ *
+ *        If all triangles are counter clockwise, outer boundaries are
+ *        counter clockwise rings and holes are clockwise rings. Rings with
+ *        less than 3 points are dropped.
+ *
+ * @param triangles     The triangles, e.g. from alphaTriangles2D()
+ */
+std::vector<BoundaryRing> boundaryRings(const std::vector<std::array<unsigned int, 3>>& triangles);
+
+/**
+ * @brief Computes the 2D alpha shape of the points projected onto the xy
+ *        plane.
+ *
+ *        The shape consists of all triangles of the Delaunay triangulation
+ *        whose circumradius is at most alpha. Small values follow concave
+ *        parts closely but may split the shape into several components,
+ *        very large values result in the convex hull.
+ *
+ * @param points    The point cloud
+ * @param alpha     Maximum circumradius of a triangle
+ *
+ * @return A mesh with the original (3D) points as vertices that were used
+ *         by at least one triangle. Empty, if less than 3 points are given.
+ */
+MeshBufferPtr alphaShape2D(PointBufferPtr points, double alpha);
+
+/**
+ * @brief Computes the boundary of the 2D alpha shape, see alphaShape2D().
+ *
+ *        Each component results in one closed, counter clockwise polyline
+ *        for its outer boundary and one clockwise polyline per hole.
+ *        The z coordinates of the original points are preserved.
+ *
+ * @param points    The point cloud
+ * @param alpha     Maximum circumradius of a triangle
+ * @param layer     Layer name assigned to the polylines
+ */
+std::vector<Polyline> alphaShapeOutlines(
+    PointBufferPtr points,
+    double alpha,
+    const std::string& layer = "outlines"
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_ALPHASHAPE_HPP
@@ -32,6 +32,7 @@
 #include <algorithm>
 #include <unordered_set>
 #include <memory>
+#include <vector>
 #include <boost/core/typeinfo.hpp>
 
 #include "lvr2/types/MultiChannelMap.hpp"
@@ -120,6 +121,48 @@ private:
     const size_t m_right;
 };
 
+/**
+ * @brief Copies the elements with the given indices into a new channel.
+ *        Channels whose number of elements differs from the expected
+ *        element count (e.g. atomics or meta data) are passed through
+ *        unchanged.
+ */
+class Select : public boost::static_visitor< MultiChannelMap::val_type >
+{
+public:
+    Select(const std::vector<size_t>& indices, size_t numElements)
+    :m_indices(indices)
+    ,m_numElements(numElements)
+    {}
+
+    template<typename T>
+    MultiChannelMap::val_type operator()(const Channel<T>& channel) const
+    {
+        MultiChannelMap::val_type vres;
+
+        if(channel.numElements() != m_numElements)
+        {
+            vres = channel;
+            return vres;
+        }
+
+        Channel<T> ret(m_indices.size(), channel.width());
+        for(size_t i = 0; i < m_indices.size(); i++)
+        {
+            for(size_t j = 0; j < channel.width(); j++)
+            {
+                ret[i][j] = channel[m_indices[i]][j];
+            }
+        }
+        vres = ret;
+        return vres;
+    }
+
+private:
+    const std::vector<size_t>& m_indices;
+    const size_t m_numElements;
+};
+
 class RandomSample : public boost::static_visitor< MultiChannelMap::val_type > 
 {
 public:
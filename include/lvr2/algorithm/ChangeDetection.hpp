@@ -0,0 +1,133 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ChangeDetection.hpp
+ *
+ * Detection of geometric changes between two epochs of a scene, e.g. to
+ * monitor construction progress. The current point cloud is compared
+ * either to a reference mesh (signed point to surface distances) or to a
+ * reference point cloud (M3C2 style distances along local normals).
+ */
+
+#ifndef LVR2_ALGORITHM_CHANGEDETECTION_HPP
+#define LVR2_ALGORITHM_CHANGEDETECTION_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+enum class ChangeLabel : unsigned char
+{
+    Unchanged = 0,
+    /// Geometry in front of the reference surface or without reference counterpart
+    Added = 1,
+    /// Geometry behind the reference surface, i.e. the reference was removed
+    Removed = 2
+};
+
+struct M3C2Options
+{
+    /// Radius of the reference neighborhood used to estimate the normal of a core point
+    double normalRadius = 0.5;
+
+    /// Radius of the cylinder around the normal in which points are averaged
+    double projectionRadius = 0.25;
+
+    /// Maximum distance along the normal in both directions
+    double maxDepth = 2.0;
+};
+
+struct ChangeDetectionResult
+{
+    /// Signed distance of each point of the current cloud to the reference.
+    /// NaN if no reference geometry was found within the search range.
+    std::vector<float> distances;
+
+    /// Label of each point of the current cloud
+    std::vector<ChangeLabel> labels;
+
+    /// Indices of reference points without counterpart in the current
+    /// cloud (cloud to cloud comparison only)
+    std::vector<size_t> removedReference;
+
+    size_t numAdded = 0;
+    size_t numRemoved = 0;
+    size_t numUnchanged = 0;
+};
+
+/**
+ * @brief Computes the signed distance of every point of the current cloud
+ *        to the closest triangle of the reference mesh. The sign is taken
+ *        from the face normal, so points in front of the surface are
+ *        labeled as added and points behind it as removed if their distance
+ *        exceeds the threshold.
+ *
+ * @param reference The reference mesh with consistently oriented faces
+ * @param current   The current point cloud
+ * @param threshold Minimum absolute distance of a change, e.g. the
+ *                  combined registration and measurement uncertainty
+ */
+ChangeDetectionResult changeDetection(MeshBufferPtr reference, PointBufferPtr current, double threshold);
+
+/**
+ * @brief Cloud to cloud change detection similar to M3C2. For each point
+ *        of the current cloud, the normal is estimated from the reference
+ *        neighborhood and the mean positions of both clouds within a
+ *        cylinder along the normal are compared. Points without reference
+ *        points in their cylinder are labeled as added. Normals are oriented
+ *        upwards, so positive distances mean the current surface is above
+ *        (or, for vertical surfaces, in front of) the reference. Reference
+ *        points without current points within the projection radius are
+ *        reported in ChangeDetectionResult::removedReference.
+ *
+ * @param reference The reference point cloud
+ * @param current   The current point cloud
+ * @param threshold Minimum absolute distance of a change
+ * @param options   Neighborhood parameters
+ */
+ChangeDetectionResult changeDetection(
+    PointBufferPtr reference,
+    PointBufferPtr current,
+    double threshold,
+    const M3C2Options& options = M3C2Options()
+);
+
+/**
+ * @brief Stores the distances ("change_distance") and labels ("change_label")
+ *        of the result as channels of the current cloud.
+ */
+void addChangeChannels(PointBufferPtr current, const ChangeDetectionResult& result);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_CHANGEDETECTION_HPP
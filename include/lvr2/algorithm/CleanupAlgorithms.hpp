@@ -33,6 +33,7 @@
 #define LVR2_ALGORITHM_CLEANUPALGORITHMS_H_
 
 #include "lvr2/geometry/BaseMesh.hpp"
+#include "lvr2/reconstruction/PointsetSurface.hpp"
 
 namespace lvr2
 {
@@ -66,6 +67,24 @@ void cleanContours(BaseMesh<BaseVecT>& mesh, int iterations, float areaThreshold
 template<typename BaseVecT>
 size_t naiveFillSmallHoles(BaseMesh<BaseVecT>& mesh, size_t maxSize, bool collapseOnly);
 
+/**
+ * @brief Removes faces in regions of low point support.
+ *
+ * Marching cubes closes the surface over sparse areas, which creates
+ * "bubbles" that are not backed by any data. For each face, the points of
+ * `surface` within `radius` of the face centroid are counted. Faces with
+ * fewer than `minPoints` points are removed.
+ *
+ * @return The number of removed faces.
+ */
+template<typename BaseVecT>
+size_t trimLowDensityFaces(
+    BaseMesh<BaseVecT>& mesh,
+    const PointsetSurface<BaseVecT>& surface,
+    float radius,
+    size_t minPoints
+);
+
 } // namespace lvr2
 
 #include "lvr2/algorithm/CleanupAlgorithms.tcc"
@@ -33,6 +33,9 @@
 #include "lvr2/algorithm/ContourAlgorithms.hpp"
 #include "lvr2/attrmaps/AttrMaps.hpp"
 #include "lvr2/util/Timestamp.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <vector>
 
 namespace lvr2
 {
@@ -280,4 +283,72 @@ size_t naiveFillSmallHoles(BaseMesh<BaseVecT>& mesh, size_t maxSize, bool collap
 }
 
 
+template<typename BaseVecT>
+size_t trimLowDensityFaces(
+    BaseMesh<BaseVecT>& mesh,
+    const PointsetSurface<BaseVecT>& surface,
+    float radius,
+    size_t minPoints
+)
+{
+    if (minPoints == 0)
+    {
+        return 0;
+    }
+
+    auto tree = surface.searchTree();
+    FloatChannel points = *surface.pointBuffer()->getFloatChannel("points");
+    const float sqRadius = radius * radius;
+
+    std::vector<FaceHandle> faces;
+    faces.reserve(mesh.numFaces());
+    for (const auto fH: mesh.faces())
+    {
+        faces.push_back(fH);
+    }
+
+    // The face is supported if its minPoints nearest neighbors lie within
+    // the radius. The distances are computed here, as the search trees
+    // differ in whether they return squared or sorted distances.
+    std::vector<char> remove(faces.size(), 0);
+    #pragma omp parallel for schedule(dynamic)
+    for (size_t i = 0; i < faces.size(); i++)
+    {
+        thread_local std::vector<size_t> neighbors;
+        const BaseVecT centroid = mesh.calcFaceCentroid(faces[i]);
+        tree->kSearch(centroid, minPoints, neighbors);
+
+        if (neighbors.size() < minPoints)
+        {
+            remove[i] = 1;
+            continue;
+        }
+
+        for (size_t index: neighbors)
+        {
+            const BaseVecT p = points[index];
+            if ((p - centroid).length2() > sqRadius)
+            {
+                remove[i] = 1;
+                break;
+            }
+        }
+    }
+
+    size_t removed = 0;
+    for (size_t i = 0; i < faces.size(); i++)
+    {
+        if (remove[i])
+        {
+            mesh.removeFace(faces[i]);
+            removed++;
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[TrimLowDensityFaces] Removed " << removed
+                        << " of " << faces.size() << " faces" << lvr2::endl;
+
+    return removed;
+}
+
 } // namespace lvr2
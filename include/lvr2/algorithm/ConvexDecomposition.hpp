@@ -0,0 +1,99 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * ConvexDecomposition.hpp
+ *
+ * Convex hulls and approximate convex decomposition of meshes into a set
+ * of convex parts, e.g. as collision proxies for physics engines and
+ * robot motion planning.
+ */
+
+#ifndef LVR2_ALGORITHM_CONVEXDECOMPOSITION_HPP
+#define LVR2_ALGORITHM_CONVEXDECOMPOSITION_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Computes the convex hull of the given points.
+ *
+ * @return A closed triangle mesh with outward facing faces that contains
+ *         only the hull vertices, or nullptr if the points are coplanar
+ *         or fewer than four
+ */
+MeshBufferPtr convexHull(const std::vector<Vector3f>& points);
+
+struct ConvexDecompositionOptions
+{
+    /// Approximate number of voxels in the bounding box of the mesh
+    size_t resolution = 100000;
+
+    /// Maximum number of convex parts
+    size_t maxHulls = 32;
+
+    /// Parts whose hull exceeds their voxel volume by less than this
+    /// fraction of the hull volume of the whole mesh are not split further
+    float maxConcavity = 0.01f;
+
+    /// Maximum number of recursive splits of a part
+    int maxDepth = 10;
+
+    /// Number of candidate split planes per axis
+    int planesPerAxis = 8;
+};
+
+/**
+ * @brief Decomposes the mesh into approximately convex parts (similar to
+ *        V-HACD) and returns the convex hull of each part.
+ *
+ *        The mesh is voxelized and the interior of closed components is
+ *        filled. The part with the largest concavity is then repeatedly
+ *        split along the axis aligned plane that minimizes the concavity
+ *        of both halves. The hulls enclose the voxels of their part, so they
+ *        may exceed the mesh by up to one voxel.
+ *
+ * @param mesh      A triangle mesh
+ * @param options   Decomposition parameters
+ *
+ * @return The convex hulls, see convexHull()
+ */
+std::vector<MeshBufferPtr> convexDecomposition(
+    MeshBufferPtr mesh,
+    const ConvexDecompositionOptions& options = ConvexDecompositionOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_CONVEXDECOMPOSITION_HPP
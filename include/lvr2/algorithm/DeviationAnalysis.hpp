@@ -0,0 +1,160 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * DeviationAnalysis.hpp
+ *
+ * Inspection of a scan or reconstructed mesh against a reference (e.g. CAD)
+ * mesh: signed deviations, a color mapping with tolerance bands and a
+ * statistics report with the percentage of the surface within specification.
+ */
+
+#ifndef LVR2_ALGORITHM_DEVIATIONANALYSIS_HPP
+#define LVR2_ALGORITHM_DEVIATIONANALYSIS_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct DeviationAnalysisOptions
+{
+    /// Lower bound of the specification, i.e. the largest allowed deviation
+    /// below the reference surface (negative)
+    float lowerTolerance = -0.005f;
+
+    /// Upper bound of the specification, i.e. the largest allowed deviation
+    /// above the reference surface
+    float upperTolerance = 0.005f;
+
+    /// Ascending deviations that separate the bands of the report. Defaults
+    /// to 1, 2 and 3 times the tolerances if empty.
+    std::vector<float> bandEdges;
+
+    /// Points that are farther away from the reference are not part of the
+    /// inspected object and are ignored. Disabled if <= 0.
+    float maxDistance = 0.0f;
+};
+
+/**
+ * @brief Range of deviations and the number of points within it
+ */
+struct DeviationBand
+{
+    /// Lower bound (inclusive), -infinity for the first band
+    float min;
+
+    /// Upper bound (exclusive), infinity for the last band
+    float max;
+
+    size_t count = 0;
+
+    /// Percentage of the valid points
+    double percent = 0;
+};
+
+struct DeviationReport
+{
+    /// Signed deviation of each point or vertex, positive above the reference
+    /// surface. NaN if the point was ignored, see DeviationAnalysisOptions::maxDistance.
+    std::vector<float> deviations;
+
+    /// Number of points that were not ignored
+    size_t numValid = 0;
+
+    size_t numInSpec = 0;
+    size_t numAbove = 0;
+    size_t numBelow = 0;
+
+    /// Percentage of the valid points within the tolerances
+    double percentInSpec = 0;
+
+    double mean = 0;
+    double stdDev = 0;
+    double rms = 0;
+    double minDeviation = 0;
+    double maxDeviation = 0;
+
+    std::vector<DeviationBand> bands;
+};
+
+/**
+ * @brief Computes the signed deviations of the points of a scan to the
+ *        reference mesh, see changeDetection(), and their statistics.
+ *
+ * @param reference The reference mesh with consistently oriented faces
+ * @param scan      The inspected point cloud, aligned to the reference (see localizeObject())
+ * @param options   Tolerances and bands
+ */
+DeviationReport deviationAnalysis(
+    MeshBufferPtr reference,
+    PointBufferPtr scan,
+    const DeviationAnalysisOptions& options = DeviationAnalysisOptions()
+);
+
+/**
+ * @brief Computes the signed deviations of the vertices of a mesh to the
+ *        reference mesh and their statistics.
+ */
+DeviationReport deviationAnalysis(
+    MeshBufferPtr reference,
+    MeshBufferPtr mesh,
+    const DeviationAnalysisOptions& options = DeviationAnalysisOptions()
+);
+
+/**
+ * @brief Stores the deviations in the float channel "deviation" and replaces
+ *        the colors of the points with a diverging color map: green within
+ *        the tolerances, blending to blue below and red above until three
+ *        times the tolerance. Ignored points are gray.
+ */
+void colorByDeviation(PointBufferPtr scan, const DeviationReport& report, const DeviationAnalysisOptions& options);
+
+/**
+ * @brief Stores the deviations in the vertex channel "deviation" and replaces
+ *        the vertex colors, see above
+ */
+void colorByDeviation(MeshBufferPtr mesh, const DeviationReport& report, const DeviationAnalysisOptions& options);
+
+/**
+ * @brief Writes the statistics and bands of the report as JSON
+ */
+void saveDeviationReport(
+    const DeviationReport& report,
+    const DeviationAnalysisOptions& options,
+    const boost::filesystem::path& file
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_DEVIATIONANALYSIS_HPP
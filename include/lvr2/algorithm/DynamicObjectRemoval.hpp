@@ -0,0 +1,108 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * DynamicObjectRemoval.hpp
+ *
+ * Removal of moving objects such as pedestrians and vehicles from a set of
+ * registered scans. An object that was only present during some of the
+ * scans occupies space that the other scans observed as empty. Without
+ * removal, such objects appear as blobs in the reconstructed mesh.
+ */
+
+#ifndef LVR2_ALGORITHM_DYNAMICOBJECTREMOVAL_HPP
+#define LVR2_ALGORITHM_DYNAMICOBJECTREMOVAL_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct DynamicRemovalOptions
+{
+    /// Edge length of the voxels used for occupancy voting
+    float voxelSize = 0.1f;
+
+    /// Rays are traced up to this distance before their measured point
+    float margin = 0.2f;
+
+    /// Minimum number of other scans that observed the voxel of a point as free
+    int minFreeScans = 1;
+
+    /// Minimum fraction of free votes among the votes of the other scans
+    /// (free and occupied) for a point to be dynamic
+    float minFreeRatio = 0.5f;
+
+    /// Points of a scan within this many voxels of one of its dynamic voxels
+    /// are removed as well, unless their voxel was confirmed as occupied by
+    /// another scan. Captures the parts of an object that were occluded in
+    /// all other scans. Disabled if 0.
+    int dilation = 1;
+};
+
+/**
+ * @brief Detects points of moving objects by occupancy voting.
+ *
+ *        The rays of all scans are traced through a voxel grid, see
+ *        VisibilityGrid. Each other scan votes for the voxel of a point
+ *        to be free (a ray passed through it) or occupied (it measured a
+ *        point in it). Points whose voxel received enough free votes are
+ *        dynamic. Only the first 64 scans vote.
+ *
+ * @param scans     Registered scans in a common coordinate system
+ * @param origins   Scanner position of each scan in the same coordinate system
+ * @param options   Voting parameters
+ *
+ * @return The indices of the dynamic points of each scan
+ *
+ * @throws std::invalid_argument if the number of scans and origins differ
+ */
+std::vector<std::vector<size_t>> detectDynamicPoints(
+    const std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const DynamicRemovalOptions& options = DynamicRemovalOptions()
+);
+
+/**
+ * @brief Removes the dynamic points of all scans (and the associated entries
+ *        of all per point channels), see detectDynamicPoints().
+ *
+ * @return The total number of removed points
+ */
+size_t removeDynamicPoints(
+    std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const DynamicRemovalOptions& options = DynamicRemovalOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_DYNAMICOBJECTREMOVAL_HPP
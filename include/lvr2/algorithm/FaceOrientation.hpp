@@ -0,0 +1,62 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * FaceOrientation.hpp
+ *
+ * Repairs inconsistent face winding in triangle meshes.
+ */
+
+#ifndef LVR2_ALGORITHM_FACEORIENTATION_HPP
+#define LVR2_ALGORITHM_FACEORIENTATION_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+namespace lvr2
+{
+
+/**
+ * @brief Makes the face winding consistent within each connected component
+ *        of the given mesh.
+ *
+ * The faces of every component are traversed in breadth first order over
+ * shared edges. A neighboring face is flipped if it traverses the shared
+ * edge in the same direction as the current face. Afterwards, closed
+ * components (every edge is shared by exactly two faces) are oriented so
+ * that their normals point outwards, i.e. their signed volume is positive.
+ *
+ * Face normals are negated for all flipped faces. Vertex normals are not
+ * modified.
+ *
+ * @param mesh  The mesh to repair. The face indices are modified in place.
+ * @return      The number of flipped faces
+ */
+size_t fixFaceOrientation(MeshBufferPtr mesh);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_FACEORIENTATION_HPP
@@ -68,6 +68,12 @@ class SimpleFinalizer
 private:
     boost::optional<const VertexMap<RGB8Color>&> m_colorData;
     boost::optional<const VertexMap<Normal<typename BaseVecT::CoordType>>&> m_normalData;
+    boost::optional<const FaceMap<uint32_t>&> m_faceRegions;
+    boost::optional<const VertexMap<float>&> m_vertexDensity;
+    boost::optional<const VertexMap<float>&> m_vertexResidual;
+
+    /// Creates a buffer of the given faces and the vertices they reference
+    MeshBufferPtr applyFaces(const BaseMesh<BaseVecT>& mesh, const std::vector<FaceHandle>& faces);
 
 public:
     SimpleFinalizer() {};
@@ -77,9 +83,22 @@ public:
      *
      * @param mesh the mesh to convert
      * @return the generated buffer
+     * @throws IndexOverflowError if the mesh has more vertices than a MeshBuffer can address
      */
     MeshBufferPtr apply(const BaseMesh<BaseVecT>& mesh);
 
+    /**
+     * Converts the given BaseMesh into one or more MeshBuffers with at most maxVertices
+     * vertices each. Meshes that fit are converted with apply. Larger meshes are split
+     * into spatially coherent parts by sorting the faces along a Morton curve. Vertices
+     * at the borders of the parts are duplicated.
+     *
+     * @param mesh the mesh to convert
+     * @param maxVertices the maximum number of vertices per buffer
+     * @return the generated buffers
+     */
+    std::vector<MeshBufferPtr> applySplit(const BaseMesh<BaseVecT>& mesh, size_t maxVertices = MaxMeshBufferVertices);
+
     /**
      * Sets vertex colors for the apply method. This has to be done before apply is called.
      *
@@ -93,6 +112,24 @@ public:
      * @param normalData normals for all vertices in the mesh which will be passed to apply
      */
     void setNormalData(const VertexMap<Normal<typename BaseVecT::CoordType>>& normalData);
+
+    /**
+     * Sets a region label per face, e.g. from ReconstructionResult::faceRegions. The labels are
+     * stored in the "face_regions" channel of the buffer. This has to be done before apply is called.
+     *
+     * @param regions region labels for all faces in the mesh which will be passed to apply
+     */
+    void setFaceRegions(const FaceMap<uint32_t>& regions);
+
+    /**
+     * Sets a quality measure per vertex, e.g. from ReconstructionResult::vertexDensity and
+     * ReconstructionResult::vertexResidual. The values are stored in the "vertex_density" and
+     * "vertex_residual" channels of the buffer. This has to be done before apply is called.
+     *
+     * @param density local point density for all vertices in the mesh
+     * @param residual local residual for all vertices in the mesh
+     */
+    void setVertexQuality(const VertexMap<float>& density, const VertexMap<float>& residual);
 };
 
 /**
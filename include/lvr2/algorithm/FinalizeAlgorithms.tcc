@@ -32,6 +32,8 @@
  *  @author Johan M. von Behren <johan@vonbehren.eu>
  */
 
+#include <algorithm>
+#include <limits>
 #include <vector>
 #include <utility>
 #include <cmath>
@@ -41,6 +43,7 @@
 #include "lvr2/util/Progress.hpp"
 #include "lvr2/util/Util.hpp"
 #include "lvr2/util/Logging.hpp"
+#include "lvr2/util/MortonOrder.hpp"
 
 namespace lvr2
 {
@@ -48,6 +51,11 @@ namespace lvr2
 template<typename BaseVecT>
 MeshBufferPtr SimpleFinalizer<BaseVecT>::apply(const BaseMesh <BaseVecT>& mesh)
 {
+    if (mesh.numVertices() > MaxMeshBufferVertices)
+    {
+        throw IndexOverflowError(mesh.numVertices());
+    }
+
     // Create vertex and normal buffer
     DenseVertexMap<size_t> idxMap;
     idxMap.reserve(mesh.numVertices());
@@ -67,6 +75,14 @@ MeshBufferPtr SimpleFinalizer<BaseVecT>::apply(const BaseMesh <BaseVecT>& mesh)
         colors.reserve(mesh.numVertices() * 3);
     }
 
+    vector<float> density;
+    vector<float> residual;
+    if (m_vertexDensity)
+    {
+        density.reserve(mesh.numVertices());
+        residual.reserve(mesh.numVertices());
+    }
+
     // for all vertices
     size_t vertexCount = 0;
     for (auto vH : mesh.vertices())
@@ -95,6 +111,12 @@ MeshBufferPtr SimpleFinalizer<BaseVecT>::apply(const BaseMesh <BaseVecT>& mesh)
             colors.push_back(static_cast<unsigned char>((*m_colorData)[vH][2]));
         }
 
+        if (m_vertexDensity)
+        {
+            density.push_back((*m_vertexDensity)[vH]);
+            residual.push_back((*m_vertexResidual)[vH]);
+        }
+
         // Save index of vertex for face mapping
         idxMap.insert(vH, vertexCount);
         vertexCount++;
@@ -103,6 +125,7 @@ MeshBufferPtr SimpleFinalizer<BaseVecT>::apply(const BaseMesh <BaseVecT>& mesh)
     // Create face buffer
     vector<unsigned int> faces;
     faces.reserve(mesh.numFaces() * 3);
+    vector<unsigned int> regions;
     for (auto fH : mesh.faces())
     {
         auto handles = mesh.getVerticesOfFace(fH);
@@ -111,6 +134,11 @@ MeshBufferPtr SimpleFinalizer<BaseVecT>::apply(const BaseMesh <BaseVecT>& mesh)
             // add faces to buffer
             faces.push_back(idxMap[handle]);
         }
+
+        if (m_faceRegions)
+        {
+            regions.push_back((*m_faceRegions)[fH]);
+        }
     }
 
     // create buffer object and pass values
@@ -129,6 +157,175 @@ MeshBufferPtr SimpleFinalizer<BaseVecT>::apply(const BaseMesh <BaseVecT>& mesh)
         buffer->setVertexColors(Util::convert_vector_to_shared_array(colors));
     }
 
+    if (m_faceRegions)
+    {
+        buffer->addIndexChannel(Util::convert_vector_to_shared_array(regions), "face_regions", regions.size(), 1);
+    }
+
+    if (m_vertexDensity)
+    {
+        buffer->addFloatChannel(Util::convert_vector_to_shared_array(density), "vertex_density", density.size(), 1);
+        buffer->addFloatChannel(Util::convert_vector_to_shared_array(residual), "vertex_residual", residual.size(), 1);
+    }
+
+    return buffer;
+}
+
+template<typename BaseVecT>
+std::vector<MeshBufferPtr> SimpleFinalizer<BaseVecT>::applySplit(const BaseMesh<BaseVecT>& mesh, size_t maxVertices)
+{
+    maxVertices = std::max<size_t>(3, std::min(maxVertices, MaxMeshBufferVertices));
+    if (mesh.numVertices() <= maxVertices)
+    {
+        return { apply(mesh) };
+    }
+
+    // Sort the faces along a Morton curve, so that each part covers a
+    // compact region and shares few vertices with the others
+    BaseVecT min(std::numeric_limits<float>::max(), std::numeric_limits<float>::max(), std::numeric_limits<float>::max());
+    BaseVecT max(std::numeric_limits<float>::lowest(), std::numeric_limits<float>::lowest(), std::numeric_limits<float>::lowest());
+    for (auto vH : mesh.vertices())
+    {
+        auto p = mesh.getVertexPosition(vH);
+        min = BaseVecT(std::min(min.x, p.x), std::min(min.y, p.y), std::min(min.z, p.z));
+        max = BaseVecT(std::max(max.x, p.x), std::max(max.y, p.y), std::max(max.z, p.z));
+    }
+    const auto extent = std::max({max.x - min.x, max.y - min.y, max.z - min.z});
+    const double scale = extent > 0 ? ((1 << 21) - 1) / extent : 0;
+
+    std::vector<std::pair<uint64_t, FaceHandle>> sorted;
+    sorted.reserve(mesh.numFaces());
+    for (auto fH : mesh.faces())
+    {
+        auto c = mesh.calcFaceCentroid(fH) - min;
+        sorted.emplace_back(mortonCode(
+            static_cast<uint32_t>(c.x * scale),
+            static_cast<uint32_t>(c.y * scale),
+            static_cast<uint32_t>(c.z * scale)), fH);
+    }
+    std::sort(sorted.begin(), sorted.end(), [](const auto& a, const auto& b)
+    {
+        return a.first < b.first;
+    });
+
+    std::vector<MeshBufferPtr> buffers;
+    std::vector<FaceHandle> part;
+    SparseVertexMap<bool> partVertices;
+    for (const auto& entry : sorted)
+    {
+        auto handles = mesh.getVerticesOfFace(entry.second);
+        size_t newVertices = 0;
+        for (auto vH : handles)
+        {
+            newVertices += partVertices.containsKey(vH) ? 0 : 1;
+        }
+
+        if (partVertices.numValues() + newVertices > maxVertices)
+        {
+            buffers.push_back(applyFaces(mesh, part));
+            part.clear();
+            partVertices.clear();
+        }
+
+        part.push_back(entry.second);
+        for (auto vH : handles)
+        {
+            partVertices.insert(vH, true);
+        }
+    }
+    if (!part.empty())
+    {
+        buffers.push_back(applyFaces(mesh, part));
+    }
+
+    lvr2::logout::get() << lvr2::info << "[SimpleFinalizer] Split mesh with " << mesh.numVertices()
+        << " vertices into " << buffers.size() << " buffers" << lvr2::endl;
+
+    return buffers;
+}
+
+template<typename BaseVecT>
+MeshBufferPtr SimpleFinalizer<BaseVecT>::applyFaces(const BaseMesh<BaseVecT>& mesh, const std::vector<FaceHandle>& faceHandles)
+{
+    SparseVertexMap<unsigned int> idxMap;
+    vector<float> vertices;
+    vector<float> normals;
+    vector<unsigned char> colors;
+    vector<float> density;
+    vector<float> residual;
+    vector<unsigned int> faces;
+    vector<unsigned int> regions;
+    faces.reserve(faceHandles.size() * 3);
+
+    for (auto fH : faceHandles)
+    {
+        for (auto vH : mesh.getVerticesOfFace(fH))
+        {
+            if (!idxMap.containsKey(vH))
+            {
+                idxMap.insert(vH, vertices.size() / 3);
+
+                auto point = mesh.getVertexPosition(vH);
+                vertices.push_back(point.x);
+                vertices.push_back(point.y);
+                vertices.push_back(point.z);
+
+                if (m_normalData)
+                {
+                    auto normal = (*m_normalData)[vH];
+                    normals.push_back(normal.getX());
+                    normals.push_back(normal.getY());
+                    normals.push_back(normal.getZ());
+                }
+
+                if (m_colorData)
+                {
+                    for (int i = 0; i < 3; i++)
+                    {
+                        colors.push_back(static_cast<unsigned char>((*m_colorData)[vH][i]));
+                    }
+                }
+
+                if (m_vertexDensity)
+                {
+                    density.push_back((*m_vertexDensity)[vH]);
+                    residual.push_back((*m_vertexResidual)[vH]);
+                }
+            }
+            faces.push_back(idxMap[vH]);
+        }
+
+        if (m_faceRegions)
+        {
+            regions.push_back((*m_faceRegions)[fH]);
+        }
+    }
+
+    MeshBufferPtr buffer( new MeshBuffer );
+    buffer->setVertices(Util::convert_vector_to_shared_array(vertices), vertices.size() / 3);
+    buffer->setFaceIndices(Util::convert_vector_to_shared_array(faces), faces.size() / 3);
+
+    if (m_normalData)
+    {
+        buffer->setVertexNormals(Util::convert_vector_to_shared_array(normals));
+    }
+
+    if (m_colorData)
+    {
+        buffer->setVertexColors(Util::convert_vector_to_shared_array(colors));
+    }
+
+    if (m_faceRegions)
+    {
+        buffer->addIndexChannel(Util::convert_vector_to_shared_array(regions), "face_regions", regions.size(), 1);
+    }
+
+    if (m_vertexDensity)
+    {
+        buffer->addFloatChannel(Util::convert_vector_to_shared_array(density), "vertex_density", density.size(), 1);
+        buffer->addFloatChannel(Util::convert_vector_to_shared_array(residual), "vertex_residual", residual.size(), 1);
+    }
+
     return buffer;
 }
 
@@ -144,6 +341,19 @@ void SimpleFinalizer<BaseVecT>::setNormalData(const VertexMap<Normal<typename Ba
     m_normalData = normalData;
 }
 
+template<typename BaseVecT>
+void SimpleFinalizer<BaseVecT>::setFaceRegions(const FaceMap<uint32_t>& regions)
+{
+    m_faceRegions = regions;
+}
+
+template<typename BaseVecT>
+void SimpleFinalizer<BaseVecT>::setVertexQuality(const VertexMap<float>& density, const VertexMap<float>& residual)
+{
+    m_vertexDensity = density;
+    m_vertexResidual = residual;
+}
+
 template<typename BaseVecT>
 TextureFinalizer<BaseVecT>::TextureFinalizer(
     const ClusterBiMap<FaceHandle>& cluster
@@ -0,0 +1,97 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * GroundDetection.hpp
+ *
+ * RANSAC based detection of the dominant ground plane of a point cloud.
+ */
+
+#ifndef LVR2_ALGORITHM_GROUNDDETECTION_HPP
+#define LVR2_ALGORITHM_GROUNDDETECTION_HPP
+
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/Plane.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct GroundDetectionOptions
+{
+    /// Maximum distance of a ground point to the plane
+    float distanceThreshold = 0.05;
+
+    /// Number of RANSAC iterations
+    int iterations = 500;
+
+    /// Maximum angle between the plane normal and the up axis in degrees
+    float maxTilt = 20;
+
+    /// The approximate up direction of the cloud
+    Vector3d up = Vector3d::UnitZ();
+
+    /// Seed of the random number generator
+    unsigned int seed = 0;
+};
+
+struct GroundDetectionResult
+{
+    /// True, if a plane with at least three inliers was found
+    bool found = false;
+
+    /// The ground plane. Its normal points upwards.
+    Plane<BaseVector<float>> plane;
+
+    /// Indices of the points within the distance threshold of the plane
+    std::vector<size_t> ground;
+
+    /// Indices of all other points
+    std::vector<size_t> nonGround;
+
+    /// Rigid transformation that rotates the plane normal onto the z axis
+    /// and moves the plane to z = 0
+    Transformd leveling = Transformd::Identity();
+};
+
+/**
+ * @brief Detects the ground plane with RANSAC. Only planes whose normal is
+ *        within options.maxTilt of the up axis are considered, the one
+ *        with most inliers is refined with a least squares fit.
+ *
+ * @param points    The point cloud
+ * @param options   Detection parameters
+ */
+GroundDetectionResult detectGround(PointBufferPtr points, const GroundDetectionOptions& options = GroundDetectionOptions());
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_GROUNDDETECTION_HPP
@@ -0,0 +1,93 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * HeightField.hpp
+ *
+ * Rasterization of 2.5D terrain meshes into regular height grids.
+ */
+
+#ifndef LVR2_ALGORITHM_HEIGHTFIELD_HPP
+#define LVR2_ALGORITHM_HEIGHTFIELD_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief A regular grid of heights. Row 0 is the northern (max y) row as
+ *        in common raster formats, heights are stored row by row.
+ */
+struct HeightGrid
+{
+    size_t cols = 0;
+    size_t rows = 0;
+
+    /// x coordinate of the left edge of the grid
+    double originX = 0;
+
+    /// y coordinate of the lower edge of the grid
+    double originY = 0;
+
+    /// Edge length of a cell
+    double cellSize = 1;
+
+    /// Value of cells that are not covered by the mesh
+    float noData = -9999.0f;
+
+    std::vector<float> heights;
+
+    float& at(size_t row, size_t col) { return heights[row * cols + col]; }
+    float at(size_t row, size_t col) const { return heights[row * cols + col]; }
+};
+
+/**
+ * @brief Samples the mesh at the cell centers of a regular grid in the
+ *        xy plane. If several faces cover a cell center, the highest
+ *        value is used.
+ *
+ * @param mesh      A mesh buffer with vertices and face indices
+ * @param cellSize  Edge length of a grid cell
+ * @param noData    Value of cells without data
+ */
+HeightGrid rasterizeHeightField(MeshBufferPtr mesh, double cellSize, float noData = -9999.0f);
+
+/**
+ * @brief Samples the mesh at the cell centers of a given grid layout, e.g.
+ *        to rasterize several meshes into identical grids. The size,
+ *        origin, cell size and no data value are taken from the layout,
+ *        its heights are ignored.
+ */
+HeightGrid rasterizeHeightField(MeshBufferPtr mesh, const HeightGrid& layout);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_HEIGHTFIELD_HPP
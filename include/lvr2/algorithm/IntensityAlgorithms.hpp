@@ -0,0 +1,90 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IntensityAlgorithms.hpp
+ *
+ * Filtering and normalization of the "intensities" channel of point
+ * clouds. Laser intensities depend on the sensor, range and incidence
+ * angle, so scans of the same scene often differ considerably.
+ */
+
+#ifndef LVR2_ALGORITHM_INTENSITYALGORITHMS_HPP
+#define LVR2_ALGORITHM_INTENSITYALGORITHMS_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Returns the points whose intensity lies in [minIntensity, maxIntensity].
+ *        All channels are copied. If the buffer has no "intensities" channel,
+ *        a warning is printed and the input buffer is returned.
+ */
+PointBufferPtr filterByIntensity(PointBufferPtr buffer, float minIntensity, float maxIntensity);
+
+/**
+ * @brief Linearly maps the intensities between the given percentiles to
+ *        [0, 1] and clamps all values outside. Clipping the tails removes
+ *        the influence of specular highlights and dark outliers on texture
+ *        colors. The result is stored back into the "intensities" channel.
+ *
+ * @param buffer            The point cloud to modify
+ * @param lowPercentile     Lower percentile in [0, 1] that is mapped to 0
+ * @param highPercentile    Upper percentile in [0, 1] that is mapped to 1
+ *
+ * @return false, if the buffer has no intensities
+ */
+bool rescaleIntensity(PointBufferPtr buffer, float lowPercentile = 0.01, float highPercentile = 0.99);
+
+/**
+ * @brief Transforms the intensities of source so that their histogram
+ *        matches the histogram of reference. The result is stored back into
+ *        the "intensities" channel of source.
+ *
+ * @param source    The point cloud to modify
+ * @param reference The point cloud with the desired intensity distribution
+ * @param bins      Number of histogram bins
+ *
+ * @return false, if one of the buffers has no intensities
+ */
+bool matchIntensityHistogram(PointBufferPtr source, PointBufferPtr reference, size_t bins = 256);
+
+/**
+ * @brief Matches the intensity histogram of every scan to the combined
+ *        histogram of all scans, so that the same surface has a similar
+ *        intensity in each scan. Scans without intensities are skipped.
+ */
+void normalizeIntensities(std::vector<PointBufferPtr>& scans, size_t bins = 256);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_INTENSITYALGORITHMS_HPP
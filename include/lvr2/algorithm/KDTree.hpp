@@ -252,6 +252,13 @@ public:
         std::vector<size_t>& indices
     ) const override;
 
+    int kSearchPoints(
+        const BaseVecT& qp,
+        int k,
+        std::vector<size_t>& indices,
+        std::vector<BaseVecT>& neighbors
+    ) const override;
+
     int radiusSearch(
         const BaseVecT& qp,
         int k,
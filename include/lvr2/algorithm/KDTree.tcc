@@ -106,7 +106,9 @@ size_t KDTree<PointT, N>::knnSearch(const InPointT& inPoint,
 {
     QueryPoint point = toQueryPoint(inPoint);
     double worstDistSq = maxDistance * maxDistance;
-    Queue queue;
+    // The queue is always drained below, so its storage can be reused
+    // by subsequent queries of the same thread
+    thread_local Queue queue;
     m_tree->knnInternal(point, k, queue, worstDistSq);
 
     neighbors.resize(queue.size());
@@ -134,7 +136,9 @@ size_t KDTree<PointT, N>::knnSearch(const InPointT& inPoint,
 {
     QueryPoint point = toQueryPoint(inPoint);
     double worstDistSq = maxDistance * maxDistance;
-    Queue queue;
+    // The queue is always drained below, so its storage can be reused
+    // by subsequent queries of the same thread
+    thread_local Queue queue;
     m_tree->knnInternal(point, k, queue, worstDistSq);
 
     neighbors.resize(queue.size());
@@ -336,7 +340,7 @@ int SearchKDTree<BaseVecT>::kSearch(
     std::vector<CoordT>& distances
 ) const
 {
-    std::vector<PointT*> neighbors;
+    thread_local std::vector<PointT*> neighbors;
     size_t n = this->knnSearch(qp, k, neighbors, distances);
 
     indices.resize(n);
@@ -354,7 +358,7 @@ int SearchKDTree<BaseVecT>::kSearch(
     std::vector<size_t>& indices
 ) const
 {
-    std::vector<PointT*> neighbors;
+    thread_local std::vector<PointT*> neighbors;
     size_t n = this->knnSearch(qp, k, neighbors);
 
     indices.resize(n);
@@ -365,6 +369,27 @@ int SearchKDTree<BaseVecT>::kSearch(
     return n;
 }
 
+template<typename BaseVecT>
+int SearchKDTree<BaseVecT>::kSearchPoints(
+    const BaseVecT& qp,
+    int k,
+    std::vector<size_t>& indices,
+    std::vector<BaseVecT>& neighbors
+) const
+{
+    thread_local std::vector<PointT*> found;
+    size_t n = this->knnSearch(qp, k, found);
+
+    indices.resize(n);
+    neighbors.resize(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        indices[i] = found[i]->index;
+        neighbors[i] = found[i]->point;
+    }
+    return n;
+}
+
 template<typename BaseVecT>
 int SearchKDTree<BaseVecT>::radiusSearch(
     const BaseVecT& qp,
@@ -374,7 +399,7 @@ int SearchKDTree<BaseVecT>::radiusSearch(
     std::vector<CoordT>& distances
 ) const
 {
-    std::vector<PointT*> neighbors;
+    thread_local std::vector<PointT*> neighbors;
     size_t n = this->knnSearch(qp, k, neighbors, distances, r);
 
     indices.resize(n);
@@ -0,0 +1,78 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * LODChain.hpp
+ *
+ * Generation of a chain of simplified versions of a mesh, e.g. for
+ * streaming large reconstructions into viewers.
+ */
+
+#ifndef LVR2_ALGORITHM_LODCHAIN_HPP
+#define LVR2_ALGORITHM_LODCHAIN_HPP
+
+#include "lvr2/geometry/PMPMesh.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Creates several levels of detail of a mesh in a single
+ *        simplification pass.
+ *
+ * The mesh is simplified successively to the requested ratios of its
+ * vertex count. All levels share the same vertex ordering: the vertices of
+ * a coarser level are a prefix of the vertices of every finer level, so a
+ * viewer can stream the vertex buffer progressively and only has to
+ * exchange the index buffer to switch between levels.
+ *
+ * The shared ordering holds for all placements, but only with
+ * pmp::VertexPlacement::Endpoint the shared vertices also have identical
+ * positions in all levels.
+ *
+ * @param mesh      The full resolution mesh. Not modified.
+ * @param ratios    Fraction of vertices to keep per level, e.g. {1.0, 0.25, 0.05}
+ * @param placement Position of the remaining vertex of each collapse
+ *
+ * @return One mesh per ratio with vertex normals, ordered from the finest
+ *         to the coarsest level
+ */
+template<typename BaseVecT>
+std::vector<MeshBufferPtr> generateLODChain(
+    const PMPMesh<BaseVecT>& mesh,
+    std::vector<float> ratios,
+    pmp::VertexPlacement placement = pmp::VertexPlacement::Endpoint
+);
+
+} // namespace lvr2
+
+#include "lvr2/algorithm/LODChain.tcc"
+
+#endif // LVR2_ALGORITHM_LODCHAIN_HPP
@@ -0,0 +1,164 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * LODChain.tcc
+ */
+
+#include "lvr2/algorithm/pmp/SurfaceNormals.h"
+#include "lvr2/algorithm/pmp/SurfaceSimplification.h"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <functional>
+
+namespace lvr2
+{
+
+template<typename BaseVecT>
+std::vector<MeshBufferPtr> generateLODChain(
+    const PMPMesh<BaseVecT>& mesh,
+    std::vector<float> ratios,
+    pmp::VertexPlacement placement)
+{
+    using pmp::IndexType;
+
+    std::sort(ratios.begin(), ratios.end(), std::greater<float>());
+
+    pmp::SurfaceMesh surface = mesh.getSurfaceMesh();
+    surface.garbage_collection();
+    const size_t numVertices = surface.n_vertices();
+
+    // Track the original index of every vertex through garbage collections
+    auto vid = surface.add_vertex_property<IndexType>("v:lod_id");
+    for (auto v : surface.vertices())
+    {
+        vid[v] = v.idx();
+    }
+
+    struct Level
+    {
+        std::vector<IndexType>  vertexIds;
+        std::vector<pmp::Point> positions;
+        std::vector<pmp::Normal> normals;
+        std::vector<IndexType>  faces;
+    };
+    std::vector<Level> levels(ratios.size());
+
+    // Index of the coarsest level that contains a vertex, -1 if the vertex
+    // was removed before the first level
+    std::vector<long> lastLevel(numVertices, -1);
+
+    {
+        pmp::SurfaceSimplification simplification(surface);
+        simplification.set_placement(placement);
+
+        for (size_t i = 0; i < ratios.size(); i++)
+        {
+            const float ratio = std::min(1.0f, std::max(0.0f, ratios[i]));
+            const size_t target = std::lround(numVertices * ratio);
+            if (target < surface.n_vertices())
+            {
+                simplification.simplify(target);
+            }
+
+            lvr2::logout::get() << lvr2::info << "[LODChain] Level " << i << ": "
+                << surface.n_vertices() << " vertices, " << surface.n_faces() << " faces" << lvr2::endl;
+
+            Level& level = levels[i];
+            auto vpoint = surface.get_vertex_property<pmp::Point>("v:point");
+            for (auto v : surface.vertices())
+            {
+                level.vertexIds.push_back(vid[v]);
+                level.positions.push_back(vpoint[v]);
+                level.normals.push_back(pmp::SurfaceNormals::compute_vertex_normal(surface, v));
+                lastLevel[vid[v]] = i;
+            }
+            for (auto f : surface.faces())
+            {
+                for (auto v : surface.vertices(f))
+                {
+                    level.faces.push_back(vid[v]);
+                }
+            }
+        }
+    }
+
+    // Global vertex order: vertices of coarser levels first
+    std::vector<IndexType> order(numVertices);
+    for (size_t i = 0; i < numVertices; i++)
+    {
+        order[i] = i;
+    }
+    std::stable_sort(order.begin(), order.end(), [&](IndexType a, IndexType b)
+    {
+        return lastLevel[a] > lastLevel[b];
+    });
+    std::vector<IndexType> rank(numVertices);
+    for (size_t i = 0; i < numVertices; i++)
+    {
+        rank[order[i]] = i;
+    }
+
+    std::vector<MeshBufferPtr> result;
+    for (Level& level : levels)
+    {
+        const size_t n = level.vertexIds.size();
+
+        // Sort the vertices of this level by their global rank. Since the
+        // levels are nested, the ranks are dense, i.e. 0 to n - 1.
+        floatArr vertices(new float[n * 3]);
+        floatArr normals(new float[n * 3]);
+        for (size_t i = 0; i < n; i++)
+        {
+            const IndexType r = rank[level.vertexIds[i]];
+            for (int j = 0; j < 3; j++)
+            {
+                vertices[r * 3 + j] = level.positions[i][j];
+                normals[r * 3 + j] = level.normals[i][j];
+            }
+        }
+
+        const size_t numFaces = level.faces.size() / 3;
+        indexArray faces(new unsigned int[level.faces.size()]);
+        for (size_t i = 0; i < level.faces.size(); i++)
+        {
+            faces[i] = rank[level.faces[i]];
+        }
+
+        MeshBufferPtr buffer = std::make_shared<MeshBuffer>();
+        buffer->setVertices(vertices, n);
+        buffer->setVertexNormals(normals);
+        buffer->setFaceIndices(faces, numFaces);
+        result.push_back(buffer);
+    }
+
+    return result;
+}
+
+} // namespace lvr2
@@ -0,0 +1,89 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshBoolean.hpp
+ *
+ * Constructive solid geometry on closed triangle meshes using binary
+ * space partitioning trees.
+ */
+
+#ifndef LVR2_ALGORITHM_MESHBOOLEAN_HPP
+#define LVR2_ALGORITHM_MESHBOOLEAN_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+namespace lvr2
+{
+
+enum class BooleanOperation
+{
+    /// Volume contained in a or b
+    Union,
+
+    /// Volume contained in a and b
+    Intersection,
+
+    /// Volume contained in a but not in b
+    Difference
+};
+
+/**
+ * @brief Computes a boolean operation between two closed, consistently
+ *        oriented (outward facing) meshes.
+ *
+ *        Both meshes are converted into BSP trees that clip each other,
+ *        so the result is exact up to floating point precision but
+ *        triangles are split along the planes of the other mesh. Vertices
+ *        are merged afterwards, the result contains only vertices and
+ *        face indices. For open meshes, the result is undefined.
+ *
+ * @param a     First operand
+ * @param b     Second operand
+ * @param op    The operation
+ */
+MeshBufferPtr meshBoolean(MeshBufferPtr a, MeshBufferPtr b, BooleanOperation op);
+
+/**
+ * @brief Creates an axis aligned box with outward facing triangles, e.g.
+ *        to cut a region out of a mesh with meshBoolean().
+ */
+MeshBufferPtr boxMesh(const Vector3d& min, const Vector3d& max);
+
+/**
+ * @brief Creates a cube of the given size that fills the half space
+ *        behind the plane (opposite to the normal) in the vicinity of
+ *        the given point. Intersecting a mesh smaller than the size with
+ *        it cuts away everything in front of the plane.
+ */
+MeshBufferPtr halfSpaceMesh(const Vector3d& point, const Vector3d& normal, double size);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_MESHBOOLEAN_HPP
@@ -0,0 +1,92 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshCurvature.hpp
+ *
+ * Discrete per-vertex curvature of triangle meshes based on the cotangent
+ * Laplacian and the angle deficit (Meyer et al., "Discrete
+ * Differential-Geometry Operators for Triangulated 2-Manifolds").
+ */
+
+#ifndef LVR2_ALGORITHM_MESHCURVATURE_HPP
+#define LVR2_ALGORITHM_MESHCURVATURE_HPP
+
+#include "lvr2/attrmaps/AttrMaps.hpp"
+#include "lvr2/geometry/BaseMesh.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+namespace lvr2
+{
+
+struct VertexCurvature
+{
+    /// Mean curvature, positive for convex regions (w.r.t. the vertex normal)
+    float mean = 0;
+
+    /// Gaussian curvature
+    float gaussian = 0;
+
+    /// Minimum principal curvature
+    float min = 0;
+
+    /// Maximum principal curvature
+    float max = 0;
+
+    /// Tangent direction of the minimum principal curvature
+    Vector3f minDirection = Vector3f::Zero();
+
+    /// Tangent direction of the maximum principal curvature
+    Vector3f maxDirection = Vector3f::Zero();
+};
+
+/**
+ * @brief Computes the curvature of each vertex. Boundary and isolated
+ *        vertices are assigned zero curvature.
+ *
+ * @param mesh              The mesh
+ * @param smoothingSteps    Number of iterations in which the curvature values
+ *                          are averaged with the neighboring vertices
+ */
+template<typename BaseVecT>
+DenseVertexMap<VertexCurvature> calcVertexCurvature(const BaseMesh<BaseVecT>& mesh, unsigned int smoothingSteps = 0);
+
+/**
+ * @brief Computes the curvature of each vertex of the mesh buffer and stores
+ *        the results in the float channels "mean_curvature", "gaussian_curvature",
+ *        "min_curvature", "max_curvature" (width 1) and "min_curvature_direction",
+ *        "max_curvature_direction" (width 3).
+ */
+void calcVertexCurvature(MeshBufferPtr mesh, unsigned int smoothingSteps = 0);
+
+} // namespace lvr2
+
+#include "lvr2/algorithm/MeshCurvature.tcc"
+
+#endif // LVR2_ALGORITHM_MESHCURVATURE_HPP
@@ -0,0 +1,212 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshCurvature.tcc
+ */
+
+#include <Eigen/Dense>
+
+#include <algorithm>
+#include <cmath>
+#include <vector>
+
+namespace lvr2
+{
+
+template<typename BaseVecT>
+DenseVertexMap<VertexCurvature> calcVertexCurvature(const BaseMesh<BaseVecT>& mesh, unsigned int smoothingSteps)
+{
+    const size_t numVertices = mesh.nextVertexIndex();
+    auto position = [&mesh](VertexHandle vH)
+    {
+        const BaseVecT p = mesh.getVertexPosition(vH);
+        return Vector3d(p.x, p.y, p.z);
+    };
+
+    DenseVertexMap<Vector3d> laplace(numVertices, Vector3d::Zero());
+    DenseVertexMap<Vector3d> normals(numVertices, Vector3d::Zero());
+    DenseVertexMap<double> areas(numVertices, 0.0);
+    DenseVertexMap<double> angleSums(numVertices, 0.0);
+    DenseVertexMap<bool> boundary(numVertices, false);
+
+    // Accumulate the cotangent Laplacian, the mixed Voronoi areas and the
+    // angle sums face by face
+    for (auto fH : mesh.faces())
+    {
+        const std::array<VertexHandle, 3> vertices = mesh.getVerticesOfFace(fH);
+        const Vector3d p[3] = {position(vertices[0]), position(vertices[1]), position(vertices[2])};
+
+        const Vector3d n = (p[1] - p[0]).cross(p[2] - p[0]);
+        const double area = 0.5 * n.norm();
+        if (area <= 0)
+        {
+            continue;
+        }
+
+        double angle[3];
+        double cot[3];
+        bool obtuse = false;
+        for (int i = 0; i < 3; i++)
+        {
+            const Vector3d u = p[(i + 1) % 3] - p[i];
+            const Vector3d w = p[(i + 2) % 3] - p[i];
+            const double sin = u.cross(w).norm();
+            angle[i] = std::atan2(sin, u.dot(w));
+            cot[i] = u.dot(w) / sin;
+            obtuse |= angle[i] > M_PI / 2;
+        }
+
+        for (int i = 0; i < 3; i++)
+        {
+            const int j = (i + 1) % 3;
+            const int k = (i + 2) % 3;
+
+            normals[vertices[i]] += n;
+            angleSums[vertices[i]] += angle[i];
+
+            // The angle at i weights the opposite edge (j, k)
+            laplace[vertices[j]] += cot[i] * (p[k] - p[j]);
+            laplace[vertices[k]] += cot[i] * (p[j] - p[k]);
+
+            if (!obtuse)
+            {
+                areas[vertices[i]] += ((p[j] - p[i]).squaredNorm() * cot[k] + (p[k] - p[i]).squaredNorm() * cot[j]) / 8.0;
+            }
+            else
+            {
+                areas[vertices[i]] += angle[i] > M_PI / 2 ? area / 2.0 : area / 4.0;
+            }
+        }
+    }
+
+    for (auto eH : mesh.edges())
+    {
+        if (mesh.isBorderEdge(eH))
+        {
+            for (auto vH : mesh.getVerticesOfEdge(eH))
+            {
+                boundary[vH] = true;
+            }
+        }
+    }
+
+    DenseVertexMap<VertexCurvature> curvature(numVertices, VertexCurvature());
+    std::vector<VertexHandle> neighbors;
+    for (auto vH : mesh.vertices())
+    {
+        if (boundary[vH] || areas[vH] <= 0 || normals[vH].squaredNorm() == 0)
+        {
+            continue;
+        }
+
+        const Vector3d n = normals[vH].normalized();
+        const double area = areas[vH];
+
+        VertexCurvature& c = curvature[vH];
+        c.mean = -laplace[vH].dot(n) / (4.0 * area);
+        c.gaussian = (2.0 * M_PI - angleSums[vH]) / area;
+        const double d = std::sqrt(std::max(0.0, (double)c.mean * c.mean - c.gaussian));
+        c.min = c.mean - d;
+        c.max = c.mean + d;
+
+        // Principal directions from a least squares fit of the curvature
+        // tensor to the normal curvatures along the adjacent edges
+        const Vector3d t1 = n.unitOrthogonal();
+        const Vector3d t2 = n.cross(t1);
+        Eigen::Matrix3d ata = Eigen::Matrix3d::Zero();
+        Vector3d atb = Vector3d::Zero();
+        neighbors.clear();
+        mesh.getNeighboursOfVertex(vH, neighbors);
+        for (VertexHandle nH : neighbors)
+        {
+            const Vector3d e = position(nH) - position(vH);
+            const Vector3d t = e - e.dot(n) * n;
+            if (e.squaredNorm() == 0 || t.squaredNorm() == 0)
+            {
+                continue;
+            }
+            const double kn = -2.0 * e.dot(n) / e.squaredNorm();
+            const double x = t.normalized().dot(t1);
+            const double y = t.normalized().dot(t2);
+            const Vector3d row(x * x, 2 * x * y, y * y);
+            ata += row * row.transpose();
+            atb += row * kn;
+        }
+
+        if (neighbors.size() >= 3 && std::abs(ata.determinant()) > 1e-12)
+        {
+            const Vector3d abc = ata.ldlt().solve(atb);
+            Eigen::Matrix2d tensor;
+            tensor << abc[0], abc[1], abc[1], abc[2];
+            Eigen::SelfAdjointEigenSolver<Eigen::Matrix2d> solver(tensor);
+            const Eigen::Matrix2d dirs = solver.eigenvectors();
+            c.minDirection = (dirs(0, 0) * t1 + dirs(1, 0) * t2).cast<float>();
+            c.maxDirection = (dirs(0, 1) * t1 + dirs(1, 1) * t2).cast<float>();
+        }
+    }
+
+    // Average the scalar curvatures over the one ring of each inner vertex
+    for (unsigned int step = 0; step < smoothingSteps; step++)
+    {
+        DenseVertexMap<VertexCurvature> smoothed = curvature;
+        for (auto vH : mesh.vertices())
+        {
+            if (boundary[vH])
+            {
+                continue;
+            }
+
+            VertexCurvature sum = curvature[vH];
+            int count = 1;
+            neighbors.clear();
+            mesh.getNeighboursOfVertex(vH, neighbors);
+            for (VertexHandle nH : neighbors)
+            {
+                if (!boundary[nH])
+                {
+                    sum.mean += curvature[nH].mean;
+                    sum.gaussian += curvature[nH].gaussian;
+                    sum.min += curvature[nH].min;
+                    sum.max += curvature[nH].max;
+                    count++;
+                }
+            }
+
+            smoothed[vH].mean = sum.mean / count;
+            smoothed[vH].gaussian = sum.gaussian / count;
+            smoothed[vH].min = sum.min / count;
+            smoothed[vH].max = sum.max / count;
+        }
+        curvature = std::move(smoothed);
+    }
+
+    return curvature;
+}
+
+} // namespace lvr2
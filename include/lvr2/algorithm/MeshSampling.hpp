@@ -0,0 +1,160 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshSampling.hpp
+ *
+ * Generation of point clouds from meshes, either by sampling the surface
+ * uniformly or by simulating a laser scanner at given poses. Useful to
+ * create test data with known ground truth.
+ */
+
+#ifndef LVR2_ALGORITHM_MESHSAMPLING_HPP
+#define LVR2_ALGORITHM_MESHSAMPLING_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Samples points uniformly on the surface of the mesh. The number
+ *        of points in each face is proportional to its area.
+ *
+ *        The returned buffer contains normals, interpolated from the vertex
+ *        normals if present, and interpolated vertex colors if the mesh has
+ *        vertex colors.
+ *
+ * @param mesh      The mesh
+ * @param density   Number of points per square unit
+ * @param seed      Seed of the random number generator
+ */
+PointBufferPtr sampleMesh(MeshBufferPtr mesh, float density, unsigned int seed = 0);
+
+struct VirtualScannerOptions
+{
+    /// Horizontal field of view in degrees, centered around the x axis
+    float horizontalFov = 360.0f;
+
+    /// Vertical field of view in degrees, relative to the xy plane
+    float verticalMin = -30.0f;
+    float verticalMax = 30.0f;
+
+    /// Angular resolution in degrees
+    float horizontalResolution = 0.5f;
+    float verticalResolution = 1.0f;
+
+    /// Hits further away than this are discarded
+    float maxRange = 100.0f;
+
+    /// Standard deviation of gaussian noise added to the measured ranges
+    float rangeNoise = 0.0f;
+
+    /// If true, the points of all poses are returned in the mesh
+    /// coordinate system. Otherwise each scan is in its scanner frame,
+    /// which is only useful for a single pose.
+    bool worldFrame = true;
+
+    unsigned int seed = 0;
+};
+
+/**
+ * @brief Simulates a laser scanner that casts rays in a regular angular
+ *        pattern from each of the given poses onto the mesh.
+ *
+ *        The returned buffer contains the hit points, normals of the hit
+ *        faces facing the scanner and the "ranges" as additional channel.
+ *
+ * @param mesh      The scanned mesh
+ * @param poses     Scanner poses (scanner to mesh coordinates)
+ * @param options   Scan pattern and noise parameters
+ */
+PointBufferPtr virtualScan(
+    MeshBufferPtr mesh,
+    const std::vector<Transformd>& poses,
+    const VirtualScannerOptions& options = VirtualScannerOptions()
+);
+
+struct VirtualScan2DOptions
+{
+    /// Angles of the first and last ray in degrees, measured counter clockwise from the x axis
+    float angleMin = -180.0f;
+    float angleMax = 180.0f;
+
+    /// Angular resolution in degrees
+    float angleResolution = 0.5f;
+
+    /// Hits closer than rangeMin or further away than rangeMax are discarded
+    float rangeMin = 0.1f;
+    float rangeMax = 30.0f;
+
+    /// Standard deviation of gaussian noise added to the measured ranges
+    float rangeNoise = 0.0f;
+
+    unsigned int seed = 0;
+};
+
+/**
+ * @brief A planar laser scan with the fields of a ROS LaserScan message
+ */
+struct LaserScan2D
+{
+    /// Angle of the first ray and between consecutive rays in radians
+    float angleMin = 0;
+    float angleIncrement = 0;
+
+    float rangeMin = 0;
+    float rangeMax = 0;
+
+    /// Measured range of each ray, infinity if nothing was hit within the valid range
+    std::vector<float> ranges;
+};
+
+/**
+ * @brief Simulates a planar laser scanner that casts rays in the xy plane
+ *        of each of the given poses onto the mesh, e.g. to test localization
+ *        against a reconstructed map.
+ *
+ * @param mesh      The scanned mesh
+ * @param poses     Scanner poses (scanner to mesh coordinates)
+ * @param options   Scan pattern and noise parameters
+ * @return One scan per pose
+ */
+std::vector<LaserScan2D> virtualScan2D(
+    MeshBufferPtr mesh,
+    const std::vector<Transformd>& poses,
+    const VirtualScan2DOptions& options = VirtualScan2DOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_MESHSAMPLING_HPP
@@ -0,0 +1,112 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * MeshTiler.hpp
+ *
+ * Splits large meshes into a regular grid of tiles, e.g. for web streaming
+ * of city-scale reconstructions.
+ */
+
+#ifndef LVR2_ALGORITHM_MESHTILER_HPP
+#define LVR2_ALGORITHM_MESHTILER_HPP
+
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/BoundingBox.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <map>
+#include <string>
+#include <utility>
+#include <vector>
+
+namespace lvr2
+{
+
+struct MeshTilerOptions
+{
+    /// Edge length of a tile in the x-y plane
+    float       tileSize = 100.0f;
+
+    /// Distance by which tiles are extended beyond their borders. If 0,
+    /// every face is assigned to exactly one tile by its centroid. Shared
+    /// border vertices are duplicated with identical positions, so
+    /// neighboring tiles are stitched without cracks. If larger than 0,
+    /// every tile contains all faces that intersect the extended tile, so
+    /// faces near the borders are contained in several tiles.
+    float       overlap = 0.0f;
+
+    /// File extension of the tile files, determines the format (see ModelFactory)
+    std::string extension = ".ply";
+};
+
+/// Grid index (x, y) of a tile
+using TileIndex = std::pair<int, int>;
+
+/**
+ * @brief A single tile of a tiled mesh
+ */
+struct MeshTile
+{
+    /// The part of the mesh inside the tile
+    MeshBufferPtr                   mesh;
+
+    /// Bounding box of the tile's geometry
+    BoundingBox<BaseVector<float>>  boundingBox;
+};
+
+/**
+ * @brief Copies the given faces and the vertices they reference into a new
+ *        mesh. Vertex normals, colors, texture coordinates, face material
+ *        indices, materials and textures are preserved.
+ */
+MeshBufferPtr extractFaces(MeshBufferPtr mesh, const std::vector<size_t>& faceIds);
+
+/**
+ * @brief Splits the given mesh into tiles of a regular grid in the x-y plane.
+ *
+ *        Vertex normals, colors, texture coordinates and face material
+ *        indices are copied to the tiles. Empty tiles are omitted.
+ */
+std::map<TileIndex, MeshTile> tileMesh(MeshBufferPtr mesh, const MeshTilerOptions& options);
+
+/**
+ * @brief Splits the given mesh into tiles and writes one file per tile
+ *        ("tile_<x>_<y><extension>") and an index "tiles.json" that lists
+ *        the files together with their grid index, bounding box and size.
+ *
+ * @param mesh          The mesh to split
+ * @param options       Tiling parameters
+ * @param outputDir     Output directory. Created if it does not exist.
+ */
+void writeMeshTiles(MeshBufferPtr mesh, const MeshTilerOptions& options, const boost::filesystem::path& outputDir);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_MESHTILER_HPP
@@ -0,0 +1,91 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * MeshVoxelization.hpp
+ *
+ * Conversion of triangle meshes into signed distance fields and solid
+ * occupancy grids, e.g. for CSG operations, collision checks or to extract
+ * a new mesh with marching cubes.
+ */
+
+#ifndef LVR2_ALGORITHM_MESHVOXELIZATION_HPP
+#define LVR2_ALGORITHM_MESHVOXELIZATION_HPP
+
+#include "lvr2/algorithm/OccupancyGrid.hpp"
+#include "lvr2/reconstruction/DistanceField.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <memory>
+
+namespace lvr2
+{
+
+struct MeshDistanceFieldOptions
+{
+    /// Edge length of the cells of the distance field
+    float voxelSize = 0.05;
+
+    /// Only cells whose corners are all within this number of voxels of the
+    /// surface are stored
+    int bandWidth = 3;
+};
+
+/**
+ * @brief Computes the signed distance of the grid corners near the surface
+ *        of the mesh. The sign is taken from the normal of the closest face,
+ *        i.e. negative values are behind the faces. The mesh should be closed
+ *        and consistently oriented.
+ *
+ *        The result can be converted to a HashGrid with DistanceField::toPointBuffer()
+ *        to extract a new mesh. The field is empty if the mesh has no faces
+ *        or the voxel size is not positive.
+ *
+ * @param mesh      A mesh buffer with vertices and face indices
+ * @param options   Voxelization parameters
+ */
+std::shared_ptr<DistanceField> meshToDistanceField(
+    MeshBufferPtr mesh,
+    const MeshDistanceFieldOptions& options = MeshDistanceFieldOptions()
+);
+
+/**
+ * @brief Marks all voxels that are intersected by a face or enclosed by the
+ *        mesh as occupied. In contrast to the distance field, the inside is
+ *        determined by a flood fill from the outside, so the orientation of
+ *        the faces does not matter, but the mesh has to be closed.
+ *
+ * @param mesh          A mesh buffer with vertices and face indices
+ * @param resolution    Edge length of a voxel
+ */
+OccupancyGrid solidOccupancyGrid(MeshBufferPtr mesh, double resolution);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_MESHVOXELIZATION_HPP
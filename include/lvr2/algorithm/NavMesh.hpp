@@ -0,0 +1,115 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * NavMesh.hpp
+ *
+ * Generation of navigation meshes for path planning on reconstructed
+ * environments (z up). Walkable floor surfaces are rasterized into a layered
+ * height grid, cells without enough clearance or too close to an edge are
+ * removed and the remaining cells are merged into convex polygons.
+ */
+
+#ifndef LVR2_ALGORITHM_NAVMESH_HPP
+#define LVR2_ALGORITHM_NAVMESH_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct NavMeshOptions
+{
+    /// Edge length of the cells of the height grid
+    float cellSize = 0.05;
+
+    /// Maximum angle between a floor face and the horizontal plane in degrees
+    float maxSlope = 35;
+
+    /// Radius of the agent. Walkable cells closer than this to an edge are removed.
+    float agentRadius = 0.3;
+
+    /// Minimum free space above a walkable cell
+    float agentHeight = 1.8;
+
+    /// Maximum height difference between adjacent walkable cells
+    float maxStepHeight = 0.3;
+
+    /// Maximum edge length of a polygon in cells
+    int maxPolygonCells = 64;
+};
+
+struct NavPolygon
+{
+    /// Counter clockwise outline (seen from above)
+    std::vector<Vector3f> vertices;
+
+    /// Indices of the polygons that share an edge with this one
+    std::vector<size_t> neighbors;
+
+    /// Area of the projection onto the xy plane
+    float area = 0;
+};
+
+struct NavMesh
+{
+    std::vector<NavPolygon> polygons;
+};
+
+/**
+ * @brief Creates a navigation mesh from the floor surfaces of the mesh.
+ *
+ *        Faces whose normal is within options.maxSlope of the z axis are
+ *        walkable. Other faces only act as obstacles, i.e. walls, furniture
+ *        and ceilings that are lower than options.agentHeight.
+ *
+ * @param mesh      Leveled mesh with outward facing faces
+ * @param options   Generation parameters
+ */
+NavMesh buildNavMesh(MeshBufferPtr mesh, const NavMeshOptions& options = NavMeshOptions());
+
+/**
+ * @brief Triangulates the polygons of the navigation mesh, e.g. for
+ *        visualization. Vertices are not shared between polygons.
+ */
+MeshBufferPtr navMeshToMeshBuffer(const NavMesh& navMesh);
+
+/**
+ * @brief Writes the polygons with their adjacency as JSON.
+ */
+void saveNavMesh(const NavMesh& navMesh, const boost::filesystem::path& file);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_NAVMESH_HPP
@@ -48,6 +48,17 @@
 namespace lvr2
 {
 
+/// Weights of the face normals when they are averaged to vertex normals
+enum class VertexNormalWeighting
+{
+    /// All adjacent faces contribute equally. Biased towards regions with many small faces.
+    Uniform,
+    /// Faces are weighted by their interior angle at the vertex
+    Angle,
+    /// Faces are weighted by their area
+    Area
+};
+
 /**
  * @brief Returns the normal of a face with the given three vertices.
  *
@@ -70,12 +81,15 @@ DenseFaceMap<Normal<typename BaseVecT::CoordType>> calcFaceNormals(const BaseMes
 /**
  * @brief Returns a vertex normal for the given vertex interpolated from the
  *        normals of its adjacent faces.
+ *
+ * @param weighting How the face normals are weighted
  */
 template<typename BaseVecT>
 boost::optional<Normal<typename BaseVecT::CoordType>> interpolatedVertexNormal(
     const BaseMesh<BaseVecT>& mesh,
     const FaceMap<Normal<typename BaseVecT::CoordType>>& normals,
-    VertexHandle handle
+    VertexHandle handle,
+    VertexNormalWeighting weighting = VertexNormalWeighting::Uniform
 );
 
 /**
@@ -85,13 +99,15 @@ boost::optional<Normal<typename BaseVecT::CoordType>> interpolatedVertexNormal(
  * adjacent faces. If a vertex doesn't have adjacent faces, the normal from
  * the nearest point in the point cloud is used.
  *
- * @param surface A point cloud with normal information
+ * @param surface   A point cloud with normal information
+ * @param weighting How the face normals are weighted
  */
 template<typename BaseVecT>
 DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
     const BaseMesh<BaseVecT>& mesh,
     const FaceMap<Normal<typename BaseVecT::CoordType>>& normals,
-    const PointsetSurface<BaseVecT>& surface
+    const PointsetSurface<BaseVecT>& surface,
+    VertexNormalWeighting weighting = VertexNormalWeighting::Uniform
 );
 
 /**
@@ -101,12 +117,32 @@ DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
  * adjacent faces. If a vertex doesn't have adjacent faces, the default
  * normal (0, 0, 1) is used.
  *
- * @param surface A point cloud with normal information
+ * @param weighting How the face normals are weighted
  */
 template<typename BaseVecT>
 DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
     const BaseMesh<BaseVecT>& mesh,
-    const FaceMap<Normal<typename BaseVecT::CoordType>>& normals
+    const FaceMap<Normal<typename BaseVecT::CoordType>>& normals,
+    VertexNormalWeighting weighting = VertexNormalWeighting::Uniform
+);
+
+/**
+ * @brief Calculates a normal for each vertex in the mesh by interpolating
+ *        the normals of the k nearest points of the point cloud.
+ *
+ * The point normals are weighted by the inverse distance to the vertex.
+ * This yields a smoother shading that follows the scanned surface more
+ * closely than averaging the face normals. If the weighted sum vanishes,
+ * the normal of the nearest point is used.
+ *
+ * @param surface A point cloud with normal information
+ * @param k       Number of point normals to interpolate
+ */
+template<typename BaseVecT>
+DenseVertexMap<Normal<typename BaseVecT::CoordType>> interpolateVertexNormalsFromPoints(
+    const BaseMesh<BaseVecT>& mesh,
+    const PointsetSurface<BaseVecT>& surface,
+    int k
 );
 
 } // namespace lvr2
@@ -33,6 +33,9 @@
  * @author Johan M. von Behren <johan@vonbehren.eu>
  */
 
+#include <algorithm>
+#include <cmath>
+#include <limits>
 #include <vector>
 
 using std::vector;
@@ -75,9 +78,12 @@ template <typename BaseVecT>
 boost::optional<Normal<typename BaseVecT::CoordType>> interpolatedVertexNormal(
     const BaseMesh<BaseVecT>& mesh,
     const FaceMap<Normal<typename BaseVecT::CoordType>>& normals,
-    VertexHandle handle
+    VertexHandle handle,
+    VertexNormalWeighting weighting
 )
 {
+    using CoordT = typename BaseVecT::CoordType;
+
     auto faces = mesh.getFacesOfVertex(handle);
 
     // Return none, if vertex does not have connected faces
@@ -90,7 +96,25 @@ boost::optional<Normal<typename BaseVecT::CoordType>> interpolatedVertexNormal(
     BaseVecT v(0, 0, 0);
     for (auto face: faces)
     {
-        v += normals[face];
+        CoordT weight = 1;
+        if (weighting == VertexNormalWeighting::Area)
+        {
+            weight = mesh.calcFaceArea(face);
+        }
+        else if (weighting == VertexNormalWeighting::Angle)
+        {
+            // Interior angle between the two edges that meet at the vertex
+            auto handles = mesh.getVerticesOfFace(face);
+            auto positions = mesh.getVertexPositionsOfFace(face);
+            int i = handles[0] == handle ? 0 : (handles[1] == handle ? 1 : 2);
+            auto e1 = positions[(i + 1) % 3] - positions[i];
+            auto e2 = positions[(i + 2) % 3] - positions[i];
+            const CoordT len = std::sqrt(e1.length2() * e2.length2());
+            weight = len > 0
+                ? std::acos(std::max<CoordT>(-1, std::min<CoordT>(1, e1.dot(e2) / len)))
+                : 0;
+        }
+        v += normals[face] * weight;
     }
 
     // It is indeed possible that `v` is the zero vector here: if there are two
@@ -102,7 +126,8 @@ template<typename BaseVecT>
 DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
     const BaseMesh<BaseVecT>& mesh,
     const FaceMap<Normal<typename BaseVecT::CoordType>>& normals,
-    const PointsetSurface<BaseVecT>& surface
+    const PointsetSurface<BaseVecT>& surface,
+    VertexNormalWeighting weighting
 )
 {
 
@@ -112,7 +137,7 @@ DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
     for (auto vH: mesh.vertices())
     {
         // Use averaged normals from adjacent faces
-        if (auto normal = interpolatedVertexNormal(mesh, normals, vH))
+        if (auto normal = interpolatedVertexNormal(mesh, normals, vH, weighting))
         {
             normalMap.insert(vH, *normal);
         }
@@ -156,7 +181,8 @@ DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
 template<typename BaseVecT>
 DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
     const BaseMesh<BaseVecT>& mesh,
-    const FaceMap<Normal<typename BaseVecT::CoordType>>& normals
+    const FaceMap<Normal<typename BaseVecT::CoordType>>& normals,
+    VertexNormalWeighting weighting
 )
 {
     DenseVertexMap<Normal<typename BaseVecT::CoordType>> normalMap;
@@ -168,7 +194,7 @@ DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
         {
 
             // Use averaged normals from adjacent faces
-            if (auto normal = interpolatedVertexNormal(mesh, normals, vH))
+            if (auto normal = interpolatedVertexNormal(mesh, normals, vH, weighting))
             {
                 normalMap.insert(vH, *normal);
             }
@@ -187,4 +213,58 @@ DenseVertexMap<Normal<typename BaseVecT::CoordType>> calcVertexNormals(
     return normalMap;
 }
 
+template<typename BaseVecT>
+DenseVertexMap<Normal<typename BaseVecT::CoordType>> interpolateVertexNormalsFromPoints(
+    const BaseMesh<BaseVecT>& mesh,
+    const PointsetSurface<BaseVecT>& surface,
+    int k
+)
+{
+    using CoordT = typename BaseVecT::CoordType;
+
+    FloatChannelOptional pointNormals = surface.pointBuffer()->getFloatChannel("normals");
+    if (!pointNormals)
+    {
+        panic("the point buffer needs normals!");
+    }
+    FloatChannel points = *surface.pointBuffer()->getFloatChannel("points");
+
+    DenseVertexMap<Normal<CoordT>> normalMap;
+    normalMap.reserve(mesh.numVertices());
+
+    vector<size_t> pointIdx;
+    for (auto vH: mesh.vertices())
+    {
+        auto vertex = mesh.getVertexPosition(vH);
+        pointIdx.clear();
+        surface.searchTree()->kSearch(vertex, std::max(k, 1), pointIdx);
+        if (pointIdx.empty())
+        {
+            normalMap.insert(vH, Normal<CoordT>(0, 0, 1));
+            continue;
+        }
+
+        BaseVecT sum(0, 0, 0);
+        for (size_t idx: pointIdx)
+        {
+            BaseVecT p = points[idx];
+            BaseVecT n = (*pointNormals)[idx];
+            CoordT weight = 1 / (vertex.distance(p) + std::numeric_limits<CoordT>::epsilon());
+            sum += n * weight;
+        }
+
+        if (sum.length2() > 0 && std::isfinite(sum.length2()))
+        {
+            normalMap.insert(vH, Normal<CoordT>(sum));
+        }
+        else
+        {
+            BaseVecT nearest = (*pointNormals)[pointIdx[0]];
+            normalMap.insert(vH, Normal<CoordT>(nearest));
+        }
+    }
+
+    return normalMap;
+}
+
 } // namespace lvr2
@@ -0,0 +1,120 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * OccupancyGrid.hpp
+ *
+ * Conversion of point clouds and meshes into regular 3D occupancy grids
+ * and their projection to 2D occupancy maps for robot navigation (z up).
+ */
+
+#ifndef LVR2_ALGORITHM_OCCUPANCYGRID_HPP
+#define LVR2_ALGORITHM_OCCUPANCYGRID_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <opencv2/core.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief A regular grid of occupied voxels. Voxel (x, y, z) covers the cube
+ *        from origin + (x, y, z) * resolution to origin + (x + 1, y + 1, z + 1) * resolution,
+ *        x runs fastest.
+ */
+struct OccupancyGrid
+{
+    Vector3i size = Vector3i::Zero();
+
+    /// Lower corner of the grid
+    Vector3d origin = Vector3d::Zero();
+
+    /// Edge length of a voxel
+    double resolution = 1;
+
+    std::vector<unsigned char> cells;
+
+    size_t index(int x, int y, int z) const
+    {
+        return (static_cast<size_t>(z) * size.y() + y) * size.x() + x;
+    }
+
+    bool occupied(int x, int y, int z) const { return cells[index(x, y, z)] != 0; }
+
+    /// Number of occupied voxels
+    size_t numOccupied() const;
+};
+
+/**
+ * @brief Marks all voxels that contain at least one point as occupied.
+ *
+ * @param points        The point cloud
+ * @param resolution    Edge length of a voxel
+ */
+OccupancyGrid occupancyGrid(PointBufferPtr points, double resolution);
+
+/**
+ * @brief Marks all voxels that are intersected by a face as occupied. The
+ *        interior of closed meshes is not filled.
+ *
+ * @param mesh          A mesh buffer with vertices and face indices
+ * @param resolution    Edge length of a voxel
+ */
+OccupancyGrid occupancyGrid(MeshBufferPtr mesh, double resolution);
+
+struct OccupancyMapOptions
+{
+    /// Occupied voxels between minHeight and maxHeight are obstacles. Occupied
+    /// voxels below minHeight are floor, i.e. mark the cell as free.
+    double minHeight = 0.1;
+    double maxHeight = 2.0;
+
+    /// Pixel values of the map. The defaults match the conventions of the ROS map_server.
+    unsigned char occupiedValue = 0;
+    unsigned char freeValue = 254;
+    unsigned char unknownValue = 205;
+};
+
+/**
+ * @brief Projects the grid onto the xy plane. Each pixel corresponds to one
+ *        voxel column, row 0 is the column with the highest y coordinate.
+ *        Columns without any occupied voxel are unknown.
+ *
+ * @return An 8 bit single channel image
+ */
+cv::Mat projectOccupancyGrid(const OccupancyGrid& grid, const OccupancyMapOptions& options = OccupancyMapOptions());
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_OCCUPANCYGRID_HPP
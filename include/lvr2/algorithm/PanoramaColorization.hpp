@@ -0,0 +1,108 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * PanoramaColorization.hpp
+ *
+ * Colorization of point clouds and meshes from the spherical panoramas
+ * that terrestrial scanners capture at each scan position.
+ */
+
+#ifndef LVR2_ALGORITHM_PANORAMACOLORIZATION_HPP
+#define LVR2_ALGORITHM_PANORAMACOLORIZATION_HPP
+
+#include "lvr2/types/CameraModels.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <opencv2/core.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief An equirectangular panorama with its pose
+ */
+struct Panorama
+{
+    /// BGR or grayscale image
+    cv::Mat image;
+
+    /// Projection of the image. Width and height default to the image size if 0.
+    EquirectangularModel model;
+
+    /// Panorama to world transformation, e.g. the pose of the scan position
+    Transformd pose = Transformd::Identity();
+};
+
+struct PanoramaColorizationOptions
+{
+    /// Replace existing colors. Otherwise, buffers that already have colors are not changed.
+    bool overwrite = false;
+
+    /// Points further away from all panoramas are not colored. Disabled if <= 0.
+    float maxDistance = 0;
+
+    /// Meshes only: skip panoramas from which a vertex is occluded by the mesh
+    bool occlusion = true;
+
+    /// Maximum distance by which an occluding face may be in front of a vertex
+    float occlusionTolerance = 0.01f;
+};
+
+/**
+ * @brief Colors each point from the closest panorama. Points that can not
+ *        be colored are black.
+ *
+ * @return The number of colored points
+ */
+size_t colorizeFromPanoramas(
+    PointBufferPtr points,
+    const std::vector<Panorama>& panoramas,
+    const PanoramaColorizationOptions& options = PanoramaColorizationOptions()
+);
+
+/**
+ * @brief Colors each vertex from the closest panorama it is visible from
+ *        and stores the result as vertex colors.
+ *
+ * @return The number of colored vertices
+ */
+size_t colorizeFromPanoramas(
+    MeshBufferPtr mesh,
+    const std::vector<Panorama>& panoramas,
+    const PanoramaColorizationOptions& options = PanoramaColorizationOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_PANORAMACOLORIZATION_HPP
@@ -0,0 +1,169 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PipeReconstruction.hpp
+ *
+ * Detection of cylindrical pipe segments in point clouds with normals and
+ * generation of parametric pipe meshes. Marching cubes needs very small
+ * voxels to reproduce thin pipes, while a fitted cylinder represents them
+ * exactly with a few parameters.
+ */
+
+#ifndef LVR2_ALGORITHM_PIPERECONSTRUCTION_HPP
+#define LVR2_ALGORITHM_PIPERECONSTRUCTION_HPP
+
+#include "lvr2/algorithm/ShapeFitting.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct PipeReconstructionOptions
+{
+    /// Maximum distance of an inlier to the cylinder surface
+    double distanceThreshold = 0.01;
+
+    /// Maximum angle between the normal of an inlier and the cylinder normal in degrees
+    double normalThreshold = 20;
+
+    /// Number of RANSAC iterations per detected cylinder
+    int iterations = 1000;
+
+    /// Minimum number of inliers of a pipe segment
+    size_t minInliers = 200;
+
+    /// Range of accepted pipe radii
+    double minRadius = 0.01;
+    double maxRadius = 1.0;
+
+    /// Maximum number of detected cylinders
+    int maxCylinders = 100;
+
+    /// Inliers of one cylinder are split into separate segments at gaps
+    /// along the axis that are larger than this
+    double segmentGap = 0.05;
+
+    /// Collinear segments are merged if the gap between them is smaller than this
+    double maxGap = 0.5;
+
+    /// Maximum angle between the axes of merged segments in degrees
+    double maxAxisAngle = 5;
+
+    /// Maximum relative radius difference of connected segments
+    double maxRadiusDeviation = 0.15;
+
+    /// Segments whose end points are closer than this (plus their radii)
+    /// belong to the same pipe run, e.g. at elbows and tees
+    double maxJointDistance = 0.1;
+
+    /// Seed of the random number generator
+    unsigned int seed = 0;
+};
+
+struct PipeSegment
+{
+    /// End points of the axis
+    Vector3d start = Vector3d::Zero();
+    Vector3d end = Vector3d::Zero();
+
+    double radius = 0;
+
+    /// Residuals of the final least squares fit
+    FitStatistics statistics;
+
+    /// Indices of the points that belong to the segment
+    std::vector<size_t> inliers;
+
+    /// Index of the run the segment belongs to
+    size_t run = 0;
+
+    double length() const { return (end - start).norm(); }
+};
+
+/// Connected segments of (approximately) equal radius
+struct PipeRun
+{
+    std::vector<size_t> segments;
+
+    /// Length weighted mean radius of the segments
+    double radius = 0;
+
+    /// Total length of all segments
+    double length = 0;
+};
+
+struct PipeReconstructionResult
+{
+    std::vector<PipeSegment> segments;
+    std::vector<PipeRun> runs;
+
+    /// Points that do not belong to any segment
+    std::vector<size_t> remaining;
+};
+
+/**
+ * @brief Detects cylinders with RANSAC, refines them with refineCylinder()
+ *        and splits them into segments at gaps along their axis. Collinear
+ *        segments are merged afterwards and adjacent ones grouped into runs.
+ *
+ * @param points    Point cloud with normals
+ * @param options   Detection parameters
+ */
+PipeReconstructionResult reconstructPipes(
+    PointBufferPtr points,
+    const PipeReconstructionOptions& options = PipeReconstructionOptions()
+);
+
+/**
+ * @brief Creates a triangle mesh of the pipe segments. Each face is labeled
+ *        with its segment ("pipe_segment" channel) and run ("pipe_run"), the
+ *        radius and length of the segment are stored in "pipe_radius" and
+ *        "pipe_length".
+ *
+ * @param pipes             Result of reconstructPipes()
+ * @param radialSegments    Number of faces around the circumference
+ * @param caps              If true, the ends of the segments are closed
+ */
+MeshBufferPtr pipeMesh(const PipeReconstructionResult& pipes, int radialSegments = 24, bool caps = false);
+
+/**
+ * @brief Writes one line per segment with its run, axis end points, radius,
+ *        length and fit rmse to a CSV file.
+ */
+void savePipeReport(const PipeReconstructionResult& pipes, const boost::filesystem::path& file);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_PIPERECONSTRUCTION_HPP
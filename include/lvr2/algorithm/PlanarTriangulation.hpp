@@ -0,0 +1,92 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PlanarTriangulation.hpp
+ *
+ * Direct triangulation of planar point segments without the voxel based
+ * reconstruction pipeline.
+ */
+
+#ifndef LVR2_ALGORITHM_PLANARTRIANGULATION_HPP
+#define LVR2_ALGORITHM_PLANARTRIANGULATION_HPP
+
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/Plane.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <limits>
+
+namespace lvr2
+{
+
+/**
+ * @brief Fits a plane to all points of the buffer using PCA. The position
+ *        of the plane is the centroid of the points.
+ */
+Plane<BaseVector<float>> fitPlane(PointBufferPtr points);
+
+/**
+ * @brief Triangulates a planar point segment.
+ *
+ *        The points are projected onto the plane, Delaunay triangulated in
+ *        plane coordinates and lifted back. Triangles with a circumradius
+ *        larger than maxRadius are removed, so the boundary of the mesh
+ *        follows the (possibly concave) outline of the segment instead of
+ *        its convex hull, see alphaShape2D().
+ *
+ * @param points        The points of the segment
+ * @param plane         The plane of the segment, e.g. from fitPlane()
+ * @param maxRadius     Maximum circumradius of a triangle
+ * @param projectPoints If true, the vertices are projected onto the plane.
+ *                      Otherwise, the original points are used.
+ *
+ * @return A mesh with vertex normals set to the plane normal. Triangles
+ *         are oriented towards the plane normal.
+ */
+MeshBufferPtr triangulatePlanarSegment(
+    PointBufferPtr points,
+    const Plane<BaseVector<float>>& plane,
+    double maxRadius = std::numeric_limits<double>::infinity(),
+    bool projectPoints = true
+);
+
+/**
+ * @brief Fits a plane to the points and triangulates them, see
+ *        triangulatePlanarSegment().
+ */
+MeshBufferPtr triangulatePlanarSegment(
+    PointBufferPtr points,
+    double maxRadius = std::numeric_limits<double>::infinity(),
+    bool projectPoints = true
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_PLANARTRIANGULATION_HPP
@@ -0,0 +1,86 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointClassification.hpp
+ *
+ * ASPRS point classes as stored in LAS files and filters that operate
+ * on the "classification" channel of a point buffer.
+ */
+
+#ifndef LVR2_ALGORITHM_POINTCLASSIFICATION_HPP
+#define LVR2_ALGORITHM_POINTCLASSIFICATION_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/// Standard ASPRS classification codes (LAS 1.4)
+namespace PointClass
+{
+    constexpr unsigned char Created           = 0;
+    constexpr unsigned char Unclassified      = 1;
+    constexpr unsigned char Ground            = 2;
+    constexpr unsigned char LowVegetation     = 3;
+    constexpr unsigned char MediumVegetation  = 4;
+    constexpr unsigned char HighVegetation    = 5;
+    constexpr unsigned char Building          = 6;
+    constexpr unsigned char LowPoint          = 7;
+    constexpr unsigned char Water             = 9;
+    constexpr unsigned char Rail              = 10;
+    constexpr unsigned char RoadSurface       = 11;
+    constexpr unsigned char WireGuard         = 13;
+    constexpr unsigned char WireConductor     = 14;
+    constexpr unsigned char TransmissionTower = 15;
+    constexpr unsigned char BridgeDeck        = 17;
+    constexpr unsigned char HighNoise         = 18;
+} // namespace PointClass
+
+/**
+ * @brief Returns the points whose classification is contained in the
+ *        given list of classes. All channels are copied.
+ *
+ *        If the buffer has no "classification" channel, a warning is
+ *        printed and the input buffer is returned unchanged.
+ *
+ * @param buffer    The input point cloud
+ * @param classes   The classes to keep
+ * @param invert    If true, the given classes are removed instead
+ */
+PointBufferPtr filterByClassification(
+    PointBufferPtr buffer,
+    const std::vector<unsigned char>& classes,
+    bool invert = false
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_POINTCLASSIFICATION_HPP
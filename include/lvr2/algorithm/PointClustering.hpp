@@ -0,0 +1,103 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointClustering.hpp
+ *
+ * Generic clustering of point cloud attributes: k-means on arbitrary
+ * float channels and density based clustering (DBSCAN) of positions.
+ */
+
+#ifndef LVR2_ALGORITHM_POINTCLUSTERING_HPP
+#define LVR2_ALGORITHM_POINTCLUSTERING_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+struct KMeansResult
+{
+    /// Cluster index of each element
+    std::vector<int> labels;
+
+    /// Cluster centers, k rows of the channel's width. Fewer if the
+    /// channel has less than k distinct values.
+    std::vector<std::vector<float>> centers;
+
+    /// Sum of squared distances of all elements to their center
+    double inertia = 0.0;
+
+    /// Number of iterations until convergence
+    int iterations = 0;
+};
+
+/**
+ * @brief Clusters the elements of a float channel with k-means. The
+ *        centers are initialized with k-means++.
+ *
+ * @param channel       The data to cluster, e.g. normals, colors or features
+ * @param k             Number of clusters
+ * @param maxIterations Maximum number of Lloyd iterations
+ * @param seed          Seed of the random number generator
+ */
+KMeansResult kMeans(const FloatChannel& channel, size_t k, int maxIterations = 100, unsigned int seed = 0);
+
+/**
+ * @brief Clusters the float channel with the given name, see kMeans().
+ *        Throws std::invalid_argument if the buffer has no such channel.
+ */
+KMeansResult kMeans(PointBufferPtr buffer, const std::string& channel, size_t k, int maxIterations = 100, unsigned int seed = 0);
+
+/// Label of points that do not belong to a cluster
+constexpr int DBSCAN_NOISE = -1;
+
+/**
+ * @brief Density based clustering of the point positions. Points with at
+ *        least minPoints neighbors (including themselves) within eps are
+ *        core points, clusters are the connected components of core points
+ *        and their neighbors.
+ *
+ * @param buffer        The point cloud
+ * @param eps           Neighborhood radius
+ * @param minPoints     Minimum neighborhood size of core points
+ * @param numClusters   Returns the number of clusters found
+ *
+ * @return Cluster index of each point or DBSCAN_NOISE
+ */
+std::vector<int> dbscan(PointBufferPtr buffer, float eps, size_t minPoints, size_t* numClusters = nullptr);
+
+/// Stores cluster labels in an int channel, noise is stored as -1
+void addLabelChannel(PointBufferPtr buffer, const std::vector<int>& labels, const std::string& name = "cluster_labels");
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_POINTCLUSTERING_HPP
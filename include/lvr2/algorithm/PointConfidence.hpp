@@ -0,0 +1,81 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointConfidence.hpp
+ *
+ * Derives per point confidence values that weight the contribution of each
+ * point to normal estimation and the distance function, see
+ * AdaptiveKSearchSurface::setConfidenceChannel().
+ */
+
+#ifndef LVR2_ALGORITHM_POINTCONFIDENCE_HPP
+#define LVR2_ALGORITHM_POINTCONFIDENCE_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <string>
+
+namespace lvr2
+{
+
+/**
+ * @brief Adds a confidence channel that decreases with the distance of each
+ *        point to the scanner position, w = 1 / (1 + (r / halfRange)^2).
+ *
+ * @param buffer        The point cloud
+ * @param origin        Scanner position in the coordinate system of the points
+ * @param halfRange     Range at which the confidence drops to 0.5
+ * @param name          Name of the created channel
+ */
+void addRangeConfidence(
+    PointBufferPtr buffer,
+    const Vector3d& origin,
+    float halfRange,
+    const std::string& name = "confidence"
+);
+
+/**
+ * @brief Adds a confidence channel by linearly mapping an existing quality
+ *        channel (e.g. reflectance or a scanner specific quality value) from
+ *        [minValue, maxValue] to [0, 1]. Values outside of the range are clamped.
+ *
+ * @return false, if the buffer has no channel with the given source name
+ */
+bool addQualityConfidence(
+    PointBufferPtr buffer,
+    const std::string& source,
+    float minValue,
+    float maxValue,
+    const std::string& name = "confidence"
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_POINTCONFIDENCE_HPP
@@ -0,0 +1,80 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointDeduplication.hpp
+ *
+ * Removal of near-coincident points, e.g. in the overlap regions of merged
+ * scans. Duplicates increase the memory consumption and bias the distance
+ * function towards the densely sampled overlaps.
+ */
+
+#ifndef LVR2_ALGORITHM_POINTDEDUPLICATION_HPP
+#define LVR2_ALGORITHM_POINTDEDUPLICATION_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <string>
+
+namespace lvr2
+{
+
+struct DeduplicationOptions
+{
+    /// Points closer than this distance are duplicates
+    float radius = 0.005f;
+
+    /// Float channel with per point weights. Of each group of duplicates,
+    /// the point with the highest value is kept. If the channel does not
+    /// exist, the point with the lowest index is kept.
+    std::string confidenceChannel = "confidence";
+
+    /// Unsigned int channel with the index of the scan each point originates
+    /// from. If set, only points of different scans are duplicates. Disabled
+    /// if empty or if the channel does not exist.
+    std::string sourceChannel;
+};
+
+/**
+ * @brief Removes points (and the associated entries of all per point
+ *        channels) that have a kept point within options.radius.
+ *
+ *        Points are visited in the order of decreasing confidence and kept
+ *        if no previously kept point lies within the radius, so the kept
+ *        points are at least options.radius apart.
+ *
+ * @return The number of removed points
+ */
+size_t removeDuplicatePoints(
+    PointBufferPtr buffer,
+    const DeduplicationOptions& options = DeduplicationOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_POINTDEDUPLICATION_HPP
@@ -0,0 +1,109 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ReflectionFilter.hpp
+ *
+ * Removal of virtual points that are caused by mirrors and glass. The laser
+ * beam is reflected by the surface and the measured point appears behind it,
+ * e.g. inside of a wall. Such points are detected by visibility conflicts
+ * between multiple registered scans: a point is virtual if other scans have
+ * observed the space it occupies as empty.
+ */
+
+#ifndef LVR2_ALGORITHM_REFLECTIONFILTER_HPP
+#define LVR2_ALGORITHM_REFLECTIONFILTER_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+struct ReflectionFilterOptions
+{
+    /// Edge length of the voxels used to record free space
+    float voxelSize = 0.05f;
+
+    /// Rays are traced up to this distance before their measured point, so
+    /// that noise on real surfaces does not carve them
+    float margin = 0.1f;
+
+    /// Number of other scans that have to observe the voxel of a point as
+    /// empty before the point is considered virtual
+    int minConflicts = 2;
+
+    /// Float channel with the intensities of the points. Disabled if empty.
+    std::string intensityChannel = "intensities";
+
+    /// Reflected beams lose energy. Points with an intensity below this value
+    /// are already removed if a single other scan observes their voxel as
+    /// empty. Disabled if <= 0.
+    float maxIntensity = 0.0f;
+};
+
+/**
+ * @brief Detects virtual points behind reflective surfaces.
+ *
+ *        The rays of all scans are traced through a voxel grid. A point is
+ *        virtual if at least options.minConflicts other scans traversed its
+ *        voxel and none of them measured a point in it. Only the first 64
+ *        scans contribute free space.
+ *
+ * @param scans     Registered scans in a common coordinate system
+ * @param origins   Scanner position of each scan in the same coordinate system
+ * @param options   Filter parameters
+ *
+ * @return The indices of the virtual points of each scan
+ *
+ * @throws std::invalid_argument if the number of scans and origins differ
+ */
+std::vector<std::vector<size_t>> detectReflections(
+    const std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const ReflectionFilterOptions& options = ReflectionFilterOptions()
+);
+
+/**
+ * @brief Removes the virtual points of all scans (and the associated
+ *        entries of all per point channels), see detectReflections().
+ *
+ * @return The total number of removed points
+ */
+size_t removeReflections(
+    std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const ReflectionFilterOptions& options = ReflectionFilterOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_REFLECTIONFILTER_HPP
@@ -0,0 +1,191 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * RoomTopology.hpp
+ *
+ * Extraction of walls, rooms and openings from leveled indoor scans (z up).
+ * Vertical planes are detected as walls and intersected into wall lines,
+ * the free space enclosed by the walls is split into room polygons and gaps
+ * in the point density of each wall are reported as doors or windows.
+ */
+
+#ifndef LVR2_ALGORITHM_ROOMTOPOLOGY_HPP
+#define LVR2_ALGORITHM_ROOMTOPOLOGY_HPP
+
+#include "lvr2/io/vector/PolylineIO.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct RoomTopologyOptions
+{
+    /// Maximum distance of a point to its wall plane
+    double distanceThreshold = 0.03;
+
+    /// Number of RANSAC iterations per wall
+    int iterations = 500;
+
+    /// Minimum number of points of a wall
+    size_t minWallPoints = 500;
+
+    /// Minimum length of a wall line
+    double minWallLength = 0.5;
+
+    /// Maximum number of detected walls
+    int maxWalls = 100;
+
+    /// Points of one plane are split into separate walls at gaps that are larger than this
+    double wallGap = 1.5;
+
+    /// End points of walls closer than this to the intersection of their lines are moved onto it
+    double cornerSnapDistance = 0.3;
+
+    /// Cell size of the floor plan raster used to find rooms
+    double gridResolution = 0.05;
+
+    /// Minimum floor area of a room
+    double minRoomArea = 2.0;
+
+    /// Cell size of the wall rasters used to find openings
+    double openingCellSize = 0.1;
+
+    /// Minimum width and height of an opening
+    double minOpeningWidth = 0.5;
+    double minOpeningHeight = 0.4;
+
+    /// Openings that reach down to the floor (within floorTolerance) and
+    /// are at least minDoorHeight high are doors, all others windows
+    double minDoorHeight = 1.8;
+    double floorTolerance = 0.1;
+
+    /// Seed of the random number generator
+    unsigned int seed = 0;
+};
+
+struct Wall
+{
+    /// Horizontal unit normal and distance of the vertical wall plane
+    Vector3d normal = Vector3d::UnitX();
+    double distance = 0;
+
+    /// End points of the wall line in the xy plane
+    Vector2d start = Vector2d::Zero();
+    Vector2d end = Vector2d::Zero();
+
+    /// Height range of the wall points
+    double bottom = 0;
+    double top = 0;
+
+    /// Indices of the wall points
+    std::vector<size_t> inliers;
+
+    /// Rooms on either side of the wall
+    std::vector<size_t> rooms;
+
+    double length() const { return (end - start).norm(); }
+};
+
+struct Room
+{
+    /// Counter clockwise outline in the xy plane
+    std::vector<Vector2d> polygon;
+
+    /// Floor area
+    double area = 0;
+};
+
+enum class OpeningType
+{
+    Door,
+    Window
+};
+
+struct Opening
+{
+    OpeningType type = OpeningType::Window;
+
+    /// Index of the wall that contains the opening
+    size_t wall = 0;
+
+    /// Center of the opening on the wall plane
+    Vector3d center = Vector3d::Zero();
+
+    double width = 0;
+    double height = 0;
+
+    /// Height of the lower edge
+    double sill = 0;
+
+    /// Rooms that are connected by the opening
+    std::vector<size_t> rooms;
+};
+
+struct RoomTopology
+{
+    std::vector<Wall> walls;
+    std::vector<Room> rooms;
+    std::vector<Opening> openings;
+
+    /// Heights of floor and ceiling of the scan
+    double floor = 0;
+    double ceiling = 0;
+};
+
+/**
+ * @brief Extracts the room graph of an indoor scan.
+ *
+ * @param points    Leveled point cloud, e.g. transformed with
+ *                  GroundDetectionResult::leveling. Normals are used to
+ *                  reject wall inliers if available.
+ * @param options   Extraction parameters
+ */
+RoomTopology extractRoomTopology(PointBufferPtr points, const RoomTopologyOptions& options = RoomTopologyOptions());
+
+/**
+ * @brief Converts the topology to polylines for saveDXF() or saveGeoJSON().
+ *        Wall lines are written to the layer "walls" (at floor height), room
+ *        outlines to "rooms" and the outlines of openings to "doors" and
+ *        "windows".
+ */
+std::vector<Polyline> roomTopologyPolylines(const RoomTopology& topology);
+
+/**
+ * @brief Writes the rooms, walls and openings with their adjacency as JSON.
+ */
+void saveRoomTopology(const RoomTopology& topology, const boost::filesystem::path& file);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_ROOMTOPOLOGY_HPP
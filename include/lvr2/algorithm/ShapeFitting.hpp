@@ -0,0 +1,188 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ShapeFitting.hpp
+ *
+ * Least squares fitting of planes, spheres and cylinders to inlier sets,
+ * e.g. from a RANSAC detection. A closed form estimate is refined with
+ * Gauss-Newton on the orthogonal distances, the remaining residuals are
+ * reported for quality assessment.
+ */
+
+#ifndef LVR2_ALGORITHM_SHAPEFITTING_HPP
+#define LVR2_ALGORITHM_SHAPEFITTING_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct ShapeFitOptions
+{
+    /// Maximum number of Gauss-Newton iterations
+    int maxIterations = 50;
+
+    /// Refinement stops if the relative change of the squared error is below this value
+    double tolerance = 1e-10;
+};
+
+/// Statistics of the orthogonal distances of the inliers to the fitted shape
+struct FitStatistics
+{
+    /// Number of points used for the fit
+    size_t numPoints = 0;
+
+    /// Root mean square distance
+    double rmse = 0;
+
+    /// Mean absolute distance
+    double meanAbsolute = 0;
+
+    /// Median absolute distance
+    double median = 0;
+
+    /// Maximum absolute distance
+    double maxAbsolute = 0;
+
+    /// Number of Gauss-Newton iterations that were performed
+    int iterations = 0;
+
+    /// True, if the refinement converged before options.maxIterations
+    bool converged = false;
+};
+
+/// Plane of all points p with normal.dot(p) == distance
+struct PlaneFit
+{
+    Vector3d normal = Vector3d::UnitZ();
+    double distance = 0;
+    FitStatistics statistics;
+};
+
+struct SphereFit
+{
+    Vector3d center = Vector3d::Zero();
+    double radius = 0;
+    FitStatistics statistics;
+};
+
+/// Infinite cylinder around the line through point with direction axis
+struct CylinderFit
+{
+    Vector3d point = Vector3d::Zero();
+    Vector3d axis = Vector3d::UnitZ();
+    double radius = 0;
+    FitStatistics statistics;
+};
+
+/**
+ * @brief Closed form plane estimate (PCA) of the given inliers.
+ *
+ * @param points    The point cloud
+ * @param inliers   Indices of the points to use. All points are used if empty.
+ */
+PlaneFit estimatePlane(PointBufferPtr points, const std::vector<size_t>& inliers = std::vector<size_t>());
+
+/**
+ * @brief Closed form algebraic sphere estimate of the given inliers.
+ */
+SphereFit estimateSphere(PointBufferPtr points, const std::vector<size_t>& inliers = std::vector<size_t>());
+
+/**
+ * @brief Closed form cylinder estimate of the given inliers. The axis is
+ *        taken from the point normals if available (the direction most
+ *        perpendicular to all normals), otherwise from the principal
+ *        direction of the points. The cross section is an algebraic
+ *        circle fit in the plane perpendicular to the axis.
+ */
+CylinderFit estimateCylinder(PointBufferPtr points, const std::vector<size_t>& inliers = std::vector<size_t>());
+
+/**
+ * @brief Refines a plane with Gauss-Newton and computes its residual statistics.
+ *
+ * @param points    The point cloud
+ * @param inliers   Indices of the points to use. All points are used if empty.
+ * @param initial   Start value, e.g. from RANSAC or estimatePlane()
+ * @param options   Refinement parameters
+ */
+PlaneFit refinePlane(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers,
+    const PlaneFit& initial,
+    const ShapeFitOptions& options = ShapeFitOptions()
+);
+
+/**
+ * @brief Refines a sphere with Gauss-Newton, see refinePlane().
+ */
+SphereFit refineSphere(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers,
+    const SphereFit& initial,
+    const ShapeFitOptions& options = ShapeFitOptions()
+);
+
+/**
+ * @brief Refines a cylinder with Gauss-Newton, see refinePlane(). The
+ *        returned point is the one on the axis closest to the inlier centroid.
+ */
+CylinderFit refineCylinder(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers,
+    const CylinderFit& initial,
+    const ShapeFitOptions& options = ShapeFitOptions()
+);
+
+/// estimatePlane() followed by refinePlane()
+PlaneFit fitPlaneLeastSquares(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers = std::vector<size_t>(),
+    const ShapeFitOptions& options = ShapeFitOptions()
+);
+
+/// estimateSphere() followed by refineSphere()
+SphereFit fitSphere(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers = std::vector<size_t>(),
+    const ShapeFitOptions& options = ShapeFitOptions()
+);
+
+/// estimateCylinder() followed by refineCylinder()
+CylinderFit fitCylinder(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers = std::vector<size_t>(),
+    const ShapeFitOptions& options = ShapeFitOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_SHAPEFITTING_HPP
@@ -0,0 +1,116 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Skeleton.hpp
+ *
+ * Medial axis extraction for planar regions and curve skeletons of
+ * tubular structures like pipes, e.g. for as-built modeling.
+ */
+
+#ifndef LVR2_ALGORITHM_SKELETON_HPP
+#define LVR2_ALGORITHM_SKELETON_HPP
+
+#include "lvr2/io/vector/PolylineIO.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Approximates the medial axis of a 2D region by the interior part
+ *        of the Voronoi diagram of densely sampled boundary points.
+ *
+ * @param rings             Closed boundary rings of the region (outer
+ *                          boundaries and holes, the orientation is ignored)
+ * @param sampleDistance    Maximum distance between two boundary samples.
+ *                          Smaller values lead to a more accurate axis.
+ * @param pruneLength       Branches that end in a leaf and are shorter than
+ *                          this are removed. Small values keep the spurious
+ *                          branches caused by noise on the boundary.
+ *
+ * @return The medial axis as open polylines with z = 0, split at junctions
+ */
+std::vector<Polyline> medialAxis2D(
+    const std::vector<std::vector<Vector2d>>& rings,
+    double sampleDistance,
+    double pruneLength
+);
+
+/**
+ * @brief Computes the medial axis of a planar segment. The points are
+ *        projected onto their best fit plane, the region is bounded by
+ *        their 2D alpha shape (see alphaTriangles2D()) and the resulting
+ *        axis is lifted back onto the plane.
+ *
+ * @param points            Points of the planar segment
+ * @param alpha             Maximum circumradius of the alpha shape triangles
+ * @param sampleDistance    See medialAxis2D()
+ * @param pruneLength       See medialAxis2D()
+ */
+std::vector<Polyline> planarSegmentSkeleton(
+    PointBufferPtr points,
+    double alpha,
+    double sampleDistance,
+    double pruneLength
+);
+
+struct CurveSkeletonOptions
+{
+    /// Neighborhood radius. Should be larger than the radius of the tubes.
+    double radius = 0.1;
+
+    /// Number of contraction iterations
+    int iterations = 10;
+
+    /// Distance between two skeleton nodes. Uses radius / 2 if 0.
+    double nodeSpacing = 0;
+
+    /// Length of the shortest branch that is kept. Uses radius if 0.
+    double pruneLength = 0;
+};
+
+/**
+ * @brief Computes a curve skeleton of tubular point clouds.
+ *
+ *        Each point is repeatedly moved onto the local axis of its
+ *        neighborhood, i.e. the line through the neighborhood centroid
+ *        along its principal direction. The contracted points are clustered
+ *        into nodes that are connected by a minimum spanning tree.
+ *
+ * @return The skeleton as polylines, split at junctions
+ * @throws std::invalid_argument if radius <= 0 or nodeSpacing < 0
+ */
+std::vector<Polyline> curveSkeleton(PointBufferPtr points, const CurveSkeletonOptions& options = CurveSkeletonOptions());
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_SKELETON_HPP
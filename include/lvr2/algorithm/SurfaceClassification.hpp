@@ -0,0 +1,100 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * SurfaceClassification.hpp
+ *
+ * Classification of mesh surfaces into floors, walls and ceilings by the
+ * direction of their area weighted normals. Used by the IFC and CityJSON
+ * exports.
+ */
+
+#ifndef LVR2_ALGORITHM_SURFACECLASSIFICATION_HPP
+#define LVR2_ALGORITHM_SURFACECLASSIFICATION_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+enum class SurfaceClass
+{
+    Floor,
+    Wall,
+    Ceiling,
+    Other
+};
+
+/**
+ * @brief Classifies a surface by the direction of its normal.
+ *
+ * Surfaces within maxWallTilt of the vertical are walls. Upward facing
+ * surfaces within maxHorizontalTilt of the horizontal are floors, downward
+ * facing ones ceilings. Everything else, including zero normals, is Other.
+ * With maxHorizontalTilt = 90, every surface that is not a wall is either a
+ * floor or a ceiling.
+ *
+ * @param normal            The (not necessarily normalized) surface normal
+ * @param maxWallTilt       Maximum deviation of walls from the vertical in degrees
+ * @param maxHorizontalTilt Maximum deviation of floors and ceilings from the horizontal in degrees
+ */
+SurfaceClass classifySurfaceNormal(const Vector3d& normal, double maxWallTilt, double maxHorizontalTilt);
+
+/**
+ * @brief Returns the area weighted normal (the length is twice the area) of
+ *        each face. If the mesh has the given face label channel, each
+ *        face gets the sum of the normals of all faces with its label.
+ *
+ * @param mesh          A triangle mesh
+ * @param regionChannel Index channel with one region label per face, e.g.
+ *                      the "face_regions" channel of SimpleFinalizer
+ */
+std::vector<Vector3d> areaWeightedFaceNormals(MeshBufferPtr mesh, const std::string& regionChannel = "face_regions");
+
+/**
+ * @brief Segments the mesh into planar regions for meshes without region
+ *        labels. Regions are grown over faces that share an edge (see
+ *        MeshBuffer::getAdjacency()) while the face normals stay within
+ *        maxAngle of the area weighted average normal of the region.
+ *
+ * @param mesh          A triangle mesh. The labels are stored in its
+ *                      regionChannel as one index per face.
+ * @param maxAngle      Maximum deviation of a face normal from the region
+ *                      normal in degrees
+ * @param regionChannel Name of the index channel for the labels
+ * @return              The number of regions
+ */
+size_t growPlanarRegions(MeshBufferPtr mesh, double maxAngle, const std::string& regionChannel = "face_regions");
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_SURFACECLASSIFICATION_HPP
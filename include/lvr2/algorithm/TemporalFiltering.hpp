@@ -0,0 +1,112 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * TemporalFiltering.hpp
+ *
+ * Filters for point clouds with per point time stamps, i.e. a double
+ * channel "timestamps" with one value per point as created by LasIO
+ * (GPS time) and readMcapPointClouds().
+ */
+
+#ifndef LVR2_ALGORITHM_TEMPORALFILTERING_HPP
+#define LVR2_ALGORITHM_TEMPORALFILTERING_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+#include "lvr2/types/Trajectory.hpp"
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Returns the minimum and maximum time stamp of the points.
+ *
+ * @return false, if the buffer has no valid time stamp channel
+ */
+bool timeRange(
+    PointBufferPtr buffer,
+    double& start,
+    double& end,
+    const std::string& channel = "timestamps"
+);
+
+/**
+ * @brief Returns a new buffer with all points whose time stamp is
+ *        within [start, end]. All other per point channels are preserved.
+ *
+ * @return nullptr, if the buffer has no valid time stamp channel
+ */
+PointBufferPtr filterByTime(
+    PointBufferPtr buffer,
+    double start,
+    double end,
+    const std::string& channel = "timestamps"
+);
+
+/**
+ * @brief Splits a point cloud into consecutive time windows of the given
+ *        length, e.g. to separate the sweeps of a rotating scanner. Empty
+ *        windows are skipped.
+ */
+std::vector<PointBufferPtr> splitByTime(
+    PointBufferPtr buffer,
+    double windowLength,
+    const std::string& channel = "timestamps"
+);
+
+/**
+ * @brief Removes the motion distortion of a point cloud that was recorded
+ *        by a moving scanner. Each point (and normal) is transformed with
+ *        the sensor pose at its time stamp and then into the sensor frame
+ *        at the reference time:
+ *
+ *        p' = T(referenceTime)^-1 * T(t) * p
+ *
+ *        with T(t) = trajectory.interpolate(t).
+ *
+ *        If referenceTime is NaN, the points are transformed into the target
+ *        frame of the trajectory instead, i.e. p' = T(t) * p. The buffer is
+ *        modified in place.
+ *
+ * @return false, if the buffer has no valid time stamp channel or the
+ *         trajectory is empty. The buffer is not changed in this case.
+ */
+bool deskewPoints(
+    PointBufferPtr buffer,
+    const Trajectory& trajectory,
+    double referenceTime,
+    const std::string& channel = "timestamps"
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_TEMPORALFILTERING_HPP
@@ -208,7 +208,7 @@ void Texturizer<BaseVecT>::findKeyPointsInTexture(const TextureHandle texH,
     }
 
     const unsigned char* img_data = texture.m_data;
-    cv::Mat image(texture.m_height, texture.m_width, CV_8UC3, (void*)img_data);
+    cv::Mat image(texture.m_height, texture.m_width, CV_MAKETYPE(CV_8U, texture.m_numChannels), (void*)img_data);
 
     detector->detectAndCompute(image, cv::noArray(), keypoints, descriptors);
 }
@@ -0,0 +1,114 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * UVAtlas.hpp
+ *
+ * Segmentation of meshes into charts, flattening of each chart with
+ * least squares conformal maps (LSCM) and packing of all charts into a
+ * single texture atlas.
+ */
+
+#ifndef LVR2_ALGORITHM_UVATLAS_HPP
+#define LVR2_ALGORITHM_UVATLAS_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <cstdint>
+#include <vector>
+
+namespace lvr2
+{
+
+struct UVAtlasOptions
+{
+    /// Maximum angle in degrees between a face normal and the average
+    /// normal of its chart during automatic segmentation
+    float maxChartAngle = 60;
+
+    /// Gap between two charts in the atlas, relative to the atlas size.
+    /// Only used if resolution is 0.
+    float padding = 0.005;
+
+    /// Edge length of an atlas page in texels. If 0, all charts are packed
+    /// into a single unit square and the materials of the input are kept.
+    /// Otherwise, the charts are distributed over as many pages as needed.
+    int resolution = 0;
+
+    /// Size of a texel in world units. Charts that are too large for a
+    /// page are scaled down.
+    float texelSize = 0.01;
+
+    /// Gap between two charts and between charts and the page border in
+    /// texels. Keeps texture filtering from bleeding across seams.
+    int paddingTexels = 4;
+};
+
+/**
+ * @brief Segments the mesh into charts by region growing over adjacent
+ *        faces with similar normals. Limiting the normal deviation keeps
+ *        charts homeomorphic to a disk, so they can be flattened.
+ *
+ * @return The chart index of each face
+ */
+std::vector<uint32_t> segmentCharts(MeshBufferPtr mesh, float maxChartAngle = 60);
+
+/**
+ * @brief Flattens each chart with LSCM and packs all charts into [0, 1]^2.
+ *        The scale of each chart is chosen to preserve its surface area,
+ *        so the texel density is uniform across the atlas. Charts that can
+ *        not be flattened fall back to a projection onto their average plane.
+ *
+ *        Vertices that are shared between charts are duplicated along the
+ *        seams. Vertex normals and vertex colors are kept, the chart of each
+ *        face is stored in the "face_charts" channel.
+ *
+ *        If options.resolution is set, each atlas page becomes a material
+ *        with an empty texture of the given resolution and the face material
+ *        indices refer to the pages. Otherwise, the face material indices of
+ *        the input are kept.
+ *
+ * @param mesh          The mesh to parameterize
+ * @param faceCharts    Chart index of each face, e.g. from segmentCharts()
+ *                      or the "face_regions" of a reconstruction
+ * @param options       Packing parameters
+ *
+ * @return A new mesh with texture coordinates
+ */
+MeshBufferPtr parameterizeCharts(
+    MeshBufferPtr mesh,
+    const std::vector<uint32_t>& faceCharts,
+    const UVAtlasOptions& options = UVAtlasOptions()
+);
+
+/// Segments the mesh with segmentCharts() and parameterizes the result
+MeshBufferPtr parameterize(MeshBufferPtr mesh, const UVAtlasOptions& options = UVAtlasOptions());
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_UVATLAS_HPP
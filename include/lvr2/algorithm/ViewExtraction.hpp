@@ -0,0 +1,91 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * ViewExtraction.hpp
+ *
+ * Extraction of the parts of point clouds and meshes that are visible from
+ * a camera, e.g. to limit rendering or texture baking to a single view.
+ */
+
+#ifndef LVR2_ALGORITHM_VIEWEXTRACTION_HPP
+#define LVR2_ALGORITHM_VIEWEXTRACTION_HPP
+
+#include "lvr2/geometry/Frustum.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Returns the indices of the points inside of the frustum
+ */
+std::vector<size_t> pointsInFrustum(PointBufferPtr points, const Frustum& frustum);
+
+/**
+ * @brief Returns the indices of the faces that intersect the frustum. Faces
+ *        close to the corners of the frustum may be included although they
+ *        are outside.
+ */
+std::vector<size_t> facesInFrustum(MeshBufferPtr mesh, const Frustum& frustum);
+
+/**
+ * @brief Returns the faces in the frustum that are not occluded. A face is
+ *        visible if the ray from the camera to its centroid hits no other
+ *        face that is closer than the centroid by more than the tolerance.
+ *
+ * @param mesh      The mesh
+ * @param frustum   The view frustum
+ * @param eye       Position of the camera
+ * @param tolerance Maximum distance by which the first hit may be closer than the centroid
+ */
+std::vector<size_t> visibleFaces(MeshBufferPtr mesh, const Frustum& frustum, const Vector3f& eye, float tolerance = 1e-3f);
+
+/**
+ * @brief Copies the points inside of the frustum with all channels
+ */
+PointBufferPtr extractFrustum(PointBufferPtr points, const Frustum& frustum);
+
+/**
+ * @brief Copies the faces that intersect the frustum, see facesInFrustum()
+ *        and extractFaces() for the preserved attributes
+ */
+MeshBufferPtr extractFrustum(MeshBufferPtr mesh, const Frustum& frustum);
+
+/**
+ * @brief Copies the faces that are visible from the camera, see visibleFaces()
+ */
+MeshBufferPtr extractVisible(MeshBufferPtr mesh, const Frustum& frustum, const Vector3f& eye, float tolerance = 1e-3f);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_VIEWEXTRACTION_HPP
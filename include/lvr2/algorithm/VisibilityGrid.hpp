@@ -0,0 +1,99 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * VisibilityGrid.hpp
+ *
+ * A sparse voxel grid that records which of several registered scans
+ * observed a voxel as free space (a ray passed through it) or as occupied
+ * (a point was measured in it). Used to find visibility conflicts between
+ * scans, see detectReflections() and detectDynamicPoints().
+ */
+
+#ifndef LVR2_ALGORITHM_VISIBILITYGRID_HPP
+#define LVR2_ALGORITHM_VISIBILITYGRID_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <cstdint>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+class VisibilityGrid
+{
+public:
+    /// Maximum number of scans that can be added
+    static constexpr size_t MaxScans = 64;
+
+    /**
+     * @param voxelSize Edge length of the voxels
+     * @param margin    Rays are traced up to this distance before their
+     *                  measured point, so that noise on real surfaces does
+     *                  not carve them
+     */
+    VisibilityGrid(double voxelSize, double margin);
+
+    /**
+     * @brief Traces the rays from the scanner position to all points of the
+     *        scan. The scan gets the index numScans().
+     *
+     * @return false, if MaxScans scans were already added
+     */
+    bool addScan(PointBufferPtr scan, const Vector3d& origin);
+
+    size_t numScans() const { return m_numScans; }
+
+    /// The voxel that contains the given point
+    Vector3i voxel(const Vector3d& p) const;
+
+    /// Bit i is set if scan i traversed the voxel
+    uint64_t freeMask(const Vector3i& voxel) const;
+
+    /// Bit i is set if scan i measured a point in the voxel
+    uint64_t occupiedMask(const Vector3i& voxel) const;
+
+    /// Bit mask of the given scan or 0 if the index exceeds MaxScans
+    static uint64_t scanBit(size_t scan) { return scan < MaxScans ? uint64_t(1) << scan : 0; }
+
+    /// Number of scans in a mask
+    static size_t count(uint64_t mask);
+
+private:
+    double m_voxelSize;
+    double m_margin;
+    size_t m_numScans = 0;
+    std::unordered_map<Vector3i, uint64_t> m_free;
+    std::unordered_map<Vector3i, uint64_t> m_occupied;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_VISIBILITYGRID_HPP
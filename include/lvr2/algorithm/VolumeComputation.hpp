@@ -0,0 +1,129 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * VolumeComputation.hpp
+ *
+ * Cut and fill volumes and cross section areas between two terrain
+ * surfaces, e.g. the terrain before and after earthworks or a stockpile
+ * and its base plane.
+ */
+
+#ifndef LVR2_ALGORITHM_VOLUMECOMPUTATION_HPP
+#define LVR2_ALGORITHM_VOLUMECOMPUTATION_HPP
+
+#include "lvr2/algorithm/HeightField.hpp"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/Plane.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct VolumeResult
+{
+    /// Signed volume of each cell: positive for fill (the surface is above
+    /// the reference), negative for cut. Cells that are not covered by both
+    /// surfaces are set to noData. Can be written with saveEsriAscii().
+    HeightGrid cellVolumes;
+
+    /// Total volume where the surface is above resp. below the reference
+    double fillVolume = 0;
+    double cutVolume = 0;
+
+    /// fillVolume - cutVolume
+    double netVolume = 0;
+
+    /// Area of the cells with fill resp. cut
+    double fillArea = 0;
+    double cutArea = 0;
+
+    /// Area covered by both surfaces
+    double coveredArea = 0;
+};
+
+/**
+ * @brief Computes the cut and fill volumes between two 2.5D meshes. Both
+ *        are rasterized into a common grid covering the reference mesh.
+ *
+ * @param reference The reference surface, e.g. the original terrain
+ * @param surface   The surface to compare, e.g. the current terrain
+ * @param cellSize  Edge length of a grid cell
+ * @param tolerance Height differences below this value are ignored
+ */
+VolumeResult computeVolumes(MeshBufferPtr reference, MeshBufferPtr surface, double cellSize, double tolerance = 0);
+
+/**
+ * @brief Computes the cut and fill volumes between a 2.5D mesh and a
+ *        (non vertical) reference plane within the extent of the mesh.
+ */
+VolumeResult computeVolumes(
+    const Plane<BaseVector<float>>& reference,
+    MeshBufferPtr surface,
+    double cellSize,
+    double tolerance = 0
+);
+
+struct CrossSection
+{
+    /// Distances of the samples from the start of the section line
+    std::vector<double> stations;
+
+    /// Heights of both surfaces at the stations. NaN where not covered.
+    std::vector<double> referenceHeights;
+    std::vector<double> surfaceHeights;
+
+    /// Area between the profiles where the surface is above resp. below the reference
+    double fillArea = 0;
+    double cutArea = 0;
+};
+
+/**
+ * @brief Samples both height grids along the line from start to end (xy)
+ *        and integrates the area between the two profiles.
+ *
+ * @param reference Height grid of the reference surface
+ * @param surface   Height grid of the compared surface
+ * @param start     Start of the section line
+ * @param end       End of the section line
+ * @param spacing   Distance between samples, the cell size if 0
+ */
+CrossSection crossSection(
+    const HeightGrid& reference,
+    const HeightGrid& surface,
+    const Vector2d& start,
+    const Vector2d& end,
+    double spacing = 0
+);
+
+} // namespace lvr2
+
+#endif // LVR2_ALGORITHM_VOLUMECOMPUTATION_HPP
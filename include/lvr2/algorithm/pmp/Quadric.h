@@ -3,6 +3,8 @@
 
 #pragma once
 
+#include <cmath>
+
 #include "lvr2/geometry/pmp/Types.h"
 
 namespace pmp {
@@ -72,6 +74,21 @@ public: // clang-format off
             +  j_;
     }
 
+    //! \brief compute the position that minimizes the quadric error
+    //! \return false if the quadric is singular, \p p is not modified then
+    bool optimize(Point& p) const
+    {
+        Eigen::Matrix3d A;
+        A << a_, b_, c_,
+             b_, e_, f_,
+             c_, f_, h_;
+        if (std::abs(A.determinant()) < 1e-12)
+            return false;
+        Eigen::Vector3d x = A.inverse() * Eigen::Vector3d(-d_, -g_, -i_);
+        p = Point(x[0], x[1], x[2]);
+        return true;
+    }
+
 private:
 
     double a_, b_, c_, d_,
@@ -13,6 +13,18 @@
 
 namespace pmp {
 
+//! \brief Position of the remaining vertex after an edge collapse
+enum class VertexPlacement
+{
+    //! keep the position of the remaining vertex (fastest)
+    Endpoint,
+    //! move the vertex to the midpoint of the collapsed edge
+    Midpoint,
+    //! move the vertex to the position minimizing the quadric error,
+    //! falls back to the midpoint if the quadric is singular
+    Optimal
+};
+
 //! \brief Surface mesh simplification based on approximation error and fairness
 //! criteria.
 //! \details Performs incremental greedy mesh simplification based on halfedge
@@ -31,10 +43,23 @@ public:
     ~SurfaceSimplification();
 
     //! Initialize with given parameters.
+    //! \details Edges between faces with different values of the face
+    //! properties "f:region" or "f:material" and texture seams of the
+    //! halfedge property "h:tex" are preserved, i.e. no vertex is moved
+    //! across or off these edges.
     void initialize(Scalar aspect_ratio = 0.0, Scalar edge_length = 0.0,
                     unsigned int max_valence = 0, Scalar normal_deviation = 0.0,
                     Scalar hausdorff_error = 0.0);
 
+    //! Set the position of the remaining vertex of each collapse.
+    void set_placement(VertexPlacement placement) { placement_ = placement; }
+
+    //! \brief Set the maximum geometric error of a collapse.
+    //! \details Collapses whose quadric error (the sum of squared distances
+    //! to the planes of the original faces) exceeds \p max_error squared are
+    //! rejected. 0 disables the bound.
+    void set_max_error(Scalar max_error) { max_error_ = max_error; }
+
     //! Simplify mesh to \p n_vertices. Returns true if \p n_vertices is reached.
     bool simplify(unsigned int n_vertices);
 
@@ -104,6 +129,9 @@ private:
     // what is the priority of collapsing the halfedge h
     float priority(const CollapseData& cd);
 
+    // position of the remaining vertex after collapsing the halfedge h
+    Point target_position(const CollapseData& cd) const;
+
     // postprocess halfedge collapse
     void postprocess_collapse(const CollapseData& cd);
 
@@ -113,6 +141,10 @@ private:
     // compute distance from point p to triangle f
     Scalar distance(Face f, const Point& p) const;
 
+    // mark edges between faces with different labels or materials and
+    // texture seams as constraints
+    void initialize_constraints();
+
     SurfaceMesh& mesh_;
 
     bool initialized_;
@@ -131,16 +163,21 @@ private:
     VertexProperty<bool> vselected_;
     VertexProperty<bool> vfeature_;
     EdgeProperty<bool> efeature_;
+    VertexProperty<bool> vconstraint_;
+    EdgeProperty<bool> econstraint_;
 
     PriorityQueue* queue_;
 
     bool has_selection_;
     bool has_features_;
+    bool has_constraints_;
     Scalar normal_deviation_;
     Scalar hausdorff_error_;
     Scalar aspect_ratio_;
     Scalar edge_length_;
     unsigned int max_valence_;
+    VertexPlacement placement_;
+    Scalar max_error_;
 };
 
 } // namespace pmp
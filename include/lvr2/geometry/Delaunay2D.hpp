@@ -0,0 +1,115 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Delaunay2D.hpp
+ *
+ * Delaunay triangulation and Voronoi diagram of 2D points, e.g. of points
+ * projected onto a plane or the xy plane.
+ */
+
+#ifndef LVR2_GEOMETRY_DELAUNAY2D_HPP
+#define LVR2_GEOMETRY_DELAUNAY2D_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+
+#include <array>
+#include <memory>
+#include <utility>
+#include <vector>
+
+namespace cv
+{
+class Subdiv2D;
+}
+
+namespace lvr2
+{
+
+struct VoronoiCell
+{
+    /// Index of the point the cell belongs to
+    unsigned int site;
+
+    /// Counter clockwise vertices of the cell
+    std::vector<Vector2d> polygon;
+};
+
+/**
+ * @brief Delaunay triangulation of a set of 2D points.
+ *
+ *        Duplicate points are only inserted once, all triangles reference
+ *        the first occurrence. The triangulation is computed relative to
+ *        the centroid of the points, so large (e.g. UTM) coordinates are
+ *        supported.
+ */
+class Delaunay2D
+{
+public:
+    explicit Delaunay2D(const std::vector<Vector2d>& points);
+
+    ~Delaunay2D();
+
+    /// The triangulated points
+    const std::vector<Vector2d>& points() const { return m_points; }
+
+    /// Counter clockwise triangles as triples of point indices
+    const std::vector<std::array<unsigned int, 3>>& triangles() const { return m_triangles; }
+
+    /// All undirected edges of the triangulation with first < second
+    std::vector<std::pair<unsigned int, unsigned int>> edges() const;
+
+    /// Circumradius of the given triangle
+    double circumradius(const std::array<unsigned int, 3>& triangle) const;
+
+    /**
+     * @brief Computes the Voronoi diagram of the points. Cells of points
+     *        on the convex hull are unbounded and clipped to the given
+     *        rectangle.
+     *
+     * @param min   Lower left corner of the clipping rectangle
+     * @param max   Upper right corner of the clipping rectangle
+     */
+    std::vector<VoronoiCell> voronoiCells(const Vector2d& min, const Vector2d& max) const;
+
+private:
+    std::vector<Vector2d> m_points;
+    std::vector<std::array<unsigned int, 3>> m_triangles;
+
+    /// Offset subtracted from all points before triangulation
+    Vector2d m_centroid;
+
+    /// Maps the subdivision vertex ids to point indices
+    std::vector<int> m_vertexToPoint;
+
+    std::unique_ptr<cv::Subdiv2D> m_subdiv;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_GEOMETRY_DELAUNAY2D_HPP
@@ -0,0 +1,121 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * Frustum.hpp
+ *
+ * View frustum of a camera, bounded by six planes, for culling points,
+ * faces and bounding boxes that are not visible from a camera pose.
+ */
+
+#ifndef LVR2_GEOMETRY_FRUSTUM_HPP
+#define LVR2_GEOMETRY_FRUSTUM_HPP
+
+#include "lvr2/types/CameraModels.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+
+#include <array>
+
+namespace lvr2
+{
+
+/**
+ * @brief A convex volume bounded by six planes (left, right, bottom, top,
+ *        near, far). Each plane is stored as (n, d) with an inward facing
+ *        unit normal n, i.e. n.dot(p) + d >= 0 for points p inside.
+ */
+class Frustum
+{
+public:
+    enum Side
+    {
+        Left = 0,
+        Right,
+        Bottom,
+        Top,
+        Near,
+        Far
+    };
+
+    enum class Containment
+    {
+        Outside,
+        Intersecting,
+        Inside
+    };
+
+    /// Creates a frustum from the given planes, which are normalized
+    explicit Frustum(const std::array<Vector4f, 6>& planes);
+
+    /**
+     * @brief Creates the frustum of a pinhole camera. The camera looks along
+     *        its z axis, x points to the right and y down in the image.
+     *
+     * @param model         Intrinsics including the image size
+     * @param cameraToWorld Pose of the camera
+     * @param nearDistance  Distance of the near plane along the viewing direction
+     * @param farDistance   Distance of the far plane along the viewing direction
+     */
+    static Frustum fromPinhole(
+        const PinholeModel& model,
+        const Transformd& cameraToWorld,
+        float nearDistance,
+        float farDistance
+    );
+
+    /**
+     * @brief Extracts the frustum from an OpenGL style view projection matrix,
+     *        i.e. the volume that is mapped to [-1, 1]^3 in clip space.
+     */
+    static Frustum fromViewProjection(const Matrix4f& viewProjection);
+
+    /// True, if the point is inside of or on the frustum
+    bool contains(const Vector3f& point) const;
+
+    /// Tests an axis aligned box against the planes. Boxes close to the corners
+    /// of the frustum may be reported as intersecting although they are outside.
+    Containment classify(const Vector3f& min, const Vector3f& max) const;
+
+    /// Conservative triangle test: false only if all corners are outside of one plane
+    bool intersects(const Vector3f& a, const Vector3f& b, const Vector3f& c) const;
+
+    /// The plane of the given side
+    const Vector4f& plane(Side side) const { return m_planes[side]; }
+
+    /// Corners of the near plane followed by the corners of the far plane,
+    /// each in the order (left, bottom), (right, bottom), (right, top), (left, top)
+    std::array<Vector3f, 8> corners() const;
+
+private:
+    std::array<Vector4f, 6> m_planes;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_GEOMETRY_FRUSTUM_HPP
@@ -136,6 +136,18 @@ struct Normal : public BaseVector<CoordType>
 
 };
 
+/**
+ * @brief Normalizes n vectors that are stored as consecutive (x, y, z)
+ *        tuples in place, e.g. the normals of a PointBuffer.
+ *
+ * Vectors with length zero or non-finite coordinates can not be normalized
+ * and are left unchanged.
+ *
+ * @return The number of vectors that could not be normalized
+ */
+template<typename CoordType>
+size_t normalizeArray(CoordType* data, size_t n);
+
 template<typename CoordType>
 inline std::ostream& operator<<(std::ostream& os, const Normal<CoordType>& n)
 {
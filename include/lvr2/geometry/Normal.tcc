@@ -35,6 +35,8 @@
 
 #include "lvr2/util/Panic.hpp"
 
+#include <cmath>
+
 
 namespace lvr2
 {
@@ -109,4 +111,24 @@ Normal<CoordType> Normal<CoordType>::operator-(const T& other) const
 }
 
 
+template<typename CoordType>
+size_t normalizeArray(CoordType* data, size_t n)
+{
+    size_t invalid = 0;
+    for (size_t i = 0; i < n; i++)
+    {
+        CoordType* v = data + 3 * i;
+        const CoordType length = std::sqrt(v[0] * v[0] + v[1] * v[1] + v[2] * v[2]);
+        if (!std::isfinite(length) || length == 0)
+        {
+            invalid++;
+            continue;
+        }
+        v[0] /= length;
+        v[1] /= length;
+        v[2] /= length;
+    }
+    return invalid;
+}
+
 } // namespace lvr2
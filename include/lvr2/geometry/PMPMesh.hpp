@@ -37,6 +37,7 @@
 
 #include "lvr2/geometry/BaseMesh.hpp"
 #include "lvr2/geometry/pmp/SurfaceMesh.h"
+#include "lvr2/algorithm/pmp/SurfaceSimplification.h"
 
 #include "lvr2/types/MeshBuffer.hpp"
 
@@ -251,10 +252,21 @@ public:
     std::pair<BaseVecT, float> triCircumCenter(FaceHandle faceH);
     /**
      * @brief Decimates the Mesh with repeated Edge Collapses until the target number of vertices is reached
+     *
+     * Edges between faces with different material indices or region labels (face
+     * properties "f:material" and "f:region", created from the "face_material_indices"
+     * and "face_regions" channels of a MeshBuffer) are not collapsed across, so
+     * segmentation boundaries are preserved.
      * 
      * @param targetNumVertices the target number of vertices
+     * @param placement position of the remaining vertex of each collapse
+     * @param maxError maximum geometric error of a collapse. 0 disables the bound.
      */
-    void simplify(size_t targetNumVertices);
+    void simplify(
+        size_t targetNumVertices,
+        pmp::VertexPlacement placement = pmp::VertexPlacement::Endpoint,
+        float maxError = 0.0f
+    );
 
     /**
      * @brief Gets rid of deleted vertices, edges and faces.
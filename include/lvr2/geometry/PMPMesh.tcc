@@ -167,6 +167,20 @@ PMPMesh<BaseVecT>::PMPMesh(MeshBufferPtr ptr)
         }
     }
 
+    // Region labels, e.g. of SimpleFinalizer, are kept by simplify()
+    if (IndexChannelOptional regions = ptr->getIndexChannel("face_regions"))
+    {
+        auto dest_regions = m_mesh.face_property<pmp::IndexType>("f:region");
+        for (size_t i = 0; i < m_mesh.faces_size() && i < regions->numElements(); i++)
+        {
+            pmp::Face fH(i);
+            if (!m_mesh.is_deleted(fH))
+            {
+                dest_regions[fH] = (*regions)[i][0];
+            }
+        }
+    }
+
     auto& textures = ptr->getTextures();
     if (textures.size() == 1)
     {
@@ -278,6 +292,14 @@ MeshBufferPtr PMPMesh<BaseVecT>::toMeshBuffer_const() const
         buffer->setFaceColors(face_colors, w);
     }
 
+    auto src_face_region = m_mesh.get_face_property<pmp::IndexType>("f:region");
+    if (src_face_region)
+    {
+        indexArray regions(new unsigned int[num_faces]);
+        std::copy_n(src_face_region.data(), num_faces, regions.get());
+        buffer->addIndexChannel(regions, "face_regions", num_faces, 1);
+    }
+
     auto texture = getTexture();
     if (texture)
     {
@@ -793,9 +815,11 @@ std::pair<BaseVecT, float> PMPMesh<BaseVecT>::triCircumCenter(FaceHandle faceH)
 }
 
 template<typename BaseVecT>
-void PMPMesh<BaseVecT>::simplify(size_t targetNumVertices)
+void PMPMesh<BaseVecT>::simplify(size_t targetNumVertices, pmp::VertexPlacement placement, float maxError)
 {
     pmp::SurfaceSimplification simplification(m_mesh);
+    simplification.set_placement(placement);
+    simplification.set_max_error(maxError);
     simplification.simplify(targetNumVertices);
 }
 
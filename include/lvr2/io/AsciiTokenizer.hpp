@@ -0,0 +1,216 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * AsciiTokenizer.hpp
+ *
+ * Locale independent splitting and number parsing for line based ASCII
+ * point cloud formats.
+ */
+
+#ifndef LVR2_IO_ASCIITOKENIZER_HPP
+#define LVR2_IO_ASCIITOKENIZER_HPP
+
+#include <ios>
+#include <istream>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Thrown if a line of an ASCII file can not be parsed. Carries the
+ *        location of the offending token, so that a bad record can be found
+ *        in large files. what() has the form
+ *
+ *        [AsciiTokenizer] file:line:column: reason (line starts at byte offset)
+ *            excerpt of the line
+ *                    ^
+ */
+class AsciiParseError : public std::runtime_error
+{
+public:
+    /**
+     * @param file      Name of the file or stream, may be empty
+     * @param line      One based line number
+     * @param column    One based character column of the token, 0 if the
+     *                  error concerns the whole line
+     * @param token     The offending token, empty if it is missing
+     * @param offset    Byte offset of the beginning of the line, -1 if unknown
+     * @param reason    Description of the error
+     * @param excerpt   The offending line, shortened to the region around the column
+     * @param marker    Position of the column within the excerpt
+     */
+    AsciiParseError(
+        const std::string& file,
+        size_t line,
+        size_t column,
+        const std::string& token,
+        std::streamoff offset,
+        const std::string& reason,
+        const std::string& excerpt = "",
+        size_t marker = 0);
+
+    /// Name of the file or stream the line was read from
+    const std::string& file() const { return m_file; }
+
+    /// The (one based) number of the offending line
+    size_t line() const { return m_line; }
+
+    /// One based character column of the offending token, 0 for the whole line
+    size_t column() const { return m_column; }
+
+    /// The offending token, empty if a token is missing
+    const std::string& token() const { return m_token; }
+
+    /// Byte offset of the beginning of the line in the file, -1 if unknown
+    std::streamoff offset() const { return m_offset; }
+
+    /// Description of the error without the location
+    const std::string& reason() const { return m_reason; }
+
+private:
+    std::string     m_file;
+    size_t          m_line;
+    size_t          m_column;
+    std::string     m_token;
+    std::streamoff  m_offset;
+    std::string     m_reason;
+};
+
+/**
+ * @brief Splits the lines of an ASCII stream into tokens and parses numbers
+ *        independent of the current C locale.
+ *
+ *        Tokens are separated by spaces, tabs and semicolons. Commas are
+ *        either field separators ("1.5,2,3") or decimal separators
+ *        ("1,5 2 3"), which is decided once per stream from the first
+ *        line that contains a comma. Windows line endings, a UTF-8 byte
+ *        order mark, empty lines and comment lines starting with '#' or
+ *        "//" are skipped.
+ */
+class AsciiTokenizer
+{
+public:
+    /**
+     * @param in            The stream to read from
+     * @param source        Name of the stream used in error messages, e.g. the file name
+     * @param lineNumber    Number of lines that were already consumed from the
+     *                      stream, e.g. when continuing at a saved position
+     */
+    AsciiTokenizer(std::istream& in, const std::string& source = "", size_t lineNumber = 0);
+
+    /**
+     * @brief Reads the next line that contains at least one token.
+     *        Returns false at the end of the stream.
+     */
+    bool next();
+
+    /// Number of tokens in the current line
+    size_t size() const { return m_tokens.size(); }
+
+    /// The i-th token of the current line
+    const std::string& token(size_t i) const;
+
+    /// The (one based) number of the current line
+    size_t lineNumber() const { return m_lineNumber; }
+
+    /// Stream position of the beginning of the current line
+    std::streampos lineStart() const { return m_lineStart; }
+
+    /**
+     * @brief Parses the i-th token as a floating point number. Decimal
+     *        points, exponents ("1e-5", "2.E+3"), "inf" and "nan" are
+     *        supported. Values that are too small to be represented are
+     *        rounded to zero.
+     *
+     * @throws AsciiParseError if the token is missing or not a number
+     */
+    double getDouble(size_t i) const;
+
+    /**
+     * @brief Like getDouble(), but also throws if the value exceeds the
+     *        range of a float
+     */
+    float getFloat(size_t i) const;
+
+    /**
+     * @brief Parses the i-th token as a decimal integer
+     *
+     * @throws AsciiParseError if the token is missing, not an integer or
+     *         out of range
+     */
+    long getInt(size_t i) const;
+
+    /**
+     * @brief Parses the i-th token like getDouble(), but returns false
+     *        instead of throwing
+     */
+    bool tryDouble(size_t i, double& value) const;
+
+    /**
+     * @brief Throws an AsciiParseError for the current line
+     */
+    [[noreturn]] void error(const std::string& msg) const;
+
+    /**
+     * @brief Throws an AsciiParseError for the i-th token of the current line.
+     *        If the token is missing, the error points to the end of the line.
+     */
+    [[noreturn]] void error(size_t i, const std::string& msg) const;
+
+private:
+    enum class CommaMode
+    {
+        Unknown,
+        Separator,
+        Decimal
+    };
+
+    void split();
+
+    std::istream&               m_in;
+    std::string                 m_source;
+    std::string                 m_line;
+    std::vector<std::string>    m_tokens;
+
+    /// Character offset of each token in m_line
+    std::vector<size_t>         m_tokenOffsets;
+    size_t                      m_lineNumber;
+    std::streampos              m_lineStart;
+    CommaMode                   m_commaMode = CommaMode::Unknown;
+
+    /// Decimal separator of the current C locale, used by strtod
+    char                        m_decimalPoint;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_IO_ASCIITOKENIZER_HPP
@@ -0,0 +1,102 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * CityJsonIO.hpp
+ *
+ * Export of reconstructed building meshes to CityJSON with semantic
+ * surfaces (roof, wall and ground) and a level of detail tag.
+ */
+
+#ifndef LVR2_IO_CITYJSONIO_HPP
+#define LVR2_IO_CITYJSONIO_HPP
+
+#include "lvr2/types/GeoMetadata.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+#include <boost/optional.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+enum class BuildingSurface
+{
+    Roof,
+    Wall,
+    Ground
+};
+
+struct CityJsonOptions
+{
+    /// Level of detail written to each geometry, e.g. "1.2", "2.2" or "3"
+    std::string lod = "2.2";
+
+    /// Faces (or regions) within this angle of the vertical are walls, in degrees
+    double maxWallTilt = 15;
+
+    /// Face label channel. If present, all faces of a region get the label
+    /// of the region's area weighted normal, see areaWeightedFaceNormals().
+    std::string regionChannel = "face_regions";
+
+    /// Quantization of the vertex coordinates
+    double scale = 0.001;
+
+    /// Geo reference. The offset is added to all coordinates and the EPSG
+    /// code is written as reference system.
+    boost::optional<GeoMetadata> geo;
+};
+
+/**
+ * @brief Labels each face of the mesh as roof (facing upwards), ground
+ *        (facing downwards) or wall (vertical within options.maxWallTilt),
+ *        see classifySurfaceNormal(). Degenerate faces are walls.
+ */
+std::vector<BuildingSurface> classifyBuildingSurfaces(MeshBufferPtr mesh, const CityJsonOptions& options = CityJsonOptions());
+
+/**
+ * @brief Writes the meshes as CityJSON (version 1.1) file with one Building
+ *        object with a semantic MultiSurface per mesh.
+ *
+ * @param buildings The building meshes
+ * @param filename  Output file
+ * @param options   Export parameters
+ * @return true on success
+ */
+bool saveCityJSON(
+    const std::vector<MeshBufferPtr>& buildings,
+    const boost::filesystem::path& filename,
+    const CityJsonOptions& options = CityJsonOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_CITYJSONIO_HPP
@@ -0,0 +1,90 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * DemIO.hpp
+ *
+ * Export of height grids (digital elevation models) to raster formats
+ * that can be used in GIS applications.
+ */
+
+#ifndef LVR2_IO_DEMIO_HPP
+#define LVR2_IO_DEMIO_HPP
+
+#include "lvr2/algorithm/HeightField.hpp"
+#include "lvr2/types/GeoMetadata.hpp"
+
+#include <boost/filesystem.hpp>
+#include <boost/optional.hpp>
+
+namespace lvr2
+{
+
+/**
+ * @brief Writes the grid as ESRI ASCII grid (.asc). If a geo reference
+ *        with an EPSG code is given, a .prj file containing the
+ *        WKT of the reference system is written next to it.
+ *
+ * @param grid      The height grid
+ * @param filename  Output file
+ * @param geo       If set, the offset is added to the grid origin and heights
+ * @return true on success
+ */
+bool saveEsriAscii(
+    const HeightGrid& grid,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo = boost::none
+);
+
+/**
+ * @brief Writes the grid as single band Float32 GeoTIFF using GDAL,
+ *        including geo transform, no data value and reference system.
+ *
+ * @param grid      The height grid
+ * @param filename  Output file
+ * @param geo       If set, the offset is added to the grid origin and heights
+ * @return true on success
+ */
+bool saveGeoTIFFDem(
+    const HeightGrid& grid,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo = boost::none
+);
+
+/**
+ * @brief Selects the format by extension: ".asc" or ".tif" / ".tiff"
+ */
+bool saveDem(
+    const HeightGrid& grid,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo = boost::none
+);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_DEMIO_HPP
@@ -0,0 +1,157 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * DepthImageIO.hpp
+ *
+ * Conversion of depth images of RGB-D cameras into point clouds.
+ */
+
+#ifndef LVR2_IO_DEPTHIMAGEIO_HPP
+#define LVR2_IO_DEPTHIMAGEIO_HPP
+
+#include "lvr2/types/CameraModels.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+#include <opencv2/core.hpp>
+
+namespace lvr2
+{
+
+/// Interpolation of the depth values of filled holes, see fillDepthHoles()
+enum class DepthFillMethod
+{
+    /// Median of the valid neighbors
+    Median,
+    /// Mean of the valid neighbors weighted by their distance and the
+    /// difference of their depth to the median
+    Bilateral
+};
+
+struct DepthImageOptions
+{
+    /// Factor that converts the values of 16 bit depth images to metric depth,
+    /// e.g. 0.001 for millimeters. Float images are expected in metric units.
+    float depthScale = 0.001f;
+
+    /// Pixels with a depth outside of [minDepth, maxDepth] are invalid. maxDepth is disabled if <= 0.
+    float minDepth = 0.0f;
+    float maxDepth = 0.0f;
+
+    /// If true, the buffer contains one point per pixel in row major order and
+    /// invalid pixels are NaN. Otherwise invalid pixels are skipped.
+    bool organized = true;
+
+    /// Fill connected regions of invalid pixels with at most this many pixels
+    /// before the conversion, see fillDepthHoles(). Disabled if 0.
+    int maxHoleSize = 0;
+
+    /// Interpolation of the filled depth values
+    DepthFillMethod fillMethod = DepthFillMethod::Median;
+
+    /// Relative depth difference at which the neighbors of a hole are
+    /// considered to lie on different surfaces. Holes at such depth
+    /// discontinuities are not filled.
+    float fillTolerance = 0.05f;
+};
+
+/**
+ * @brief Inpaints small regions of invalid pixels, e.g. sensor dropouts on
+ *        dark or specular surfaces, which would otherwise cause holes in the
+ *        reconstructed surface. Holes are filled from their border inwards
+ *        using the valid pixels in a 5x5 neighborhood. Holes that touch the
+ *        image border or are larger than options.maxHoleSize are kept.
+ *
+ * @param depth     16 bit unsigned or 32 bit float depth image, see fromDepthImage()
+ * @param options   options.depthScale, minDepth and maxDepth define the valid
+ *                  pixels, maxHoleSize, fillMethod and fillTolerance the filling
+ *
+ * @return A 32 bit float image with metric depth. Invalid pixels are zero.
+ */
+cv::Mat fillDepthHoles(const cv::Mat& depth, const DepthImageOptions& options);
+
+/**
+ * @brief Converts a depth image into a point cloud. If options.maxHoleSize is
+ *        set, small holes are filled first, see fillDepthHoles().
+ *
+ * @param depth         16 bit unsigned or 32 bit float single channel depth image.
+ *                      Depth is measured along the optical axis, zero is invalid.
+ * @param intrinsics    Pinhole model of the depth camera. The image is assumed
+ *                      to be undistorted.
+ * @param pose          Camera to world transformation
+ * @param options       Conversion parameters
+ */
+PointBufferPtr fromDepthImage(
+    const cv::Mat& depth,
+    const PinholeModel& intrinsics,
+    const Transformd& pose = Transformd::Identity(),
+    const DepthImageOptions& options = DepthImageOptions()
+);
+
+/**
+ * @brief Converts a depth image into a point cloud and colors the points by
+ *        projecting them into a color image. Points outside of the color
+ *        image are black.
+ *
+ * @param depth             Depth image, see above
+ * @param depthIntrinsics   Pinhole model of the depth camera
+ * @param color             8 bit BGR color image
+ * @param colorIntrinsics   Pinhole model of the color camera. For images that are already
+ *                          registered to the depth image, use the depth intrinsics.
+ * @param depthToColor      Transformation from the depth to the color camera frame
+ * @param pose              Depth camera to world transformation
+ * @param options           Conversion parameters
+ */
+PointBufferPtr fromDepthImage(
+    const cv::Mat& depth,
+    const PinholeModel& depthIntrinsics,
+    const cv::Mat& color,
+    const PinholeModel& colorIntrinsics,
+    const Transformd& depthToColor = Transformd::Identity(),
+    const Transformd& pose = Transformd::Identity(),
+    const DepthImageOptions& options = DepthImageOptions()
+);
+
+/**
+ * @brief Reads a depth image (e.g. a 16 bit PNG) and converts it, see above
+ *
+ * @throws std::runtime_error if the image can not be read
+ */
+PointBufferPtr loadDepthImage(
+    const boost::filesystem::path& file,
+    const PinholeModel& intrinsics,
+    const Transformd& pose = Transformd::Identity(),
+    const DepthImageOptions& options = DepthImageOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_DEPTHIMAGEIO_HPP
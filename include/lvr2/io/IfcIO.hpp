@@ -0,0 +1,107 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IfcIO.hpp
+ *
+ * Export of classified planar regions of a mesh to IFC (STEP physical
+ * file, IFC2X3 coordination view) so that scan-to-BIM results can be
+ * imported into BIM tools.
+ */
+
+#ifndef LVR2_IO_IFCIO_HPP
+#define LVR2_IO_IFCIO_HPP
+
+#include "lvr2/algorithm/SurfaceClassification.hpp"
+#include "lvr2/types/GeoMetadata.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+#include <boost/optional.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/// A planar region of a mesh
+struct ClassifiedSurface
+{
+    SurfaceClass type = SurfaceClass::Other;
+
+    /// The faces of the region
+    MeshBufferPtr mesh;
+
+    /// Label of the region in the source mesh
+    unsigned int region = 0;
+};
+
+/**
+ * @brief Splits the mesh into its regions and classifies each by its
+ *        area weighted normal: Upward facing horizontal regions are
+ *        floors, downward facing ones ceilings and vertical regions walls,
+ *        see classifySurfaceNormal().
+ *
+ * @param mesh          A (retessellated) mesh with a face label channel
+ * @param regionChannel Index channel with one region label per face, e.g.
+ *                      the "face_regions" channel of SimpleFinalizer. If
+ *                      the mesh has no such channel, it is filled by
+ *                      growPlanarRegions() with maxTilt as angle.
+ * @param maxTilt       Maximum deviation of a region from the horizontal
+ *                      or vertical direction in degrees
+ * @param minArea       Smaller regions are skipped
+ */
+std::vector<ClassifiedSurface> classifyPlanarRegions(
+    MeshBufferPtr mesh,
+    const std::string& regionChannel = "face_regions",
+    double maxTilt = 10,
+    double minArea = 0.5
+);
+
+/**
+ * @brief Writes the surfaces as IFC file. Walls are exported as IfcWall,
+ *        floors and ceilings as IfcSlab (FLOOR resp. USERDEFINED with
+ *        object type "Ceiling") in a single building storey. The geometry
+ *        of each element is a surface model of its triangles. Surfaces
+ *        of type Other are skipped.
+ *
+ * @param surfaces  The classified surfaces
+ * @param filename  Output file
+ * @param geo       If set, the offset is added to all coordinates
+ * @return true on success
+ */
+bool saveIFC(
+    const std::vector<ClassifiedSurface>& surfaces,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo = boost::none
+);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_IFCIO_HPP
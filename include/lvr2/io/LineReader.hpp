@@ -62,6 +62,8 @@ struct fileAttribut
     bool m_ply;
     bool m_binary;
     size_t m_line_element_amount;
+    /// Number of lines before m_filePos, used for error messages of ASCII files
+    size_t m_lineNumber;
 };
 
 #if defined(WIN32) || defined(_WIN32) || defined(__WIN32__) || defined(__NT__)
@@ -0,0 +1,113 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * McapIO.hpp
+ *
+ * Extraction of sensor_msgs/PointCloud2 messages from mcap files, the
+ * default storage format of rosbag2. Only available if lvr2 was built with
+ * LVR2_WITH_MCAP (LVR2_USE_MCAP is defined).
+ */
+
+#ifndef LVR2_IO_MCAPIO_HPP
+#define LVR2_IO_MCAPIO_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+struct McapReadOptions
+{
+    /// Topics to read. All PointCloud2 topics are read if empty.
+    std::vector<std::string> topics;
+
+    /// Frame the poses are computed in using the /tf and /tf_static messages
+    /// of the file, e.g. "map" or "odom". No poses are computed if empty.
+    std::string fixedFrame;
+
+    /// Transform the points into the fixed frame
+    bool transformPoints = false;
+
+    /// Only read every n-th point cloud
+    size_t stride = 1;
+
+    /// Maximum number of point clouds. Disabled if 0.
+    size_t maxClouds = 0;
+};
+
+/**
+ * @brief A point cloud message with its time stamp and pose
+ */
+struct TimestampedPointCloud
+{
+    /// Time stamp of the message header in seconds
+    double timestamp = 0;
+
+    std::string topic;
+
+    /// Frame of the points in the message
+    std::string frame;
+
+    /// x, y and z as points, "intensity" as intensities, "rgb" or "rgba" as colors,
+    /// "time", "t" or "timestamp" as absolute per point time stamps in seconds
+    /// (double channel "timestamps") and all other scalar fields as float channels
+    /// with the field name.
+    /// Points with NaN coordinates are removed.
+    PointBufferPtr points;
+
+    /// Transformation from frame to the fixed frame at the time stamp
+    Transformd pose = Transformd::Identity();
+
+    /// False, if no transformation to the fixed frame was found
+    bool hasPose = false;
+};
+
+/**
+ * @brief Reads the PointCloud2 messages (CDR encoded, i.e. ROS 2) of an mcap file.
+ *
+ *        Transformations are interpolated between the messages of /tf,
+ *        transformations of /tf_static are constant.
+ *
+ * @throws std::runtime_error if the file can not be opened
+ */
+std::vector<TimestampedPointCloud> readMcapPointClouds(
+    const boost::filesystem::path& file,
+    const McapReadOptions& options = McapReadOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_MCAPIO_HPP
@@ -0,0 +1,73 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * OccupancyGridIO.hpp
+ *
+ * Export of occupancy grids as binvox voxel files and as 2D maps in the
+ * format of the ROS map_server.
+ */
+
+#ifndef LVR2_IO_OCCUPANCYGRIDIO_HPP
+#define LVR2_IO_OCCUPANCYGRIDIO_HPP
+
+#include "lvr2/algorithm/OccupancyGrid.hpp"
+
+#include <boost/filesystem.hpp>
+
+namespace lvr2
+{
+
+/**
+ * @brief Writes the grid as run length encoded binvox file. The grid is
+ *        padded to a cube, the axes are written unchanged, i.e. z is up
+ *        and not y as assumed by some viewers.
+ *
+ * @return true on success
+ */
+bool saveBinvox(const OccupancyGrid& grid, const boost::filesystem::path& filename);
+
+/**
+ * @brief Writes the projection of the grid (see projectOccupancyGrid()) as
+ *        image and a map_server YAML file with the same name next to it.
+ *
+ * @param grid      The occupancy grid
+ * @param filename  Image file, e.g. "map.pgm"
+ * @param options   Projection parameters
+ * @return true on success
+ */
+bool saveOccupancyMap(
+    const OccupancyGrid& grid,
+    const boost::filesystem::path& filename,
+    const OccupancyMapOptions& options = OccupancyMapOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_OCCUPANCYGRIDIO_HPP
@@ -0,0 +1,94 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * OctreeCompression.hpp
+ *
+ * Compact storage of point clouds: positions are quantized to the leaves
+ * of an octree whose occupancy is stored breadth first with one byte per
+ * inner node. Attribute channels are stored per point in leaf order. The
+ * result is additionally compressed with LZ4.
+ */
+
+#ifndef LVR2_IO_OCTREECOMPRESSION_HPP
+#define LVR2_IO_OCTREECOMPRESSION_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct OctreeCompressionOptions
+{
+    /// Edge length of the octree leaves. Positions are reconstructed at the
+    /// leaf centers, so the maximum error is resolution * sqrt(3) / 2.
+    double resolution = 0.001;
+
+    /// If true, the number of points per leaf is stored so that all points
+    /// and their attributes are restored. Otherwise, only the first point
+    /// of each leaf is kept.
+    bool keepAllPoints = true;
+
+    /// Compress the encoded data with LZ4
+    bool lz4 = true;
+};
+
+/**
+ * @brief Encodes the point cloud. All channels with one entry per point of
+ *        type unsigned char, int, unsigned int, float or double are
+ *        preserved, other channels are skipped with a warning.
+ *
+ * @throws std::invalid_argument if the resolution is not positive
+ */
+std::vector<char> compressPointCloud(PointBufferPtr buffer, const OctreeCompressionOptions& options = OctreeCompressionOptions());
+
+/**
+ * @brief Decodes a point cloud encoded with compressPointCloud(). The points
+ *        are ordered along the octree leaves, not in their original order.
+ *
+ * @throws std::runtime_error if the data is not valid
+ */
+PointBufferPtr decompressPointCloud(const std::vector<char>& data);
+
+/// Writes the result of compressPointCloud() to the given file
+void saveCompressedPointCloud(
+    PointBufferPtr buffer,
+    const boost::filesystem::path& filename,
+    const OctreeCompressionOptions& options = OctreeCompressionOptions()
+);
+
+/// Reads a file written by saveCompressedPointCloud()
+PointBufferPtr loadCompressedPointCloud(const boost::filesystem::path& filename);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_OCTREECOMPRESSION_HPP
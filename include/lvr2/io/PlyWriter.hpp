@@ -0,0 +1,154 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PlyWriter.hpp
+ *
+ * Incremental writing of binary PLY meshes, e.g. for chunked or out of
+ * core reconstructions that never hold the complete mesh in memory.
+ */
+
+#ifndef LVR2_IO_PLYWRITER_HPP
+#define LVR2_IO_PLYWRITER_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <fstream>
+#include <string>
+
+namespace lvr2
+{
+
+/**
+ * @brief Writes a binary PLY mesh that is fed with vertices and faces
+ *        incrementally.
+ *
+ *        Vertices are written to the output file directly, faces to a
+ *        temporary file next to it, since PLY stores all vertices before
+ *        the first face. close() appends the faces and patches the element
+ *        counts in the header, which is written with fixed width
+ *        placeholders. The file can be read with PLYIO.
+ *
+ *        Face indices refer to all vertices that were added so far. When
+ *        appending independent meshes, the offset returned by addVertices()
+ *        or addMesh() has to be added to their indices.
+ */
+class PlyWriter
+{
+public:
+    /**
+     * @brief Creates the file and writes the header
+     *
+     * @param file      The output file
+     * @param normals   Write vertex normals (nx, ny, nz)
+     * @param colors    Write vertex colors (red, green, blue)
+     *
+     * @throws std::runtime_error if the file can not be created
+     */
+    PlyWriter(const boost::filesystem::path& file, bool normals = false, bool colors = false);
+
+    /// Closes the file if close() was not called
+    ~PlyWriter();
+
+    PlyWriter(const PlyWriter&) = delete;
+    PlyWriter& operator=(const PlyWriter&) = delete;
+
+    /**
+     * @brief Appends n vertices
+     *
+     * @param vertices  Three floats per vertex
+     * @param normals   Three floats per vertex. Required if the writer was
+     *                  created with normals, ignored otherwise.
+     * @param colors    Three bytes per vertex. Required if the writer was
+     *                  created with colors, ignored otherwise.
+     * @param n         Number of vertices
+     *
+     * @return The index of the first added vertex
+     */
+    size_t addVertices(const float* vertices, const float* normals, const unsigned char* colors, size_t n);
+
+    /**
+     * @brief Appends n triangles
+     *
+     * @param indices   Three vertex indices per face
+     * @param n         Number of faces
+     * @param offset    Added to all indices
+     *
+     * @throws std::out_of_range if an index does not refer to a vertex that
+     *         was already added. No face of the batch is written in that case.
+     */
+    void addFaces(const unsigned int* indices, size_t n, size_t offset = 0);
+
+    /**
+     * @brief Appends the vertices and faces of the given mesh. Missing
+     *        normals or colors are written as zero and white.
+     *
+     * @return The index of the mesh's first vertex in the output
+     */
+    size_t addMesh(MeshBufferPtr mesh);
+
+    /**
+     * @brief Appends the faces and writes the final element counts. No data
+     *        can be added afterwards. Called by the destructor.
+     *
+     * @throws std::runtime_error if writing fails
+     */
+    void close();
+
+    /// Number of vertices written so far
+    size_t numVertices() const { return m_numVertices; }
+
+    /// Number of faces written so far
+    size_t numFaces() const { return m_numFaces; }
+
+private:
+    void checkOpen() const;
+
+    boost::filesystem::path m_file;
+    boost::filesystem::path m_faceFile;
+
+    std::fstream m_out;
+    std::fstream m_faces;
+
+    /// Positions of the vertex and face count placeholders in the header
+    std::streampos m_vertexCountPos;
+    std::streampos m_faceCountPos;
+
+    bool m_normals;
+    bool m_colors;
+    bool m_closed = false;
+
+    size_t m_numVertices = 0;
+    size_t m_numFaces = 0;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_IO_PLYWRITER_HPP
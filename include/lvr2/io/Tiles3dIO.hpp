@@ -40,9 +40,35 @@
 
 #include <Cesium3DTiles/Tile.h>
 
+#include <boost/optional.hpp>
+
+#include <array>
+
 namespace lvr2
 {
 
+/**
+ * @brief Position of a tileset on the globe
+ */
+struct GeoReference
+{
+    /// WGS84 latitude of the reference point in degrees
+    double latitude = 0.0;
+
+    /// WGS84 longitude of the reference point in degrees
+    double longitude = 0.0;
+
+    /// Height of the reference point above the WGS84 ellipsoid in meters
+    double height = 0.0;
+
+    /// Mesh coordinates of the reference point, e.g. the offset that was
+    /// subtracted from UTM coordinates. Subtracted before placing the mesh.
+    std::array<double, 3> offset = { 0.0, 0.0, 0.0 };
+
+    /// EPSG code of the original coordinate system. Only stored as metadata.
+    int epsg = 0;
+};
+
 template<typename BaseVecT>
 class Tiles3dIO
 {
@@ -66,6 +92,17 @@ public:
      * @param scale scale factor for the meshes
      */
     void write(TreeConstPtr& tree, bool compress = false, float scale = 1.0f);
+
+    /**
+     * @brief Places the tileset on the globe. The x, y and z axes of the mesh
+     *        are mapped to east, north and up at the reference point. The
+     *        reference is also stored in the extras of the tileset.
+     *
+     *        Without a geo reference, the mesh is placed at an arbitrary
+     *        position on the equator.
+     */
+    void setGeoReference(const GeoReference& geoReference) { m_geoReference = geoReference; }
+
     void read(TreePtr& tree)
     {
         throw std::runtime_error("Not implemented yet");
@@ -80,6 +117,8 @@ private:
                     lvr2::Monitor& progress);
 
     std::string m_rootDir;
+
+    boost::optional<GeoReference> m_geoReference;
 };
 
 } // namespace lvr2
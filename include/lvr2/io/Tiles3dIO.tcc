@@ -51,7 +51,8 @@ namespace Tiles3dIO_internal
 
 void convertBoundingBox(const pmp::BoundingBox& in, Cesium3DTiles::BoundingVolume& out);
 void indexToName(int i, std::string& name, size_t max);
-void writeTileset(Cesium3DTiles::Tileset& tileset, const std::string& outputDir, float scale);
+void writeTileset(Cesium3DTiles::Tileset& tileset, const std::string& outputDir, float scale,
+                  const boost::optional<GeoReference>& geoReference = boost::none);
 
 }
 
@@ -85,7 +86,7 @@ void Tiles3dIO<BaseVecT>::write(TreeConstPtr& tree, bool compress, float scale)
     writeTiles(tileset.root, tree, compress, m_rootDir, "tiles/s", progress);
     progress.terminate();
 
-    Tiles3dIO_internal::writeTileset(tileset, m_rootDir, scale);
+    Tiles3dIO_internal::writeTileset(tileset, m_rootDir, scale, m_geoReference);
 }
 
 template<typename BaseVecT>
@@ -0,0 +1,68 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * TrajectoryIO.hpp
+ *
+ * Reading and writing of sensor trajectories in ASCII format.
+ */
+
+#ifndef LVR2_IO_TRAJECTORYIO_HPP
+#define LVR2_IO_TRAJECTORYIO_HPP
+
+#include "lvr2/types/Trajectory.hpp"
+
+#include <boost/filesystem.hpp>
+
+namespace lvr2
+{
+
+/**
+ * @brief Loads a trajectory with one pose per line. Two formats are
+ *        detected by the number of values per line:
+ *
+ *        8 values:  time x y z qx qy qz qw (TUM format)
+ *        13 values: time followed by the first three rows of the pose
+ *                   matrix in row major order
+ *
+ *        Values may be separated by spaces, tabs or commas. Empty lines and
+ *        lines starting with '#' are skipped.
+ *
+ * @throws std::runtime_error if the file can not be opened or a line can
+ *         not be parsed
+ */
+Trajectory loadTrajectory(const boost::filesystem::path& file);
+
+/**
+ * @brief Saves the trajectory in TUM format, see loadTrajectory()
+ */
+void saveTrajectory(const boost::filesystem::path& file, const Trajectory& trajectory);
+
+} // namespace lvr2
+
+#endif // LVR2_IO_TRAJECTORYIO_HPP
@@ -6,6 +6,9 @@
 #include "lvr2/io/meshio/ClusterIO.hpp"
 #include "lvr2/io/meshio/FaceIO.hpp"
 
+#include <map>
+#include <string>
+
 namespace lvr2
 {
 namespace meshio
@@ -15,9 +18,19 @@ template <typename BaseIO>
 class MeshIO
 {
 public:
+    /**
+     * @brief Saves the mesh with the given name
+     *
+     * @param mesh_name The name of the mesh
+     * @param mesh The mesh to save
+     * @param metadata Additional entries for the meta data of the mesh (e.g.
+     *                 HDF5 attributes), stored under "metadata/<key>". See
+     *                 reconstructionProvenance().
+     */
     void saveMesh(
         const std::string mesh_name,
-        const MeshBufferPtr mesh
+        const MeshBufferPtr mesh,
+        const std::map<std::string, std::string>& metadata = {}
     ) const;
 
     MeshBufferPtr loadMesh(
@@ -15,7 +15,8 @@ namespace meshio
 template <typename BaseIO>
 void MeshIO<BaseIO>::saveMesh(
     const std::string mesh_name, 
-    const MeshBufferPtr mesh
+    const MeshBufferPtr mesh,
+    const std::map<std::string, std::string>& metadata
     ) const
 {
     std::cout << timestamp << "[MeshIO] Saving '" << mesh_name << "' to " 
@@ -28,6 +29,10 @@ void MeshIO<BaseIO>::saveMesh(
         node["n_materials"] = (uint64_t) mesh->getMaterials().size();
         node["n_textures"]  = (uint64_t) mesh->getTextures().size();
         node["n_faces"]     = (uint64_t) mesh->numFaces();
+        for (const auto& entry : metadata)
+        {
+            node["metadata"][entry.first] = entry.second;
+        }
         m_baseIO->m_kernel->saveMetaYAML(
             *desc.metaRoot,
             *desc.meta,
@@ -64,7 +64,9 @@ struct convert<lvr2::Material>
         {
             node["color"] = lvr2::RGB8Color({255, 255, 255});
         }
-        
+        node["metallic"] = material.m_metallic;
+        node["roughness"] = material.m_roughness;
+
 
         return node;
     }
@@ -81,6 +83,16 @@ struct convert<lvr2::Material>
         lvr2::RGB8Color color = node["color"].as<lvr2::RGB8Color>();
         material.m_color = color;
 
+        // Optional, older files do not contain PBR parameters
+        if (node["metallic"])
+        {
+            material.m_metallic = node["metallic"].as<float>();
+        }
+        if (node["roughness"])
+        {
+            material.m_roughness = node["roughness"].as<float>();
+        }
+
         return true;
     }
 };
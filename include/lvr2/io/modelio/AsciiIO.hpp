@@ -80,6 +80,9 @@ class AsciiIO : public ModelIOBase
          *              respective parameters given to this function. Each line may
          *              consist of more attributes, but only the ones specified are
          *              parsed. Not existing attributes are indicated by -1.
+         *              Columns may be separated by spaces, tabs, semicolons or commas
+         *              and numbers are parsed independent of the current locale, see
+         *              AsciiTokenizer. Parse errors are reported with their line number.
          *
          * @param filename  The file to parse
          * @param x         The colum number containing the x-coordinate of a point
@@ -110,7 +113,7 @@ class AsciiIO : public ModelIOBase
 
         /**
          * @brief Helper method. Returns the number of columns in the
-         *        given file.
+         *        first point line of the given file.
          */
         static int getEntriesInLine(string filename);
 };
@@ -36,6 +36,30 @@
 namespace lvr2
 {
 
+/**
+ * @brief Quantization and compression settings of the draco encoder.
+ *        A quantization of 0 bits disables the quantization of the
+ *        attribute, i.e. it is stored lossless. Quantization is opt-in,
+ *        all attributes are lossless by default.
+ */
+struct DracoOptions
+{
+    /// Bits per coordinate of positions, e.g. 14
+    int positionBits = 0;
+
+    /// Bits per component of normals, e.g. 10
+    int normalBits = 0;
+
+    /// Bits per component of texture coordinates, e.g. 12
+    int texCoordBits = 0;
+
+    /// Bits per component of colors and other generic attributes, e.g. 8
+    int genericBits = 0;
+
+    /// Compression level from 0 (fastest) to 10 (smallest files)
+    int compressionLevel = 7;
+};
+
 /**
  * @brief encodes Model to draco Mesh
  * 
@@ -56,6 +80,20 @@ std::unique_ptr<draco::Mesh> toDracoMesh(ModelPtr modelPtr);
 std::unique_ptr<draco::EncoderBuffer> encodeDraco(ModelPtr                   modelPtr,
                                                   draco::EncodedGeometryType type);
 
+/**
+ * @brief encodes Model to draco EncodeBuffer using the given quantization
+ *        and compression settings
+ *
+ * @param modelptr modelPtr to Model that shall be encoded
+ * @param type GeometryType of Geometry to be encoded
+ * @param options Quantization and compression settings
+ *
+ * @return unique_ptr pointing to a EncoderBuffer that can be used to write a draco file
+ **/
+std::unique_ptr<draco::EncoderBuffer> encodeDraco(ModelPtr                   modelPtr,
+                                                  draco::EncodedGeometryType type,
+                                                  const DracoOptions&        options);
+
 } // namespace lvr
 
 #endif // DRACOENCODER
@@ -30,6 +30,7 @@
 #ifndef DRCIO
 #define DRCIO
 
+#include "lvr2/io/modelio/DracoEncoder.hpp"
 #include "lvr2/io/modelio/ModelIOBase.hpp"
 
 namespace lvr2
@@ -40,6 +41,12 @@ class DrcIO : public ModelIOBase
   public:
     DrcIO(){};
 
+    /**
+     * @brief Creates an io that writes meshes and point clouds with the
+     *        given quantization and compression settings.
+     */
+    DrcIO(const DracoOptions& options) : m_options(options), m_useOptions(true) {};
+
     /**
      * @brief Parse the draco and load supported elements.
      *
@@ -61,6 +68,12 @@ class DrcIO : public ModelIOBase
      * @param filename Filename of the file to write.
      */
     virtual void save(ModelPtr model, string filename);
+
+  private:
+    DracoOptions m_options;
+
+    /// If false, the default settings of encodeDraco() are used
+    bool m_useOptions = false;
 };
 
 } /* namespace lvr */
@@ -0,0 +1,72 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * @file       GltfLodIO.hpp
+ * @brief      Export of level of detail chains as a single glTF binary.
+ * @details    The levels are written as separate nodes linked with the
+ *             MSFT_lod extension, so viewers that support the extension
+ *             can switch between them based on the screen coverage.
+ */
+
+#pragma once
+
+#ifdef LVR2_USE_3DTILES
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <map>
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Writes the given levels of detail into one .glb file.
+ *
+ * If the vertices of all levels are prefixes of the finest level (see
+ * generateLODChain), the vertex positions are stored only once and shared
+ * by all levels.
+ *
+ * @param lods              The levels of detail, ordered from finest to coarsest
+ * @param filename          Name of the output file
+ * @param screenCoverage    Minimum screen coverage of each level (MSFT_screencoverage).
+ *                          Omitted if empty, otherwise one value per level.
+ * @param metadata          Stored as string properties in the extras of the
+ *                          asset, e.g. reconstructionProvenance().
+ */
+void saveGlbLODs(
+    const std::vector<MeshBufferPtr>& lods,
+    const std::string& filename,
+    const std::vector<float>& screenCoverage = {},
+    const std::map<std::string, std::string>& metadata = {}
+);
+
+} // namespace lvr2
+
+#endif // LVR2_USE_3DTILES
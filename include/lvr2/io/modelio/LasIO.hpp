@@ -41,7 +41,9 @@ namespace lvr2
 {
 
 /**
- * @brief   Interface class to read laser scan data in .las-Format
+ * @brief   Interface class to read and write laser scan data in .las-Format.
+ *          The ASPRS classification of each point is stored in the
+ *          "classification" channel.
  */
 class LasIO : public ModelIOBase
 {
@@ -59,10 +61,27 @@ public:
     /**
      * @brief Save the loaded elements to the given file.
      *
+     *        The header offset is the geo offset of the buffer if set, the
+     *        centre of the points otherwise. Coordinates are stored with
+     *        millimetre precision unless the extent of the points requires
+     *        a coarser scale.
+     *
      * @param filename Filename of the file to write.
      */
     virtual void save( string filename );
 
+    /**
+     * @brief If enabled, read() stores the points relative to the offset of
+     *        the LAS header to keep float precision for large (e.g. UTM)
+     *        coordinates. The offset is stored in the GeoMetadata of the
+     *        buffer and has to be added to get absolute coordinates again.
+     *        Disabled by default, i.e. the points are absolute and the
+     *        GeoMetadata only carries the reference system.
+     */
+    void setRelativeCoordinates(bool relative) { m_relativeCoordinates = relative; }
+
+private:
+    bool m_relativeCoordinates = false;
 };
 
 } /* namespace lvr2 */
@@ -99,7 +99,23 @@ ELEMENT point
 ELEMENT face
    PROPERTY vertex_indices (LIST uchar int)
    PROPERTY   vertex_index (LIST uchar int)  <<  [only read]
+   PROPERTY       texcoord (LIST uchar float)
+   PROPERTY      texnumber (int)
 \endverbatim
+ * Textured meshes follow the MeshLab convention: Each texture image is
+ * referenced by a \c TextureFile comment, relative to the PLY file. The
+ * \c texcoord property stores the texture coordinates of the three corners
+ * of a face and \c texnumber the index of the face's texture. The texture
+ * number is only written if there is more than one texture. When reading,
+ * vertices that are used with different texture coordinates are duplicated,
+ * and one textured material is created per texture file.
+ * Colors of type \c ushort are additionally stored with full precision in
+ * the \c colors16 channel (\c vertex_colors16 for meshes) and intensities of
+ * type \c uint in the \c intensities32 channel (\c vertex_intensities32).
+ * The default channels contain the scaled down or converted values. If the
+ * wide channels exist, they are used when saving. The 16 bit colors are
+ * only used while the 8 bit colors still match them, so edits of the
+ * default colors are never reverted.
  */
 class PLYIO : public ModelIOBase
 {
@@ -179,6 +195,20 @@ class PLYIO : public ModelIOBase
         static int readColorCb( p_ply_argument argument );
 
 
+        /**
+         * \brief Callback for read 16 bit color information.
+         * \param argument  Argument to pass the read data.
+         **/
+        static int readColor16Cb( p_ply_argument argument );
+
+
+        /**
+         * \brief Callback for read 32 bit integer intensities.
+         * \param argument  Argument to pass the read data.
+         **/
+        static int readIntensity32Cb( p_ply_argument argument );
+
+
         /**
          * \brief Callback for read faces.
          * \param argument  Argument to pass the read data.
@@ -186,6 +216,20 @@ class PLYIO : public ModelIOBase
         static int readFaceCb( p_ply_argument argument );
 
 
+        /**
+         * \brief Callback for read face texture coordinates.
+         * \param argument  Argument to pass the read data.
+         **/
+        static int readFaceTexcoordCb( p_ply_argument argument );
+
+
+        /**
+         * \brief Callback for read face texture numbers.
+         * \param argument  Argument to pass the read data.
+         **/
+        static int readTexNumberCb( p_ply_argument argument );
+
+
         /**
          * \brief Callback for read panorama coords.
          * \param argument  Argument to pass the read data.
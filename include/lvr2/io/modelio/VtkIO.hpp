@@ -0,0 +1,74 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * VtkIO.hpp
+ *
+ * Writer for meshes and point clouds in the VTK formats used by ParaView.
+ */
+
+#ifndef LVR2_IO_MODELIO_VTKIO_HPP
+#define LVR2_IO_MODELIO_VTKIO_HPP
+
+#include "lvr2/io/modelio/ModelIOBase.hpp"
+
+namespace lvr2
+{
+
+/**
+ * @brief Writes a model to legacy ASCII .vtk files or XML .vtp (PolyData)
+ *        and .vtu (UnstructuredGrid) files. The format is selected by the
+ *        file extension.
+ *
+ *        If the model contains a mesh, the mesh is written. Otherwise the
+ *        point cloud is written as a set of vertex cells. All additional
+ *        channels with one element per vertex / point are exported as point
+ *        data, mesh channels with one element per face as cell data. If
+ *        both counts are equal, channels named "face_..." are cell data and
+ *        all others point data. This
+ *        way, e.g. distance errors, curvatures and labels can be inspected
+ *        in ParaView.
+ */
+class VtkIO : public ModelIOBase
+{
+public:
+    VtkIO() = default;
+    virtual ~VtkIO() = default;
+
+    virtual void save(std::string filename);
+    virtual void save(ModelPtr model, std::string filename);
+
+    /**
+     * @brief Reading VTK files is not supported. Returns an empty model.
+     */
+    virtual ModelPtr read(std::string filename);
+};
+
+} // namespace lvr2
+
+#endif // LVR2_IO_MODELIO_VTKIO_HPP
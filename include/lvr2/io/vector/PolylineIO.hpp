@@ -0,0 +1,106 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PolylineIO.hpp
+ *
+ * Writers for polyline outputs like mesh contours: DXF for CAD
+ * applications and GeoJSON for GIS applications.
+ */
+
+#ifndef LVR2_IO_VECTOR_POLYLINEIO_HPP
+#define LVR2_IO_VECTOR_POLYLINEIO_HPP
+
+#include "lvr2/geometry/BaseMesh.hpp"
+#include "lvr2/types/GeoMetadata.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+
+#include <boost/filesystem.hpp>
+#include <boost/optional.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+struct Polyline
+{
+    /// The vertices of the polyline in local coordinates
+    std::vector<Vector3d> points;
+
+    /// If true, the last point is connected to the first one
+    bool closed = false;
+
+    /// Layer (DXF) or "layer" property (GeoJSON) of the polyline
+    std::string layer = "0";
+};
+
+/**
+ * @brief Extracts all boundary contours of the mesh as closed polylines.
+ *
+ * @param mesh  The mesh
+ * @param layer Layer name assigned to the polylines
+ */
+template<typename BaseVecT>
+std::vector<Polyline> contourPolylines(const BaseMesh<BaseVecT>& mesh, const std::string& layer = "contours");
+
+/**
+ * @brief Writes the polylines as 3D POLYLINE entities into an ASCII DXF
+ *        (AutoCAD R12) file.
+ *
+ * @param polylines The polylines
+ * @param filename  Output file
+ * @param geo       If set, the offset is added to all coordinates
+ */
+void saveDXF(
+    const std::vector<Polyline>& polylines,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo = boost::none
+);
+
+/**
+ * @brief Writes the polylines as a GeoJSON FeatureCollection of
+ *        LineString (open) and Polygon (closed) features.
+ *
+ * @param polylines The polylines
+ * @param filename  Output file
+ * @param geo       If set, the offset is added to all coordinates and the
+ *                  EPSG code is written as named CRS
+ */
+void saveGeoJSON(
+    const std::vector<Polyline>& polylines,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo = boost::none
+);
+
+} // namespace lvr2
+
+#include "lvr2/io/vector/PolylineIO.tcc"
+
+#endif // LVR2_IO_VECTOR_POLYLINEIO_HPP
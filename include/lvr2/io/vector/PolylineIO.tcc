@@ -0,0 +1,80 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PolylineIO.tcc
+ */
+
+#include "lvr2/algorithm/ContourAlgorithms.hpp"
+#include "lvr2/attrmaps/AttrMaps.hpp"
+
+namespace lvr2
+{
+
+template<typename BaseVecT>
+std::vector<Polyline> contourPolylines(const BaseMesh<BaseVecT>& mesh, const std::string& layer)
+{
+    std::vector<Polyline> polylines;
+
+    DenseEdgeMap<bool> visited(mesh.nextEdgeIndex(), false);
+    std::vector<VertexHandle> contour;
+    std::vector<EdgeHandle> contourEdges;
+
+    for (auto eH: mesh.edges())
+    {
+        if (visited[eH] || mesh.numAdjacentFaces(eH) != 1)
+        {
+            continue;
+        }
+
+        contourEdges.clear();
+        calcContourEdges(mesh, eH, contourEdges);
+        for (auto edgeH: contourEdges)
+        {
+            visited[edgeH] = true;
+        }
+
+        contour.clear();
+        calcContourVertices(mesh, eH, contour);
+
+        Polyline polyline;
+        polyline.closed = true;
+        polyline.layer = layer;
+        polyline.points.reserve(contour.size());
+        for (auto vH: contour)
+        {
+            auto p = mesh.getVertexPosition(vH);
+            polyline.points.emplace_back(p.x, p.y, p.z);
+        }
+        polylines.push_back(std::move(polyline));
+    }
+
+    return polylines;
+}
+
+} // namespace lvr2
@@ -36,6 +36,7 @@
 #ifndef ADAPTIVEKSEARCHSURFACE
 #define ADAPTIVEKSEARCHSURFACE
 
+#include <algorithm>
 #include <cassert>
 #include <fstream>
 #include <iostream>
@@ -45,6 +46,8 @@
 #include <chrono>
 #include <cmath>
 
+#include <boost/optional.hpp>
+
 #include "SearchTreeFlann.hpp"
 #include "PointsetSurface.hpp"
 
@@ -52,6 +55,7 @@
 #include "lvr2/geometry/Normal.hpp"
 #include "lvr2/geometry/Plane.hpp"
 #include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/util/MortonOrder.hpp"
 #include "lvr2/util/Progress.hpp"
 
 
@@ -73,6 +77,25 @@ using std::isnan;
 namespace lvr2
 {
 
+/**
+ * @brief Methods to estimate the tangent plane of a point's neighborhood
+ */
+enum class NormalEstimationMethod
+{
+    /// Least squares fit (default)
+    PCA = 0,
+
+    /// RANSAC plane fit with a least squares refinement on the inliers.
+    /// More robust near edges and in the presence of outliers.
+    RANSAC = 1,
+
+    /// Iterative covariance based fit
+    Iterative = 2,
+
+    /// Exact incremental PCA
+    IPCAExact = 3
+};
+
 /**
  * @brief A point cloud manager class that uses a search tree for
  *        nearest neighbor searches.
@@ -95,6 +118,8 @@ public:
      * @param ki         The number of neighbor points used for normal interpolation
      * @param kd         The number of neighbor points used for distance value calculation
      * @param calcMethod Normal calculation method. 0: PCA(default), 1: RANSAC, 2: Iterative
+     * @param poseFile   File with scan poses used to flip the normals
+     * @param cacheDirectory If not empty, the search tree is cached in this directory
      */
     AdaptiveKSearchSurface(
         PointBufferPtr loader,
@@ -103,9 +128,25 @@ public:
         int ki = 10,
         int kd = 10,
         int calcMethod = 0,
-        string poseFile = ""
+        string poseFile = "",
+        string cacheDirectory = ""
     );
 
+    /**
+     * @brief Constructor. Like the one above, but with a typed normal
+     *        estimation method.
+     */
+    AdaptiveKSearchSurface(
+        PointBufferPtr loader,
+        std::string searchTreeName,
+        int kn,
+        int ki,
+        int kd,
+        NormalEstimationMethod method,
+        string poseFile = "",
+        string cacheDirectory = ""
+    ) : AdaptiveKSearchSurface(loader, searchTreeName, kn, ki, kd, static_cast<int>(method), poseFile, cacheDirectory) {}
+
     /**
      * @brief standard Constructor
      *
@@ -187,6 +228,38 @@ public:
      */
     void interpolateSurfaceNormals();
 
+    /**
+     * @brief Weights the contribution of each point to the normal estimation
+     *        (PCA), normal interpolation and distance function with the given
+     *        float channel, e.g. a confidence derived from scanner quality or
+     *        range (see PointConfidence.hpp). The "confidence" channel is used
+     *        by default if present. An empty or unknown name disables weighting.
+     */
+    void setConfidenceChannel(const std::string& name);
+
+    /**
+     * @brief Computes the surface variation l0 / (l0 + l1 + l2) of the
+     *        covariance eigenvalues of each point's neighborhood as a
+     *        curvature estimate and stores it in the "curvature" channel.
+     *        Reuses the neighborhoods of calculateSurfaceNormals() if
+     *        setStoreNeighbors() was enabled, otherwise \ref m_kn
+     *        neighbors are searched.
+     */
+    void calculateSurfaceCurvature();
+
+    /**
+     * @brief If enabled (default), points are processed in blocks along a
+     *        Morton curve, so that consecutive neighborhood queries hit the
+     *        same parts of the search tree and point array.
+     */
+    void setMortonOrder(bool enabled) { m_mortonOrder = enabled; }
+
+    /**
+     * @brief Keep the neighborhoods found by calculateSurfaceNormals() for
+     *        calculateSurfaceCurvature(). Trades memory for a second search.
+     */
+    void setStoreNeighbors(bool enabled) { m_storeNeighbors = enabled; }
+
 protected:
     using Base::m_points;
 
@@ -267,6 +340,30 @@ private:
         const vector<size_t> &id
     );
 
+    /**
+     * @brief Like the other overload, but uses the already known positions
+     *        of the neighborhood points instead of looking them up in
+     *        \ref m_points.
+     *
+     * @param queryPoint    The point for which the tangent plane is created
+     * @param id            The positions of the neighborhood points in \ref m_points
+     * @param neighbors     The neighborhood points, in the same order as `id`
+     */
+    Plane<BaseVecT> calcPlane(
+        const BaseVecT &queryPoint,
+        const vector<size_t> &id,
+        const vector<BaseVecT> &neighbors
+    );
+
+    /**
+     * @brief Calculates a tangent plane for the query point by fitting
+     *        planes through random samples of three neighbors and refining
+     *        the plane with the most inliers with a least squares fit.
+     *
+     * @param queryPoint    The point for which the tangent plane is created
+     * @param id            The positions of the neighborhood points in \ref m_points
+     * @param ok            False, if no plane with enough inliers was found
+     */
     Plane<BaseVecT> calcPlaneRANSAC(
         const BaseVecT &queryPoint,
         const vector<size_t> &id,
@@ -308,11 +405,57 @@ private:
 
     /// Type of used search tree
     std::string m_searchTreeName;
+
+    /// Per point weights, see setConfidenceChannel()
+    FloatChannelOptional m_confidence;
+
+    /// See setMortonOrder()
+    bool m_mortonOrder = true;
+
+    /// See setStoreNeighbors()
+    bool m_storeNeighbors = false;
+
+    /// Neighborhoods of the last normal estimation if m_storeNeighbors is set
+    std::vector<std::vector<size_t>> m_neighbors;
+
+    /// Number of consecutive points along the Morton curve processed by one thread
+    static constexpr size_t MortonBlockSize = 256;
+
+    /// Returns the (non-negative) weight of the point with the given index
+    float weight(size_t index) const
+    {
+        return m_confidence ? std::max((*m_confidence)[index][0], 0.0f) : 1.0f;
+    }
 };
 
 template<typename BaseVecT>
 using AdaptiveKSearchSurfacePtr = std::shared_ptr<AdaptiveKSearchSurface<BaseVecT>>;
 
+/**
+ * @brief Estimates point normals and stores them in the "normals" channel
+ *        of the given buffer. The buffer is modified in place, i.e., no
+ *        copy of the point data is created. An existing normal channel of
+ *        matching size is overwritten instead of being reallocated.
+ *
+ * @param buffer          The point buffer to estimate normals for
+ * @param kn              The number of neighbor points used for normal estimation
+ * @param ki              The number of neighbor points used for normal interpolation.
+ *                        No interpolation is done if ki is 0.
+ * @param calcMethod      Normal calculation method. 0: PCA(default), 1: RANSAC, 2: Iterative
+ * @param searchTreeName  Type of the search tree
+ * @param flipPoint       Normals are flipped towards this point. If not given, the
+ *                        centroid of the bounding box is used.
+ */
+template<typename BaseVecT>
+void estimateNormalsInto(
+    PointBufferPtr buffer,
+    int kn,
+    int ki = 0,
+    int calcMethod = 0,
+    const std::string& searchTreeName = "FLANN",
+    boost::optional<BaseVecT> flipPoint = boost::none
+);
+
 
 } // namespace lvr2
 
@@ -61,7 +61,8 @@ AdaptiveKSearchSurface<BaseVecT>::AdaptiveKSearchSurface(
     int ki,
     int kd,
     int calcMethod,
-    std::string posefile
+    std::string posefile,
+    std::string cacheDirectory
 ) :
     PointsetSurface<BaseVecT>(buffer),
     m_searchTreeName(searchTreeName),
@@ -73,11 +74,11 @@ AdaptiveKSearchSurface<BaseVecT>::AdaptiveKSearchSurface(
 
     init();
 
-    this->m_searchTree = getSearchTree<BaseVecT>(m_searchTreeName, buffer);
+    this->m_searchTree = getSearchTree<BaseVecT>(m_searchTreeName, buffer, cacheDirectory);
 
     if(!this->m_searchTree)
     {
-       this->m_searchTree = getSearchTree<BaseVecT>("flann", buffer);
+       this->m_searchTree = getSearchTree<BaseVecT>("flann", buffer, cacheDirectory);
        lvr2::logout::get() << lvr2::warning << "[AdaptiveKSearchSurface] No valid search tree specified (" << searchTreeName << ")." << lvr2::endl;
        lvr2::logout::get() << lvr2::warning << "[AdaptiveKSearchSurface] Maybe you did not install the required library." << lvr2::endl;
        lvr2::logout::get() << lvr2::warning << "[AdaptiveKSearchSurface] Defaulting to flann." << lvr2::endl;
@@ -153,6 +154,23 @@ void AdaptiveKSearchSurface<BaseVecT>::init()
         << lvr2::endl;
 
     this->m_flipPoint = this->m_boundingBox.getCentroid();
+
+    setConfidenceChannel("confidence");
+}
+
+template<typename BaseVecT>
+void AdaptiveKSearchSurface<BaseVecT>::setConfidenceChannel(const std::string& name)
+{
+    m_confidence = name.empty() ? FloatChannelOptional() : this->m_pointBuffer->getFloatChannel(name);
+    if(m_confidence && m_confidence->width() != 1)
+    {
+        lvr2::logout::get() << lvr2::warning << "[AdaptiveKSearchSurface] Confidence channel '" << name << "' has width " << m_confidence->width() << ". Ignoring it." << lvr2::endl;
+        m_confidence = FloatChannelOptional();
+    }
+    if(m_confidence)
+    {
+        lvr2::logout::get() << lvr2::info << "[AdaptiveKSearchSurface] Weighting points with channel '" << name << "'" << lvr2::endl;
+    }
 }
 
 template<typename BaseVecT>
@@ -161,10 +179,21 @@ void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceNormals()
     int k_0 = this->m_kn;
     const size_t numPoints = m_points.numElements();
 
-    lvr2::logout::get() << lvr2::info << "[AdaptiveKSearchSurface] Initializing normal array..." << lvr2::endl;
-
-    floatArr normals = floatArr(new float[numPoints * 3]);
-    this->m_pointBuffer->setNormalArray(normals, numPoints);
+    // Reuse an existing normal channel of matching size to avoid
+    // allocating a second normal array for large point clouds
+    floatArr normals;
+    FloatChannelOptional existing = this->m_pointBuffer->getFloatChannel("normals");
+    if(existing && existing->numElements() == numPoints && existing->width() == 3)
+    {
+        lvr2::logout::get() << lvr2::info << "[AdaptiveKSearchSurface] Overwriting existing normal array..." << lvr2::endl;
+        normals = existing->dataPtr();
+    }
+    else
+    {
+        lvr2::logout::get() << lvr2::info << "[AdaptiveKSearchSurface] Initializing normal array..." << lvr2::endl;
+        normals = floatArr(new float[numPoints * 3]);
+        this->m_pointBuffer->setNormalArray(normals, numPoints);
+    }
 
     const int max_threads = omp_get_max_threads();
     const int normal_estimation_threads = max_threads;
@@ -175,14 +204,31 @@ void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceNormals()
 
     // lvr2::PacmanProgressBar monitor(numPoints / normal_estimation_threads, "[AdaptiveKSearchSurface] Estimating Normals");
 
-    #pragma omp parallel for schedule(dynamic) num_threads(normal_estimation_threads) shared(monitor)
-    for(size_t i = 0; i < numPoints; i++)
+    // Process the points in blocks along a space filling curve to keep
+    // consecutive queries spatially close
+    std::vector<size_t> order;
+    if(m_mortonOrder)
     {
+        order = mortonOrder(m_points);
+    }
+
+    m_neighbors.clear();
+    if(m_storeNeighbors)
+    {
+        m_neighbors.resize(numPoints);
+    }
+
+    #pragma omp parallel for schedule(dynamic, MortonBlockSize) num_threads(normal_estimation_threads) shared(monitor)
+    for(size_t j = 0; j < numPoints; j++)
+    {
+        const size_t i = order.empty() ? j : order[j];
+
         // We have to fit these vector to have the
         // correct return values when performing the
         // search on the search tree. So we don't use
         // the template parameter T for di
         std::vector<size_t> id;
+        std::vector<BaseVecT> neighbors;
 
         int n = 0;
         size_t k = k_0;
@@ -200,13 +246,13 @@ void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceNormals()
 
             id.clear();
 
-            this->m_searchTree->kSearch(m_points[i], k, id);
+            this->m_searchTree->kSearchPoints(m_points[i], k, id, neighbors);
 
             // Calculate the bounding box of found point set
             BoundingBox<BaseVecT> bb;
-            for (auto& index : id)
+            for (auto& neighbor : neighbors)
             {
-                bb.expand(BaseVecT(m_points[index]));
+                bb.expand(neighbor);
             }
 
             if(boundingBoxOK(bb))
@@ -222,27 +268,27 @@ void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceNormals()
         Plane<BaseVecT> p;
         bool ransac_ok;
 
-        if(m_calcMethod == 1)
+        if(m_calcMethod == static_cast<int>(NormalEstimationMethod::RANSAC))
         {
             p = calcPlaneRANSAC(queryPoint, id, ransac_ok);
             // Fallback if RANSAC failed
             if(!ransac_ok)
             {
                 // compare speed
-                p = calcPlane(queryPoint, id);
+                p = calcPlane(queryPoint, id, neighbors);
             }
         }
-        else if(m_calcMethod == 2)
+        else if(m_calcMethod == static_cast<int>(NormalEstimationMethod::Iterative))
         {
             p = calcPlaneIterative(queryPoint, id);
         }
-        else if(m_calcMethod == 3)
+        else if(m_calcMethod == static_cast<int>(NormalEstimationMethod::IPCAExact))
         {
             p = calcPlaneIPCAExact(queryPoint, id);
         }
         else
         {
-            p = calcPlane(queryPoint, id);
+            p = calcPlane(queryPoint, id, neighbors);
         }
         // Get the mean distance to the tangent plane
         //mean_distance = meanDistance(p, id, k);
@@ -278,6 +324,11 @@ void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceNormals()
         normals[i*3 + 1] = normal.y;
         normals[i*3 + 2] = normal.z;
 
+        if(m_storeNeighbors)
+        {
+            m_neighbors[i] = std::move(id);
+        }
+
         ++monitor;
     }
 
@@ -290,6 +341,75 @@ void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceNormals()
 }
 
 
+template<typename BaseVecT>
+void AdaptiveKSearchSurface<BaseVecT>::calculateSurfaceCurvature()
+{
+    const size_t numPoints = m_points.numElements();
+    floatArr curvature(new float[numPoints]);
+
+    const bool reuse = m_neighbors.size() == numPoints;
+    lvr2::logout::get() << lvr2::info << "[AdaptiveKSearchSurface] Estimating curvature"
+        << (reuse ? " from stored neighborhoods" : "") << " ..." << lvr2::endl;
+    lvr2::Monitor monitor(lvr2::LogLevel::info, "[AdaptiveKSearchSurface] Estimating curvature", numPoints);
+
+    std::vector<size_t> order;
+    if(m_mortonOrder)
+    {
+        order = mortonOrder(m_points);
+    }
+
+    #pragma omp parallel for schedule(dynamic, MortonBlockSize) shared(monitor)
+    for(size_t j = 0; j < numPoints; j++)
+    {
+        const size_t i = order.empty() ? j : order[j];
+
+        thread_local std::vector<size_t> searched;
+        const std::vector<size_t>* id = &searched;
+        if(reuse)
+        {
+            id = &m_neighbors[i];
+        }
+        else
+        {
+            searched.clear();
+            this->m_searchTree->kSearch(m_points[i], this->m_kn, searched);
+        }
+
+        if(id->size() < 3)
+        {
+            curvature[i] = 0;
+            ++monitor;
+            continue;
+        }
+
+        Eigen::Vector3d mean = Eigen::Vector3d::Zero();
+        for(size_t index : *id)
+        {
+            mean += Eigen::Vector3d(m_points[index][0], m_points[index][1], m_points[index][2]);
+        }
+        mean /= id->size();
+
+        Eigen::Matrix3d cov = Eigen::Matrix3d::Zero();
+        for(size_t index : *id)
+        {
+            const Eigen::Vector3d d = Eigen::Vector3d(m_points[index][0], m_points[index][1], m_points[index][2]) - mean;
+            cov += d * d.transpose();
+        }
+
+        // Eigenvalues are sorted in increasing order
+        Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> solver(cov, Eigen::EigenvaluesOnly);
+        const Eigen::Vector3d ev = solver.eigenvalues();
+        const double sum = ev.sum();
+        curvature[i] = sum > 0 ? ev[0] / sum : 0;
+
+        ++monitor;
+    }
+
+    monitor.terminate();
+
+    this->m_pointBuffer->addFloatChannel(curvature, "curvature", numPoints, 1);
+}
+
 template<typename BaseVecT>
 void AdaptiveKSearchSurface<BaseVecT>::interpolateSurfaceNormals()
 {
@@ -309,17 +429,32 @@ void AdaptiveKSearchSurface<BaseVecT>::interpolateSurfaceNormals()
     lvr2::Monitor monitor(lvr2::LogLevel::info, "[AdaptiveKSearchSurface] Interpolating normals", numPoints);
 
     // Interpolate normals
-    #pragma omp parallel for schedule(dynamic) num_threads(normal_interpolation_threads) shared(monitor)
-    for( size_t i = 0; i < numPoints; i++)
+    std::vector<size_t> order;
+    if(m_mortonOrder)
     {
+        order = mortonOrder(m_points);
+    }
+
+    #pragma omp parallel for schedule(dynamic, MortonBlockSize) num_threads(normal_interpolation_threads) shared(monitor)
+    for(size_t j = 0; j < numPoints; j++)
+    {
+        const size_t i = order.empty() ? j : order[j];
         vector<size_t> id;
 
         this->m_searchTree->kSearch(m_points[i], this->m_ki, id);
 
-        BaseVecT mean = normals[i];
+        typename BaseVecT::CoordType weightSum = weight(i);
+        for(auto& index : id)
+        {
+            weightSum += weight(index);
+        }
+
+        // Fall back to the unweighted mean if no neighbor has a positive weight
+        const bool weighted = weightSum > 0;
+        BaseVecT mean = BaseVecT(normals[i]) * (weighted ? weight(i) : 1);
         for(auto& index : id)
         {
-            mean += normals[index];
+            mean += BaseVecT(normals[index]) * (weighted ? weight(index) : 1);
         }
         tmp[i] = mean.normalized();
 
@@ -416,36 +551,56 @@ pair<typename BaseVecT::CoordType, typename BaseVecT::CoordType>
     AdaptiveKSearchSurface<BaseVecT>::distance(BaseVecT p) const
 {
     const FloatChannel normals = *(this->m_pointBuffer->getFloatChannel("normals"));
-    size_t numPoints     = m_points.numElements();
 
-    vector<size_t> id;
+    // This function is called for every grid corner. Keep the neighbor
+    // buffers alive between calls to avoid allocations in the grid loop.
+    // The positions are returned by the same query, so the points do not
+    // have to be looked up again.
+    thread_local vector<size_t> id;
+    thread_local vector<BaseVecT> neighbors;
 
     // Find nearest tangent plane
-    this->m_searchTree->kSearch( p, this->m_kd, id );
+    this->m_searchTree->kSearchPoints( p, this->m_kd, id, neighbors );
 
     if (id.empty())
     {
-        auto dist = std::numeric_limits<typename BaseVecT::CoordType>::max();
-        return std::make_pair(dist, dist);
+        auto maxDist = std::numeric_limits<typename BaseVecT::CoordType>::max();
+        return std::make_pair(maxDist, maxDist);
     }
 
     BaseVecT nearest;
     BaseVecT avg_normal;
+    typename BaseVecT::CoordType weightSum = 0;
 
     for ( auto& index : id )
     {
+        weightSum += weight(index);
+    }
+
+    // Fall back to the unweighted mean if no neighbor has a positive weight
+    const bool weighted = weightSum > 0;
+
+    for ( size_t i = 0; i < id.size(); i++ )
+    {
+        const size_t index = id[i];
+        const typename BaseVecT::CoordType w = weighted ? weight(index) : 1;
+
         //Get nearest tangent plane
-        auto vq = m_points[index];
+        const BaseVecT& vq = neighbors[i];
 
         //Get normal
-        auto n = normals[index];
+        BaseVecT n = normals[index];
 
-        nearest += vq;
-        avg_normal += n;
+        nearest += vq * w;
+        avg_normal += n * w;
     }
 
-    avg_normal /= id.size();
-    nearest /= id.size();
+    if (!weighted)
+    {
+        weightSum = id.size();
+    }
+    avg_normal /= weightSum;
+    nearest /= weightSum;
     auto normal = avg_normal.normalized();
 
     //Calculate distance
@@ -470,8 +625,22 @@ Plane<BaseVecT> AdaptiveKSearchSurface<BaseVecT>::calcPlane(
     const std::vector<size_t> &id
 )
 {
-    const size_t numPoints     = m_points.numElements();
+    std::vector<BaseVecT> neighbors;
+    neighbors.reserve(id.size());
+    for(size_t index : id)
+    {
+        neighbors.push_back(m_points[index]);
+    }
+    return calcPlane(queryPoint, id, neighbors);
+}
 
+template<typename BaseVecT>
+Plane<BaseVecT> AdaptiveKSearchSurface<BaseVecT>::calcPlane(
+    const BaseVecT &queryPoint,
+    const std::vector<size_t> &id,
+    const std::vector<BaseVecT> &neighbors
+)
+{
     /**
      * @todo Think of a better way to code this magic number.
      */
@@ -482,13 +651,25 @@ Plane<BaseVecT> AdaptiveKSearchSurface<BaseVecT>::calcPlane(
     Eigen::VectorXf F(id.size());
     Eigen::MatrixXf B(id.size(), 3);
 
+    // Fall back to the unweighted fit if no neighbor has a positive weight
+    bool weighted = false;
+    if(m_confidence)
+    {
+        for(size_t j = 0; j < id.size() && !weighted; j++)
+        {
+            weighted = weight(id[j]) > 0;
+        }
+    }
+
     for(size_t j = 0; j < id.size(); j++) 
     {
-        const BaseVecT p = m_points[id[j]];
-        F(j)    = p.y;
-        B(j, 0) = 1.0f;
-        B(j, 1) = p.x;
-        B(j, 2) = p.z;
+        // Weighted least squares: scale each equation with the root of its weight
+        const float w = weighted ? std::sqrt(weight(id[j])) : 1.0f;
+        const BaseVecT& p = neighbors[j];
+        F(j)    = w * p.y;
+        B(j, 0) = w;
+        B(j, 1) = w * p.x;
+        B(j, 2) = w * p.z;
     }
 
     C = B.jacobiSvd(Eigen::ComputeThinU | Eigen::ComputeThinV).solve(F);
@@ -693,91 +874,95 @@ Plane<BaseVecT> AdaptiveKSearchSurface<BaseVecT>::calcPlaneRANSAC(
     bool &ok
 )
 {
-    FloatChannel normals = *(this->m_pointBuffer->getFloatChannel("normals"));
-    size_t numPoints     = m_points.numElements();
+    ok = false;
+    if(id.size() < 3)
+    {
+        return Plane<BaseVecT>();
+    }
 
-   Plane<BaseVecT> p;
+    // Inlier threshold relative to the size of the neighborhood
+    float meanDist = 0;
+    for(size_t index : id)
+    {
+        meanDist += queryPoint.distance(m_points[index]);
+    }
+    meanDist /= id.size();
+    const float threshold = 0.1f * meanDist;
 
-   //representation of best regression plane by point and normal
-   BaseVecT bestPoint;
-   Normal<typename BaseVecT::CoordType> bestNorm(0, 0, 1);
+    const int maxIterations = 50;
 
-   float bestdist = numeric_limits<float>::max();
-   float dist     = 0;
+    // Seed with the query point's neighborhood to get reproducible results
+    std::minstd_rand generator(id.front());
+    std::uniform_int_distribution<size_t> distribution(0, id.size() - 1);
 
-   int iterations              = 0;
-   int nonimproving_iterations = 0;
+    size_t bestInliers = 0;
+    BaseVecT bestNormal;
+    BaseVecT bestPoint;
+
+    for(int it = 0; it < maxIterations; it++)
+    {
+        size_t i1 = distribution(generator);
+        size_t i2 = distribution(generator);
+        size_t i3 = distribution(generator);
+        if(i1 == i2 || i1 == i3 || i2 == i3)
+        {
+            continue;
+        }
 
-   //  int max_nonimproving = max(5, k / 2);
-   int max_interations  = 10;
+        BaseVecT point1 = m_points[id[i1]];
+        BaseVecT point2 = m_points[id[i2]];
+        BaseVecT point3 = m_points[id[i3]];
 
-   std::unordered_set<size_t> ids;
-   std::default_random_engine generator;
-   std::uniform_int_distribution<size_t> distribution(0, id.size() - 1);
-   auto number = std::bind(distribution, generator);
+        BaseVecT n0 = (point2 - point1).cross(point3 - point1);
+        if(n0.length2() < std::numeric_limits<float>::epsilon())
+        {
+            // Degenerate sample
+            continue;
+        }
+        n0.normalize();
 
-   while((nonimproving_iterations < 5) && (iterations < max_interations))
-   {
-       // randomly choose 3 disjoint points
-        int c = 0;
+        size_t inliers = 0;
+        for(size_t index : id)
+        {
+            if(std::fabs((m_points[index] - point1).dot(n0)) < threshold)
+            {
+                inliers++;
+            }
+        }
 
-        ids.clear();
-        do
+        if(inliers > bestInliers)
         {
-            ids.insert(number());
-            c++;
-            if (c == 20)
+            bestInliers = inliers;
+            bestNormal = n0;
+            bestPoint = point1;
+
+            // Stop early if almost all points support the plane
+            if(bestInliers > 0.9 * id.size())
             {
-                lvr2::logout::get() << lvr2::warning << "[AdaptiveKSearchSurface] Deadlock" << lvr2::endl;
+                break;
             }
-        } 
-        while (ids.size() < 3 && c <= 20);
-
-       auto it = ids.begin();
-
-       BaseVecT point1 = m_points[*it];
-       BaseVecT point2 = m_points[*(++it)];
-       BaseVecT point3 = m_points[*(++it)];
-
-       auto n0 = (point1 - point2).cross(point1 - point3).normalized();
-
-       //compute error to at most 50 other randomly chosen points
-       dist = 0;
-       int n = std::min(50, (int)id.size());
-       for(int i = 0; i < n; i++)
-       {
-           int index = id[number()];
-           BaseVecT refpoint = m_points[index];
-           dist += fabs(refpoint.dot(n0) - point1.dot(n0));
-       }
-       if(n != 0) dist /= n;
-
-       //a new optimum is found
-       if(dist < bestdist)
-       {
-           bestdist = dist;
-
-           bestPoint = point1;
-           bestNorm = n0;
-
-           nonimproving_iterations = 0;
-       }
-       else
-       {
-           nonimproving_iterations++;
-       }
-
-       iterations++;
-   }
-
-   // Save plane parameters
-   // p.a = 0;
-   // p.b = 0;
-   // p.c = 0;
-   p.normal = bestNorm;
-   p.pos = bestPoint;
-
-   return p;
+        }
+    }
+
+    if(bestInliers < 3)
+    {
+        return Plane<BaseVecT>();
+    }
+
+    // Refine with a least squares fit to the inliers
+    vector<size_t> inlierIds;
+    inlierIds.reserve(bestInliers);
+    for(size_t index : id)
+    {
+        if(std::fabs((m_points[index] - bestPoint).dot(bestNormal)) < threshold)
+        {
+            inlierIds.push_back(index);
+        }
+    }
+
+    Plane<BaseVecT> p = calcPlane(queryPoint, inlierIds);
+    ok = std::isfinite(p.normal.getX()) && std::isfinite(p.normal.getY()) && std::isfinite(p.normal.getZ());
+    return p;
 }
 
 
@@ -931,4 +1116,23 @@ Plane<BaseVecT> AdaptiveKSearchSurface<BaseVecT>::calcPlaneRANSAC(
 
 
 
+template<typename BaseVecT>
+void estimateNormalsInto(
+    PointBufferPtr buffer,
+    int kn,
+    int ki,
+    int calcMethod,
+    const std::string& searchTreeName,
+    boost::optional<BaseVecT> flipPoint)
+{
+    // The surface only references the buffer, so the normals are
+    // written directly into the caller's buffer
+    AdaptiveKSearchSurface<BaseVecT> surface(buffer, searchTreeName, kn, ki, 0, calcMethod);
+    if(flipPoint)
+    {
+        surface.setFlipPoint(*flipPoint);
+    }
+    surface.calculateSurfaceNormals();
+}
+
 } // namespace lvr2
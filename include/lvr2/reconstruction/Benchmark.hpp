@@ -0,0 +1,128 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Benchmark.hpp
+ *
+ * Helpers to time the reconstruction on synthetic data of increasing
+ * size, e.g. to compare search tree backends, decompositions or thread
+ * counts on the local hardware and to detect performance regressions.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_BENCHMARK_HPP
+#define LVR2_RECONSTRUCTION_BENCHMARK_HPP
+
+#include "lvr2/reconstruction/Reconstruction.hpp"
+#include "lvr2/util/Synthetic.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+struct BenchmarkCase
+{
+    /// Name used in the result table
+    std::string name;
+
+    /// Reconstruction parameters. The voxel size is scaled with the point
+    /// density of each input size, see BenchmarkConfig::pointsPerVoxel.
+    ReconstructionOptions options;
+
+    /// Number of OpenMP threads, 0 to use all
+    int threads = 0;
+};
+
+struct BenchmarkConfig
+{
+    /// Number of sampled points of the benchmark inputs
+    std::vector<size_t> sizes = {10000, 100000, 1000000};
+
+    /// Number of timed runs per case and size. The minimum is reported.
+    int repetitions = 3;
+
+    /// Unmeasured runs before the timed runs
+    int warmup = 1;
+
+    /// Approximate number of points per voxel face, used to choose the
+    /// voxel size for each input size
+    float pointsPerVoxel = 4.0f;
+
+    /// The sampled shape. A sphere with radius 1 if not set.
+    synthetic::ShapePtr shape;
+
+    /// Noise added to the samples
+    synthetic::NoiseOptions noise;
+};
+
+struct BenchmarkResult
+{
+    std::string name;
+    size_t numPoints = 0;
+    int threads = 0;
+    float voxelSize = 0;
+
+    /// Minimum, mean and maximum of the timed runs in seconds
+    double minSeconds = 0;
+    double meanSeconds = 0;
+    double maxSeconds = 0;
+
+    size_t numFaces = 0;
+
+    /// Set if the reconstruction threw an exception
+    std::string failure;
+};
+
+/**
+ * @brief Runs every case on inputs of every configured size and measures
+ *        the time of the full reconstruction including normal estimation.
+ */
+template<typename BaseVecT, typename MeshT = PMPMesh<BaseVecT>>
+std::vector<BenchmarkResult> runBenchmark(
+    const std::vector<BenchmarkCase>& cases,
+    const BenchmarkConfig& config = BenchmarkConfig()
+);
+
+/**
+ * @brief Formats the results as aligned text table
+ */
+std::string benchmarkTable(const std::vector<BenchmarkResult>& results);
+
+/**
+ * @brief Writes the results as CSV file with a header line
+ */
+void saveBenchmarkCsv(const std::vector<BenchmarkResult>& results, const boost::filesystem::path& filename);
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/Benchmark.tcc"
+
+#endif // LVR2_RECONSTRUCTION_BENCHMARK_HPP
@@ -0,0 +1,134 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Benchmark.tcc
+ */
+
+#include "lvr2/config/lvropenmp.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Timestamp.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+template<typename BaseVecT, typename MeshT>
+std::vector<BenchmarkResult> runBenchmark(
+    const std::vector<BenchmarkCase>& cases,
+    const BenchmarkConfig& config)
+{
+    std::vector<BenchmarkResult> results;
+
+    synthetic::ShapePtr shape = config.shape ? config.shape : std::make_shared<synthetic::Sphere>();
+    const int previousThreads = OpenMPConfig::getNumThreads();
+
+    for (size_t size : config.sizes)
+    {
+        PointBufferPtr input = synthetic::samplePoints(*shape, size, config.noise);
+
+        // Choose the voxel size so that a voxel face contains about
+        // pointsPerVoxel points. The surface area is estimated from the
+        // number of points of a sphere-like shape with diameter of the
+        // bounding box.
+        Vector3f min, max;
+        shape->bounds(min, max);
+        const double area = M_PI * std::pow((max - min).maxCoeff(), 2);
+        const float voxelSize = std::sqrt(area * config.pointsPerVoxel / size);
+
+        for (const BenchmarkCase& c : cases)
+        {
+            BenchmarkResult result;
+            result.name = c.name;
+            result.numPoints = size;
+            result.voxelSize = voxelSize;
+
+            if (c.threads > 0)
+            {
+                OpenMPConfig::setNumThreads(c.threads);
+            }
+            else
+            {
+                OpenMPConfig::setMaxNumThreads();
+            }
+            result.threads = OpenMPConfig::getNumThreads();
+
+            ReconstructionOptions options = c.options;
+            options.voxelSize = voxelSize;
+            options.recalcNormals = true;
+
+            std::vector<double> times;
+            try
+            {
+                for (int i = 0; i < config.warmup + config.repetitions; i++)
+                {
+                    // Each run gets its own copy as reconstruct() modifies the buffer
+                    PointBufferPtr buffer = std::make_shared<PointBuffer>(input->clone());
+
+                    Timestamp ts;
+                    auto reconstruction = reconstruct<BaseVecT, MeshT>(buffer, options);
+                    double seconds = ts.getElapsedTimeInS();
+
+                    if (i >= config.warmup)
+                    {
+                        times.push_back(seconds);
+                        result.numFaces = reconstruction.mesh.numFaces();
+                    }
+                }
+            }
+            catch (const std::exception& e)
+            {
+                result.failure = e.what();
+            }
+
+            if (!times.empty())
+            {
+                result.minSeconds = *std::min_element(times.begin(), times.end());
+                result.maxSeconds = *std::max_element(times.begin(), times.end());
+                double sum = 0;
+                for (double t : times)
+                {
+                    sum += t;
+                }
+                result.meanSeconds = sum / times.size();
+            }
+
+            lvr2::logout::get() << lvr2::info << "[Benchmark] " << c.name << " with " << size
+                << " points: " << result.minSeconds << "s" << lvr2::endl;
+            results.push_back(result);
+        }
+    }
+
+    OpenMPConfig::setNumThreads(previousThreads);
+    return results;
+}
+
+} // namespace lvr2
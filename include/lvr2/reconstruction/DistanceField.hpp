@@ -0,0 +1,132 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * DistanceField.hpp
+ *
+ * Read-only copy of the signed distance values of a reconstruction grid
+ * that can be queried at arbitrary positions, e.g. for clearance checks.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_DISTANCEFIELD_HPP
+#define LVR2_RECONSTRUCTION_DISTANCEFIELD_HPP
+
+#include "lvr2/reconstruction/HashGrid.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <array>
+#include <memory>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+/**
+ * @brief The corner distances of all cells of a reconstruction grid.
+ *
+ *        The field does not change after construction, so it can be
+ *        queried from several threads without locking. Positions outside
+ *        of the grid cells and cells with invalid corners yield NaN.
+ */
+class DistanceField
+{
+public:
+    /// Corner distances of a cell in the order of box_creation_table
+    using CellDistances = std::array<float, 8>;
+
+    /**
+     * @brief Creates a distance field from the given cells
+     *
+     * @param voxelSize     Edge length of the cells. Cell (i, j, k) covers
+     *                      [i, i + 1] * voxelSize etc.
+     * @param cells         Corner distances per cell index
+     */
+    DistanceField(float voxelSize, std::unordered_map<Vector3i, CellDistances> cells);
+
+    /**
+     * @brief Copies the distances from the given grid. Invalid corners
+     *        are stored as NaN.
+     */
+    template<typename BaseVecT, typename BoxT>
+    explicit DistanceField(const HashGrid<BaseVecT, BoxT>& grid);
+
+    /**
+     * @brief Creates a distance field from a buffer in the format of
+     *        HashGrid::toPointBuffer(), i.e. the cell centers as points
+     *        and the corner distances in the channel "tsdf_values".
+     *
+     * @return nullptr, if the buffer has no "tsdf_values" channel
+     */
+    static std::shared_ptr<DistanceField> fromPointBuffer(PointBufferPtr buffer, float voxelSize);
+
+    /**
+     * @brief Converts the field to the format of HashGrid::toPointBuffer(), e.g.
+     *        to extract a mesh from it with a HashGrid and marching cubes.
+     *        Invalid corners are stored as NaN.
+     */
+    PointBufferPtr toPointBuffer() const;
+
+    /**
+     * @brief Returns the trilinearly interpolated signed distance at the
+     *        given position. Negative values are inside of the surface.
+     *
+     * @return The distance or NaN, if the position is not covered by a
+     *         cell with valid corner distances
+     */
+    float query(const Vector3f& position) const;
+
+    /// True, if query() returns a valid distance for the given position
+    bool contains(const Vector3f& position) const;
+
+    /// Edge length of the cells
+    float voxelSize() const { return m_voxelSize; }
+
+    /// Number of cells in the field
+    size_t numCells() const { return m_cells.size(); }
+
+    /// Minimum and maximum corner of the covered region
+    std::pair<Vector3f, Vector3f> bounds() const;
+
+private:
+    /// Index of the cell that contains the given position
+    Vector3i cellIndex(const Vector3f& position) const;
+
+    float m_voxelSize;
+
+    std::unordered_map<Vector3i, CellDistances> m_cells;
+};
+
+using DistanceFieldPtr = std::shared_ptr<const DistanceField>;
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/DistanceField.tcc"
+
+#endif // LVR2_RECONSTRUCTION_DISTANCEFIELD_HPP
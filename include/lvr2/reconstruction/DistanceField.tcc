@@ -0,0 +1,57 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * DistanceField.tcc
+ */
+
+#include <limits>
+
+namespace lvr2
+{
+
+template<typename BaseVecT, typename BoxT>
+DistanceField::DistanceField(const HashGrid<BaseVecT, BoxT>& grid)
+    : m_voxelSize(grid.getVoxelsize())
+{
+    const auto& queryPoints = grid.getQueryPoints();
+    m_cells.reserve(grid.getNumberOfCells());
+    for (const auto& [index, cell] : grid.getCells())
+    {
+        CellDistances distances;
+        for (int i = 0; i < 8; i++)
+        {
+            const auto& qp = queryPoints[cell->getVertex(i)];
+            distances[i] = qp.m_invalid ? std::numeric_limits<float>::quiet_NaN() : qp.m_distance;
+        }
+        m_cells.emplace(index, distances);
+    }
+}
+
+} // namespace lvr2
@@ -215,6 +215,11 @@ public:
      */
     size_t getNumberOfCells() const { return m_cells.size(); }
 
+    /***
+     * @brief   Returns the edge length of the cells.
+     */
+    float getVoxelsize() const { return m_voxelsize; }
+
     /**
      * @return  Returns an iterator to the first box in the cell map.
      */
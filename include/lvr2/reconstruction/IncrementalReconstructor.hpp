@@ -0,0 +1,131 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IncrementalReconstructor.hpp
+ *
+ * Frame-to-model reconstruction: successive registered point batches are
+ * fused into a sparse truncated signed distance field, from which the
+ * current mesh can be extracted at any time.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_INCREMENTALRECONSTRUCTOR_HPP
+#define LVR2_RECONSTRUCTION_INCREMENTALRECONSTRUCTOR_HPP
+
+#include "lvr2/geometry/PMPMesh.hpp"
+#include "lvr2/reconstruction/FastBox.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <unordered_map>
+
+namespace lvr2
+{
+
+struct IncrementalReconstructionOptions
+{
+    /// Voxel size of the distance field
+    float voxelSize = 10;
+
+    /// Maximum distance between a point and the lattice points it updates.
+    /// Defaults to twice the voxel size if <= 0.
+    float truncation = 0;
+
+    /// Size of k-neighborhood used to estimate normals of batches without normals
+    int kn = 10;
+
+    /// Minimum accumulated weight of a lattice point to be used for meshing
+    float minWeight = 0.5;
+};
+
+/**
+ * @brief Fuses registered point batches into a sparse signed distance
+ *        field and extracts meshes with marching cubes on demand.
+ *
+ *        Each point updates the lattice points within the truncation
+ *        distance with its point-to-plane distance, weighted by the
+ *        distance to the point. The field is a running weighted average,
+ *        so later batches refine the surface instead of replacing it.
+ *
+ * @tparam BaseVecT The vector type of the extracted mesh
+ * @tparam BoxT     The marching cubes box type. Boxes that need a surface
+ *                  (PMC, SF) are not supported.
+ */
+template<typename BaseVecT, typename BoxT = FastBox<BaseVecT>>
+class IncrementalReconstructor
+{
+public:
+    explicit IncrementalReconstructor(const IncrementalReconstructionOptions& options);
+
+    /**
+     * @brief Integrates a batch of points into the distance field.
+     *
+     * @param batch The points in the sensor frame. If the buffer contains
+     *              no normals, they are estimated and oriented towards the
+     *              sensor origin.
+     * @param pose  Transformation from the sensor frame to the model frame
+     */
+    void integrate(PointBufferPtr batch, const Transformd& pose = Transformd::Identity());
+
+    /// Extracts a mesh of the current state of the distance field
+    PMPMesh<BaseVecT> extractMesh() const;
+
+    /// Number of integrated batches
+    size_t numBatches() const { return m_numBatches; }
+
+    /// Number of lattice points with a distance value
+    size_t numLatticePoints() const { return m_lattice.size(); }
+
+    /// Removes all integrated data
+    void clear();
+
+private:
+    struct LatticeValue
+    {
+        /// Sum of weighted distances
+        float distance = 0;
+
+        /// Sum of weights
+        float weight = 0;
+    };
+
+    using Lattice = std::unordered_map<Vector3i, LatticeValue>;
+
+    IncrementalReconstructionOptions m_options;
+
+    /// Lattice point (i, j, k) is located at (i, j, k) * voxelSize
+    Lattice m_lattice;
+
+    size_t m_numBatches = 0;
+};
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/IncrementalReconstructor.tcc"
+
+#endif // LVR2_RECONSTRUCTION_INCREMENTALRECONSTRUCTOR_HPP
@@ -0,0 +1,228 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IncrementalReconstructor.tcc
+ */
+
+#include "lvr2/geometry/BoundingBox.hpp"
+#include "lvr2/reconstruction/AdaptiveKSearchSurface.hpp"
+#include "lvr2/reconstruction/FastReconstruction.hpp"
+#include "lvr2/reconstruction/FastReconstructionTables.hpp"
+#include "lvr2/reconstruction/HashGrid.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <unordered_set>
+
+namespace lvr2
+{
+
+template<typename BaseVecT, typename BoxT>
+IncrementalReconstructor<BaseVecT, BoxT>::IncrementalReconstructor(const IncrementalReconstructionOptions& options)
+    : m_options(options)
+{
+    if(m_options.truncation <= 0)
+    {
+        m_options.truncation = 2 * m_options.voxelSize;
+    }
+}
+
+template<typename BaseVecT, typename BoxT>
+void IncrementalReconstructor<BaseVecT, BoxT>::integrate(PointBufferPtr batch, const Transformd& pose)
+{
+    if(!batch || batch->numPoints() == 0)
+    {
+        return;
+    }
+
+    if(!batch->hasNormals())
+    {
+        // Estimate normals in the sensor frame, so they can be oriented
+        // towards the sensor
+        AdaptiveKSearchSurface<BaseVecT> surface(batch, "flann", m_options.kn, m_options.kn, m_options.kn);
+        surface.setFlipPoint(BaseVecT(0, 0, 0));
+        surface.calculateSurfaceNormals();
+    }
+
+    const size_t n = batch->numPoints();
+    floatArr points = batch->getPointArray();
+    floatArr normals = batch->getNormalArray();
+
+    const Eigen::Matrix3d rotation = pose.block<3, 3>(0, 0);
+    const Eigen::Vector3d translation = pose.block<3, 1>(0, 3);
+    const float voxelSize = m_options.voxelSize;
+    const float truncation = m_options.truncation;
+    const int range = std::ceil(truncation / voxelSize);
+
+    #pragma omp parallel
+    {
+        Lattice local;
+
+        #pragma omp for schedule(dynamic, 1024) nowait
+        for(size_t i = 0; i < n; i++)
+        {
+            Eigen::Vector3d p = rotation * Eigen::Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]) + translation;
+            Eigen::Vector3d normal = rotation * Eigen::Vector3d(normals[3 * i], normals[3 * i + 1], normals[3 * i + 2]);
+            if(!normal.allFinite() || normal.squaredNorm() == 0)
+            {
+                continue;
+            }
+            normal.normalize();
+
+            Vector3i base(
+                std::floor(p.x() / voxelSize),
+                std::floor(p.y() / voxelSize),
+                std::floor(p.z() / voxelSize)
+            );
+            for(int dx = -range; dx <= range + 1; dx++)
+            {
+                for(int dy = -range; dy <= range + 1; dy++)
+                {
+                    for(int dz = -range; dz <= range + 1; dz++)
+                    {
+                        Vector3i index = base + Vector3i(dx, dy, dz);
+                        Eigen::Vector3d position = index.cast<double>() * voxelSize;
+                        double dist = (position - p).norm();
+                        if(dist > truncation)
+                        {
+                            continue;
+                        }
+
+                        // Lattice points close to the point are more reliable
+                        float weight = 1.0 - dist / truncation;
+                        LatticeValue& value = local[index];
+                        value.distance += weight * normal.dot(position - p);
+                        value.weight += weight;
+                    }
+                }
+            }
+        }
+
+        #pragma omp critical
+        {
+            for(const auto& [index, value] : local)
+            {
+                LatticeValue& global = m_lattice[index];
+                global.distance += value.distance;
+                global.weight += value.weight;
+            }
+        }
+    }
+
+    m_numBatches++;
+    lvr2::logout::get() << lvr2::info << "[IncrementalReconstructor] Integrated batch " << m_numBatches
+                        << " with " << n << " points. Lattice points: " << m_lattice.size() << lvr2::endl;
+}
+
+template<typename BaseVecT, typename BoxT>
+PMPMesh<BaseVecT> IncrementalReconstructor<BaseVecT, BoxT>::extractMesh() const
+{
+    PMPMesh<BaseVecT> mesh;
+    const float voxelSize = m_options.voxelSize;
+
+    auto valid = [&](const Vector3i& index, float& distance)
+    {
+        auto it = m_lattice.find(index);
+        if(it == m_lattice.end() || it->second.weight < m_options.minWeight)
+        {
+            return false;
+        }
+        distance = it->second.distance / it->second.weight;
+        return true;
+    };
+
+    // Every lattice point is the minimal corner of one cell and the other
+    // corners of seven more. Collect all candidates, keep those with
+    // eight valid corners.
+    std::unordered_set<Vector3i> candidates;
+    for(const auto& entry : m_lattice)
+    {
+        for(int j = 0; j < 8; j++)
+        {
+            candidates.insert(entry.first - Vector3i(j & 1, (j >> 1) & 1, (j >> 2) & 1));
+        }
+    }
+
+    std::vector<float> centers;
+    std::vector<float> distances;
+    BoundingBox<BaseVecT> bb;
+    for(const Vector3i& cell : candidates)
+    {
+        float cellDistances[8];
+        bool complete = true;
+        for(int j = 0; j < 8 && complete; j++)
+        {
+            // Cell (i, j, k) spans from lattice point (i, j, k) to (i + 1, j + 1, k + 1)
+            Vector3i corner = cell + Vector3i(
+                (box_creation_table[j][0] + 1) / 2,
+                (box_creation_table[j][1] + 1) / 2,
+                (box_creation_table[j][2] + 1) / 2
+            );
+            complete = valid(corner, cellDistances[j]);
+        }
+        if(!complete)
+        {
+            continue;
+        }
+
+        BaseVecT center((cell.x() + 0.5f) * voxelSize, (cell.y() + 0.5f) * voxelSize, (cell.z() + 0.5f) * voxelSize);
+        bb.expand(center);
+        centers.insert(centers.end(), {center.x, center.y, center.z});
+        distances.insert(distances.end(), cellDistances, cellDistances + 8);
+    }
+
+    const size_t numCells = centers.size() / 3;
+    if(numCells == 0)
+    {
+        return mesh;
+    }
+
+    floatArr centerArr(new float[centers.size()]);
+    floatArr distanceArr(new float[distances.size()]);
+    std::copy(centers.begin(), centers.end(), centerArr.get());
+    std::copy(distances.begin(), distances.end(), distanceArr.get());
+    auto cells = std::make_shared<PointBuffer>(centerArr, numCells);
+    cells->addFloatChannel(distanceArr, "tsdf_values", numCells, 8);
+
+    BoxT::m_voxelsize = voxelSize;
+    auto grid = std::make_shared<HashGrid<BaseVecT, BoxT>>(cells, bb, voxelSize);
+    FastReconstruction<BaseVecT, BoxT> reconstruction(grid);
+    reconstruction.getMesh(mesh);
+    return mesh;
+}
+
+template<typename BaseVecT, typename BoxT>
+void IncrementalReconstructor<BaseVecT, BoxT>::clear()
+{
+    m_lattice.clear();
+    m_numBatches = 0;
+}
+
+} // namespace lvr2
@@ -0,0 +1,112 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IndexCache.hpp
+ *
+ * Binary caches for search trees and reconstruction grids. Cache files are
+ * keyed by a hash of the input data and parameters, so repeated runs on
+ * the same data can skip the construction of these structures.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_INDEXCACHE_HPP
+#define LVR2_RECONSTRUCTION_INDEXCACHE_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <cstdint>
+#include <string>
+
+namespace lvr2
+{
+
+/**
+ * @brief Incrementally hashes the given bytes (64 bit FNV-1a).
+ *
+ * @param data  The data to hash
+ * @param bytes Number of bytes
+ * @param seed  Result of a previous call or the default offset basis
+ */
+uint64_t hashBytes(const void* data, size_t bytes, uint64_t seed = 14695981039346656037ULL);
+
+/// Convenience overload of hashBytes() for trivially copyable values
+template<typename T>
+uint64_t hashValue(const T& value, uint64_t seed)
+{
+    return hashBytes(&value, sizeof(T), seed);
+}
+
+/**
+ * @brief Hashes the number of points and their coordinates and,
+ *        if requested and present, the normals.
+ */
+uint64_t hashPointBuffer(PointBufferPtr buffer, bool includeNormals = false);
+
+/**
+ * @brief Returns the path of the cache file for the given key, i.e.
+ *        directory/<prefix>_<hex key>.cache
+ */
+boost::filesystem::path indexCacheFile(
+    const boost::filesystem::path& directory,
+    const std::string& prefix,
+    uint64_t key
+);
+
+/**
+ * @brief Returns a unique temporary path next to the given cache file.
+ *        Caches are written there first and moved into place with
+ *        commitCacheFile(), so interrupted runs never leave partial files.
+ */
+boost::filesystem::path temporaryCacheFile(const boost::filesystem::path& file);
+
+/**
+ * @brief Atomically replaces file with tmp. Removes tmp on failure.
+ *
+ * @return true on success
+ */
+bool commitCacheFile(const boost::filesystem::path& tmp, const boost::filesystem::path& file);
+
+/**
+ * @brief Writes a grid created with HashGrid::toPointBuffer() to a binary
+ *        cache file. Can be restored with loadGridCache().
+ */
+void saveGridCache(PointBufferPtr grid, const boost::filesystem::path& file);
+
+/**
+ * @brief Reads a grid written by saveGridCache(). Pass the result to
+ *        HashGrid(PointBufferPtr, ...) to restore the grid.
+ *
+ * @return The grid buffer or nullptr if the file does not exist or is invalid
+ */
+PointBufferPtr loadGridCache(const boost::filesystem::path& file);
+
+} // namespace lvr2
+
+#endif // LVR2_RECONSTRUCTION_INDEXCACHE_HPP
@@ -0,0 +1,82 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MemoryEstimate.hpp
+ *
+ * A rough prediction of the peak memory of a reconstruction, used to
+ * decide whether a point cloud can be processed at once or has to be split
+ * into chunks, see ReconstructionOptions::memoryBudget.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_MEMORYESTIMATE_HPP
+#define LVR2_RECONSTRUCTION_MEMORYESTIMATE_HPP
+
+#include "lvr2/reconstruction/Reconstruction.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <cstddef>
+
+namespace lvr2
+{
+
+/**
+ * @brief Predicted memory usage of the parts of a reconstruction in bytes
+ */
+struct MemoryEstimate
+{
+    /// Point coordinates, normals and the other channels of the input
+    size_t points = 0;
+
+    /// Search tree for neighborhood queries
+    size_t searchTree = 0;
+
+    /// Cells and query points of the reconstruction grid
+    size_t grid = 0;
+
+    /// The extracted mesh
+    size_t mesh = 0;
+
+    /// Number of grid cells that contain points
+    size_t occupiedCells = 0;
+
+    /// All parts are alive at the same time during mesh extraction
+    size_t peak() const { return points + searchTree + grid + mesh; }
+};
+
+/**
+ * @brief Predicts the peak memory of reconstructing the given point cloud
+ *        with reconstruct(). The grid and mesh sizes are derived from the
+ *        number of voxels that contain points, so the estimate is most
+ *        accurate for surface-like data.
+ */
+MemoryEstimate estimateMemory(PointBufferPtr buffer, const ReconstructionOptions& options);
+
+} // namespace lvr2
+
+#endif // LVR2_RECONSTRUCTION_MEMORYESTIMATE_HPP
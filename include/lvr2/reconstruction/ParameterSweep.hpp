@@ -0,0 +1,127 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * ParameterSweep.hpp
+ *
+ * Runs the reconstruction for all combinations of a set of parameters
+ * and ranks the resulting meshes by their distance to the input points.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_PARAMETERSWEEP_HPP
+#define LVR2_RECONSTRUCTION_PARAMETERSWEEP_HPP
+
+#include "lvr2/reconstruction/Reconstruction.hpp"
+#include "lvr2/reconstruction/metrics/PointToMeshMetric.hpp"
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief The parameter values to test. An empty list means that the
+ *        value of the base options is used.
+ */
+struct ParameterGrid
+{
+    /// Voxel sizes
+    std::vector<float>  voxelSizes;
+
+    /// Sizes of the k-neighborhood used for normal estimation
+    std::vector<int>    kn;
+
+    /// Number of points used for distance function evaluation
+    std::vector<int>    kd;
+};
+
+/**
+ * @brief Result of a single reconstruction of a parameter sweep
+ */
+struct SweepEntry
+{
+    /// Options used for the reconstruction
+    ReconstructionOptions       options;
+
+    /// Distance of the input points to the reconstructed mesh
+    PointToMeshError            error;
+
+    /// Number of vertices of the mesh
+    size_t                      numVertices = 0;
+
+    /// Number of faces of the mesh
+    size_t                      numFaces = 0;
+
+    /// Wall time of the reconstruction in seconds
+    double                      seconds = 0.0;
+
+    /// Warnings reported by the reconstruction
+    std::vector<std::string>    warnings;
+
+    /// Message of the ReconstructionError if the reconstruction failed, empty otherwise
+    std::string                 failure;
+
+    bool failed() const { return !failure.empty(); }
+};
+
+/**
+ * @brief Results of a parameter sweep
+ */
+struct SweepReport
+{
+    /// All entries, sorted by ascending RMS error. Failed runs are placed at the end.
+    std::vector<SweepEntry> entries;
+
+    /// The entry with the lowest error. Must not be called if all runs failed.
+    const SweepEntry& best() const;
+};
+
+/**
+ * @brief Reconstructs the given point cloud with all parameter combinations
+ *        of the given grid and ranks the results by the point to mesh error.
+ *
+ *        The runs are executed in parallel using OpenMP. Decompositions that
+ *        keep the surface in static state (PMC and SF) are run sequentially.
+ *        Each run works on its own copy of the input points.
+ *
+ * @param points    The input point cloud. Not modified.
+ * @param base      Options for all parameters that are not part of the grid
+ * @param grid      Parameter values to test
+ */
+template<typename BaseVecT, typename MeshT = PMPMesh<BaseVecT>>
+SweepReport parameterSweep(
+    PointBufferPtr points,
+    const ReconstructionOptions& base,
+    const ParameterGrid& grid
+);
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/ParameterSweep.tcc"
+
+#endif // LVR2_RECONSTRUCTION_PARAMETERSWEEP_HPP
@@ -0,0 +1,135 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * ParameterSweep.tcc
+ */
+
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Timestamp.hpp"
+
+#include <algorithm>
+
+namespace lvr2
+{
+
+inline const SweepEntry& SweepReport::best() const
+{
+    if(entries.empty() || entries.front().failed())
+    {
+        throw std::logic_error("[SweepReport] No successful reconstruction");
+    }
+    return entries.front();
+}
+
+template<typename BaseVecT, typename MeshT>
+SweepReport parameterSweep(
+    PointBufferPtr points,
+    const ReconstructionOptions& base,
+    const ParameterGrid& grid)
+{
+    std::vector<float> voxelSizes = grid.voxelSizes.empty() ? std::vector<float>{base.voxelSize} : grid.voxelSizes;
+    std::vector<int> kn = grid.kn.empty() ? std::vector<int>{base.kn} : grid.kn;
+    std::vector<int> kd = grid.kd.empty() ? std::vector<int>{base.kd} : grid.kd;
+
+    SweepReport report;
+    for(float voxelSize : voxelSizes)
+    {
+        for(int k : kn)
+        {
+            for(int d : kd)
+            {
+                SweepEntry entry;
+                entry.options = base;
+                entry.options.voxelSize = voxelSize;
+                entry.options.kn = k;
+                entry.options.kd = d;
+                report.entries.push_back(entry);
+            }
+        }
+    }
+
+    // PMC and SF store the surface in a static member of the box type
+    const bool parallel = base.decomposition == "MC" || base.decomposition == "MT";
+
+    lvr2::logout::get() << lvr2::info << "[ParameterSweep] Running " << report.entries.size()
+        << " reconstructions" << (parallel ? " in parallel" : "") << lvr2::endl;
+
+    const long numEntries = report.entries.size();
+    #pragma omp parallel for schedule(dynamic) if(parallel)
+    for(long i = 0; i < numEntries; i++)
+    {
+        SweepEntry& entry = report.entries[i];
+        PointBufferPtr copy = std::make_shared<PointBuffer>(points->clone());
+
+        Timestamp ts;
+        try
+        {
+            auto result = reconstruct<BaseVecT, MeshT>(copy, entry.options);
+            MeshBufferPtr mesh = result.mesh.toMeshBuffer();
+            entry.seconds = ts.getElapsedTimeInS();
+            entry.numVertices = result.mesh.numVertices();
+            entry.numFaces = result.mesh.numFaces();
+            entry.warnings = result.warnings;
+            entry.error = pointToMeshError<BaseVecT>(points, mesh, 5, base.searchTree);
+        }
+        catch(const ReconstructionError& e)
+        {
+            entry.seconds = ts.getElapsedTimeInS();
+            entry.failure = e.what();
+        }
+    }
+
+    std::stable_sort(report.entries.begin(), report.entries.end(),
+        [](const SweepEntry& a, const SweepEntry& b)
+        {
+            if(a.failed() != b.failed())
+            {
+                return b.failed();
+            }
+            return a.error.rms < b.error.rms;
+        });
+
+    for(const SweepEntry& e : report.entries)
+    {
+        lvr2::logout::get() << lvr2::info << "[ParameterSweep] voxel size " << e.options.voxelSize
+            << ", kn " << e.options.kn << ", kd " << e.options.kd << ": ";
+        if(e.failed())
+        {
+            lvr2::logout::get() << e.failure << lvr2::endl;
+        }
+        else
+        {
+            lvr2::logout::get() << "rms " << e.error.rms << ", max " << e.error.max
+                << ", " << e.numFaces << " faces, " << e.seconds << "s" << lvr2::endl;
+        }
+    }
+
+    return report;
+}
+
+} // namespace lvr2
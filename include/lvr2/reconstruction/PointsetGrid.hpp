@@ -40,6 +40,8 @@
 #include "PointsetSurface.hpp"
 #include "lvr2/geometry/BoundingBox.hpp"
 
+#include <functional>
+
 namespace lvr2
 {
 
@@ -64,15 +66,88 @@ public:
         bool extrude = true
     );
 
+    /**
+     * @brief Construct a grid that contains exactly the given cells, e.g.
+     *        the result of surfaceBandCells()
+     *
+     * @param voxelsize the voxel size
+     * @param surface the surface to be used for the grid
+     * @param bb the bounding box of the grid
+     * @param cells the indices of the cells
+     */
+    PointsetGrid(
+        float voxelsize,
+        PointsetSurfacePtr<BaseVecT> surface,
+        BoundingBox<BaseVecT> bb,
+        const std::unordered_set<Vector3i>& cells
+    );
+
     virtual ~PointsetGrid() {}
 
     void calcDistanceValues();
 
+    /**
+     * @brief Clamps the projected distances to [-distance, distance] to
+     *        avoid large, unreliable values far from the data. Disabled if
+     *        <= 0 (default).
+     */
+    void setTruncationDistance(float distance) { m_truncationDistance = distance; }
+
+    /**
+     * @brief Sets the maximum euclidean distance of a cell corner to the
+     *        nearest points. Cells with a corner beyond this distance are
+     *        removed in calcDistanceValues(). Defaults to 1.75 * voxelsize
+     *        if <= 0. Smaller values than sqrt(3) * voxelsize cause holes.
+     */
+    void setMaxDistance(float distance) { m_maxDistance = distance; }
+
+    /**
+     * @brief Sets a function that is called for every query point in
+     *        calcDistanceValues() after its distance was calculated and
+     *        before the cells with invalid corners are removed. Grids that
+     *        share query points, e.g. neighboring chunks, can use it to
+     *        replace the values with the ones of the other grid.
+     */
+    void setDistanceFilter(std::function<void(QueryPoint<BaseVecT>&)> filter) { m_distanceFilter = filter; }
+
 private:
 
     PointsetSurfacePtr<BaseVecT> m_surface;
+
+    /// See setTruncationDistance()
+    float m_truncationDistance = 0;
+
+    /// See setMaxDistance()
+    float m_maxDistance = 0;
+
+    /// See setDistanceFilter()
+    std::function<void(QueryPoint<BaseVecT>&)> m_distanceFilter;
 };
 
+/**
+ * @brief Coarse to fine search for the cells of a grid with the given voxel
+ *        size that are intersected by the surface.
+ *
+ *        The cells containing points are determined at a voxel size of
+ *        voxelsize * 2^levels. In each refinement step, every cell is split
+ *        into its eight children and only children whose center is closer
+ *        to the surface than their size are kept. The distance function is
+ *        therefore only evaluated in a narrow band around the surface
+ *        instead of in all cells that contain (possibly noisy) points.
+ *
+ * @param surface the surface
+ * @param voxelsize the voxel size of the finest level
+ * @param levels the number of refinement steps
+ * @param extrude add cells around the cells of the finest level
+ */
+template<typename BaseVecT>
+std::unordered_set<Vector3i> surfaceBandCells(
+    PointsetSurfacePtr<BaseVecT> surface,
+    float voxelsize,
+    int levels,
+    bool extrude = true
+);
+
 } // namespace lvr2
 
 #include "lvr2/reconstruction/PointsetGrid.tcc"
@@ -35,6 +35,9 @@
 #include "lvr2/util/Logging.hpp"
 #include "lvr2/util/Progress.hpp"
 
+#include <algorithm>
+#include <cmath>
+
 namespace lvr2
 {
 
@@ -108,12 +111,131 @@ PointsetGrid<BaseVecT, BoxT>::PointsetGrid(
     this->addLatticePoints(requiredCells);
 }
 
+template<typename BaseVecT, typename BoxT>
+PointsetGrid<BaseVecT, BoxT>::PointsetGrid(
+    float voxelsize,
+    PointsetSurfacePtr<BaseVecT> surface,
+    BoundingBox<BaseVecT> bb,
+    const std::unordered_set<Vector3i>& cells
+) :
+    HashGrid<BaseVecT, BoxT>(voxelsize, bb, true, false),
+    m_surface(surface)
+{
+    this->addLatticePoints(cells);
+}
+
+template<typename BaseVecT>
+std::unordered_set<Vector3i> surfaceBandCells(
+    PointsetSurfacePtr<BaseVecT> surface,
+    float voxelsize,
+    int levels,
+    bool extrude
+)
+{
+    const BoundingBox<BaseVecT> bb = surface->getBoundingBox();
+    FloatChannel pts = *(surface->pointBuffer()->getFloatChannel("points"));
+    const size_t numPoints = surface->pointBuffer()->numPoints();
+
+    auto addNeighbors = [&bb](std::unordered_set<Vector3i>& cells, float size)
+    {
+        std::unordered_set<Vector3i> inner = cells;
+        for (const Vector3i& index : inner)
+        {
+            for (int dx = -1; dx <= 1; dx++)
+            {
+                for (int dy = -1; dy <= 1; dy++)
+                {
+                    for (int dz = -1; dz <= 1; dz++)
+                    {
+                        Vector3i pos = index + Vector3i(dx, dy, dz);
+                        BaseVecT center((pos.x() + 0.5f) * size, (pos.y() + 0.5f) * size, (pos.z() + 0.5f) * size);
+                        if (bb.contains(center))
+                        {
+                            cells.insert(pos);
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // Occupied cells of the coarsest level. Their neighbors are added so
+    // that no surface part close to a cell border is missed.
+    float size = voxelsize * (1 << std::max(levels, 0));
+    std::unordered_set<Vector3i> cells;
+    for (size_t i = 0; i < numPoints; i++)
+    {
+        BaseVecT point = pts[i];
+        cells.insert(Vector3i(std::floor(point.x / size), std::floor(point.y / size), std::floor(point.z / size)));
+    }
+    addNeighbors(cells, size);
+    const size_t numCoarseCells = cells.size();
+
+    for (int level = levels; level > 0; level--)
+    {
+        const float childSize = size / 2;
+        const std::vector<Vector3i> parents(cells.begin(), cells.end());
+        std::unordered_set<Vector3i> children;
+
+        #pragma omp parallel
+        {
+            std::unordered_set<Vector3i> localChildren;
+            #pragma omp for schedule(dynamic, 64) nowait
+            for (size_t i = 0; i < parents.size(); i++)
+            {
+                for (int child = 0; child < 8; child++)
+                {
+                    Vector3i index = parents[i] * 2 + Vector3i(child & 1, (child >> 1) & 1, (child >> 2) & 1);
+                    BaseVecT center((index.x() + 0.5f) * childSize, (index.y() + 0.5f) * childSize, (index.z() + 0.5f) * childSize);
+                    if (!bb.contains(center))
+                    {
+                        continue;
+                    }
+
+                    // The surface intersects the cell if it is closer than half the
+                    // cell diagonal (~0.87 * size) to its center. Points farther away than
+                    // 1.75 voxels are invalid in calcDistanceValues(), see there.
+                    float projectedDistance;
+                    float euklideanDistance;
+                    std::tie(projectedDistance, euklideanDistance) = surface->distance(center);
+                    if (std::abs(projectedDistance) <= childSize && euklideanDistance <= 1.75f * childSize)
+                    {
+                        localChildren.insert(index);
+                    }
+                }
+            }
+
+            #pragma omp critical
+            {
+                children.insert(localChildren.begin(), localChildren.end());
+            }
+        }
+
+        cells = std::move(children);
+        size = childSize;
+    }
+
+    if (extrude)
+    {
+        addNeighbors(cells, size);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[PointsetGrid] Refined " << numCoarseCells << " coarse cells to "
+                        << cells.size() << " surface cells in " << levels << " levels" << lvr2::endl;
+    return cells;
+}
+
 template<typename BaseVecT, typename BoxT>
 void PointsetGrid<BaseVecT, BoxT>::calcDistanceValues()
 {
     const int max_threads = omp_get_max_threads();
     const int used_threads = max_threads;
 
+    // the mesh gets holes for if this value is set to something < 1.7320508075688772
+    // it stays consistent for everything > 1.7320508075688772, however, the runtime gets worse
+    // so: 1.75
+    const float maxDistance = m_maxDistance > 0 ? m_maxDistance : 1.75 * this->m_voxelsize;
+
     // Status message output
     lvr2::Monitor progress(lvr2::LogLevel::info, "Calculating distance values", this->m_queryPoints.size());
     // lvr2::PacmanProgressBar progress(this->m_queryPoints.size() / used_threads, "[PointsetGrid] Calculating Distance Values.");
@@ -132,15 +254,16 @@ void PointsetGrid<BaseVecT, BoxT>::calcDistanceValues()
             this->m_surface->distance(this->m_queryPoints[i].m_position);
         // if (euklideanDistance > 10 * this->m_voxelsize)
 
-        // the mesh gets holes for if this value is set to something < 1.7320508075688772
-        // it stays consistent for everything > 1.7320508075688772, however, the runtime gets worse
-        // so: 1.75
-        if (euklideanDistance > 1.75 * this->m_voxelsize)
+        if (!std::isfinite(euklideanDistance) || euklideanDistance > maxDistance)
         {
             this->m_queryPoints[i].m_invalid = true;
         } else {
             this->m_queryPoints[i].m_invalid = false;
         }
+        if (m_truncationDistance > 0)
+        {
+            projectedDistance = std::clamp(projectedDistance, -m_truncationDistance, m_truncationDistance);
+        }
         this->m_queryPoints[i].m_distance = projectedDistance;
         // if(omp_get_thread_num() == 0)
         // {
@@ -150,6 +273,14 @@ void PointsetGrid<BaseVecT, BoxT>::calcDistanceValues()
     // std::cout << std::endl;
     progress.terminate();
 
+    if (m_distanceFilter)
+    {
+        for (auto& qp : this->m_queryPoints)
+        {
+            m_distanceFilter(qp);
+        }
+    }
+
     // remove cells with invalid corners
     auto it = this->m_cells.begin();
     while (it != this->m_cells.end())
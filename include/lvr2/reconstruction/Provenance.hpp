@@ -0,0 +1,83 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Provenance.hpp
+ *
+ * Metadata that describes how a reconstruction was created (library
+ * version, input file hash and the full set of options). It can be
+ * embedded into saved meshes as PLY comments (Model::m_metadata), glTF
+ * extras (saveGlbLODs()) or HDF5 attributes (meshio::MeshIO::saveMesh()),
+ * so results can be reproduced and audited later.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_PROVENANCE_HPP
+#define LVR2_RECONSTRUCTION_PROVENANCE_HPP
+
+#include "lvr2/reconstruction/Reconstruction.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <map>
+#include <string>
+
+namespace lvr2
+{
+
+/**
+ * @brief Returns the version of the library, e.g. "25.2.3"
+ */
+std::string lvr2Version();
+
+/**
+ * @brief Returns the 64 bit FNV-1a hash of the content of the given file
+ *        as 16 hexadecimal digits or an empty string if it can't be read.
+ *        For directories, the relative paths and contents of all files
+ *        below it are hashed in lexicographical order.
+ */
+std::string hashFile(const boost::filesystem::path& file);
+
+/**
+ * @brief Serializes all fields of the given options into a JSON object
+ */
+std::string reconstructionOptionsToJson(const ReconstructionOptions& options);
+
+/**
+ * @brief Collects the provenance of a reconstruction: the keys "generator",
+ *        "lvr2_version", "timestamp" (UTC, ISO 8601), "options" (JSON) and,
+ *        if an input file is given, "input_file" and "input_hash" (prefixed
+ *        with "fnv1a64:", see hashFile(), omitted if it can't be read).
+ */
+std::map<std::string, std::string> reconstructionProvenance(
+    const ReconstructionOptions& options,
+    const boost::filesystem::path& inputFile = boost::filesystem::path()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_RECONSTRUCTION_PROVENANCE_HPP
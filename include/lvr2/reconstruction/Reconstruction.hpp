@@ -0,0 +1,226 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * Reconstruction.hpp
+ *
+ * A library entry point for the standard point cloud to mesh pipeline:
+ * normal estimation, distance function evaluation and marching cubes style
+ * surface extraction.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_RECONSTRUCTION_HPP
+#define LVR2_RECONSTRUCTION_RECONSTRUCTION_HPP
+
+#include "lvr2/algorithm/NormalAlgorithms.hpp"
+#include "lvr2/attrmaps/AttrMaps.hpp"
+#include "lvr2/geometry/Normal.hpp"
+#include "lvr2/geometry/PMPMesh.hpp"
+#include "lvr2/reconstruction/AdaptiveKSearchSurface.hpp"
+#include "lvr2/reconstruction/DistanceField.hpp"
+#include "lvr2/reconstruction/HashGrid.hpp"
+#include "lvr2/reconstruction/ReconstructionError.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <memory>
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/// Origin that is used to label the faces of a reconstruction
+enum class FaceRegionType
+{
+    /// No labels are computed
+    None,
+    /// Chunk of the reconstruction grid that contains the face centroid
+    Chunk,
+    /// Planar cluster the face belongs to
+    Cluster
+};
+
+struct ReconstructionOptions
+{
+    /// Decomposition type: "MC", "PMC", "MT" or "SF"
+    std::string decomposition = "PMC";
+
+    /// Voxel size of the reconstruction grid
+    float voxelSize = 10;
+
+    /// Extend the grid. Might avoid additional holes in sparse data sets, but can cause
+    /// artifacts in dense data sets.
+    bool extrude = false;
+
+    /// Number of coarse to fine refinement steps used to find the cells that
+    /// are intersected by the surface, see surfaceBandCells(). The coarsest
+    /// grid has a voxel size of voxelSize * 2^refinementLevels. Disabled if 0.
+    int refinementLevels = 0;
+
+    /// Projected distances are clamped to [-truncationDistance, truncationDistance].
+    /// Disabled if <= 0.
+    float truncationDistance = 0;
+
+    /// Cells with a corner whose distance to the nearest points exceeds this
+    /// value are skipped. Defaults to 1.75 * voxelSize if <= 0.
+    float maxDistance = 0;
+
+    /// Search tree used for all neighborhood queries
+    std::string searchTree = "FLANN";
+
+    /// Size of k-neighborhood used for normal estimation
+    int kn = 10;
+
+    /// Number of normals used in the normal interpolation process. Also used
+    /// to interpolate the vertex normals of the mesh from the point normals.
+    /// If 0, the vertex normals are averaged from the adjacent faces.
+    int ki = 10;
+
+    /// Weighting of the face normals if the vertex normals are averaged from the
+    /// adjacent faces, see ki
+    VertexNormalWeighting normalWeighting = VertexNormalWeighting::Uniform;
+
+    /// Number of points used for distance function evaluation
+    int kd = 5;
+
+    /// Normal estimation method
+    NormalEstimationMethod normalMethod = NormalEstimationMethod::PCA;
+
+    /// Recalculate normals even if the input already contains normals
+    bool recalcNormals = false;
+
+    /// Float channel with per point weights for normal estimation and the
+    /// distance function, e.g. from scanner quality or range. Points with a
+    /// low confidence contribute less to the surface. Ignored if the channel
+    /// does not exist, disabled if empty.
+    std::string confidenceChannel = "confidence";
+
+    /// Point to flip normals towards. The bounding box centroid is used if empty.
+    std::vector<float> flipPoint;
+
+    /// Return incomplete results together with warnings instead of throwing
+    /// a ReconstructionError if a non-fatal problem occurs
+    bool allowPartial = false;
+
+    /// Directory for cached search trees and distance grids, keyed by a hash
+    /// of the input and the relevant parameters. Disabled if empty.
+    std::string cacheDirectory;
+
+    /// Remove faces with fewer than trimMinPoints points within trimRadius
+    /// of their centroid, see trimLowDensityFaces(). Disabled if 0.
+    int trimMinPoints = 0;
+
+    /// Search radius for trimMinPoints. The voxel size is used if <= 0.
+    float trimRadius = 0;
+
+    /// Compute a local point density and residual for each vertex, see
+    /// ReconstructionResult::vertexDensity and ReconstructionResult::vertexResidual
+    bool vertexQuality = false;
+
+    /// Upper bound for the predicted peak memory in bytes, see estimateMemory().
+    /// If the prediction exceeds the budget, the point cloud is split into
+    /// chunks that are reconstructed one after another and merged. Disabled if 0.
+    size_t memoryBudget = 0;
+
+    /// Keep a copy of the distance values of the grid, see ReconstructionResult::distanceField
+    bool keepDistanceField = false;
+
+    /// Label each face with the region it originated from, see ReconstructionResult::faceRegions
+    FaceRegionType faceRegions = FaceRegionType::None;
+
+    /// Edge length of a chunk in voxels for FaceRegionType::Chunk
+    int regionChunkSize = 16;
+
+    /// Minimum sine of the angle between adjacent faces of a cluster for FaceRegionType::Cluster
+    float regionMinSinAngle = 0.85;
+};
+
+template<typename BaseVecT, typename MeshT = PMPMesh<BaseVecT>>
+struct ReconstructionResult
+{
+    /// The reconstructed mesh
+    MeshT mesh;
+
+    /// Vertex normals of the mesh, see ReconstructionOptions::ki
+    DenseVertexMap<Normal<typename BaseVecT::CoordType>> vertexNormals;
+
+    /// The surface that was used for reconstruction. Its point buffer
+    /// contains the (possibly estimated) normals. Empty if the point cloud
+    /// was reconstructed in chunks, see ReconstructionOptions::memoryBudget.
+    PointsetSurfacePtr<BaseVecT> surface;
+
+    /// Problems that were encountered but did not abort the reconstruction
+    std::vector<std::string> warnings;
+
+    /// True, if the mesh is incomplete due to one of the reported warnings
+    bool partial = false;
+
+    /// Number of points per unit area around each vertex if ReconstructionOptions::vertexQuality
+    /// is set, estimated from the distance to the kd nearest points. Can be stored in a buffer
+    /// with SimpleFinalizer::setVertexQuality().
+    DenseVertexMap<float> vertexDensity;
+
+    /// RMS distance of the kd nearest points to the tangent plane of each vertex if
+    /// ReconstructionOptions::vertexQuality is set. Large values indicate noisy or
+    /// inconsistent data.
+    DenseVertexMap<float> vertexResidual;
+
+    /// Signed distance field the mesh was extracted from if ReconstructionOptions::keepDistanceField
+    /// is set. Empty if the point cloud was reconstructed in chunks.
+    DistanceFieldPtr distanceField;
+
+    /// Region label of each face if ReconstructionOptions::faceRegions is set.
+    /// Can be stored in a buffer with SimpleFinalizer::setFaceRegions().
+    DenseFaceMap<uint32_t> faceRegions;
+};
+
+/**
+ * @brief Reconstructs a triangle mesh from the given point cloud.
+ *
+ *        Normals are estimated if the buffer does not contain any
+ *        (or options.recalcNormals is set). The buffer is modified in
+ *        place, no copy of the point data is created.
+ *
+ * @param buffer    The input point cloud
+ * @param options   Reconstruction parameters
+ *
+ * @throws ReconstructionError (or a subclass) if no valid mesh can be
+ *         created. If options.allowPartial is set, only unrecoverable
+ *         errors are thrown and all other problems are reported in
+ *         ReconstructionResult::warnings.
+ */
+template<typename BaseVecT, typename MeshT = PMPMesh<BaseVecT>>
+ReconstructionResult<BaseVecT, MeshT> reconstruct(
+    PointBufferPtr buffer,
+    const ReconstructionOptions& options
+);
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/Reconstruction.tcc"
+
+#endif // LVR2_RECONSTRUCTION_RECONSTRUCTION_HPP
@@ -0,0 +1,790 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * Reconstruction.tcc
+ */
+
+#include "lvr2/algorithm/CleanupAlgorithms.hpp"
+#include "lvr2/algorithm/ClusterAlgorithms.hpp"
+#include "lvr2/algorithm/NormalAlgorithms.hpp"
+#include "lvr2/reconstruction/BilinearFastBox.hpp"
+#include "lvr2/reconstruction/FastBox.hpp"
+#include "lvr2/reconstruction/FastReconstruction.hpp"
+#include "lvr2/reconstruction/IndexCache.hpp"
+#include "lvr2/reconstruction/MemoryEstimate.hpp"
+#include "lvr2/reconstruction/PointsetGrid.hpp"
+#include "lvr2/reconstruction/SharpBox.hpp"
+#include "lvr2/reconstruction/TetraederBox.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <array>
+#include <cmath>
+#include <exception>
+#include <functional>
+#include <limits>
+#include <map>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+template<typename BaseVecT, typename BoxT, typename MeshT>
+void extractSurface(
+    PointsetSurfacePtr<BaseVecT> surface,
+    const ReconstructionOptions& options,
+    ReconstructionResult<BaseVecT, MeshT>& result,
+    const std::function<void(QueryPoint<BaseVecT>&)>& distanceFilter = {})
+{
+    // The distances only depend on the points, their normals and the
+    // parameters of the grid and distance function. Filtered distances
+    // depend on other grids and are not cached.
+    boost::filesystem::path cacheFile;
+    std::shared_ptr<HashGrid<BaseVecT, BoxT>> grid;
+    if(!options.cacheDirectory.empty() && !distanceFilter)
+    {
+        uint64_t key = hashPointBuffer(surface->pointBuffer(), true);
+        key = hashValue(options.voxelSize, key);
+        key = hashValue(options.extrude, key);
+        key = hashValue(options.refinementLevels, key);
+        key = hashValue(options.kd, key);
+        key = hashValue(options.truncationDistance, key);
+        key = hashValue(options.maxDistance, key);
+        if(auto confidence = surface->pointBuffer()->getFloatChannel(options.confidenceChannel))
+        {
+            key = hashBytes(confidence->dataPtr().get(), confidence->numElements() * sizeof(float), key);
+        }
+        cacheFile = indexCacheFile(options.cacheDirectory, "grid", key);
+
+        if(PointBufferPtr cached = loadGridCache(cacheFile))
+        {
+            lvr2::logout::get() << lvr2::info << "[Reconstruction] Loading grid from " << cacheFile << lvr2::endl;
+            BoxT::m_voxelsize = options.voxelSize;
+            grid = std::make_shared<HashGrid<BaseVecT, BoxT>>(cached, surface->getBoundingBox(), options.voxelSize);
+        }
+    }
+
+    if(!grid)
+    {
+        std::shared_ptr<PointsetGrid<BaseVecT, BoxT>> pointsetGrid;
+        if(options.refinementLevels > 0)
+        {
+            pointsetGrid = std::make_shared<PointsetGrid<BaseVecT, BoxT>>(
+                options.voxelSize,
+                surface,
+                surface->getBoundingBox(),
+                surfaceBandCells(surface, options.voxelSize, options.refinementLevels, options.extrude)
+            );
+        }
+        else
+        {
+            pointsetGrid = std::make_shared<PointsetGrid<BaseVecT, BoxT>>(
+                options.voxelSize,
+                surface,
+                surface->getBoundingBox(),
+                true,
+                options.extrude
+            );
+        }
+        pointsetGrid->setTruncationDistance(options.truncationDistance);
+        pointsetGrid->setMaxDistance(options.maxDistance);
+        pointsetGrid->setDistanceFilter(distanceFilter);
+        pointsetGrid->calcDistanceValues();
+        grid = pointsetGrid;
+
+        if(!cacheFile.empty())
+        {
+            saveGridCache(grid->toPointBuffer(), cacheFile);
+        }
+    }
+
+    const size_t numCells = grid->getNumberOfCells();
+    if(numCells == 0)
+    {
+        if(!options.allowPartial)
+        {
+            throw DegenerateGridError(numCells);
+        }
+        result.warnings.push_back(DegenerateGridError(numCells).what());
+        result.partial = true;
+        return;
+    }
+
+    if(options.keepDistanceField)
+    {
+        result.distanceField = std::make_shared<const DistanceField>(*grid);
+    }
+
+    FastReconstruction<BaseVecT, BoxT> reconstruction(grid);
+    reconstruction.getMesh(result.mesh);
+}
+
+/**
+ * @brief Returns the index of the first point with an invalid
+ *        (NaN or zero length) normal or the number of points if
+ *        all normals are valid.
+ */
+inline size_t findInvalidNormal(PointBufferPtr buffer, std::vector<size_t>& valid)
+{
+    const size_t n = buffer->numPoints();
+    FloatChannel normals = *buffer->getFloatChannel("normals");
+
+    size_t first = n;
+    valid.clear();
+    valid.reserve(n);
+    for(size_t i = 0; i < n; i++)
+    {
+        const float x = normals[i][0];
+        const float y = normals[i][1];
+        const float z = normals[i][2];
+        if(std::isfinite(x) && std::isfinite(y) && std::isfinite(z) && (x != 0 || y != 0 || z != 0))
+        {
+            valid.push_back(i);
+        }
+        else if(first == n)
+        {
+            first = i;
+        }
+    }
+    return first;
+}
+
+template<typename BaseVecT>
+PointsetSurfacePtr<BaseVecT> createReconstructionSurface(
+    PointBufferPtr buffer,
+    const ReconstructionOptions& options)
+{
+    auto surface = std::make_shared<AdaptiveKSearchSurface<BaseVecT>>(
+        buffer,
+        options.searchTree,
+        options.kn,
+        options.ki,
+        options.kd,
+        options.normalMethod,
+        "",
+        options.cacheDirectory
+    );
+    surface->setConfidenceChannel(options.confidenceChannel);
+
+    if(options.flipPoint.size() == 3)
+    {
+        surface->setFlipPoint(BaseVecT(options.flipPoint[0], options.flipPoint[1], options.flipPoint[2]));
+    }
+    return surface;
+}
+
+template<typename BaseVecT, typename MeshT>
+void computeVertexNormals(
+    ReconstructionResult<BaseVecT, MeshT>& result,
+    const ReconstructionOptions& options)
+{
+    if(options.ki > 0 && result.surface)
+    {
+        result.vertexNormals = interpolateVertexNormalsFromPoints(result.mesh, *result.surface, options.ki);
+    }
+    else
+    {
+        result.vertexNormals = calcVertexNormals(result.mesh, calcFaceNormals(result.mesh), options.normalWeighting);
+    }
+}
+
+template<typename BaseVecT, typename MeshT>
+void computeVertexQuality(
+    ReconstructionResult<BaseVecT, MeshT>& result,
+    const ReconstructionOptions& options)
+{
+    using CoordT = typename BaseVecT::CoordType;
+
+    FloatChannel points = *result.surface->pointBuffer()->getFloatChannel("points");
+    auto tree = result.surface->searchTree();
+    const int k = std::max(options.kd, 3);
+
+    result.vertexDensity.clear();
+    result.vertexResidual.clear();
+    result.vertexDensity.reserve(result.mesh.numVertices());
+    result.vertexResidual.reserve(result.mesh.numVertices());
+
+    std::vector<size_t> neighbors;
+    for(auto vH : result.mesh.vertices())
+    {
+        const BaseVecT v = result.mesh.getVertexPosition(vH);
+        const Normal<CoordT>& n = result.vertexNormals[vH];
+
+        neighbors.clear();
+        tree->kSearch(v, k, neighbors);
+
+        // Distances are computed here, as the search trees differ in
+        // whether they return squared distances
+        CoordT maxDist2 = 0;
+        CoordT sumResidual2 = 0;
+        for(size_t index : neighbors)
+        {
+            const BaseVecT p = points[index];
+            const BaseVecT d = p - v;
+            maxDist2 = std::max(maxDist2, d.length2());
+            const CoordT r = d.dot(n);
+            sumResidual2 += r * r;
+        }
+
+        float density = 0;
+        float residual = 0;
+        if(!neighbors.empty())
+        {
+            density = maxDist2 > 0 ? neighbors.size() / (M_PI * maxDist2) : std::numeric_limits<float>::infinity();
+            residual = std::sqrt(sumResidual2 / neighbors.size());
+        }
+        result.vertexDensity.insert(vH, density);
+        result.vertexResidual.insert(vH, residual);
+    }
+}
+
+template<typename BaseVecT, typename MeshT>
+void labelFaceRegions(
+    ReconstructionResult<BaseVecT, MeshT>& result,
+    const ReconstructionOptions& options)
+{
+    result.faceRegions.clear();
+    result.faceRegions.reserve(result.mesh.numFaces());
+
+    if(options.faceRegions == FaceRegionType::Cluster)
+    {
+        auto faceNormals = calcFaceNormals(result.mesh);
+        ClusterBiMap<FaceHandle> clusters = planarClusterGrowing(result.mesh, faceNormals, options.regionMinSinAngle);
+        for(auto fH : result.mesh.faces())
+        {
+            result.faceRegions.insert(fH, clusters.getClusterH(fH).idx());
+        }
+    }
+    else if(options.faceRegions == FaceRegionType::Chunk)
+    {
+        // Consecutive ids in the order the chunks are encountered
+        const BaseVecT min = result.surface->getBoundingBox().getMin();
+        const float chunkSize = options.voxelSize * std::max(1, options.regionChunkSize);
+        std::map<std::array<int, 3>, uint32_t> chunkIds;
+        for(auto fH : result.mesh.faces())
+        {
+            const BaseVecT c = result.mesh.calcFaceCentroid(fH) - min;
+            const std::array<int, 3> chunk = {
+                (int)std::floor(c.x / chunkSize),
+                (int)std::floor(c.y / chunkSize),
+                (int)std::floor(c.z / chunkSize)
+            };
+            auto it = chunkIds.emplace(chunk, chunkIds.size()).first;
+            result.faceRegions.insert(fH, it->second);
+        }
+    }
+}
+
+/**
+ * @brief Reconstructs the whole point cloud at once, see reconstruct().
+ *        The distance filter is passed to the grid, see
+ *        PointsetGrid::setDistanceFilter().
+ */
+template<typename BaseVecT, typename MeshT>
+ReconstructionResult<BaseVecT, MeshT> reconstructSurface(
+    PointBufferPtr buffer,
+    const ReconstructionOptions& options,
+    const std::function<void(QueryPoint<BaseVecT>&)>& distanceFilter = {})
+{
+    ReconstructionResult<BaseVecT, MeshT> result;
+
+    if(!buffer || buffer->numPoints() == 0)
+    {
+        throw EmptyInputError();
+    }
+
+    PointsetSurfacePtr<BaseVecT> surface = createReconstructionSurface<BaseVecT>(buffer, options);
+
+    if(!buffer->hasNormals() || options.recalcNormals)
+    {
+        surface->calculateSurfaceNormals();
+    }
+    else
+    {
+        // Normals from files are not necessarily of unit length. Degenerate
+        // ones are reported below.
+        buffer->normalizeNormals();
+    }
+
+    // Check for points whose neighborhood did not allow a valid normal
+    std::vector<size_t> valid;
+    size_t invalid = findInvalidNormal(buffer, valid);
+    if(invalid < buffer->numPoints())
+    {
+        if(!options.allowPartial)
+        {
+            throw NormalEstimationError(invalid);
+        }
+
+        const size_t numInvalid = buffer->numPoints() - valid.size();
+        result.warnings.push_back(
+            std::string(NormalEstimationError(invalid).what())
+            + ". Ignoring " + std::to_string(numInvalid) + " points without valid normals."
+        );
+        result.partial = true;
+
+        if(valid.empty())
+        {
+            throw EmptyInputError();
+        }
+
+        // Rebuild the surface without the invalid points
+        buffer = std::make_shared<PointBuffer>(buffer->select(valid));
+        surface = createReconstructionSurface<BaseVecT>(buffer, options);
+    }
+
+    result.surface = surface;
+
+    std::string decomposition = options.decomposition;
+    if(decomposition == "MC")
+    {
+        extractSurface<BaseVecT, FastBox<BaseVecT>>(surface, options, result, distanceFilter);
+    }
+    else if(decomposition == "MT")
+    {
+        extractSurface<BaseVecT, TetraederBox<BaseVecT>>(surface, options, result, distanceFilter);
+    }
+    else if(decomposition == "SF")
+    {
+        SharpBox<BaseVecT>::m_surface = surface;
+        extractSurface<BaseVecT, SharpBox<BaseVecT>>(surface, options, result, distanceFilter);
+    }
+    else
+    {
+        if(decomposition != "PMC")
+        {
+            result.warnings.push_back("[Reconstruction] Unsupported decomposition type " + decomposition + ". Defaulting to PMC.");
+        }
+        BilinearFastBox<BaseVecT>::m_surface = surface;
+        extractSurface<BaseVecT, BilinearFastBox<BaseVecT>>(surface, options, result, distanceFilter);
+    }
+
+    if(options.trimMinPoints > 0 && result.mesh.numFaces() > 0)
+    {
+        const float radius = options.trimRadius > 0 ? options.trimRadius : options.voxelSize;
+        trimLowDensityFaces(result.mesh, *surface, radius, options.trimMinPoints);
+    }
+
+    if(result.mesh.numFaces() == 0)
+    {
+        if(!options.allowPartial)
+        {
+            throw EmptySurfaceError();
+        }
+        result.warnings.push_back(EmptySurfaceError().what());
+        result.partial = true;
+    }
+    else
+    {
+        computeVertexNormals(result, options);
+
+        if(options.vertexQuality)
+        {
+            computeVertexQuality(result, options);
+        }
+
+        if(options.faceRegions != FaceRegionType::None)
+        {
+            labelFaceRegions(result, options);
+        }
+    }
+
+    for(const std::string& w : result.warnings)
+    {
+        lvr2::logout::get() << lvr2::warning << w << lvr2::endl;
+    }
+
+    return result;
+}
+
+/**
+ * @brief Reconstructs the point cloud in axis aligned chunks that are small
+ *        enough for the memory budget and merges the resulting meshes.
+ *
+ *        The chunks are aligned to the reconstruction grid and contain the
+ *        points of an overlap region. The distance values of grid corners on
+ *        the chunk borders are computed by the first chunk that evaluates
+ *        them and reused by its neighbors, so the border vertices of
+ *        neighboring chunks coincide. Each face is kept in the chunk that
+ *        contains its centroid. Vertices are merged by the topology of each
+ *        chunk and, on the chunk borders, by their position.
+ */
+template<typename BaseVecT, typename MeshT>
+ReconstructionResult<BaseVecT, MeshT> reconstructChunked(
+    PointBufferPtr buffer,
+    const ReconstructionOptions& options,
+    const MemoryEstimate& estimate)
+{
+    ReconstructionResult<BaseVecT, MeshT> result;
+
+    const size_t n = buffer->numPoints();
+    FloatChannel points = *buffer->getFloatChannel("points");
+    const double voxelSize = options.voxelSize;
+
+    std::array<double, 3> min = {
+        std::numeric_limits<double>::max(),
+        std::numeric_limits<double>::max(),
+        std::numeric_limits<double>::max()
+    };
+    std::array<double, 3> max = {
+        std::numeric_limits<double>::lowest(),
+        std::numeric_limits<double>::lowest(),
+        std::numeric_limits<double>::lowest()
+    };
+    for(size_t i = 0; i < n; i++)
+    {
+        for(int a = 0; a < 3; a++)
+        {
+            min[a] = std::min(min[a], (double)points[i][a]);
+            max[a] = std::max(max[a], (double)points[i][a]);
+        }
+    }
+
+    // Split the longest axis until the chunks should fit. Twice the number
+    // of chunks leaves room for the overlap and uneven point distribution.
+    const size_t numChunks = 2 * ((estimate.peak() + options.memoryBudget - 1) / options.memoryBudget);
+    std::array<size_t, 3> dims = {1, 1, 1};
+    while(dims[0] * dims[1] * dims[2] < numChunks)
+    {
+        int axis = 0;
+        for(int a = 1; a < 3; a++)
+        {
+            if((max[a] - min[a]) / dims[a] > (max[axis] - min[axis]) / dims[axis])
+            {
+                axis = a;
+            }
+        }
+        dims[axis]++;
+    }
+
+    // Chunk borders on grid planes, so all chunks share the global grid
+    std::array<int64_t, 3> originCell, chunkCells;
+    std::array<double, 3> origin, chunkSize;
+    for(int a = 0; a < 3; a++)
+    {
+        originCell[a] = (int64_t)std::floor(min[a] / voxelSize);
+        chunkCells[a] = std::max<int64_t>(1, (int64_t)std::ceil((max[a] - originCell[a] * voxelSize) / dims[a] / voxelSize + 1e-6));
+        origin[a] = originCell[a] * voxelSize;
+        chunkSize[a] = chunkCells[a] * voxelSize;
+    }
+
+    // Enough context for the normal estimation and the cells at the border
+    const double overlap = 3 * voxelSize;
+
+    lvr2::logout::get() << lvr2::info << "[Reconstruction] Estimated peak memory of "
+        << (estimate.peak() >> 20) << " MB exceeds the budget of " << (options.memoryBudget >> 20)
+        << " MB. Reconstructing in " << dims[0] << " x " << dims[1] << " x " << dims[2] << " chunks." << lvr2::endl;
+
+    ReconstructionOptions chunkOptions = options;
+    chunkOptions.memoryBudget = 0;
+    chunkOptions.allowPartial = true;
+    chunkOptions.faceRegions = FaceRegionType::None;
+    chunkOptions.vertexQuality = false;
+    chunkOptions.keepDistanceField = false;
+    if(chunkOptions.flipPoint.size() != 3)
+    {
+        // The bounding box centroids of the chunks would flip inconsistently
+        chunkOptions.flipPoint = {
+            (float)((min[0] + max[0]) / 2),
+            (float)((min[1] + max[1]) / 2),
+            (float)((min[2] + max[2]) / 2)
+        };
+    }
+
+    // The chunks report their own warnings, only the ones raised here are logged
+    auto warn = [&](const std::string& w)
+    {
+        lvr2::logout::get() << lvr2::warning << w << lvr2::endl;
+        result.warnings.push_back(w);
+    };
+
+    if(options.faceRegions == FaceRegionType::Chunk)
+    {
+        warn("[Reconstruction] Chunk region labels are not supported for chunked reconstructions.");
+    }
+    if(options.keepDistanceField)
+    {
+        warn("[Reconstruction] The distance field is not kept for chunked reconstructions.");
+    }
+
+    // Sort the points into all chunks whose extended box contains them
+    auto chunkRange = [&](double v, int a, double border)
+    {
+        const double rel = (v - origin[a]) / chunkSize[a];
+        const double extent = border / chunkSize[a];
+        const int64_t last = (int64_t)dims[a] - 1;
+        return std::make_pair(
+            std::clamp<int64_t>((int64_t)std::floor(rel - extent), 0, last),
+            std::clamp<int64_t>((int64_t)std::floor(rel + extent), 0, last)
+        );
+    };
+    auto chunkId = [&](size_t cx, size_t cy, size_t cz)
+    {
+        return (cx * dims[1] + cy) * dims[2] + cz;
+    };
+
+    std::vector<std::vector<size_t>> chunkPoints(dims[0] * dims[1] * dims[2]);
+    std::vector<bool> hasCorePoints(chunkPoints.size(), false);
+    for(size_t i = 0; i < n; i++)
+    {
+        std::array<std::pair<int64_t, int64_t>, 3> range;
+        std::array<int64_t, 3> core;
+        for(int a = 0; a < 3; a++)
+        {
+            range[a] = chunkRange(points[i][a], a, overlap);
+            core[a] = chunkRange(points[i][a], a, 0).first;
+        }
+        for(int64_t cx = range[0].first; cx <= range[0].second; cx++)
+        for(int64_t cy = range[1].first; cy <= range[1].second; cy++)
+        for(int64_t cz = range[2].first; cz <= range[2].second; cz++)
+        {
+            chunkPoints[chunkId(cx, cy, cz)].push_back(i);
+        }
+        hasCorePoints[chunkId(core[0], core[1], core[2])] = true;
+    }
+
+    // Distance values of the query points on the chunk borders, shared by
+    // the neighboring chunks
+    auto onBorder = [&](int64_t cell, int a)
+    {
+        const int64_t rel = cell - originCell[a];
+        return ((rel % chunkCells[a]) + chunkCells[a]) % chunkCells[a] == 0;
+    };
+    std::unordered_map<Vector3i, std::pair<float, bool>> borderValues;
+    auto shareBorderValues = [&](QueryPoint<BaseVecT>& qp)
+    {
+        const Vector3i cell(
+            (int)std::llround(qp.m_position.x / voxelSize),
+            (int)std::llround(qp.m_position.y / voxelSize),
+            (int)std::llround(qp.m_position.z / voxelSize)
+        );
+        if(!onBorder(cell.x(), 0) && !onBorder(cell.y(), 1) && !onBorder(cell.z(), 2))
+        {
+            return;
+        }
+        auto it = borderValues.emplace(cell, std::make_pair(qp.m_distance, qp.m_invalid)).first;
+        qp.m_distance = it->second.first;
+        qp.m_invalid = it->second.second;
+    };
+
+    // Estimated normals are copied back into the input buffer
+    const bool copyNormals = !buffer->hasNormals() || options.recalcNormals;
+    floatArr normals;
+    if(copyNormals)
+    {
+        normals = floatArr(new float[n * 3]);
+        std::fill(normals.get(), normals.get() + n * 3, 0.0f);
+    }
+
+    // Vertices on the chunk borders are the only ones shared by chunks.
+    // Thanks to the shared distance values, their positions are identical.
+    const double eps = voxelSize * 1e-4;
+    auto nearBorder = [&](const BaseVecT& p)
+    {
+        for(int a = 0; a < 3; a++)
+        {
+            const double cell = std::round(p[a] / voxelSize);
+            if(std::abs(p[a] - cell * voxelSize) < eps && onBorder((int64_t)cell, a))
+            {
+                return true;
+            }
+        }
+        return false;
+    };
+    std::map<std::array<int64_t, 3>, VertexHandle> borderVertices;
+    size_t droppedFaces = 0;
+
+    for(size_t cx = 0; cx < dims[0]; cx++)
+    for(size_t cy = 0; cy < dims[1]; cy++)
+    for(size_t cz = 0; cz < dims[2]; cz++)
+    {
+        const size_t id = chunkId(cx, cy, cz);
+        if(!hasCorePoints[id])
+        {
+            continue;
+        }
+
+        const std::array<size_t, 3> c = {cx, cy, cz};
+        std::array<double, 3> lo, hi;
+        for(int a = 0; a < 3; a++)
+        {
+            lo[a] = origin[a] + c[a] * chunkSize[a];
+            hi[a] = lo[a] + chunkSize[a];
+        }
+
+        auto inCore = [&](const BaseVecT& p)
+        {
+            return p.x >= lo[0] && p.x < hi[0]
+                && p.y >= lo[1] && p.y < hi[1]
+                && p.z >= lo[2] && p.z < hi[2];
+        };
+
+        std::vector<size_t> indices;
+        indices.swap(chunkPoints[id]);
+        PointBufferPtr chunkBuffer = std::make_shared<PointBuffer>(buffer->select(indices));
+
+        ReconstructionResult<BaseVecT, MeshT> chunk;
+        try
+        {
+            chunk = reconstructSurface<BaseVecT, MeshT>(chunkBuffer, chunkOptions, shareBorderValues);
+        }
+        catch(const EmptyInputError&)
+        {
+            continue;
+        }
+
+        for(const std::string& w : chunk.warnings)
+        {
+            result.warnings.push_back(w);
+        }
+        result.partial |= chunk.partial;
+
+        if(copyNormals)
+        {
+            FloatChannel chunkNormals = *chunkBuffer->getFloatChannel("normals");
+            for(size_t j = 0; j < indices.size(); j++)
+            {
+                const size_t i = indices[j];
+                if(inCore(BaseVecT(points[i][0], points[i][1], points[i][2])))
+                {
+                    for(int a = 0; a < 3; a++)
+                    {
+                        normals[i * 3 + a] = chunkNormals[j][a];
+                    }
+                }
+            }
+        }
+
+        // Merge the faces of the chunk core
+        std::unordered_map<size_t, VertexHandle> chunkVertices;
+        for(auto fH : chunk.mesh.faces())
+        {
+            if(!inCore(chunk.mesh.calcFaceCentroid(fH)))
+            {
+                continue;
+            }
+
+            std::array<VertexHandle, 3> handles = {VertexHandle(0), VertexHandle(0), VertexHandle(0)};
+            auto vertices = chunk.mesh.getVerticesOfFace(fH);
+            for(int k = 0; k < 3; k++)
+            {
+                auto local = chunkVertices.find(vertices[k].idx());
+                if(local == chunkVertices.end())
+                {
+                    const BaseVecT pos = chunk.mesh.getVertexPosition(vertices[k]);
+                    if(nearBorder(pos))
+                    {
+                        const std::array<int64_t, 3> key = {
+                            (int64_t)std::llround(pos.x / eps),
+                            (int64_t)std::llround(pos.y / eps),
+                            (int64_t)std::llround(pos.z / eps)
+                        };
+                        auto it = borderVertices.find(key);
+                        if(it == borderVertices.end())
+                        {
+                            it = borderVertices.emplace(key, result.mesh.addVertex(pos)).first;
+                        }
+                        local = chunkVertices.emplace(vertices[k].idx(), it->second).first;
+                    }
+                    else
+                    {
+                        local = chunkVertices.emplace(vertices[k].idx(), result.mesh.addVertex(pos)).first;
+                    }
+                }
+                handles[k] = local->second;
+            }
+
+            try
+            {
+                result.mesh.addFace(handles[0], handles[1], handles[2]);
+            }
+            catch(const std::exception&)
+            {
+                droppedFaces++;
+            }
+        }
+    }
+
+    if(copyNormals)
+    {
+        buffer->setNormalArray(normals, n);
+    }
+
+    if(droppedFaces > 0)
+    {
+        warn("[Reconstruction] Dropped " + std::to_string(droppedFaces) + " non-manifold faces while merging the chunks.");
+        result.partial = true;
+    }
+
+    if(result.mesh.numFaces() == 0)
+    {
+        if(!options.allowPartial)
+        {
+            throw EmptySurfaceError();
+        }
+        warn(EmptySurfaceError().what());
+        result.partial = true;
+    }
+    else
+    {
+        // Without a surface over the whole cloud, the vertex normals are
+        // averaged from the faces
+        result.vertexNormals = calcVertexNormals(result.mesh, calcFaceNormals(result.mesh), options.normalWeighting);
+
+        if(options.faceRegions == FaceRegionType::Cluster)
+        {
+            labelFaceRegions(result, options);
+        }
+    }
+
+    return result;
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionResult<BaseVecT, MeshT> reconstruct(
+    PointBufferPtr buffer,
+    const ReconstructionOptions& options)
+{
+    if(!buffer || buffer->numPoints() == 0)
+    {
+        throw EmptyInputError();
+    }
+
+    if(options.memoryBudget > 0)
+    {
+        const MemoryEstimate estimate = estimateMemory(buffer, options);
+        if(estimate.peak() > options.memoryBudget)
+        {
+            return reconstructChunked<BaseVecT, MeshT>(buffer, options, estimate);
+        }
+    }
+
+    return reconstructSurface<BaseVecT, MeshT>(buffer, options);
+}
+
+} // namespace lvr2
@@ -0,0 +1,115 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * ReconstructionError.hpp
+ *
+ * Exceptions that are thrown by the surface reconstruction if it can not
+ * produce a valid result. All exceptions are derived from
+ * ReconstructionError, so callers that are not interested in the details
+ * can catch a single type.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_RECONSTRUCTIONERROR_HPP
+#define LVR2_RECONSTRUCTION_RECONSTRUCTIONERROR_HPP
+
+#include <stdexcept>
+#include <string>
+
+namespace lvr2
+{
+
+/**
+ * @brief Base class of all errors raised during surface reconstruction
+ */
+class ReconstructionError : public std::runtime_error
+{
+public:
+    explicit ReconstructionError(const std::string& msg)
+        : std::runtime_error("[Reconstruction] " + msg) {}
+};
+
+/**
+ * @brief The input point cloud does not contain any points
+ */
+class EmptyInputError : public ReconstructionError
+{
+public:
+    EmptyInputError()
+        : ReconstructionError("Input point cloud is empty") {}
+};
+
+/**
+ * @brief Marching cubes did not extract a single face
+ */
+class EmptySurfaceError : public ReconstructionError
+{
+public:
+    EmptySurfaceError()
+        : ReconstructionError("No surface was extracted from the distance field") {}
+};
+
+/**
+ * @brief The reconstruction grid contains too few cells, e.g. because
+ *        the voxel size is larger than the point cloud's extent or all
+ *        cells were discarded during distance evaluation.
+ */
+class DegenerateGridError : public ReconstructionError
+{
+public:
+    explicit DegenerateGridError(size_t cells)
+        : ReconstructionError("Degenerate grid with " + std::to_string(cells) + " cells")
+        , m_cells(cells) {}
+
+    /// The number of cells remaining in the grid
+    size_t cells() const { return m_cells; }
+
+private:
+    size_t m_cells;
+};
+
+/**
+ * @brief The normal of a point could not be estimated, e.g. because its
+ *        neighborhood is degenerate. Reports the first offending point.
+ */
+class NormalEstimationError : public ReconstructionError
+{
+public:
+    explicit NormalEstimationError(size_t index)
+        : ReconstructionError("Normal estimation failed for point " + std::to_string(index))
+        , m_index(index) {}
+
+    /// Index of the first point with an invalid normal
+    size_t index() const { return m_index; }
+
+private:
+    size_t m_index;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_RECONSTRUCTION_RECONSTRUCTIONERROR_HPP
@@ -0,0 +1,158 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * ReconstructionPipeline.hpp
+ *
+ * A builder to compose point filters, normal estimation, surface
+ * reconstruction and mesh post processing into one run with shared
+ * progress output, per-stage timing and optional caching of
+ * intermediate results.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_RECONSTRUCTIONPIPELINE_HPP
+#define LVR2_RECONSTRUCTION_RECONSTRUCTIONPIPELINE_HPP
+
+#include "lvr2/reconstruction/Reconstruction.hpp"
+#include "lvr2/reconstruction/RunReport.hpp"
+#include "lvr2/registration/ReductionAlgorithm.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/optional.hpp>
+
+#include <functional>
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Composes the steps of a reconstruction into a single run.
+ *
+ * \code{.cpp}
+ * ReconstructionPipeline<Vec> pipeline;
+ * pipeline.filter(std::make_shared<OctreeReductionAlgorithm>(0.05, 5))
+ *         .normals(20, 20)
+ *         .reconstruct(options)
+ *         .smooth(5)
+ *         .simplify(0.5);
+ * auto result = pipeline.run(points);
+ * \endcode
+ *
+ * Point stages have to be added before the reconstruct stage, mesh
+ * stages after it. The vertex normals, the vertex quality and the face
+ * regions of the result are recomputed after every mesh stage, since the
+ * stage may change the vertex and face handles. Cluster region labels are
+ * therefore not stable across mesh stages.
+ */
+template<typename BaseVecT, typename MeshT = PMPMesh<BaseVecT>>
+class ReconstructionPipeline
+{
+public:
+    using Result = ReconstructionResult<BaseVecT, MeshT>;
+
+    /// A stage that transforms a point cloud
+    using PointStage = std::function<PointBufferPtr(PointBufferPtr)>;
+
+    /// A stage that modifies a mesh in place
+    using MeshStage = std::function<void(MeshT&)>;
+
+    ReconstructionPipeline() = default;
+
+    /// Adds a point stage using the given reduction algorithm
+    ReconstructionPipeline& filter(ReductionAlgorithmPtr reduction, const std::string& name = "filter");
+
+    /// Adds a custom point stage
+    ReconstructionPipeline& filter(PointStage stage, const std::string& name = "filter");
+
+    /// Adds a normal estimation stage. See @ref estimateNormalsInto.
+    ReconstructionPipeline& normals(int kn, int ki = 0, int calcMethod = 0);
+
+    /// Adds the surface reconstruction stage
+    ReconstructionPipeline& reconstruct(const ReconstructionOptions& options);
+
+    /// Adds explicit laplacian smoothing. See PMPMesh::laplacianSmoothing.
+    ReconstructionPipeline& smooth(int iterations, float smoothFactor = 0.5, bool uniformLaplace = true);
+
+    /// Adds an edge collapse simplification that removes the given ratio (0 - 1) of vertices
+    ReconstructionPipeline& simplify(float reductionRatio);
+
+    /// Adds a custom mesh stage
+    ReconstructionPipeline& mesh(MeshStage stage, const std::string& name = "mesh");
+
+    /**
+     * @brief Keep a copy of the output of every stage. The copies can be
+     *        accessed via @ref cachedPoints and @ref cachedMesh after
+     *        @ref run was called.
+     */
+    void setCacheIntermediate(bool cache) { m_cacheIntermediate = cache; }
+
+    /**
+     * @brief Executes all stages on the given point cloud
+     *
+     * @throws ReconstructionError if no reconstruct stage was added or
+     *         the reconstruction failed
+     */
+    Result run(PointBufferPtr points);
+
+    /// Timing, memory and size statistics of the last run
+    const RunReport& report() const { return m_report; }
+
+    /// Output of the given point stage in the last run (if caching is enabled)
+    PointBufferPtr cachedPoints(size_t stage) const;
+
+    /// Output of the given mesh stage in the last run (if caching is enabled)
+    MeshBufferPtr cachedMesh(size_t stage) const;
+
+private:
+    enum class StageType { Points, Reconstruct, Mesh };
+
+    struct Stage
+    {
+        StageType           type;
+        std::string         name;
+        PointStage          pointStage;
+        MeshStage           meshStage;
+    };
+
+    void addStage(Stage&& stage);
+
+    std::vector<Stage>                      m_stages;
+    boost::optional<ReconstructionOptions>  m_options;
+    bool                                    m_cacheIntermediate = false;
+
+    RunReport                               m_report;
+    std::vector<PointBufferPtr>             m_cachedPoints;
+    std::vector<MeshBufferPtr>              m_cachedMeshes;
+};
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/ReconstructionPipeline.tcc"
+
+#endif // LVR2_RECONSTRUCTION_RECONSTRUCTIONPIPELINE_HPP
@@ -0,0 +1,239 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * ReconstructionPipeline.tcc
+ */
+
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Timestamp.hpp"
+
+#include <algorithm>
+#include <cmath>
+
+namespace lvr2
+{
+
+template<typename BaseVecT, typename MeshT>
+void ReconstructionPipeline<BaseVecT, MeshT>::addStage(Stage&& stage)
+{
+    bool reconstructed = std::any_of(m_stages.begin(), m_stages.end(),
+        [](const Stage& s) { return s.type == StageType::Reconstruct; });
+
+    if(stage.type == StageType::Points && reconstructed)
+    {
+        throw std::logic_error("[ReconstructionPipeline] Point stage '" + stage.name + "' added after reconstruct stage");
+    }
+    if(stage.type == StageType::Mesh && !reconstructed)
+    {
+        throw std::logic_error("[ReconstructionPipeline] Mesh stage '" + stage.name + "' added before reconstruct stage");
+    }
+    if(stage.type == StageType::Reconstruct && reconstructed)
+    {
+        throw std::logic_error("[ReconstructionPipeline] Only one reconstruct stage is allowed");
+    }
+    m_stages.push_back(std::move(stage));
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::filter(
+    ReductionAlgorithmPtr reduction,
+    const std::string& name)
+{
+    return filter([reduction](PointBufferPtr points)
+    {
+        reduction->setPointBuffer(points);
+        return reduction->getReducedPoints();
+    }, name);
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::filter(
+    PointStage stage,
+    const std::string& name)
+{
+    addStage(Stage{StageType::Points, name, std::move(stage), MeshStage()});
+    return *this;
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::normals(int kn, int ki, int calcMethod)
+{
+    return filter([kn, ki, calcMethod](PointBufferPtr points)
+    {
+        estimateNormalsInto<BaseVecT>(points, kn, ki, calcMethod);
+        return points;
+    }, "normals");
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::reconstruct(const ReconstructionOptions& options)
+{
+    addStage(Stage{StageType::Reconstruct, "reconstruct", PointStage(), MeshStage()});
+    m_options = options;
+    return *this;
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::smooth(
+    int iterations,
+    float smoothFactor,
+    bool uniformLaplace)
+{
+    return mesh([=](MeshT& m)
+    {
+        m.laplacianSmoothing(smoothFactor, iterations, uniformLaplace);
+    }, "smooth");
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::simplify(float reductionRatio)
+{
+    return mesh([reductionRatio](MeshT& m)
+    {
+        float keep = std::min(1.0f, std::max(0.0f, 1.0f - reductionRatio));
+        m.simplify(static_cast<size_t>(std::round(m.numVertices() * keep)));
+    }, "simplify");
+}
+
+template<typename BaseVecT, typename MeshT>
+ReconstructionPipeline<BaseVecT, MeshT>& ReconstructionPipeline<BaseVecT, MeshT>::mesh(
+    MeshStage stage,
+    const std::string& name)
+{
+    addStage(Stage{StageType::Mesh, name, PointStage(), std::move(stage)});
+    return *this;
+}
+
+template<typename BaseVecT, typename MeshT>
+typename ReconstructionPipeline<BaseVecT, MeshT>::Result ReconstructionPipeline<BaseVecT, MeshT>::run(PointBufferPtr points)
+{
+    if(!m_options)
+    {
+        throw ReconstructionError("Pipeline does not contain a reconstruct stage");
+    }
+
+    m_report = RunReport();
+    m_report.inputPoints = points ? points->numPoints() : 0;
+    m_cachedPoints.clear();
+    m_cachedMeshes.clear();
+
+    Timestamp total;
+    Result result;
+    const size_t numStages = m_stages.size();
+    for(size_t i = 0; i < numStages; i++)
+    {
+        Stage& stage = m_stages[i];
+        lvr2::logout::get() << lvr2::info << "[ReconstructionPipeline] Stage "
+            << (i + 1) << " / " << numStages << ": " << stage.name << lvr2::endl;
+
+        PipelineStageInfo info;
+        info.name = stage.name;
+
+        Timestamp ts;
+        switch(stage.type)
+        {
+        case StageType::Points:
+            info.elementsIn = points ? points->numPoints() : 0;
+            points = stage.pointStage(points);
+            info.elementsOut = points ? points->numPoints() : 0;
+            break;
+        case StageType::Reconstruct:
+            info.elementsIn = points ? points->numPoints() : 0;
+            result = lvr2::reconstruct<BaseVecT, MeshT>(points, *m_options);
+            info.elementsOut = result.mesh.numVertices();
+            break;
+        case StageType::Mesh:
+            info.elementsIn = result.mesh.numVertices();
+            stage.meshStage(result.mesh);
+            // Vertex handles may have changed
+            computeVertexNormals(result, *m_options);
+            if(m_options->vertexQuality && result.surface)
+            {
+                computeVertexQuality(result, *m_options);
+            }
+            else
+            {
+                result.vertexDensity.clear();
+                result.vertexResidual.clear();
+            }
+            // Face handles may have changed as well, label the new faces
+            if(m_options->faceRegions == FaceRegionType::Cluster
+                || (m_options->faceRegions == FaceRegionType::Chunk && result.surface))
+            {
+                labelFaceRegions(result, *m_options);
+            }
+            else
+            {
+                result.faceRegions.clear();
+            }
+            info.elementsOut = result.mesh.numVertices();
+            break;
+        }
+        info.seconds = ts.getElapsedTimeInS();
+        info.peakMemory = peakMemoryUsage();
+
+        lvr2::logout::get() << lvr2::info << "[ReconstructionPipeline] " << stage.name << " finished in "
+            << info.seconds << "s (" << info.elementsIn << " -> " << info.elementsOut << ")" << lvr2::endl;
+        m_report.stages.push_back(info);
+
+        if(m_cacheIntermediate)
+        {
+            if(stage.type == StageType::Points)
+            {
+                m_cachedPoints.push_back(points ? std::make_shared<PointBuffer>(points->clone()) : nullptr);
+                m_cachedMeshes.push_back(nullptr);
+            }
+            else
+            {
+                m_cachedPoints.push_back(nullptr);
+                m_cachedMeshes.push_back(result.mesh.toMeshBuffer());
+            }
+        }
+    }
+
+    m_report.totalSeconds = total.getElapsedTimeInS();
+    m_report.peakMemory = peakMemoryUsage();
+    m_report.outputVertices = result.mesh.numVertices();
+    m_report.outputFaces = result.mesh.numFaces();
+
+    return result;
+}
+
+template<typename BaseVecT, typename MeshT>
+PointBufferPtr ReconstructionPipeline<BaseVecT, MeshT>::cachedPoints(size_t stage) const
+{
+    return stage < m_cachedPoints.size() ? m_cachedPoints[stage] : nullptr;
+}
+
+template<typename BaseVecT, typename MeshT>
+MeshBufferPtr ReconstructionPipeline<BaseVecT, MeshT>::cachedMesh(size_t stage) const
+{
+    return stage < m_cachedMeshes.size() ? m_cachedMeshes[stage] : nullptr;
+}
+
+} // namespace lvr2
@@ -0,0 +1,106 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * RunReport.hpp
+ *
+ * Timing, memory and size statistics of a reconstruction run in a
+ * machine-readable form, e.g. to benchmark parameter sweeps.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_RUNREPORT_HPP
+#define LVR2_RECONSTRUCTION_RUNREPORT_HPP
+
+#include <boost/filesystem.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Information about a single executed pipeline stage
+ */
+struct PipelineStageInfo
+{
+    /// Name of the stage
+    std::string name;
+
+    /// Wall time in seconds
+    double      seconds = 0.0;
+
+    /// Peak resident memory of the process in bytes after the stage
+    /// finished. 0 if not available on this platform.
+    size_t      peakMemory = 0;
+
+    /// Number of points or vertices before the stage was run
+    size_t      elementsIn = 0;
+
+    /// Number of points or vertices after the stage was run
+    size_t      elementsOut = 0;
+};
+
+/**
+ * @brief Statistics of a complete pipeline run
+ */
+struct RunReport
+{
+    /// Statistics of all executed stages in execution order
+    std::vector<PipelineStageInfo> stages;
+
+    /// Wall time of the whole run in seconds
+    double      totalSeconds = 0.0;
+
+    /// Peak resident memory of the process in bytes. 0 if not available.
+    size_t      peakMemory = 0;
+
+    /// Number of input points
+    size_t      inputPoints = 0;
+
+    /// Number of vertices of the resulting mesh
+    size_t      outputVertices = 0;
+
+    /// Number of faces of the resulting mesh
+    size_t      outputFaces = 0;
+
+    /// Serializes the report to a JSON object
+    std::string toJson() const;
+
+    /// Writes the JSON representation of the report to the given file
+    void saveJson(const boost::filesystem::path& path) const;
+};
+
+/**
+ * @brief Returns the peak resident set size of the current process in
+ *        bytes or 0 if it can not be determined on this platform.
+ */
+size_t peakMemoryUsage();
+
+} // namespace lvr2
+
+#endif // LVR2_RECONSTRUCTION_RUNREPORT_HPP
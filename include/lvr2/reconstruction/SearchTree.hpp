@@ -92,12 +92,34 @@ public:
     ) const = 0;
 
     /// Like the other overload, but ignoring the `distances` vector.
+    /// The default implementation uses a thread local buffer for the
+    /// distances, so repeated calls do not allocate memory.
     virtual int kSearch(
         const BaseVecT& qp,
         int k,
         std::vector<size_t>& indices
     ) const;
 
+    /**
+     * @brief Like kSearch(), but additionally returns the positions of the
+     *        neighbours, so callers do not need a second lookup in the
+     *        point data for every found index.
+     *
+     * @param qp          The query point.
+     * @param k           The number of neighbours that should be searched.
+     * @param indices     A vector that stores the indices for the neighbours
+     *                    within the dataset.
+     * @param neighbors   A vector that stores the positions of the
+     *                    neighbours in the same order as `indices`.
+     * @returns           The number of neighbours found
+     */
+    virtual int kSearchPoints(
+        const BaseVecT& qp,
+        int k,
+        std::vector<size_t>& indices,
+        std::vector<BaseVecT>& neighbors
+    ) const = 0;
+
      virtual void kSearchParallel(
         const BaseVecT* query,
         int n,
@@ -106,11 +128,13 @@ public:
         std::vector<CoordT>& distances
     ) const
     {
+        std::vector<size_t>  indices_vec;
+        std::vector<CoordT> distances_vec;
+        indices_vec.reserve(k);
+        distances_vec.reserve(k);
+
         for(int i = 0; i < n; i++)
         {
-            std::vector<size_t>  indices_vec;
-            std::vector<CoordT> distances_vec; 
-
             this->kSearch(
                 query[i], 
                 k,
@@ -125,6 +149,38 @@ public:
             }
         }
     }
+    /**
+     * @brief Adds points to the tree. Depending on the implementation, the
+     *        tree is not rebalanced, so rebuildIfNeeded() should be called
+     *        after a batch of updates.
+     *
+     *        The default implementation panics, as not all search trees
+     *        support incremental updates.
+     *
+     * @param points    The new points
+     * @returns         The index of the first new point. The following points
+     *                  are numbered consecutively.
+     */
+    virtual size_t insert(const std::vector<BaseVecT>& points);
+
+    /**
+     * @brief Removes the point with the given index from the tree. The
+     *        indices of all other points remain valid.
+     *
+     *        The default implementation panics.
+     */
+    virtual void remove(size_t index);
+
+    /**
+     * @brief Rebuilds the tree if too many points were inserted or removed
+     *        since it was last built.
+     *
+     *        The default implementation does nothing.
+     *
+     * @returns true, if the tree was rebuilt
+     */
+    virtual bool rebuildIfNeeded() { return false; }
+
     // /**
     //  * @brief Set the number of neighbours used to estimate and interpolate normals.
     //  */
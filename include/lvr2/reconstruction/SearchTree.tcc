@@ -34,6 +34,7 @@
  */
 
 #include "lvr2/util/Timestamp.hpp"
+#include "lvr2/util/Panic.hpp"
 
 #include <iostream>
 
@@ -47,10 +48,25 @@ int SearchTree<BaseVecT>::kSearch(
     std::vector<size_t>& indices
 ) const
 {
-    std::vector<CoordT> distances;
+    // Reuse the distance buffer between queries of the same thread to
+    // avoid a heap allocation per query in hot loops
+    thread_local std::vector<CoordT> distances;
     return this->kSearch(qp, neighbours, indices, distances);
 }
 
+template<typename BaseVecT>
+size_t SearchTree<BaseVecT>::insert(const std::vector<BaseVecT>& points)
+{
+    panic_unimplemented("SearchTree::insert() is not supported by this search tree");
+    return 0;
+}
+
+template<typename BaseVecT>
+void SearchTree<BaseVecT>::remove(size_t index)
+{
+    panic_unimplemented("SearchTree::remove() is not supported by this search tree");
+}
+
 // template<typename BaseVecT>
 // void SearchTree<BaseVecT>::setKi(int ki)
 // {
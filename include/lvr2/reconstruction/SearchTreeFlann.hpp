@@ -47,6 +47,8 @@
 #include "lvr2/types/PointBuffer.hpp"
 #include "lvr2/reconstruction/SearchTree.hpp"
 
+#include <boost/filesystem.hpp>
+
 using std::vector;
 using std::unique_ptr;
 
@@ -74,6 +76,17 @@ public:
      */
     SearchTreeFlann(PointBufferPtr buffer);
 
+    /**
+     *  @brief Like the other constructor, but loads the tree from a cache
+     *         file in the given directory if one exists for the points.
+     *         Otherwise, the tree is built and written to the cache.
+     *
+     *  @param buffer          A PointBuffer point that holds the data.
+     *  @param cacheDirectory  Directory of the cache files. No cache is
+     *                         used if empty.
+     */
+    SearchTreeFlann(PointBufferPtr buffer, const boost::filesystem::path& cacheDirectory);
+
     /// See interface documentation.
     virtual int kSearch(
         const BaseVecT& qp,
@@ -82,6 +95,14 @@ public:
         vector<CoordT>& distances
     ) const override;
 
+    /// See interface documentation.
+    virtual int kSearchPoints(
+        const BaseVecT& qp,
+        int k,
+        vector<size_t>& indices,
+        vector<BaseVecT>& neighbors
+    ) const override;
+
     /// See interface documentation.
     virtual int radiusSearch(
         const BaseVecT& qp,
@@ -91,6 +112,27 @@ public:
         vector<CoordT>& distances
     ) const override;
 
+    /**
+     * @brief Adds the points to the tree. FLANN's single kd-tree index
+     *        does not support incremental insertion, so every call
+     *        rebuilds the whole index (including dropping removed points).
+     *        Insert points in few, large batches.
+     */
+    virtual size_t insert(const vector<BaseVecT>& points) override;
+
+    /// See interface documentation.
+    virtual void remove(size_t index) override;
+
+    /**
+     * @brief Rebuilds the tree if the number of inserted and removed points
+     *        since the last build exceeds the rebuild ratio times the number
+     *        of points at that time.
+     */
+    virtual bool rebuildIfNeeded() override;
+
+    /// Sets the ratio of changed points that triggers a rebuild. Default: 0.5
+    void setRebuildRatio(float ratio) { m_rebuildRatio = ratio; }
+
     void kSearchMany(
         const BaseVecT* query,
         int n,
@@ -105,6 +147,20 @@ protected:
     unique_ptr<flann::Index<flann::L2_Simple<CoordT>>> m_tree;
 
     boost::shared_array<CoordT> m_data;
+
+    /// FLANN does not copy inserted points, so they are kept alive here
+    vector<boost::shared_array<CoordT>> m_insertedData;
+
+    /// Number of points ever added, i.e. the index of the next new point
+    size_t m_numPoints = 0;
+
+    /// Number of points in the tree when it was last built
+    size_t m_numPointsAtBuild = 0;
+
+    /// Number of inserted and removed points since the last build
+    size_t m_numChanges = 0;
+
+    float m_rebuildRatio = 0.5;
 };
 
 template <typename BaseVecT>
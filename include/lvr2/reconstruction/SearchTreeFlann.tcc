@@ -35,6 +35,8 @@
 #include "lvr2/reconstruction/SearchTreeFlann.hpp"
 #include "lvr2/util/Timestamp.hpp"
 #include "lvr2/util/Panic.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/reconstruction/IndexCache.hpp"
 
 #ifndef __APPLE__
 #include <omp.h>
@@ -47,6 +49,12 @@ namespace lvr2
 
 template<typename BaseVecT>
 SearchTreeFlann<BaseVecT>::SearchTreeFlann(PointBufferPtr buffer)
+    : SearchTreeFlann(buffer, boost::filesystem::path())
+{
+}
+
+template<typename BaseVecT>
+SearchTreeFlann<BaseVecT>::SearchTreeFlann(PointBufferPtr buffer, const boost::filesystem::path& cacheDirectory)
 {
     auto n = buffer->numPoints();
     FloatChannelOptional pts_optional = buffer->getFloatChannel("points");
@@ -62,14 +70,110 @@ SearchTreeFlann<BaseVecT>::SearchTreeFlann(PointBufferPtr buffer)
         flannPoints[i][2] = p.z;
     }
 
+    m_numPoints = n;
+    m_numPointsAtBuild = n;
+
+    boost::filesystem::path cacheFile;
+    if(!cacheDirectory.empty())
+    {
+        cacheFile = indexCacheFile(cacheDirectory, "flann", hashPointBuffer(buffer));
+        if(boost::filesystem::exists(cacheFile))
+        {
+            lvr2::logout::get() << lvr2::info << "[SearchTreeFlann] Loading tree from " << cacheFile << lvr2::endl;
+            try
+            {
+                m_tree = make_unique<flann::Index<flann::L2_Simple<CoordT>>>(
+                             flannPoints,
+                             ::flann::SavedIndexParams(cacheFile.string())
+                         );
+                return;
+            }
+            catch(const std::exception& e)
+            {
+                // Corrupt or incompatible cache, rebuild and overwrite it
+                lvr2::logout::get() << lvr2::warning << "[SearchTreeFlann] Ignoring invalid cache " << cacheFile
+                                    << ": " << e.what() << lvr2::endl;
+            }
+        }
+    }
+
     m_tree = make_unique<flann::Index<flann::L2_Simple<CoordT>>>(
                  flannPoints,
                  ::flann::KDTreeSingleIndexParams(10, false)
              );
     m_tree->buildIndex();
+
+    if(!cacheFile.empty())
+    {
+        boost::filesystem::create_directories(cacheDirectory);
+        const boost::filesystem::path tmp = temporaryCacheFile(cacheFile);
+        try
+        {
+            m_tree->save(tmp.string());
+            if(commitCacheFile(tmp, cacheFile))
+            {
+                lvr2::logout::get() << lvr2::info << "[SearchTreeFlann] Saved tree to " << cacheFile << lvr2::endl;
+            }
+        }
+        catch(const std::exception& e)
+        {
+            lvr2::logout::get() << lvr2::warning << "[SearchTreeFlann] Unable to save tree to " << cacheFile
+                                << ": " << e.what() << lvr2::endl;
+        }
+    }
 }
 
 
+template<typename BaseVecT>
+size_t SearchTreeFlann<BaseVecT>::insert(const vector<BaseVecT>& points)
+{
+    size_t first = m_numPoints;
+    if(points.empty())
+    {
+        return first;
+    }
+
+    boost::shared_array<CoordT> data(new CoordT[3 * points.size()]);
+    flann::Matrix<CoordT> flannPoints(data.get(), points.size(), 3);
+    for(size_t i = 0; i < points.size(); i++)
+    {
+        flannPoints[i][0] = points[i].x;
+        flannPoints[i][1] = points[i].y;
+        flannPoints[i][2] = points[i].z;
+    }
+
+    // The single kd-tree index always rebuilds itself when points are added
+    m_tree->addPoints(flannPoints);
+    m_insertedData.push_back(data);
+
+    m_numPoints += points.size();
+    m_numPointsAtBuild = m_tree->size();
+    m_numChanges = 0;
+    return first;
+}
+
+template<typename BaseVecT>
+void SearchTreeFlann<BaseVecT>::remove(size_t index)
+{
+    m_tree->removePoint(index);
+    m_numChanges++;
+}
+
+template<typename BaseVecT>
+bool SearchTreeFlann<BaseVecT>::rebuildIfNeeded()
+{
+    if(m_numChanges <= m_rebuildRatio * m_numPointsAtBuild)
+    {
+        return false;
+    }
+
+    // Rebuilding also drops removed points, FLANN keeps the original indices
+    m_tree->buildIndex();
+    m_numPointsAtBuild = m_tree->size();
+    m_numChanges = 0;
+    return true;
+}
+
 template<typename BaseVecT>
 int SearchTreeFlann<BaseVecT>::kSearch(
     const BaseVecT& qp,
@@ -90,6 +194,28 @@ int SearchTreeFlann<BaseVecT>::kSearch(
     return m_tree->knnSearch(query_point, ind, dist, k, flann::SearchParams());
 }
 
+template<typename BaseVecT>
+int SearchTreeFlann<BaseVecT>::kSearchPoints(
+    const BaseVecT& qp,
+    int k,
+    vector<size_t>& indices,
+    vector<BaseVecT>& neighbors
+) const
+{
+    thread_local vector<CoordT> distances;
+    int n = kSearch(qp, k, indices, distances);
+
+    indices.resize(n);
+    neighbors.resize(n);
+    for (int i = 0; i < n; i++)
+    {
+        // FLANN keeps the original indices, including those of inserted points
+        const CoordT* p = m_tree->getPoint(indices[i]);
+        neighbors[i] = BaseVecT(p[0], p[1], p[2]);
+    }
+    return n;
+}
+
 template<typename BaseVecT>
 int SearchTreeFlann<BaseVecT>::radiusSearch(
     const BaseVecT& qp,
@@ -37,6 +37,14 @@ public:
         vector<CoordT>& distances
     ) const override;
 
+    /// See interface documentation.
+    virtual int kSearchPoints(
+        const BaseVecT& qp,
+        int k,
+        vector<size_t>& indices,
+        vector<BaseVecT>& neighbors
+    ) const override;
+
     /// See interface documentation.
     virtual int radiusSearch(
         const BaseVecT& qp,
@@ -87,6 +95,9 @@ public:
 protected:
 
     lbvh::LBVHIndex m_tree;
+
+    /// The indexed points, used to return neighbour positions
+    floatArr m_points;
 };
 } // namespace lvr2
 
@@ -8,10 +8,10 @@ SearchTreeLBVH<BaseVecT>::SearchTreeLBVH(PointBufferPtr pbuffer)
 {
     m_tree = lbvh::LBVHIndex(1, true, true);
     
-    float* points = &pbuffer->getPointArray()[0];
+    m_points = pbuffer->getPointArray();
     size_t num_points = pbuffer->numPoints();
 
-    m_tree.build(points, num_points);
+    m_tree.build(m_points.get(), num_points);
 
 }
 
@@ -59,6 +59,46 @@ int SearchTreeLBVH<BaseVecT>::kSearch(
     return n_neighbors_out[0];
 }
 
+template<typename BaseVecT>
+int SearchTreeLBVH<BaseVecT>::kSearchPoints(
+    const BaseVecT& qp,
+    int K,
+    vector<size_t>& indices,
+    vector<BaseVecT>& neighbors
+) const
+{
+    float query_point[] = {qp.x, qp.y, qp.z};
+    size_t num_queries = 1;
+
+    // Create the return arrays
+    unsigned int n_neighbors_out;
+    vector<unsigned int> indices_out(K);
+    vector<float> distances_out(K);
+
+    // Perform the knn search
+    m_tree.kSearch(
+        query_point, num_queries,
+        K,
+        &n_neighbors_out,
+        indices_out.data(),
+        distances_out.data()
+    );
+
+    size_t n = n_neighbors_out;
+
+    indices.resize(n);
+    neighbors.resize(n);
+
+    for(size_t i = 0; i < n; i++)
+    {
+        const float* p = m_points.get() + 3 * indices_out[i];
+        indices[i] = indices_out[i];
+        neighbors[i] = BaseVecT(p[0], p[1], p[2]);
+    }
+
+    return n;
+}
+
 template<typename BaseVecT>
 int SearchTreeLBVH<BaseVecT>::radiusSearch(
     const BaseVecT& qp,
@@ -0,0 +1,93 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshDistanceMetric.hpp
+ *
+ * Sampling based distance between two meshes, e.g. to compare a mesh
+ * before and after simplification or smoothing.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_METRICS_MESHDISTANCEMETRIC_HPP
+#define LVR2_RECONSTRUCTION_METRICS_MESHDISTANCEMETRIC_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+namespace lvr2
+{
+
+/**
+ * @brief Statistics of the distances of the samples of one mesh to the
+ *        surface of the other one
+ */
+struct OneSidedMeshDistance
+{
+    double mean = 0.0;
+    double rms = 0.0;
+
+    /// One sided Hausdorff distance
+    double max = 0.0;
+
+    size_t numSamples = 0;
+};
+
+struct MeshDistance
+{
+    /// Distances of the samples of mesh a to mesh b
+    OneSidedMeshDistance aToB;
+
+    /// Distances of the samples of mesh b to mesh a
+    OneSidedMeshDistance bToA;
+
+    /// Symmetric Hausdorff distance, i.e. max(aToB.max, bToA.max)
+    double hausdorff = 0.0;
+
+    /// Mean of the distances in both directions
+    double mean = 0.0;
+};
+
+/**
+ * @brief Computes the symmetric Hausdorff and mean distance between the
+ *        surfaces of two meshes.
+ *
+ *        Both surfaces are sampled uniformly with the given density in
+ *        addition to their vertices. For each sample, the exact distance
+ *        to the closest triangle of the other mesh is computed using a
+ *        triangle kd-tree. The result approaches the exact distances with
+ *        increasing density.
+ *
+ * @param a         First mesh
+ * @param b         Second mesh
+ * @param density   Samples per square unit on each surface
+ * @param seed      Seed for the sampling
+ */
+MeshDistance meshDistance(MeshBufferPtr a, MeshBufferPtr b, float density, unsigned int seed = 0);
+
+} // namespace lvr2
+
+#endif // LVR2_RECONSTRUCTION_METRICS_MESHDISTANCEMETRIC_HPP
@@ -0,0 +1,90 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * PointToMeshMetric.hpp
+ *
+ * Measures how well a reconstructed mesh fits the point cloud it was
+ * created from by computing the distance of every point to the closest
+ * triangle of the mesh.
+ */
+
+#ifndef LVR2_RECONSTRUCTION_METRICS_POINTTOMESHMETRIC_HPP
+#define LVR2_RECONSTRUCTION_METRICS_POINTTOMESHMETRIC_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <string>
+
+namespace lvr2
+{
+
+/**
+ * @brief Statistics of the point to mesh distances
+ */
+struct PointToMeshError
+{
+    /// Mean distance of the points to the mesh
+    double      mean = 0.0;
+
+    /// Root mean square of the distances
+    double      rms = 0.0;
+
+    /// Largest distance of a point to the mesh
+    double      max = 0.0;
+
+    /// Number of evaluated points
+    size_t      numPoints = 0;
+};
+
+/**
+ * @brief Computes the distance of every point to the closest triangle
+ *        of the given mesh.
+ *
+ *        The closest triangle is searched among all faces adjacent to the
+ *        k nearest mesh vertices, so the result is an upper bound of the
+ *        exact distance that is tight for reasonably shaped triangles.
+ *
+ * @param points            The reference point cloud
+ * @param mesh              The mesh to evaluate
+ * @param k                 Number of nearest vertices whose faces are checked
+ * @param searchTreeName    Search tree to use for the vertex lookup
+ */
+template<typename BaseVecT>
+PointToMeshError pointToMeshError(
+    PointBufferPtr points,
+    MeshBufferPtr mesh,
+    int k = 5,
+    const std::string& searchTreeName = "FLANN"
+);
+
+} // namespace lvr2
+
+#include "lvr2/reconstruction/metrics/PointToMeshMetric.tcc"
+
+#endif // LVR2_RECONSTRUCTION_METRICS_POINTTOMESHMETRIC_HPP
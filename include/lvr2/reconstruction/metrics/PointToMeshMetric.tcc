@@ -0,0 +1,199 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * PointToMeshMetric.tcc
+ */
+
+#include "lvr2/util/Factories.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Squared distance between p and the triangle (a, b, c).
+ *        See Ericson, Real-Time Collision Detection, 5.1.5.
+ */
+template<typename BaseVecT>
+typename BaseVecT::CoordType pointTriangleDistance2(
+    const BaseVecT& p,
+    const BaseVecT& a,
+    const BaseVecT& b,
+    const BaseVecT& c)
+{
+    using CoordT = typename BaseVecT::CoordType;
+
+    BaseVecT ab = b - a;
+    BaseVecT ac = c - a;
+    BaseVecT ap = p - a;
+    CoordT d1 = ab.dot(ap);
+    CoordT d2 = ac.dot(ap);
+    if(d1 <= 0 && d2 <= 0)
+    {
+        return p.distance2(a);
+    }
+
+    BaseVecT bp = p - b;
+    CoordT d3 = ab.dot(bp);
+    CoordT d4 = ac.dot(bp);
+    if(d3 >= 0 && d4 <= d3)
+    {
+        return p.distance2(b);
+    }
+
+    CoordT vc = d1 * d4 - d3 * d2;
+    if(vc <= 0 && d1 >= 0 && d3 <= 0)
+    {
+        CoordT v = d1 / (d1 - d3);
+        return p.distance2(a + ab * v);
+    }
+
+    BaseVecT cp = p - c;
+    CoordT d5 = ab.dot(cp);
+    CoordT d6 = ac.dot(cp);
+    if(d6 >= 0 && d5 <= d6)
+    {
+        return p.distance2(c);
+    }
+
+    CoordT vb = d5 * d2 - d1 * d6;
+    if(vb <= 0 && d2 >= 0 && d6 <= 0)
+    {
+        CoordT w = d2 / (d2 - d6);
+        return p.distance2(a + ac * w);
+    }
+
+    CoordT va = d3 * d6 - d5 * d4;
+    if(va <= 0 && (d4 - d3) >= 0 && (d5 - d6) >= 0)
+    {
+        CoordT w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return p.distance2(b + (c - b) * w);
+    }
+
+    CoordT denom = 1 / (va + vb + vc);
+    CoordT v = vb * denom;
+    CoordT w = vc * denom;
+    return p.distance2(a + ab * v + ac * w);
+}
+
+template<typename BaseVecT>
+PointToMeshError pointToMeshError(
+    PointBufferPtr points,
+    MeshBufferPtr mesh,
+    int k,
+    const std::string& searchTreeName)
+{
+    PointToMeshError error;
+    if(!points || !mesh || points->numPoints() == 0 || mesh->numFaces() == 0)
+    {
+        return error;
+    }
+
+    const size_t numVertices = mesh->numVertices();
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+
+    // Vertex to face adjacency
+    std::vector<std::vector<size_t>> vertexFaces(numVertices);
+    for(size_t i = 0; i < numFaces; i++)
+    {
+        for(size_t j = 0; j < 3; j++)
+        {
+            vertexFaces[faces[3 * i + j]].push_back(i);
+        }
+    }
+
+    auto vertex = [&vertices](size_t i)
+    {
+        return BaseVecT(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    PointBufferPtr vertexBuffer = std::make_shared<PointBuffer>(vertices, numVertices);
+    auto tree = getSearchTree<BaseVecT>(searchTreeName, vertexBuffer);
+
+    const size_t n = points->numPoints();
+    floatArr pts = points->getPointArray();
+
+    double sum = 0.0;
+    double sum2 = 0.0;
+    double max = 0.0;
+
+    #pragma omp parallel for reduction(+:sum, sum2) reduction(max:max)
+    for(size_t i = 0; i < n; i++)
+    {
+        BaseVecT p(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+
+        // The tree returns fewer than k neighbors for small meshes
+        std::vector<size_t> neighbors;
+        const int found = tree->kSearch(p, k, neighbors);
+        neighbors.resize(std::min(neighbors.size(), static_cast<size_t>(std::max(found, 0))));
+
+        auto best = std::numeric_limits<typename BaseVecT::CoordType>::max();
+        for(size_t v : neighbors)
+        {
+            if(v >= numVertices)
+            {
+                continue;
+            }
+            for(size_t f : vertexFaces[v])
+            {
+                best = std::min(best, pointTriangleDistance2(
+                    p,
+                    vertex(faces[3 * f]),
+                    vertex(faces[3 * f + 1]),
+                    vertex(faces[3 * f + 2])
+                ));
+            }
+        }
+
+        if(best == std::numeric_limits<typename BaseVecT::CoordType>::max() && !neighbors.empty()
+            && neighbors[0] < numVertices)
+        {
+            // Only isolated vertices nearby
+            best = p.distance2(vertex(neighbors[0]));
+        }
+
+        double d = std::sqrt(static_cast<double>(best));
+        sum += d;
+        sum2 += d * d;
+        max = std::max(max, d);
+    }
+
+    error.numPoints = n;
+    error.mean = sum / n;
+    error.rms = std::sqrt(sum2 / n);
+    error.max = max;
+    return error;
+}
+
+} // namespace lvr2
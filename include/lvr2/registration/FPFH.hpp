@@ -4,6 +4,7 @@
 #include <Eigen/Core>
 
 #include <memory>
+#include <vector>
 
 #include <lvr2/reconstruction/SearchTree.hpp>
 #include <lvr2/types/PointBuffer.hpp>
@@ -28,6 +29,19 @@ using FPFHFeaturePtr = std::shared_ptr<FPFHFeature>;
  */
 FPFHFeaturePtr computeFPFHFeatures(const PointBufferPtr pointCloud, size_t k);
 
+/**
+ * @brief Computes FPFH features only for the given keypoints, e.g. from
+ *        detectISSKeypoints(). The SPFH histograms are computed for the
+ *        keypoints and their neighbors only.
+ *
+ * @param pointCloud        A point cloud containing normals
+ * @param keypoints         Indices of the points to compute features for
+ * @param k                 Number of nearest neighbors
+ * @return FPFHFeaturePtr   An Eigen matrix containing the computed features
+ *                          (33 x keypoints.size())
+ */
+FPFHFeaturePtr computeFPFHFeatures(const PointBufferPtr pointCloud, const std::vector<size_t>& keypoints, size_t k);
+
 } // namespace lvr2
 
 #endif // FPFH
@@ -0,0 +1,121 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Features.hpp
+ *
+ * Local 3D descriptors at keypoints (FPFH, see FPFH.hpp, and SHOT) and
+ * matching of descriptors between two point clouds. The correspondences
+ * are the input of global registration and object recognition.
+ */
+
+#ifndef LVR2_REGISTRATION_FEATURES_HPP
+#define LVR2_REGISTRATION_FEATURES_HPP
+
+#include "lvr2/registration/FPFH.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <Eigen/Core>
+
+#include <memory>
+#include <vector>
+
+namespace lvr2
+{
+
+using SHOTFeature = Eigen::MatrixXf;
+using SHOTFeaturePtr = std::shared_ptr<SHOTFeature>;
+
+/**
+ * @brief Computes SHOT descriptors (Tombari et al., 2010) for the given
+ *        keypoints. Requires normals.
+ *
+ *        A repeatable local reference frame is estimated from the distance
+ *        weighted covariance of the neighborhood. The spherical support is
+ *        divided into 32 volumes (8 azimuth, 2 elevation and 2 radial
+ *        divisions) and for each volume, a histogram with 11 bins of the
+ *        cosine between the neighbor normals and the normal axis of the
+ *        reference frame is accumulated. The cosine bins are interpolated
+ *        linearly, the spatial bins are not. Descriptors are normalized to
+ *        unit length and zero if a keypoint has fewer than 5 neighbors.
+ *
+ * @param pointCloud    A point cloud containing normals
+ * @param keypoints     Indices of the points to compute descriptors for
+ * @param radius        Radius of the support
+ * @return SHOTFeaturePtr An Eigen matrix containing the descriptors
+ *                        (352 x keypoints.size())
+ */
+SHOTFeaturePtr computeSHOTFeatures(const PointBufferPtr pointCloud, const std::vector<size_t>& keypoints, float radius);
+
+/**
+ * @brief A match between the descriptors of two point clouds
+ */
+struct FeatureCorrespondence
+{
+    /// Column of the source descriptor
+    size_t source;
+
+    /// Column of the target descriptor
+    size_t target;
+
+    /// Euclidean distance of the descriptors
+    float distance;
+};
+
+struct FeatureMatchingOptions
+{
+    /// Only keep matches that are also the nearest neighbor of the target descriptor
+    bool mutual = true;
+
+    /// Ratio test: the distance to the nearest target descriptor has to be
+    /// smaller than this fraction of the distance to the second nearest.
+    /// Disabled if >= 1.
+    float maxRatio = 0.9f;
+
+    /// Maximum distance of matched descriptors. Disabled if <= 0.
+    float maxDistance = 0.0f;
+};
+
+/**
+ * @brief Matches each source descriptor to its nearest target descriptor
+ *        by brute force search in descriptor space and filters the matches.
+ *
+ * @param source    Source descriptors, one per column
+ * @param target    Target descriptors, one per column, with the same number of rows
+ * @param options   Filter parameters
+ * @return The correspondences sorted by increasing descriptor distance
+ */
+std::vector<FeatureCorrespondence> matchFeatures(
+    const Eigen::MatrixXf& source,
+    const Eigen::MatrixXf& target,
+    const FeatureMatchingOptions& options = FeatureMatchingOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_FEATURES_HPP
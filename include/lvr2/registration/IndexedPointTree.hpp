@@ -0,0 +1,77 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * IndexedPointTree.hpp
+ *
+ * Search tree over the points of a point buffer that keeps the index of
+ * each point, shared by the keypoint detectors and the local descriptors.
+ */
+
+#ifndef LVR2_REGISTRATION_INDEXEDPOINTTREE_HPP
+#define LVR2_REGISTRATION_INDEXEDPOINTTREE_HPP
+
+#include "lvr2/algorithm/KDTree.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <memory>
+
+namespace lvr2
+{
+
+/// A point of the search tree with its index in the buffer
+struct IndexedPoint3f
+{
+    Vector3f point;
+    size_t index;
+    float operator[](unsigned int i) const { return point[i]; }
+};
+
+using IndexedPointTree = KDTree<IndexedPoint3f>;
+
+/**
+ * @brief Builds a search tree over all points of the buffer.
+ */
+inline IndexedPointTree::Ptr buildIndexedPointTree(PointBufferPtr buffer)
+{
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    std::unique_ptr<IndexedPoint3f[]> treePoints(new IndexedPoint3f[n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        treePoints[i].point = Vector3f(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        treePoints[i].index = i;
+    }
+    return IndexedPointTree::create(std::move(treePoints), n);
+}
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_INDEXEDPOINTTREE_HPP
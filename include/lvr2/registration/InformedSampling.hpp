@@ -0,0 +1,115 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * InformedSampling.hpp
+ *
+ * Subsampling strategies that select well-conditioned point subsets for
+ * point-to-plane ICP instead of spatially uniform ones.
+ */
+
+#ifndef LVR2_REGISTRATION_INFORMEDSAMPLING_HPP
+#define LVR2_REGISTRATION_INFORMEDSAMPLING_HPP
+
+#include "lvr2/registration/ReductionAlgorithm.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Normal-space sampling (Rusinkiewicz and Levoy, 2001).
+ *
+ *        The points are sorted into buckets by the direction of their
+ *        normals and the samples are drawn from the buckets in turn. Thus,
+ *        the few points on small structures with distinct normals are
+ *        kept even if most points lie on a few large planes.
+ */
+class NormalSpaceSampling : public ReductionAlgorithm
+{
+public:
+    /**
+     * @param numPoints     Number of points to select
+     * @param binsPerAxis   Number of buckets per axis of the normal
+     *                      parametrization (azimuth and polar angle)
+     * @param seed          Seed for the random selection within buckets
+     */
+    NormalSpaceSampling(size_t numPoints, size_t binsPerAxis = 8, unsigned int seed = 0)
+        : m_numPoints(numPoints), m_binsPerAxis(binsPerAxis), m_seed(seed) {}
+
+    /// Returns the selected points with all channels. Requires normals.
+    virtual PointBufferPtr getReducedPoints() override;
+
+    /// Returns the indices of the selected points
+    std::vector<size_t> selectIndices() const;
+
+private:
+    size_t          m_numPoints;
+    size_t          m_binsPerAxis;
+    unsigned int    m_seed;
+};
+
+/**
+ * @brief Covariance (stability) sampling (Gelfand et al., 2003).
+ *
+ *        Each point with normal n contributes the constraint vector
+ *        (p x n, n) to the 6x6 covariance matrix of point-to-plane ICP.
+ *        Points are selected greedily such that the eigen directions of
+ *        this matrix are constrained as evenly as possible. This keeps the
+ *        points with the highest leverage on directions that are otherwise
+ *        weakly constrained, e.g. sliding along a corridor.
+ */
+class CovarianceSampling : public ReductionAlgorithm
+{
+public:
+    /**
+     * @param numPoints Number of points to select
+     */
+    CovarianceSampling(size_t numPoints) : m_numPoints(numPoints) {}
+
+    /// Returns the selected points with all channels. Requires normals.
+    virtual PointBufferPtr getReducedPoints() override;
+
+    /// Returns the indices of the selected points
+    std::vector<size_t> selectIndices() const;
+
+    /**
+     * @brief Returns the condition number (largest / smallest eigenvalue)
+     *        of the covariance matrix of the given points. Large values
+     *        indicate that ICP is poorly constrained in some direction.
+     */
+    static double conditionNumber(PointBufferPtr points, const std::vector<size_t>& indices);
+
+private:
+    size_t m_numPoints;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_INFORMEDSAMPLING_HPP
@@ -0,0 +1,121 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Keypoints.hpp
+ *
+ * 3D keypoint detectors that select distinctive points of a point cloud,
+ * e.g. to compute FPFH features (see computeFPFHFeatures()) only for a
+ * sparse set of points for matching and the initialization of registration.
+ */
+
+#ifndef LVR2_REGISTRATION_KEYPOINTS_HPP
+#define LVR2_REGISTRATION_KEYPOINTS_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct ISSKeypointOptions
+{
+    /// Radius of the neighborhood used to compute the scatter matrix
+    float salientRadius = 0.1f;
+
+    /// Radius of the non maximum suppression
+    float nonMaxRadius = 0.1f;
+
+    /// Upper bound of the ratio of the second to the first eigenvalue
+    float gamma21 = 0.975f;
+
+    /// Upper bound of the ratio of the third to the second eigenvalue
+    float gamma32 = 0.975f;
+
+    /// Points with fewer neighbors within salientRadius are skipped
+    size_t minNeighbors = 5;
+
+    /// Maximum number of neighbors that are considered within the radii
+    size_t maxNeighbors = 100;
+};
+
+struct HarrisKeypointOptions
+{
+    /// Radius of the neighborhood whose normals are evaluated
+    float radius = 0.1f;
+
+    /// Radius of the non maximum suppression
+    float nonMaxRadius = 0.1f;
+
+    /// Minimum response of a keypoint
+    float threshold = 1e-6f;
+
+    /// Sensitivity factor of the response det(C) - k * trace(C)^2
+    float k = 0.04f;
+
+    /// Points with fewer neighbors within radius are skipped
+    size_t minNeighbors = 5;
+
+    /// Maximum number of neighbors that are considered within the radii
+    size_t maxNeighbors = 100;
+};
+
+/**
+ * @brief Intrinsic Shape Signatures keypoints (Zhong, 2009).
+ *
+ *        The eigenvalues l1 >= l2 >= l3 of the scatter matrix of the
+ *        neighborhood of each point are computed. Points with distinct
+ *        eigenvalues (l2 / l1 < gamma21 and l3 / l2 < gamma32) are
+ *        candidates and the local maxima of l3 are kept.
+ *
+ * @return Indices of the keypoints sorted by decreasing saliency
+ */
+std::vector<size_t> detectISSKeypoints(
+    PointBufferPtr buffer,
+    const ISSKeypointOptions& options = ISSKeypointOptions()
+);
+
+/**
+ * @brief Harris 3D keypoints based on the covariance C of the normals in
+ *        the neighborhood of each point. The response det(C) - k * trace(C)^2
+ *        is positive if the normals vary in all directions, e.g. at corners,
+ *        and negative at edges. The local maxima above the threshold are
+ *        kept. Requires normals.
+ *
+ * @return Indices of the keypoints sorted by decreasing response. Empty if
+ *         the buffer has no normals.
+ */
+std::vector<size_t> detectHarrisKeypoints(
+    PointBufferPtr buffer,
+    const HarrisKeypointOptions& options = HarrisKeypointOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_KEYPOINTS_HPP
@@ -0,0 +1,119 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ObjectLocalization.hpp
+ *
+ * Localization of a known reference mesh (e.g. a CAD model) in a scanned
+ * point cloud: coarse alignment by matching FPFH descriptors at ISS
+ * keypoints with RANSAC, followed by ICP refinement.
+ */
+
+#ifndef LVR2_REGISTRATION_OBJECTLOCALIZATION_HPP
+#define LVR2_REGISTRATION_OBJECTLOCALIZATION_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+namespace lvr2
+{
+
+struct ObjectLocalizationOptions
+{
+    /// Resolution of the matching. The model is sampled and both clouds are
+    /// downsampled to one point per voxel of this size. All radii and
+    /// distances below are derived from it if they are <= 0.
+    float voxelSize = 0.01f;
+
+    /// Number of neighbors for the normal estimation of the scene (if it has
+    /// no normals) and for the FPFH descriptors
+    size_t kn = 10;
+    size_t kf = 30;
+
+    /// Maximum number of RANSAC iterations
+    int ransacIterations = 10000;
+
+    /// Maximum distance of a correspondence to be a RANSAC inlier. Defaults
+    /// to 2 * voxelSize.
+    float inlierDistance = 0.0f;
+
+    /// Maximum number of ICP iterations
+    int icpIterations = 50;
+
+    /// Maximum distance of ICP point pairs and of the points that contribute
+    /// to the fit error. Defaults to 3 * voxelSize.
+    float maxDistance = 0.0f;
+
+    /// Seed for the random sampling
+    unsigned int seed = 0;
+};
+
+struct ObjectLocalizationResult
+{
+    /// Transformation from model to scene coordinates
+    Transformd pose = Transformd::Identity();
+
+    /// RMS distance of the model samples that have a scene point within maxDistance
+    double rmsError = 0;
+
+    /// Fraction of the model samples that have a scene point within maxDistance
+    double fitness = 0;
+
+    /// Number of descriptor correspondences
+    size_t numCorrespondences = 0;
+
+    /// Number of correspondences that agree with the coarse pose
+    size_t numInliers = 0;
+
+    /// False, if no coarse alignment was found. pose is the identity in this case.
+    bool success = false;
+};
+
+/**
+ * @brief Localizes a reference mesh in a scanned point cloud.
+ *
+ *        The mesh is sampled with normals, both clouds are downsampled,
+ *        ISS keypoints with FPFH descriptors are matched and a coarse pose
+ *        is estimated with RANSAC. The pose is refined with point to point
+ *        ICP and the fit error is evaluated. If the scene has no normals,
+ *        they are estimated and oriented towards the origin (the scanner).
+ *
+ * @param model     The reference mesh
+ * @param scene     The scanned point cloud
+ * @param options   Matching parameters
+ */
+ObjectLocalizationResult localizeObject(
+    MeshBufferPtr model,
+    PointBufferPtr scene,
+    const ObjectLocalizationOptions& options = ObjectLocalizationOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_OBJECTLOCALIZATION_HPP
@@ -0,0 +1,180 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PoseGraph.hpp
+ *
+ * Pose graph optimization with relative pose constraints. In contrast to
+ * GraphSLAM, which works on the points of the scans, the graph only
+ * contains the poses and measured relative transformations between them,
+ * e.g. from odometry, pairwise ICP and loop closures. Optimizing the graph
+ * distributes the drift that is revealed by loop closures over all poses.
+ */
+
+#ifndef LVR2_REGISTRATION_POSEGRAPH_HPP
+#define LVR2_REGISTRATION_POSEGRAPH_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/ScanTypes.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief A measured relative transformation between two poses
+ */
+struct PoseGraphEdge
+{
+    /// Index of the first pose
+    size_t from = 0;
+
+    /// Index of the second pose
+    size_t to = 0;
+
+    /// Pose of to in the frame of from, i.e. X_from^-1 * X_to
+    Transformd measurement = Transformd::Identity();
+
+    /// Inverse covariance of the measurement. The first three components
+    /// are the translation, the last three the rotation (axis angle).
+    Matrix6d information = Matrix6d::Identity();
+
+    /// Loop closures are subject to the robust kernel, see PoseGraphOptions::huberDelta
+    bool loopClosure = false;
+};
+
+struct PoseGraphOptions
+{
+    /// Maximum number of Gauss-Newton iterations
+    int maxIterations = 20;
+
+    /// Stop if the relative decrease of the error is below this value
+    double epsilon = 1e-6;
+
+    /// Loop closure residuals (Mahalanobis distance) larger than this value are
+    /// down weighted with a Huber kernel, so single wrong loop closures do not
+    /// distort the whole graph. Disabled if <= 0.
+    double huberDelta = 0;
+};
+
+struct PoseGraphResult
+{
+    /// Sum of the squared Mahalanobis distances of all edges before optimization
+    double initialError = 0;
+
+    /// Sum of the squared Mahalanobis distances of all edges after optimization
+    double finalError = 0;
+
+    /// Number of performed iterations
+    int iterations = 0;
+
+    /// True, if the error converged before the maximum number of iterations.
+    /// False if the maximum was reached or a step increased the error.
+    bool converged = false;
+};
+
+/**
+ * @brief Graph of poses and relative pose constraints, optimized with
+ *        Gauss-Newton on SE(3).
+ *
+ * This is deliberately separate from GraphSLAM: GraphSLAM is a step of
+ * SLAMAlign that needs the points of all scans and re-estimates the
+ * constraints from point correspondences in every iteration. The pose
+ * graph only needs the poses and already measured relative transformations,
+ * so it also corrects projects whose scans are registered by other means
+ * (e.g. external ICP or GNSS/odometry) and is cheap enough to run on large
+ * projects before fusion. Use poseGraphFromScanProject() and
+ * applyPoseGraph() to run it on a registered scan project.
+ */
+class PoseGraph
+{
+public:
+    PoseGraph() = default;
+
+    /**
+     * @brief Adds a pose to the graph. The first pose is fixed.
+     *
+     * @return The index of the pose
+     */
+    size_t addPose(const Transformd& pose);
+
+    /**
+     * @brief Adds a constraint between two poses
+     *
+     * @throws std::out_of_range if one of the indices does not exist
+     */
+    void addEdge(const PoseGraphEdge& edge);
+
+    /// Adds an edge with the given relative transformation
+    void addEdge(size_t from, size_t to, const Transformd& measurement,
+                 const Matrix6d& information = Matrix6d::Identity(), bool loopClosure = false);
+
+    /// Excludes a pose from the optimization
+    void setFixed(size_t index, bool fixed = true);
+
+    /**
+     * @brief Optimizes the poses by Gauss-Newton on SE(3). Poses are
+     *        updated by right multiplication with a local increment.
+     */
+    PoseGraphResult optimize(const PoseGraphOptions& options = PoseGraphOptions());
+
+    /// Sum of the squared Mahalanobis distances of all edges
+    double error() const;
+
+    const std::vector<Transformd>& poses() const { return m_poses; }
+
+    const std::vector<PoseGraphEdge>& edges() const { return m_edges; }
+
+    size_t numPoses() const { return m_poses.size(); }
+
+private:
+    std::vector<Transformd> m_poses;
+    std::vector<bool> m_fixed;
+    std::vector<PoseGraphEdge> m_edges;
+};
+
+/**
+ * @brief Creates a pose graph from the scan positions of a project. The
+ *        poses are the transformations of the positions, consecutive
+ *        positions are connected by their current relative transformation
+ *        and the given loop closures are added.
+ */
+PoseGraph poseGraphFromScanProject(ScanProjectPtr project, const std::vector<PoseGraphEdge>& loopClosures);
+
+/**
+ * @brief Writes the (optimized) poses of the graph back to the scan
+ *        positions of the project
+ *
+ * @return false, if the number of poses and positions differ
+ */
+bool applyPoseGraph(ScanProjectPtr project, const PoseGraph& graph);
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_POSEGRAPH_HPP
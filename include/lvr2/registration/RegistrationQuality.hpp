@@ -0,0 +1,118 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * RegistrationQuality.hpp
+ *
+ * Validation of the alignment of two registered point clouds before they
+ * are merged and reconstructed: overlap, residuals and a per region error
+ * heatmap that shows where the clouds are misaligned.
+ */
+
+#ifndef LVR2_REGISTRATION_REGISTRATIONQUALITY_HPP
+#define LVR2_REGISTRATION_REGISTRATIONQUALITY_HPP
+
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+struct RegistrationQualityOptions
+{
+    /// Source points whose nearest target point is farther away are
+    /// outside of the overlap
+    double maxDistance = 0.1;
+
+    /// Number of target points used to fit the local plane for point to
+    /// plane residuals. Point to point distances are used if < 3.
+    size_t k = 8;
+
+    /// Edge length of the cubic regions of the error heatmap
+    double regionSize = 1.0;
+};
+
+struct RegistrationQualityReport
+{
+    /// Fraction of source points with a target point within maxDistance
+    double overlapRatio = 0;
+
+    /// Number of source points in the overlap
+    size_t numOverlapping = 0;
+
+    /// RMS of the residuals of the overlapping points
+    double rmsResidual = 0;
+
+    /// Mean of the residuals of the overlapping points
+    double meanResidual = 0;
+
+    /// Residual of each source point. NaN outside of the overlap.
+    std::vector<float> residuals;
+
+    /// RMS residual of the region of each source point. NaN if the region
+    /// does not overlap the target.
+    std::vector<float> regionErrors;
+};
+
+/**
+ * @brief Compares two registered point clouds in a common coordinate system.
+ *
+ *        For each source point, the residual is its distance to the plane
+ *        fitted to its k nearest target points (or to the nearest target
+ *        point). Only source points with a target point within
+ *        options.maxDistance belong to the overlap and contribute to the
+ *        statistics.
+ *
+ * @param source    The cloud that is evaluated
+ * @param target    The reference cloud
+ * @param options   Evaluation parameters
+ */
+RegistrationQualityReport registrationQuality(
+    PointBufferPtr source,
+    PointBufferPtr target,
+    const RegistrationQualityOptions& options = RegistrationQualityOptions()
+);
+
+/**
+ * @brief Stores the region errors of the report in the float channel
+ *        "registration_error" of the source cloud and replaces its colors
+ *        with a heatmap (jet, blue = aligned, red = maxError or larger).
+ *        Points outside of the overlap are gray.
+ *
+ * @param maxError  Error that is mapped to the end of the color scale
+ */
+void addRegistrationHeatmap(
+    PointBufferPtr source,
+    const RegistrationQualityReport& report,
+    float maxError
+);
+
+} // namespace lvr2
+
+#endif // LVR2_REGISTRATION_REGISTRATIONQUALITY_HPP
@@ -49,8 +49,16 @@ struct Material
     boost::optional<TextureHandle> m_texture;
     /// Optional color
     boost::optional<RGB8Color> m_color;
+    /// Metalness in [0, 1] of the glTF metallic-roughness model
+    float m_metallic = 0.0f;
+    /// Roughness in [0, 1] of the glTF metallic-roughness model
+    float m_roughness = 1.0f;
     /// Map from texture layer to texture
     LayerMap m_layers;
+    /// Name of the material, e.g. from an MTL file
+    std::string m_name;
+    /// Path of the image file the texture was loaded from, if any
+    boost::optional<std::string> m_textureFile;
 };
 
 } // namespace lvr2
@@ -0,0 +1,157 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * PhotoTexturing.hpp
+ *
+ * Texturing of meshes from registered photos: each face is projected into
+ * all photos, the best unoccluded view is selected and the photos are used
+ * directly as textures.
+ */
+
+#ifndef LVR2_TEXTURE_PHOTOTEXTURING_HPP
+#define LVR2_TEXTURE_PHOTOTEXTURING_HPP
+
+#include "lvr2/types/CameraModels.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <boost/filesystem.hpp>
+#include <boost/optional.hpp>
+#include <opencv2/core.hpp>
+
+#include <memory>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief A photo with its intrinsics and pose
+ */
+struct Photo
+{
+    /// BGR or grayscale image
+    cv::Mat image;
+
+    /// Projection model, e.g. PinholeModel or FisheyeModel. The camera looks
+    /// along its z axis, x points to the right and y down in the image.
+    std::shared_ptr<const CameraModel> model;
+
+    /// Camera to world transformation
+    Transformd cameraToWorld = Transformd::Identity();
+};
+
+/**
+ * @brief A set of registered photos of a scene
+ */
+class PhotoSet
+{
+public:
+    /// Adds the photo and returns its index
+    size_t addPhoto(const cv::Mat& image, std::shared_ptr<const CameraModel> model, const Transformd& cameraToWorld);
+
+    /**
+     * @brief Loads the image from the given file and adds it
+     *
+     * @throws std::runtime_error if the image can not be read
+     */
+    size_t addPhoto(const boost::filesystem::path& file, std::shared_ptr<const CameraModel> model, const Transformd& cameraToWorld);
+
+    size_t size() const { return m_photos.size(); }
+
+    const Photo& operator[](size_t index) const { return m_photos[index]; }
+
+    /// Position of the camera of the given photo
+    Vector3f cameraPosition(size_t index) const;
+
+    /**
+     * @brief Projects the world point into the given photo
+     *
+     * @return The pixel coordinates or none, if the point is behind the
+     *         camera or further than border pixels outside of the image
+     */
+    boost::optional<Vector2f> project(size_t index, const Vector3f& point, float border = 0) const;
+
+private:
+    std::vector<Photo> m_photos;
+
+    /// Inverse poses of the photos
+    std::vector<Transformd> m_worldToCamera;
+};
+
+struct PhotoTexturingOptions
+{
+    /// Minimum cosine of the angle between the face normal and the direction to the camera
+    float minCosAngle = 0.2f;
+
+    /// Skip views in which a face is occluded by other faces
+    bool occlusion = true;
+
+    /// Maximum distance by which an occluding face may be in front of the face centroid
+    float occlusionTolerance = 0.01f;
+
+    /// Minimum distance of the projected faces to the image border in pixels
+    float border = 2.0f;
+};
+
+/**
+ * @brief Selects the best photo for each face, i.e. the one in which the
+ *        projection of the face has the largest area. This prefers close,
+ *        frontal and high resolution views.
+ *
+ * @return The photo index for each face, or -1 if the face is not visible in any photo
+ */
+std::vector<int> selectViews(
+    MeshBufferPtr mesh,
+    const PhotoSet& photos,
+    const PhotoTexturingOptions& options = PhotoTexturingOptions()
+);
+
+/**
+ * @brief Creates a textured copy of the mesh that uses each photo as a
+ *        texture. Vertices are duplicated for each photo their faces are
+ *        projected into. Faces without a view use an untextured gray material.
+ *
+ * @param mesh      The mesh
+ * @param photos    The photos
+ * @param views     The photo of each face, see selectViews()
+ */
+MeshBufferPtr texturizeFromPhotos(MeshBufferPtr mesh, const PhotoSet& photos, const std::vector<int>& views);
+
+/// Selects the views with selectViews() and texturizes the mesh
+MeshBufferPtr texturizeFromPhotos(
+    MeshBufferPtr mesh,
+    const PhotoSet& photos,
+    const PhotoTexturingOptions& options = PhotoTexturingOptions()
+);
+
+} // namespace lvr2
+
+#endif // LVR2_TEXTURE_PHOTOTEXTURING_HPP
@@ -0,0 +1,101 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * TextureBaking.hpp
+ *
+ * Transfers surface detail of a high resolution mesh into normal and
+ * height maps in the UV layout of a simplified mesh.
+ */
+
+#ifndef LVR2_TEXTURE_TEXTUREBAKING_HPP
+#define LVR2_TEXTURE_TEXTUREBAKING_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <opencv2/core.hpp>
+
+#include <string>
+
+namespace lvr2
+{
+
+struct BakingOptions
+{
+    /// Size of the generated maps in pixels
+    int width = 1024;
+    int height = 1024;
+
+    /// Maximum distance between the simplified and the high resolution
+    /// surface. Rays are cast along the normals of the simplified mesh
+    /// within [-maxDistance, maxDistance].
+    float maxDistance = 0.1f;
+
+    /// Store normals relative to the tangent frame of the simplified mesh
+    /// (as expected by most game engines) instead of in object space
+    bool tangentSpace = true;
+
+    /// Number of pixels the baked regions are extended into empty areas
+    /// to avoid seams when the maps are filtered
+    int padding = 2;
+};
+
+struct BakedMaps
+{
+    /// 8 bit three channel normal map in OpenCV (BGR) channel order
+    cv::Mat normalMap;
+
+    /// Signed displacement along the normals of the simplified mesh (float)
+    cv::Mat heightMap;
+
+    /// Non zero for pixels that received a value
+    cv::Mat mask;
+};
+
+/**
+ * @brief Bakes normal and height maps from a high resolution mesh onto the
+ *        texture coordinates of a simplified version of it.
+ *
+ * @param highRes   The high resolution mesh. Uses its vertex normals if
+ *                  present, face normals otherwise.
+ * @param lowRes    The simplified mesh. Needs texture coordinates for all
+ *                  vertices. Vertex normals are computed if missing.
+ * @param options   Baking parameters
+ */
+BakedMaps bakeMaps(MeshBufferPtr highRes, MeshBufferPtr lowRes, const BakingOptions& options = BakingOptions());
+
+/**
+ * @brief Saves the maps as <prefix>_normal.png, <prefix>_height.tif
+ *        (32 bit float) and <prefix>_height.png (16 bit, normalized to
+ *        [-maxDistance, maxDistance]).
+ */
+void saveBakedMaps(const BakedMaps& maps, const std::string& prefix, float maxDistance);
+
+} // namespace lvr2
+
+#endif // LVR2_TEXTURE_TEXTUREBAKING_HPP
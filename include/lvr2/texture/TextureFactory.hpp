@@ -37,7 +37,14 @@
 #ifndef TEXTUREFACTORY_H_
 #define TEXTUREFACTORY_H_
 
+#include <cstdint>
 #include <string>
+#include <vector>
+
+namespace cv
+{
+class Mat;
+}
 
 namespace lvr2
 {
@@ -56,9 +63,50 @@ public:
     static Texture readTexture(std::string filename);
 
     /**
-     * @brief   TODO
+     * @brief   Saves the texture to the given file. The image format is
+     *          derived from the file extension (e.g. .png, .jpg, .ppm).
+     *          The alpha channel of RGBA textures is kept for PNG files.
      */
     static void saveTexture(const Texture& texture, std::string filename);
+
+    /**
+     * @brief   Encodes the texture into an in-memory image, e.g. for
+     *          embedding it into glTF files.
+     *
+     * @param texture   The texture to encode
+     * @param extension The image format, e.g. ".png", ".jpg" or ".webp"
+     * @param output    The encoded image data
+     * @param quality   JPEG/WebP quality in [1, 100] or -1 for the default
+     *
+     * @return false, if the texture could not be encoded
+     */
+    static bool encodeTexture(const Texture& texture, const std::string& extension,
+                              std::vector<uint8_t>& output, int quality = -1);
+
+    /**
+     * @brief   Decodes an in-memory image (PNG, JPEG, ...) into a texture.
+     *          Returns an empty texture if the data can not be decoded.
+     */
+    static Texture decodeTexture(const std::vector<uint8_t>& data);
+
+    /**
+     * @brief   Converts an OpenCV image (BGR(A) or grayscale, any depth) into
+     *          an 8 bit texture. Returns an empty texture for other formats.
+     */
+    static Texture fromMat(const cv::Mat& image);
+
+    /**
+     * @brief   Returns a copy of the texture with three 8 bit RGB channels
+     *          for consumers that do not support grayscale or RGBA data.
+     *          Gray values are replicated, alpha channels are dropped.
+     */
+    static Texture toRGB(const Texture& texture);
+
+    /**
+     * @brief   Returns the MIME type for the given image extension,
+     *          e.g. "image/png" for ".png"
+     */
+    static std::string mimeType(const std::string& extension);
 };
 
 } // namespace lvr2
@@ -33,6 +33,7 @@
 #include <vector>
 #include "MultiChannelMap.hpp"
 #include "lvr2/io/DataStruct.hpp"
+#include "lvr2/types/GeoMetadata.hpp"
 
 namespace lvr2 {
 
@@ -664,6 +665,86 @@ public:
         return getAtomic<int>(name);
     }
 
+    ///////////////////////////////
+    //// Georeferencing ////
+
+    /**
+     * @brief Attaches georeferencing information to the buffer. It is
+     *        stored as the atomic channels "geo_epsg" and "geo_offset",
+     *        replacing any existing information.
+     *
+     * @param[in] geo EPSG code and global offset of the local coordinates.
+     */
+    inline void setGeoMetadata(const GeoMetadata& geo)
+    {
+        this->erase("geo_epsg");
+        this->erase("geo_offset");
+
+        addAtomic<int>(geo.epsg, "geo_epsg");
+
+        Channel<double> offset(1, 3);
+        offset[0][0] = geo.offset[0];
+        offset[0][1] = geo.offset[1];
+        offset[0][2] = geo.offset[2];
+        this->insert({"geo_offset", offset});
+    }
+
+    /**
+     * @brief Gets the georeferencing information of the buffer.
+     *
+     * @return The information as optional. The optional is not set if the
+     *         buffer is not georeferenced.
+     */
+    inline boost::optional<GeoMetadata> getGeoMetadata() const
+    {
+        boost::optional<GeoMetadata> ret;
+        auto epsg = getChannel<int>("geo_epsg");
+        auto offset = getChannel<double>("geo_offset");
+        if(epsg || offset)
+        {
+            GeoMetadata geo;
+            if(epsg)
+            {
+                geo.epsg = (*epsg)[0][0];
+            }
+            if(offset && offset->width() == 3)
+            {
+                geo.offset = {(*offset)[0][0], (*offset)[0][1], (*offset)[0][2]};
+            }
+            ret = geo;
+        }
+        return ret;
+    }
+
+    /**
+     * @brief Adds the global offset to the given coordinate channel and
+     *        resets the stored offset to zero. The EPSG code is kept.
+     *        Note that large offsets lose precision in float channels.
+     *
+     * @param[in] coordinateChannel Key of the float channel to transform,
+     *            e.g. "points" or "vertices".
+     */
+    inline void applyGeoOffset(const std::string& coordinateChannel)
+    {
+        boost::optional<GeoMetadata> geo = getGeoMetadata();
+        FloatChannelOptional coords = getChannel<float>(coordinateChannel);
+        if(!geo || !coords || coords->width() < 3)
+        {
+            return;
+        }
+
+        for(size_t i = 0; i < coords->numElements(); i++)
+        {
+            for(size_t j = 0; j < 3; j++)
+            {
+                coords->dataPtr()[i * coords->width() + j] += geo->offset[j];
+            }
+        }
+
+        geo->offset = {0.0, 0.0, 0.0};
+        setGeoMetadata(*geo);
+    }
+
     template<typename V>
     BaseBuffer manipulate(V visitor)
     {
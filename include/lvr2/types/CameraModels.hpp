@@ -1,6 +1,8 @@
 #ifndef CAMERAMODELS
 #define CAMERAMODELS
 
+#include <array>
+#include <cmath>
 #include <vector>
 #include <string>
 #include <memory>
@@ -163,6 +165,78 @@ namespace lvr2
     }
 
 
+    /**
+     * @brief Fisheye camera with the equidistant projection model of
+     *        OpenCV's fisheye module (Kannala-Brandt with four coefficients)
+     */
+    struct FisheyeModel : CameraModel
+    {
+        static constexpr char type[] = "fisheye";
+
+        double fx = 0;
+        double fy = 0;
+        double cx = 0;
+        double cy = 0;
+        unsigned width = 0;
+        unsigned height = 0;
+
+        /// Distortion coefficients k1 - k4 of the angle polynomial
+        std::array<double, 4> k = {0, 0, 0, 0};
+
+        Eigen::Vector2f projectPoint(const Eigen::Vector3f& p) const override
+        {
+            const double r = std::hypot(p.x(), p.y());
+            if (r < 1e-12)
+            {
+                return Eigen::Vector2f(cx, cy);
+            }
+
+            // Angle to the optical axis, also valid for points behind the camera
+            const double theta = std::atan2(r, p.z());
+            const double theta2 = theta * theta;
+            const double thetaD = theta * (1 + theta2 * (k[0] + theta2 * (k[1] + theta2 * (k[2] + theta2 * k[3]))));
+
+            const double scale = thetaD / r;
+            return Eigen::Vector2f(fx * p.x() * scale + cx, fy * p.y() * scale + cy);
+        }
+    };
+
+    using FisheyeModelPtr = std::shared_ptr<FisheyeModel>;
+
+    /**
+     * @brief Spherical panorama in equirectangular projection. The camera
+     *        frame has z up, the image rows cover the elevation from +90
+     *        to -90 degrees and the columns the full circle of azimuths.
+     */
+    struct EquirectangularModel : CameraModel
+    {
+        static constexpr char type[] = "equirectangular";
+
+        unsigned width = 0;
+        unsigned height = 0;
+
+        /// Azimuth in degrees (counter clockwise from the x axis) of the left image border
+        double azimuthOffset = 180.0;
+
+        /// If true, the azimuth decreases from left to right as seen from the
+        /// center of the panorama, which is the common convention
+        bool clockwise = true;
+
+        Eigen::Vector2f projectPoint(const Eigen::Vector3f& p) const override
+        {
+            const double azimuth = std::atan2(p.y(), p.x()) * 180.0 / M_PI;
+            const double elevation = std::atan2(p.z(), std::hypot(p.x(), p.y())) * 180.0 / M_PI;
+
+            double column = clockwise ? azimuthOffset - azimuth : azimuth - azimuthOffset;
+            column = std::fmod(column, 360.0);
+            if (column < 0)
+            {
+                column += 360.0;
+            }
+            return Eigen::Vector2f(column / 360.0 * width, (90.0 - elevation) / 180.0 * height);
+        }
+    };
+
     struct CylindricalModel : CameraModel
     {
         static constexpr char type[] = "cylindrical";
@@ -0,0 +1,58 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * GeoMetadata.hpp
+ *
+ * Coordinate reference information that can be attached to point
+ * and mesh buffers. Large global coordinates (e.g. UTM) do not fit
+ * into float channels without losing precision, so buffers store local
+ * coordinates and keep the global offset in double precision.
+ */
+
+#ifndef LVR2_TYPES_GEOMETADATA_HPP
+#define LVR2_TYPES_GEOMETADATA_HPP
+
+#include <array>
+
+namespace lvr2
+{
+
+struct GeoMetadata
+{
+    /// EPSG code of the coordinate reference system, 0 if unknown
+    int epsg = 0;
+
+    /// Offset that has to be added to the local coordinates to get
+    /// coordinates in the reference system
+    std::array<double, 3> offset{{0.0, 0.0, 0.0}};
+};
+
+} // namespace lvr2
+
+#endif // LVR2_TYPES_GEOMETADATA_HPP
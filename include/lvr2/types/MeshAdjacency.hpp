@@ -0,0 +1,200 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * MeshAdjacency.hpp
+ *
+ * Vertex and face one-rings and the unique edges of an indexed triangle
+ * mesh in compressed row storage. Used by algorithms that work directly on a MeshBuffer, e.g.
+ * smoothing, normal computation and region growing, see MeshBuffer::getAdjacency().
+ */
+
+#ifndef LVR2_TYPES_MESHADJACENCY_HPP
+#define LVR2_TYPES_MESHADJACENCY_HPP
+
+#include <cstddef>
+#include <limits>
+#include <memory>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief A contiguous range of vertex or face indices
+ */
+class IndexRange
+{
+public:
+    IndexRange(const unsigned int* begin, const unsigned int* end)
+        : m_begin(begin), m_end(end) {}
+
+    const unsigned int* begin() const { return m_begin; }
+    const unsigned int* end() const { return m_end; }
+    size_t size() const { return m_end - m_begin; }
+    bool empty() const { return m_begin == m_end; }
+    unsigned int operator[](size_t i) const { return m_begin[i]; }
+
+private:
+    const unsigned int* m_begin;
+    const unsigned int* m_end;
+};
+
+/**
+ * @brief An undirected edge of a triangle mesh and its incident faces
+ */
+struct MeshEdge
+{
+    /// Marks a missing face in faces
+    static constexpr unsigned int NoFace = std::numeric_limits<unsigned int>::max();
+
+    /// The smaller vertex index
+    unsigned int v1;
+
+    /// The larger vertex index
+    unsigned int v2;
+
+    /// Number of faces that contain the edge
+    unsigned int numFaces;
+
+    /// The first two incident faces in ascending order or NoFace. Use
+    /// MeshAdjacency::edgeFaces() to get all faces of non-manifold edges.
+    unsigned int faces[2];
+
+    /// True, if the edge belongs to exactly one face
+    bool isBoundary() const { return numFaces == 1; }
+
+    /// True, if the edge belongs to more than two faces
+    bool isNonManifold() const { return numFaces > 2; }
+};
+
+/**
+ * @brief Adjacency information of a triangle mesh that is given by a face
+ *        index array with three vertex indices per face.
+ */
+class MeshAdjacency
+{
+public:
+    /**
+     * @brief Builds the adjacency information
+     *
+     * @param faces         Three vertex indices per face
+     * @param numFaces      Number of faces
+     * @param numVertices   Number of vertices. Faces with out of range
+     *                      indices are ignored.
+     */
+    MeshAdjacency(const unsigned int* faces, size_t numFaces, size_t numVertices);
+
+    size_t numVertices() const { return m_vertexFaceOffsets.size() - 1; }
+
+    size_t numFaces() const { return m_faceNeighborOffsets.size() - 1; }
+
+    size_t numEdges() const { return m_edges.size(); }
+
+    /// The unique edges of the mesh, sorted by their vertex indices
+    const std::vector<MeshEdge>& edges() const { return m_edges; }
+
+    /// The edges that belong to exactly one face
+    std::vector<MeshEdge> boundaryEdges() const;
+
+    /// Vertices that share an edge with the given vertex in ascending order
+    IndexRange vertexNeighbors(size_t vertex) const
+    {
+        return range(m_vertexNeighbors, m_vertexNeighborOffsets, vertex);
+    }
+
+    /// Faces that contain the given vertex in ascending order
+    IndexRange vertexFaces(size_t vertex) const
+    {
+        return range(m_vertexFaces, m_vertexFaceOffsets, vertex);
+    }
+
+    /// Faces that share an edge with the given face in ascending order.
+    /// Non-manifold edges connect all faces that contain them.
+    IndexRange faceNeighbors(size_t face) const
+    {
+        return range(m_faceNeighbors, m_faceNeighborOffsets, face);
+    }
+
+    /// Faces that contain the edge between the given vertices in ascending order
+    std::vector<unsigned int> edgeFaces(size_t v1, size_t v2) const;
+
+    /// True, if the edge between the given vertices belongs to exactly one face
+    bool isBoundaryEdge(size_t v1, size_t v2) const;
+
+    /// True, if the vertex has a boundary edge or no faces at all
+    bool isBoundaryVertex(size_t vertex) const;
+
+private:
+    static IndexRange range(
+        const std::vector<unsigned int>& values,
+        const std::vector<size_t>& offsets,
+        size_t i)
+    {
+        return IndexRange(values.data() + offsets[i], values.data() + offsets[i + 1]);
+    }
+
+    std::vector<size_t>       m_vertexNeighborOffsets;
+    std::vector<unsigned int> m_vertexNeighbors;
+    std::vector<size_t>       m_vertexFaceOffsets;
+    std::vector<unsigned int> m_vertexFaces;
+    std::vector<size_t>       m_faceNeighborOffsets;
+    std::vector<unsigned int> m_faceNeighbors;
+    std::vector<MeshEdge>     m_edges;
+};
+
+using MeshAdjacencyPtr = std::shared_ptr<const MeshAdjacency>;
+
+/**
+ * @brief Iterable range over the edges of a MeshAdjacency that keeps the
+ *        adjacency alive, see MeshBuffer::edges()
+ */
+class MeshEdgeRange
+{
+public:
+    using const_iterator = std::vector<MeshEdge>::const_iterator;
+
+    MeshEdgeRange() = default;
+
+    explicit MeshEdgeRange(MeshAdjacencyPtr adjacency)
+        : m_adjacency(adjacency) {}
+
+    const_iterator begin() const { return m_adjacency ? m_adjacency->edges().begin() : m_empty.begin(); }
+    const_iterator end() const { return m_adjacency ? m_adjacency->edges().end() : m_empty.end(); }
+    size_t size() const { return m_adjacency ? m_adjacency->numEdges() : 0; }
+    bool empty() const { return size() == 0; }
+
+private:
+    MeshAdjacencyPtr m_adjacency;
+    std::vector<MeshEdge> m_empty;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_TYPES_MESHADJACENCY_HPP
@@ -31,13 +31,43 @@
 #include "lvr2/texture/Material.hpp"
 #include "lvr2/texture/Texture.hpp"
 #include "lvr2/io/DataStruct.hpp" // floatArr, etc
+#include "lvr2/geometry/Normal.hpp"
 #include "lvr2/types/BaseBuffer.hpp"
+#include "lvr2/types/MeshAdjacency.hpp"
 
+#include <boost/optional.hpp>
+
+#include <limits>
+#include <stdexcept>
+#include <string>
 #include <vector>
 
 namespace lvr2
 {
 
+/// Largest number of vertices that can be addressed by the 32 bit face indices of a MeshBuffer
+constexpr size_t MaxMeshBufferVertices = std::numeric_limits<unsigned int>::max();
+
+///
+/// \brief Thrown if a mesh has more vertices than the 32 bit face indices of
+///        a MeshBuffer can address. Use SimpleFinalizer::applySplit() to
+///        split such meshes into several buffers.
+///
+class IndexOverflowError : public std::overflow_error
+{
+public:
+    explicit IndexOverflowError(size_t numVertices)
+        : std::overflow_error("Mesh with " + std::to_string(numVertices)
+            + " vertices exceeds the 32 bit index range of MeshBuffer")
+        , m_numVertices(numVertices) {}
+
+    /// The number of vertices of the mesh
+    size_t numVertices() const { return m_numVertices; }
+
+private:
+    size_t m_numVertices;
+};
+
 ////
 /// \brief The MeshBuffer Mesh representation for I/O modules.
 ///
@@ -60,6 +90,7 @@ public:
     /// \brief addVertices      Adds the vertex array. Three floats per vertex
     /// \param vertices         The vertex array
     /// \param n                Number of vertices
+    /// \throws IndexOverflowError if n exceeds MaxMeshBufferVertices
     ///
     void setVertices(floatArr vertices, size_t n);
 
@@ -116,11 +147,13 @@ public:
     void setTextures(std::vector<Texture>& textures)
     {
         m_textures = std::move(textures);
+        invalidateAdjacency();
     }
 
     void setMaterials(std::vector<Material>& materials)
     {
         m_materials = std::move(materials);
+        invalidateAdjacency();
     }
 
     ///
@@ -153,6 +186,36 @@ public:
     ///
     floatArr getVertexNormals();
 
+    ///
+    /// \brief getVertexNormal  Returns the normal of the i-th vertex or none, if the buffer
+    ///                         has no vertex normals, i is out of range or the stored vector
+    ///                         has length zero or non-finite coordinates.
+    ///
+    boost::optional<Normal<float>> getVertexNormal(size_t i) const;
+
+    ///
+    /// \brief setVertexNormal  Normalizes the given vector and stores it as normal of the
+    ///                         i-th vertex. Returns false without changing the buffer if
+    ///                         there are no vertex normals, i is out of range or the vector
+    ///                         can not be normalized.
+    ///
+    bool setVertexNormal(size_t i, const BaseVector<float>& normal);
+
+    ///
+    /// \brief normalizeNormals Normalizes all vertex and face normals in place, e.g. after
+    ///                         they were loaded from a file. Returns the number of normals
+    ///                         that could not be normalized and were left unchanged.
+    ///
+    size_t normalizeNormals();
+
+    ///
+    /// \brief computeVertexNormals Computes angle weighted vertex normals from the face
+    ///                         one-rings of getAdjacency() and stores them as vertex normals.
+    ///                         Vertices without faces get a zero normal. Returns false if
+    ///                         the buffer has no faces.
+    ///
+    bool computeVertexNormals();
+
     ///
     /// \brief getTextureCoordinates Returns an array with texture coordinates. Two
     ///                         normalized floats per vertex. Returns an empty array
@@ -194,6 +257,18 @@ public:
     ///
     std::vector<Material>& getMaterials();
 
+    ///
+    /// \brief getFaceMaterial  Returns the material of the given face, or nothing
+    ///                         if the face has no valid material index
+    ///
+    boost::optional<Material&> getFaceMaterial(size_t faceIndex);
+
+    ///
+    /// \brief getMaterialTexture Returns the texture of the given material, or nothing
+    ///                         if the material is untextured
+    ///
+    boost::optional<Texture&> getMaterialTexture(const Material& material);
+
     bool hasVertices() const;
 
     bool hasFaces() const;
@@ -206,10 +281,43 @@ public:
 
     bool hasVertexNormals() const;
 
+    ///
+    /// \brief getAdjacency     Returns the vertex and face one-rings of the mesh. They are
+    ///                         computed on the first call and cached until one of the setters
+    ///                         is called or the vertices or faces are replaced. Returns nullptr
+    ///                         if the buffer has no faces.
+    ///
+    MeshAdjacencyPtr getAdjacency();
+
+    ///
+    /// \brief invalidateAdjacency Drops the cached adjacency. Has to be called after the
+    ///                         face indices were modified in place.
+    ///
+    void invalidateAdjacency();
+
+    ///
+    /// \brief edges            Returns the unique edges of the mesh with their incident faces.
+    ///                         Empty if the buffer has no faces. See getAdjacency().
+    ///
+    MeshEdgeRange edges();
+
+    ///
+    /// \brief boundaryEdges    Returns the edges that belong to exactly one face
+    ///
+    std::vector<MeshEdge> boundaryEdges();
+
     /// TODO: CHANNEL BASED SETTER / GETTER!
 
 private:
 
+    /// Cached result of getAdjacency()
+    MeshAdjacencyPtr         m_adjacency;
+
+    /// Face index array and element counts the cached adjacency was built from
+    const unsigned int*      m_adjacencyFaces = nullptr;
+    size_t                   m_adjacencyNumFaces = 0;
+    size_t                   m_adjacencyNumVertices = 0;
+
     /// Vector containing all material definitions
     std::vector<Material>    m_materials;
 
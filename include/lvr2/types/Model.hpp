@@ -41,7 +41,12 @@
 #include <boost/shared_ptr.hpp>
 
 #include <algorithm>
+#include <ctime>
+#include <iomanip>
 #include <iostream>
+#include <map>
+#include <sstream>
+#include <string>
 
 typedef unsigned int uint;
 
@@ -77,8 +82,32 @@ public:
 
     virtual ~Model() {}
 
+    /**
+     * @brief Records the generating program, its parameters and the current
+     *        time (UTC, ISO 8601) in the metadata, so the provenance of the
+     *        data is embedded in the saved file.
+     */
+    void setProvenance(const std::string& generator, const std::string& parameters = "")
+    {
+        m_metadata["generator"] = generator;
+        if(!parameters.empty())
+        {
+            m_metadata["parameters"] = parameters;
+        }
+
+        std::time_t now = std::time(nullptr);
+        std::stringstream ss;
+        ss << std::put_time(std::gmtime(&now), "%Y-%m-%dT%H:%M:%SZ");
+        m_metadata["timestamp"] = ss.str();
+    }
+
     PointBufferPtr         m_pointCloud;
     MeshBufferPtr          m_mesh;
+
+    /// File header information, e.g. PLY comments ("key value ..." is stored as
+    /// key -> "value ...") and obj_info lines (stored under the key "obj_info").
+    /// Multiple lines with the same key are separated by newlines.
+    std::map<std::string, std::string> m_metadata;
 };
 
 using ModelPtr = std::shared_ptr<Model>;
@@ -29,11 +29,14 @@
 #define LVR2_POINTBUFFER_HPP
 
 #include "lvr2/io/DataStruct.hpp"
+#include "lvr2/geometry/Normal.hpp"
 #include "lvr2/types/BaseBuffer.hpp"
 
 #include <map>
 #include <string>
+#include <vector>
 
+#include <boost/optional.hpp>
 #include <boost/shared_array.hpp>
 #include <iostream>
 
@@ -114,6 +117,34 @@ public:
     /// contains a nullptr.
     ucharArr getColorArray(size_t& w);
 
+    /**
+     * @brief Returns the normal of the i-th point.
+     *
+     * @return The normal or none, if the buffer has no normals, i is out
+     *         of range or the stored vector has length zero or non-finite
+     *         coordinates
+     */
+    boost::optional<Normal<float>> getNormal(size_t i) const;
+
+    /**
+     * @brief Sets the normal of the i-th point. The vector is normalized
+     *        before it is stored.
+     *
+     * @return false, if the buffer has no normals, i is out of range or the
+     *         vector can not be normalized. The buffer is not changed in
+     *         this case.
+     */
+    bool setNormal(size_t i, const BaseVector<float>& normal);
+
+    /**
+     * @brief Normalizes all normals in place, e.g. after they were loaded
+     *        from a file. Normals that can not be normalized (length zero
+     *        or non-finite coordinates) are left unchanged.
+     *
+     * @return The number of normals that could not be normalized
+     */
+    size_t normalizeNormals();
+
     /// True, if buffer contains colors
     bool hasColors() const;
 
@@ -126,6 +157,60 @@ public:
     /// Makes a clone
     PointBuffer clone() const;
 
+    /**
+     * @brief Returns a new buffer that contains only the points with
+     *        the given indices. All per-point channels are subsampled
+     *        consistently, other channels are copied shallow.
+     *
+     * @param indices   Indices of the points to keep. Indices may
+     *                  occur more than once.
+     */
+    PointBuffer select(const std::vector<size_t>& indices) const;
+
+    /**
+     * @brief Permutes the points (and the entries of all per-point
+     *        channels), so that the point indices[i] becomes point i.
+     *        Use e.g. mortonOrder() or hilbertOrder() to obtain a spatially
+     *        coherent layout.
+     *
+     * @param indices   A permutation of 0, ..., numPoints() - 1
+     *
+     * @return false, if indices is not a permutation of the points. The
+     *         buffer is not changed in this case.
+     */
+    bool reorder(const std::vector<size_t>& indices);
+
+    /**
+     * @brief Removes all points (and the associated entries of all
+     *        per-point channels) for which the given predicate returns
+     *        false.
+     *
+     * @param pred      A callable with signature bool(size_t index)
+     *
+     * @return The number of points that were removed
+     */
+    template<typename Pred>
+    size_t retain(Pred pred)
+    {
+        const size_t n = numPoints();
+        std::vector<size_t> indices;
+        indices.reserve(n);
+        for(size_t i = 0; i < n; i++)
+        {
+            if(pred(i))
+            {
+                indices.push_back(i);
+            }
+        }
+
+        if(indices.size() != n)
+        {
+            PointBuffer tmp = select(indices);
+            this->swap(tmp);
+        }
+        return n - indices.size();
+    }
+
     template<typename V>
     PointBuffer manipulate(V visitor)
     {
@@ -0,0 +1,108 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Trajectory.hpp
+ *
+ * Time stamped poses of a moving sensor, e.g. from a mobile mapping system
+ * or SLAM. Used to compensate the motion of the sensor during a scan, see
+ * deskewPoints(), and loaded with loadTrajectory().
+ */
+
+#ifndef LVR2_TYPES_TRAJECTORY_HPP
+#define LVR2_TYPES_TRAJECTORY_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+
+#include <memory>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief A pose of a moving sensor at a point in time
+ */
+struct TimedPose
+{
+    /// Time stamp in the unit of the point time stamps, usually seconds
+    double time = 0;
+
+    /// Transformation from the sensor frame to the target frame
+    Transformd pose = Transformd::Identity();
+};
+
+/**
+ * @brief Sensor poses sorted by time stamp
+ */
+class Trajectory
+{
+public:
+    Trajectory() = default;
+
+    /// Creates a trajectory from the given poses, which are sorted by time
+    explicit Trajectory(std::vector<TimedPose> poses);
+
+    /// Inserts a pose while keeping the poses sorted by time
+    void addPose(double time, const Transformd& pose);
+
+    /**
+     * @brief Interpolates the pose at the given time. Translations are
+     *        interpolated linearly, rotations by slerp. Times outside of
+     *        the trajectory are clamped to the first or last pose.
+     *
+     * @throws std::out_of_range if the trajectory is empty
+     */
+    Transformd interpolate(double time) const;
+
+    /// Time stamp of the first pose
+    double startTime() const { return m_poses.front().time; }
+
+    /// Time stamp of the last pose
+    double endTime() const { return m_poses.back().time; }
+
+    /// True, if time lies within [startTime(), endTime()]
+    bool covers(double time) const;
+
+    size_t size() const { return m_poses.size(); }
+
+    bool empty() const { return m_poses.empty(); }
+
+    const std::vector<TimedPose>& poses() const { return m_poses; }
+
+    const TimedPose& operator[](size_t i) const { return m_poses[i]; }
+
+private:
+    std::vector<TimedPose> m_poses;
+};
+
+using TrajectoryPtr = std::shared_ptr<Trajectory>;
+
+} // namespace lvr2
+
+#endif // LVR2_TYPES_TRAJECTORY_HPP
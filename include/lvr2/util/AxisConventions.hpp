@@ -0,0 +1,119 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * AxisConventions.hpp
+ *
+ * Conversion between the Y-up convention of graphics formats (glTF, OBJ)
+ * and the Z-up convention of scanners, and between length units.
+ */
+
+#ifndef LVR2_UTIL_AXISCONVENTIONS_HPP
+#define LVR2_UTIL_AXISCONVENTIONS_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <string>
+
+namespace lvr2
+{
+
+/// Up axis of a right handed coordinate system
+enum class UpAxis
+{
+    Y,
+    Z
+};
+
+enum class LengthUnit
+{
+    Millimetres,
+    Centimetres,
+    Metres,
+    Inches,
+    Feet
+};
+
+/// Parses "mm", "cm", "m", "in" or "ft". Throws std::invalid_argument otherwise.
+LengthUnit parseLengthUnit(const std::string& unit);
+
+/// Returns the factor that converts lengths from one unit to another
+double unitScale(LengthUnit from, LengthUnit to);
+
+/**
+ * @brief Returns the rotation that converts coordinates between the up
+ *        axis conventions. Y-up to Z-up maps (x, y, z) to (x, -z, y),
+ *        so both systems stay right handed.
+ */
+Transformd upAxisTransform(UpAxis from, UpAxis to);
+
+/// Rotates the points and normals of the buffer, see upAxisTransform()
+void convertUpAxis(PointBufferPtr buffer, UpAxis from, UpAxis to);
+
+/// Rotates the vertices, vertex normals and face normals of the mesh, see upAxisTransform()
+void convertUpAxis(MeshBufferPtr mesh, UpAxis from, UpAxis to);
+
+/// Scales the points of the buffer. Normals are not changed.
+void convertUnits(PointBufferPtr buffer, LengthUnit from, LengthUnit to);
+
+/// Scales the vertices of the mesh. Normals are not changed.
+void convertUnits(MeshBufferPtr mesh, LengthUnit from, LengthUnit to);
+
+/**
+ * @brief Guesses the up axis of the points.
+ *
+ *        If normals are given, the axis most normals are aligned with is
+ *        returned, as floors and ceilings are usually the largest planes.
+ *        Otherwise, the axis (Y or Z) with the smaller extent is assumed
+ *        to be vertical, which holds for most scenes that are wider than
+ *        tall.
+ *
+ * @param points    Coordinates as xyz triples
+ * @param n         Number of points
+ * @param normals   Normals as xyz triples, may be empty
+ */
+UpAxis detectUpAxis(const floatArr& points, size_t n, const floatArr& normals = floatArr());
+
+/// Guesses the up axis of the point cloud, see the other overload
+UpAxis detectUpAxis(PointBufferPtr buffer);
+
+/// Guesses the up axis of the mesh, see the other overload
+UpAxis detectUpAxis(MeshBufferPtr mesh);
+
+/**
+ * @brief Guesses whether the coordinates are in millimetres or metres,
+ *        assuming a scene extent between 10 cm and 2 km: A bounding box
+ *        diagonal above 2000 units is considered to be in millimetres.
+ */
+LengthUnit detectLengthUnit(const floatArr& points, size_t n);
+
+} // namespace lvr2
+
+#endif // LVR2_UTIL_AXISCONVENTIONS_HPP
@@ -0,0 +1,103 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ColorSpace.hpp
+ *
+ * Color space conversion and photometric corrections for the "colors"
+ * channel of point clouds and the "vertex_colors" channel of meshes.
+ */
+
+#ifndef LVR2_UTIL_COLORSPACE_HPP
+#define LVR2_UTIL_COLORSPACE_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+#include <vector>
+
+namespace lvr2
+{
+
+/// Converts an 8 bit RGB color to HSV. Hue is in degrees [0, 360), saturation and value in [0, 1].
+Vector3f rgbToHsv(unsigned char r, unsigned char g, unsigned char b);
+
+/// Converts a HSV color (see rgbToHsv()) to 8 bit RGB
+void hsvToRgb(const Vector3f& hsv, unsigned char* rgb);
+
+/// Converts n colors with the given width (3 or 4) to an array of n HSV triples
+floatArr colorsToHsv(const ucharArr& colors, size_t n, size_t width);
+
+/// Converts n HSV triples to an array of n RGB colors
+ucharArr hsvToColors(const floatArr& hsv, size_t n);
+
+/**
+ * @brief Checks whether n 8 bit colors with the given width (3 or 4) are
+ *        the upper bytes of the 16 bit RGB colors, i.e. whether the 8 bit
+ *        colors were left unchanged since both were read. Writers only
+ *        prefer the 16 bit colors in this case.
+ */
+bool colorsMatch16(const ucharArr& colors, size_t width, const ushortArr& colors16, size_t n);
+
+/**
+ * @brief Applies out = 255 * (in / 255)^(1 / gamma) to all color
+ *        components. Gamma values > 1 brighten dark areas.
+ *
+ * @return false, if the buffer is null or has no colors
+ */
+bool gammaCorrect(PointBufferPtr buffer, float gamma);
+bool gammaCorrect(MeshBufferPtr mesh, float gamma);
+
+/**
+ * @brief Scales all colors uniformly so that the mean luminance
+ *        equals targetBrightness (in [0, 1]).
+ *
+ * @return false, if the buffer is null or has no colors
+ */
+bool normalizeExposure(PointBufferPtr buffer, float targetBrightness = 0.5);
+bool normalizeExposure(MeshBufferPtr mesh, float targetBrightness = 0.5);
+
+/**
+ * @brief Gray world white balance: scales each color channel so that
+ *        the means of red, green and blue are equal.
+ *
+ * @return false, if the buffer is null or has no colors
+ */
+bool whiteBalance(PointBufferPtr buffer);
+bool whiteBalance(MeshBufferPtr mesh);
+
+/**
+ * @brief White balances each scan on its own. Should be applied before
+ *        merging scans that were colored by different cameras.
+ */
+void whiteBalanceScans(std::vector<PointBufferPtr>& scans);
+
+} // namespace lvr2
+
+#endif // LVR2_UTIL_COLORSPACE_HPP
@@ -51,9 +51,11 @@ namespace lvr2
  *
  * If `name` doesn't contain a valid implementation, `nullptr` is returned.
  * Currently, the only supported implementation is "flann".
+ * If `cacheDirectory` is not empty, trees that support it are loaded from
+ * or written to a cache file in this directory, see IndexCache.hpp.
  */
 template <typename BaseVecT>
-SearchTreePtr<BaseVecT> getSearchTree(string name, PointBufferPtr buffer);
+SearchTreePtr<BaseVecT> getSearchTree(string name, PointBufferPtr buffer, const string& cacheDirectory = "");
 
 } // namespace lvr2
 
@@ -41,7 +41,7 @@ namespace lvr2
 
 
 template <typename BaseVecT>
-SearchTreePtr<BaseVecT> getSearchTree(string name, PointBufferPtr buffer)
+SearchTreePtr<BaseVecT> getSearchTree(string name, PointBufferPtr buffer, const string& cacheDirectory)
 {
     // Transform name to lowercase (only works for ASCII, but this is not a
     // problem in our case).
@@ -71,7 +71,7 @@ SearchTreePtr<BaseVecT> getSearchTree(string name, PointBufferPtr buffer)
 
     if(name == "flann")
     {
-        return std::make_shared<SearchTreeFlann<BaseVecT>>(buffer);
+        return std::make_shared<SearchTreeFlann<BaseVecT>>(buffer, cacheDirectory);
     }
 
     if (name == "lvr2")
@@ -0,0 +1,109 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshPreview.hpp
+ *
+ * A small software rasterizer to render shaded preview images of meshes
+ * without an OpenGL context, e.g. for thumbnails in batch processing.
+ */
+
+#ifndef LVR2_UTIL_MESHPREVIEW_HPP
+#define LVR2_UTIL_MESHPREVIEW_HPP
+
+#include "lvr2/types/MeshBuffer.hpp"
+
+#include <opencv2/core.hpp>
+
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Canonical orthographic viewpoints. The z axis is assumed to
+ *        point upwards, "Front" looks along the positive y axis.
+ */
+enum class PreviewView
+{
+    Front,
+    Back,
+    Left,
+    Right,
+    Top,
+    Isometric
+};
+
+struct PreviewOptions
+{
+    /// Size of each rendered view in pixels
+    int width = 512;
+    int height = 512;
+
+    /// Views that are rendered by savePreviews() and renderPreviewSheet()
+    std::vector<PreviewView> views = {
+        PreviewView::Front, PreviewView::Right, PreviewView::Top, PreviewView::Isometric
+    };
+
+    /// Background color (BGR)
+    cv::Vec3b background = cv::Vec3b(255, 255, 255);
+
+    /// Use the vertex colors of the mesh if present. Otherwise the mesh is
+    /// rendered in a uniform gray.
+    bool useVertexColors = true;
+};
+
+/**
+ * @brief Returns a lower case name of the view, e.g. "isometric"
+ */
+std::string previewViewName(PreviewView view);
+
+/**
+ * @brief Renders the mesh from the given view with headlight shading.
+ *
+ * @return A CV_8UC3 image in BGR order
+ */
+cv::Mat renderPreview(MeshBufferPtr mesh, PreviewView view, const PreviewOptions& options = PreviewOptions());
+
+/**
+ * @brief Renders all configured views next to each other into one image.
+ */
+cv::Mat renderPreviewSheet(MeshBufferPtr mesh, const PreviewOptions& options = PreviewOptions());
+
+/**
+ * @brief Renders all configured views and saves them as
+ *        <prefix>_<view>.png.
+ *
+ * @return true if all images were written
+ */
+bool savePreviews(MeshBufferPtr mesh, const std::string& prefix, const PreviewOptions& options = PreviewOptions());
+
+} // namespace lvr2
+
+#endif // LVR2_UTIL_MESHPREVIEW_HPP
@@ -0,0 +1,80 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MortonOrder.hpp
+ *
+ * Sorting of points along a Z-order (Morton) or Hilbert curve. Processing
+ * points in this order keeps consecutive neighborhood queries spatially
+ * close, which greatly improves cache usage of the search trees on large
+ * point clouds. Together with PointBuffer::reorder(), this can also be used
+ * to store point clouds in a spatially coherent layout.
+ */
+
+#ifndef LVR2_UTIL_MORTONORDER_HPP
+#define LVR2_UTIL_MORTONORDER_HPP
+
+#include "lvr2/types/Channel.hpp"
+
+#include <cstdint>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Interleaves the lower 21 bits of the given coordinates to a 63 bit Morton code
+ */
+uint64_t mortonCode(uint32_t x, uint32_t y, uint32_t z);
+
+/**
+ * @brief Returns the indices of the given points sorted by the Morton code
+ *        of their position on a 2^21 grid spanning the bounding box.
+ *
+ * @param points A channel of width 3 with the point coordinates
+ */
+std::vector<size_t> mortonOrder(const FloatChannel& points);
+
+/**
+ * @brief Returns the 63 bit index of the given cell on a 3D Hilbert curve
+ *        of order 21. Unlike the Morton curve, consecutive cells of the
+ *        Hilbert curve are always adjacent.
+ */
+uint64_t hilbertCode(uint32_t x, uint32_t y, uint32_t z);
+
+/**
+ * @brief Returns the indices of the given points sorted by their Hilbert
+ *        index on a 2^21 grid spanning the bounding box.
+ *
+ * @param points A channel of width 3 with the point coordinates
+ */
+std::vector<size_t> hilbertOrder(const FloatChannel& points);
+
+} // namespace lvr2
+
+#endif // LVR2_UTIL_MORTONORDER_HPP
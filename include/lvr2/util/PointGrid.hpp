@@ -0,0 +1,118 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * PointGrid.hpp
+ *
+ * Uniform hash grid for fixed radius neighbor queries on small to medium
+ * point sets, where building a search tree does not pay off.
+ */
+
+#ifndef LVR2_UTIL_POINTGRID_HPP
+#define LVR2_UTIL_POINTGRID_HPP
+
+#include "lvr2/types/MatrixTypes.hpp"
+
+#include <cmath>
+#include <cstdint>
+#include <unordered_map>
+#include <vector>
+
+namespace lvr2
+{
+
+/**
+ * @brief Uniform grid for fixed radius neighbor queries.
+ *
+ * The grid only references the points, so they have to outlive it. Queries
+ * are fastest if the radius is close to the cell size.
+ */
+class PointGrid
+{
+public:
+    PointGrid(const std::vector<Vector3d>& points, double cellSize)
+        : m_points(points), m_cellSize(cellSize)
+    {
+        for (size_t i = 0; i < points.size(); i++)
+        {
+            m_cells[key(cell(points[i]))].push_back(i);
+        }
+    }
+
+    /**
+     * @brief Returns the indices of all points within distance r of p,
+     *        including p itself if it is part of the grid.
+     */
+    void radiusSearch(const Vector3d& p, double r, std::vector<size_t>& out) const
+    {
+        out.clear();
+        const Eigen::Vector3i c = cell(p);
+        const int range = std::ceil(r / m_cellSize);
+        for (int dx = -range; dx <= range; dx++)
+        {
+            for (int dy = -range; dy <= range; dy++)
+            {
+                for (int dz = -range; dz <= range; dz++)
+                {
+                    auto it = m_cells.find(key(c + Eigen::Vector3i(dx, dy, dz)));
+                    if (it == m_cells.end())
+                    {
+                        continue;
+                    }
+                    for (size_t i : it->second)
+                    {
+                        if ((m_points[i] - p).squaredNorm() <= r * r)
+                        {
+                            out.push_back(i);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+private:
+    Eigen::Vector3i cell(const Vector3d& p) const
+    {
+        return (p / m_cellSize).array().floor().cast<int>();
+    }
+
+    static int64_t key(const Eigen::Vector3i& c)
+    {
+        return (int64_t(c.x()) * 73856093) ^ (int64_t(c.y()) * 19349663) ^ (int64_t(c.z()) * 83492791);
+    }
+
+    const std::vector<Vector3d>& m_points;
+    double m_cellSize;
+    std::unordered_map<int64_t, std::vector<size_t>> m_cells;
+};
+
+} // namespace lvr2
+
+#endif // LVR2_UTIL_POINTGRID_HPP
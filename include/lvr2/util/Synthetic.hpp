@@ -4,8 +4,12 @@
 
 #include "lvr2/geometry/BaseVector.hpp"
 #include "lvr2/types/MeshBuffer.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
 #include "lvr2/types/ScanTypes.hpp"
 
+#include <memory>
+#include <random>
+
 namespace lvr2 {
 
 namespace synthetic {
@@ -19,6 +23,120 @@ PointBufferPtr genSpherePoints(int num_long=50,
 
 CameraImagePtr genLVRImage();
 
+/**
+ * @brief A shape with an analytic signed distance function (negative
+ *        inside) that can be sampled uniformly on its surface.
+ */
+class Shape
+{
+public:
+    virtual ~Shape() = default;
+
+    /// Signed distance of p to the surface
+    virtual float sdf(const Vector3f& p) const = 0;
+
+    /// Uniformly distributed point on the surface and its outward normal
+    virtual Vector3f sample(std::mt19937& rng, Vector3f& normal) const = 0;
+
+    /// Axis aligned bounding box of the surface
+    virtual void bounds(Vector3f& min, Vector3f& max) const = 0;
+};
+
+using ShapePtr = std::shared_ptr<Shape>;
+
+class Sphere : public Shape
+{
+public:
+    Sphere(const Vector3f& center = Vector3f::Zero(), float radius = 1.0f);
+    float sdf(const Vector3f& p) const override;
+    Vector3f sample(std::mt19937& rng, Vector3f& normal) const override;
+    void bounds(Vector3f& min, Vector3f& max) const override;
+
+private:
+    Vector3f m_center;
+    float m_radius;
+};
+
+/// Axis aligned box
+class Box : public Shape
+{
+public:
+    Box(const Vector3f& center = Vector3f::Zero(), const Vector3f& halfExtents = Vector3f::Ones());
+    float sdf(const Vector3f& p) const override;
+    Vector3f sample(std::mt19937& rng, Vector3f& normal) const override;
+    void bounds(Vector3f& min, Vector3f& max) const override;
+
+private:
+    Vector3f m_center;
+    Vector3f m_halfExtents;
+};
+
+/// Torus around the z axis
+class Torus : public Shape
+{
+public:
+    Torus(const Vector3f& center = Vector3f::Zero(), float majorRadius = 1.0f, float minorRadius = 0.25f);
+    float sdf(const Vector3f& p) const override;
+    Vector3f sample(std::mt19937& rng, Vector3f& normal) const override;
+    void bounds(Vector3f& min, Vector3f& max) const override;
+
+private:
+    Vector3f m_center;
+    float m_majorRadius;
+    float m_minorRadius;
+};
+
+/// Square patch of a plane. The sdf is the signed distance to the
+/// infinite plane.
+class Plane : public Shape
+{
+public:
+    Plane(const Vector3f& center = Vector3f::Zero(), const Vector3f& normal = Vector3f::UnitZ(), float size = 2.0f);
+    float sdf(const Vector3f& p) const override;
+    Vector3f sample(std::mt19937& rng, Vector3f& normal) const override;
+    void bounds(Vector3f& min, Vector3f& max) const override;
+
+private:
+    Vector3f m_center;
+    Vector3f m_normal;
+    Vector3f m_u;
+    Vector3f m_v;
+    float m_size;
+};
+
+struct NoiseOptions
+{
+    /// Standard deviation of gaussian noise along the surface normal
+    float gaussian = 0.0f;
+
+    /// Number of outliers relative to the number of surface points
+    float outlierRatio = 0.0f;
+
+    /// Outliers are distributed uniformly in the bounding box of the shape,
+    /// enlarged by this fraction of its diagonal on each side
+    float outlierMargin = 0.1f;
+
+    unsigned int seed = 0;
+};
+
+/**
+ * @brief Samples points on the surface of the shape, adds noise and
+ *        outliers. The buffer contains the true surface normals (random
+ *        directions for outliers) and an unsigned char channel "outliers"
+ *        marking the outliers.
+ *
+ * @param shape     The shape
+ * @param numPoints Number of surface points (without outliers)
+ * @param noise     Noise configuration
+ */
+PointBufferPtr samplePoints(const Shape& shape, size_t numPoints, const NoiseOptions& noise = NoiseOptions());
+
+/**
+ * @brief Evaluates the signed distance of the shape for all points of the
+ *        buffer and stores it as float channel "sdf".
+ */
+void addSignedDistances(const Shape& shape, PointBufferPtr points);
+
 } // namespace synthetic
 
 } // namespace lvr2
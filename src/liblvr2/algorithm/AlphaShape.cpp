@@ -0,0 +1,205 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * AlphaShape.cpp
+ */
+
+#include "lvr2/algorithm/AlphaShape.hpp"
+#include "lvr2/geometry/Delaunay2D.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <map>
+#include <set>
+#include <utility>
+
+namespace lvr2
+{
+
+std::vector<std::array<unsigned int, 3>> alphaTriangles2D(const std::vector<Vector2d>& points, double alpha)
+{
+    std::vector<std::array<unsigned int, 3>> triangles;
+    if (points.size() < 3)
+    {
+        return triangles;
+    }
+
+    Delaunay2D delaunay(points);
+    for (const auto& t : delaunay.triangles())
+    {
+        if (delaunay.circumradius(t) <= alpha)
+        {
+            triangles.push_back(t);
+        }
+    }
+    return triangles;
+}
+
+std::vector<BoundaryRing> boundaryRings(const std::vector<std::array<unsigned int, 3>>& triangles)
+{
+    // A directed edge is on the boundary if its reverse edge does not exist
+    std::set<std::pair<unsigned int, unsigned int>> edges;
+    for (const auto& t : triangles)
+    {
+        for (int j = 0; j < 3; j++)
+        {
+            edges.emplace(t[j], t[(j + 1) % 3]);
+        }
+    }
+
+    std::multimap<unsigned int, unsigned int> boundary;
+    for (const auto& e : edges)
+    {
+        if (edges.find(std::make_pair(e.second, e.first)) == edges.end())
+        {
+            boundary.emplace(e.first, e.second);
+        }
+    }
+
+    std::vector<BoundaryRing> rings;
+    while (!boundary.empty())
+    {
+        BoundaryRing ring;
+        unsigned int start = boundary.begin()->first;
+        unsigned int current = start;
+        do
+        {
+            auto it = boundary.find(current);
+            if (it == boundary.end())
+            {
+                // Can only happen for inconsistent triangulations
+                lvr2::logout::get() << lvr2::warning << "[AlphaShape] Open boundary at point "
+                                    << current << lvr2::endl;
+                ring.closed = false;
+                break;
+            }
+            ring.indices.push_back(current);
+            current = it->second;
+            boundary.erase(it);
+        } while (current != start);
+
+        if (ring.indices.size() >= 3)
+        {
+            rings.push_back(std::move(ring));
+        }
+    }
+    return rings;
+}
+
+namespace
+{
+
+std::vector<std::array<unsigned int, 3>> alphaTriangles(PointBufferPtr points, double alpha)
+{
+    if (!points)
+    {
+        return {};
+    }
+
+    floatArr pts = points->getPointArray();
+    std::vector<Vector2d> projected(points->numPoints());
+    for (size_t i = 0; i < projected.size(); i++)
+    {
+        projected[i] = Vector2d(pts[3 * i], pts[3 * i + 1]);
+    }
+    return alphaTriangles2D(projected, alpha);
+}
+
+} // anonymous namespace
+
+MeshBufferPtr alphaShape2D(PointBufferPtr points, double alpha)
+{
+    MeshBufferPtr mesh(new MeshBuffer);
+    std::vector<std::array<unsigned int, 3>> triangles = alphaTriangles(points, alpha);
+    if (triangles.empty())
+    {
+        return mesh;
+    }
+
+    // Only keep points that are part of the shape
+    std::map<unsigned int, unsigned int> vertexIndex;
+    for (const auto& t : triangles)
+    {
+        for (unsigned int v : t)
+        {
+            vertexIndex.emplace(v, 0);
+        }
+    }
+
+    floatArr pts = points->getPointArray();
+    floatArr vertices(new float[3 * vertexIndex.size()]);
+    unsigned int next = 0;
+    for (auto& v : vertexIndex)
+    {
+        v.second = next;
+        std::copy(pts.get() + 3 * v.first, pts.get() + 3 * v.first + 3, vertices.get() + 3 * next);
+        next++;
+    }
+
+    indexArray faces(new unsigned int[3 * triangles.size()]);
+    for (size_t i = 0; i < triangles.size(); i++)
+    {
+        for (int j = 0; j < 3; j++)
+        {
+            faces[3 * i + j] = vertexIndex[triangles[i][j]];
+        }
+    }
+
+    mesh->setVertices(vertices, vertexIndex.size());
+    mesh->setFaceIndices(faces, triangles.size());
+    return mesh;
+}
+
+std::vector<Polyline> alphaShapeOutlines(PointBufferPtr points, double alpha, const std::string& layer)
+{
+    std::vector<Polyline> outlines;
+    std::vector<std::array<unsigned int, 3>> triangles = alphaTriangles(points, alpha);
+    if (triangles.empty())
+    {
+        return outlines;
+    }
+
+    // As all triangles are counter clockwise, the boundary forms counter
+    // clockwise outer loops and clockwise holes.
+    floatArr pts = points->getPointArray();
+    for (const BoundaryRing& ring : boundaryRings(triangles))
+    {
+        Polyline line;
+        line.closed = ring.closed;
+        line.layer = layer;
+        for (unsigned int i : ring.indices)
+        {
+            line.points.emplace_back(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+        }
+        outlines.push_back(std::move(line));
+    }
+    return outlines;
+}
+
+} // namespace lvr2
@@ -0,0 +1,232 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ChangeDetection.cpp
+ */
+
+#include "lvr2/algorithm/ChangeDetection.hpp"
+#include "lvr2/algorithm/pmp/SurfaceNormals.h"
+#include "lvr2/algorithm/pmp/TriangleKdTree.h"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/PMPMesh.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/PointGrid.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+std::vector<Vector3d> toVectors(PointBufferPtr buffer)
+{
+    floatArr pts = buffer->getPointArray();
+    std::vector<Vector3d> out(buffer->numPoints());
+    for (size_t i = 0; i < out.size(); i++)
+    {
+        out[i] = Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+    }
+    return out;
+}
+
+void labelDistances(ChangeDetectionResult& result, double threshold)
+{
+    result.labels.resize(result.distances.size());
+    for (size_t i = 0; i < result.distances.size(); i++)
+    {
+        const float d = result.distances[i];
+        if (std::isnan(d) || d > threshold)
+        {
+            result.labels[i] = ChangeLabel::Added;
+            result.numAdded++;
+        }
+        else if (d < -threshold)
+        {
+            result.labels[i] = ChangeLabel::Removed;
+            result.numRemoved++;
+        }
+        else
+        {
+            result.labels[i] = ChangeLabel::Unchanged;
+            result.numUnchanged++;
+        }
+    }
+}
+
+void logResult(const ChangeDetectionResult& result)
+{
+    lvr2::logout::get() << lvr2::info << "[ChangeDetection] " << result.numAdded << " added, " << result.numRemoved
+                        << " removed and " << result.numUnchanged << " unchanged points" << lvr2::endl;
+}
+
+} // anonymous namespace
+
+ChangeDetectionResult changeDetection(MeshBufferPtr reference, PointBufferPtr current, double threshold)
+{
+    ChangeDetectionResult result;
+    const size_t n = current->numPoints();
+    result.distances.resize(n, std::numeric_limits<float>::quiet_NaN());
+    if (reference->numFaces() == 0)
+    {
+        labelDistances(result, threshold);
+        return result;
+    }
+
+    PMPMesh<BaseVector<float>> target(reference);
+    const pmp::SurfaceMesh& surface = target.getSurfaceMesh();
+    pmp::TriangleKdTree tree(surface);
+
+    floatArr pts = current->getPointArray();
+
+    #pragma omp parallel for schedule(dynamic, 1024)
+    for (size_t i = 0; i < n; i++)
+    {
+        const pmp::Point p(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+        const auto nearest = tree.nearest(p);
+        const pmp::Normal normal = pmp::SurfaceNormals::compute_face_normal(surface, nearest.face);
+        const double side = (p - nearest.nearest).dot(normal);
+        result.distances[i] = side < 0 ? -nearest.dist : nearest.dist;
+    }
+
+    labelDistances(result, threshold);
+    logResult(result);
+    return result;
+}
+
+ChangeDetectionResult changeDetection(
+    PointBufferPtr reference,
+    PointBufferPtr current,
+    double threshold,
+    const M3C2Options& options)
+{
+    ChangeDetectionResult result;
+    const std::vector<Vector3d> ref = toVectors(reference);
+    const std::vector<Vector3d> cur = toVectors(current);
+    result.distances.resize(cur.size(), std::numeric_limits<float>::quiet_NaN());
+
+    const double cylinderRadius = std::hypot(options.maxDepth, options.projectionRadius);
+    const double cellSize = std::max(options.normalRadius, options.projectionRadius);
+    const PointGrid refGrid(ref, cellSize);
+    const PointGrid curGrid(cur, cellSize);
+
+    #pragma omp parallel for schedule(dynamic, 256)
+    for (size_t i = 0; i < cur.size(); i++)
+    {
+        const Vector3d& p = cur[i];
+        std::vector<size_t> neighbors;
+        refGrid.radiusSearch(p, options.normalRadius, neighbors);
+        if (neighbors.size() < 3)
+        {
+            continue;
+        }
+
+        // Normal of the reference surface near the core point
+        Vector3d centroid = Vector3d::Zero();
+        for (size_t j : neighbors)
+        {
+            centroid += ref[j];
+        }
+        centroid /= neighbors.size();
+        Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+        for (size_t j : neighbors)
+        {
+            covariance += (ref[j] - centroid) * (ref[j] - centroid).transpose();
+        }
+        Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> solver(covariance);
+        Vector3d normal = solver.eigenvectors().col(0);
+        if (normal.z() < 0)
+        {
+            normal = -normal;
+        }
+
+        // Mean positions of both clouds along the normal within the cylinder
+        auto meanOffset = [&](const PointGrid& grid, const std::vector<Vector3d>& points, double& mean)
+        {
+            grid.radiusSearch(p, cylinderRadius, neighbors);
+            double sum = 0;
+            size_t count = 0;
+            for (size_t j : neighbors)
+            {
+                const Vector3d d = points[j] - p;
+                const double t = d.dot(normal);
+                if (std::abs(t) <= options.maxDepth && (d - t * normal).norm() <= options.projectionRadius)
+                {
+                    sum += t;
+                    count++;
+                }
+            }
+            mean = count ? sum / count : 0;
+            return count > 0;
+        };
+
+        double refMean;
+        double curMean;
+        if (meanOffset(refGrid, ref, refMean) && meanOffset(curGrid, cur, curMean))
+        {
+            result.distances[i] = curMean - refMean;
+        }
+    }
+
+    // Reference geometry that is missing in the current epoch
+    std::vector<size_t> neighbors;
+    for (size_t i = 0; i < ref.size(); i++)
+    {
+        curGrid.radiusSearch(ref[i], options.projectionRadius, neighbors);
+        if (neighbors.empty())
+        {
+            result.removedReference.push_back(i);
+        }
+    }
+
+    labelDistances(result, threshold);
+    logResult(result);
+    return result;
+}
+
+void addChangeChannels(PointBufferPtr current, const ChangeDetectionResult& result)
+{
+    const size_t n = current->numPoints();
+    floatArr distances(new float[n]);
+    ucharArr labels(new unsigned char[n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        distances[i] = i < result.distances.size() ? result.distances[i] : std::numeric_limits<float>::quiet_NaN();
+        labels[i] = i < result.labels.size() ? static_cast<unsigned char>(result.labels[i]) : 0;
+    }
+    current->addFloatChannel(distances, "change_distance", n, 1);
+    current->addUCharChannel(labels, "change_label", n, 1);
+}
+
+} // namespace lvr2
@@ -0,0 +1,559 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * ConvexDecomposition.cpp
+ */
+
+#include "lvr2/algorithm/ConvexDecomposition.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Util.hpp"
+
+#include <Eigen/Dense>
+
+#include <algorithm>
+#include <array>
+#include <cmath>
+#include <limits>
+#include <numeric>
+#include <queue>
+#include <random>
+#include <set>
+#include <unordered_map>
+#include <unordered_set>
+#include <utility>
+
+namespace lvr2
+{
+
+namespace
+{
+
+using Triangle = std::array<int, 3>;
+
+struct HullFace
+{
+    Triangle v;
+    Eigen::Vector3d normal;
+    double offset;
+};
+
+HullFace makeFace(const std::vector<Eigen::Vector3d>& points, int a, int b, int c)
+{
+    HullFace face;
+    face.v = {a, b, c};
+    face.normal = (points[b] - points[a]).cross(points[c] - points[a]);
+    const double length = face.normal.norm();
+    // Degenerate faces are never visible
+    face.normal = length > 0 ? Eigen::Vector3d(face.normal / length) : Eigen::Vector3d::Zero();
+    face.offset = face.normal.dot(points[a]);
+    return face;
+}
+
+/// Incremental convex hull. Returns false if the points do not span a volume.
+bool hullTriangles(const std::vector<Eigen::Vector3d>& points, std::vector<Triangle>& triangles)
+{
+    triangles.clear();
+    const int n = points.size();
+    if (n < 4)
+    {
+        return false;
+    }
+
+    Eigen::Vector3d min = points[0];
+    Eigen::Vector3d max = points[0];
+    for (const auto& p : points)
+    {
+        min = min.cwiseMin(p);
+        max = max.cwiseMax(p);
+    }
+    const double eps = 1e-9 * std::max(1.0, (max - min).norm());
+
+    // Initial tetrahedron from extreme points
+    int i0 = 0;
+    for (int i = 1; i < n; i++)
+    {
+        if (points[i].x() < points[i0].x())
+        {
+            i0 = i;
+        }
+    }
+    auto farthest = [&](auto distance)
+    {
+        int best = -1;
+        double bestDistance = eps;
+        for (int i = 0; i < n; i++)
+        {
+            const double d = distance(points[i]);
+            if (d > bestDistance)
+            {
+                best = i;
+                bestDistance = d;
+            }
+        }
+        return best;
+    };
+    const int i1 = farthest([&](const Eigen::Vector3d& p) { return (p - points[i0]).norm(); });
+    if (i1 < 0)
+    {
+        return false;
+    }
+    const Eigen::Vector3d dir = (points[i1] - points[i0]).normalized();
+    const int i2 = farthest([&](const Eigen::Vector3d& p) { return (p - points[i0]).cross(dir).norm(); });
+    if (i2 < 0)
+    {
+        return false;
+    }
+    const Eigen::Vector3d planeNormal = (points[i1] - points[i0]).cross(points[i2] - points[i0]).normalized();
+    const int i3 = farthest([&](const Eigen::Vector3d& p) { return std::abs(planeNormal.dot(p - points[i0])); });
+    if (i3 < 0)
+    {
+        return false;
+    }
+
+    const Eigen::Vector3d center = (points[i0] + points[i1] + points[i2] + points[i3]) / 4;
+    std::vector<HullFace> faces;
+    for (const Triangle& t : std::vector<Triangle>{{i0, i1, i2}, {i0, i3, i1}, {i1, i3, i2}, {i2, i3, i0}})
+    {
+        HullFace face = makeFace(points, t[0], t[1], t[2]);
+        if (face.normal.dot(center) > face.offset)
+        {
+            face = makeFace(points, t[0], t[2], t[1]);
+        }
+        faces.push_back(face);
+    }
+
+    // Adding the points in random order avoids the worst case for sorted input
+    std::vector<int> order(n);
+    std::iota(order.begin(), order.end(), 0);
+    std::shuffle(order.begin(), order.end(), std::mt19937(42));
+
+    std::vector<bool> visible;
+    std::set<std::pair<int, int>> edges;
+    for (int i : order)
+    {
+        if (i == i0 || i == i1 || i == i2 || i == i3)
+        {
+            continue;
+        }
+
+        visible.assign(faces.size(), false);
+        bool any = false;
+        for (size_t f = 0; f < faces.size(); f++)
+        {
+            if (faces[f].normal.dot(points[i]) - faces[f].offset > eps)
+            {
+                visible[f] = true;
+                any = true;
+            }
+        }
+        if (!any)
+        {
+            continue;
+        }
+
+        // The horizon consists of the edges of visible faces whose twin
+        // belongs to a hidden face
+        edges.clear();
+        for (size_t f = 0; f < faces.size(); f++)
+        {
+            if (visible[f])
+            {
+                for (int k = 0; k < 3; k++)
+                {
+                    edges.emplace(faces[f].v[k], faces[f].v[(k + 1) % 3]);
+                }
+            }
+        }
+
+        std::vector<HullFace> next;
+        next.reserve(faces.size() + edges.size());
+        for (size_t f = 0; f < faces.size(); f++)
+        {
+            if (!visible[f])
+            {
+                next.push_back(faces[f]);
+            }
+        }
+        for (const auto& e : edges)
+        {
+            if (!edges.count(std::make_pair(e.second, e.first)))
+            {
+                next.push_back(makeFace(points, e.first, e.second, i));
+            }
+        }
+        faces.swap(next);
+    }
+
+    for (const HullFace& face : faces)
+    {
+        triangles.push_back(face.v);
+    }
+    return true;
+}
+
+double hullVolume(const std::vector<Eigen::Vector3d>& points, const std::vector<Triangle>& triangles)
+{
+    double volume = 0;
+    for (const Triangle& t : triangles)
+    {
+        volume += points[t[0]].dot(points[t[1]].cross(points[t[2]])) / 6.0;
+    }
+    return volume;
+}
+
+/// Creates a buffer with the hull vertices, transformed by p * scale + offset
+MeshBufferPtr hullToMeshBuffer(
+    const std::vector<Eigen::Vector3d>& points,
+    const std::vector<Triangle>& triangles,
+    double scale,
+    const Eigen::Vector3d& offset)
+{
+    std::unordered_map<int, unsigned int> index;
+    std::vector<float> vertices;
+    std::vector<unsigned int> faces;
+    for (const Triangle& t : triangles)
+    {
+        for (int v : t)
+        {
+            auto it = index.find(v);
+            if (it == index.end())
+            {
+                it = index.emplace(v, vertices.size() / 3).first;
+                const Eigen::Vector3d p = points[v] * scale + offset;
+                vertices.push_back(p.x());
+                vertices.push_back(p.y());
+                vertices.push_back(p.z());
+            }
+            faces.push_back(it->second);
+        }
+    }
+
+    MeshBufferPtr buffer(new MeshBuffer);
+    buffer->setVertices(Util::convert_vector_to_shared_array(vertices), vertices.size() / 3);
+    buffer->setFaceIndices(Util::convert_vector_to_shared_array(faces), faces.size() / 3);
+    return buffer;
+}
+
+struct Part
+{
+    std::vector<Vector3i> voxels;
+    std::vector<Eigen::Vector3d> hullPoints;
+    std::vector<Triangle> hull;
+    double volume = 0;
+    int depth = 0;
+    bool splittable = true;
+};
+
+/// Computes the hull of the voxels of a part in voxel units
+void computeHull(Part& part)
+{
+    // The hull of a voxel set is the hull of the lowest and highest voxel of each column
+    std::unordered_map<int64_t, std::pair<int, int>> columns;
+    for (const Vector3i& v : part.voxels)
+    {
+        const int64_t key = (static_cast<int64_t>(v.x()) << 32) | static_cast<uint32_t>(v.y());
+        auto it = columns.find(key);
+        if (it == columns.end())
+        {
+            columns.emplace(key, std::make_pair(v.z(), v.z()));
+        }
+        else
+        {
+            it->second.first = std::min(it->second.first, v.z());
+            it->second.second = std::max(it->second.second, v.z());
+        }
+    }
+
+    std::unordered_set<Vector3i> corners;
+    for (const auto& column : columns)
+    {
+        const int x = column.first >> 32;
+        const int y = static_cast<int32_t>(column.first & 0xffffffff);
+        for (int dx = 0; dx <= 1; dx++)
+        {
+            for (int dy = 0; dy <= 1; dy++)
+            {
+                corners.insert(Vector3i(x + dx, y + dy, column.second.first));
+                corners.insert(Vector3i(x + dx, y + dy, column.second.second + 1));
+            }
+        }
+    }
+
+    part.hullPoints.clear();
+    for (const Vector3i& c : corners)
+    {
+        part.hullPoints.push_back(c.cast<double>());
+    }
+    // A non-empty voxel set always spans a volume
+    hullTriangles(part.hullPoints, part.hull);
+    part.volume = hullVolume(part.hullPoints, part.hull);
+}
+
+/// Hull volume that is not covered by the voxels of the part
+double excessVolume(const Part& part)
+{
+    return std::max(0.0, part.volume - part.voxels.size());
+}
+
+/// Splits the part along the axis aligned plane that minimizes the excess volume of both halves
+bool splitPart(const Part& part, int planesPerAxis, Part& left, Part& right)
+{
+    double bestCost = std::numeric_limits<double>::max();
+    bool found = false;
+    for (int axis = 0; axis < 3; axis++)
+    {
+        int min = std::numeric_limits<int>::max();
+        int max = std::numeric_limits<int>::lowest();
+        for (const Vector3i& v : part.voxels)
+        {
+            min = std::min(min, v[axis]);
+            max = std::max(max, v[axis]);
+        }
+        if (min == max)
+        {
+            continue;
+        }
+
+        std::set<int> planes;
+        for (int s = 1; s <= planesPerAxis; s++)
+        {
+            planes.insert(min + std::max(1, static_cast<int>(std::lround((max - min + 1.0) * s / (planesPerAxis + 1)))));
+        }
+
+        for (int plane : planes)
+        {
+            if (plane > max)
+            {
+                continue;
+            }
+
+            Part l, r;
+            for (const Vector3i& v : part.voxels)
+            {
+                (v[axis] < plane ? l : r).voxels.push_back(v);
+            }
+            if (l.voxels.empty() || r.voxels.empty())
+            {
+                continue;
+            }
+
+            computeHull(l);
+            computeHull(r);
+            const double cost = excessVolume(l) + excessVolume(r);
+            if (cost < bestCost)
+            {
+                bestCost = cost;
+                left = std::move(l);
+                right = std::move(r);
+                found = true;
+            }
+        }
+    }
+
+    left.depth = right.depth = part.depth + 1;
+    return found;
+}
+
+} // anonymous namespace
+
+MeshBufferPtr convexHull(const std::vector<Vector3f>& points)
+{
+    std::vector<Eigen::Vector3d> p;
+    p.reserve(points.size());
+    for (const Vector3f& v : points)
+    {
+        p.push_back(v.cast<double>());
+    }
+
+    std::vector<Triangle> triangles;
+    if (!hullTriangles(p, triangles))
+    {
+        return MeshBufferPtr();
+    }
+    return hullToMeshBuffer(p, triangles, 1.0, Eigen::Vector3d::Zero());
+}
+
+std::vector<MeshBufferPtr> convexDecomposition(MeshBufferPtr mesh, const ConvexDecompositionOptions& options)
+{
+    std::vector<MeshBufferPtr> hulls;
+    const size_t numVertices = mesh->numVertices();
+    const size_t numFaces = mesh->numFaces();
+    if (numVertices == 0 || numFaces == 0)
+    {
+        return hulls;
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](size_t i)
+    {
+        return Eigen::Vector3d(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    Eigen::Vector3d min = vertex(0);
+    Eigen::Vector3d max = min;
+    for (size_t i = 1; i < numVertices; i++)
+    {
+        min = min.cwiseMin(vertex(i));
+        max = max.cwiseMax(vertex(i));
+    }
+
+    // Voxel size for the requested resolution. Flat meshes get a minimal thickness.
+    const Eigen::Vector3d extent = (max - min).cwiseMax((max - min).maxCoeff() * 1e-3);
+    const double h = std::cbrt(extent.prod() / std::max<size_t>(options.resolution, 1));
+    if (!(h > 0))
+    {
+        return hulls;
+    }
+
+    // One voxel of padding on each side, so that the outside is connected
+    const Eigen::Vector3d origin = min - Eigen::Vector3d::Constant(h);
+    Vector3i dims;
+    for (int a = 0; a < 3; a++)
+    {
+        dims[a] = static_cast<int>(std::ceil((max[a] - min[a]) / h)) + 3;
+    }
+    auto cellIndex = [&](int x, int y, int z)
+    {
+        return (static_cast<size_t>(z) * dims.y() + y) * dims.x() + x;
+    };
+
+    enum : uint8_t { Empty = 0, Surface = 1, Outside = 2 };
+    std::vector<uint8_t> grid(static_cast<size_t>(dims.x()) * dims.y() * dims.z(), Empty);
+
+    // Mark the voxels that are touched by the faces by sampling them densely
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const Eigen::Vector3d a = vertex(faces[3 * f]);
+        const Eigen::Vector3d b = vertex(faces[3 * f + 1]);
+        const Eigen::Vector3d c = vertex(faces[3 * f + 2]);
+        const double longest = std::max({(b - a).norm(), (c - a).norm(), (c - b).norm()});
+        const int steps = std::max(1, static_cast<int>(std::ceil(2 * longest / h)));
+        for (int i = 0; i <= steps; i++)
+        {
+            for (int j = 0; i + j <= steps; j++)
+            {
+                const Eigen::Vector3d p = a + (b - a) * (double(i) / steps) + (c - a) * (double(j) / steps);
+                Vector3i v;
+                for (int k = 0; k < 3; k++)
+                {
+                    v[k] = std::min(dims[k] - 2, std::max(1, static_cast<int>(std::floor((p[k] - origin[k]) / h))));
+                }
+                grid[cellIndex(v.x(), v.y(), v.z())] = Surface;
+            }
+        }
+    }
+
+    // Flood fill the outside. Empty voxels that are not reached are inside of closed components.
+    std::queue<Vector3i> queue;
+    grid[cellIndex(0, 0, 0)] = Outside;
+    queue.push(Vector3i(0, 0, 0));
+    const int offsets[6][3] = {{1, 0, 0}, {-1, 0, 0}, {0, 1, 0}, {0, -1, 0}, {0, 0, 1}, {0, 0, -1}};
+    while (!queue.empty())
+    {
+        const Vector3i v = queue.front();
+        queue.pop();
+        for (const auto& o : offsets)
+        {
+            const Vector3i n(v.x() + o[0], v.y() + o[1], v.z() + o[2]);
+            if (n.x() < 0 || n.y() < 0 || n.z() < 0 || n.x() >= dims.x() || n.y() >= dims.y() || n.z() >= dims.z())
+            {
+                continue;
+            }
+            uint8_t& state = grid[cellIndex(n.x(), n.y(), n.z())];
+            if (state == Empty)
+            {
+                state = Outside;
+                queue.push(n);
+            }
+        }
+    }
+
+    Part root;
+    for (int z = 0; z < dims.z(); z++)
+    {
+        for (int y = 0; y < dims.y(); y++)
+        {
+            for (int x = 0; x < dims.x(); x++)
+            {
+                if (grid[cellIndex(x, y, z)] != Outside)
+                {
+                    root.voxels.push_back(Vector3i(x, y, z));
+                }
+            }
+        }
+    }
+    grid.clear();
+    grid.shrink_to_fit();
+
+    computeHull(root);
+    const double rootVolume = root.volume;
+
+    std::vector<Part> parts;
+    parts.push_back(std::move(root));
+    while (parts.size() < std::max<size_t>(options.maxHulls, 1))
+    {
+        // Split the most concave part first
+        int worst = -1;
+        for (size_t i = 0; i < parts.size(); i++)
+        {
+            if (parts[i].splittable && parts[i].depth < options.maxDepth
+                && (worst < 0 || excessVolume(parts[i]) > excessVolume(parts[worst])))
+            {
+                worst = i;
+            }
+        }
+        if (worst < 0 || excessVolume(parts[worst]) <= options.maxConcavity * rootVolume)
+        {
+            break;
+        }
+
+        Part left, right;
+        if (!splitPart(parts[worst], std::max(options.planesPerAxis, 1), left, right))
+        {
+            parts[worst].splittable = false;
+            continue;
+        }
+        parts[worst] = std::move(left);
+        parts.push_back(std::move(right));
+    }
+
+    for (const Part& part : parts)
+    {
+        hulls.push_back(hullToMeshBuffer(part.hullPoints, part.hull, h, origin));
+    }
+
+    lvr2::logout::get() << lvr2::info << "[ConvexDecomposition] Decomposed mesh into " << hulls.size()
+        << " convex parts" << lvr2::endl;
+
+    return hulls;
+}
+
+} // namespace lvr2
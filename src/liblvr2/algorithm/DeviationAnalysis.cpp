@@ -0,0 +1,277 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * DeviationAnalysis.cpp
+ */
+
+#include "lvr2/algorithm/DeviationAnalysis.hpp"
+#include "lvr2/algorithm/ChangeDetection.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <iomanip>
+#include <limits>
+#include <sstream>
+
+namespace lvr2
+{
+
+namespace
+{
+
+void computeStatistics(DeviationReport& report, const DeviationAnalysisOptions& options)
+{
+    std::vector<float> edges = options.bandEdges;
+    if (edges.empty())
+    {
+        const float l = options.lowerTolerance;
+        const float u = options.upperTolerance;
+        edges = {3 * l, 2 * l, l, u, 2 * u, 3 * u};
+    }
+    std::sort(edges.begin(), edges.end());
+
+    const float inf = std::numeric_limits<float>::infinity();
+    edges.insert(edges.begin(), -inf);
+    edges.push_back(inf);
+    for (size_t b = 0; b + 1 < edges.size(); b++)
+    {
+        DeviationBand band;
+        band.min = edges[b];
+        band.max = edges[b + 1];
+        report.bands.push_back(band);
+    }
+
+    double sum = 0;
+    double sumSq = 0;
+    report.minDeviation = inf;
+    report.maxDeviation = -inf;
+    for (float d : report.deviations)
+    {
+        if (std::isnan(d))
+        {
+            continue;
+        }
+        report.numValid++;
+        sum += d;
+        sumSq += d * d;
+        report.minDeviation = std::min<double>(report.minDeviation, d);
+        report.maxDeviation = std::max<double>(report.maxDeviation, d);
+
+        if (d < options.lowerTolerance)
+        {
+            report.numBelow++;
+        }
+        else if (d > options.upperTolerance)
+        {
+            report.numAbove++;
+        }
+        else
+        {
+            report.numInSpec++;
+        }
+
+        for (DeviationBand& band : report.bands)
+        {
+            if (d >= band.min && d < band.max)
+            {
+                band.count++;
+                break;
+            }
+        }
+    }
+
+    if (report.numValid == 0)
+    {
+        report.minDeviation = 0;
+        report.maxDeviation = 0;
+        return;
+    }
+
+    const double n = report.numValid;
+    report.mean = sum / n;
+    report.rms = std::sqrt(sumSq / n);
+    report.stdDev = std::sqrt(std::max(sumSq / n - report.mean * report.mean, 0.0));
+    report.percentInSpec = 100.0 * report.numInSpec / n;
+    for (DeviationBand& band : report.bands)
+    {
+        band.percent = 100.0 * band.count / n;
+    }
+
+    lvr2::logout::get() << lvr2::info << "[DeviationAnalysis] " << report.percentInSpec << "% in spec, "
+        << report.numBelow << " below, " << report.numAbove << " above, mean: " << report.mean
+        << ", std. dev.: " << report.stdDev << lvr2::endl;
+}
+
+/// Green within the tolerances, blending to blue (below) and red (above)
+void deviationColor(float d, const DeviationAnalysisOptions& options, unsigned char* rgb)
+{
+    if (std::isnan(d))
+    {
+        rgb[0] = rgb[1] = rgb[2] = 128;
+        return;
+    }
+    if (d >= options.lowerTolerance && d <= options.upperTolerance)
+    {
+        rgb[0] = 0;
+        rgb[1] = 200;
+        rgb[2] = 0;
+        return;
+    }
+
+    const float tolerance = d > 0 ? options.upperTolerance : -options.lowerTolerance;
+    const float excess = std::abs(d) - tolerance;
+    const float t = tolerance > 0 ? std::min(excess / (2 * tolerance), 1.0f) : 1.0f;
+
+    // From yellow (red) or cyan (blue) at the tolerance to saturated at three times it
+    const unsigned char full = 255;
+    const unsigned char fade = static_cast<unsigned char>(255 * (1 - t));
+    if (d > 0)
+    {
+        rgb[0] = full;
+        rgb[1] = fade;
+        rgb[2] = 0;
+    }
+    else
+    {
+        rgb[0] = 0;
+        rgb[1] = fade;
+        rgb[2] = full;
+    }
+}
+
+} // anonymous namespace
+
+DeviationReport deviationAnalysis(
+    MeshBufferPtr reference,
+    PointBufferPtr scan,
+    const DeviationAnalysisOptions& options)
+{
+    DeviationReport report;
+    ChangeDetectionResult changes = changeDetection(reference, scan, options.upperTolerance);
+    report.deviations = std::move(changes.distances);
+    if (options.maxDistance > 0)
+    {
+        for (float& d : report.deviations)
+        {
+            if (std::abs(d) > options.maxDistance)
+            {
+                d = std::numeric_limits<float>::quiet_NaN();
+            }
+        }
+    }
+
+    computeStatistics(report, options);
+    return report;
+}
+
+DeviationReport deviationAnalysis(
+    MeshBufferPtr reference,
+    MeshBufferPtr mesh,
+    const DeviationAnalysisOptions& options)
+{
+    PointBufferPtr vertices = std::make_shared<PointBuffer>(mesh->getVertices(), mesh->numVertices());
+    return deviationAnalysis(reference, vertices, options);
+}
+
+void colorByDeviation(PointBufferPtr scan, const DeviationReport& report, const DeviationAnalysisOptions& options)
+{
+    const size_t n = scan->numPoints();
+    floatArr deviations(new float[n]);
+    ucharArr colors(new unsigned char[3 * n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        deviations[i] = i < report.deviations.size() ? report.deviations[i] : std::numeric_limits<float>::quiet_NaN();
+        deviationColor(deviations[i], options, colors.get() + 3 * i);
+    }
+    scan->addFloatChannel(deviations, "deviation", n, 1);
+    scan->setColorArray(colors, n);
+}
+
+void colorByDeviation(MeshBufferPtr mesh, const DeviationReport& report, const DeviationAnalysisOptions& options)
+{
+    const size_t n = mesh->numVertices();
+    floatArr deviations(new float[n]);
+    ucharArr colors(new unsigned char[3 * n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        deviations[i] = i < report.deviations.size() ? report.deviations[i] : std::numeric_limits<float>::quiet_NaN();
+        deviationColor(deviations[i], options, colors.get() + 3 * i);
+    }
+    mesh->addFloatChannel(deviations, "deviation", n, 1);
+    mesh->setVertexColors(colors, 3);
+}
+
+void saveDeviationReport(
+    const DeviationReport& report,
+    const DeviationAnalysisOptions& options,
+    const boost::filesystem::path& file)
+{
+    // JSON has no infinity, open bands are written as null
+    auto bound = [](float v) -> std::string
+    {
+        if (std::isinf(v))
+        {
+            return "null";
+        }
+        std::ostringstream ss;
+        ss << std::setprecision(9) << v;
+        return ss.str();
+    };
+
+    boost::filesystem::ofstream out(file);
+    out << std::setprecision(9);
+    out << "{\n";
+    out << "  \"lower_tolerance\": " << options.lowerTolerance << ",\n";
+    out << "  \"upper_tolerance\": " << options.upperTolerance << ",\n";
+    out << "  \"num_valid\": " << report.numValid << ",\n";
+    out << "  \"num_in_spec\": " << report.numInSpec << ",\n";
+    out << "  \"num_below\": " << report.numBelow << ",\n";
+    out << "  \"num_above\": " << report.numAbove << ",\n";
+    out << "  \"percent_in_spec\": " << report.percentInSpec << ",\n";
+    out << "  \"mean\": " << report.mean << ",\n";
+    out << "  \"std_dev\": " << report.stdDev << ",\n";
+    out << "  \"rms\": " << report.rms << ",\n";
+    out << "  \"min\": " << report.minDeviation << ",\n";
+    out << "  \"max\": " << report.maxDeviation << ",\n";
+
+    out << "  \"bands\": [";
+    for (size_t b = 0; b < report.bands.size(); b++)
+    {
+        const DeviationBand& band = report.bands[b];
+        out << (b ? ",\n" : "\n") << "    {\"min\": " << bound(band.min) << ", \"max\": " << bound(band.max)
+            << ", \"count\": " << band.count << ", \"percent\": " << band.percent << "}";
+    }
+    out << (report.bands.empty() ? "]\n" : "\n  ]\n");
+    out << "}\n";
+}
+
+} // namespace lvr2
@@ -0,0 +1,159 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * DynamicObjectRemoval.cpp
+ */
+
+#include "lvr2/algorithm/DynamicObjectRemoval.hpp"
+#include "lvr2/algorithm/VisibilityGrid.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <stdexcept>
+#include <unordered_set>
+
+namespace lvr2
+{
+
+std::vector<std::vector<size_t>> detectDynamicPoints(
+    const std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const DynamicRemovalOptions& options)
+{
+    if (scans.size() != origins.size())
+    {
+        throw std::invalid_argument("[DynamicObjectRemoval] Number of scans and origins differ");
+    }
+    if (scans.size() > VisibilityGrid::MaxScans)
+    {
+        lvr2::logout::get() << lvr2::warning << "[DynamicObjectRemoval] Only the first " << VisibilityGrid::MaxScans
+            << " of " << scans.size() << " scans vote" << lvr2::endl;
+    }
+
+    VisibilityGrid grid(options.voxelSize, options.margin);
+    for (size_t s = 0; s < scans.size(); s++)
+    {
+        if (!grid.addScan(scans[s], origins[s]))
+        {
+            break;
+        }
+    }
+
+    std::vector<std::vector<size_t>> dynamic(scans.size());
+    size_t total = 0;
+    for (size_t s = 0; s < scans.size(); s++)
+    {
+        if (!scans[s])
+        {
+            continue;
+        }
+        const uint64_t own = VisibilityGrid::scanBit(s);
+        const size_t n = scans[s]->numPoints();
+        floatArr points = scans[s]->getPointArray();
+
+        std::vector<Vector3i> voxels(n);
+        std::vector<bool> isDynamic(n, false);
+        std::unordered_set<Vector3i> dynamicVoxels;
+        for (size_t i = 0; i < n; i++)
+        {
+            voxels[i] = grid.voxel(Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]));
+
+            const size_t free = VisibilityGrid::count(grid.freeMask(voxels[i]) & ~own);
+            const size_t occupied = VisibilityGrid::count(grid.occupiedMask(voxels[i]) & ~own);
+            if (free >= static_cast<size_t>(options.minFreeScans) && free >= options.minFreeRatio * (free + occupied))
+            {
+                isDynamic[i] = true;
+                dynamicVoxels.insert(voxels[i]);
+            }
+        }
+
+        // Extend the detected voxels to the occluded parts of the objects
+        if (options.dilation > 0 && !dynamicVoxels.empty())
+        {
+            const int d = options.dilation;
+            for (size_t i = 0; i < n; i++)
+            {
+                if (isDynamic[i] || (grid.occupiedMask(voxels[i]) & ~own) != 0)
+                {
+                    continue;
+                }
+                bool near = false;
+                for (int dx = -d; dx <= d && !near; dx++)
+                {
+                    for (int dy = -d; dy <= d && !near; dy++)
+                    {
+                        for (int dz = -d; dz <= d && !near; dz++)
+                        {
+                            near = dynamicVoxels.count(voxels[i] + Vector3i(dx, dy, dz)) > 0;
+                        }
+                    }
+                }
+                isDynamic[i] = near;
+            }
+        }
+
+        for (size_t i = 0; i < n; i++)
+        {
+            if (isDynamic[i])
+            {
+                dynamic[s].push_back(i);
+            }
+        }
+        total += dynamic[s].size();
+    }
+
+    lvr2::logout::get() << lvr2::info << "[DynamicObjectRemoval] Detected " << total
+        << " dynamic points in " << scans.size() << " scans" << lvr2::endl;
+    return dynamic;
+}
+
+size_t removeDynamicPoints(
+    std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const DynamicRemovalOptions& options)
+{
+    std::vector<std::vector<size_t>> dynamic = detectDynamicPoints(scans, origins, options);
+
+    size_t removed = 0;
+    for (size_t s = 0; s < scans.size(); s++)
+    {
+        if (dynamic[s].empty())
+        {
+            continue;
+        }
+        std::vector<bool> isDynamic(scans[s]->numPoints(), false);
+        for (size_t i : dynamic[s])
+        {
+            isDynamic[i] = true;
+        }
+        removed += scans[s]->retain([&](size_t i) { return !isDynamic[i]; });
+    }
+    return removed;
+}
+
+} // namespace lvr2
@@ -0,0 +1,187 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * FaceOrientation.cpp
+ */
+
+#include "lvr2/algorithm/FaceOrientation.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Dense>
+
+#include <algorithm>
+#include <queue>
+#include <utility>
+#include <vector>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// True, if the face traverses the edge from a to b
+bool hasDirectedEdge(const unsigned int* face, unsigned int a, unsigned int b)
+{
+    for(int i = 0; i < 3; i++)
+    {
+        if(face[i] == a && face[(i + 1) % 3] == b)
+        {
+            return true;
+        }
+    }
+    return false;
+}
+
+} // namespace
+
+size_t fixFaceOrientation(MeshBufferPtr mesh)
+{
+    const size_t numFaces = mesh->numFaces();
+    if(numFaces == 0)
+    {
+        return 0;
+    }
+
+    indexArray faces = mesh->getFaceIndices();
+    floatArr vertices = mesh->getVertices();
+    floatArr faceNormals = mesh->getFaceNormals();
+
+    // Flipping a face does not change its vertices, so the adjacency stays valid
+    MeshAdjacencyPtr adjacency = mesh->getAdjacency();
+
+    std::vector<bool> flipped(numFaces, false);
+    std::vector<bool> visited(numFaces, false);
+
+    auto flip = [&](size_t f)
+    {
+        std::swap(faces[3 * f + 1], faces[3 * f + 2]);
+        flipped[f] = !flipped[f];
+    };
+
+    size_t numComponents = 0;
+    std::vector<size_t> component;
+    std::queue<size_t> queue;
+    for(size_t seed = 0; seed < numFaces; seed++)
+    {
+        if(visited[seed])
+        {
+            continue;
+        }
+
+        // Breadth first traversal of the connected component
+        component.clear();
+        bool closed = true;
+        visited[seed] = true;
+        queue.push(seed);
+        while(!queue.empty())
+        {
+            size_t f = queue.front();
+            queue.pop();
+            component.push_back(f);
+
+            const unsigned int* face = &faces[3 * f];
+            for(int i = 0; i < 3; i++)
+            {
+                unsigned int a = face[i];
+                unsigned int b = face[(i + 1) % 3];
+                const std::vector<unsigned int> neighbors = adjacency->edgeFaces(a, b);
+                if(neighbors.size() != 2)
+                {
+                    closed = false;
+                }
+
+                for(unsigned int g : neighbors)
+                {
+                    if(g == f || visited[g])
+                    {
+                        continue;
+                    }
+                    // A consistently oriented neighbor traverses the edge from b to a
+                    if(hasDirectedEdge(&faces[3 * g], a, b))
+                    {
+                        flip(g);
+                    }
+                    visited[g] = true;
+                    queue.push(g);
+                }
+            }
+        }
+        numComponents++;
+
+        if(!closed)
+        {
+            continue;
+        }
+
+        // Orient closed components outwards
+        double volume = 0.0;
+        for(size_t f : component)
+        {
+            Eigen::Vector3d v[3];
+            for(int i = 0; i < 3; i++)
+            {
+                const size_t idx = faces[3 * f + i];
+                v[i] = Eigen::Vector3d(vertices[3 * idx], vertices[3 * idx + 1], vertices[3 * idx + 2]);
+            }
+            volume += v[0].dot(v[1].cross(v[2])) / 6.0;
+        }
+
+        if(volume < 0)
+        {
+            for(size_t f : component)
+            {
+                flip(f);
+            }
+        }
+    }
+
+    size_t numFlipped = 0;
+    for(size_t f = 0; f < numFaces; f++)
+    {
+        if(!flipped[f])
+        {
+            continue;
+        }
+        numFlipped++;
+        if(faceNormals)
+        {
+            for(int i = 0; i < 3; i++)
+            {
+                faceNormals[3 * f + i] = -faceNormals[3 * f + i];
+            }
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[FaceOrientation] Flipped " << numFlipped << " of "
+        << numFaces << " faces in " << numComponents << " components" << lvr2::endl;
+
+    return numFlipped;
+}
+
+} // namespace lvr2
@@ -0,0 +1,164 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * GroundDetection.cpp
+ */
+
+#include "lvr2/algorithm/GroundDetection.hpp"
+#include "lvr2/algorithm/PlanarTriangulation.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Geometry>
+
+#include <cmath>
+#include <random>
+
+namespace lvr2
+{
+
+namespace
+{
+
+size_t countInliers(const floatArr& points, size_t n, const Vector3d& normal, double d, double threshold)
+{
+    size_t count = 0;
+    for (size_t i = 0; i < n; i++)
+    {
+        if (std::abs(normal.dot(Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2])) - d) <= threshold)
+        {
+            count++;
+        }
+    }
+    return count;
+}
+
+} // anonymous namespace
+
+GroundDetectionResult detectGround(PointBufferPtr points, const GroundDetectionOptions& options)
+{
+    GroundDetectionResult result;
+    const size_t n = points ? points->numPoints() : 0;
+    if (n < 3)
+    {
+        return result;
+    }
+
+    floatArr pts = points->getPointArray();
+    const Vector3d up = options.up.normalized();
+    const double minCos = std::cos(options.maxTilt * M_PI / 180.0);
+
+    std::mt19937 rng(options.seed);
+    std::uniform_int_distribution<size_t> dist(0, n - 1);
+    auto point = [&](size_t i) { return Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]); };
+
+    Vector3d bestNormal;
+    double bestD = 0;
+    size_t bestInliers = 0;
+    for (int it = 0; it < options.iterations; it++)
+    {
+        Vector3d a = point(dist(rng));
+        Vector3d b = point(dist(rng));
+        Vector3d c = point(dist(rng));
+        Vector3d normal = (b - a).cross(c - a);
+        if (normal.norm() < 1e-12)
+        {
+            continue;
+        }
+        normal.normalize();
+        if (normal.dot(up) < 0)
+        {
+            normal = -normal;
+        }
+        if (normal.dot(up) < minCos)
+        {
+            continue;
+        }
+
+        double d = normal.dot(a);
+        size_t inliers = countInliers(pts, n, normal, d, options.distanceThreshold);
+        if (inliers > bestInliers)
+        {
+            bestInliers = inliers;
+            bestNormal = normal;
+            bestD = d;
+        }
+    }
+
+    if (bestInliers < 3)
+    {
+        lvr2::logout::get() << lvr2::warning << "[GroundDetection] No ground plane found" << lvr2::endl;
+        return result;
+    }
+
+    // Refine the plane with a least squares fit to the inliers
+    std::vector<size_t> inliers;
+    for (size_t i = 0; i < n; i++)
+    {
+        if (std::abs(bestNormal.dot(point(i)) - bestD) <= options.distanceThreshold)
+        {
+            inliers.push_back(i);
+        }
+    }
+    PointBufferPtr inlierPoints = std::make_shared<PointBuffer>(points->select(inliers));
+    Plane<BaseVector<float>> plane = fitPlane(inlierPoints);
+    Vector3d normal(plane.normal.x, plane.normal.y, plane.normal.z);
+    if (normal.dot(up) < 0)
+    {
+        plane.normal = -plane.normal;
+        normal = -normal;
+    }
+    const Vector3d pos(plane.pos.x, plane.pos.y, plane.pos.z);
+    const double d = normal.dot(pos);
+
+    for (size_t i = 0; i < n; i++)
+    {
+        if (std::abs(normal.dot(point(i)) - d) <= options.distanceThreshold)
+        {
+            result.ground.push_back(i);
+        }
+        else
+        {
+            result.nonGround.push_back(i);
+        }
+    }
+
+    // Rotate the normal onto the z axis, then move the plane to z = 0
+    Eigen::Matrix3d rotation = Eigen::Quaterniond::FromTwoVectors(normal, Vector3d::UnitZ()).toRotationMatrix();
+    result.leveling.block<3, 3>(0, 0) = rotation;
+    result.leveling(2, 3) = -(rotation * pos).z();
+
+    result.plane = plane;
+    result.found = true;
+
+    lvr2::logout::get() << lvr2::info << "[GroundDetection] Found ground plane " << plane << " with "
+                        << result.ground.size() << " of " << n << " points" << lvr2::endl;
+    return result;
+}
+
+} // namespace lvr2
@@ -0,0 +1,150 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * HeightField.cpp
+ */
+
+#include "lvr2/algorithm/HeightField.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+HeightGrid rasterizeHeightField(MeshBufferPtr mesh, double cellSize, float noData)
+{
+    HeightGrid grid;
+    grid.cellSize = cellSize;
+    grid.noData = noData;
+
+    if (!mesh || mesh->numVertices() == 0 || mesh->numFaces() == 0 || cellSize <= 0)
+    {
+        return grid;
+    }
+
+    floatArr vertices = mesh->getVertices();
+    const size_t numVertices = mesh->numVertices();
+
+    double minX = std::numeric_limits<double>::max();
+    double minY = std::numeric_limits<double>::max();
+    double maxX = std::numeric_limits<double>::lowest();
+    double maxY = std::numeric_limits<double>::lowest();
+    for (size_t i = 0; i < numVertices; i++)
+    {
+        minX = std::min(minX, (double)vertices[3 * i]);
+        maxX = std::max(maxX, (double)vertices[3 * i]);
+        minY = std::min(minY, (double)vertices[3 * i + 1]);
+        maxY = std::max(maxY, (double)vertices[3 * i + 1]);
+    }
+
+    grid.originX = minX;
+    grid.originY = minY;
+    grid.cols = (size_t)std::floor((maxX - minX) / cellSize) + 1;
+    grid.rows = (size_t)std::floor((maxY - minY) / cellSize) + 1;
+
+    return rasterizeHeightField(mesh, grid);
+}
+
+HeightGrid rasterizeHeightField(MeshBufferPtr mesh, const HeightGrid& layout)
+{
+    HeightGrid grid = layout;
+    grid.heights.assign(grid.cols * grid.rows, grid.noData);
+
+    if (!mesh || mesh->numVertices() == 0 || mesh->numFaces() == 0 || grid.cellSize <= 0)
+    {
+        return grid;
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    const size_t numFaces = mesh->numFaces();
+    const double cellSize = grid.cellSize;
+    const double minX = grid.originX;
+    const double minY = grid.originY;
+
+    std::vector<bool> filled(grid.heights.size(), false);
+
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const float* a = &vertices[3 * faces[3 * f]];
+        const float* b = &vertices[3 * faces[3 * f + 1]];
+        const float* c = &vertices[3 * faces[3 * f + 2]];
+
+        // Faces that are vertical in the xy plane do not contribute
+        const double det = (b[1] - c[1]) * (a[0] - c[0]) + (c[0] - b[0]) * (a[1] - c[1]);
+        if (std::abs(det) < 1e-12)
+        {
+            continue;
+        }
+
+        const double fx0 = std::min({a[0], b[0], c[0]});
+        const double fx1 = std::max({a[0], b[0], c[0]});
+        const double fy0 = std::min({a[1], b[1], c[1]});
+        const double fy1 = std::max({a[1], b[1], c[1]});
+
+        // Range of columns / rows (counted from the south) whose centers
+        // might be covered by the face
+        const long c0 = std::max(0L, (long)std::ceil((fx0 - minX) / cellSize - 0.5));
+        const long c1 = std::min((long)grid.cols - 1, (long)std::floor((fx1 - minX) / cellSize - 0.5));
+        const long r0 = std::max(0L, (long)std::ceil((fy0 - minY) / cellSize - 0.5));
+        const long r1 = std::min((long)grid.rows - 1, (long)std::floor((fy1 - minY) / cellSize - 0.5));
+
+        for (long r = r0; r <= r1; r++)
+        {
+            const double y = minY + (r + 0.5) * cellSize;
+            for (long col = c0; col <= c1; col++)
+            {
+                const double x = minX + (col + 0.5) * cellSize;
+
+                const double l1 = ((b[1] - c[1]) * (x - c[0]) + (c[0] - b[0]) * (y - c[1])) / det;
+                const double l2 = ((c[1] - a[1]) * (x - c[0]) + (a[0] - c[0]) * (y - c[1])) / det;
+                const double l3 = 1.0 - l1 - l2;
+                const double eps = -1e-9;
+                if (l1 < eps || l2 < eps || l3 < eps)
+                {
+                    continue;
+                }
+
+                const float z = (float)(l1 * a[2] + l2 * b[2] + l3 * c[2]);
+                const size_t idx = (grid.rows - 1 - r) * grid.cols + col;
+                if (!filled[idx] || z > grid.heights[idx])
+                {
+                    grid.heights[idx] = z;
+                    filled[idx] = true;
+                }
+            }
+        }
+    }
+
+    return grid;
+}
+
+} // namespace lvr2
@@ -0,0 +1,232 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IntensityAlgorithms.cpp
+ */
+
+#include "lvr2/algorithm/IntensityAlgorithms.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Copies the first component of the "intensities" channel
+bool getIntensities(PointBufferPtr buffer, std::vector<float>& values)
+{
+    FloatChannelOptional channel = buffer ? buffer->getFloatChannel("intensities") : FloatChannelOptional();
+    if (!channel)
+    {
+        return false;
+    }
+
+    values.resize(channel->numElements());
+    for (size_t i = 0; i < values.size(); i++)
+    {
+        values[i] = (*channel)[i][0];
+    }
+    return true;
+}
+
+void setIntensities(PointBufferPtr buffer, const std::vector<float>& values)
+{
+    FloatChannelOptional channel = buffer->getFloatChannel("intensities");
+    for (size_t i = 0; i < values.size(); i++)
+    {
+        (*channel)[i][0] = values[i];
+    }
+}
+
+/// Cumulative, normalized histogram of the values in [lo, hi]
+std::vector<double> cumulativeHistogram(const std::vector<float>& values, float lo, float hi, size_t bins)
+{
+    std::vector<double> cdf(bins, 0.0);
+    if (values.empty())
+    {
+        return cdf;
+    }
+
+    const double scale = hi > lo ? bins / (double)(hi - lo) : 0.0;
+    for (float v : values)
+    {
+        size_t b = std::min(bins - 1, (size_t)std::max(0.0, (v - lo) * scale));
+        cdf[b] += 1.0;
+    }
+
+    double sum = 0.0;
+    for (double& c : cdf)
+    {
+        sum += c;
+        c = sum / values.size();
+    }
+    return cdf;
+}
+
+/// Maps the source values so that their histogram matches the one of the reference values
+void matchHistogram(std::vector<float>& source, const std::vector<float>& reference, size_t bins)
+{
+    if (source.empty() || reference.empty() || bins == 0)
+    {
+        return;
+    }
+
+    float lo = std::numeric_limits<float>::max();
+    float hi = std::numeric_limits<float>::lowest();
+    for (float v : source)
+    {
+        lo = std::min(lo, v);
+        hi = std::max(hi, v);
+    }
+    for (float v : reference)
+    {
+        lo = std::min(lo, v);
+        hi = std::max(hi, v);
+    }
+    if (hi <= lo)
+    {
+        return;
+    }
+
+    std::vector<double> srcCdf = cumulativeHistogram(source, lo, hi, bins);
+    std::vector<double> refCdf = cumulativeHistogram(reference, lo, hi, bins);
+
+    // For each source bin, find the first reference bin with at least the same cumulative frequency
+    const float width = (hi - lo) / bins;
+    std::vector<float> lut(bins);
+    for (size_t b = 0; b < bins; b++)
+    {
+        size_t j = std::lower_bound(refCdf.begin(), refCdf.end(), srcCdf[b] - 1e-9) - refCdf.begin();
+        lut[b] = lo + (std::min(j, bins - 1) + 0.5f) * width;
+    }
+
+    const double scale = bins / (double)(hi - lo);
+    for (float& v : source)
+    {
+        v = lut[std::min(bins - 1, (size_t)std::max(0.0, (v - lo) * scale))];
+    }
+}
+
+} // anonymous namespace
+
+PointBufferPtr filterByIntensity(PointBufferPtr buffer, float minIntensity, float maxIntensity)
+{
+    std::vector<float> values;
+    if (!getIntensities(buffer, values))
+    {
+        lvr2::logout::get() << lvr2::warning << "[IntensityAlgorithms] Point cloud has no intensities. "
+                            << "Keeping all points." << lvr2::endl;
+        return buffer;
+    }
+
+    std::vector<size_t> indices;
+    for (size_t i = 0; i < values.size(); i++)
+    {
+        if (values[i] >= minIntensity && values[i] <= maxIntensity)
+        {
+            indices.push_back(i);
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[IntensityAlgorithms] Kept " << indices.size()
+                        << " of " << values.size() << " points." << lvr2::endl;
+
+    return std::make_shared<PointBuffer>(buffer->select(indices));
+}
+
+bool rescaleIntensity(PointBufferPtr buffer, float lowPercentile, float highPercentile)
+{
+    std::vector<float> values;
+    if (!getIntensities(buffer, values) || values.empty())
+    {
+        return false;
+    }
+
+    std::vector<float> sorted = values;
+    std::sort(sorted.begin(), sorted.end());
+    auto percentile = [&sorted](float p)
+    {
+        p = std::min(1.0f, std::max(0.0f, p));
+        return sorted[(size_t)(p * (sorted.size() - 1))];
+    };
+
+    const float lo = percentile(lowPercentile);
+    const float hi = percentile(highPercentile);
+    const float range = hi > lo ? hi - lo : 1.0f;
+    for (float& v : values)
+    {
+        v = std::min(1.0f, std::max(0.0f, (v - lo) / range));
+    }
+
+    setIntensities(buffer, values);
+    return true;
+}
+
+bool matchIntensityHistogram(PointBufferPtr source, PointBufferPtr reference, size_t bins)
+{
+    std::vector<float> srcValues;
+    std::vector<float> refValues;
+    if (!getIntensities(source, srcValues) || !getIntensities(reference, refValues))
+    {
+        return false;
+    }
+
+    matchHistogram(srcValues, refValues, bins);
+    setIntensities(source, srcValues);
+    return true;
+}
+
+void normalizeIntensities(std::vector<PointBufferPtr>& scans, size_t bins)
+{
+    // The combined intensities of all scans serve as reference
+    std::vector<float> pooled;
+    std::vector<float> values;
+    for (PointBufferPtr scan : scans)
+    {
+        if (getIntensities(scan, values))
+        {
+            pooled.insert(pooled.end(), values.begin(), values.end());
+        }
+    }
+
+    for (PointBufferPtr scan : scans)
+    {
+        if (getIntensities(scan, values))
+        {
+            matchHistogram(values, pooled, bins);
+            setIntensities(scan, values);
+        }
+    }
+}
+
+} // namespace lvr2
@@ -0,0 +1,494 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshBoolean.cpp
+ *
+ * The BSP based clipping follows the approach of csg.js by Evan Wallace.
+ * Tree construction and traversal are iterative to support meshes with
+ * many faces without exhausting the stack.
+ */
+
+#include "lvr2/algorithm/MeshBoolean.hpp"
+
+#include <algorithm>
+#include <array>
+#include <cmath>
+#include <limits>
+#include <map>
+#include <memory>
+#include <stack>
+#include <utility>
+#include <vector>
+
+namespace lvr2
+{
+
+namespace
+{
+
+const double BSP_EPSILON = 1e-7;
+
+struct BspPlane
+{
+    Vector3d normal = Vector3d::Zero();
+    double w = 0;
+
+    void flip()
+    {
+        normal = -normal;
+        w = -w;
+    }
+};
+
+struct BspPolygon
+{
+    std::vector<Vector3d> vertices;
+    BspPlane plane;
+
+    void flip()
+    {
+        std::reverse(vertices.begin(), vertices.end());
+        plane.flip();
+    }
+};
+
+enum PointSide
+{
+    COPLANAR = 0,
+    FRONT = 1,
+    BACK = 2,
+    SPANNING = 3
+};
+
+void splitPolygon(
+    const BspPlane& plane,
+    const BspPolygon& polygon,
+    std::vector<BspPolygon>& coplanarFront,
+    std::vector<BspPolygon>& coplanarBack,
+    std::vector<BspPolygon>& front,
+    std::vector<BspPolygon>& back)
+{
+    const size_t n = polygon.vertices.size();
+    int polygonType = 0;
+    std::vector<int> types(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        double t = plane.normal.dot(polygon.vertices[i]) - plane.w;
+        types[i] = t < -BSP_EPSILON ? BACK : (t > BSP_EPSILON ? FRONT : COPLANAR);
+        polygonType |= types[i];
+    }
+
+    switch (polygonType)
+    {
+    case COPLANAR:
+        (plane.normal.dot(polygon.plane.normal) > 0 ? coplanarFront : coplanarBack).push_back(polygon);
+        break;
+    case FRONT:
+        front.push_back(polygon);
+        break;
+    case BACK:
+        back.push_back(polygon);
+        break;
+    case SPANNING:
+    {
+        BspPolygon f, b;
+        f.plane = b.plane = polygon.plane;
+        for (size_t i = 0; i < n; i++)
+        {
+            size_t j = (i + 1) % n;
+            int ti = types[i];
+            int tj = types[j];
+            const Vector3d& vi = polygon.vertices[i];
+            const Vector3d& vj = polygon.vertices[j];
+            if (ti != BACK)
+            {
+                f.vertices.push_back(vi);
+            }
+            if (ti != FRONT)
+            {
+                b.vertices.push_back(vi);
+            }
+            if ((ti | tj) == SPANNING)
+            {
+                double t = (plane.w - plane.normal.dot(vi)) / plane.normal.dot(vj - vi);
+                Vector3d v = vi + (vj - vi) * t;
+                f.vertices.push_back(v);
+                b.vertices.push_back(v);
+            }
+        }
+        if (f.vertices.size() >= 3)
+        {
+            front.push_back(std::move(f));
+        }
+        if (b.vertices.size() >= 3)
+        {
+            back.push_back(std::move(b));
+        }
+        break;
+    }
+    }
+}
+
+struct BspNode
+{
+    bool hasPlane = false;
+    BspPlane plane;
+    BspNode* front = nullptr;
+    BspNode* back = nullptr;
+    std::vector<BspPolygon> polygons;
+};
+
+class BspTree
+{
+public:
+    BspTree(std::vector<BspPolygon> polygons)
+    {
+        m_root = newNode();
+        build(std::move(polygons));
+    }
+
+    /// Converts solid space to empty space and vice versa
+    void invert()
+    {
+        for (auto& node : m_nodes)
+        {
+            for (BspPolygon& p : node->polygons)
+            {
+                p.flip();
+            }
+            node->plane.flip();
+            std::swap(node->front, node->back);
+        }
+    }
+
+    /// Removes all parts of the polygons that are inside of this tree
+    std::vector<BspPolygon> clipPolygons(std::vector<BspPolygon> polygons) const
+    {
+        std::vector<BspPolygon> result;
+        std::stack<std::pair<const BspNode*, std::vector<BspPolygon>>> work;
+        work.push({m_root, std::move(polygons)});
+
+        while (!work.empty())
+        {
+            const BspNode* node = work.top().first;
+            std::vector<BspPolygon> current = std::move(work.top().second);
+            work.pop();
+
+            if (!node->hasPlane)
+            {
+                result.insert(result.end(), current.begin(), current.end());
+                continue;
+            }
+
+            std::vector<BspPolygon> front, back;
+            for (const BspPolygon& p : current)
+            {
+                splitPolygon(node->plane, p, front, back, front, back);
+            }
+
+            if (node->front)
+            {
+                work.push({node->front, std::move(front)});
+            }
+            else
+            {
+                result.insert(result.end(), front.begin(), front.end());
+            }
+
+            // Polygons behind a leaf are inside of the solid and discarded
+            if (node->back)
+            {
+                work.push({node->back, std::move(back)});
+            }
+        }
+        return result;
+    }
+
+    /// Removes all parts of the polygons of this tree that are inside of other
+    void clipTo(const BspTree& other)
+    {
+        for (auto& node : m_nodes)
+        {
+            node->polygons = other.clipPolygons(std::move(node->polygons));
+        }
+    }
+
+    std::vector<BspPolygon> allPolygons() const
+    {
+        std::vector<BspPolygon> result;
+        for (const auto& node : m_nodes)
+        {
+            result.insert(result.end(), node->polygons.begin(), node->polygons.end());
+        }
+        return result;
+    }
+
+    /// Adds the polygons to the tree, creating new nodes where necessary
+    void build(std::vector<BspPolygon> polygons)
+    {
+        std::stack<std::pair<BspNode*, std::vector<BspPolygon>>> work;
+        work.push({m_root, std::move(polygons)});
+
+        while (!work.empty())
+        {
+            BspNode* node = work.top().first;
+            std::vector<BspPolygon> current = std::move(work.top().second);
+            work.pop();
+
+            if (current.empty())
+            {
+                continue;
+            }
+            if (!node->hasPlane)
+            {
+                node->plane = current[0].plane;
+                node->hasPlane = true;
+            }
+
+            std::vector<BspPolygon> front, back;
+            for (const BspPolygon& p : current)
+            {
+                splitPolygon(node->plane, p, node->polygons, node->polygons, front, back);
+            }
+
+            if (!front.empty())
+            {
+                if (!node->front)
+                {
+                    node->front = newNode();
+                }
+                work.push({node->front, std::move(front)});
+            }
+            if (!back.empty())
+            {
+                if (!node->back)
+                {
+                    node->back = newNode();
+                }
+                work.push({node->back, std::move(back)});
+            }
+        }
+    }
+
+private:
+    BspNode* newNode()
+    {
+        m_nodes.push_back(std::make_unique<BspNode>());
+        return m_nodes.back().get();
+    }
+
+    std::vector<std::unique_ptr<BspNode>> m_nodes;
+    BspNode* m_root;
+};
+
+std::vector<BspPolygon> toPolygons(MeshBufferPtr mesh)
+{
+    std::vector<BspPolygon> polygons;
+    if (!mesh || mesh->numFaces() == 0)
+    {
+        return polygons;
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    polygons.reserve(mesh->numFaces());
+    for (size_t f = 0; f < mesh->numFaces(); f++)
+    {
+        BspPolygon p;
+        for (int j = 0; j < 3; j++)
+        {
+            unsigned int v = faces[3 * f + j];
+            p.vertices.emplace_back(vertices[3 * v], vertices[3 * v + 1], vertices[3 * v + 2]);
+        }
+        Vector3d n = (p.vertices[1] - p.vertices[0]).cross(p.vertices[2] - p.vertices[0]);
+        if (n.norm() < 1e-12)
+        {
+            // Degenerate faces do not define a plane
+            continue;
+        }
+        p.plane.normal = n.normalized();
+        p.plane.w = p.plane.normal.dot(p.vertices[0]);
+        polygons.push_back(std::move(p));
+    }
+    return polygons;
+}
+
+MeshBufferPtr toMesh(const std::vector<BspPolygon>& polygons)
+{
+    // Merge vertices that are closer than a small fraction of the extent
+    Vector3d min = Vector3d::Constant(std::numeric_limits<double>::max());
+    Vector3d max = Vector3d::Constant(std::numeric_limits<double>::lowest());
+    for (const BspPolygon& p : polygons)
+    {
+        for (const Vector3d& v : p.vertices)
+        {
+            min = min.cwiseMin(v);
+            max = max.cwiseMax(v);
+        }
+    }
+    const double cell = std::max((max - min).norm(), 1e-12) * 1e-9;
+
+    std::map<std::array<long long, 3>, unsigned int> vertexIndex;
+    std::vector<float> vertices;
+    std::vector<unsigned int> faces;
+
+    auto index = [&](const Vector3d& v)
+    {
+        std::array<long long, 3> key = {
+            std::llround(v.x() / cell), std::llround(v.y() / cell), std::llround(v.z() / cell)
+        };
+        auto it = vertexIndex.find(key);
+        if (it != vertexIndex.end())
+        {
+            return it->second;
+        }
+        unsigned int i = vertices.size() / 3;
+        vertices.insert(vertices.end(), {(float)v.x(), (float)v.y(), (float)v.z()});
+        vertexIndex[key] = i;
+        return i;
+    };
+
+    for (const BspPolygon& p : polygons)
+    {
+        unsigned int first = index(p.vertices[0]);
+        for (size_t i = 1; i + 1 < p.vertices.size(); i++)
+        {
+            unsigned int b = index(p.vertices[i]);
+            unsigned int c = index(p.vertices[i + 1]);
+            if (first != b && b != c && first != c)
+            {
+                faces.insert(faces.end(), {first, b, c});
+            }
+        }
+    }
+
+    MeshBufferPtr mesh(new MeshBuffer);
+    floatArr vertexArr(new float[vertices.size()]);
+    indexArray faceArr(new unsigned int[faces.size()]);
+    std::copy(vertices.begin(), vertices.end(), vertexArr.get());
+    std::copy(faces.begin(), faces.end(), faceArr.get());
+    mesh->setVertices(vertexArr, vertices.size() / 3);
+    mesh->setFaceIndices(faceArr, faces.size() / 3);
+    return mesh;
+}
+
+} // anonymous namespace
+
+MeshBufferPtr meshBoolean(MeshBufferPtr a, MeshBufferPtr b, BooleanOperation op)
+{
+    BspTree ta(toPolygons(a));
+    BspTree tb(toPolygons(b));
+
+    switch (op)
+    {
+    case BooleanOperation::Union:
+        ta.clipTo(tb);
+        tb.clipTo(ta);
+        tb.invert();
+        tb.clipTo(ta);
+        tb.invert();
+        ta.build(tb.allPolygons());
+        break;
+    case BooleanOperation::Difference:
+        ta.invert();
+        ta.clipTo(tb);
+        tb.clipTo(ta);
+        tb.invert();
+        tb.clipTo(ta);
+        tb.invert();
+        ta.build(tb.allPolygons());
+        ta.invert();
+        break;
+    case BooleanOperation::Intersection:
+        ta.invert();
+        tb.clipTo(ta);
+        tb.invert();
+        ta.clipTo(tb);
+        tb.clipTo(ta);
+        ta.build(tb.allPolygons());
+        ta.invert();
+        break;
+    }
+
+    return toMesh(ta.allPolygons());
+}
+
+MeshBufferPtr boxMesh(const Vector3d& min, const Vector3d& max)
+{
+    floatArr vertices(new float[24]);
+    for (int i = 0; i < 8; i++)
+    {
+        vertices[3 * i] = (i & 1) ? max.x() : min.x();
+        vertices[3 * i + 1] = (i & 2) ? max.y() : min.y();
+        vertices[3 * i + 2] = (i & 4) ? max.z() : min.z();
+    }
+
+    // Two counter clockwise (seen from outside) triangles per side
+    const unsigned int quads[6][4] = {
+        {0, 4, 6, 2}, // -x
+        {1, 3, 7, 5}, // +x
+        {0, 1, 5, 4}, // -y
+        {2, 6, 7, 3}, // +y
+        {0, 2, 3, 1}, // -z
+        {4, 5, 7, 6}  // +z
+    };
+    indexArray faces(new unsigned int[36]);
+    for (int q = 0; q < 6; q++)
+    {
+        const unsigned int* v = quads[q];
+        unsigned int tris[6] = {v[0], v[1], v[2], v[0], v[2], v[3]};
+        std::copy(tris, tris + 6, faces.get() + 6 * q);
+    }
+
+    MeshBufferPtr mesh(new MeshBuffer);
+    mesh->setVertices(vertices, 8);
+    mesh->setFaceIndices(faces, 12);
+    return mesh;
+}
+
+MeshBufferPtr halfSpaceMesh(const Vector3d& point, const Vector3d& normal, double size)
+{
+    // Axis aligned cube with its +z face on the plane, rotated so that z
+    // points along the normal
+    MeshBufferPtr box = boxMesh(Vector3d(-size / 2, -size / 2, -size), Vector3d(size / 2, size / 2, 0));
+    Eigen::Matrix3d rotation = Eigen::Quaterniond::FromTwoVectors(Vector3d::UnitZ(), normal.normalized()).toRotationMatrix();
+
+    floatArr vertices = box->getVertices();
+    for (size_t i = 0; i < box->numVertices(); i++)
+    {
+        Vector3d v(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+        v = rotation * v + point;
+        vertices[3 * i] = v.x();
+        vertices[3 * i + 1] = v.y();
+        vertices[3 * i + 2] = v.z();
+    }
+    return box;
+}
+
+} // namespace lvr2
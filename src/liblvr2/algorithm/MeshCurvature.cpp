@@ -0,0 +1,75 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshCurvature.cpp
+ */
+
+#include "lvr2/algorithm/MeshCurvature.hpp"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/PMPMesh.hpp"
+
+namespace lvr2
+{
+
+void calcVertexCurvature(MeshBufferPtr buffer, unsigned int smoothingSteps)
+{
+    // PMPMesh keeps the vertex order of the buffer
+    PMPMesh<BaseVector<float>> mesh(buffer);
+    DenseVertexMap<VertexCurvature> curvature = calcVertexCurvature(mesh, smoothingSteps);
+
+    const size_t n = buffer->numVertices();
+    floatArr mean(new float[n]);
+    floatArr gaussian(new float[n]);
+    floatArr min(new float[n]);
+    floatArr max(new float[n]);
+    floatArr minDirection(new float[3 * n]);
+    floatArr maxDirection(new float[3 * n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        const VertexCurvature& c = curvature[VertexHandle(i)];
+        mean[i] = c.mean;
+        gaussian[i] = c.gaussian;
+        min[i] = c.min;
+        max[i] = c.max;
+        for (int j = 0; j < 3; j++)
+        {
+            minDirection[3 * i + j] = c.minDirection[j];
+            maxDirection[3 * i + j] = c.maxDirection[j];
+        }
+    }
+
+    buffer->addFloatChannel(mean, "mean_curvature", n, 1);
+    buffer->addFloatChannel(gaussian, "gaussian_curvature", n, 1);
+    buffer->addFloatChannel(min, "min_curvature", n, 1);
+    buffer->addFloatChannel(max, "max_curvature", n, 1);
+    buffer->addFloatChannel(minDirection, "min_curvature_direction", n, 3);
+    buffer->addFloatChannel(maxDirection, "max_curvature_direction", n, 3);
+}
+
+} // namespace lvr2
@@ -0,0 +1,338 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshSampling.cpp
+ */
+
+#include "lvr2/algorithm/MeshSampling.hpp"
+#include "lvr2/algorithm/raycasting/BVHRaycaster.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/optional.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <random>
+
+namespace lvr2
+{
+
+namespace
+{
+
+Vector3f vec3(const floatArr& arr, size_t i)
+{
+    return Vector3f(arr[3 * i], arr[3 * i + 1], arr[3 * i + 2]);
+}
+
+} // anonymous namespace
+
+PointBufferPtr sampleMesh(MeshBufferPtr mesh, float density, unsigned int seed)
+{
+    PointBufferPtr out(new PointBuffer);
+    if (!mesh || mesh->numFaces() == 0 || density <= 0)
+    {
+        return out;
+    }
+
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    floatArr vertexNormals = mesh->hasVertexNormals() ? mesh->getVertexNormals() : floatArr();
+    size_t colorWidth = 0;
+    ucharArr vertexColors = mesh->getVertexColors(colorWidth);
+
+    // Cumulative face areas to pick faces proportional to their area
+    std::vector<double> cumulative(numFaces);
+    double totalArea = 0;
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        Vector3f a = vec3(vertices, faces[3 * f]);
+        Vector3f b = vec3(vertices, faces[3 * f + 1]);
+        Vector3f c = vec3(vertices, faces[3 * f + 2]);
+        totalArea += 0.5 * (b - a).cross(c - a).norm();
+        cumulative[f] = totalArea;
+    }
+
+    const size_t numPoints = (size_t)std::round(totalArea * density);
+    if (numPoints == 0)
+    {
+        return out;
+    }
+
+    floatArr points(new float[3 * numPoints]);
+    floatArr normals(new float[3 * numPoints]);
+    ucharArr colors = vertexColors ? ucharArr(new unsigned char[3 * numPoints]) : ucharArr();
+
+    std::mt19937 rng(seed);
+    std::uniform_real_distribution<double> areaDist(0.0, totalArea);
+    std::uniform_real_distribution<float> unit(0.0f, 1.0f);
+
+    for (size_t i = 0; i < numPoints; i++)
+    {
+        size_t f = std::lower_bound(cumulative.begin(), cumulative.end(), areaDist(rng)) - cumulative.begin();
+        f = std::min(f, numFaces - 1);
+        const unsigned int* idx = &faces[3 * f];
+
+        // Uniform barycentric coordinates
+        float r1 = std::sqrt(unit(rng));
+        float r2 = unit(rng);
+        float w0 = 1.0f - r1;
+        float w1 = r1 * (1.0f - r2);
+        float w2 = r1 * r2;
+
+        Vector3f a = vec3(vertices, idx[0]);
+        Vector3f b = vec3(vertices, idx[1]);
+        Vector3f c = vec3(vertices, idx[2]);
+        Vector3f p = w0 * a + w1 * b + w2 * c;
+
+        Vector3f n;
+        if (vertexNormals)
+        {
+            n = w0 * vec3(vertexNormals, idx[0]) + w1 * vec3(vertexNormals, idx[1]) + w2 * vec3(vertexNormals, idx[2]);
+        }
+        else
+        {
+            n = (b - a).cross(c - a);
+        }
+        n.normalize();
+
+        for (int j = 0; j < 3; j++)
+        {
+            points[3 * i + j] = p[j];
+            normals[3 * i + j] = n[j];
+            if (colors)
+            {
+                colors[3 * i + j] = (unsigned char)std::round(
+                    w0 * vertexColors[colorWidth * idx[0] + j] +
+                    w1 * vertexColors[colorWidth * idx[1] + j] +
+                    w2 * vertexColors[colorWidth * idx[2] + j]);
+            }
+        }
+    }
+
+    out->setPointArray(points, numPoints);
+    out->setNormalArray(normals, numPoints);
+    if (colors)
+    {
+        out->setColorArray(colors, numPoints);
+    }
+    return out;
+}
+
+PointBufferPtr virtualScan(
+    MeshBufferPtr mesh,
+    const std::vector<Transformd>& poses,
+    const VirtualScannerOptions& options)
+{
+    using ScanInt = Intersection<intelem::Point, intelem::Distance, intelem::Normal>;
+
+    PointBufferPtr out(new PointBuffer);
+    if (!mesh || mesh->numFaces() == 0 || poses.empty())
+    {
+        return out;
+    }
+
+    // Ray directions in the scanner frame
+    const float deg2rad = M_PI / 180.0f;
+    std::vector<Vector3f> directions;
+    const int hSteps = std::max(1, (int)std::floor(options.horizontalFov / options.horizontalResolution));
+    const int vSteps = std::max(1, (int)std::floor((options.verticalMax - options.verticalMin) / options.verticalResolution) + 1);
+    for (int h = 0; h < hSteps; h++)
+    {
+        const float azimuth = (-options.horizontalFov / 2 + h * options.horizontalResolution) * deg2rad;
+        for (int v = 0; v < vSteps; v++)
+        {
+            const float elevation = (options.verticalMin + v * options.verticalResolution) * deg2rad;
+            directions.emplace_back(
+                std::cos(elevation) * std::cos(azimuth),
+                std::cos(elevation) * std::sin(azimuth),
+                std::sin(elevation));
+        }
+    }
+
+    BVHRaycaster<ScanInt> raycaster(mesh);
+    std::mt19937 rng(options.seed);
+    // A normal distribution requires a positive standard deviation
+    boost::optional<std::normal_distribution<float>> noise;
+    if (options.rangeNoise > 0)
+    {
+        noise = std::normal_distribution<float>(0.0f, options.rangeNoise);
+    }
+
+    std::vector<float> points;
+    std::vector<float> normals;
+    std::vector<float> ranges;
+
+    for (const Transformd& pose : poses)
+    {
+        const Eigen::Matrix3f rotation = pose.block<3, 3>(0, 0).cast<float>();
+        const Vector3f origin = pose.block<3, 1>(0, 3).cast<float>();
+
+        std::vector<Vector3f> worldDirections(directions.size());
+        for (size_t i = 0; i < directions.size(); i++)
+        {
+            worldDirections[i] = rotation * directions[i];
+        }
+
+        std::vector<ScanInt> intersections;
+        std::vector<uint8_t> hits;
+        raycaster.castRays(origin, worldDirections, intersections, hits);
+
+        for (size_t i = 0; i < directions.size(); i++)
+        {
+            if (!hits[i] || intersections[i].dist > options.maxRange)
+            {
+                continue;
+            }
+
+            float range = intersections[i].dist;
+            if (noise)
+            {
+                range = std::max(0.0f, range + (*noise)(rng));
+            }
+
+            Vector3f p = origin + worldDirections[i] * range;
+            Vector3f n = intersections[i].normal;
+            if (!options.worldFrame)
+            {
+                p = directions[i] * range;
+                n = rotation.transpose() * n;
+            }
+
+            points.insert(points.end(), {p.x(), p.y(), p.z()});
+            normals.insert(normals.end(), {n.x(), n.y(), n.z()});
+            ranges.push_back(range);
+        }
+    }
+
+    const size_t numPoints = ranges.size();
+    lvr2::logout::get() << lvr2::info << "[VirtualScanner] " << numPoints << " hits from "
+        << poses.size() << " poses" << lvr2::endl;
+    if (numPoints == 0)
+    {
+        return out;
+    }
+
+    floatArr pointArr(new float[points.size()]);
+    floatArr normalArr(new float[normals.size()]);
+    floatArr rangeArr(new float[numPoints]);
+    std::copy(points.begin(), points.end(), pointArr.get());
+    std::copy(normals.begin(), normals.end(), normalArr.get());
+    std::copy(ranges.begin(), ranges.end(), rangeArr.get());
+
+    out->setPointArray(pointArr, numPoints);
+    out->setNormalArray(normalArr, numPoints);
+    out->addFloatChannel(rangeArr, "ranges", numPoints, 1);
+    return out;
+}
+
+std::vector<LaserScan2D> virtualScan2D(
+    MeshBufferPtr mesh,
+    const std::vector<Transformd>& poses,
+    const VirtualScan2DOptions& options)
+{
+    using ScanInt = Intersection<intelem::Distance>;
+
+    const float deg2rad = M_PI / 180.0f;
+    LaserScan2D layout;
+    layout.angleMin = options.angleMin * deg2rad;
+    layout.angleIncrement = options.angleResolution * deg2rad;
+    layout.rangeMin = options.rangeMin;
+    layout.rangeMax = options.rangeMax;
+
+    // Ray directions in the scanner frame
+    const int steps = std::max(1, (int)std::floor((options.angleMax - options.angleMin) / options.angleResolution) + 1);
+    std::vector<Vector3f> directions;
+    for (int i = 0; i < steps; i++)
+    {
+        const float angle = layout.angleMin + i * layout.angleIncrement;
+        directions.emplace_back(std::cos(angle), std::sin(angle), 0.0f);
+    }
+
+    std::vector<LaserScan2D> scans(poses.size(), layout);
+    for (LaserScan2D& scan : scans)
+    {
+        scan.ranges.assign(steps, std::numeric_limits<float>::infinity());
+    }
+    if (!mesh || mesh->numFaces() == 0)
+    {
+        return scans;
+    }
+
+    BVHRaycaster<ScanInt> raycaster(mesh);
+    std::mt19937 rng(options.seed);
+    // A normal distribution requires a positive standard deviation
+    boost::optional<std::normal_distribution<float>> noise;
+    if (options.rangeNoise > 0)
+    {
+        noise = std::normal_distribution<float>(0.0f, options.rangeNoise);
+    }
+
+    for (size_t p = 0; p < poses.size(); p++)
+    {
+        const Eigen::Matrix3f rotation = poses[p].block<3, 3>(0, 0).cast<float>();
+        const Vector3f origin = poses[p].block<3, 1>(0, 3).cast<float>();
+
+        std::vector<Vector3f> worldDirections(directions.size());
+        for (size_t i = 0; i < directions.size(); i++)
+        {
+            worldDirections[i] = rotation * directions[i];
+        }
+
+        std::vector<ScanInt> intersections;
+        std::vector<uint8_t> hits;
+        raycaster.castRays(origin, worldDirections, intersections, hits);
+
+        for (size_t i = 0; i < directions.size(); i++)
+        {
+            if (!hits[i])
+            {
+                continue;
+            }
+
+            float range = intersections[i].dist;
+            if (noise)
+            {
+                range = std::max(0.0f, range + (*noise)(rng));
+            }
+            if (range >= options.rangeMin && range <= options.rangeMax)
+            {
+                scans[p].ranges[i] = range;
+            }
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[VirtualScanner] Simulated " << scans.size() << " planar scans with "
+        << steps << " rays" << lvr2::endl;
+    return scans;
+}
+
+} // namespace lvr2
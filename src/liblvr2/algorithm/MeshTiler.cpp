@@ -0,0 +1,261 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * MeshTiler.cpp
+ */
+
+#include "lvr2/algorithm/MeshTiler.hpp"
+#include "lvr2/io/ModelFactory.hpp"
+#include "lvr2/types/Model.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <fstream>
+#include <iomanip>
+#include <limits>
+#include <sstream>
+#include <stdexcept>
+#include <unordered_map>
+#include <vector>
+
+namespace lvr2
+{
+
+MeshBufferPtr extractFaces(MeshBufferPtr mesh, const std::vector<size_t>& faceIds)
+{
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    floatArr normals = mesh->getVertexNormals();
+    floatArr texCoords = mesh->getTextureCoordinates();
+    indexArray materials = mesh->getFaceMaterialIndices();
+    size_t colorWidth = 0;
+    ucharArr colors = mesh->getVertexColors(colorWidth);
+
+    // Map old vertex indices to new ones
+    std::unordered_map<unsigned int, unsigned int> vertexMap;
+    std::vector<unsigned int> usedVertices;
+    indexArray tileFaces(new unsigned int[faceIds.size() * 3]);
+    for (size_t i = 0; i < faceIds.size(); i++)
+    {
+        for (size_t j = 0; j < 3; j++)
+        {
+            unsigned int v = faces[faceIds[i] * 3 + j];
+            auto it = vertexMap.find(v);
+            if (it == vertexMap.end())
+            {
+                it = vertexMap.emplace(v, usedVertices.size()).first;
+                usedVertices.push_back(v);
+            }
+            tileFaces[i * 3 + j] = it->second;
+        }
+    }
+
+    const size_t n = usedVertices.size();
+    MeshBufferPtr tile = std::make_shared<MeshBuffer>();
+
+    floatArr tileVertices(new float[n * 3]);
+    for (size_t i = 0; i < n; i++)
+    {
+        std::copy_n(&vertices[usedVertices[i] * 3], 3, &tileVertices[i * 3]);
+    }
+    tile->setVertices(tileVertices, n);
+    tile->setFaceIndices(tileFaces, faceIds.size());
+
+    if (normals)
+    {
+        floatArr tileNormals(new float[n * 3]);
+        for (size_t i = 0; i < n; i++)
+        {
+            std::copy_n(&normals[usedVertices[i] * 3], 3, &tileNormals[i * 3]);
+        }
+        tile->setVertexNormals(tileNormals);
+    }
+
+    if (colors)
+    {
+        ucharArr tileColors(new unsigned char[n * colorWidth]);
+        for (size_t i = 0; i < n; i++)
+        {
+            std::copy_n(&colors[usedVertices[i] * colorWidth], colorWidth, &tileColors[i * colorWidth]);
+        }
+        tile->setVertexColors(tileColors, colorWidth);
+    }
+
+    if (texCoords)
+    {
+        floatArr tileTexCoords(new float[n * 2]);
+        for (size_t i = 0; i < n; i++)
+        {
+            std::copy_n(&texCoords[usedVertices[i] * 2], 2, &tileTexCoords[i * 2]);
+        }
+        tile->setTextureCoordinates(tileTexCoords);
+    }
+
+    if (materials)
+    {
+        indexArray tileMaterials(new unsigned int[faceIds.size()]);
+        for (size_t i = 0; i < faceIds.size(); i++)
+        {
+            tileMaterials[i] = materials[faceIds[i]];
+        }
+        tile->setFaceMaterialIndices(tileMaterials);
+
+        std::vector<Material> meshMaterials = mesh->getMaterials();
+        std::vector<Texture> meshTextures = mesh->getTextures();
+        tile->setMaterials(meshMaterials);
+        tile->setTextures(meshTextures);
+    }
+
+    return tile;
+}
+
+std::map<TileIndex, MeshTile> tileMesh(MeshBufferPtr mesh, const MeshTilerOptions& options)
+{
+    if (options.tileSize <= 0)
+    {
+        throw std::invalid_argument("[MeshTiler] Tile size has to be positive");
+    }
+
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+
+    auto cell = [&options](float value)
+    {
+        return static_cast<int>(std::floor(value / options.tileSize));
+    };
+
+    std::map<TileIndex, std::vector<size_t>> tileFaces;
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        float minX = std::numeric_limits<float>::max();
+        float minY = std::numeric_limits<float>::max();
+        float maxX = std::numeric_limits<float>::lowest();
+        float maxY = std::numeric_limits<float>::lowest();
+        float cx = 0;
+        float cy = 0;
+        for (size_t j = 0; j < 3; j++)
+        {
+            const unsigned int v = faces[f * 3 + j];
+            const float x = vertices[v * 3];
+            const float y = vertices[v * 3 + 1];
+            minX = std::min(minX, x);
+            minY = std::min(minY, y);
+            maxX = std::max(maxX, x);
+            maxY = std::max(maxY, y);
+            cx += x / 3;
+            cy += y / 3;
+        }
+
+        if (options.overlap <= 0)
+        {
+            tileFaces[TileIndex(cell(cx), cell(cy))].push_back(f);
+            continue;
+        }
+
+        // All tiles whose extended area intersects the face's bounding box
+        for (int x = cell(minX - options.overlap); x <= cell(maxX + options.overlap); x++)
+        {
+            for (int y = cell(minY - options.overlap); y <= cell(maxY + options.overlap); y++)
+            {
+                tileFaces[TileIndex(x, y)].push_back(f);
+            }
+        }
+    }
+
+    std::map<TileIndex, MeshTile> tiles;
+    for (auto& [index, faceIds] : tileFaces)
+    {
+        MeshTile tile;
+        tile.mesh = extractFaces(mesh, faceIds);
+
+        floatArr tileVertices = tile.mesh->getVertices();
+        for (size_t i = 0; i < tile.mesh->numVertices(); i++)
+        {
+            tile.boundingBox.expand(BaseVector<float>(tileVertices[i * 3], tileVertices[i * 3 + 1], tileVertices[i * 3 + 2]));
+        }
+        tiles.emplace(index, tile);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[MeshTiler] Split mesh with " << numFaces
+        << " faces into " << tiles.size() << " tiles" << lvr2::endl;
+
+    return tiles;
+}
+
+void writeMeshTiles(MeshBufferPtr mesh, const MeshTilerOptions& options, const boost::filesystem::path& outputDir)
+{
+    boost::filesystem::create_directories(outputDir);
+
+    std::map<TileIndex, MeshTile> tiles = tileMesh(mesh, options);
+
+    auto writeVector = [](std::ostream& os, const BaseVector<float>& v)
+    {
+        os << "[" << v.x << ", " << v.y << ", " << v.z << "]";
+    };
+
+    std::stringstream index;
+    index << std::setprecision(9);
+    index << "{\n";
+    index << "  \"tile_size\": " << options.tileSize << ",\n";
+    index << "  \"overlap\": " << options.overlap << ",\n";
+    index << "  \"tiles\": [";
+
+    bool first = true;
+    for (auto& [tileIndex, tile] : tiles)
+    {
+        std::string filename = "tile_" + std::to_string(tileIndex.first) + "_"
+            + std::to_string(tileIndex.second) + options.extension;
+
+        ModelPtr model = std::make_shared<Model>(tile.mesh);
+        ModelFactory::saveModel(model, (outputDir / filename).string());
+
+        index << (first ? "\n" : ",\n");
+        first = false;
+        index << "    {\"x\": " << tileIndex.first
+              << ", \"y\": " << tileIndex.second
+              << ", \"file\": \"" << filename << "\""
+              << ", \"vertices\": " << tile.mesh->numVertices()
+              << ", \"faces\": " << tile.mesh->numFaces()
+              << ", \"min\": ";
+        writeVector(index, tile.boundingBox.getMin());
+        index << ", \"max\": ";
+        writeVector(index, tile.boundingBox.getMax());
+        index << "}";
+    }
+    index << (tiles.empty() ? "]\n" : "\n  ]\n");
+    index << "}\n";
+
+    std::ofstream out((outputDir / "tiles.json").string());
+    out << index.str();
+
+    lvr2::logout::get() << lvr2::info << "[MeshTiler] Wrote " << tiles.size() << " tiles to " << outputDir.string() << lvr2::endl;
+}
+
+} // namespace lvr2
@@ -0,0 +1,269 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * MeshVoxelization.cpp
+ */
+
+#include "lvr2/algorithm/MeshVoxelization.hpp"
+#include "lvr2/reconstruction/FastReconstructionTables.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <queue>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Closest point on the triangle abc to p (Ericson, Real-Time Collision Detection, 5.1.5)
+Vector3f closestPointOnTriangle(const Vector3f& p, const Vector3f& a, const Vector3f& b, const Vector3f& c)
+{
+    const Vector3f ab = b - a;
+    const Vector3f ac = c - a;
+    const Vector3f ap = p - a;
+    const float d1 = ab.dot(ap);
+    const float d2 = ac.dot(ap);
+    if (d1 <= 0 && d2 <= 0)
+    {
+        return a;
+    }
+
+    const Vector3f bp = p - b;
+    const float d3 = ab.dot(bp);
+    const float d4 = ac.dot(bp);
+    if (d3 >= 0 && d4 <= d3)
+    {
+        return b;
+    }
+
+    const float vc = d1 * d4 - d3 * d2;
+    if (vc <= 0 && d1 >= 0 && d3 <= 0)
+    {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    const Vector3f cp = p - c;
+    const float d5 = ab.dot(cp);
+    const float d6 = ac.dot(cp);
+    if (d6 >= 0 && d5 <= d6)
+    {
+        return c;
+    }
+
+    const float vb = d5 * d2 - d1 * d6;
+    if (vb <= 0 && d2 >= 0 && d6 <= 0)
+    {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    const float va = d3 * d6 - d5 * d4;
+    if (va <= 0 && (d4 - d3) >= 0 && (d5 - d6) >= 0)
+    {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    const float denom = 1.0f / (va + vb + vc);
+    return a + ab * (vb * denom) + ac * (vc * denom);
+}
+
+struct CornerDistance
+{
+    float distance = std::numeric_limits<float>::max();
+
+    /// Cosine between the face normal and the direction from the closest point to the corner
+    float alignment = 0;
+};
+
+} // anonymous namespace
+
+std::shared_ptr<DistanceField> meshToDistanceField(MeshBufferPtr mesh, const MeshDistanceFieldOptions& options)
+{
+    const float vs = options.voxelSize;
+    const size_t numFaces = mesh->numFaces();
+    if (numFaces == 0 || vs <= 0)
+    {
+        return std::make_shared<DistanceField>(vs, std::unordered_map<Vector3i, DistanceField::CellDistances>());
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](size_t i)
+    {
+        return Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    // Distance of all grid corners within the band around each face. If several faces
+    // are equally close, e.g. at edges and vertices, the one whose normal is most aligned
+    // with the direction to the corner determines the sign.
+    const float band = std::max(1, options.bandWidth) * vs;
+    const float tolerance = 1e-5f * vs;
+    std::unordered_map<Vector3i, CornerDistance> corners;
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const Vector3f a = vertex(faces[3 * f]);
+        const Vector3f b = vertex(faces[3 * f + 1]);
+        const Vector3f c = vertex(faces[3 * f + 2]);
+        const Vector3f normal = (b - a).cross(c - a).normalized();
+        if (!normal.allFinite())
+        {
+            continue;
+        }
+
+        const Vector3f min = a.cwiseMin(b).cwiseMin(c) - Vector3f::Constant(band);
+        const Vector3f max = a.cwiseMax(b).cwiseMax(c) + Vector3f::Constant(band);
+        const Vector3i first = (min / vs).array().ceil().cast<int>().matrix();
+        const Vector3i last = (max / vs).array().floor().cast<int>().matrix();
+        for (int x = first.x(); x <= last.x(); x++)
+        {
+            for (int y = first.y(); y <= last.y(); y++)
+            {
+                for (int z = first.z(); z <= last.z(); z++)
+                {
+                    const Vector3f p = Vector3f(x, y, z) * vs;
+                    const Vector3f offset = p - closestPointOnTriangle(p, a, b, c);
+                    const float distance = offset.norm();
+                    if (distance > band)
+                    {
+                        continue;
+                    }
+
+                    const float alignment = distance > 0 ? normal.dot(offset) / distance : 0;
+                    CornerDistance& corner = corners[Vector3i(x, y, z)];
+                    if (distance < corner.distance - tolerance
+                        || (distance <= corner.distance + tolerance && std::abs(alignment) > std::abs(corner.alignment)))
+                    {
+                        corner.distance = distance;
+                        corner.alignment = alignment;
+                    }
+                }
+            }
+        }
+    }
+
+    // Keep all cells whose corners are all within the band
+    std::unordered_map<Vector3i, DistanceField::CellDistances> cells;
+    for (const auto& corner : corners)
+    {
+        // Each cell is visited from its lower corner
+        const Vector3i& index = corner.first;
+        DistanceField::CellDistances distances;
+        bool complete = true;
+        for (int i = 0; i < 8 && complete; i++)
+        {
+            const Vector3i offset(
+                box_creation_table[i][0] > 0 ? 1 : 0,
+                box_creation_table[i][1] > 0 ? 1 : 0,
+                box_creation_table[i][2] > 0 ? 1 : 0);
+            auto it = corners.find(index + offset);
+            if (it == corners.end())
+            {
+                complete = false;
+                break;
+            }
+            distances[i] = it->second.alignment < 0 ? -it->second.distance : it->second.distance;
+        }
+        if (complete)
+        {
+            cells.emplace(index, distances);
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[MeshVoxelization] Created distance field with " << cells.size()
+        << " cells" << lvr2::endl;
+
+    return std::make_shared<DistanceField>(vs, std::move(cells));
+}
+
+OccupancyGrid solidOccupancyGrid(MeshBufferPtr mesh, double resolution)
+{
+    OccupancyGrid grid = occupancyGrid(mesh, resolution);
+    if (grid.cells.empty())
+    {
+        return grid;
+    }
+
+    // Flood fill the outside from all empty voxels on the border of the grid
+    enum : unsigned char { Empty = 0, Occupied = 1, Outside = 2 };
+    const Vector3i& size = grid.size;
+    std::queue<Vector3i> queue;
+    auto visit = [&](int x, int y, int z)
+    {
+        if (x < 0 || y < 0 || z < 0 || x >= size.x() || y >= size.y() || z >= size.z())
+        {
+            return;
+        }
+        unsigned char& cell = grid.cells[grid.index(x, y, z)];
+        if (cell == Empty)
+        {
+            cell = Outside;
+            queue.push(Vector3i(x, y, z));
+        }
+    };
+    for (int z = 0; z < size.z(); z++)
+    {
+        for (int y = 0; y < size.y(); y++)
+        {
+            for (int x = 0; x < size.x(); x++)
+            {
+                if (x == 0 || y == 0 || z == 0 || x == size.x() - 1 || y == size.y() - 1 || z == size.z() - 1)
+                {
+                    visit(x, y, z);
+                }
+            }
+        }
+    }
+    while (!queue.empty())
+    {
+        const Vector3i v = queue.front();
+        queue.pop();
+        visit(v.x() + 1, v.y(), v.z());
+        visit(v.x() - 1, v.y(), v.z());
+        visit(v.x(), v.y() + 1, v.z());
+        visit(v.x(), v.y() - 1, v.z());
+        visit(v.x(), v.y(), v.z() + 1);
+        visit(v.x(), v.y(), v.z() - 1);
+    }
+
+    for (unsigned char& cell : grid.cells)
+    {
+        cell = cell == Outside ? 0 : 1;
+    }
+
+    lvr2::logout::get() << lvr2::info << "[MeshVoxelization] " << grid.numOccupied() << " of " << grid.cells.size()
+        << " voxels inside or on the surface" << lvr2::endl;
+    return grid;
+}
+
+} // namespace lvr2
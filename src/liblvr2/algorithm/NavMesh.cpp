@@ -0,0 +1,411 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * NavMesh.cpp
+ */
+
+#include "lvr2/algorithm/NavMesh.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Util.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <iomanip>
+#include <limits>
+#include <queue>
+#include <set>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Link directions of a node: +x, -x, +y, -y
+constexpr int dirX[4] = {1, -1, 0, 0};
+constexpr int dirY[4] = {0, 0, 1, -1};
+
+struct NavNode
+{
+    int x;
+    int y;
+    float z;
+    int links[4] = {-1, -1, -1, -1};
+    bool walkable = true;
+    float distance = std::numeric_limits<float>::max();
+    int polygon = -1;
+};
+
+struct HeightSample
+{
+    float z;
+    bool floor;
+
+    bool operator<(const HeightSample& other) const { return z < other.z; }
+};
+
+} // anonymous namespace
+
+NavMesh buildNavMesh(MeshBufferPtr mesh, const NavMeshOptions& options)
+{
+    NavMesh navMesh;
+    const size_t numVertices = mesh->numVertices();
+    const size_t numFaces = mesh->numFaces();
+    if (numVertices == 0 || numFaces == 0 || options.cellSize <= 0)
+    {
+        return navMesh;
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](size_t i)
+    {
+        return Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    Vector3f min = vertex(0);
+    Vector3f max = min;
+    for (size_t i = 1; i < numVertices; i++)
+    {
+        min = min.cwiseMin(vertex(i));
+        max = max.cwiseMax(vertex(i));
+    }
+
+    const float cs = options.cellSize;
+    const int width = static_cast<int>(std::floor((max.x() - min.x()) / cs)) + 1;
+    const int height = static_cast<int>(std::floor((max.y() - min.y()) / cs)) + 1;
+    const float minNormalZ = std::cos(options.maxSlope * M_PI / 180.0);
+
+    // Rasterize all faces by sampling them densely. Walkable faces provide floor
+    // heights, all faces are obstacles for the floors below them.
+    std::vector<std::vector<HeightSample>> samples(static_cast<size_t>(width) * height);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const Vector3f a = vertex(faces[3 * f]);
+        const Vector3f b = vertex(faces[3 * f + 1]);
+        const Vector3f c = vertex(faces[3 * f + 2]);
+        const Vector3f normal = (b - a).cross(c - a).normalized();
+        const bool floor = normal.z() >= minNormalZ;
+
+        const float longest = std::max({(b - a).norm(), (c - a).norm(), (c - b).norm()});
+        const int steps = std::max(1, static_cast<int>(std::ceil(2 * longest / cs)));
+        for (int i = 0; i <= steps; i++)
+        {
+            for (int j = 0; i + j <= steps; j++)
+            {
+                const Vector3f p = a + (b - a) * (float(i) / steps) + (c - a) * (float(j) / steps);
+                const int x = std::min(width - 1, std::max(0, static_cast<int>((p.x() - min.x()) / cs)));
+                const int y = std::min(height - 1, std::max(0, static_cast<int>((p.y() - min.y()) / cs)));
+                samples[static_cast<size_t>(y) * width + x].push_back({p.z(), floor});
+            }
+        }
+    }
+
+    // Create a node for each floor level of a cell with enough clearance above it
+    std::vector<NavNode> nodes;
+    std::vector<std::vector<int>> cellNodes(samples.size());
+    for (int y = 0; y < height; y++)
+    {
+        for (int x = 0; x < width; x++)
+        {
+            const size_t cell = static_cast<size_t>(y) * width + x;
+            std::vector<HeightSample>& cellSamples = samples[cell];
+            std::sort(cellSamples.begin(), cellSamples.end());
+
+            std::vector<float> levels;
+            for (const HeightSample& s : cellSamples)
+            {
+                if (!s.floor)
+                {
+                    continue;
+                }
+                if (!levels.empty() && s.z - levels.back() <= options.maxStepHeight)
+                {
+                    levels.back() = s.z;
+                }
+                else
+                {
+                    levels.push_back(s.z);
+                }
+            }
+
+            for (float level : levels)
+            {
+                auto it = std::upper_bound(
+                    cellSamples.begin(), cellSamples.end(), HeightSample{level + options.maxStepHeight, false});
+                if (it != cellSamples.end() && it->z < level + options.agentHeight)
+                {
+                    continue;
+                }
+
+                NavNode node;
+                node.x = x;
+                node.y = y;
+                node.z = level;
+                cellNodes[cell].push_back(nodes.size());
+                nodes.push_back(node);
+            }
+            cellSamples.clear();
+            cellSamples.shrink_to_fit();
+        }
+    }
+
+    // Link each node to the closest level of the adjacent cells that can be stepped on
+    for (NavNode& node : nodes)
+    {
+        for (int d = 0; d < 4; d++)
+        {
+            const int x = node.x + dirX[d];
+            const int y = node.y + dirY[d];
+            if (x < 0 || y < 0 || x >= width || y >= height)
+            {
+                continue;
+            }
+            float best = options.maxStepHeight;
+            for (int n : cellNodes[static_cast<size_t>(y) * width + x])
+            {
+                const float dz = std::abs(nodes[n].z - node.z);
+                if (dz <= best)
+                {
+                    best = dz;
+                    node.links[d] = n;
+                }
+            }
+        }
+    }
+
+    // Erode the walkable area by the agent radius. Distances to the nearest edge
+    // are propagated from the boundary nodes with a chamfer metric.
+    using QueueEntry = std::pair<float, int>;
+    std::priority_queue<QueueEntry, std::vector<QueueEntry>, std::greater<QueueEntry>> queue;
+    for (size_t i = 0; i < nodes.size(); i++)
+    {
+        if (std::find(std::begin(nodes[i].links), std::end(nodes[i].links), -1) != std::end(nodes[i].links))
+        {
+            nodes[i].distance = 0.5;
+            queue.emplace(0.5f, i);
+        }
+    }
+    while (!queue.empty())
+    {
+        const QueueEntry entry = queue.top();
+        queue.pop();
+        const NavNode& node = nodes[entry.second];
+        if (entry.first > node.distance)
+        {
+            continue;
+        }
+
+        auto relax = [&](int n, float weight)
+        {
+            if (n >= 0 && entry.first + weight < nodes[n].distance)
+            {
+                nodes[n].distance = entry.first + weight;
+                queue.emplace(nodes[n].distance, n);
+            }
+        };
+        for (int d = 0; d < 4; d++)
+        {
+            relax(node.links[d], 1.0f);
+        }
+        for (int dx : {0, 1})
+        {
+            for (int dy : {2, 3})
+            {
+                const int n = node.links[dx];
+                relax(n >= 0 ? nodes[n].links[dy] : -1, M_SQRT2);
+            }
+        }
+    }
+    for (NavNode& node : nodes)
+    {
+        node.walkable = node.distance * cs >= options.agentRadius;
+    }
+
+    auto available = [&](int n)
+    {
+        return n >= 0 && nodes[n].walkable && nodes[n].polygon < 0;
+    };
+
+    // Merge the walkable nodes into rectangles, growing in x first and then in y
+    const int maxCells = std::max(1, options.maxPolygonCells);
+    std::vector<std::vector<int>> polygonCorners;
+    for (const std::vector<int>& cell : cellNodes)
+    {
+        for (int start : cell)
+        {
+            if (!available(start))
+            {
+                continue;
+            }
+
+            const int id = polygonCorners.size();
+            std::vector<int> row = {start};
+            while (static_cast<int>(row.size()) < maxCells && available(nodes[row.back()].links[0]))
+            {
+                row.push_back(nodes[row.back()].links[0]);
+            }
+            for (int n : row)
+            {
+                nodes[n].polygon = id;
+            }
+
+            const int lowerRight = row.back();
+            int rows = 1;
+            while (rows < maxCells)
+            {
+                std::vector<int> next;
+                for (int n : row)
+                {
+                    const int above = nodes[n].links[2];
+                    if (!available(above) || (!next.empty() && nodes[next.back()].links[0] != above))
+                    {
+                        break;
+                    }
+                    next.push_back(above);
+                }
+                if (next.size() != row.size())
+                {
+                    break;
+                }
+                for (int n : next)
+                {
+                    nodes[n].polygon = id;
+                }
+                row.swap(next);
+                rows++;
+            }
+
+            polygonCorners.push_back({start, lowerRight, row.back(), row.front()});
+        }
+    }
+
+    navMesh.polygons.resize(polygonCorners.size());
+    std::vector<std::set<size_t>> neighbors(polygonCorners.size());
+    for (const NavNode& node : nodes)
+    {
+        if (node.polygon < 0)
+        {
+            continue;
+        }
+        for (int link : node.links)
+        {
+            if (link >= 0 && nodes[link].polygon >= 0 && nodes[link].polygon != node.polygon)
+            {
+                neighbors[node.polygon].insert(nodes[link].polygon);
+            }
+        }
+    }
+
+    for (size_t p = 0; p < polygonCorners.size(); p++)
+    {
+        const NavNode& lowerLeft = nodes[polygonCorners[p][0]];
+        const NavNode& lowerRight = nodes[polygonCorners[p][1]];
+        const NavNode& upperRight = nodes[polygonCorners[p][2]];
+        const NavNode& upperLeft = nodes[polygonCorners[p][3]];
+
+        const float x0 = min.x() + lowerLeft.x * cs;
+        const float x1 = min.x() + (lowerRight.x + 1) * cs;
+        const float y0 = min.y() + lowerLeft.y * cs;
+        const float y1 = min.y() + (upperLeft.y + 1) * cs;
+
+        NavPolygon& polygon = navMesh.polygons[p];
+        polygon.vertices = {
+            Vector3f(x0, y0, lowerLeft.z),
+            Vector3f(x1, y0, lowerRight.z),
+            Vector3f(x1, y1, upperRight.z),
+            Vector3f(x0, y1, upperLeft.z)
+        };
+        polygon.neighbors.assign(neighbors[p].begin(), neighbors[p].end());
+        polygon.area = (x1 - x0) * (y1 - y0);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[NavMesh] Created " << navMesh.polygons.size()
+        << " polygons from " << nodes.size() << " floor cells" << lvr2::endl;
+
+    return navMesh;
+}
+
+MeshBufferPtr navMeshToMeshBuffer(const NavMesh& navMesh)
+{
+    std::vector<float> vertices;
+    std::vector<unsigned int> faces;
+    for (const NavPolygon& polygon : navMesh.polygons)
+    {
+        const unsigned int first = vertices.size() / 3;
+        for (const Vector3f& v : polygon.vertices)
+        {
+            vertices.push_back(v.x());
+            vertices.push_back(v.y());
+            vertices.push_back(v.z());
+        }
+        for (size_t i = 1; i + 1 < polygon.vertices.size(); i++)
+        {
+            faces.push_back(first);
+            faces.push_back(first + i);
+            faces.push_back(first + i + 1);
+        }
+    }
+
+    MeshBufferPtr buffer(new MeshBuffer);
+    buffer->setVertices(Util::convert_vector_to_shared_array(vertices), vertices.size() / 3);
+    buffer->setFaceIndices(Util::convert_vector_to_shared_array(faces), faces.size() / 3);
+    return buffer;
+}
+
+void saveNavMesh(const NavMesh& navMesh, const boost::filesystem::path& file)
+{
+    boost::filesystem::ofstream out(file);
+    out << std::setprecision(9);
+    out << "{\n";
+    out << "  \"polygons\": [";
+    for (size_t p = 0; p < navMesh.polygons.size(); p++)
+    {
+        const NavPolygon& polygon = navMesh.polygons[p];
+        out << (p ? ",\n" : "\n") << "    {\"id\": " << p << ", \"area\": " << polygon.area << ", \"vertices\": [";
+        for (size_t i = 0; i < polygon.vertices.size(); i++)
+        {
+            const Vector3f& v = polygon.vertices[i];
+            out << (i ? ", " : "") << "[" << v.x() << ", " << v.y() << ", " << v.z() << "]";
+        }
+        out << "], \"neighbors\": [";
+        for (size_t i = 0; i < polygon.neighbors.size(); i++)
+        {
+            out << (i ? ", " : "") << polygon.neighbors[i];
+        }
+        out << "]}";
+    }
+    out << (navMesh.polygons.empty() ? "]\n" : "\n  ]\n");
+    out << "}\n";
+}
+
+} // namespace lvr2
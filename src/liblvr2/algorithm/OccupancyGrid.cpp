@@ -0,0 +1,196 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * OccupancyGrid.cpp
+ */
+
+#include "lvr2/algorithm/OccupancyGrid.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Creates an empty grid that covers the given bounding box
+OccupancyGrid emptyGrid(const Vector3d& min, const Vector3d& max, double resolution)
+{
+    OccupancyGrid grid;
+    grid.origin = min;
+    grid.resolution = resolution;
+    for (int a = 0; a < 3; a++)
+    {
+        grid.size[a] = static_cast<int>(std::floor((max[a] - min[a]) / resolution)) + 1;
+    }
+    grid.cells.assign(static_cast<size_t>(grid.size.x()) * grid.size.y() * grid.size.z(), 0);
+    return grid;
+}
+
+void mark(OccupancyGrid& grid, const Vector3d& p)
+{
+    int v[3];
+    for (int a = 0; a < 3; a++)
+    {
+        v[a] = std::min(grid.size[a] - 1, std::max(0, static_cast<int>((p[a] - grid.origin[a]) / grid.resolution)));
+    }
+    grid.cells[grid.index(v[0], v[1], v[2])] = 1;
+}
+
+} // anonymous namespace
+
+size_t OccupancyGrid::numOccupied() const
+{
+    return std::count_if(cells.begin(), cells.end(), [](unsigned char c) { return c != 0; });
+}
+
+OccupancyGrid occupancyGrid(PointBufferPtr points, double resolution)
+{
+    const size_t n = points->numPoints();
+    floatArr coords = points->getPointArray();
+    if (n == 0 || resolution <= 0)
+    {
+        return OccupancyGrid();
+    }
+
+    auto point = [&](size_t i)
+    {
+        return Vector3d(coords[3 * i], coords[3 * i + 1], coords[3 * i + 2]);
+    };
+    Vector3d min = point(0);
+    Vector3d max = min;
+    for (size_t i = 1; i < n; i++)
+    {
+        min = min.cwiseMin(point(i));
+        max = max.cwiseMax(point(i));
+    }
+
+    OccupancyGrid grid = emptyGrid(min, max, resolution);
+    for (size_t i = 0; i < n; i++)
+    {
+        mark(grid, point(i));
+    }
+
+    lvr2::logout::get() << lvr2::info << "[OccupancyGrid] " << grid.numOccupied() << " of " << grid.cells.size()
+        << " voxels occupied" << lvr2::endl;
+    return grid;
+}
+
+OccupancyGrid occupancyGrid(MeshBufferPtr mesh, double resolution)
+{
+    const size_t numVertices = mesh->numVertices();
+    const size_t numFaces = mesh->numFaces();
+    if (numVertices == 0 || resolution <= 0)
+    {
+        return OccupancyGrid();
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](size_t i)
+    {
+        return Vector3d(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+    Vector3d min = vertex(0);
+    Vector3d max = min;
+    for (size_t i = 1; i < numVertices; i++)
+    {
+        min = min.cwiseMin(vertex(i));
+        max = max.cwiseMax(vertex(i));
+    }
+
+    // Sample each face with half the voxel size, so that no intersected voxel is missed
+    OccupancyGrid grid = emptyGrid(min, max, resolution);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const Vector3d a = vertex(faces[3 * f]);
+        const Vector3d b = vertex(faces[3 * f + 1]);
+        const Vector3d c = vertex(faces[3 * f + 2]);
+        const double longest = std::max({(b - a).norm(), (c - a).norm(), (c - b).norm()});
+        const int steps = std::max(1, static_cast<int>(std::ceil(2 * longest / resolution)));
+        for (int i = 0; i <= steps; i++)
+        {
+            for (int j = 0; i + j <= steps; j++)
+            {
+                mark(grid, a + (b - a) * (double(i) / steps) + (c - a) * (double(j) / steps));
+            }
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[OccupancyGrid] " << grid.numOccupied() << " of " << grid.cells.size()
+        << " voxels occupied" << lvr2::endl;
+    return grid;
+}
+
+cv::Mat projectOccupancyGrid(const OccupancyGrid& grid, const OccupancyMapOptions& options)
+{
+    cv::Mat map(grid.size.y(), grid.size.x(), CV_8UC1, cv::Scalar(options.unknownValue));
+    for (int y = 0; y < grid.size.y(); y++)
+    {
+        const int row = grid.size.y() - 1 - y;
+        for (int x = 0; x < grid.size.x(); x++)
+        {
+            bool floor = false;
+            bool obstacle = false;
+            for (int z = 0; z < grid.size.z() && !obstacle; z++)
+            {
+                if (!grid.occupied(x, y, z))
+                {
+                    continue;
+                }
+                // Use the voxel center to classify the voxel
+                const double height = grid.origin.z() + (z + 0.5) * grid.resolution;
+                if (height < options.minHeight)
+                {
+                    floor = true;
+                }
+                else if (height <= options.maxHeight)
+                {
+                    obstacle = true;
+                }
+            }
+
+            if (obstacle)
+            {
+                map.at<unsigned char>(row, x) = options.occupiedValue;
+            }
+            else if (floor)
+            {
+                map.at<unsigned char>(row, x) = options.freeValue;
+            }
+        }
+    }
+    return map;
+}
+
+} // namespace lvr2
@@ -0,0 +1,263 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * PanoramaColorization.cpp
+ */
+
+#include "lvr2/algorithm/PanoramaColorization.hpp"
+#include "lvr2/algorithm/raycasting/BVHRaycaster.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <opencv2/imgproc.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <numeric>
+
+namespace lvr2
+{
+
+namespace
+{
+
+struct PreparedPanorama
+{
+    /// 8 bit BGR image
+    cv::Mat image;
+
+    EquirectangularModel model;
+
+    Eigen::Matrix3f rotation;
+    Vector3f translation;
+
+    /// Center of the panorama in world coordinates
+    Vector3f position;
+};
+
+std::vector<PreparedPanorama> prepare(const std::vector<Panorama>& panoramas)
+{
+    std::vector<PreparedPanorama> prepared;
+    for (const Panorama& panorama : panoramas)
+    {
+        PreparedPanorama p;
+        p.image = panorama.image;
+        if (p.image.depth() != CV_8U)
+        {
+            p.image.convertTo(p.image, CV_8U, p.image.depth() == CV_16U ? 1.0 / 257.0 : 1.0);
+        }
+        if (p.image.channels() == 1)
+        {
+            cv::cvtColor(p.image, p.image, cv::COLOR_GRAY2BGR);
+        }
+        else if (p.image.channels() == 4)
+        {
+            cv::cvtColor(p.image, p.image, cv::COLOR_BGRA2BGR);
+        }
+        if (p.image.empty() || p.image.channels() != 3)
+        {
+            lvr2::logout::get() << lvr2::warning << "[PanoramaColorization] Skipping panorama with unsupported image format"
+                << lvr2::endl;
+            continue;
+        }
+
+        // Project into an image of the actual size
+        p.model = panorama.model;
+        p.model.width = p.image.cols;
+        p.model.height = p.image.rows;
+
+        const Transformd inverse = panorama.pose.inverse();
+        p.rotation = inverse.block<3, 3>(0, 0).cast<float>();
+        p.translation = inverse.block<3, 1>(0, 3).cast<float>();
+        p.position = panorama.pose.block<3, 1>(0, 3).cast<float>();
+        prepared.push_back(p);
+    }
+    return prepared;
+}
+
+/// Bilinear interpolation with pixel centers at +0.5, wrapping around horizontally
+void sample(const cv::Mat& image, const Eigen::Vector2f& pixel, unsigned char* rgb)
+{
+    const float x = pixel.x() - 0.5f;
+    const float y = std::min(std::max(pixel.y() - 0.5f, 0.0f), image.rows - 1.0f);
+    const int x0 = static_cast<int>(std::floor(x));
+    const int y0 = static_cast<int>(std::floor(y));
+    const float tx = x - x0;
+    const float ty = y - y0;
+
+    float color[3] = {0, 0, 0};
+    for (int dy = 0; dy <= 1; dy++)
+    {
+        const int row = std::min(y0 + dy, image.rows - 1);
+        for (int dx = 0; dx <= 1; dx++)
+        {
+            const int col = ((x0 + dx) % image.cols + image.cols) % image.cols;
+            const float weight = (dx ? tx : 1 - tx) * (dy ? ty : 1 - ty);
+            const cv::Vec3b& bgr = image.at<cv::Vec3b>(row, col);
+            for (int c = 0; c < 3; c++)
+            {
+                color[c] += weight * bgr[2 - c];
+            }
+        }
+    }
+    for (int c = 0; c < 3; c++)
+    {
+        rgb[c] = static_cast<unsigned char>(std::min(255.0f, std::round(color[c])));
+    }
+}
+
+/**
+ * Colors each position from the closest panorama for which visible(index, panorama)
+ * returns true. Returns the number of colored positions.
+ */
+template<typename VisibleT>
+size_t colorize(
+    size_t n,
+    const floatArr& coords,
+    const std::vector<PreparedPanorama>& panoramas,
+    const PanoramaColorizationOptions& options,
+    VisibleT visible,
+    ucharArr colors)
+{
+    size_t colored = 0;
+    std::vector<size_t> order(panoramas.size());
+    std::vector<float> distances(panoramas.size());
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3f p(coords[3 * i], coords[3 * i + 1], coords[3 * i + 2]);
+        for (size_t k = 0; k < panoramas.size(); k++)
+        {
+            distances[k] = (p - panoramas[k].position).norm();
+        }
+        std::iota(order.begin(), order.end(), 0);
+        std::sort(order.begin(), order.end(), [&](size_t a, size_t b) { return distances[a] < distances[b]; });
+
+        std::fill_n(colors.get() + 3 * i, 3, 0);
+        for (size_t k : order)
+        {
+            if (options.maxDistance > 0 && distances[k] > options.maxDistance)
+            {
+                break;
+            }
+            if (!visible(i, k))
+            {
+                continue;
+            }
+
+            const PreparedPanorama& panorama = panoramas[k];
+            const Vector3f local = panorama.rotation * p + panorama.translation;
+            sample(panorama.image, panorama.model.projectPoint(local), colors.get() + 3 * i);
+            colored++;
+            break;
+        }
+    }
+    return colored;
+}
+
+} // anonymous namespace
+
+size_t colorizeFromPanoramas(
+    PointBufferPtr points,
+    const std::vector<Panorama>& panoramas,
+    const PanoramaColorizationOptions& options)
+{
+    if (points->hasColors() && !options.overwrite)
+    {
+        lvr2::logout::get() << lvr2::info << "[PanoramaColorization] Point cloud already has colors" << lvr2::endl;
+        return 0;
+    }
+
+    const size_t n = points->numPoints();
+    std::vector<PreparedPanorama> prepared = prepare(panoramas);
+    ucharArr colors(new unsigned char[3 * n]);
+    const size_t colored = colorize(n, points->getPointArray(), prepared, options,
+        [](size_t, size_t) { return true; }, colors);
+    points->setColorArray(colors, n);
+
+    lvr2::logout::get() << lvr2::info << "[PanoramaColorization] Colored " << colored << " of "
+        << n << " points" << lvr2::endl;
+    return colored;
+}
+
+size_t colorizeFromPanoramas(
+    MeshBufferPtr mesh,
+    const std::vector<Panorama>& panoramas,
+    const PanoramaColorizationOptions& options)
+{
+    if (mesh->hasVertexColors() && !options.overwrite)
+    {
+        lvr2::logout::get() << lvr2::info << "[PanoramaColorization] Mesh already has vertex colors" << lvr2::endl;
+        return 0;
+    }
+
+    const size_t n = mesh->numVertices();
+    floatArr vertices = mesh->getVertices();
+    std::vector<PreparedPanorama> prepared = prepare(panoramas);
+
+    // Visibility of all vertices from each panorama
+    std::vector<std::vector<uint8_t>> visibility;
+    if (options.occlusion && mesh->numFaces() > 0 && !prepared.empty())
+    {
+        BVHRaycaster<DistInt> raycaster(mesh);
+        std::vector<Vector3f> directions(n);
+        std::vector<float> distances(n);
+        for (const PreparedPanorama& panorama : prepared)
+        {
+            for (size_t i = 0; i < n; i++)
+            {
+                const Vector3f offset = Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]) - panorama.position;
+                distances[i] = offset.norm();
+                directions[i] = offset / std::max(distances[i], std::numeric_limits<float>::min());
+            }
+
+            std::vector<DistInt> intersections;
+            std::vector<uint8_t> hits;
+            raycaster.castRays(panorama.position, directions, intersections, hits);
+
+            std::vector<uint8_t> visible(n);
+            for (size_t i = 0; i < n; i++)
+            {
+                visible[i] = !hits[i] || intersections[i].dist >= distances[i] - options.occlusionTolerance;
+            }
+            visibility.push_back(std::move(visible));
+        }
+    }
+
+    ucharArr colors(new unsigned char[3 * n]);
+    const size_t colored = colorize(n, vertices, prepared, options,
+        [&](size_t i, size_t k) { return visibility.empty() || visibility[k][i]; }, colors);
+    mesh->setVertexColors(colors, 3);
+
+    lvr2::logout::get() << lvr2::info << "[PanoramaColorization] Colored " << colored << " of "
+        << n << " vertices" << lvr2::endl;
+    return colored;
+}
+
+} // namespace lvr2
@@ -0,0 +1,487 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PipeReconstruction.cpp
+ */
+
+#include "lvr2/algorithm/PipeReconstruction.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <numeric>
+#include <random>
+
+namespace lvr2
+{
+
+namespace
+{
+
+struct Cylinder
+{
+    Vector3d point;
+    Vector3d axis;
+    double radius;
+};
+
+/// Cylinder through two oriented points. Its axis is perpendicular to both normals.
+bool cylinderFromSample(const Vector3d& p1, const Vector3d& n1, const Vector3d& p2, const Vector3d& n2, Cylinder& out)
+{
+    Vector3d axis = n1.cross(n2);
+    if (axis.norm() < 1e-6)
+    {
+        return false;
+    }
+    axis.normalize();
+    const Vector3d u = axis.unitOrthogonal();
+    const Vector3d v = axis.cross(u);
+
+    // Intersect the normal lines in the plane perpendicular to the axis
+    const Eigen::Vector2d q1(p1.dot(u), p1.dot(v));
+    const Eigen::Vector2d q2(p2.dot(u), p2.dot(v));
+    const Eigen::Vector2d m1(n1.dot(u), n1.dot(v));
+    const Eigen::Vector2d m2(n2.dot(u), n2.dot(v));
+    Eigen::Matrix2d A;
+    A << m1.x(), -m2.x(), m1.y(), -m2.y();
+    if (std::abs(A.determinant()) < 1e-9)
+    {
+        return false;
+    }
+    const Eigen::Vector2d st = A.inverse() * (q2 - q1);
+    const Eigen::Vector2d center = q1 + st[0] * m1;
+
+    out.axis = axis;
+    out.point = center.x() * u + center.y() * v;
+    out.radius = (center - q1).norm();
+    return true;
+}
+
+bool isInlier(const Cylinder& c, const Vector3d& p, const Vector3d& n, double threshold, double minCos)
+{
+    const Vector3d w = p - c.point;
+    const Vector3d q = w - w.dot(c.axis) * c.axis;
+    const double length = q.norm();
+    return std::abs(length - c.radius) <= threshold && length > 0 && std::abs(n.dot(q) / length) >= minCos;
+}
+
+/// Refines the cylinder on the given inliers and computes the extent of the axis
+PipeSegment makeSegment(PointBufferPtr points, const std::vector<Vector3d>& pts, std::vector<size_t> inliers, const Cylinder& initial)
+{
+    CylinderFit start;
+    start.point = initial.point;
+    start.axis = initial.axis;
+    start.radius = initial.radius;
+    const CylinderFit fit = refineCylinder(points, inliers, start);
+
+    double tMin = std::numeric_limits<double>::max();
+    double tMax = std::numeric_limits<double>::lowest();
+    for (size_t i : inliers)
+    {
+        const double t = (pts[i] - fit.point).dot(fit.axis);
+        tMin = std::min(tMin, t);
+        tMax = std::max(tMax, t);
+    }
+
+    PipeSegment segment;
+    segment.start = fit.point + tMin * fit.axis;
+    segment.end = fit.point + tMax * fit.axis;
+    segment.radius = fit.radius;
+    segment.statistics = fit.statistics;
+    segment.inliers = std::move(inliers);
+    return segment;
+}
+
+Cylinder toCylinder(const PipeSegment& s)
+{
+    return Cylinder{s.start, (s.end - s.start).normalized(), s.radius};
+}
+
+bool similarRadius(const PipeSegment& a, const PipeSegment& b, double maxDeviation)
+{
+    return std::abs(a.radius - b.radius) <= maxDeviation * std::max(a.radius, b.radius);
+}
+
+bool collinear(const PipeSegment& a, const PipeSegment& b, const PipeReconstructionOptions& options)
+{
+    const Vector3d axis = (a.end - a.start).normalized();
+    if (std::abs(axis.dot((b.end - b.start).normalized())) < std::cos(options.maxAxisAngle * M_PI / 180.0)
+        || !similarRadius(a, b, options.maxRadiusDeviation))
+    {
+        return false;
+    }
+
+    // Both end points of b have to be close to the axis of a
+    const double maxOffset = options.maxRadiusDeviation * std::max(a.radius, b.radius) + options.distanceThreshold;
+    double t[2];
+    const Vector3d ends[2] = {b.start, b.end};
+    for (int i = 0; i < 2; i++)
+    {
+        const Vector3d w = ends[i] - a.start;
+        t[i] = w.dot(axis);
+        if ((w - t[i] * axis).norm() > maxOffset)
+        {
+            return false;
+        }
+    }
+
+    const double gap = std::max({std::min(t[0], t[1]) - a.length(), -std::max(t[0], t[1]), 0.0});
+    return gap <= options.maxGap;
+}
+
+} // anonymous namespace
+
+PipeReconstructionResult reconstructPipes(PointBufferPtr points, const PipeReconstructionOptions& options)
+{
+    PipeReconstructionResult result;
+    const size_t n = points ? points->numPoints() : 0;
+    FloatChannelOptional normalChannel = points ? points->getFloatChannel("normals") : FloatChannelOptional();
+    if (!normalChannel)
+    {
+        lvr2::logout::get() << lvr2::warning << "[PipeReconstruction] Point cloud has no normals" << lvr2::endl;
+        return result;
+    }
+
+    floatArr pointArray = points->getPointArray();
+    std::vector<Vector3d> pts(n);
+    std::vector<Vector3d> normals(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        pts[i] = Vector3d(pointArray[3 * i], pointArray[3 * i + 1], pointArray[3 * i + 2]);
+        normals[i] = Vector3d((*normalChannel)[i][0], (*normalChannel)[i][1], (*normalChannel)[i][2]).normalized();
+    }
+
+    const double minCos = std::cos(options.normalThreshold * M_PI / 180.0);
+    const size_t maxScoringPoints = 20000;
+    std::mt19937 rng(options.seed);
+
+    // Points that are assigned to a segment or were rejected as small pieces
+    std::vector<bool> used(n, false);
+
+    for (int c = 0; c < options.maxCylinders; c++)
+    {
+        std::vector<size_t> candidates;
+        for (size_t i = 0; i < n; i++)
+        {
+            if (!used[i])
+            {
+                candidates.push_back(i);
+            }
+        }
+        if (candidates.size() < options.minInliers)
+        {
+            break;
+        }
+
+        // Score hypotheses on a random subset of the candidates
+        std::vector<size_t> scoring = candidates;
+        if (scoring.size() > maxScoringPoints)
+        {
+            std::shuffle(scoring.begin(), scoring.end(), rng);
+            scoring.resize(maxScoringPoints);
+        }
+
+        std::uniform_int_distribution<size_t> dist(0, candidates.size() - 1);
+        Cylinder best;
+        size_t bestScore = 0;
+        for (int it = 0; it < options.iterations; it++)
+        {
+            const size_t a = candidates[dist(rng)];
+            const size_t b = candidates[dist(rng)];
+            Cylinder cylinder;
+            if ((pts[a] - pts[b]).norm() > 2 * options.maxRadius
+                || !cylinderFromSample(pts[a], normals[a], pts[b], normals[b], cylinder)
+                || cylinder.radius < options.minRadius || cylinder.radius > options.maxRadius)
+            {
+                continue;
+            }
+
+            size_t score = 0;
+            for (size_t i : scoring)
+            {
+                if (isInlier(cylinder, pts[i], normals[i], options.distanceThreshold, minCos))
+                {
+                    score++;
+                }
+            }
+            if (score > bestScore)
+            {
+                bestScore = score;
+                best = cylinder;
+            }
+        }
+        if (bestScore == 0)
+        {
+            break;
+        }
+
+        std::vector<size_t> inliers;
+        for (size_t i : candidates)
+        {
+            if (isInlier(best, pts[i], normals[i], options.distanceThreshold, minCos))
+            {
+                inliers.push_back(i);
+            }
+        }
+        if (inliers.size() < options.minInliers)
+        {
+            break;
+        }
+
+        // Split the inliers at gaps along the axis
+        const PipeSegment whole = makeSegment(points, pts, inliers, best);
+        const Cylinder refined = toCylinder(whole);
+        std::vector<std::pair<double, size_t>> projected;
+        for (size_t i : inliers)
+        {
+            projected.emplace_back((pts[i] - refined.point).dot(refined.axis), i);
+        }
+        std::sort(projected.begin(), projected.end());
+
+        std::vector<size_t> piece;
+        for (size_t k = 0; k < projected.size(); k++)
+        {
+            piece.push_back(projected[k].second);
+            used[projected[k].second] = true;
+            const bool last = k + 1 == projected.size();
+            if (last || projected[k + 1].first - projected[k].first > options.segmentGap)
+            {
+                if (piece.size() >= options.minInliers)
+                {
+                    result.segments.push_back(makeSegment(points, pts, piece, refined));
+                }
+                piece.clear();
+            }
+        }
+    }
+
+    // Merge collinear segments, e.g. pipes that are interrupted by occlusions
+    bool merged = true;
+    while (merged)
+    {
+        merged = false;
+        for (size_t i = 0; i < result.segments.size() && !merged; i++)
+        {
+            for (size_t j = i + 1; j < result.segments.size() && !merged; j++)
+            {
+                PipeSegment& a = result.segments[i];
+                PipeSegment& b = result.segments[j];
+                if (collinear(a, b, options) && collinear(b, a, options))
+                {
+                    std::vector<size_t> inliers = a.inliers;
+                    inliers.insert(inliers.end(), b.inliers.begin(), b.inliers.end());
+                    a = makeSegment(points, pts, inliers, toCylinder(a.length() >= b.length() ? a : b));
+                    result.segments.erase(result.segments.begin() + j);
+                    merged = true;
+                }
+            }
+        }
+    }
+
+    // Group adjacent segments into runs
+    const size_t numSegments = result.segments.size();
+    std::vector<size_t> parent(numSegments);
+    std::iota(parent.begin(), parent.end(), 0);
+    auto find = [&parent](size_t x)
+    {
+        while (parent[x] != x)
+        {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        return x;
+    };
+    for (size_t i = 0; i < numSegments; i++)
+    {
+        for (size_t j = i + 1; j < numSegments; j++)
+        {
+            const PipeSegment& a = result.segments[i];
+            const PipeSegment& b = result.segments[j];
+            const double distance = std::min({
+                (a.start - b.start).norm(), (a.start - b.end).norm(),
+                (a.end - b.start).norm(), (a.end - b.end).norm()
+            });
+            if (similarRadius(a, b, options.maxRadiusDeviation)
+                && distance <= options.maxJointDistance + a.radius + b.radius)
+            {
+                parent[find(i)] = find(j);
+            }
+        }
+    }
+
+    std::vector<long> runOfRoot(numSegments, -1);
+    for (size_t i = 0; i < numSegments; i++)
+    {
+        const size_t root = find(i);
+        if (runOfRoot[root] < 0)
+        {
+            runOfRoot[root] = result.runs.size();
+            result.runs.emplace_back();
+        }
+        PipeSegment& segment = result.segments[i];
+        PipeRun& run = result.runs[runOfRoot[root]];
+        segment.run = runOfRoot[root];
+        run.segments.push_back(i);
+        run.length += segment.length();
+        run.radius += segment.length() * segment.radius;
+    }
+    for (PipeRun& run : result.runs)
+    {
+        run.radius = run.length > 0 ? run.radius / run.length : 0;
+    }
+
+    std::vector<bool> assigned(n, false);
+    for (const PipeSegment& segment : result.segments)
+    {
+        for (size_t i : segment.inliers)
+        {
+            assigned[i] = true;
+        }
+    }
+    for (size_t i = 0; i < n; i++)
+    {
+        if (!assigned[i])
+        {
+            result.remaining.push_back(i);
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[PipeReconstruction] Found " << result.segments.size() << " pipe segments in "
+                        << result.runs.size() << " runs, " << result.remaining.size() << " of " << n
+                        << " points remain" << lvr2::endl;
+    return result;
+}
+
+MeshBufferPtr pipeMesh(const PipeReconstructionResult& pipes, int radialSegments, bool caps)
+{
+    const size_t k = std::max(3, radialSegments);
+    const size_t numSegments = pipes.segments.size();
+    const size_t verticesPerSegment = 2 * k + (caps ? 2 : 0);
+    const size_t facesPerSegment = 2 * k + (caps ? 2 * k : 0);
+    const size_t numVertices = numSegments * verticesPerSegment;
+    const size_t numFaces = numSegments * facesPerSegment;
+
+    floatArr vertices(new float[3 * numVertices]);
+    indexArray faces(new unsigned int[3 * numFaces]);
+    indexArray segmentIds(new unsigned int[numFaces]);
+    indexArray runIds(new unsigned int[numFaces]);
+    floatArr radii(new float[numFaces]);
+    floatArr lengths(new float[numFaces]);
+
+    size_t vertex = 0;
+    size_t face = 0;
+    auto addVertex = [&](const Vector3d& p)
+    {
+        vertices[3 * vertex] = p.x();
+        vertices[3 * vertex + 1] = p.y();
+        vertices[3 * vertex + 2] = p.z();
+        return vertex++;
+    };
+
+    for (size_t s = 0; s < numSegments; s++)
+    {
+        const PipeSegment& segment = pipes.segments[s];
+        const Vector3d axis = (segment.end - segment.start).normalized();
+        const Vector3d u = axis.unitOrthogonal();
+        const Vector3d v = axis.cross(u);
+
+        auto addFace = [&](size_t a, size_t b, size_t c)
+        {
+            faces[3 * face] = a;
+            faces[3 * face + 1] = b;
+            faces[3 * face + 2] = c;
+            segmentIds[face] = s;
+            runIds[face] = segment.run;
+            radii[face] = segment.radius;
+            lengths[face] = segment.length();
+            face++;
+        };
+
+        // Rings are counter clockwise around the axis, so the faces point outwards
+        const size_t startRing = vertex;
+        for (size_t i = 0; i < k; i++)
+        {
+            const double angle = 2 * M_PI * i / k;
+            addVertex(segment.start + segment.radius * (std::cos(angle) * u + std::sin(angle) * v));
+        }
+        const size_t endRing = vertex;
+        for (size_t i = 0; i < k; i++)
+        {
+            const double angle = 2 * M_PI * i / k;
+            addVertex(segment.end + segment.radius * (std::cos(angle) * u + std::sin(angle) * v));
+        }
+
+        for (size_t i = 0; i < k; i++)
+        {
+            const size_t next = (i + 1) % k;
+            addFace(startRing + i, startRing + next, endRing + next);
+            addFace(startRing + i, endRing + next, endRing + i);
+        }
+
+        if (caps)
+        {
+            const size_t startCenter = addVertex(segment.start);
+            const size_t endCenter = addVertex(segment.end);
+            for (size_t i = 0; i < k; i++)
+            {
+                const size_t next = (i + 1) % k;
+                addFace(startCenter, startRing + next, startRing + i);
+                addFace(endCenter, endRing + i, endRing + next);
+            }
+        }
+    }
+
+    MeshBufferPtr mesh = std::make_shared<MeshBuffer>();
+    mesh->setVertices(vertices, numVertices);
+    mesh->setFaceIndices(faces, numFaces);
+    mesh->addIndexChannel(segmentIds, "pipe_segment", numFaces, 1);
+    mesh->addIndexChannel(runIds, "pipe_run", numFaces, 1);
+    mesh->addFloatChannel(radii, "pipe_radius", numFaces, 1);
+    mesh->addFloatChannel(lengths, "pipe_length", numFaces, 1);
+    return mesh;
+}
+
+void savePipeReport(const PipeReconstructionResult& pipes, const boost::filesystem::path& file)
+{
+    boost::filesystem::ofstream out(file);
+    out << "segment,run,start_x,start_y,start_z,end_x,end_y,end_z,radius,length,rmse,points" << std::endl;
+    for (size_t i = 0; i < pipes.segments.size(); i++)
+    {
+        const PipeSegment& s = pipes.segments[i];
+        out << i << "," << s.run << ","
+            << s.start.x() << "," << s.start.y() << "," << s.start.z() << ","
+            << s.end.x() << "," << s.end.y() << "," << s.end.z() << ","
+            << s.radius << "," << s.length() << "," << s.statistics.rmse << "," << s.inliers.size() << std::endl;
+    }
+}
+
+} // namespace lvr2
@@ -0,0 +1,172 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PlanarTriangulation.cpp
+ */
+
+#include "lvr2/algorithm/PlanarTriangulation.hpp"
+#include "lvr2/algorithm/AlphaShape.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <map>
+
+namespace lvr2
+{
+
+Plane<BaseVector<float>> fitPlane(PointBufferPtr points)
+{
+    Plane<BaseVector<float>> plane;
+    if (!points || points->numPoints() < 3)
+    {
+        return plane;
+    }
+
+    const size_t n = points->numPoints();
+    floatArr pts = points->getPointArray();
+
+    Vector3d center = Vector3d::Zero();
+    for (size_t i = 0; i < n; i++)
+    {
+        center += Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+    }
+    center /= n;
+
+    Eigen::Matrix3d cov = Eigen::Matrix3d::Zero();
+    for (size_t i = 0; i < n; i++)
+    {
+        Vector3d d = Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]) - center;
+        cov += d * d.transpose();
+    }
+    cov /= n;
+
+    // Eigenvalues are sorted in increasing order, the normal is the
+    // direction of the smallest variance
+    Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> eig(cov);
+    Vector3d normal = eig.eigenvectors().col(0).normalized();
+
+    plane.pos = BaseVector<float>(center.x(), center.y(), center.z());
+    plane.normal = Normal<float>(normal.x(), normal.y(), normal.z());
+    return plane;
+}
+
+MeshBufferPtr triangulatePlanarSegment(
+    PointBufferPtr points,
+    const Plane<BaseVector<float>>& plane,
+    double maxRadius,
+    bool projectPoints)
+{
+    MeshBufferPtr mesh(new MeshBuffer);
+    if (!points || points->numPoints() < 3)
+    {
+        return mesh;
+    }
+
+    const size_t n = points->numPoints();
+    floatArr pts = points->getPointArray();
+
+    // Orthonormal basis (u, v, normal) of the plane
+    Vector3d normal(plane.normal.x, plane.normal.y, plane.normal.z);
+    Vector3d origin(plane.pos.x, plane.pos.y, plane.pos.z);
+    Vector3d u = normal.unitOrthogonal();
+    Vector3d v = normal.cross(u);
+
+    std::vector<Vector2d> planar(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        Vector3d d = Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]) - origin;
+        planar[i] = Vector2d(d.dot(u), d.dot(v));
+    }
+
+    // As (u, v, normal) is right handed, counter clockwise triangles in
+    // plane coordinates face towards the normal
+    std::vector<std::array<unsigned int, 3>> triangles = alphaTriangles2D(planar, maxRadius);
+    if (triangles.empty())
+    {
+        return mesh;
+    }
+
+    std::map<unsigned int, unsigned int> vertexIndex;
+    for (const auto& t : triangles)
+    {
+        for (unsigned int i : t)
+        {
+            vertexIndex.emplace(i, 0);
+        }
+    }
+
+    const size_t numVertices = vertexIndex.size();
+    floatArr vertices(new float[3 * numVertices]);
+    floatArr normals(new float[3 * numVertices]);
+    unsigned int next = 0;
+    for (auto& entry : vertexIndex)
+    {
+        const unsigned int i = entry.first;
+        entry.second = next;
+
+        Vector3d p;
+        if (projectPoints)
+        {
+            p = origin + u * planar[i].x() + v * planar[i].y();
+        }
+        else
+        {
+            p = Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+        }
+
+        for (int j = 0; j < 3; j++)
+        {
+            vertices[3 * next + j] = p[j];
+            normals[3 * next + j] = normal[j];
+        }
+        next++;
+    }
+
+    indexArray faces(new unsigned int[3 * triangles.size()]);
+    for (size_t i = 0; i < triangles.size(); i++)
+    {
+        for (int j = 0; j < 3; j++)
+        {
+            faces[3 * i + j] = vertexIndex[triangles[i][j]];
+        }
+    }
+
+    mesh->setVertices(vertices, numVertices);
+    mesh->setFaceIndices(faces, triangles.size());
+    mesh->setVertexNormals(normals);
+    return mesh;
+}
+
+MeshBufferPtr triangulatePlanarSegment(PointBufferPtr points, double maxRadius, bool projectPoints)
+{
+    return triangulatePlanarSegment(points, fitPlane(points), maxRadius, projectPoints);
+}
+
+} // namespace lvr2
@@ -0,0 +1,76 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointClassification.cpp
+ */
+
+#include "lvr2/algorithm/PointClassification.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+
+namespace lvr2
+{
+
+PointBufferPtr filterByClassification(
+    PointBufferPtr buffer,
+    const std::vector<unsigned char>& classes,
+    bool invert)
+{
+    UCharChannelOptional classification = buffer->getUCharChannel("classification");
+    if (!classification)
+    {
+        lvr2::logout::get() << lvr2::warning << "[PointClassification] Point cloud has no classification channel. "
+                            << "Keeping all points." << lvr2::endl;
+        return buffer;
+    }
+
+    bool selected[256] = {false};
+    for (unsigned char c : classes)
+    {
+        selected[c] = true;
+    }
+
+    std::vector<size_t> indices;
+    const size_t n = buffer->numPoints();
+    for (size_t i = 0; i < n; i++)
+    {
+        if (selected[(*classification)[i][0]] != invert)
+        {
+            indices.push_back(i);
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[PointClassification] Kept " << indices.size()
+                        << " of " << n << " points." << lvr2::endl;
+
+    return std::make_shared<PointBuffer>(buffer->select(indices));
+}
+
+} // namespace lvr2
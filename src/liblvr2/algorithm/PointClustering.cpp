@@ -0,0 +1,268 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointClustering.cpp
+ */
+
+#include "lvr2/algorithm/PointClustering.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/PointGrid.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <numeric>
+#include <random>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+double squaredDistance(const float* a, const float* b, size_t width)
+{
+    double d = 0.0;
+    for (size_t j = 0; j < width; j++)
+    {
+        const double diff = a[j] - b[j];
+        d += diff * diff;
+    }
+    return d;
+}
+
+} // anonymous namespace
+
+KMeansResult kMeans(const FloatChannel& channel, size_t k, int maxIterations, unsigned int seed)
+{
+    KMeansResult result;
+    const size_t n = channel.numElements();
+    const size_t width = channel.width();
+    const float* data = channel.dataPtr().get();
+    if (n == 0 || k == 0)
+    {
+        return result;
+    }
+    k = std::min(k, n);
+
+    // k-means++ initialization: choose each new center with probability
+    // proportional to the squared distance to the closest chosen center
+    std::mt19937 rng(seed);
+    std::vector<float> centers(k * width);
+    std::vector<double> minDist(n, std::numeric_limits<double>::max());
+    size_t first = std::uniform_int_distribution<size_t>(0, n - 1)(rng);
+    std::copy(data + first * width, data + (first + 1) * width, centers.begin());
+    for (size_t c = 1; c < k; c++)
+    {
+        const float* last = centers.data() + (c - 1) * width;
+        for (size_t i = 0; i < n; i++)
+        {
+            minDist[i] = std::min(minDist[i], squaredDistance(data + i * width, last, width));
+        }
+        if (std::accumulate(minDist.begin(), minDist.end(), 0.0) <= 0.0)
+        {
+            // All elements coincide with a chosen center, so there are
+            // fewer than k distinct values
+            k = c;
+            centers.resize(k * width);
+            break;
+        }
+        std::discrete_distribution<size_t> dist(minDist.begin(), minDist.end());
+        size_t next = dist(rng);
+        std::copy(data + next * width, data + (next + 1) * width, centers.begin() + c * width);
+    }
+
+    // Lloyd iterations
+    result.labels.assign(n, -1);
+    std::vector<double> sums(k * width);
+    std::vector<size_t> counts(k);
+    for (result.iterations = 0; result.iterations < maxIterations; result.iterations++)
+    {
+        size_t changed = 0;
+        double inertia = 0.0;
+
+        #pragma omp parallel for reduction(+:changed, inertia)
+        for (size_t i = 0; i < n; i++)
+        {
+            int best = 0;
+            double bestDist = std::numeric_limits<double>::max();
+            for (size_t c = 0; c < k; c++)
+            {
+                double d = squaredDistance(data + i * width, centers.data() + c * width, width);
+                if (d < bestDist)
+                {
+                    bestDist = d;
+                    best = c;
+                }
+            }
+            if (result.labels[i] != best)
+            {
+                result.labels[i] = best;
+                changed++;
+            }
+            inertia += bestDist;
+        }
+        result.inertia = inertia;
+
+        if (changed == 0)
+        {
+            break;
+        }
+
+        std::fill(sums.begin(), sums.end(), 0.0);
+        std::fill(counts.begin(), counts.end(), 0);
+        for (size_t i = 0; i < n; i++)
+        {
+            const size_t c = result.labels[i];
+            counts[c]++;
+            for (size_t j = 0; j < width; j++)
+            {
+                sums[c * width + j] += data[i * width + j];
+            }
+        }
+        for (size_t c = 0; c < k; c++)
+        {
+            // Empty clusters keep their previous center
+            if (counts[c] > 0)
+            {
+                for (size_t j = 0; j < width; j++)
+                {
+                    centers[c * width + j] = sums[c * width + j] / counts[c];
+                }
+            }
+        }
+    }
+
+    result.centers.resize(k);
+    for (size_t c = 0; c < k; c++)
+    {
+        result.centers[c].assign(centers.begin() + c * width, centers.begin() + (c + 1) * width);
+    }
+    return result;
+}
+
+KMeansResult kMeans(PointBufferPtr buffer, const std::string& channel, size_t k, int maxIterations, unsigned int seed)
+{
+    FloatChannelOptional data = buffer->getFloatChannel(channel);
+    if (!data)
+    {
+        throw std::invalid_argument("[PointClustering] Point cloud has no float channel '" + channel + "'");
+    }
+    return kMeans(*data, k, maxIterations, seed);
+}
+
+std::vector<int> dbscan(PointBufferPtr buffer, float eps, size_t minPoints, size_t* numClusters)
+{
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    std::vector<int> labels(n, DBSCAN_NOISE);
+    if (n == 0 || eps <= 0)
+    {
+        if (numClusters)
+        {
+            *numClusters = 0;
+        }
+        return labels;
+    }
+
+    // Hash grid with cell size eps, so all neighbors are in the adjacent cells
+    std::vector<Vector3d> positions(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        positions[i] = Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+    }
+    const PointGrid grid(positions, eps);
+    auto neighbors = [&](size_t i, std::vector<size_t>& out)
+    {
+        grid.radiusSearch(positions[i], eps, out);
+    };
+
+    std::vector<bool> visited(n, false);
+    std::vector<size_t> neighborhood;
+    std::vector<size_t> queue;
+    int cluster = 0;
+    for (size_t i = 0; i < n; i++)
+    {
+        if (visited[i])
+        {
+            continue;
+        }
+        visited[i] = true;
+
+        neighbors(i, neighborhood);
+        if (neighborhood.size() < minPoints)
+        {
+            continue;
+        }
+
+        // Expand a new cluster from the core point i
+        labels[i] = cluster;
+        queue = neighborhood;
+        while (!queue.empty())
+        {
+            size_t j = queue.back();
+            queue.pop_back();
+
+            if (labels[j] == DBSCAN_NOISE)
+            {
+                labels[j] = cluster;
+            }
+            if (visited[j])
+            {
+                continue;
+            }
+            visited[j] = true;
+
+            neighbors(j, neighborhood);
+            if (neighborhood.size() >= minPoints)
+            {
+                queue.insert(queue.end(), neighborhood.begin(), neighborhood.end());
+            }
+        }
+        cluster++;
+    }
+
+    lvr2::logout::get() << lvr2::info << "[PointClustering] DBSCAN found " << cluster << " clusters." << lvr2::endl;
+
+    if (numClusters)
+    {
+        *numClusters = cluster;
+    }
+    return labels;
+}
+
+void addLabelChannel(PointBufferPtr buffer, const std::vector<int>& labels, const std::string& name)
+{
+    boost::shared_array<int> data(new int[labels.size()]);
+    std::copy(labels.begin(), labels.end(), data.get());
+    buffer->addChannel<int>(data, name, labels.size(), 1);
+}
+
+} // namespace lvr2
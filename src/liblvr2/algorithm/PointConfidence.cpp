@@ -0,0 +1,91 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointConfidence.cpp
+ */
+
+#include "lvr2/algorithm/PointConfidence.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+
+namespace lvr2
+{
+
+void addRangeConfidence(
+    PointBufferPtr buffer,
+    const Vector3d& origin,
+    float halfRange,
+    const std::string& name)
+{
+    const size_t n = buffer->numPoints();
+    FloatChannel points = *buffer->getFloatChannel("points");
+    floatArr confidence(new float[n]);
+
+    const double scale = halfRange > 0 ? 1.0 / halfRange : 0.0;
+
+    #pragma omp parallel for
+    for(size_t i = 0; i < n; i++)
+    {
+        const Vector3d p(points[i][0], points[i][1], points[i][2]);
+        const double r = (p - origin).norm() * scale;
+        confidence[i] = 1.0 / (1.0 + r * r);
+    }
+
+    buffer->addFloatChannel(confidence, name, n, 1);
+}
+
+bool addQualityConfidence(
+    PointBufferPtr buffer,
+    const std::string& source,
+    float minValue,
+    float maxValue,
+    const std::string& name)
+{
+    auto quality = buffer->getFloatChannel(source);
+    if(!quality)
+    {
+        lvr2::logout::get() << lvr2::warning << "[PointConfidence] Point buffer has no channel '" << source << "'" << lvr2::endl;
+        return false;
+    }
+
+    const size_t n = buffer->numPoints();
+    floatArr confidence(new float[n]);
+    const float range = std::max(maxValue - minValue, 1e-6f);
+
+    for(size_t i = 0; i < n; i++)
+    {
+        confidence[i] = std::clamp(((*quality)[i][0] - minValue) / range, 0.0f, 1.0f);
+    }
+
+    buffer->addFloatChannel(confidence, name, n, 1);
+    return true;
+}
+
+} // namespace lvr2
@@ -0,0 +1,140 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PointDeduplication.cpp
+ */
+
+#include "lvr2/algorithm/PointDeduplication.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <numeric>
+#include <unordered_map>
+#include <vector>
+
+namespace lvr2
+{
+
+size_t removeDuplicatePoints(
+    PointBufferPtr buffer,
+    const DeduplicationOptions& options)
+{
+    const size_t n = buffer->numPoints();
+    if (n == 0 || options.radius <= 0)
+    {
+        return 0;
+    }
+    floatArr points = buffer->getPointArray();
+
+    // Visit the most confident points first
+    std::vector<size_t> order(n);
+    std::iota(order.begin(), order.end(), 0);
+    FloatChannelOptional confidence;
+    if (!options.confidenceChannel.empty())
+    {
+        confidence = buffer->getFloatChannel(options.confidenceChannel);
+    }
+    if (confidence)
+    {
+        std::stable_sort(order.begin(), order.end(), [&](size_t a, size_t b)
+        {
+            return (*confidence)[a][0] > (*confidence)[b][0];
+        });
+    }
+
+    IndexChannelOptional source;
+    if (!options.sourceChannel.empty())
+    {
+        source = buffer->getIndexChannel(options.sourceChannel);
+    }
+
+    // Kept points in a grid with the radius as cell size, so all neighbors
+    // of a point are within the adjacent cells
+    const float cellSize = options.radius;
+    const float radiusSq = options.radius * options.radius;
+    auto cellOf = [&](size_t i)
+    {
+        return Vector3i(
+            static_cast<int>(std::floor(points[3 * i] / cellSize)),
+            static_cast<int>(std::floor(points[3 * i + 1] / cellSize)),
+            static_cast<int>(std::floor(points[3 * i + 2] / cellSize)));
+    };
+
+    std::unordered_map<Vector3i, std::vector<size_t>> kept;
+    std::vector<bool> keep(n, false);
+    for (size_t i : order)
+    {
+        const Vector3i cell = cellOf(i);
+        bool duplicate = false;
+        for (int dx = -1; dx <= 1 && !duplicate; dx++)
+        {
+            for (int dy = -1; dy <= 1 && !duplicate; dy++)
+            {
+                for (int dz = -1; dz <= 1 && !duplicate; dz++)
+                {
+                    auto it = kept.find(cell + Vector3i(dx, dy, dz));
+                    if (it == kept.end())
+                    {
+                        continue;
+                    }
+                    for (size_t j : it->second)
+                    {
+                        if (source && (*source)[i][0] == (*source)[j][0])
+                        {
+                            continue;
+                        }
+                        const float ex = points[3 * i] - points[3 * j];
+                        const float ey = points[3 * i + 1] - points[3 * j + 1];
+                        const float ez = points[3 * i + 2] - points[3 * j + 2];
+                        if (ex * ex + ey * ey + ez * ez < radiusSq)
+                        {
+                            duplicate = true;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        if (!duplicate)
+        {
+            keep[i] = true;
+            kept[cell].push_back(i);
+        }
+    }
+
+    const size_t removed = buffer->retain([&](size_t i) { return keep[i]; });
+    lvr2::logout::get() << lvr2::info << "[PointDeduplication] Removed " << removed
+        << " of " << n << " points" << lvr2::endl;
+    return removed;
+}
+
+} // namespace lvr2
@@ -0,0 +1,137 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ReflectionFilter.cpp
+ */
+
+#include "lvr2/algorithm/ReflectionFilter.hpp"
+#include "lvr2/algorithm/VisibilityGrid.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <stdexcept>
+
+namespace lvr2
+{
+
+std::vector<std::vector<size_t>> detectReflections(
+    const std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const ReflectionFilterOptions& options)
+{
+    if (scans.size() != origins.size())
+    {
+        throw std::invalid_argument("[ReflectionFilter] Number of scans and origins differ");
+    }
+    if (scans.size() > VisibilityGrid::MaxScans)
+    {
+        lvr2::logout::get() << lvr2::warning << "[ReflectionFilter] Only the first " << VisibilityGrid::MaxScans
+            << " of " << scans.size() << " scans contribute free space" << lvr2::endl;
+    }
+
+    VisibilityGrid grid(options.voxelSize, options.margin);
+    for (size_t s = 0; s < scans.size(); s++)
+    {
+        if (!grid.addScan(scans[s], origins[s]))
+        {
+            break;
+        }
+    }
+
+    std::vector<std::vector<size_t>> reflections(scans.size());
+    size_t total = 0;
+    for (size_t s = 0; s < scans.size(); s++)
+    {
+        if (!scans[s])
+        {
+            continue;
+        }
+        const uint64_t own = VisibilityGrid::scanBit(s);
+        const size_t n = scans[s]->numPoints();
+        floatArr points = scans[s]->getPointArray();
+        FloatChannelOptional intensities;
+        if (!options.intensityChannel.empty() && options.maxIntensity > 0)
+        {
+            intensities = scans[s]->getFloatChannel(options.intensityChannel);
+        }
+
+        for (size_t i = 0; i < n; i++)
+        {
+            const Vector3d p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+            const Vector3i voxel = grid.voxel(p);
+
+            // A surface that was confirmed by another scan is real
+            if ((grid.occupiedMask(voxel) & ~own) != 0)
+            {
+                continue;
+            }
+
+            const size_t conflicts = VisibilityGrid::count(grid.freeMask(voxel) & ~own);
+            size_t required = options.minConflicts;
+            if (intensities && (*intensities)[i][0] < options.maxIntensity)
+            {
+                required = 1;
+            }
+            if (conflicts >= required)
+            {
+                reflections[s].push_back(i);
+            }
+        }
+        total += reflections[s].size();
+    }
+
+    lvr2::logout::get() << lvr2::info << "[ReflectionFilter] Detected " << total
+        << " virtual points in " << scans.size() << " scans" << lvr2::endl;
+    return reflections;
+}
+
+size_t removeReflections(
+    std::vector<PointBufferPtr>& scans,
+    const std::vector<Vector3d>& origins,
+    const ReflectionFilterOptions& options)
+{
+    std::vector<std::vector<size_t>> reflections = detectReflections(scans, origins, options);
+
+    size_t removed = 0;
+    for (size_t s = 0; s < scans.size(); s++)
+    {
+        if (reflections[s].empty())
+        {
+            continue;
+        }
+        std::vector<bool> isVirtual(scans[s]->numPoints(), false);
+        for (size_t i : reflections[s])
+        {
+            isVirtual[i] = true;
+        }
+        removed += scans[s]->retain([&](size_t i) { return !isVirtual[i]; });
+    }
+    return removed;
+}
+
+} // namespace lvr2
@@ -0,0 +1,625 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * RoomTopology.cpp
+ */
+
+#include "lvr2/algorithm/RoomTopology.hpp"
+#include "lvr2/algorithm/ShapeFitting.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+#include <opencv2/imgproc.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <iomanip>
+#include <limits>
+#include <map>
+#include <random>
+
+namespace lvr2
+{
+
+namespace
+{
+
+Vector2d lineDirection(const Wall& wall)
+{
+    return Vector2d(-wall.normal.y(), wall.normal.x());
+}
+
+/// Detects vertical planes with RANSAC and splits their points into wall segments
+std::vector<Wall> detectWalls(
+    PointBufferPtr points,
+    const std::vector<Vector3d>& pts,
+    const std::vector<Vector3d>& normals,
+    const RoomTopologyOptions& options)
+{
+    const size_t n = pts.size();
+    const size_t maxScoringPoints = 20000;
+    const double minCos = std::cos(20.0 * M_PI / 180.0);
+    std::mt19937 rng(options.seed);
+    std::vector<bool> used(n, false);
+    std::vector<Wall> walls;
+
+    auto isInlier = [&](const Vector3d& normal, double d, size_t i)
+    {
+        return std::abs(normal.dot(pts[i]) - d) <= options.distanceThreshold
+            && (normals.empty() || std::abs(normals[i].dot(normal)) >= minCos);
+    };
+
+    for (int w = 0; w < options.maxWalls; w++)
+    {
+        std::vector<size_t> candidates;
+        for (size_t i = 0; i < n; i++)
+        {
+            if (!used[i])
+            {
+                candidates.push_back(i);
+            }
+        }
+        if (candidates.size() < options.minWallPoints)
+        {
+            break;
+        }
+
+        std::vector<size_t> scoring = candidates;
+        if (scoring.size() > maxScoringPoints)
+        {
+            std::shuffle(scoring.begin(), scoring.end(), rng);
+            scoring.resize(maxScoringPoints);
+        }
+
+        // Vertical plane through two random points
+        std::uniform_int_distribution<size_t> dist(0, candidates.size() - 1);
+        Vector3d bestNormal;
+        double bestD = 0;
+        size_t bestScore = 0;
+        for (int it = 0; it < options.iterations; it++)
+        {
+            const Vector3d a = pts[candidates[dist(rng)]];
+            const Vector3d b = pts[candidates[dist(rng)]];
+            Vector3d normal = (b - a).cross(Vector3d::UnitZ());
+            if (normal.norm() < 1e-6)
+            {
+                continue;
+            }
+            normal.normalize();
+            const double d = normal.dot(a);
+
+            size_t score = 0;
+            for (size_t i : scoring)
+            {
+                if (isInlier(normal, d, i))
+                {
+                    score++;
+                }
+            }
+            if (score > bestScore)
+            {
+                bestScore = score;
+                bestNormal = normal;
+                bestD = d;
+            }
+        }
+        if (bestScore == 0)
+        {
+            break;
+        }
+
+        std::vector<size_t> inliers;
+        for (size_t i : candidates)
+        {
+            if (isInlier(bestNormal, bestD, i))
+            {
+                inliers.push_back(i);
+            }
+        }
+        if (inliers.size() < options.minWallPoints)
+        {
+            break;
+        }
+
+        // Least squares refinement, then force the plane to be vertical
+        PlaneFit fit = fitPlaneLeastSquares(points, inliers);
+        Vector3d normal(fit.normal.x(), fit.normal.y(), 0);
+        if (normal.norm() < 1e-6)
+        {
+            normal = bestNormal;
+        }
+        normal.normalize();
+        Vector3d centroid = Vector3d::Zero();
+        for (size_t i : inliers)
+        {
+            centroid += pts[i];
+        }
+        centroid /= inliers.size();
+        const double d = normal.dot(centroid);
+
+        // Split the plane at large gaps along the wall line
+        Wall wall;
+        wall.normal = normal;
+        wall.distance = d;
+        const Vector2d direction = lineDirection(wall);
+        const Vector2d origin = d * normal.head<2>();
+
+        std::vector<std::pair<double, size_t>> projected;
+        for (size_t i : inliers)
+        {
+            projected.emplace_back((pts[i].head<2>() - origin).dot(direction), i);
+            used[i] = true;
+        }
+        std::sort(projected.begin(), projected.end());
+
+        size_t first = 0;
+        for (size_t k = 0; k < projected.size(); k++)
+        {
+            const bool last = k + 1 == projected.size();
+            if (!last && projected[k + 1].first - projected[k].first <= options.wallGap)
+            {
+                continue;
+            }
+
+            Wall segment = wall;
+            segment.start = origin + projected[first].first * direction;
+            segment.end = origin + projected[k].first * direction;
+            segment.bottom = std::numeric_limits<double>::max();
+            segment.top = std::numeric_limits<double>::lowest();
+            for (size_t j = first; j <= k; j++)
+            {
+                const size_t i = projected[j].second;
+                segment.inliers.push_back(i);
+                segment.bottom = std::min(segment.bottom, pts[i].z());
+                segment.top = std::max(segment.top, pts[i].z());
+            }
+            if (segment.inliers.size() >= options.minWallPoints && segment.length() >= options.minWallLength)
+            {
+                walls.push_back(std::move(segment));
+            }
+            first = k + 1;
+        }
+    }
+    return walls;
+}
+
+/// Moves the end points of non-parallel walls onto the intersection of their lines
+void snapCorners(std::vector<Wall>& walls, double snapDistance)
+{
+    const double maxCos = std::cos(30.0 * M_PI / 180.0);
+    for (size_t i = 0; i < walls.size(); i++)
+    {
+        for (size_t j = i + 1; j < walls.size(); j++)
+        {
+            Wall& a = walls[i];
+            Wall& b = walls[j];
+            const Vector2d da = lineDirection(a);
+            const Vector2d db = lineDirection(b);
+            if (std::abs(da.dot(db)) > maxCos)
+            {
+                continue;
+            }
+
+            // Solve a.start + s * da = b.start + t * db
+            Eigen::Matrix2d A;
+            A << da.x(), -db.x(), da.y(), -db.y();
+            const Vector2d st = A.inverse() * (b.start - a.start);
+            const Vector2d corner = a.start + st[0] * da;
+
+            Vector2d& endA = (a.start - corner).norm() < (a.end - corner).norm() ? a.start : a.end;
+            Vector2d& endB = (b.start - corner).norm() < (b.end - corner).norm() ? b.start : b.end;
+            if ((endA - corner).norm() <= snapDistance && (endB - corner).norm() <= snapDistance)
+            {
+                endA = corner;
+                endB = corner;
+            }
+        }
+    }
+}
+
+/// Raster of the floor plan
+struct FloorGrid
+{
+    Vector2d min;
+    double resolution;
+    int width;
+    int height;
+
+    cv::Point cell(const Vector2d& p) const
+    {
+        return cv::Point((int)std::floor((p.x() - min.x()) / resolution), (int)std::floor((p.y() - min.y()) / resolution));
+    }
+
+    Vector2d position(double col, double row) const
+    {
+        return min + Vector2d((col + 0.5) * resolution, (row + 0.5) * resolution);
+    }
+
+    bool contains(const cv::Point& c) const
+    {
+        return c.x >= 0 && c.y >= 0 && c.x < width && c.y < height;
+    }
+};
+
+/// Finds the connected free space regions enclosed by the walls
+std::vector<Room> findRooms(
+    std::vector<Wall>& walls,
+    const std::vector<Vector3d>& pts,
+    const RoomTopologyOptions& options)
+{
+    FloorGrid grid;
+    Vector2d min(std::numeric_limits<double>::max(), std::numeric_limits<double>::max());
+    Vector2d max = -min;
+    for (const Wall& wall : walls)
+    {
+        min = min.cwiseMin(wall.start).cwiseMin(wall.end);
+        max = max.cwiseMax(wall.start).cwiseMax(wall.end);
+    }
+    const double margin = 4 * options.gridResolution;
+    grid.min = min - Vector2d(margin, margin);
+    grid.resolution = options.gridResolution;
+    grid.width = (int)std::ceil((max.x() - min.x() + 2 * margin) / grid.resolution) + 1;
+    grid.height = (int)std::ceil((max.y() - min.y() + 2 * margin) / grid.resolution) + 1;
+
+    cv::Mat walls8U = cv::Mat::zeros(grid.height, grid.width, CV_8U);
+    const int thickness = std::max(1, (int)std::round(2 * options.distanceThreshold / grid.resolution));
+    for (const Wall& wall : walls)
+    {
+        cv::line(walls8U, grid.cell(wall.start), grid.cell(wall.end), cv::Scalar(255), thickness);
+    }
+
+    // Cells that contain at least one point, used to discard empty regions
+    cv::Mat occupied = cv::Mat::zeros(grid.height, grid.width, CV_8U);
+    for (const Vector3d& p : pts)
+    {
+        const cv::Point c = grid.cell(p.head<2>());
+        if (grid.contains(c))
+        {
+            occupied.at<uchar>(c) = 1;
+        }
+    }
+
+    cv::Mat free = walls8U == 0;
+    cv::Mat labels;
+    cv::Mat stats;
+    cv::Mat centroids;
+    const int numLabels = cv::connectedComponentsWithStats(free, labels, stats, centroids, 4, CV_32S);
+
+    std::vector<Room> rooms;
+    std::map<int, size_t> roomOfLabel;
+    const double cellArea = grid.resolution * grid.resolution;
+    for (int l = 1; l < numLabels; l++)
+    {
+        const int x = stats.at<int>(l, cv::CC_STAT_LEFT);
+        const int y = stats.at<int>(l, cv::CC_STAT_TOP);
+        const int w = stats.at<int>(l, cv::CC_STAT_WIDTH);
+        const int h = stats.at<int>(l, cv::CC_STAT_HEIGHT);
+        const double area = stats.at<int>(l, cv::CC_STAT_AREA) * cellArea;
+
+        // Regions touching the raster border are outside of the building
+        if (x == 0 || y == 0 || x + w == grid.width || y + h == grid.height || area < options.minRoomArea)
+        {
+            continue;
+        }
+
+        cv::Mat mask = labels == l;
+        if (cv::countNonZero(mask & occupied) == 0)
+        {
+            continue;
+        }
+
+        std::vector<std::vector<cv::Point>> contours;
+        cv::findContours(mask, contours, cv::RETR_EXTERNAL, cv::CHAIN_APPROX_SIMPLE);
+        if (contours.empty())
+        {
+            continue;
+        }
+        auto largest = std::max_element(contours.begin(), contours.end(),
+            [](const std::vector<cv::Point>& a, const std::vector<cv::Point>& b) { return a.size() < b.size(); });
+        std::vector<cv::Point> simplified;
+        cv::approxPolyDP(*largest, simplified, 2.0, true);
+
+        Room room;
+        room.area = area;
+        for (const cv::Point& c : simplified)
+        {
+            room.polygon.push_back(grid.position(c.x, c.y));
+        }
+
+        // Make the outline counter clockwise
+        double signedArea = 0;
+        for (size_t i = 0; i < room.polygon.size(); i++)
+        {
+            const Vector2d& a = room.polygon[i];
+            const Vector2d& b = room.polygon[(i + 1) % room.polygon.size()];
+            signedArea += a.x() * b.y() - b.x() * a.y();
+        }
+        if (signedArea < 0)
+        {
+            std::reverse(room.polygon.begin(), room.polygon.end());
+        }
+
+        roomOfLabel[l] = rooms.size();
+        rooms.push_back(std::move(room));
+    }
+
+    // Assign the rooms on both sides of each wall
+    const double offset = (thickness + 2) * grid.resolution;
+    for (Wall& wall : walls)
+    {
+        const Vector2d center = 0.5 * (wall.start + wall.end);
+        for (double side : {-1.0, 1.0})
+        {
+            const cv::Point c = grid.cell(center + side * offset * wall.normal.head<2>());
+            if (!grid.contains(c))
+            {
+                continue;
+            }
+            auto it = roomOfLabel.find(labels.at<int>(c));
+            if (it != roomOfLabel.end()
+                && std::find(wall.rooms.begin(), wall.rooms.end(), it->second) == wall.rooms.end())
+            {
+                wall.rooms.push_back(it->second);
+            }
+        }
+    }
+    return rooms;
+}
+
+/// Finds empty regions in the raster of a wall
+void findOpenings(
+    const Wall& wall,
+    size_t wallIndex,
+    const std::vector<Vector3d>& pts,
+    const RoomTopology& topology,
+    const RoomTopologyOptions& options,
+    std::vector<Opening>& openings)
+{
+    const double cell = options.openingCellSize;
+    const Vector2d direction = (wall.end - wall.start).normalized();
+    const int columns = (int)std::ceil(wall.length() / cell);
+    const int rows = (int)std::ceil((topology.ceiling - topology.floor) / cell);
+    if (columns < 3 || rows < 3)
+    {
+        return;
+    }
+
+    cv::Mat occupied = cv::Mat::zeros(rows, columns, CV_8U);
+    for (size_t i : wall.inliers)
+    {
+        const int col = (int)std::floor((pts[i].head<2>() - wall.start).dot(direction) / cell);
+        const int row = (int)std::floor((pts[i].z() - topology.floor) / cell);
+        if (col >= 0 && col < columns && row >= 0 && row < rows)
+        {
+            occupied.at<uchar>(row, col) = 255;
+        }
+    }
+
+    cv::Mat empty = occupied == 0;
+    cv::Mat labels;
+    cv::Mat stats;
+    cv::Mat centroids;
+    const int numLabels = cv::connectedComponentsWithStats(empty, labels, stats, centroids, 8, CV_32S);
+    for (int l = 1; l < numLabels; l++)
+    {
+        const int x = stats.at<int>(l, cv::CC_STAT_LEFT);
+        const int y = stats.at<int>(l, cv::CC_STAT_TOP);
+        const int w = stats.at<int>(l, cv::CC_STAT_WIDTH);
+        const int h = stats.at<int>(l, cv::CC_STAT_HEIGHT);
+
+        // Gaps at the ends or the top of a wall are usually missing data
+        if (x == 0 || x + w == columns || y + h == rows)
+        {
+            continue;
+        }
+
+        Opening opening;
+        opening.wall = wallIndex;
+        opening.width = w * cell;
+        opening.height = h * cell;
+        opening.sill = topology.floor + y * cell;
+        if (opening.width < options.minOpeningWidth || opening.height < options.minOpeningHeight)
+        {
+            continue;
+        }
+
+        const bool onFloor = y * cell <= options.floorTolerance;
+        opening.type = onFloor && opening.height >= options.minDoorHeight ? OpeningType::Door : OpeningType::Window;
+        if (onFloor && opening.type == OpeningType::Window)
+        {
+            // Too low for a door, e.g. the gap below a table
+            continue;
+        }
+
+        const Vector2d center = wall.start + (x + 0.5 * w) * cell * direction;
+        opening.center = Vector3d(center.x(), center.y(), opening.sill + 0.5 * opening.height);
+        opening.rooms = wall.rooms;
+        openings.push_back(opening);
+    }
+}
+
+} // anonymous namespace
+
+RoomTopology extractRoomTopology(PointBufferPtr points, const RoomTopologyOptions& options)
+{
+    RoomTopology topology;
+    const size_t n = points ? points->numPoints() : 0;
+    if (n == 0)
+    {
+        return topology;
+    }
+
+    floatArr pointArray = points->getPointArray();
+    std::vector<Vector3d> pts(n);
+    std::vector<double> heights(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        pts[i] = Vector3d(pointArray[3 * i], pointArray[3 * i + 1], pointArray[3 * i + 2]);
+        heights[i] = pts[i].z();
+    }
+
+    std::vector<Vector3d> normals;
+    if (FloatChannelOptional normalChannel = points->getFloatChannel("normals"))
+    {
+        normals.resize(n);
+        for (size_t i = 0; i < n; i++)
+        {
+            normals[i] = Vector3d((*normalChannel)[i][0], (*normalChannel)[i][1], (*normalChannel)[i][2]).normalized();
+        }
+    }
+
+    // Robust floor and ceiling heights
+    std::sort(heights.begin(), heights.end());
+    topology.floor = heights[n / 100];
+    topology.ceiling = heights[n - 1 - n / 100];
+
+    topology.walls = detectWalls(points, pts, normals, options);
+    if (topology.walls.empty())
+    {
+        lvr2::logout::get() << lvr2::warning << "[RoomTopology] No walls found" << lvr2::endl;
+        return topology;
+    }
+    snapCorners(topology.walls, options.cornerSnapDistance);
+
+    topology.rooms = findRooms(topology.walls, pts, options);
+
+    for (size_t w = 0; w < topology.walls.size(); w++)
+    {
+        findOpenings(topology.walls[w], w, pts, topology, options, topology.openings);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[RoomTopology] Found " << topology.walls.size() << " walls, "
+                        << topology.rooms.size() << " rooms and " << topology.openings.size() << " openings" << lvr2::endl;
+    return topology;
+}
+
+std::vector<Polyline> roomTopologyPolylines(const RoomTopology& topology)
+{
+    std::vector<Polyline> polylines;
+    for (const Wall& wall : topology.walls)
+    {
+        Polyline line;
+        line.layer = "walls";
+        line.points.push_back(Vector3d(wall.start.x(), wall.start.y(), topology.floor));
+        line.points.push_back(Vector3d(wall.end.x(), wall.end.y(), topology.floor));
+        polylines.push_back(line);
+    }
+
+    for (const Room& room : topology.rooms)
+    {
+        Polyline outline;
+        outline.layer = "rooms";
+        outline.closed = true;
+        for (const Vector2d& p : room.polygon)
+        {
+            outline.points.push_back(Vector3d(p.x(), p.y(), topology.floor));
+        }
+        polylines.push_back(outline);
+    }
+
+    for (const Opening& opening : topology.openings)
+    {
+        const Wall& wall = topology.walls[opening.wall];
+        const Vector2d d = (wall.end - wall.start).normalized() * (0.5 * opening.width);
+        const Vector3d side(d.x(), d.y(), 0);
+        const Vector3d bottom(opening.center.x(), opening.center.y(), opening.sill);
+        const Vector3d up(0, 0, opening.height);
+
+        Polyline outline;
+        outline.layer = opening.type == OpeningType::Door ? "doors" : "windows";
+        outline.closed = true;
+        outline.points = {bottom - side, bottom + side, bottom + side + up, bottom - side + up};
+        polylines.push_back(outline);
+    }
+    return polylines;
+}
+
+void saveRoomTopology(const RoomTopology& topology, const boost::filesystem::path& file)
+{
+    auto writeIndices = [](std::ostream& os, const std::vector<size_t>& indices)
+    {
+        os << "[";
+        for (size_t i = 0; i < indices.size(); i++)
+        {
+            os << (i ? ", " : "") << indices[i];
+        }
+        os << "]";
+    };
+
+    boost::filesystem::ofstream out(file);
+    out << std::setprecision(9);
+    out << "{\n";
+    out << "  \"floor\": " << topology.floor << ",\n";
+    out << "  \"ceiling\": " << topology.ceiling << ",\n";
+
+    out << "  \"rooms\": [";
+    for (size_t r = 0; r < topology.rooms.size(); r++)
+    {
+        const Room& room = topology.rooms[r];
+        out << (r ? ",\n" : "\n") << "    {\"id\": " << r << ", \"area\": " << room.area << ", \"polygon\": [";
+        for (size_t i = 0; i < room.polygon.size(); i++)
+        {
+            out << (i ? ", " : "") << "[" << room.polygon[i].x() << ", " << room.polygon[i].y() << "]";
+        }
+        out << "]}";
+    }
+    out << (topology.rooms.empty() ? "],\n" : "\n  ],\n");
+
+    out << "  \"walls\": [";
+    for (size_t w = 0; w < topology.walls.size(); w++)
+    {
+        const Wall& wall = topology.walls[w];
+        out << (w ? ",\n" : "\n") << "    {\"id\": " << w
+            << ", \"start\": [" << wall.start.x() << ", " << wall.start.y() << "]"
+            << ", \"end\": [" << wall.end.x() << ", " << wall.end.y() << "]"
+            << ", \"bottom\": " << wall.bottom << ", \"top\": " << wall.top
+            << ", \"rooms\": ";
+        writeIndices(out, wall.rooms);
+        out << "}";
+    }
+    out << (topology.walls.empty() ? "],\n" : "\n  ],\n");
+
+    out << "  \"openings\": [";
+    for (size_t o = 0; o < topology.openings.size(); o++)
+    {
+        const Opening& opening = topology.openings[o];
+        out << (o ? ",\n" : "\n") << "    {\"id\": " << o
+            << ", \"type\": \"" << (opening.type == OpeningType::Door ? "door" : "window") << "\""
+            << ", \"wall\": " << opening.wall
+            << ", \"center\": [" << opening.center.x() << ", " << opening.center.y() << ", " << opening.center.z() << "]"
+            << ", \"width\": " << opening.width << ", \"height\": " << opening.height << ", \"sill\": " << opening.sill
+            << ", \"rooms\": ";
+        writeIndices(out, opening.rooms);
+        out << "}";
+    }
+    out << (topology.openings.empty() ? "]\n" : "\n  ]\n");
+    out << "}\n";
+}
+
+} // namespace lvr2
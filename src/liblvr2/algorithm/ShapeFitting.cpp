@@ -0,0 +1,411 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ShapeFitting.cpp
+ */
+
+#include "lvr2/algorithm/ShapeFitting.hpp"
+
+#include <Eigen/Cholesky>
+#include <Eigen/Eigenvalues>
+
+#include <algorithm>
+#include <cmath>
+
+namespace lvr2
+{
+
+namespace
+{
+
+std::vector<Vector3d> gatherPoints(PointBufferPtr points, const std::vector<size_t>& inliers)
+{
+    floatArr pts = points->getPointArray();
+    const size_t n = inliers.empty() ? points->numPoints() : inliers.size();
+    std::vector<Vector3d> out(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        const size_t j = inliers.empty() ? i : inliers[i];
+        out[i] = Vector3d(pts[3 * j], pts[3 * j + 1], pts[3 * j + 2]);
+    }
+    return out;
+}
+
+/// Centroid and covariance eigen decomposition with ascending eigenvalues
+Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> principalAxes(const std::vector<Vector3d>& pts, Vector3d& centroid)
+{
+    centroid = Vector3d::Zero();
+    for (const Vector3d& p : pts)
+    {
+        centroid += p;
+    }
+    centroid /= std::max<size_t>(pts.size(), 1);
+
+    Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+    for (const Vector3d& p : pts)
+    {
+        covariance += (p - centroid) * (p - centroid).transpose();
+    }
+    return Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d>(covariance);
+}
+
+void computeStatistics(const Eigen::VectorXd& residuals, FitStatistics& stats)
+{
+    const size_t n = residuals.size();
+    stats.numPoints = n;
+    if (n == 0)
+    {
+        return;
+    }
+
+    std::vector<double> absolute(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        absolute[i] = std::abs(residuals[i]);
+    }
+    stats.rmse = std::sqrt(residuals.squaredNorm() / n);
+    stats.meanAbsolute = residuals.cwiseAbs().sum() / n;
+    stats.maxAbsolute = residuals.cwiseAbs().maxCoeff();
+    std::nth_element(absolute.begin(), absolute.begin() + n / 2, absolute.end());
+    stats.median = absolute[n / 2];
+}
+
+/**
+ * Minimizes the squared residuals. evaluate(model, r, J) fills the residuals
+ * and, if J is not null, their Jacobian. update(model, delta) applies a step.
+ * Steps that increase the error are halved.
+ */
+template<typename ModelT, typename EvaluateF, typename UpdateF>
+ModelT gaussNewton(
+    ModelT model,
+    size_t numResiduals,
+    int numParams,
+    EvaluateF evaluate,
+    UpdateF update,
+    const ShapeFitOptions& options,
+    FitStatistics& stats)
+{
+    Eigen::VectorXd r(numResiduals);
+    Eigen::MatrixXd J(numResiduals, numParams);
+    evaluate(model, r, &J);
+    double error = r.squaredNorm();
+
+    stats.iterations = 0;
+    stats.converged = false;
+    while (stats.iterations < options.maxIterations && numResiduals >= (size_t)numParams)
+    {
+        stats.iterations++;
+        const Eigen::VectorXd delta = (J.transpose() * J).ldlt().solve(-J.transpose() * r);
+        if (!delta.allFinite())
+        {
+            break;
+        }
+
+        bool accepted = false;
+        double step = 1.0;
+        ModelT candidate = model;
+        Eigen::VectorXd candidateR(numResiduals);
+        for (int halving = 0; halving < 16; halving++, step *= 0.5)
+        {
+            candidate = update(model, delta * step);
+            evaluate(candidate, candidateR, nullptr);
+            if (candidateR.squaredNorm() <= error)
+            {
+                accepted = true;
+                break;
+            }
+        }
+
+        if (!accepted)
+        {
+            // No descent possible anymore: we are at a minimum
+            stats.converged = true;
+            break;
+        }
+
+        const double newError = candidateR.squaredNorm();
+        model = candidate;
+        evaluate(model, r, &J);
+        if (error - newError <= options.tolerance * std::max(error, 1e-300))
+        {
+            stats.converged = true;
+            break;
+        }
+        error = newError;
+    }
+
+    evaluate(model, r, nullptr);
+    computeStatistics(r, stats);
+    return model;
+}
+
+/// Orthonormal basis u, v perpendicular to the given unit vector
+void perpendicularBasis(const Vector3d& n, Vector3d& u, Vector3d& v)
+{
+    u = n.unitOrthogonal();
+    v = n.cross(u);
+}
+
+} // anonymous namespace
+
+PlaneFit estimatePlane(PointBufferPtr points, const std::vector<size_t>& inliers)
+{
+    const std::vector<Vector3d> pts = gatherPoints(points, inliers);
+    PlaneFit fit;
+    Vector3d centroid;
+    auto solver = principalAxes(pts, centroid);
+    fit.normal = solver.eigenvectors().col(0).normalized();
+    fit.distance = fit.normal.dot(centroid);
+    return fit;
+}
+
+SphereFit estimateSphere(PointBufferPtr points, const std::vector<size_t>& inliers)
+{
+    const std::vector<Vector3d> pts = gatherPoints(points, inliers);
+    SphereFit fit;
+    if (pts.size() < 4)
+    {
+        return fit;
+    }
+
+    // |p|^2 = 2 c.p + k with k = r^2 - |c|^2
+    Eigen::MatrixXd A(pts.size(), 4);
+    Eigen::VectorXd b(pts.size());
+    for (size_t i = 0; i < pts.size(); i++)
+    {
+        A.row(i) << 2 * pts[i].x(), 2 * pts[i].y(), 2 * pts[i].z(), 1.0;
+        b[i] = pts[i].squaredNorm();
+    }
+    const Eigen::Vector4d x = (A.transpose() * A).ldlt().solve(A.transpose() * b);
+    fit.center = x.head<3>();
+    fit.radius = std::sqrt(std::max(0.0, x[3] + fit.center.squaredNorm()));
+    return fit;
+}
+
+CylinderFit estimateCylinder(PointBufferPtr points, const std::vector<size_t>& inliers)
+{
+    const std::vector<Vector3d> pts = gatherPoints(points, inliers);
+    CylinderFit fit;
+    if (pts.size() < 5)
+    {
+        return fit;
+    }
+
+    Vector3d centroid;
+    auto solver = principalAxes(pts, centroid);
+    fit.axis = solver.eigenvectors().col(2).normalized();
+
+    // The normals of a cylinder are perpendicular to its axis
+    if (FloatChannelOptional normals = points->getFloatChannel("normals"))
+    {
+        Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+        for (size_t i = 0; i < pts.size(); i++)
+        {
+            const size_t j = inliers.empty() ? i : inliers[i];
+            const Vector3d n((*normals)[j][0], (*normals)[j][1], (*normals)[j][2]);
+            covariance += n * n.transpose();
+        }
+        Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> normalSolver(covariance);
+        fit.axis = normalSolver.eigenvectors().col(0).normalized();
+    }
+
+    // Algebraic circle fit in the plane perpendicular to the axis
+    Vector3d u, v;
+    perpendicularBasis(fit.axis, u, v);
+    Eigen::MatrixXd A(pts.size(), 3);
+    Eigen::VectorXd b(pts.size());
+    for (size_t i = 0; i < pts.size(); i++)
+    {
+        const Vector3d p = pts[i] - centroid;
+        const double x = p.dot(u);
+        const double y = p.dot(v);
+        A.row(i) << 2 * x, 2 * y, 1.0;
+        b[i] = x * x + y * y;
+    }
+    const Eigen::Vector3d x = (A.transpose() * A).ldlt().solve(A.transpose() * b);
+    fit.point = centroid + x[0] * u + x[1] * v;
+    fit.radius = std::sqrt(std::max(0.0, x[2] + x[0] * x[0] + x[1] * x[1]));
+    return fit;
+}
+
+PlaneFit refinePlane(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers,
+    const PlaneFit& initial,
+    const ShapeFitOptions& options)
+{
+    const std::vector<Vector3d> pts = gatherPoints(points, inliers);
+
+    PlaneFit start = initial;
+    start.normal.normalize();
+
+    auto evaluate = [&pts](const PlaneFit& m, Eigen::VectorXd& r, Eigen::MatrixXd* J)
+    {
+        Vector3d u, v;
+        perpendicularBasis(m.normal, u, v);
+        for (size_t i = 0; i < pts.size(); i++)
+        {
+            r[i] = m.normal.dot(pts[i]) - m.distance;
+            if (J)
+            {
+                J->row(i) << pts[i].dot(u), pts[i].dot(v), -1.0;
+            }
+        }
+    };
+    auto update = [](const PlaneFit& m, const Eigen::VectorXd& delta)
+    {
+        Vector3d u, v;
+        perpendicularBasis(m.normal, u, v);
+        PlaneFit next = m;
+        next.normal = (m.normal + delta[0] * u + delta[1] * v).normalized();
+        next.distance = m.distance + delta[2];
+        return next;
+    };
+
+    FitStatistics stats;
+    PlaneFit fit = gaussNewton(start, pts.size(), 3, evaluate, update, options, stats);
+    fit.statistics = stats;
+    return fit;
+}
+
+SphereFit refineSphere(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers,
+    const SphereFit& initial,
+    const ShapeFitOptions& options)
+{
+    const std::vector<Vector3d> pts = gatherPoints(points, inliers);
+
+    auto evaluate = [&pts](const SphereFit& m, Eigen::VectorXd& r, Eigen::MatrixXd* J)
+    {
+        for (size_t i = 0; i < pts.size(); i++)
+        {
+            const Vector3d d = pts[i] - m.center;
+            const double length = d.norm();
+            r[i] = length - m.radius;
+            if (J)
+            {
+                const Vector3d dir = length > 0 ? Vector3d(d / length) : Vector3d::Zero();
+                J->row(i) << -dir.x(), -dir.y(), -dir.z(), -1.0;
+            }
+        }
+    };
+    auto update = [](const SphereFit& m, const Eigen::VectorXd& delta)
+    {
+        SphereFit next = m;
+        next.center += delta.head<3>();
+        next.radius += delta[3];
+        return next;
+    };
+
+    FitStatistics stats;
+    SphereFit fit = gaussNewton(initial, pts.size(), 4, evaluate, update, options, stats);
+    fit.radius = std::abs(fit.radius);
+    fit.statistics = stats;
+    return fit;
+}
+
+CylinderFit refineCylinder(
+    PointBufferPtr points,
+    const std::vector<size_t>& inliers,
+    const CylinderFit& initial,
+    const ShapeFitOptions& options)
+{
+    const std::vector<Vector3d> pts = gatherPoints(points, inliers);
+
+    CylinderFit start = initial;
+    start.axis.normalize();
+
+    // Parameters: axis rotation (2), axis translation perpendicular to the axis (2), radius
+    auto evaluate = [&pts](const CylinderFit& m, Eigen::VectorXd& r, Eigen::MatrixXd* J)
+    {
+        Vector3d u, v;
+        perpendicularBasis(m.axis, u, v);
+        for (size_t i = 0; i < pts.size(); i++)
+        {
+            const Vector3d w = pts[i] - m.point;
+            const double t = w.dot(m.axis);
+            const Vector3d q = w - t * m.axis;
+            const double length = q.norm();
+            r[i] = length - m.radius;
+            if (J)
+            {
+                const Vector3d dir = length > 0 ? Vector3d(q / length) : Vector3d::Zero();
+                const double du = dir.dot(u);
+                const double dv = dir.dot(v);
+                J->row(i) << -t * du, -t * dv, -du, -dv, -1.0;
+            }
+        }
+    };
+    auto update = [](const CylinderFit& m, const Eigen::VectorXd& delta)
+    {
+        Vector3d u, v;
+        perpendicularBasis(m.axis, u, v);
+        CylinderFit next = m;
+        next.axis = (m.axis + delta[0] * u + delta[1] * v).normalized();
+        next.point = m.point + delta[2] * u + delta[3] * v;
+        next.radius = m.radius + delta[4];
+        return next;
+    };
+
+    FitStatistics stats;
+    CylinderFit fit = gaussNewton(start, pts.size(), 5, evaluate, update, options, stats);
+    fit.radius = std::abs(fit.radius);
+    fit.statistics = stats;
+
+    // Move the axis point next to the data
+    Vector3d centroid = Vector3d::Zero();
+    for (const Vector3d& p : pts)
+    {
+        centroid += p;
+    }
+    if (!pts.empty())
+    {
+        centroid /= pts.size();
+        fit.point += (centroid - fit.point).dot(fit.axis) * fit.axis;
+    }
+    return fit;
+}
+
+PlaneFit fitPlaneLeastSquares(PointBufferPtr points, const std::vector<size_t>& inliers, const ShapeFitOptions& options)
+{
+    return refinePlane(points, inliers, estimatePlane(points, inliers), options);
+}
+
+SphereFit fitSphere(PointBufferPtr points, const std::vector<size_t>& inliers, const ShapeFitOptions& options)
+{
+    return refineSphere(points, inliers, estimateSphere(points, inliers), options);
+}
+
+CylinderFit fitCylinder(PointBufferPtr points, const std::vector<size_t>& inliers, const ShapeFitOptions& options)
+{
+    return refineCylinder(points, inliers, estimateCylinder(points, inliers), options);
+}
+
+} // namespace lvr2
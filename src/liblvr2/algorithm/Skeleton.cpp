@@ -0,0 +1,473 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Skeleton.cpp
+ */
+
+#include "lvr2/algorithm/Skeleton.hpp"
+#include "lvr2/algorithm/AlphaShape.hpp"
+#include "lvr2/algorithm/PlanarTriangulation.hpp"
+#include "lvr2/geometry/Delaunay2D.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/PointGrid.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <algorithm>
+#include <array>
+#include <cmath>
+#include <map>
+#include <numeric>
+#include <set>
+#include <stdexcept>
+#include <tuple>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Undirected graph with embedded nodes
+struct SkeletonGraph
+{
+    std::vector<Vector3d> nodes;
+    std::vector<std::set<size_t>> adjacency;
+
+    size_t addNode(const Vector3d& p)
+    {
+        nodes.push_back(p);
+        adjacency.emplace_back();
+        return nodes.size() - 1;
+    }
+
+    void addEdge(size_t a, size_t b)
+    {
+        if (a != b)
+        {
+            adjacency[a].insert(b);
+            adjacency[b].insert(a);
+        }
+    }
+};
+
+/// Removes branches that end in a leaf, start at a junction and are shorter than minLength
+void pruneBranches(SkeletonGraph& graph, double minLength)
+{
+    bool changed = true;
+    while (changed && minLength > 0)
+    {
+        changed = false;
+        for (size_t leaf = 0; leaf < graph.nodes.size(); leaf++)
+        {
+            if (graph.adjacency[leaf].size() != 1)
+            {
+                continue;
+            }
+
+            // Walk along the branch until a junction or another leaf is reached
+            std::vector<size_t> branch = {leaf};
+            size_t prev = leaf;
+            size_t current = *graph.adjacency[leaf].begin();
+            double length = (graph.nodes[current] - graph.nodes[leaf]).norm();
+            while (graph.adjacency[current].size() == 2 && length < minLength)
+            {
+                branch.push_back(current);
+                size_t next = *graph.adjacency[current].begin() == prev
+                    ? *graph.adjacency[current].rbegin()
+                    : *graph.adjacency[current].begin();
+                length += (graph.nodes[next] - graph.nodes[current]).norm();
+                prev = current;
+                current = next;
+            }
+
+            if (graph.adjacency[current].size() >= 3 && length < minLength)
+            {
+                graph.adjacency[current].erase(branch.back());
+                for (size_t n : branch)
+                {
+                    for (size_t m : graph.adjacency[n])
+                    {
+                        if (m != current)
+                        {
+                            graph.adjacency[m].erase(n);
+                        }
+                    }
+                    graph.adjacency[n].clear();
+                }
+                changed = true;
+            }
+        }
+    }
+}
+
+/// Splits the graph into polylines between leaves and junctions. Cycles become closed polylines.
+std::vector<Polyline> tracePolylines(const SkeletonGraph& graph, const std::string& layer)
+{
+    std::set<std::pair<size_t, size_t>> visited;
+    auto visit = [&visited](size_t a, size_t b)
+    {
+        return visited.insert(std::make_pair(std::min(a, b), std::max(a, b))).second;
+    };
+
+    auto walk = [&](size_t start, size_t next, Polyline& line)
+    {
+        line.points.push_back(graph.nodes[start]);
+        size_t prev = start;
+        size_t current = next;
+        while (true)
+        {
+            line.points.push_back(graph.nodes[current]);
+            if (graph.adjacency[current].size() != 2 || current == start)
+            {
+                break;
+            }
+            size_t following = *graph.adjacency[current].begin() == prev
+                ? *graph.adjacency[current].rbegin()
+                : *graph.adjacency[current].begin();
+            if (!visit(current, following))
+            {
+                break;
+            }
+            prev = current;
+            current = following;
+        }
+    };
+
+    std::vector<Polyline> lines;
+    for (size_t n = 0; n < graph.nodes.size(); n++)
+    {
+        if (graph.adjacency[n].size() == 2 || graph.adjacency[n].empty())
+        {
+            continue;
+        }
+        for (size_t m : graph.adjacency[n])
+        {
+            if (visit(n, m))
+            {
+                Polyline line;
+                line.layer = layer;
+                walk(n, m, line);
+                lines.push_back(std::move(line));
+            }
+        }
+    }
+
+    // Remaining edges belong to cycles without junctions
+    for (size_t n = 0; n < graph.nodes.size(); n++)
+    {
+        for (size_t m : graph.adjacency[n])
+        {
+            if (visit(n, m))
+            {
+                Polyline line;
+                line.layer = layer;
+                line.closed = true;
+                walk(n, m, line);
+                if (line.points.size() > 1 && line.points.front() == line.points.back())
+                {
+                    line.points.pop_back();
+                }
+                lines.push_back(std::move(line));
+            }
+        }
+    }
+    return lines;
+}
+
+bool insideRings(const Vector2d& p, const std::vector<std::vector<Vector2d>>& rings)
+{
+    bool inside = false;
+    for (const auto& ring : rings)
+    {
+        for (size_t i = 0, j = ring.size() - 1; i < ring.size(); j = i++)
+        {
+            const Vector2d& a = ring[i];
+            const Vector2d& b = ring[j];
+            if ((a.y() > p.y()) != (b.y() > p.y())
+                && p.x() < (b.x() - a.x()) * (p.y() - a.y()) / (b.y() - a.y()) + a.x())
+            {
+                inside = !inside;
+            }
+        }
+    }
+    return inside;
+}
+
+Vector2d circumcenter(const Vector2d& a, const Vector2d& b, const Vector2d& c)
+{
+    const Vector2d ab = b - a;
+    const Vector2d ac = c - a;
+    const double d = 2.0 * (ab.x() * ac.y() - ab.y() * ac.x());
+    const double ab2 = ab.squaredNorm();
+    const double ac2 = ac.squaredNorm();
+    return a + Vector2d(ac.y() * ab2 - ab.y() * ac2, ab.x() * ac2 - ac.x() * ab2) / d;
+}
+
+} // anonymous namespace
+
+std::vector<Polyline> medialAxis2D(
+    const std::vector<std::vector<Vector2d>>& rings,
+    double sampleDistance,
+    double pruneLength)
+{
+    // Densely sample the boundary
+    std::vector<Vector2d> samples;
+    for (const auto& ring : rings)
+    {
+        for (size_t i = 0; i < ring.size(); i++)
+        {
+            const Vector2d& a = ring[i];
+            const Vector2d& b = ring[(i + 1) % ring.size()];
+            const int steps = std::max(1, (int)std::ceil((b - a).norm() / sampleDistance));
+            for (int s = 0; s < steps; s++)
+            {
+                samples.push_back(a + (b - a) * (double(s) / steps));
+            }
+        }
+    }
+    if (samples.size() < 3)
+    {
+        return std::vector<Polyline>();
+    }
+
+    // The interior Voronoi vertices (circumcenters) and edges approximate the medial axis
+    Delaunay2D delaunay(samples);
+    const auto& points = delaunay.points();
+    const auto& triangles = delaunay.triangles();
+
+    SkeletonGraph graph;
+    std::vector<long> nodeOfTriangle(triangles.size(), -1);
+    std::map<std::pair<unsigned int, unsigned int>, std::vector<size_t>> edgeTriangles;
+    for (size_t t = 0; t < triangles.size(); t++)
+    {
+        const auto& tri = triangles[t];
+        const Vector2d c = circumcenter(points[tri[0]], points[tri[1]], points[tri[2]]);
+        if (std::isfinite(c.x()) && std::isfinite(c.y()) && insideRings(c, rings))
+        {
+            nodeOfTriangle[t] = graph.addNode(Vector3d(c.x(), c.y(), 0.0));
+        }
+        for (int i = 0; i < 3; i++)
+        {
+            unsigned int a = tri[i];
+            unsigned int b = tri[(i + 1) % 3];
+            edgeTriangles[std::make_pair(std::min(a, b), std::max(a, b))].push_back(t);
+        }
+    }
+
+    for (const auto& entry : edgeTriangles)
+    {
+        const auto& adjacent = entry.second;
+        if (adjacent.size() == 2 && nodeOfTriangle[adjacent[0]] >= 0 && nodeOfTriangle[adjacent[1]] >= 0)
+        {
+            graph.addEdge(nodeOfTriangle[adjacent[0]], nodeOfTriangle[adjacent[1]]);
+        }
+    }
+
+    pruneBranches(graph, pruneLength);
+    return tracePolylines(graph, "medial_axis");
+}
+
+std::vector<Polyline> planarSegmentSkeleton(
+    PointBufferPtr points,
+    double alpha,
+    double sampleDistance,
+    double pruneLength)
+{
+    const size_t n = points->numPoints();
+    if (n < 3)
+    {
+        return std::vector<Polyline>();
+    }
+
+    // Project the points into the plane
+    const Plane<BaseVector<float>> plane = fitPlane(points);
+    const Vector3d origin(plane.pos.x, plane.pos.y, plane.pos.z);
+    const Vector3d normal(plane.normal.x, plane.normal.y, plane.normal.z);
+    const Vector3d u = normal.unitOrthogonal();
+    const Vector3d v = normal.cross(u);
+
+    floatArr pts = points->getPointArray();
+    std::vector<Vector2d> projected(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3d p = Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]) - origin;
+        projected[i] = Vector2d(p.dot(u), p.dot(v));
+    }
+
+    // The boundary of the alpha shape as rings in the plane
+    std::vector<std::vector<Vector2d>> rings;
+    for (const BoundaryRing& boundary : boundaryRings(alphaTriangles2D(projected, alpha)))
+    {
+        std::vector<Vector2d> ring;
+        for (unsigned int i : boundary.indices)
+        {
+            ring.push_back(projected[i]);
+        }
+        rings.push_back(std::move(ring));
+    }
+
+    std::vector<Polyline> axis = medialAxis2D(rings, sampleDistance, pruneLength);
+    for (Polyline& line : axis)
+    {
+        for (Vector3d& p : line.points)
+        {
+            p = origin + p.x() * u + p.y() * v;
+        }
+    }
+    return axis;
+}
+
+std::vector<Polyline> curveSkeleton(PointBufferPtr points, const CurveSkeletonOptions& options)
+{
+    if (options.radius <= 0 || options.nodeSpacing < 0)
+    {
+        throw std::invalid_argument("[Skeleton] radius must be positive and nodeSpacing must not be negative");
+    }
+
+    const size_t n = points->numPoints();
+    const double radius = options.radius;
+    const double spacing = options.nodeSpacing > 0 ? options.nodeSpacing : radius / 2;
+    const double pruneLength = options.pruneLength > 0 ? options.pruneLength : radius;
+
+    floatArr pts = points->getPointArray();
+    std::vector<Vector3d> contracted(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        contracted[i] = Vector3d(pts[3 * i], pts[3 * i + 1], pts[3 * i + 2]);
+    }
+
+    // Move each point onto the principal axis of its neighborhood
+    for (int iteration = 0; iteration < options.iterations; iteration++)
+    {
+        PointGrid grid(contracted, radius);
+        std::vector<Vector3d> next = contracted;
+
+        #pragma omp parallel for schedule(dynamic, 256)
+        for (size_t i = 0; i < n; i++)
+        {
+            std::vector<size_t> neighbors;
+            grid.radiusSearch(contracted[i], radius, neighbors);
+            if (neighbors.size() < 3)
+            {
+                continue;
+            }
+
+            Vector3d centroid = Vector3d::Zero();
+            for (size_t j : neighbors)
+            {
+                centroid += contracted[j];
+            }
+            centroid /= neighbors.size();
+
+            Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+            for (size_t j : neighbors)
+            {
+                const Vector3d d = contracted[j] - centroid;
+                covariance += d * d.transpose();
+            }
+            Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> solver(covariance);
+            const Vector3d axis = solver.eigenvectors().col(2);
+
+            next[i] = centroid + (contracted[i] - centroid).dot(axis) * axis;
+        }
+        contracted = std::move(next);
+    }
+
+    // Cluster the contracted points into nodes
+    std::map<std::array<int64_t, 3>, std::pair<Vector3d, size_t>> cells;
+    for (const Vector3d& p : contracted)
+    {
+        std::array<int64_t, 3> key = {
+            (int64_t)std::floor(p.x() / spacing),
+            (int64_t)std::floor(p.y() / spacing),
+            (int64_t)std::floor(p.z() / spacing)
+        };
+        // Eigen leaves default constructed vectors uninitialized
+        auto& cell = cells.emplace(key, std::make_pair(Vector3d::Zero(), size_t(0))).first->second;
+        cell.first += p;
+        cell.second++;
+    }
+
+    SkeletonGraph graph;
+    for (auto& entry : cells)
+    {
+        graph.addNode(entry.second.first / entry.second.second);
+    }
+
+    // Minimum spanning tree (Kruskal) over the node pairs closer than twice the spacing
+    std::vector<std::tuple<double, size_t, size_t>> candidates;
+    {
+        PointGrid grid(graph.nodes, 2 * spacing);
+        std::vector<size_t> neighbors;
+        for (size_t a = 0; a < graph.nodes.size(); a++)
+        {
+            grid.radiusSearch(graph.nodes[a], 2 * spacing, neighbors);
+            for (size_t b : neighbors)
+            {
+                if (a < b)
+                {
+                    candidates.emplace_back((graph.nodes[a] - graph.nodes[b]).norm(), a, b);
+                }
+            }
+        }
+    }
+    std::sort(candidates.begin(), candidates.end());
+
+    std::vector<size_t> parent(graph.nodes.size());
+    std::iota(parent.begin(), parent.end(), 0);
+    auto find = [&parent](size_t x)
+    {
+        while (parent[x] != x)
+        {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        return x;
+    };
+    for (const auto& c : candidates)
+    {
+        const size_t ra = find(std::get<1>(c));
+        const size_t rb = find(std::get<2>(c));
+        if (ra != rb)
+        {
+            parent[ra] = rb;
+            graph.addEdge(std::get<1>(c), std::get<2>(c));
+        }
+    }
+
+    pruneBranches(graph, pruneLength);
+    std::vector<Polyline> skeleton = tracePolylines(graph, "skeleton");
+
+    lvr2::logout::get() << lvr2::info << "[Skeleton] Extracted " << skeleton.size() << " skeleton curves from "
+                        << graph.nodes.size() << " nodes." << lvr2::endl;
+    return skeleton;
+}
+
+} // namespace lvr2
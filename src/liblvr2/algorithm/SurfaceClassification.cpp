@@ -0,0 +1,150 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * SurfaceClassification.cpp
+ */
+
+#include "lvr2/algorithm/SurfaceClassification.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <queue>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+SurfaceClass classifySurfaceNormal(const Vector3d& normal, double maxWallTilt, double maxHorizontalTilt)
+{
+    const double up = normal.normalized().z();
+    if (!std::isfinite(up))
+    {
+        return SurfaceClass::Other;
+    }
+    if (std::abs(up) <= std::sin(maxWallTilt * M_PI / 180.0))
+    {
+        return SurfaceClass::Wall;
+    }
+    const double cosTilt = std::cos(maxHorizontalTilt * M_PI / 180.0);
+    if (up >= cosTilt)
+    {
+        return SurfaceClass::Floor;
+    }
+    if (up <= -cosTilt)
+    {
+        return SurfaceClass::Ceiling;
+    }
+    return SurfaceClass::Other;
+}
+
+std::vector<Vector3d> areaWeightedFaceNormals(MeshBufferPtr mesh, const std::string& regionChannel)
+{
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](unsigned int i) { return Vector3d(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]); };
+
+    std::vector<Vector3d> normals(numFaces);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const Vector3d a = vertex(faces[3 * f]);
+        normals[f] = (vertex(faces[3 * f + 1]) - a).cross(vertex(faces[3 * f + 2]) - a);
+    }
+
+    IndexChannelOptional regions = mesh->getIndexChannel(regionChannel);
+    if (regions)
+    {
+        std::unordered_map<unsigned int, Vector3d> regionNormals;
+        for (size_t f = 0; f < numFaces; f++)
+        {
+            auto it = regionNormals.emplace((*regions)[f][0], Vector3d::Zero()).first;
+            it->second += normals[f];
+        }
+        for (size_t f = 0; f < numFaces; f++)
+        {
+            normals[f] = regionNormals[(*regions)[f][0]];
+        }
+    }
+    return normals;
+}
+
+size_t growPlanarRegions(MeshBufferPtr mesh, double maxAngle, const std::string& regionChannel)
+{
+    const size_t numFaces = mesh->numFaces();
+    MeshAdjacencyPtr adjacency = mesh->getAdjacency();
+    if (!adjacency)
+    {
+        return 0;
+    }
+
+    // Per face normals, the region channel must not be used here
+    const std::vector<Vector3d> normals = areaWeightedFaceNormals(mesh, "");
+
+    const double minCos = std::cos(maxAngle * M_PI / 180.0);
+    const unsigned int unassigned = std::numeric_limits<unsigned int>::max();
+    indexArray regions(new unsigned int[numFaces]);
+    std::fill(regions.get(), regions.get() + numFaces, unassigned);
+    unsigned int numRegions = 0;
+    for (size_t seed = 0; seed < numFaces; seed++)
+    {
+        if (regions[seed] != unassigned)
+        {
+            continue;
+        }
+
+        const unsigned int region = numRegions++;
+        Vector3d sum = normals[seed];
+        regions[seed] = region;
+        std::queue<size_t> queue;
+        queue.push(seed);
+        while (!queue.empty())
+        {
+            const size_t f = queue.front();
+            queue.pop();
+            for (unsigned int n : adjacency->faceNeighbors(f))
+            {
+                if (regions[n] != unassigned || normals[n].squaredNorm() == 0 || sum.squaredNorm() == 0)
+                {
+                    continue;
+                }
+                if (normals[n].normalized().dot(sum.normalized()) >= minCos)
+                {
+                    regions[n] = region;
+                    sum += normals[n];
+                    queue.push(n);
+                }
+            }
+        }
+    }
+
+    mesh->addIndexChannel(regions, regionChannel, numFaces, 1);
+    return numRegions;
+}
+
+} // namespace lvr2
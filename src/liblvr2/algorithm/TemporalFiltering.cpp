@@ -0,0 +1,186 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * TemporalFiltering.cpp
+ */
+
+#include "lvr2/algorithm/TemporalFiltering.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Returns the time stamps of the points or an empty array if the
+/// channel does not exist or does not match the number of points
+doubleArr getTimestamps(PointBufferPtr buffer, const std::string& channel)
+{
+    size_t n = 0;
+    size_t w = 0;
+    doubleArr timestamps = buffer->getArray<double>(channel, n, w);
+    if(!timestamps || n != buffer->numPoints() || w != 1)
+    {
+        lvr2::logout::get() << lvr2::warning << "[TemporalFiltering] Point buffer has no valid channel '" << channel << "'" << lvr2::endl;
+        return doubleArr();
+    }
+    return timestamps;
+}
+
+} // anonymous namespace
+
+bool timeRange(
+    PointBufferPtr buffer,
+    double& start,
+    double& end,
+    const std::string& channel)
+{
+    doubleArr timestamps = getTimestamps(buffer, channel);
+    if(!timestamps)
+    {
+        return false;
+    }
+
+    start = std::numeric_limits<double>::infinity();
+    end = -std::numeric_limits<double>::infinity();
+    for(size_t i = 0; i < buffer->numPoints(); i++)
+    {
+        start = std::min(start, timestamps[i]);
+        end = std::max(end, timestamps[i]);
+    }
+    return start <= end;
+}
+
+PointBufferPtr filterByTime(
+    PointBufferPtr buffer,
+    double start,
+    double end,
+    const std::string& channel)
+{
+    doubleArr timestamps = getTimestamps(buffer, channel);
+    if(!timestamps)
+    {
+        return PointBufferPtr();
+    }
+
+    std::vector<size_t> indices;
+    for(size_t i = 0; i < buffer->numPoints(); i++)
+    {
+        if(timestamps[i] >= start && timestamps[i] <= end)
+        {
+            indices.push_back(i);
+        }
+    }
+    return std::make_shared<PointBuffer>(buffer->select(indices));
+}
+
+std::vector<PointBufferPtr> splitByTime(
+    PointBufferPtr buffer,
+    double windowLength,
+    const std::string& channel)
+{
+    std::vector<PointBufferPtr> windows;
+    double start, end;
+    if(windowLength <= 0 || !timeRange(buffer, start, end, channel))
+    {
+        return windows;
+    }
+
+    doubleArr timestamps = getTimestamps(buffer, channel);
+    const size_t numWindows = static_cast<size_t>((end - start) / windowLength) + 1;
+    std::vector<std::vector<size_t>> indices(numWindows);
+    for(size_t i = 0; i < buffer->numPoints(); i++)
+    {
+        const size_t w = std::min(static_cast<size_t>((timestamps[i] - start) / windowLength), numWindows - 1);
+        indices[w].push_back(i);
+    }
+
+    for(const std::vector<size_t>& window : indices)
+    {
+        if(!window.empty())
+        {
+            windows.push_back(std::make_shared<PointBuffer>(buffer->select(window)));
+        }
+    }
+    return windows;
+}
+
+bool deskewPoints(
+    PointBufferPtr buffer,
+    const Trajectory& trajectory,
+    double referenceTime,
+    const std::string& channel)
+{
+    doubleArr timestamps = getTimestamps(buffer, channel);
+    if(!timestamps || trajectory.empty())
+    {
+        return false;
+    }
+
+    Transformd reference = Transformd::Identity();
+    if(!std::isnan(referenceTime))
+    {
+        reference = trajectory.interpolate(referenceTime).inverse();
+    }
+
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    floatArr normals = buffer->hasNormals() ? buffer->getNormalArray() : floatArr();
+
+    #pragma omp parallel for
+    for(size_t i = 0; i < n; i++)
+    {
+        const Transformd T = reference * trajectory.interpolate(timestamps[i]);
+
+        const Vector3d p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        const Vector3d q = T.block<3, 3>(0, 0) * p + T.block<3, 1>(0, 3);
+        points[3 * i]     = q.x();
+        points[3 * i + 1] = q.y();
+        points[3 * i + 2] = q.z();
+
+        if(normals)
+        {
+            const Vector3d nrm(normals[3 * i], normals[3 * i + 1], normals[3 * i + 2]);
+            const Vector3d r = T.block<3, 3>(0, 0) * nrm;
+            normals[3 * i]     = r.x();
+            normals[3 * i + 1] = r.y();
+            normals[3 * i + 2] = r.z();
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[TemporalFiltering] Deskewed " << n << " points" << lvr2::endl;
+    return true;
+}
+
+} // namespace lvr2
@@ -0,0 +1,547 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * UVAtlas.cpp
+ */
+
+#include "lvr2/algorithm/UVAtlas.hpp"
+#include "lvr2/algorithm/pmp/SurfaceParameterization.h"
+#include "lvr2/geometry/pmp/SurfaceMesh.h"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <numeric>
+#include <stdexcept>
+#include <queue>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+namespace
+{
+
+struct Chart
+{
+    /// Indices of the faces of the chart
+    std::vector<size_t> faces;
+
+    /// Global indices of the chart's vertices
+    std::vector<unsigned int> vertices;
+
+    /// Texture coordinates of the chart's vertices
+    std::vector<Vector2d> uv;
+
+    /// Extent of the texture coordinates after normalization
+    Vector2d size = Vector2d::Zero();
+};
+
+Vector3d vertexAt(const floatArr& vertices, unsigned int i)
+{
+    return Vector3d(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+}
+
+/// Unnormalized face normal, its length is twice the face area
+Vector3d faceNormal(const floatArr& vertices, const indexArray& faces, size_t f)
+{
+    const Vector3d a = vertexAt(vertices, faces[3 * f]);
+    const Vector3d b = vertexAt(vertices, faces[3 * f + 1]);
+    const Vector3d c = vertexAt(vertices, faces[3 * f + 2]);
+    return (b - a).cross(c - a);
+}
+
+/// Flattens the chart with LSCM, returns false if the chart is not a topological disk
+bool flattenLSCM(const floatArr& vertices, const indexArray& faces, Chart& chart,
+                 const std::unordered_map<unsigned int, unsigned int>& local)
+{
+    pmp::SurfaceMesh mesh;
+    for (unsigned int v : chart.vertices)
+    {
+        mesh.add_vertex(pmp::Point(vertices[3 * v], vertices[3 * v + 1], vertices[3 * v + 2]));
+    }
+
+    try
+    {
+        for (size_t f : chart.faces)
+        {
+            mesh.add_triangle(
+                pmp::Vertex(local.at(faces[3 * f])),
+                pmp::Vertex(local.at(faces[3 * f + 1])),
+                pmp::Vertex(local.at(faces[3 * f + 2]))
+            );
+        }
+
+        pmp::SurfaceParameterization parameterization(mesh);
+        parameterization.lscm();
+    }
+    catch (const std::exception&)
+    {
+        return false;
+    }
+
+    auto tex = mesh.vertex_property<pmp::TexCoord>("v:tex");
+    chart.uv.resize(chart.vertices.size());
+    for (size_t i = 0; i < chart.vertices.size(); i++)
+    {
+        const pmp::TexCoord& t = tex[pmp::Vertex(i)];
+        if (!std::isfinite(t[0]) || !std::isfinite(t[1]))
+        {
+            return false;
+        }
+        chart.uv[i] = Vector2d(t[0], t[1]);
+    }
+    return true;
+}
+
+/// Projects the chart onto the plane orthogonal to its average normal
+void flattenPlanar(const floatArr& vertices, const indexArray& faces, Chart& chart)
+{
+    Vector3d normal = Vector3d::Zero();
+    for (size_t f : chart.faces)
+    {
+        normal += faceNormal(vertices, faces, f);
+    }
+    normal = normal.squaredNorm() > 0 ? normal.normalized() : Vector3d::UnitZ();
+
+    const Vector3d u = normal.unitOrthogonal();
+    const Vector3d v = normal.cross(u);
+    chart.uv.resize(chart.vertices.size());
+    for (size_t i = 0; i < chart.vertices.size(); i++)
+    {
+        const Vector3d p = vertexAt(vertices, chart.vertices[i]);
+        chart.uv[i] = Vector2d(p.dot(u), p.dot(v));
+    }
+}
+
+/// Scales the chart to its surface area and moves it to the origin
+void normalizeChart(const floatArr& vertices, const indexArray& faces, Chart& chart,
+                    const std::unordered_map<unsigned int, unsigned int>& local)
+{
+    double area3d = 0.0;
+    double area2d = 0.0;
+    for (size_t f : chart.faces)
+    {
+        area3d += 0.5 * faceNormal(vertices, faces, f).norm();
+
+        const Vector2d& a = chart.uv[local.at(faces[3 * f])];
+        const Vector2d& b = chart.uv[local.at(faces[3 * f + 1])];
+        const Vector2d& c = chart.uv[local.at(faces[3 * f + 2])];
+        const Vector2d ab = b - a;
+        const Vector2d ac = c - a;
+        area2d += 0.5 * std::abs(ab.x() * ac.y() - ab.y() * ac.x());
+    }
+
+    const double scale = area2d > 0 ? std::sqrt(area3d / area2d) : 1.0;
+    Vector2d min = Vector2d::Constant(std::numeric_limits<double>::max());
+    Vector2d max = Vector2d::Constant(std::numeric_limits<double>::lowest());
+    for (Vector2d& t : chart.uv)
+    {
+        t *= scale;
+        min = min.cwiseMin(t);
+        max = max.cwiseMax(t);
+    }
+    for (Vector2d& t : chart.uv)
+    {
+        t -= min;
+    }
+    chart.size = max - min;
+}
+
+/// Shelf packing of the chart bounding boxes into the unit square
+void packCharts(std::vector<Chart>& charts, float padding)
+{
+    double area = 0.0;
+    double maxWidth = 0.0;
+    for (const Chart& c : charts)
+    {
+        area += c.size.x() * c.size.y();
+        maxWidth = std::max(maxWidth, c.size.x());
+    }
+    const double pad = padding * std::sqrt(std::max(area, 1e-12));
+
+    std::vector<size_t> order(charts.size());
+    std::iota(order.begin(), order.end(), 0);
+    std::sort(order.begin(), order.end(), [&](size_t a, size_t b)
+    {
+        return charts[a].size.y() > charts[b].size.y();
+    });
+
+    // Aim for a square atlas
+    const double rowWidth = std::max(maxWidth + pad, std::sqrt(area) * 1.1);
+    double x = 0.0;
+    double y = 0.0;
+    double rowHeight = 0.0;
+    double usedWidth = 0.0;
+    std::vector<Vector2d> offsets(charts.size());
+    for (size_t i : order)
+    {
+        const Chart& c = charts[i];
+        if (x > 0 && x + c.size.x() > rowWidth)
+        {
+            x = 0.0;
+            y += rowHeight + pad;
+            rowHeight = 0.0;
+        }
+        offsets[i] = Vector2d(x, y);
+        x += c.size.x() + pad;
+        usedWidth = std::max(usedWidth, x);
+        rowHeight = std::max(rowHeight, c.size.y());
+    }
+
+    const double atlasSize = std::max(std::max(usedWidth, y + rowHeight), 1e-12);
+    for (size_t i = 0; i < charts.size(); i++)
+    {
+        for (Vector2d& t : charts[i].uv)
+        {
+            t = (t + offsets[i]) / atlasSize;
+        }
+    }
+}
+
+/**
+ * Shelf packing of the charts into square pages of the given resolution.
+ * The texture coordinates are normalized to their page.
+ *
+ * @return The page of each chart
+ */
+std::vector<unsigned int> packChartsIntoPages(std::vector<Chart>& charts, const UVAtlasOptions& options, size_t& numPages)
+{
+    const double res = options.resolution;
+    const double pad = std::max(0, options.paddingTexels);
+    const double texel = options.texelSize > 0 ? options.texelSize : 1.0;
+    const double maxSize = res - 2 * pad;
+    if (maxSize <= 0)
+    {
+        throw std::invalid_argument("[UVAtlas] Padding is too large for the atlas resolution");
+    }
+
+    // Convert to texels, downscale charts that do not fit on a page
+    size_t scaled = 0;
+    for (Chart& c : charts)
+    {
+        double s = 1.0 / texel;
+        const double extent = std::max(c.size.x(), c.size.y()) * s;
+        if (extent > maxSize)
+        {
+            s *= maxSize / extent;
+            scaled++;
+        }
+        for (Vector2d& t : c.uv)
+        {
+            t *= s;
+        }
+        c.size *= s;
+    }
+    if (scaled > 0)
+    {
+        lvr2::logout::get() << lvr2::warning << "[UVAtlas] " << scaled << " charts are larger than an atlas page "
+                            << "and were scaled down." << lvr2::endl;
+    }
+
+    std::vector<size_t> order(charts.size());
+    std::iota(order.begin(), order.end(), 0);
+    std::sort(order.begin(), order.end(), [&](size_t a, size_t b)
+    {
+        return charts[a].size.y() > charts[b].size.y();
+    });
+
+    std::vector<unsigned int> pages(charts.size());
+    numPages = charts.empty() ? 0 : 1;
+    double x = pad;
+    double y = pad;
+    double rowHeight = 0.0;
+    for (size_t i : order)
+    {
+        Chart& c = charts[i];
+        const double w = std::ceil(c.size.x());
+        const double h = std::ceil(c.size.y());
+
+        if (x > pad && x + w + pad > res)
+        {
+            // Next row
+            x = pad;
+            y += rowHeight + pad;
+            rowHeight = 0.0;
+        }
+        if (y + h + pad > res)
+        {
+            // Next page
+            numPages++;
+            x = pad;
+            y = pad;
+            rowHeight = 0.0;
+        }
+
+        pages[i] = numPages - 1;
+        for (Vector2d& t : c.uv)
+        {
+            t = (t + Vector2d(x, y)) / res;
+        }
+        x += w + pad;
+        rowHeight = std::max(rowHeight, h);
+    }
+    return pages;
+}
+
+} // anonymous namespace
+
+std::vector<uint32_t> segmentCharts(MeshBufferPtr mesh, float maxChartAngle)
+{
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+
+    MeshAdjacencyPtr adjacency = mesh->getAdjacency();
+
+    std::vector<Vector3d> normals(numFaces);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        normals[f] = faceNormal(vertices, faces, f);
+    }
+
+    const double minCos = std::cos(maxChartAngle * M_PI / 180.0);
+    const uint32_t unassigned = std::numeric_limits<uint32_t>::max();
+    std::vector<uint32_t> charts(numFaces, unassigned);
+    uint32_t numCharts = 0;
+    for (size_t seed = 0; seed < numFaces; seed++)
+    {
+        if (charts[seed] != unassigned)
+        {
+            continue;
+        }
+
+        // Grow the chart while the faces stay within the cone around the area weighted average normal
+        const uint32_t chart = numCharts++;
+        Vector3d sum = normals[seed];
+        charts[seed] = chart;
+        std::queue<size_t> queue;
+        queue.push(seed);
+        while (!queue.empty())
+        {
+            const size_t f = queue.front();
+            queue.pop();
+            for (unsigned int n : adjacency->faceNeighbors(f))
+            {
+                if (charts[n] != unassigned || normals[n].squaredNorm() == 0)
+                {
+                    continue;
+                }
+                const Vector3d avg = sum.normalized();
+                if (normals[n].normalized().dot(avg) >= minCos)
+                {
+                    charts[n] = chart;
+                    sum += normals[n];
+                    queue.push(n);
+                }
+            }
+        }
+    }
+
+    lvr2::logout::get() << lvr2::info << "[UVAtlas] Segmented mesh into " << numCharts << " charts." << lvr2::endl;
+    return charts;
+}
+
+MeshBufferPtr parameterizeCharts(
+    MeshBufferPtr mesh,
+    const std::vector<uint32_t>& faceCharts,
+    const UVAtlasOptions& options)
+{
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    if (faceCharts.size() != numFaces)
+    {
+        throw std::invalid_argument("[UVAtlas] Number of chart indices does not match the number of faces");
+    }
+
+    // Collect the faces and vertices of each chart
+    std::unordered_map<uint32_t, size_t> chartIndex;
+    std::vector<Chart> charts;
+    std::vector<std::unordered_map<unsigned int, unsigned int>> local;
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        auto it = chartIndex.emplace(faceCharts[f], charts.size()).first;
+        if (it->second == charts.size())
+        {
+            charts.emplace_back();
+            local.emplace_back();
+        }
+
+        Chart& chart = charts[it->second];
+        chart.faces.push_back(f);
+        for (int i = 0; i < 3; i++)
+        {
+            const unsigned int v = faces[3 * f + i];
+            if (local[it->second].emplace(v, chart.vertices.size()).second)
+            {
+                chart.vertices.push_back(v);
+            }
+        }
+    }
+
+    size_t fallbacks = 0;
+    #pragma omp parallel for schedule(dynamic) reduction(+:fallbacks)
+    for (size_t c = 0; c < charts.size(); c++)
+    {
+        if (!flattenLSCM(vertices, faces, charts[c], local[c]))
+        {
+            flattenPlanar(vertices, faces, charts[c]);
+            fallbacks++;
+        }
+        normalizeChart(vertices, faces, charts[c], local[c]);
+    }
+
+    if (fallbacks > 0)
+    {
+        lvr2::logout::get() << lvr2::warning << "[UVAtlas] " << fallbacks << " of " << charts.size()
+                            << " charts could not be flattened with LSCM and were projected." << lvr2::endl;
+    }
+
+    size_t numPages = 0;
+    std::vector<unsigned int> chartPages;
+    if (options.resolution > 0)
+    {
+        chartPages = packChartsIntoPages(charts, options, numPages);
+        lvr2::logout::get() << lvr2::info << "[UVAtlas] Packed " << charts.size() << " charts into "
+                            << numPages << " pages." << lvr2::endl;
+    }
+    else
+    {
+        packCharts(charts, options.padding);
+    }
+
+    // Build the output mesh, vertices on seams are duplicated for each chart
+    std::vector<size_t> firstVertex(charts.size());
+    size_t numVertices = 0;
+    for (size_t c = 0; c < charts.size(); c++)
+    {
+        firstVertex[c] = numVertices;
+        numVertices += charts[c].vertices.size();
+    }
+
+    floatArr outVertices(new float[3 * numVertices]);
+    floatArr outTexCoords(new float[2 * numVertices]);
+    indexArray outFaces(new unsigned int[3 * numFaces]);
+    indexArray outCharts(new unsigned int[numFaces]);
+    std::vector<unsigned int> sourceVertex(numVertices);
+    for (size_t c = 0; c < charts.size(); c++)
+    {
+        const Chart& chart = charts[c];
+        for (size_t i = 0; i < chart.vertices.size(); i++)
+        {
+            const size_t v = firstVertex[c] + i;
+            sourceVertex[v] = chart.vertices[i];
+            std::copy_n(vertices.get() + 3 * chart.vertices[i], 3, outVertices.get() + 3 * v);
+            outTexCoords[2 * v] = chart.uv[i].x();
+            outTexCoords[2 * v + 1] = chart.uv[i].y();
+        }
+        for (size_t f : chart.faces)
+        {
+            for (int i = 0; i < 3; i++)
+            {
+                outFaces[3 * f + i] = firstVertex[c] + local[c].at(faces[3 * f + i]);
+            }
+            outCharts[f] = c;
+        }
+    }
+
+    MeshBufferPtr out(new MeshBuffer);
+    out->setVertices(outVertices, numVertices);
+    out->setFaceIndices(outFaces, numFaces);
+    out->setTextureCoordinates(outTexCoords);
+    out->addIndexChannel(outCharts, "face_charts", numFaces, 1);
+
+    if (mesh->hasVertexNormals())
+    {
+        floatArr normals = mesh->getVertexNormals();
+        floatArr outNormals(new float[3 * numVertices]);
+        for (size_t v = 0; v < numVertices; v++)
+        {
+            std::copy_n(normals.get() + 3 * sourceVertex[v], 3, outNormals.get() + 3 * v);
+        }
+        out->setVertexNormals(outNormals);
+    }
+
+    if (mesh->hasVertexColors())
+    {
+        size_t w;
+        ucharArr colors = mesh->getVertexColors(w);
+        ucharArr outColors(new unsigned char[w * numVertices]);
+        for (size_t v = 0; v < numVertices; v++)
+        {
+            std::copy_n(colors.get() + w * sourceVertex[v], w, outColors.get() + w * v);
+        }
+        out->setVertexColors(outColors, w);
+    }
+
+    if (options.resolution > 0)
+    {
+        // One material and texture per page
+        indexArray outMaterials(new unsigned int[numFaces]);
+        for (size_t f = 0; f < numFaces; f++)
+        {
+            outMaterials[f] = chartPages[outCharts[f]];
+        }
+        out->setFaceMaterialIndices(outMaterials);
+
+        std::vector<Material> materials(numPages);
+        std::vector<Texture> textures;
+        for (size_t p = 0; p < numPages; p++)
+        {
+            materials[p].m_texture = TextureHandle(p);
+            textures.emplace_back(p, options.resolution, options.resolution, 3, 1, options.texelSize);
+        }
+        out->setMaterials(materials);
+        out->setTextures(textures);
+    }
+    else if (indexArray materials = mesh->getFaceMaterialIndices())
+    {
+        indexArray outMaterials(new unsigned int[numFaces]);
+        std::copy_n(materials.get(), numFaces, outMaterials.get());
+        out->setFaceMaterialIndices(outMaterials);
+
+        // The setters move from their arguments, so pass copies
+        std::vector<Material> outMaterialList = mesh->getMaterials();
+        std::vector<Texture> outTextures = mesh->getTextures();
+        out->setMaterials(outMaterialList);
+        out->setTextures(outTextures);
+    }
+
+    return out;
+}
+
+MeshBufferPtr parameterize(MeshBufferPtr mesh, const UVAtlasOptions& options)
+{
+    return parameterizeCharts(mesh, segmentCharts(mesh, options.maxChartAngle), options);
+}
+
+} // namespace lvr2
@@ -0,0 +1,143 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * ViewExtraction.cpp
+ */
+
+#include "lvr2/algorithm/ViewExtraction.hpp"
+#include "lvr2/algorithm/MeshTiler.hpp"
+#include "lvr2/algorithm/raycasting/BVHRaycaster.hpp"
+#include "lvr2/util/Logging.hpp"
+
+namespace lvr2
+{
+
+std::vector<size_t> pointsInFrustum(PointBufferPtr points, const Frustum& frustum)
+{
+    std::vector<size_t> indices;
+    const size_t n = points->numPoints();
+    floatArr coords = points->getPointArray();
+    for (size_t i = 0; i < n; i++)
+    {
+        if (frustum.contains(Vector3f(coords[3 * i], coords[3 * i + 1], coords[3 * i + 2])))
+        {
+            indices.push_back(i);
+        }
+    }
+    return indices;
+}
+
+std::vector<size_t> facesInFrustum(MeshBufferPtr mesh, const Frustum& frustum)
+{
+    std::vector<size_t> indices;
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](size_t i)
+    {
+        return Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        if (frustum.intersects(vertex(faces[3 * f]), vertex(faces[3 * f + 1]), vertex(faces[3 * f + 2])))
+        {
+            indices.push_back(f);
+        }
+    }
+    return indices;
+}
+
+std::vector<size_t> visibleFaces(MeshBufferPtr mesh, const Frustum& frustum, const Vector3f& eye, float tolerance)
+{
+    std::vector<size_t> candidates = facesInFrustum(mesh, frustum);
+    if (candidates.empty())
+    {
+        return candidates;
+    }
+
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    std::vector<Vector3f> directions(candidates.size());
+    std::vector<float> distances(candidates.size());
+    for (size_t i = 0; i < candidates.size(); i++)
+    {
+        Vector3f centroid = Vector3f::Zero();
+        for (int j = 0; j < 3; j++)
+        {
+            const size_t v = faces[3 * candidates[i] + j];
+            centroid += Vector3f(vertices[3 * v], vertices[3 * v + 1], vertices[3 * v + 2]) / 3.0f;
+        }
+        distances[i] = (centroid - eye).norm();
+        directions[i] = (centroid - eye) / distances[i];
+    }
+
+    BVHRaycaster<DistInt> raycaster(mesh);
+    std::vector<DistInt> intersections;
+    std::vector<uint8_t> hits;
+    raycaster.castRays(eye, directions, intersections, hits);
+
+    std::vector<size_t> visible;
+    for (size_t i = 0; i < candidates.size(); i++)
+    {
+        // A missed ray can only happen due to numerical issues at the face borders
+        if (!hits[i] || intersections[i].dist >= distances[i] - tolerance)
+        {
+            visible.push_back(candidates[i]);
+        }
+    }
+    return visible;
+}
+
+PointBufferPtr extractFrustum(PointBufferPtr points, const Frustum& frustum)
+{
+    std::vector<size_t> indices = pointsInFrustum(points, frustum);
+    lvr2::logout::get() << lvr2::info << "[ViewExtraction] " << indices.size() << " of "
+        << points->numPoints() << " points in frustum" << lvr2::endl;
+    return std::make_shared<PointBuffer>(points->select(indices));
+}
+
+MeshBufferPtr extractFrustum(MeshBufferPtr mesh, const Frustum& frustum)
+{
+    std::vector<size_t> indices = facesInFrustum(mesh, frustum);
+    lvr2::logout::get() << lvr2::info << "[ViewExtraction] " << indices.size() << " of "
+        << mesh->numFaces() << " faces in frustum" << lvr2::endl;
+    return extractFaces(mesh, indices);
+}
+
+MeshBufferPtr extractVisible(MeshBufferPtr mesh, const Frustum& frustum, const Vector3f& eye, float tolerance)
+{
+    std::vector<size_t> indices = visibleFaces(mesh, frustum, eye, tolerance);
+    lvr2::logout::get() << lvr2::info << "[ViewExtraction] " << indices.size() << " of "
+        << mesh->numFaces() << " faces visible" << lvr2::endl;
+    return extractFaces(mesh, indices);
+}
+
+} // namespace lvr2
@@ -0,0 +1,177 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * VisibilityGrid.cpp
+ */
+
+#include "lvr2/algorithm/VisibilityGrid.hpp"
+
+#include <bitset>
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Visits all voxels on the segment from start to end (3D DDA)
+template<typename F>
+void traverse(const VisibilityGrid& grid, const Vector3d& start, const Vector3d& end, double voxelSize, F visit)
+{
+    const Vector3d dir = end - start;
+    const double length = dir.norm();
+    Vector3i voxel = grid.voxel(start);
+    const Vector3i last = grid.voxel(end);
+    if (length == 0)
+    {
+        visit(voxel);
+        return;
+    }
+
+    Vector3i step;
+    Vector3d tMax, tDelta;
+    for (int i = 0; i < 3; i++)
+    {
+        const double d = dir[i] / length;
+        if (d > 0)
+        {
+            step[i] = 1;
+            tMax[i] = ((voxel[i] + 1) * voxelSize - start[i]) / d;
+            tDelta[i] = voxelSize / d;
+        }
+        else if (d < 0)
+        {
+            step[i] = -1;
+            tMax[i] = (voxel[i] * voxelSize - start[i]) / d;
+            tDelta[i] = -voxelSize / d;
+        }
+        else
+        {
+            step[i] = 0;
+            tMax[i] = std::numeric_limits<double>::infinity();
+            tDelta[i] = std::numeric_limits<double>::infinity();
+        }
+    }
+
+    visit(voxel);
+    while (voxel != last)
+    {
+        int axis = 0;
+        if (tMax[1] < tMax[axis])
+        {
+            axis = 1;
+        }
+        if (tMax[2] < tMax[axis])
+        {
+            axis = 2;
+        }
+        if (tMax[axis] > length)
+        {
+            break;
+        }
+        voxel[axis] += step[axis];
+        tMax[axis] += tDelta[axis];
+        visit(voxel);
+    }
+}
+
+} // anonymous namespace
+
+VisibilityGrid::VisibilityGrid(double voxelSize, double margin)
+    : m_voxelSize(voxelSize), m_margin(margin)
+{
+}
+
+bool VisibilityGrid::addScan(PointBufferPtr scan, const Vector3d& origin)
+{
+    if (m_numScans >= MaxScans)
+    {
+        return false;
+    }
+
+    const uint64_t bit = scanBit(m_numScans++);
+    if (!scan)
+    {
+        return true;
+    }
+
+    const size_t n = scan->numPoints();
+    floatArr points = scan->getPointArray();
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3d p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        const Vector3i target = voxel(p);
+        m_occupied[target] |= bit;
+
+        const Vector3d ray = p - origin;
+        const double range = ray.norm();
+        if (range <= m_margin)
+        {
+            continue;
+        }
+        const Vector3d end = origin + ray * ((range - m_margin) / range);
+        traverse(*this, origin, end, m_voxelSize, [&](const Vector3i& v)
+        {
+            if (v != target)
+            {
+                m_free[v] |= bit;
+            }
+        });
+    }
+    return true;
+}
+
+Vector3i VisibilityGrid::voxel(const Vector3d& p) const
+{
+    return Vector3i(
+        static_cast<int>(std::floor(p.x() / m_voxelSize)),
+        static_cast<int>(std::floor(p.y() / m_voxelSize)),
+        static_cast<int>(std::floor(p.z() / m_voxelSize)));
+}
+
+uint64_t VisibilityGrid::freeMask(const Vector3i& voxel) const
+{
+    auto it = m_free.find(voxel);
+    return it == m_free.end() ? 0 : it->second;
+}
+
+uint64_t VisibilityGrid::occupiedMask(const Vector3i& voxel) const
+{
+    auto it = m_occupied.find(voxel);
+    return it == m_occupied.end() ? 0 : it->second;
+}
+
+size_t VisibilityGrid::count(uint64_t mask)
+{
+    return std::bitset<64>(mask).count();
+}
+
+} // namespace lvr2
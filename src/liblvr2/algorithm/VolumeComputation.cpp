@@ -0,0 +1,195 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * VolumeComputation.cpp
+ */
+
+#include "lvr2/algorithm/VolumeComputation.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Accumulates the volumes of the height differences of two grids with identical layout
+VolumeResult accumulateVolumes(const HeightGrid& reference, const HeightGrid& surface, double tolerance)
+{
+    VolumeResult result;
+    result.cellVolumes = reference;
+
+    const double cellArea = reference.cellSize * reference.cellSize;
+    for (size_t i = 0; i < reference.heights.size(); i++)
+    {
+        if (reference.heights[i] == reference.noData || surface.heights[i] == surface.noData)
+        {
+            result.cellVolumes.heights[i] = reference.noData;
+            continue;
+        }
+
+        double dz = surface.heights[i] - reference.heights[i];
+        if (std::abs(dz) < tolerance)
+        {
+            dz = 0;
+        }
+
+        const double volume = dz * cellArea;
+        result.cellVolumes.heights[i] = volume;
+        result.coveredArea += cellArea;
+        if (volume > 0)
+        {
+            result.fillVolume += volume;
+            result.fillArea += cellArea;
+        }
+        else if (volume < 0)
+        {
+            result.cutVolume -= volume;
+            result.cutArea += cellArea;
+        }
+    }
+    result.netVolume = result.fillVolume - result.cutVolume;
+
+    lvr2::logout::get() << lvr2::info << "[VolumeComputation] Fill: " << result.fillVolume << " (" << result.fillArea
+                        << " area), cut: " << result.cutVolume << " (" << result.cutArea << " area), net: "
+                        << result.netVolume << lvr2::endl;
+    return result;
+}
+
+/// Height of the grid at the given position or NaN if the cell has no data
+double sampleGrid(const HeightGrid& grid, const Vector2d& p)
+{
+    const long col = (long)std::floor((p.x() - grid.originX) / grid.cellSize);
+    const long row = (long)std::floor((p.y() - grid.originY) / grid.cellSize);
+    if (col < 0 || row < 0 || col >= (long)grid.cols || row >= (long)grid.rows)
+    {
+        return std::numeric_limits<double>::quiet_NaN();
+    }
+
+    // Row 0 is the northern row
+    const float h = grid.at(grid.rows - 1 - row, col);
+    return h == grid.noData ? std::numeric_limits<double>::quiet_NaN() : h;
+}
+
+} // anonymous namespace
+
+VolumeResult computeVolumes(MeshBufferPtr reference, MeshBufferPtr surface, double cellSize, double tolerance)
+{
+    const HeightGrid referenceGrid = rasterizeHeightField(reference, cellSize);
+    const HeightGrid surfaceGrid = rasterizeHeightField(surface, referenceGrid);
+    return accumulateVolumes(referenceGrid, surfaceGrid, tolerance);
+}
+
+VolumeResult computeVolumes(
+    const Plane<BaseVector<float>>& reference,
+    MeshBufferPtr surface,
+    double cellSize,
+    double tolerance)
+{
+    const HeightGrid surfaceGrid = rasterizeHeightField(surface, cellSize);
+    HeightGrid planeGrid = surfaceGrid;
+
+    const double nz = reference.normal.z;
+    if (std::abs(nz) < 1e-9)
+    {
+        lvr2::logout::get() << lvr2::warning << "[VolumeComputation] Reference plane is vertical" << lvr2::endl;
+        planeGrid.heights.assign(planeGrid.heights.size(), planeGrid.noData);
+        return accumulateVolumes(planeGrid, surfaceGrid, tolerance);
+    }
+
+    // z = (d - nx * x - ny * y) / nz at the cell centers
+    const double d = reference.normal.x * reference.pos.x + reference.normal.y * reference.pos.y + nz * reference.pos.z;
+    for (size_t row = 0; row < planeGrid.rows; row++)
+    {
+        const double y = planeGrid.originY + (planeGrid.rows - 1 - row + 0.5) * planeGrid.cellSize;
+        for (size_t col = 0; col < planeGrid.cols; col++)
+        {
+            const double x = planeGrid.originX + (col + 0.5) * planeGrid.cellSize;
+            planeGrid.at(row, col) = (d - reference.normal.x * x - reference.normal.y * y) / nz;
+        }
+    }
+    return accumulateVolumes(planeGrid, surfaceGrid, tolerance);
+}
+
+CrossSection crossSection(
+    const HeightGrid& reference,
+    const HeightGrid& surface,
+    const Vector2d& start,
+    const Vector2d& end,
+    double spacing)
+{
+    CrossSection section;
+    const double length = (end - start).norm();
+    if (spacing <= 0)
+    {
+        spacing = std::min(reference.cellSize, surface.cellSize);
+    }
+    const size_t numSamples = (size_t)std::floor(length / spacing) + 1;
+
+    for (size_t i = 0; i < numSamples; i++)
+    {
+        const double station = std::min(i * spacing, length);
+        const Vector2d p = length > 0 ? Vector2d(start + (end - start) * (station / length)) : start;
+        section.stations.push_back(station);
+        section.referenceHeights.push_back(sampleGrid(reference, p));
+        section.surfaceHeights.push_back(sampleGrid(surface, p));
+    }
+
+    // Trapezoidal integration, split at the crossings of both profiles
+    for (size_t i = 0; i + 1 < numSamples; i++)
+    {
+        const double d0 = section.surfaceHeights[i] - section.referenceHeights[i];
+        const double d1 = section.surfaceHeights[i + 1] - section.referenceHeights[i + 1];
+        const double w = section.stations[i + 1] - section.stations[i];
+        if (std::isnan(d0) || std::isnan(d1) || w <= 0)
+        {
+            continue;
+        }
+
+        if ((d0 >= 0) == (d1 >= 0))
+        {
+            const double area = 0.5 * (d0 + d1) * w;
+            (area >= 0 ? section.fillArea : section.cutArea) += std::abs(area);
+        }
+        else
+        {
+            const double t = d0 / (d0 - d1);
+            const double a0 = 0.5 * d0 * t * w;
+            const double a1 = 0.5 * d1 * (1 - t) * w;
+            (a0 >= 0 ? section.fillArea : section.cutArea) += std::abs(a0);
+            (a1 >= 0 ? section.fillArea : section.cutArea) += std::abs(a1);
+        }
+    }
+    return section;
+}
+
+} // namespace lvr2
@@ -23,6 +23,9 @@ SurfaceSimplification::SurfaceSimplification(SurfaceMesh& mesh, bool keep_quadri
     max_valence_ = 0;
     normal_deviation_ = 0;
     hausdorff_error_ = 0;
+    has_constraints_ = false;
+    placement_ = VertexPlacement::Endpoint;
+    max_error_ = 0;
 
     // add properties
     had_quadrics_ = mesh_.has_vertex_property("v:quadric");
@@ -44,6 +47,8 @@ SurfaceSimplification::~SurfaceSimplification()
         mesh_.remove_vertex_property(vquadric_);
     mesh_.remove_face_property(normal_cone_);
     mesh_.remove_face_property(face_points_);
+    mesh_.remove_vertex_property(vconstraint_);
+    mesh_.remove_edge_property(econstraint_);
 }
 
 void SurfaceSimplification::initialize(Scalar aspect_ratio, Scalar edge_length,
@@ -99,6 +104,9 @@ void SurfaceSimplification::initialize(Scalar aspect_ratio, Scalar edge_length,
         }
     }
 
+    // label, material and texture boundaries
+    initialize_constraints();
+
     // initialize quadrics
     if (!had_quadrics_)
     {
@@ -174,6 +182,7 @@ bool SurfaceSimplification::simplify(unsigned int n_vertices)
         }
 
         // perform collapse
+        vpoint_[cd.v1] = target_position(cd);
         mesh_.collapse(h);
         --nv;
 
@@ -279,6 +288,19 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
             return false;
     }
 
+    // test label, material and texture boundaries
+    if (has_constraints_)
+    {
+        if (vconstraint_[cd.v0] && !econstraint_[mesh_.edge(cd.v0v1)])
+            return false;
+
+        if (cd.vl.is_valid() && econstraint_[mesh_.edge(cd.vlv0)])
+            return false;
+
+        if (cd.vr.is_valid() && econstraint_[mesh_.edge(cd.v0vr)])
+            return false;
+    }
+
     // do not collapse boundary vertices to interior vertices
     if (mesh_.is_boundary(cd.v0) && !mesh_.is_boundary(cd.v1))
         return false;
@@ -309,6 +331,42 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
     // remember the positions of the endpoints
     const Point p0 = vpoint_[cd.v0];
     const Point p1 = vpoint_[cd.v1];
+    const Point target = target_position(cd);
+
+    // check geometric error bound
+    if (max_error_)
+    {
+        Quadric Q = vquadric_[cd.v0];
+        Q += vquadric_[cd.v1];
+        if (Q(target) > max_error_ * max_error_)
+            return false;
+    }
+
+    // faces whose shape changes by the collapse. If the remaining vertex
+    // is moved, the faces around v1 change as well.
+    std::vector<Face> faces;
+    for (auto f : mesh_.faces(cd.v0))
+    {
+        if (f != cd.fl && f != cd.fr)
+            faces.push_back(f);
+    }
+    if (placement_ != VertexPlacement::Endpoint)
+    {
+        for (auto f : mesh_.faces(cd.v1))
+        {
+            if (f != cd.fl && f != cd.fr)
+                faces.push_back(f);
+        }
+    }
+
+    auto move = [&]() {
+        vpoint_[cd.v0] = target;
+        vpoint_[cd.v1] = target;
+    };
+    auto restore = [&]() {
+        vpoint_[cd.v0] = p0;
+        vpoint_[cd.v1] = p1;
+    };
 
     // check for maximum edge length
     if (edge_length_)
@@ -317,7 +375,15 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
         {
             if (v != cd.v1 && v != cd.vl && v != cd.vr)
             {
-                if ((vpoint_[v] - p1).norm() > edge_length_)
+                if ((vpoint_[v] - target).norm() > edge_length_)
+                    return false;
+            }
+        }
+        if (placement_ != VertexPlacement::Endpoint)
+        {
+            for (auto v : mesh_.vertices(cd.v1))
+            {
+                if (v != cd.v0 && (vpoint_[v] - target).norm() > edge_length_)
                     return false;
             }
         }
@@ -326,27 +392,24 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
     // check for flipping normals
     if (normal_deviation_ == 0.0)
     {
-        vpoint_[cd.v0] = p1;
-        for (auto f : mesh_.faces(cd.v0))
+        move();
+        for (auto f : faces)
         {
-            if (f != cd.fl && f != cd.fr)
+            Normal n0 = fnormal_[f];
+            Normal n1 = SurfaceNormals::compute_face_normal(mesh_, f);
+            if (n0.dot(n1) < 0.0)
             {
-                Normal n0 = fnormal_[f];
-                Normal n1 = SurfaceNormals::compute_face_normal(mesh_, f);
-                if (n0.dot(n1) < 0.0)
-                {
-                    vpoint_[cd.v0] = p0;
-                    return false;
-                }
+                restore();
+                return false;
             }
         }
-        vpoint_[cd.v0] = p0;
+        restore();
     }
 
     // check normal cone
     else
     {
-        vpoint_[cd.v0] = p1;
+        move();
 
         Face fll, frr;
         if (cd.vl.is_valid())
@@ -356,27 +419,24 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
             frr = mesh_.face(
                 mesh_.opposite_halfedge(mesh_.next_halfedge(cd.v1v0)));
 
-        for (auto f : mesh_.faces(cd.v0))
+        for (auto f : faces)
         {
-            if (f != cd.fl && f != cd.fr)
-            {
-                NormalCone nc = normal_cone_[f];
-                nc.merge(SurfaceNormals::compute_face_normal(mesh_, f));
+            NormalCone nc = normal_cone_[f];
+            nc.merge(SurfaceNormals::compute_face_normal(mesh_, f));
 
-                if (f == fll)
-                    nc.merge(normal_cone_[cd.fl]);
-                if (f == frr)
-                    nc.merge(normal_cone_[cd.fr]);
+            if (f == fll)
+                nc.merge(normal_cone_[cd.fl]);
+            if (f == frr)
+                nc.merge(normal_cone_[cd.fr]);
 
-                if (nc.angle() > 0.5 * normal_deviation_)
-                {
-                    vpoint_[cd.v0] = p0;
-                    return false;
-                }
+            if (nc.angle() > 0.5 * normal_deviation_)
+            {
+                restore();
+                return false;
             }
         }
 
-        vpoint_[cd.v0] = p0;
+        restore();
     }
 
     // check aspect ratio
@@ -384,17 +444,14 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
     {
         Scalar ar0(0), ar1(0);
 
-        for (auto f : mesh_.faces(cd.v0))
+        for (auto f : faces)
         {
-            if (f != cd.fl && f != cd.fr)
-            {
-                // worst aspect ratio after collapse
-                vpoint_[cd.v0] = p1;
-                ar1 = std::max(ar1, aspect_ratio(f));
-                // worst aspect ratio before collapse
-                vpoint_[cd.v0] = p0;
-                ar0 = std::max(ar0, aspect_ratio(f));
-            }
+            // worst aspect ratio after collapse
+            move();
+            ar1 = std::max(ar1, aspect_ratio(f));
+            // worst aspect ratio before collapse
+            restore();
+            ar0 = std::max(ar0, aspect_ratio(f));
         }
 
         // aspect ratio is too bad, and it does also not improve
@@ -414,45 +471,130 @@ bool SurfaceSimplification::is_collapse_legal(const CollapseData& cd)
             std::copy(face_points_[f].begin(), face_points_[f].end(),
                       std::back_inserter(points));
         }
-        points.push_back(vpoint_[cd.v0]);
+        if (placement_ != VertexPlacement::Endpoint)
+        {
+            for (auto f : mesh_.faces(cd.v1))
+            {
+                if (f != cd.fl && f != cd.fr)
+                    std::copy(face_points_[f].begin(), face_points_[f].end(),
+                              std::back_inserter(points));
+            }
+            points.push_back(p1);
+        }
+        points.push_back(p0);
 
         // test points against all faces
-        vpoint_[cd.v0] = p1;
+        move();
         for (auto point : points)
         {
             ok = false;
 
-            for (auto f : mesh_.faces(cd.v0))
+            for (auto f : faces)
             {
-                if (f != cd.fl && f != cd.fr)
+                if (distance(f, point) < hausdorff_error_)
                 {
-                    if (distance(f, point) < hausdorff_error_)
-                    {
-                        ok = true;
-                        break;
-                    }
+                    ok = true;
+                    break;
                 }
             }
 
             if (!ok)
             {
-                vpoint_[cd.v0] = p0;
+                restore();
                 return false;
             }
         }
-        vpoint_[cd.v0] = p0;
+        restore();
     }
 
     // collapse passed all tests -> ok
     return true;
 }
 
+void SurfaceSimplification::initialize_constraints()
+{
+    has_constraints_ = false;
+    mesh_.remove_vertex_property(vconstraint_);
+    mesh_.remove_edge_property(econstraint_);
+
+    auto fregion = mesh_.get_face_property<IndexType>("f:region");
+    auto fmaterial = mesh_.get_face_property<IndexType>("f:material");
+    auto htex = mesh_.get_halfedge_property<TexCoord>("h:tex");
+    if (!fregion && !fmaterial && !htex)
+        return;
+
+    vconstraint_ = mesh_.add_vertex_property<bool>("v:simplification_constraint", false);
+    econstraint_ = mesh_.add_edge_property<bool>("e:simplification_constraint", false);
+
+    for (auto e : mesh_.edges())
+    {
+        if (mesh_.is_boundary(e))
+            continue;
+
+        Halfedge h0 = mesh_.halfedge(e, 0);
+        Halfedge h1 = mesh_.halfedge(e, 1);
+        Face f0 = mesh_.face(h0);
+        Face f1 = mesh_.face(h1);
+
+        bool constraint = false;
+        if (fregion && fregion[f0] != fregion[f1])
+            constraint = true;
+        if (fmaterial && fmaterial[f0] != fmaterial[f1])
+            constraint = true;
+
+        // h:tex stores the texture coordinate of the target vertex in the
+        // halfedge's face
+        if (htex && (htex[h0] != htex[mesh_.prev_halfedge(h1)] ||
+                     htex[h1] != htex[mesh_.prev_halfedge(h0)]))
+            constraint = true;
+
+        if (constraint)
+        {
+            econstraint_[e] = true;
+            vconstraint_[mesh_.vertex(e, 0)] = true;
+            vconstraint_[mesh_.vertex(e, 1)] = true;
+            has_constraints_ = true;
+        }
+    }
+}
+
 float SurfaceSimplification::priority(const CollapseData& cd)
 {
     // computer quadric error metric
     Quadric Q = vquadric_[cd.v0];
     Q += vquadric_[cd.v1];
-    return Q(vpoint_[cd.v1]);
+    return Q(target_position(cd));
+}
+
+Point SurfaceSimplification::target_position(const CollapseData& cd) const
+{
+    const Point& p0 = vpoint_[cd.v0];
+    const Point& p1 = vpoint_[cd.v1];
+
+    // vertices on boundaries, features or constraints must not be moved
+    if (mesh_.is_boundary(cd.v1) || (has_features_ && vfeature_[cd.v1]) ||
+        (has_constraints_ && vconstraint_[cd.v1]) ||
+        (has_selection_ && !vselected_[cd.v1]))
+        return p1;
+
+    switch (placement_)
+    {
+        case VertexPlacement::Midpoint:
+            return 0.5 * (p0 + p1);
+
+        case VertexPlacement::Optimal:
+        {
+            Quadric Q = vquadric_[cd.v0];
+            Q += vquadric_[cd.v1];
+            Point p;
+            if (Q.optimize(p))
+                return p;
+            return 0.5 * (p0 + p1);
+        }
+
+        default:
+            return p1;
+    }
 }
 
 void SurfaceSimplification::postprocess_collapse(const CollapseData& cd)
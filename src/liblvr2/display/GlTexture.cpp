@@ -73,17 +73,11 @@ GlTexture::GlTexture(const Texture &other)
     for (size_t i = 0; i < m_width * m_height; i++)
     {
             size_t current_idx = i * other.m_numChannels * other.m_numBytesPerChan;
-            if (other.m_numChannels > 0)
+            for (size_t c = 0; c < 3 && other.m_numChannels > 0; c++)
             {
-                m_pixels[i*3 + 0] = other.m_data[current_idx + other.m_numBytesPerChan * 0];
-            }
-            if (other.m_numChannels > 1)
-            {
-                m_pixels[i*3 + 1] = other.m_data[current_idx + other.m_numBytesPerChan * 1];
-            }
-            if (other.m_numChannels > 2)
-            {
-                m_pixels[i*3 + 2] = other.m_data[current_idx + other.m_numBytesPerChan * 2];
+                // Grayscale textures (with or without alpha) are replicated
+                size_t channel = other.m_numChannels >= 3 ? c : 0;
+                m_pixels[i*3 + c] = other.m_data[current_idx + other.m_numBytesPerChan * channel];
             }
     }
 
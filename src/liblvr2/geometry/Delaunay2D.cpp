@@ -0,0 +1,241 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Delaunay2D.cpp
+ */
+
+#include "lvr2/geometry/Delaunay2D.hpp"
+
+#include <opencv2/imgproc.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <map>
+#include <set>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Clips the polygon against the half plane axis * sign <= bound * sign
+std::vector<Vector2d> clipPolygon(const std::vector<Vector2d>& polygon, int axis, double bound, double sign)
+{
+    std::vector<Vector2d> result;
+    auto inside = [&](const Vector2d& p) { return sign * p[axis] <= sign * bound; };
+
+    for (size_t i = 0; i < polygon.size(); i++)
+    {
+        const Vector2d& a = polygon[i];
+        const Vector2d& b = polygon[(i + 1) % polygon.size()];
+        if (inside(a))
+        {
+            result.push_back(a);
+        }
+        if (inside(a) != inside(b))
+        {
+            double t = (bound - a[axis]) / (b[axis] - a[axis]);
+            result.push_back(a + (b - a) * t);
+        }
+    }
+    return result;
+}
+
+} // anonymous namespace
+
+Delaunay2D::Delaunay2D(const std::vector<Vector2d>& points)
+    : m_points(points)
+    , m_centroid(Vector2d::Zero())
+    , m_subdiv(new cv::Subdiv2D)
+{
+    const size_t n = m_points.size();
+    if (n == 0)
+    {
+        return;
+    }
+
+    // Subdiv2D only works with float precision
+    for (const Vector2d& p : m_points)
+    {
+        m_centroid += p;
+    }
+    m_centroid /= n;
+
+    Vector2d min = Vector2d::Constant(std::numeric_limits<double>::max());
+    Vector2d max = Vector2d::Constant(std::numeric_limits<double>::lowest());
+    for (const Vector2d& p : m_points)
+    {
+        min = min.cwiseMin(p - m_centroid);
+        max = max.cwiseMax(p - m_centroid);
+    }
+    m_subdiv->initDelaunay(cv::Rect2f(min.x() - 1, min.y() - 1, max.x() - min.x() + 2, max.y() - min.y() + 2));
+
+    std::map<std::pair<float, float>, unsigned int> index;
+    for (size_t i = 0; i < n; i++)
+    {
+        cv::Point2f p(m_points[i].x() - m_centroid.x(), m_points[i].y() - m_centroid.y());
+        if (index.emplace(std::make_pair(p.x, p.y), i).second)
+        {
+            int id = m_subdiv->insert(p);
+            if (id >= (int)m_vertexToPoint.size())
+            {
+                m_vertexToPoint.resize(id + 1, -1);
+            }
+            m_vertexToPoint[id] = i;
+        }
+    }
+
+    std::vector<cv::Vec6f> triangleList;
+    m_subdiv->getTriangleList(triangleList);
+    for (const cv::Vec6f& t : triangleList)
+    {
+        std::array<unsigned int, 3> tri;
+        bool valid = true;
+        for (int j = 0; j < 3 && valid; j++)
+        {
+            // Triangles connected to the virtual outer vertices are not found
+            auto it = index.find(std::make_pair(t[2 * j], t[2 * j + 1]));
+            valid = it != index.end();
+            if (valid)
+            {
+                tri[j] = it->second;
+            }
+        }
+        if (!valid)
+        {
+            continue;
+        }
+
+        Vector2d ab = m_points[tri[1]] - m_points[tri[0]];
+        Vector2d ac = m_points[tri[2]] - m_points[tri[0]];
+        double cross = ab.x() * ac.y() - ab.y() * ac.x();
+        if (std::abs(cross) < 1e-12)
+        {
+            continue;
+        }
+        if (cross < 0)
+        {
+            std::swap(tri[1], tri[2]);
+        }
+        m_triangles.push_back(tri);
+    }
+}
+
+Delaunay2D::~Delaunay2D() = default;
+
+std::vector<std::pair<unsigned int, unsigned int>> Delaunay2D::edges() const
+{
+    std::set<std::pair<unsigned int, unsigned int>> edges;
+    for (const auto& t : m_triangles)
+    {
+        for (int j = 0; j < 3; j++)
+        {
+            unsigned int a = t[j];
+            unsigned int b = t[(j + 1) % 3];
+            edges.emplace(std::min(a, b), std::max(a, b));
+        }
+    }
+    return std::vector<std::pair<unsigned int, unsigned int>>(edges.begin(), edges.end());
+}
+
+double Delaunay2D::circumradius(const std::array<unsigned int, 3>& triangle) const
+{
+    const Vector2d& a = m_points[triangle[0]];
+    const Vector2d& b = m_points[triangle[1]];
+    const Vector2d& c = m_points[triangle[2]];
+    Vector2d ab = b - a;
+    Vector2d ac = c - a;
+    double cross = std::abs(ab.x() * ac.y() - ab.y() * ac.x());
+    if (cross < 1e-12)
+    {
+        return std::numeric_limits<double>::infinity();
+    }
+
+    // R = abc / (4 * area) with area = cross / 2
+    return ab.norm() * (c - b).norm() * ac.norm() / (2 * cross);
+}
+
+std::vector<VoronoiCell> Delaunay2D::voronoiCells(const Vector2d& min, const Vector2d& max) const
+{
+    std::vector<VoronoiCell> cells;
+    if (m_points.empty())
+    {
+        return cells;
+    }
+
+    std::vector<int> ids;
+    for (size_t id = 0; id < m_vertexToPoint.size(); id++)
+    {
+        if (m_vertexToPoint[id] >= 0)
+        {
+            ids.push_back(id);
+        }
+    }
+
+    std::vector<std::vector<cv::Point2f>> facets;
+    std::vector<cv::Point2f> centers;
+    m_subdiv->getVoronoiFacetList(ids, facets, centers);
+
+    for (size_t i = 0; i < ids.size() && i < facets.size(); i++)
+    {
+        VoronoiCell cell;
+        cell.site = m_vertexToPoint[ids[i]];
+        for (const cv::Point2f& p : facets[i])
+        {
+            cell.polygon.push_back(Vector2d(p.x, p.y) + m_centroid);
+        }
+
+        // The orientation of the facets is not specified, make them counter clockwise
+        double area = 0;
+        for (size_t j = 0; j < cell.polygon.size(); j++)
+        {
+            const Vector2d& a = cell.polygon[j];
+            const Vector2d& b = cell.polygon[(j + 1) % cell.polygon.size()];
+            area += a.x() * b.y() - b.x() * a.y();
+        }
+        if (area < 0)
+        {
+            std::reverse(cell.polygon.begin(), cell.polygon.end());
+        }
+
+        cell.polygon = clipPolygon(cell.polygon, 0, min.x(), -1);
+        cell.polygon = clipPolygon(cell.polygon, 0, max.x(), 1);
+        cell.polygon = clipPolygon(cell.polygon, 1, min.y(), -1);
+        cell.polygon = clipPolygon(cell.polygon, 1, max.y(), 1);
+        if (cell.polygon.size() >= 3)
+        {
+            cells.push_back(std::move(cell));
+        }
+    }
+    return cells;
+}
+
+} // namespace lvr2
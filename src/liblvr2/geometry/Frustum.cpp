@@ -0,0 +1,205 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * Frustum.cpp
+ */
+
+#include "lvr2/geometry/Frustum.hpp"
+
+#include <Eigen/Dense>
+
+namespace lvr2
+{
+
+namespace
+{
+
+float signedDistance(const Vector4f& plane, const Vector3f& point)
+{
+    return plane.head<3>().dot(point) + plane.w();
+}
+
+/// Plane through the given point with the given (not necessarily normalized) inward normal
+Vector4f planeFromPoint(const Vector3f& normal, const Vector3f& point)
+{
+    const Vector3f n = normal.normalized();
+    return Vector4f(n.x(), n.y(), n.z(), -n.dot(point));
+}
+
+} // anonymous namespace
+
+Frustum::Frustum(const std::array<Vector4f, 6>& planes)
+    : m_planes(planes)
+{
+    for (Vector4f& plane : m_planes)
+    {
+        const float length = plane.head<3>().norm();
+        if (length > 0)
+        {
+            plane /= length;
+        }
+    }
+}
+
+Frustum Frustum::fromPinhole(
+    const PinholeModel& model,
+    const Transformd& cameraToWorld,
+    float nearDistance,
+    float farDistance)
+{
+    const Eigen::Matrix3f rotation = cameraToWorld.block<3, 3>(0, 0).cast<float>();
+    const Vector3f eye = cameraToWorld.block<3, 1>(0, 3).cast<float>();
+    const Vector3f forward = rotation * Vector3f::UnitZ();
+
+    // Rays through the image corners in world coordinates
+    auto ray = [&](double u, double v)
+    {
+        return Vector3f(rotation * Vector3f((u - model.cx) / model.fx, (v - model.cy) / model.fy, 1.0f));
+    };
+    const Vector3f topLeft = ray(0, 0);
+    const Vector3f topRight = ray(model.width, 0);
+    const Vector3f bottomRight = ray(model.width, model.height);
+    const Vector3f bottomLeft = ray(0, model.height);
+
+    // Orient the side planes towards the ray through the image center
+    const Vector3f center = ray(model.width / 2.0, model.height / 2.0);
+    auto sidePlane = [&](const Vector3f& d1, const Vector3f& d2)
+    {
+        Vector3f normal = d1.cross(d2);
+        if (normal.dot(center) < 0)
+        {
+            normal = -normal;
+        }
+        return planeFromPoint(normal, eye);
+    };
+
+    std::array<Vector4f, 6> planes;
+    planes[Left] = sidePlane(topLeft, bottomLeft);
+    planes[Right] = sidePlane(topRight, bottomRight);
+    planes[Bottom] = sidePlane(bottomLeft, bottomRight);
+    planes[Top] = sidePlane(topLeft, topRight);
+    planes[Near] = planeFromPoint(forward, eye + forward * nearDistance);
+    planes[Far] = planeFromPoint(-forward, eye + forward * farDistance);
+    return Frustum(planes);
+}
+
+Frustum Frustum::fromViewProjection(const Matrix4f& viewProjection)
+{
+    // Gribb and Hartmann, "Fast Extraction of Viewing Frustum Planes from the World-View-Projection Matrix"
+    const Vector4f r0 = viewProjection.row(0).transpose();
+    const Vector4f r1 = viewProjection.row(1).transpose();
+    const Vector4f r2 = viewProjection.row(2).transpose();
+    const Vector4f r3 = viewProjection.row(3).transpose();
+
+    std::array<Vector4f, 6> planes;
+    planes[Left] = r3 + r0;
+    planes[Right] = r3 - r0;
+    planes[Bottom] = r3 + r1;
+    planes[Top] = r3 - r1;
+    planes[Near] = r3 + r2;
+    planes[Far] = r3 - r2;
+    return Frustum(planes);
+}
+
+bool Frustum::contains(const Vector3f& point) const
+{
+    for (const Vector4f& plane : m_planes)
+    {
+        if (signedDistance(plane, point) < 0)
+        {
+            return false;
+        }
+    }
+    return true;
+}
+
+Frustum::Containment Frustum::classify(const Vector3f& min, const Vector3f& max) const
+{
+    Containment result = Containment::Inside;
+    for (const Vector4f& plane : m_planes)
+    {
+        // Box corners furthest along and against the plane normal
+        Vector3f positive;
+        Vector3f negative;
+        for (int a = 0; a < 3; a++)
+        {
+            positive[a] = plane[a] >= 0 ? max[a] : min[a];
+            negative[a] = plane[a] >= 0 ? min[a] : max[a];
+        }
+
+        if (signedDistance(plane, positive) < 0)
+        {
+            return Containment::Outside;
+        }
+        if (signedDistance(plane, negative) < 0)
+        {
+            result = Containment::Intersecting;
+        }
+    }
+    return result;
+}
+
+bool Frustum::intersects(const Vector3f& a, const Vector3f& b, const Vector3f& c) const
+{
+    for (const Vector4f& plane : m_planes)
+    {
+        if (signedDistance(plane, a) < 0 && signedDistance(plane, b) < 0 && signedDistance(plane, c) < 0)
+        {
+            return false;
+        }
+    }
+    return true;
+}
+
+std::array<Vector3f, 8> Frustum::corners() const
+{
+    auto intersect = [&](Side s1, Side s2, Side s3)
+    {
+        Eigen::Matrix3f normals;
+        normals.row(0) = m_planes[s1].head<3>().transpose();
+        normals.row(1) = m_planes[s2].head<3>().transpose();
+        normals.row(2) = m_planes[s3].head<3>().transpose();
+        const Vector3f offsets(-m_planes[s1].w(), -m_planes[s2].w(), -m_planes[s3].w());
+        return Vector3f(normals.colPivHouseholderQr().solve(offsets));
+    };
+
+    std::array<Vector3f, 8> result;
+    const Side depth[2] = {Near, Far};
+    for (int i = 0; i < 2; i++)
+    {
+        result[4 * i + 0] = intersect(depth[i], Left, Bottom);
+        result[4 * i + 1] = intersect(depth[i], Right, Bottom);
+        result[4 * i + 2] = intersect(depth[i], Right, Top);
+        result[4 * i + 3] = intersect(depth[i], Left, Top);
+    }
+    return result;
+}
+
+} // namespace lvr2
@@ -0,0 +1,322 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * AsciiTokenizer.cpp
+ */
+
+#include "lvr2/io/AsciiTokenizer.hpp"
+
+#include <algorithm>
+#include <cerrno>
+#include <cfloat>
+#include <clocale>
+#include <cmath>
+#include <cstdlib>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Maximum length of the line excerpt in error messages
+constexpr size_t MaxExcerptLength = 80;
+
+std::string formatParseError(
+    const std::string& file,
+    size_t line,
+    size_t column,
+    std::streamoff offset,
+    const std::string& reason,
+    const std::string& excerpt,
+    size_t marker)
+{
+    std::string msg = "[AsciiTokenizer] ";
+    if (!file.empty())
+    {
+        msg += file + ":";
+    }
+    msg += std::to_string(line) + ":";
+    if (column > 0)
+    {
+        msg += std::to_string(column) + ":";
+    }
+    msg += " " + reason;
+    if (offset >= 0)
+    {
+        msg += " (line starts at byte " + std::to_string(offset) + ")";
+    }
+    if (!excerpt.empty())
+    {
+        msg += "\n    " + excerpt;
+        if (column > 0)
+        {
+            msg += "\n    " + std::string(marker, ' ') + "^";
+        }
+    }
+    return msg;
+}
+
+bool isSeparator(char c, bool comma)
+{
+    return c == ' ' || c == '\t' || c == ';' || c == '\r' || c == '\f' || c == '\v' || (comma && c == ',');
+}
+
+/// Decides whether the commas in the given tokens separate fields
+bool commasSeparateFields(const std::vector<std::string>& tokens)
+{
+    if (tokens.size() == 1)
+    {
+        return true;
+    }
+    for (const std::string& token : tokens)
+    {
+        const size_t commas = std::count(token.begin(), token.end(), ',');
+        if (commas > 1 || (commas == 1 && token.find('.') != std::string::npos)
+            || token.front() == ',' || token.back() == ',')
+        {
+            return true;
+        }
+    }
+    return false;
+}
+
+} // anonymous namespace
+
+AsciiParseError::AsciiParseError(
+    const std::string& file,
+    size_t line,
+    size_t column,
+    const std::string& token,
+    std::streamoff offset,
+    const std::string& reason,
+    const std::string& excerpt,
+    size_t marker)
+    : std::runtime_error(formatParseError(file, line, column, offset, reason, excerpt, marker))
+    , m_file(file)
+    , m_line(line)
+    , m_column(column)
+    , m_token(token)
+    , m_offset(offset)
+    , m_reason(reason)
+{
+}
+
+AsciiTokenizer::AsciiTokenizer(std::istream& in, const std::string& source, size_t lineNumber)
+    : m_in(in)
+    , m_source(source)
+    , m_lineNumber(lineNumber)
+    , m_lineStart(-1)
+{
+    const char* decimalPoint = std::localeconv()->decimal_point;
+    m_decimalPoint = decimalPoint && decimalPoint[0] ? decimalPoint[0] : '.';
+}
+
+bool AsciiTokenizer::next()
+{
+    m_tokens.clear();
+    m_tokenOffsets.clear();
+    while (m_tokens.empty())
+    {
+        m_lineStart = m_in.tellg();
+        if (!std::getline(m_in, m_line))
+        {
+            return false;
+        }
+        m_lineNumber++;
+
+        // UTF-8 byte order mark
+        if (m_lineNumber == 1 && m_line.compare(0, 3, "\xEF\xBB\xBF") == 0)
+        {
+            m_line.erase(0, 3);
+        }
+
+        const size_t first = m_line.find_first_not_of(" \t\r");
+        if (first == std::string::npos || m_line[first] == '#' || m_line.compare(first, 2, "//") == 0)
+        {
+            continue;
+        }
+        split();
+    }
+    return true;
+}
+
+void AsciiTokenizer::split()
+{
+    auto tokenize = [this](bool comma)
+    {
+        m_tokens.clear();
+        m_tokenOffsets.clear();
+        size_t i = 0;
+        while (i < m_line.size())
+        {
+            while (i < m_line.size() && isSeparator(m_line[i], comma))
+            {
+                i++;
+            }
+            size_t start = i;
+            while (i < m_line.size() && !isSeparator(m_line[i], comma))
+            {
+                i++;
+            }
+            if (i > start)
+            {
+                m_tokens.emplace_back(m_line, start, i - start);
+                m_tokenOffsets.push_back(start);
+            }
+        }
+    };
+
+    tokenize(m_commaMode == CommaMode::Separator);
+    if (m_commaMode == CommaMode::Unknown && m_line.find(',') != std::string::npos)
+    {
+        m_commaMode = commasSeparateFields(m_tokens) ? CommaMode::Separator : CommaMode::Decimal;
+        if (m_commaMode == CommaMode::Separator)
+        {
+            tokenize(true);
+        }
+    }
+    if (m_commaMode == CommaMode::Decimal)
+    {
+        for (std::string& token : m_tokens)
+        {
+            std::replace(token.begin(), token.end(), ',', '.');
+        }
+    }
+}
+
+const std::string& AsciiTokenizer::token(size_t i) const
+{
+    if (i >= m_tokens.size())
+    {
+        error(i, "Expected at least " + std::to_string(i + 1) + " columns, found " + std::to_string(m_tokens.size()));
+    }
+    return m_tokens[i];
+}
+
+bool AsciiTokenizer::tryDouble(size_t i, double& value) const
+{
+    if (i >= m_tokens.size())
+    {
+        return false;
+    }
+
+    // strtod depends on the decimal separator of the C locale
+    std::string token = m_tokens[i];
+    if (m_decimalPoint != '.')
+    {
+        std::replace(token.begin(), token.end(), '.', m_decimalPoint);
+    }
+
+    const char* begin = token.c_str();
+    char* end = nullptr;
+    errno = 0;
+    value = std::strtod(begin, &end);
+    if (end != begin + token.size())
+    {
+        return false;
+    }
+
+    // Underflow is rounded to zero, overflow is an error
+    return errno != ERANGE || std::abs(value) < 1.0;
+}
+
+double AsciiTokenizer::getDouble(size_t i) const
+{
+    double value;
+    if (!tryDouble(i, value))
+    {
+        error(i, "Invalid number '" + token(i) + "' in column " + std::to_string(i + 1));
+    }
+    return value;
+}
+
+float AsciiTokenizer::getFloat(size_t i) const
+{
+    const double value = getDouble(i);
+    if (std::isfinite(value) && std::abs(value) > FLT_MAX)
+    {
+        error(i, "Number '" + token(i) + "' in column " + std::to_string(i + 1) + " exceeds the float range");
+    }
+    return static_cast<float>(value);
+}
+
+long AsciiTokenizer::getInt(size_t i) const
+{
+    const std::string& t = token(i);
+    const char* begin = t.c_str();
+    char* end = nullptr;
+    errno = 0;
+    const long value = std::strtol(begin, &end, 10);
+    if (end != begin + t.size() || errno == ERANGE)
+    {
+        error(i, "Invalid integer '" + t + "' in column " + std::to_string(i + 1));
+    }
+    return value;
+}
+
+void AsciiTokenizer::error(const std::string& msg) const
+{
+    std::string excerpt = m_line.substr(0, MaxExcerptLength);
+    if (excerpt.size() < m_line.size())
+    {
+        excerpt += "...";
+    }
+    throw AsciiParseError(m_source, m_lineNumber, 0, "", m_lineStart, msg, excerpt);
+}
+
+void AsciiTokenizer::error(size_t i, const std::string& msg) const
+{
+    // Trailing whitespace and line breaks are not part of the excerpt
+    const size_t length = m_line.find_last_not_of(" \t\r") + 1;
+    const size_t position = i < m_tokenOffsets.size() ? m_tokenOffsets[i] : length;
+    const std::string token = i < m_tokens.size() ? m_line.substr(position, m_tokens[i].size()) : "";
+
+    // Window of the line around the token
+    size_t begin = 0;
+    if (length > MaxExcerptLength && position > MaxExcerptLength / 2)
+    {
+        begin = std::min(position - MaxExcerptLength / 2, length - MaxExcerptLength);
+    }
+    std::string excerpt = m_line.substr(begin, std::min(MaxExcerptLength, length - begin));
+    size_t marker = position - begin;
+    if (begin > 0)
+    {
+        excerpt = "..." + excerpt;
+        marker += 3;
+    }
+    if (begin + MaxExcerptLength < length)
+    {
+        excerpt += "...";
+    }
+
+    throw AsciiParseError(m_source, m_lineNumber, position + 1, token, m_lineStart, msg, excerpt, marker);
+}
+
+} // namespace lvr2
@@ -0,0 +1,159 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * CityJsonIO.cpp
+ */
+
+#include "lvr2/io/CityJsonIO.hpp"
+#include "lvr2/algorithm/SurfaceClassification.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+
+#include <cmath>
+#include <iomanip>
+#include <limits>
+
+namespace lvr2
+{
+
+std::vector<BuildingSurface> classifyBuildingSurfaces(MeshBufferPtr mesh, const CityJsonOptions& options)
+{
+    const std::vector<Vector3d> normals = areaWeightedFaceNormals(mesh, options.regionChannel);
+    std::vector<BuildingSurface> labels(normals.size());
+    for (size_t f = 0; f < normals.size(); f++)
+    {
+        // Every surface that is no wall faces either upwards or downwards
+        switch (classifySurfaceNormal(normals[f], options.maxWallTilt, 90))
+        {
+        case SurfaceClass::Floor:
+            labels[f] = BuildingSurface::Roof;
+            break;
+        case SurfaceClass::Ceiling:
+            labels[f] = BuildingSurface::Ground;
+            break;
+        default:
+            labels[f] = BuildingSurface::Wall;
+        }
+    }
+    return labels;
+}
+
+bool saveCityJSON(
+    const std::vector<MeshBufferPtr>& buildings,
+    const boost::filesystem::path& filename,
+    const CityJsonOptions& options)
+{
+    boost::filesystem::ofstream out(filename);
+    if (!out.good())
+    {
+        lvr2::logout::get() << lvr2::error << "[CityJsonIO] Unable to open " << filename << lvr2::endl;
+        return false;
+    }
+
+    const std::array<double, 3> offset = options.geo ? options.geo->offset : std::array<double, 3>{{0.0, 0.0, 0.0}};
+
+    // The vertices are stored as integers relative to the minimum of all buildings
+    Vector3d min = Vector3d::Constant(std::numeric_limits<double>::max());
+    for (const MeshBufferPtr& mesh : buildings)
+    {
+        floatArr vertices = mesh->getVertices();
+        for (size_t v = 0; v < mesh->numVertices(); v++)
+        {
+            min = min.cwiseMin(Vector3d(vertices[3 * v], vertices[3 * v + 1], vertices[3 * v + 2]));
+        }
+    }
+    if (buildings.empty())
+    {
+        min = Vector3d::Zero();
+    }
+
+    out << std::setprecision(12);
+    out << "{\n";
+    out << "  \"type\": \"CityJSON\",\n";
+    out << "  \"version\": \"1.1\",\n";
+    out << "  \"transform\": {\"scale\": [" << options.scale << ", " << options.scale << ", " << options.scale << "], "
+        << "\"translate\": [" << min.x() + offset[0] << ", " << min.y() + offset[1] << ", " << min.z() + offset[2] << "]},\n";
+    if (options.geo && options.geo->epsg > 0)
+    {
+        out << "  \"metadata\": {\"referenceSystem\": \"https://www.opengis.net/def/crs/EPSG/0/" << options.geo->epsg << "\"},\n";
+    }
+
+    static const char* surfaceTypes[] = {"RoofSurface", "WallSurface", "GroundSurface"};
+
+    out << "  \"CityObjects\": {";
+    size_t vertexOffset = 0;
+    for (size_t b = 0; b < buildings.size(); b++)
+    {
+        const MeshBufferPtr& mesh = buildings[b];
+        indexArray faces = mesh->getFaceIndices();
+        const std::vector<BuildingSurface> labels = classifyBuildingSurfaces(mesh, options);
+
+        out << (b ? ",\n" : "\n") << "    \"building_" << b << "\": {\"type\": \"Building\", \"geometry\": [{"
+            << "\"type\": \"MultiSurface\", \"lod\": \"" << options.lod << "\", \"boundaries\": [";
+        for (size_t f = 0; f < mesh->numFaces(); f++)
+        {
+            out << (f ? ", " : "") << "[[" << faces[3 * f] + vertexOffset << ", " << faces[3 * f + 1] + vertexOffset
+                << ", " << faces[3 * f + 2] + vertexOffset << "]]";
+        }
+        out << "], \"semantics\": {\"surfaces\": [{\"type\": \"" << surfaceTypes[0] << "\"}, {\"type\": \""
+            << surfaceTypes[1] << "\"}, {\"type\": \"" << surfaceTypes[2] << "\"}], \"values\": [";
+        for (size_t f = 0; f < labels.size(); f++)
+        {
+            out << (f ? ", " : "") << static_cast<int>(labels[f]);
+        }
+        out << "]}}]}";
+        vertexOffset += mesh->numVertices();
+    }
+    out << (buildings.empty() ? "},\n" : "\n  },\n");
+
+    out << "  \"vertices\": [";
+    bool first = true;
+    for (const MeshBufferPtr& mesh : buildings)
+    {
+        floatArr vertices = mesh->getVertices();
+        for (size_t v = 0; v < mesh->numVertices(); v++)
+        {
+            out << (first ? "" : ", ") << "["
+                << std::llround((vertices[3 * v] - min.x()) / options.scale) << ", "
+                << std::llround((vertices[3 * v + 1] - min.y()) / options.scale) << ", "
+                << std::llround((vertices[3 * v + 2] - min.z()) / options.scale) << "]";
+            first = false;
+        }
+    }
+    out << "]\n";
+    out << "}\n";
+
+    lvr2::logout::get() << lvr2::info << "[CityJsonIO] Wrote " << buildings.size() << " buildings to "
+                        << filename.string() << lvr2::endl;
+    return out.good();
+}
+
+} // namespace lvr2
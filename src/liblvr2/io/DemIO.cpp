@@ -0,0 +1,192 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * DemIO.cpp
+ */
+
+#include "lvr2/io/DemIO.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <gdal_priv.h>
+#include <ogr_spatialref.h>
+
+#include <fstream>
+#include <iomanip>
+
+namespace lvr2
+{
+
+namespace
+{
+
+std::array<double, 3> demOffset(const boost::optional<GeoMetadata>& geo)
+{
+    return geo ? geo->offset : std::array<double, 3>{{0.0, 0.0, 0.0}};
+}
+
+std::string epsgToWkt(int epsg)
+{
+    OGRSpatialReference srs;
+    if (srs.importFromEPSG(epsg) != OGRERR_NONE)
+    {
+        lvr2::logout::get() << lvr2::warning << "[DemIO] Unknown EPSG code " << epsg << lvr2::endl;
+        return "";
+    }
+    char* wkt = nullptr;
+    srs.exportToWkt(&wkt);
+    std::string ret(wkt ? wkt : "");
+    CPLFree(wkt);
+    return ret;
+}
+
+} // anonymous namespace
+
+bool saveEsriAscii(
+    const HeightGrid& grid,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo)
+{
+    std::ofstream out(filename.string());
+    if (!out.good())
+    {
+        lvr2::logout::get() << lvr2::error << "[DemIO] Could not open " << filename.string() << lvr2::endl;
+        return false;
+    }
+
+    const std::array<double, 3> offset = demOffset(geo);
+    out << std::setprecision(12);
+    out << "ncols " << grid.cols << "\n"
+        << "nrows " << grid.rows << "\n"
+        << "xllcorner " << grid.originX + offset[0] << "\n"
+        << "yllcorner " << grid.originY + offset[1] << "\n"
+        << "cellsize " << grid.cellSize << "\n"
+        << "NODATA_value " << grid.noData << "\n";
+
+    out << std::setprecision(9);
+    for (size_t r = 0; r < grid.rows; r++)
+    {
+        for (size_t c = 0; c < grid.cols; c++)
+        {
+            const float h = grid.at(r, c);
+            out << (h == grid.noData ? h : h + offset[2]) << (c + 1 < grid.cols ? " " : "\n");
+        }
+    }
+
+    if (geo && geo->epsg > 0)
+    {
+        std::string wkt = epsgToWkt(geo->epsg);
+        if (!wkt.empty())
+        {
+            boost::filesystem::path prj = filename;
+            prj.replace_extension(".prj");
+            std::ofstream(prj.string()) << wkt;
+        }
+    }
+    return true;
+}
+
+bool saveGeoTIFFDem(
+    const HeightGrid& grid,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo)
+{
+    GDALAllRegister();
+    GDALDriver* driver = GetGDALDriverManager()->GetDriverByName("GTiff");
+    if (!driver || grid.cols == 0 || grid.rows == 0)
+    {
+        lvr2::logout::get() << lvr2::error << "[DemIO] Could not create GeoTIFF " << filename.string() << lvr2::endl;
+        return false;
+    }
+
+    GDALDataset* dataset = driver->Create(filename.string().c_str(), grid.cols, grid.rows, 1, GDT_Float32, NULL);
+    if (!dataset)
+    {
+        lvr2::logout::get() << lvr2::error << "[DemIO] Could not create GeoTIFF " << filename.string() << lvr2::endl;
+        return false;
+    }
+
+    // Top left corner, pixel size and (zero) rotation
+    const std::array<double, 3> offset = demOffset(geo);
+    double transform[6] = {
+        grid.originX + offset[0], grid.cellSize, 0.0,
+        grid.originY + offset[1] + grid.rows * grid.cellSize, 0.0, -grid.cellSize
+    };
+    dataset->SetGeoTransform(transform);
+
+    if (geo && geo->epsg > 0)
+    {
+        std::string wkt = epsgToWkt(geo->epsg);
+        if (!wkt.empty())
+        {
+            dataset->SetProjection(wkt.c_str());
+        }
+    }
+
+    std::vector<float> heights(grid.heights);
+    for (float& h : heights)
+    {
+        if (h != grid.noData)
+        {
+            h += offset[2];
+        }
+    }
+
+    GDALRasterBand* band = dataset->GetRasterBand(1);
+    band->SetNoDataValue(grid.noData);
+    bool ok = band->RasterIO(GF_Write, 0, 0, grid.cols, grid.rows, heights.data(),
+        grid.cols, grid.rows, GDT_Float32, 0, 0) == CE_None;
+    if (!ok)
+    {
+        lvr2::logout::get() << lvr2::error << "[DemIO] Writing raster data failed" << lvr2::endl;
+    }
+
+    GDALClose(dataset);
+    return ok;
+}
+
+bool saveDem(
+    const HeightGrid& grid,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo)
+{
+    const std::string extension = filename.extension().string();
+    if (extension == ".asc")
+    {
+        return saveEsriAscii(grid, filename, geo);
+    }
+    else if (extension == ".tif" || extension == ".tiff")
+    {
+        return saveGeoTIFFDem(grid, filename, geo);
+    }
+
+    lvr2::logout::get() << lvr2::error << "[DemIO] Unsupported DEM format " << extension << lvr2::endl;
+    return false;
+}
+
+} // namespace lvr2
@@ -0,0 +1,344 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * DepthImageIO.cpp
+ */
+
+#include "lvr2/io/DepthImageIO.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Util.hpp"
+
+#include <opencv2/imgcodecs.hpp>
+#include <opencv2/imgproc.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Metric depth of the pixel or 0 if it is invalid
+float depthAt(const cv::Mat& depth, int row, int col, const DepthImageOptions& options)
+{
+    float d = depth.depth() == CV_16U
+        ? depth.at<uint16_t>(row, col) * options.depthScale
+        : depth.at<float>(row, col);
+    if (!std::isfinite(d) || d <= 0 || d < options.minDepth || (options.maxDepth > 0 && d > options.maxDepth))
+    {
+        return 0;
+    }
+    return d;
+}
+
+PointBufferPtr convert(
+    const cv::Mat& input,
+    const PinholeModel& intrinsics,
+    const cv::Mat* color,
+    const PinholeModel* colorIntrinsics,
+    const Transformd& depthToColor,
+    const Transformd& pose,
+    const DepthImageOptions& options)
+{
+    if (input.channels() != 1 || (input.depth() != CV_16U && input.depth() != CV_32F))
+    {
+        throw std::invalid_argument("[DepthImageIO] Depth images have to be single channel 16 bit unsigned or 32 bit float");
+    }
+    const cv::Mat depth = options.maxHoleSize > 0 ? fillDepthHoles(input, options) : input;
+    if (color && (color->type() != CV_8UC3))
+    {
+        throw std::invalid_argument("[DepthImageIO] Color images have to be 8 bit BGR");
+    }
+
+    const Eigen::Matrix3d rotation = pose.block<3, 3>(0, 0);
+    const Vector3d translation = pose.block<3, 1>(0, 3);
+    const Eigen::Matrix3d colorRotation = depthToColor.block<3, 3>(0, 0);
+    const Vector3d colorTranslation = depthToColor.block<3, 1>(0, 3);
+
+    std::vector<float> points;
+    std::vector<unsigned char> colors;
+    const size_t reserve = static_cast<size_t>(depth.rows) * depth.cols;
+    points.reserve(3 * reserve);
+    if (color)
+    {
+        colors.reserve(3 * reserve);
+    }
+
+    size_t valid = 0;
+    for (int row = 0; row < depth.rows; row++)
+    {
+        for (int col = 0; col < depth.cols; col++)
+        {
+            const float d = depthAt(depth, row, col, options);
+            if (d == 0)
+            {
+                if (options.organized)
+                {
+                    points.insert(points.end(), 3, std::numeric_limits<float>::quiet_NaN());
+                    if (color)
+                    {
+                        colors.insert(colors.end(), 3, 0);
+                    }
+                }
+                continue;
+            }
+
+            // Back projection through the pixel center
+            const Vector3d local(
+                (col - intrinsics.cx) / intrinsics.fx * d,
+                (row - intrinsics.cy) / intrinsics.fy * d,
+                d);
+            const Vector3d world = rotation * local + translation;
+            points.insert(points.end(), {(float)world.x(), (float)world.y(), (float)world.z()});
+            valid++;
+
+            if (color)
+            {
+                unsigned char rgb[3] = {0, 0, 0};
+                const Vector3d c = colorRotation * local + colorTranslation;
+                if (c.z() > 0)
+                {
+                    const int u = std::lround(colorIntrinsics->fx * c.x() / c.z() + colorIntrinsics->cx);
+                    const int v = std::lround(colorIntrinsics->fy * c.y() / c.z() + colorIntrinsics->cy);
+                    if (u >= 0 && v >= 0 && u < color->cols && v < color->rows)
+                    {
+                        const cv::Vec3b& bgr = color->at<cv::Vec3b>(v, u);
+                        rgb[0] = bgr[2];
+                        rgb[1] = bgr[1];
+                        rgb[2] = bgr[0];
+                    }
+                }
+                colors.insert(colors.end(), rgb, rgb + 3);
+            }
+        }
+    }
+
+    const size_t n = points.size() / 3;
+    PointBufferPtr buffer = std::make_shared<PointBuffer>();
+    if (n == 0)
+    {
+        return buffer;
+    }
+    buffer->setPointArray(Util::convert_vector_to_shared_array(points), n);
+    if (color)
+    {
+        buffer->setColorArray(Util::convert_vector_to_shared_array(colors), n);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[DepthImageIO] Converted " << valid << " of "
+        << reserve << " pixels" << lvr2::endl;
+    return buffer;
+}
+
+} // anonymous namespace
+
+cv::Mat fillDepthHoles(const cv::Mat& depth, const DepthImageOptions& options)
+{
+    if (depth.channels() != 1 || (depth.depth() != CV_16U && depth.depth() != CV_32F))
+    {
+        throw std::invalid_argument("[DepthImageIO] Depth images have to be single channel 16 bit unsigned or 32 bit float");
+    }
+
+    cv::Mat metric(depth.size(), CV_32F);
+    cv::Mat invalid(depth.size(), CV_8U);
+    for (int row = 0; row < depth.rows; row++)
+    {
+        for (int col = 0; col < depth.cols; col++)
+        {
+            const float d = depthAt(depth, row, col, options);
+            metric.at<float>(row, col) = d;
+            invalid.at<uint8_t>(row, col) = d == 0 ? 255 : 0;
+        }
+    }
+    if (options.maxHoleSize <= 0)
+    {
+        return metric;
+    }
+
+    // Only small holes that are enclosed by valid pixels are filled
+    cv::Mat labels, stats, centroids;
+    const int numLabels = cv::connectedComponentsWithStats(invalid, labels, stats, centroids, 4, CV_32S);
+    std::vector<bool> fillable(numLabels, false);
+    for (int l = 1; l < numLabels; l++)
+    {
+        const int x = stats.at<int>(l, cv::CC_STAT_LEFT);
+        const int y = stats.at<int>(l, cv::CC_STAT_TOP);
+        const int w = stats.at<int>(l, cv::CC_STAT_WIDTH);
+        const int h = stats.at<int>(l, cv::CC_STAT_HEIGHT);
+        const bool border = x == 0 || y == 0 || x + w == depth.cols || y + h == depth.rows;
+        fillable[l] = !border && stats.at<int>(l, cv::CC_STAT_AREA) <= options.maxHoleSize;
+    }
+
+    std::vector<cv::Point> pending;
+    for (int row = 0; row < depth.rows; row++)
+    {
+        for (int col = 0; col < depth.cols; col++)
+        {
+            if (fillable[labels.at<int>(row, col)])
+            {
+                pending.emplace_back(col, row);
+            }
+        }
+    }
+
+    // Fill the holes layer by layer from their border inwards. Values of a
+    // layer are written after all of its pixels were evaluated.
+    const int radius = 2;
+    const float sigmaSpace = radius;
+    size_t filled = 0;
+    std::vector<float> neighbors;
+    std::vector<std::pair<cv::Point, float>> layer;
+    while (!pending.empty())
+    {
+        layer.clear();
+        std::vector<cv::Point> remaining;
+        for (const cv::Point& p : pending)
+        {
+            neighbors.clear();
+            bool touchesValid = false;
+            for (int dy = -radius; dy <= radius; dy++)
+            {
+                for (int dx = -radius; dx <= radius; dx++)
+                {
+                    const int row = p.y + dy;
+                    const int col = p.x + dx;
+                    if (row < 0 || col < 0 || row >= depth.rows || col >= depth.cols)
+                    {
+                        continue;
+                    }
+                    const float d = metric.at<float>(row, col);
+                    if (d > 0)
+                    {
+                        neighbors.push_back(d);
+                        touchesValid |= std::abs(dx) <= 1 && std::abs(dy) <= 1;
+                    }
+                }
+            }
+            if (!touchesValid)
+            {
+                remaining.push_back(p);
+                continue;
+            }
+
+            std::sort(neighbors.begin(), neighbors.end());
+            const float median = neighbors[neighbors.size() / 2];
+            if (neighbors.back() - neighbors.front() > options.fillTolerance * median)
+            {
+                // Depth discontinuity, the hole is not part of a single surface
+                continue;
+            }
+
+            float value = median;
+            if (options.fillMethod == DepthFillMethod::Bilateral)
+            {
+                const float sigmaRange = std::max(options.fillTolerance * median * 0.5f, 1e-6f);
+                float sum = 0;
+                float weights = 0;
+                for (int dy = -radius; dy <= radius; dy++)
+                {
+                    for (int dx = -radius; dx <= radius; dx++)
+                    {
+                        const int row = p.y + dy;
+                        const int col = p.x + dx;
+                        if (row < 0 || col < 0 || row >= depth.rows || col >= depth.cols)
+                        {
+                            continue;
+                        }
+                        const float d = metric.at<float>(row, col);
+                        if (d > 0)
+                        {
+                            const float w = std::exp(-(dx * dx + dy * dy) / (2 * sigmaSpace * sigmaSpace)
+                                - (d - median) * (d - median) / (2 * sigmaRange * sigmaRange));
+                            sum += w * d;
+                            weights += w;
+                        }
+                    }
+                }
+                value = weights > 0 ? sum / weights : median;
+            }
+            layer.emplace_back(p, value);
+        }
+
+        if (layer.empty())
+        {
+            break;
+        }
+        for (const auto& entry : layer)
+        {
+            metric.at<float>(entry.first) = entry.second;
+        }
+        filled += layer.size();
+        pending.swap(remaining);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[DepthImageIO] Filled " << filled << " invalid pixels" << lvr2::endl;
+    return metric;
+}
+
+PointBufferPtr fromDepthImage(
+    const cv::Mat& depth,
+    const PinholeModel& intrinsics,
+    const Transformd& pose,
+    const DepthImageOptions& options)
+{
+    return convert(depth, intrinsics, nullptr, nullptr, Transformd::Identity(), pose, options);
+}
+
+PointBufferPtr fromDepthImage(
+    const cv::Mat& depth,
+    const PinholeModel& depthIntrinsics,
+    const cv::Mat& color,
+    const PinholeModel& colorIntrinsics,
+    const Transformd& depthToColor,
+    const Transformd& pose,
+    const DepthImageOptions& options)
+{
+    return convert(depth, depthIntrinsics, &color, &colorIntrinsics, depthToColor, pose, options);
+}
+
+PointBufferPtr loadDepthImage(
+    const boost::filesystem::path& file,
+    const PinholeModel& intrinsics,
+    const Transformd& pose,
+    const DepthImageOptions& options)
+{
+    cv::Mat depth = cv::imread(file.string(), cv::IMREAD_ANYDEPTH);
+    if (depth.empty())
+    {
+        throw std::runtime_error("[DepthImageIO] Could not read depth image " + file.string());
+    }
+    return fromDepthImage(depth, intrinsics, pose, options);
+}
+
+} // namespace lvr2
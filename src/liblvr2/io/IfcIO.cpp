@@ -0,0 +1,340 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IfcIO.cpp
+ */
+
+#include "lvr2/io/IfcIO.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+#include <boost/uuid/random_generator.hpp>
+#include <boost/uuid/uuid.hpp>
+
+#include <array>
+#include <cmath>
+#include <ctime>
+#include <iomanip>
+#include <map>
+#include <sstream>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Copies the given faces and the vertices they reference
+MeshBufferPtr regionMesh(MeshBufferPtr mesh, const std::vector<size_t>& faceIds)
+{
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+
+    std::unordered_map<unsigned int, unsigned int> vertexMap;
+    std::vector<unsigned int> usedVertices;
+    indexArray regionFaces(new unsigned int[faceIds.size() * 3]);
+    for (size_t i = 0; i < faceIds.size(); i++)
+    {
+        for (size_t j = 0; j < 3; j++)
+        {
+            unsigned int v = faces[faceIds[i] * 3 + j];
+            auto it = vertexMap.emplace(v, usedVertices.size()).first;
+            if (it->second == usedVertices.size())
+            {
+                usedVertices.push_back(v);
+            }
+            regionFaces[i * 3 + j] = it->second;
+        }
+    }
+
+    floatArr regionVertices(new float[usedVertices.size() * 3]);
+    for (size_t i = 0; i < usedVertices.size(); i++)
+    {
+        std::copy_n(&vertices[usedVertices[i] * 3], 3, &regionVertices[i * 3]);
+    }
+
+    MeshBufferPtr region = std::make_shared<MeshBuffer>();
+    region->setVertices(regionVertices, usedVertices.size());
+    region->setFaceIndices(regionFaces, faceIds.size());
+    return region;
+}
+
+/// Random IFC GlobalId: 128 bits encoded with 22 characters of the IFC base64 alphabet
+std::string ifcGuid(boost::uuids::random_generator& generator)
+{
+    static const char* alphabet = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_$";
+    const boost::uuids::uuid uuid = generator();
+
+    // The first character holds the two most significant bits, all others six bits each
+    std::string guid(22, '0');
+    for (int c = 0; c < 22; c++)
+    {
+        int value = 0;
+        for (int b = 0; b < 6; b++)
+        {
+            const int bit = c * 6 + b - 4;
+            value <<= 1;
+            if (bit >= 0)
+            {
+                value |= (uuid.data[bit / 8] >> (7 - bit % 8)) & 1;
+            }
+        }
+        guid[c] = alphabet[value];
+    }
+    return guid;
+}
+
+/// Writes numbered STEP entities
+class StepWriter
+{
+public:
+    explicit StepWriter(std::ostream& out) : m_out(out), m_next(1) {}
+
+    size_t add(const std::string& entity)
+    {
+        m_out << "#" << m_next << "=" << entity << ";\n";
+        return m_next++;
+    }
+
+private:
+    std::ostream& m_out;
+    size_t m_next;
+};
+
+std::string ref(size_t id)
+{
+    return "#" + std::to_string(id);
+}
+
+std::string refList(const std::vector<size_t>& ids)
+{
+    std::string list = "(";
+    for (size_t i = 0; i < ids.size(); i++)
+    {
+        list += (i ? "," : "") + ref(ids[i]);
+    }
+    return list + ")";
+}
+
+std::string real(double value)
+{
+    std::ostringstream s;
+    s << std::setprecision(10) << value;
+    std::string str = s.str();
+    // STEP reals require a decimal point
+    if (str.find_first_of(".eE") == std::string::npos)
+    {
+        str += ".";
+    }
+    return str;
+}
+
+} // anonymous namespace
+
+std::vector<ClassifiedSurface> classifyPlanarRegions(
+    MeshBufferPtr mesh,
+    const std::string& regionChannel,
+    double maxTilt,
+    double minArea)
+{
+    std::vector<ClassifiedSurface> surfaces;
+    IndexChannelOptional regions = mesh->getIndexChannel(regionChannel);
+    if (!regions)
+    {
+        const size_t numRegions = growPlanarRegions(mesh, maxTilt, regionChannel);
+        lvr2::logout::get() << lvr2::warning << "[IfcIO] Mesh has no region channel '" << regionChannel
+                            << "', grew " << numRegions << " planar regions" << lvr2::endl;
+        regions = mesh->getIndexChannel(regionChannel);
+        if (!regions)
+        {
+            return surfaces;
+        }
+    }
+
+    const std::vector<Vector3d> normals = areaWeightedFaceNormals(mesh, regionChannel);
+    std::map<unsigned int, std::vector<size_t>> regionFaces;
+    for (size_t f = 0; f < mesh->numFaces(); f++)
+    {
+        regionFaces[(*regions)[f][0]].push_back(f);
+    }
+
+    for (const auto& [label, faceIds] : regionFaces)
+    {
+        // All faces of a region share the region's normal
+        const Vector3d& normal = normals[faceIds.front()];
+        const double area = 0.5 * normal.norm();
+        if (area < minArea)
+        {
+            continue;
+        }
+
+        ClassifiedSurface surface;
+        surface.region = label;
+        surface.type = classifySurfaceNormal(normal, maxTilt, maxTilt);
+        surface.mesh = regionMesh(mesh, faceIds);
+        surfaces.push_back(surface);
+    }
+    return surfaces;
+}
+
+bool saveIFC(
+    const std::vector<ClassifiedSurface>& surfaces,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo)
+{
+    boost::filesystem::ofstream out(filename);
+    if (!out.good())
+    {
+        lvr2::logout::get() << lvr2::error << "[IfcIO] Unable to open " << filename << lvr2::endl;
+        return false;
+    }
+
+    const std::array<double, 3> offset = geo ? geo->offset : std::array<double, 3>{{0.0, 0.0, 0.0}};
+
+    std::time_t now = std::time(nullptr);
+    char timestamp[32];
+    std::strftime(timestamp, sizeof(timestamp), "%Y-%m-%dT%H:%M:%S", std::gmtime(&now));
+
+    out << "ISO-10303-21;\n";
+    out << "HEADER;\n";
+    out << "FILE_DESCRIPTION(('ViewDefinition [CoordinationView]'),'2;1');\n";
+    out << "FILE_NAME('" << filename.filename().string() << "','" << timestamp << "',(''),(''),'LVR2','LVR2','');\n";
+    out << "FILE_SCHEMA(('IFC2X3'));\n";
+    out << "ENDSEC;\n";
+    out << "DATA;\n";
+
+    boost::uuids::random_generator generator;
+    auto guid = [&generator]() { return "'" + ifcGuid(generator) + "'"; };
+
+    StepWriter step(out);
+
+    // Project context and spatial structure
+    const size_t person = step.add("IFCPERSON($,$,'',$,$,$,$,$)");
+    const size_t organization = step.add("IFCORGANIZATION($,'LVR2',$,$,$)");
+    const size_t personOrg = step.add("IFCPERSONANDORGANIZATION(" + ref(person) + "," + ref(organization) + ",$)");
+    const size_t application = step.add("IFCAPPLICATION(" + ref(organization) + ",'1.0','LVR2','LVR2')");
+    const size_t owner = step.add("IFCOWNERHISTORY(" + ref(personOrg) + "," + ref(application) + ",$,.ADDED.,$,$,$," + std::to_string(now) + ")");
+    const std::string ownerRef = ref(owner);
+
+    std::vector<size_t> units = {
+        step.add("IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.)"),
+        step.add("IFCSIUNIT(*,.AREAUNIT.,$,.SQUARE_METRE.)"),
+        step.add("IFCSIUNIT(*,.VOLUMEUNIT.,$,.CUBIC_METRE.)"),
+        step.add("IFCSIUNIT(*,.PLANEANGLEUNIT.,$,.RADIAN.)")
+    };
+    const size_t unitAssignment = step.add("IFCUNITASSIGNMENT(" + refList(units) + ")");
+
+    const size_t origin = step.add("IFCCARTESIANPOINT((0.,0.,0.))");
+    const size_t zAxis = step.add("IFCDIRECTION((0.,0.,1.))");
+    const size_t xAxis = step.add("IFCDIRECTION((1.,0.,0.))");
+    const size_t placement = step.add("IFCAXIS2PLACEMENT3D(" + ref(origin) + "," + ref(zAxis) + "," + ref(xAxis) + ")");
+    const size_t context = step.add("IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.E-05," + ref(placement) + ",$)");
+    const size_t project = step.add("IFCPROJECT(" + guid() + "," + ownerRef + ",'Project',$,$,$,$,(" + ref(context) + ")," + ref(unitAssignment) + ")");
+
+    const size_t sitePlacement = step.add("IFCLOCALPLACEMENT($," + ref(placement) + ")");
+    const size_t site = step.add("IFCSITE(" + guid() + "," + ownerRef + ",'Site',$,$," + ref(sitePlacement) + ",$,$,.ELEMENT.,$,$,$,$,$)");
+    const size_t buildingPlacement = step.add("IFCLOCALPLACEMENT(" + ref(sitePlacement) + "," + ref(placement) + ")");
+    const size_t building = step.add("IFCBUILDING(" + guid() + "," + ownerRef + ",'Building',$,$," + ref(buildingPlacement) + ",$,$,.ELEMENT.,$,$,$)");
+    const size_t storeyPlacement = step.add("IFCLOCALPLACEMENT(" + ref(buildingPlacement) + "," + ref(placement) + ")");
+    const size_t storey = step.add("IFCBUILDINGSTOREY(" + guid() + "," + ownerRef + ",'Storey',$,$," + ref(storeyPlacement) + ",$,$,.ELEMENT.,0.)");
+
+    step.add("IFCRELAGGREGATES(" + guid() + "," + ownerRef + ",$,$," + ref(project) + ",(" + ref(site) + "))");
+    step.add("IFCRELAGGREGATES(" + guid() + "," + ownerRef + ",$,$," + ref(site) + ",(" + ref(building) + "))");
+    step.add("IFCRELAGGREGATES(" + guid() + "," + ownerRef + ",$,$," + ref(building) + ",(" + ref(storey) + "))");
+
+    // One element with a surface model per classified region
+    std::vector<size_t> elements;
+    size_t numWalls = 0;
+    size_t numFloors = 0;
+    size_t numCeilings = 0;
+    for (const ClassifiedSurface& surface : surfaces)
+    {
+        if (surface.type == SurfaceClass::Other || !surface.mesh)
+        {
+            continue;
+        }
+
+        floatArr vertices = surface.mesh->getVertices();
+        indexArray faces = surface.mesh->getFaceIndices();
+
+        std::vector<size_t> points(surface.mesh->numVertices());
+        for (size_t v = 0; v < points.size(); v++)
+        {
+            points[v] = step.add("IFCCARTESIANPOINT(("
+                + real(vertices[3 * v] + offset[0]) + ","
+                + real(vertices[3 * v + 1] + offset[1]) + ","
+                + real(vertices[3 * v + 2] + offset[2]) + "))");
+        }
+
+        std::vector<size_t> ifcFaces;
+        for (size_t f = 0; f < surface.mesh->numFaces(); f++)
+        {
+            const size_t loop = step.add("IFCPOLYLOOP(" + refList({points[faces[3 * f]], points[faces[3 * f + 1]], points[faces[3 * f + 2]]}) + ")");
+            const size_t bound = step.add("IFCFACEOUTERBOUND(" + ref(loop) + ",.T.)");
+            ifcFaces.push_back(step.add("IFCFACE((" + ref(bound) + "))"));
+        }
+
+        const size_t shell = step.add("IFCOPENSHELL(" + refList(ifcFaces) + ")");
+        const size_t model = step.add("IFCSHELLBASEDSURFACEMODEL((" + ref(shell) + "))");
+        const size_t representation = step.add("IFCSHAPEREPRESENTATION(" + ref(context) + ",'Body','SurfaceModel',(" + ref(model) + "))");
+        const size_t shape = step.add("IFCPRODUCTDEFINITIONSHAPE($,$,(" + ref(representation) + "))");
+        const size_t elementPlacement = step.add("IFCLOCALPLACEMENT(" + ref(storeyPlacement) + "," + ref(placement) + ")");
+        const std::string common = guid() + "," + ownerRef + ",";
+        const std::string geometry = ref(elementPlacement) + "," + ref(shape) + ",'" + std::to_string(surface.region) + "'";
+
+        if (surface.type == SurfaceClass::Wall)
+        {
+            elements.push_back(step.add("IFCWALL(" + common + "'Wall " + std::to_string(++numWalls) + "',$,$," + geometry + ")"));
+        }
+        else if (surface.type == SurfaceClass::Floor)
+        {
+            elements.push_back(step.add("IFCSLAB(" + common + "'Floor " + std::to_string(++numFloors) + "',$,$," + geometry + ",.FLOOR.)"));
+        }
+        else
+        {
+            elements.push_back(step.add("IFCSLAB(" + common + "'Ceiling " + std::to_string(++numCeilings) + "',$,'Ceiling'," + geometry + ",.USERDEFINED.)"));
+        }
+    }
+
+    if (!elements.empty())
+    {
+        step.add("IFCRELCONTAINEDINSPATIALSTRUCTURE(" + guid() + "," + ownerRef + ",$,$," + refList(elements) + "," + ref(storey) + ")");
+    }
+
+    out << "ENDSEC;\n";
+    out << "END-ISO-10303-21;\n";
+
+    lvr2::logout::get() << lvr2::info << "[IfcIO] Wrote " << numWalls << " walls, " << numFloors << " floors and "
+                        << numCeilings << " ceilings to " << filename.string() << lvr2::endl;
+    return out.good();
+}
+
+} // namespace lvr2
@@ -33,7 +33,9 @@
  */
 
 #include <boost/algorithm/string.hpp>
+#include <algorithm>
 #include <cerrno>
+#include <cstring>
 #include <exception>
 #include <fstream>
 #include <iostream>
@@ -42,10 +44,85 @@
 #include <stdio.h>
 
 #include "lvr2/io/LineReader.hpp"
+#include "lvr2/io/AsciiTokenizer.hpp"
 
 namespace lvr2
 {
 
+namespace
+{
+
+/**
+ * Reads up to amount points from an ASCII file, starting at the saved file
+ * position. Colors precede normals, see LineReader::open(). The position
+ * and line number of the file attributes are updated.
+ */
+boost::shared_ptr<void> readAsciiPoints(fileAttribut& attr, size_t amount, size_t& return_amount)
+{
+    std::ifstream in(attr.m_filePath, std::ios::binary);
+    in.seekg(attr.m_filePos);
+    AsciiTokenizer tokens(in, attr.m_filePath, attr.m_lineNumber);
+
+    const bool hasNormals = attr.m_fileType == XYZN || attr.m_fileType == XYZNRGB;
+    const bool hasColors = attr.m_fileType == XYZRGB || attr.m_fileType == XYZNRGB;
+
+    boost::shared_ptr<void> pArray(
+        new char[amount * attr.m_PointBlockSize],
+        std::default_delete<char[]>());
+    char* out = static_cast<char*>(pArray.get());
+
+    size_t readCount = 0;
+    while (readCount < amount && tokens.next())
+    {
+        xyznc pc;
+        pc.point.x = tokens.getFloat(0);
+        pc.point.y = tokens.getFloat(1);
+        pc.point.z = tokens.getFloat(2);
+
+        size_t column = 3;
+        if (hasColors)
+        {
+            unsigned char rgb[3];
+            for (unsigned char& c : rgb)
+            {
+                c = static_cast<unsigned char>(std::min(std::max(tokens.getDouble(column++), 0.0), 255.0));
+            }
+            pc.color.r = rgb[0];
+            pc.color.g = rgb[1];
+            pc.color.b = rgb[2];
+        }
+        if (hasNormals)
+        {
+            pc.normal.x = tokens.getFloat(column++);
+            pc.normal.y = tokens.getFloat(column++);
+            pc.normal.z = tokens.getFloat(column++);
+        }
+
+        char* block = out + readCount * attr.m_PointBlockSize;
+        if (attr.m_fileType == XYZRGB)
+        {
+            xyzc p;
+            p.point = pc.point;
+            p.color = pc.color;
+            memcpy(block, &p, sizeof(xyzc));
+        }
+        else
+        {
+            // xyz and xyzn are prefixes of xyznc
+            memcpy(block, &pc, attr.m_PointBlockSize);
+        }
+        readCount++;
+    }
+
+    in.clear();
+    attr.m_filePos = in.tellg();
+    attr.m_lineNumber = tokens.lineNumber();
+    return_amount = readCount;
+    return pArray;
+}
+
+} // anonymous namespace
+
 LineReader::LineReader() {}
 
 LineReader::LineReader(std::vector<std::string> filePaths)
@@ -82,6 +159,7 @@ void LineReader::open(std::vector<std::string> filePaths)
         bool gotcolor = false;
         bool gotnormal = false;
         bool readHeader = false;
+        currentAttr.m_lineNumber = 0;
 
         if (boost::algorithm::contains(filePath, ".ply"))
         {
@@ -101,6 +179,7 @@ void LineReader::open(std::vector<std::string> filePaths)
             while (!readHeader)
             {
                 std::getline(ifs, line);
+                currentAttr.m_lineNumber++;
                 if (boost::algorithm::contains(line, "element vertex") ||
                     boost::algorithm::contains(line, "element point"))
                 {
@@ -164,41 +243,45 @@ void LineReader::open(std::vector<std::string> filePaths)
         else
         {
             std::cout << "File Type is not PLY, checking file... " << std::endl;
-            std::string line;
-            std::getline(ifs, line);
-            std::stringstream ss(line);
-            string tmp;
-            unsigned int number_of_line_elements = 0;
-            while (ss >> tmp)
+            AsciiTokenizer tokens(ifs, filePath);
+
+            // Skip a header line with less than three values, e.g. the point count of .pts files
+            if (tokens.next() && tokens.size() < 3)
             {
-                number_of_line_elements++;
-                if (number_of_line_elements >= 3)
-                    gotxyz = true;
-                if (number_of_line_elements == 6)
-                {
-                    if (boost::algorithm::contains(tmp, "."))
-                    {
-                        gotnormal = true;
-                    }
-                    else
-                    {
-                        gotcolor = true;
-                    }
-                }
-                if (number_of_line_elements == 9)
+                tokens.next();
+            }
+
+            unsigned int number_of_line_elements = tokens.size();
+            if (number_of_line_elements >= 3)
+            {
+                gotxyz = true;
+            }
+            if (number_of_line_elements == 6)
+            {
+                if (boost::algorithm::contains(tokens.token(5), "."))
                 {
                     gotnormal = true;
-                    gotcolor = true;
                 }
-                if (number_of_line_elements > 9)
+                else
                 {
-                    throw std::range_error("Wrong file format, expecting file ascii or ply file "
-                                           "format, ascii file format must have order:  x y z [nx "
-                                           "ny nz] [cx cy cz] (points, normals, colors)");
+                    gotcolor = true;
                 }
             }
+            if (number_of_line_elements == 9)
+            {
+                gotnormal = true;
+                gotcolor = true;
+            }
+            if (number_of_line_elements > 9)
+            {
+                throw std::range_error("Wrong file format, expecting file ascii or ply file "
+                                       "format, ascii file format must have order:  x y z "
+                                       "[cx cy cz] [nx ny nz] (points, colors, normals)");
+            }
             currentAttr.m_line_element_amount = number_of_line_elements;
-            ifs.seekg(0);
+            currentAttr.m_lineNumber = tokens.lineNumber() > 0 ? tokens.lineNumber() - 1 : 0;
+            ifs.clear();
+            ifs.seekg(tokens.lineStart() >= 0 ? tokens.lineStart() : std::streampos(0));
         }
 
         currentAttr.m_filePos = ifs.tellg();
@@ -312,182 +395,11 @@ boost::shared_ptr<void> LineReader::getNextPoints(size_t& return_amount, size_t
         }
         else
         {
-            fseek(pFile, m_fileAttributes[m_currentReadFile].m_filePos, SEEK_SET);
-            size_t readCount = 0;
-            if (m_fileAttributes[m_currentReadFile].m_fileType == XYZ &&
-                m_fileAttributes[m_currentReadFile].m_line_element_amount != 3)
-            {
-
-                std::vector<float> input;
-                input.reserve(amount * 3);
-                boost::shared_ptr<void> pArray(
-                    new char[amount * m_fileAttributes[m_currentReadFile].m_PointBlockSize],
-                    std::default_delete<char[]>());
-                float ax, ay, az;
-                char lineBuffer[1024];
-                while ((fgets(lineBuffer, 1024, pFile) != NULL) && readCount < amount)
-                {
-                    sscanf(lineBuffer, "%f %f %f", &ax, &ay, &az);
-                    readCount++;
-                    input.push_back(ax);
-                    input.push_back(ay);
-                    input.push_back(az);
-                }
-
-                memcpy(pArray.get(),
-                       input.data(),
-                       m_fileAttributes[m_currentReadFile].m_PointBlockSize * readCount);
-                return_amount = readCount;
-                if (return_amount < amount)
-                    m_openNextFile = true;
-                m_fileAttributes[m_currentReadFile].m_filePos = ftell(pFile);
-                fclose(pFile);
-                return pArray;
-            }
-            if (m_fileAttributes[m_currentReadFile].m_fileType == XYZ)
-            {
-                std::vector<float> input;
-                input.reserve(amount * 3);
-                boost::shared_ptr<void> pArray(
-                    new char[amount * m_fileAttributes[m_currentReadFile].m_PointBlockSize],
-                    std::default_delete<char[]>());
-                float ax, ay, az;
-                while ((fscanf(pFile, "%f %f %f", &ax, &ay, &az) != EOF) && readCount < amount)
-                {
-                    readCount++;
-                    input.push_back(ax);
-                    input.push_back(ay);
-                    input.push_back(az);
-                }
-
-                memcpy(pArray.get(),
-                       input.data(),
-                       m_fileAttributes[m_currentReadFile].m_PointBlockSize * readCount);
-                return_amount = readCount;
-                if (return_amount < amount)
-                {
-                    m_openNextFile = true;
-                }
-                else
-                {
-                    m_openNextFile = false;
-                }
-                m_fileAttributes[m_currentReadFile].m_filePos = ftell(pFile);
-                fclose(pFile);
-                return pArray;
-            }
-            else if (m_fileAttributes[m_currentReadFile].m_fileType == XYZN)
-            {
-                std::vector<float> input;
-                input.reserve(amount * 6);
-                boost::shared_ptr<void> pArray(
-                    new char[amount * m_fileAttributes[m_currentReadFile].m_PointBlockSize],
-                    std::default_delete<char[]>());
-                float ax, ay, az, nx, ny, nz;
-                while ((fscanf(pFile, "%f %f %f %f %f %f", &ax, &ay, &az, &nx, &ny, &nz) != EOF) &&
-                       readCount < amount)
-                {
-                    readCount++;
-                    input.push_back(ax);
-                    input.push_back(ay);
-                    input.push_back(az);
-                }
-                memcpy(pArray.get(),
-                       input.data(),
-                       m_fileAttributes[m_currentReadFile].m_PointBlockSize * readCount);
-                return_amount = readCount;
-                if (return_amount < amount)
-                    m_openNextFile = true;
-                m_fileAttributes[m_currentReadFile].m_filePos = ftell(pFile);
-                fclose(pFile);
-                return pArray;
-            }
-            else if (m_fileAttributes[m_currentReadFile].m_fileType == XYZRGB)
-            {
-                std::vector<xyzc> input;
-                input.reserve(amount * 6);
-                boost::shared_ptr<void> pArray(
-                    new char[amount * m_fileAttributes[m_currentReadFile].m_PointBlockSize],
-                    std::default_delete<char[]>());
-                xyzc pc;
-                float tmp_x, tmp_y, tmp_z;
-                unsigned char tmp_r, tmp_g, tmp_b;
-                while ((fscanf(pFile,
-                               "%f %f %f %hhu %hhu %hhu",  // don't read directly in to struct!
-                               &tmp_x,
-                               &tmp_y,
-                               &tmp_z,
-                               &tmp_r,
-                               &tmp_g,
-                               &tmp_b) != EOF) &&
-                       readCount < amount)
-                {
-                    pc.point.x = tmp_x;
-                    pc.point.y = tmp_y;
-                    pc.point.z = tmp_z;
-                    pc.color.r = tmp_r;
-                    pc.color.g = tmp_g;
-                    pc.color.b = tmp_b;
-                    readCount++;
-                    input.push_back(pc);
-                }
-                memcpy(pArray.get(),
-                       input.data(),
-                       m_fileAttributes[m_currentReadFile].m_PointBlockSize * readCount);
-                return_amount = readCount;
-                if (return_amount < amount)
-                    m_openNextFile = true;
-                m_fileAttributes[m_currentReadFile].m_filePos = ftell(pFile);
-                fclose(pFile);
-                return pArray;
-            }
-            else if (m_fileAttributes[m_currentReadFile].m_fileType == XYZNRGB)
-            {
-                std::vector<xyznc> input;
-                input.reserve(amount * 6);
-                boost::shared_ptr<void> pArray(
-                    new char[amount * m_fileAttributes[m_currentReadFile].m_PointBlockSize],
-                    std::default_delete<char[]>());
-                xyznc pc;
-                float tmp_x, tmp_y, tmp_z, tmp_nx, tmp_ny, tmp_nz;
-                unsigned char tmp_r, tmp_g, tmp_b;
-                while ((fscanf(pFile,
-                               "%f %f %f %hhu %hhu %hhu %f %f %f",
-                               &tmp_x,
-                               &tmp_y,
-                               &tmp_z,
-                               &tmp_r,
-                               &tmp_g,
-                               &tmp_b,
-                               &tmp_nx,
-                               &tmp_ny,
-                               &tmp_nz) != EOF) &&
-                       readCount < amount)
-                {
-                    pc.point.x = tmp_x;
-                    pc.point.y = tmp_y;
-                    pc.point.z = tmp_z;
-                    pc.color.r = tmp_r;
-                    pc.color.g = tmp_g;
-                    pc.color.b = tmp_b;
-                    pc.normal.x = tmp_nx;
-                    pc.normal.y = tmp_ny;
-                    pc.normal.z = tmp_nz;
-                    readCount++;
-                    input.push_back(pc);
-                }
-                memcpy(pArray.get(),
-                       input.data(),
-                       m_fileAttributes[m_currentReadFile].m_PointBlockSize * readCount);
-                return_amount = readCount;
-                m_fileAttributes[m_currentReadFile].m_filePos = ftell(pFile);
-                if (return_amount < amount)
-                {
-                    m_openNextFile = true;
-                }
-                fclose(pFile);
-                return pArray;
-            }
+            fclose(pFile);
+            boost::shared_ptr<void> pArray =
+                readAsciiPoints(m_fileAttributes[m_currentReadFile], amount, return_amount);
+            m_openNextFile = return_amount < amount;
+            return pArray;
         }
         fclose(pFile);
     }
@@ -0,0 +1,613 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * McapIO.cpp
+ */
+
+#include "lvr2/io/McapIO.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Util.hpp"
+
+#define MCAP_IMPLEMENTATION
+#include <mcap/reader.hpp>
+
+#include <Eigen/Geometry>
+
+#include <algorithm>
+#include <cmath>
+#include <cstring>
+#include <limits>
+#include <map>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Minimal reader for CDR serialized ROS 2 messages
+class CdrReader
+{
+public:
+    CdrReader(const std::byte* data, size_t size)
+        : m_data(reinterpret_cast<const uint8_t*>(data))
+        , m_size(size)
+        , m_pos(4)
+    {
+        if (size < 4)
+        {
+            throw std::runtime_error("[McapIO] CDR message without encapsulation header");
+        }
+        // CDR_LE and PL_CDR_LE
+        m_littleEndian = m_data[1] == 0x01 || m_data[1] == 0x03;
+    }
+
+    template<typename T>
+    T read()
+    {
+        align(sizeof(T));
+        check(sizeof(T));
+        uint8_t bytes[sizeof(T)];
+        std::memcpy(bytes, m_data + m_pos, sizeof(T));
+        if (!m_littleEndian)
+        {
+            std::reverse(bytes, bytes + sizeof(T));
+        }
+        m_pos += sizeof(T);
+
+        T value;
+        std::memcpy(&value, bytes, sizeof(T));
+        return value;
+    }
+
+    std::string readString()
+    {
+        const uint32_t length = read<uint32_t>();
+        const uint8_t* chars = readBytes(length);
+        // The length includes the terminating null character
+        return std::string(reinterpret_cast<const char*>(chars), length > 0 ? length - 1 : 0);
+    }
+
+    const uint8_t* readBytes(size_t n)
+    {
+        check(n);
+        const uint8_t* bytes = m_data + m_pos;
+        m_pos += n;
+        return bytes;
+    }
+
+private:
+    /// Primitives are aligned to their size relative to the end of the encapsulation header
+    void align(size_t n)
+    {
+        const size_t offset = m_pos - 4;
+        m_pos += (n - offset % n) % n;
+    }
+
+    void check(size_t n) const
+    {
+        if (m_pos + n > m_size)
+        {
+            throw std::runtime_error("[McapIO] Truncated CDR message");
+        }
+    }
+
+    const uint8_t* m_data;
+    size_t m_size;
+    size_t m_pos;
+    bool m_littleEndian;
+};
+
+/// Reads a std_msgs/Header and returns the stamp in seconds
+double readHeader(CdrReader& reader, std::string& frame)
+{
+    const int32_t sec = reader.read<int32_t>();
+    const uint32_t nanosec = reader.read<uint32_t>();
+    frame = reader.readString();
+    return sec + nanosec * 1e-9;
+}
+
+struct TfSample
+{
+    double time;
+    Eigen::Vector3d translation;
+    Eigen::Quaterniond rotation;
+};
+
+/**
+ * Transformations of a tf tree. Each frame has at most one parent.
+ */
+class TfBuffer
+{
+public:
+    void add(const std::string& parent, const std::string& child, const TfSample& sample, bool isStatic)
+    {
+        Edge& edge = m_edges[child];
+        edge.parent = parent;
+        edge.isStatic = isStatic;
+        if (isStatic)
+        {
+            edge.samples.assign(1, sample);
+        }
+        else
+        {
+            edge.samples.push_back(sample);
+        }
+    }
+
+    void sort()
+    {
+        for (auto& edge : m_edges)
+        {
+            std::sort(edge.second.samples.begin(), edge.second.samples.end(),
+                [](const TfSample& a, const TfSample& b) { return a.time < b.time; });
+        }
+    }
+
+    /// Transformation from source to target at the given time
+    bool lookup(const std::string& target, const std::string& source, double time, Eigen::Isometry3d& transform) const
+    {
+        Eigen::Isometry3d sourceToRoot;
+        Eigen::Isometry3d targetToRoot;
+        std::string sourceRoot;
+        std::string targetRoot;
+        if (!toRoot(source, time, sourceToRoot, sourceRoot) || !toRoot(target, time, targetToRoot, targetRoot)
+            || sourceRoot != targetRoot)
+        {
+            return false;
+        }
+        transform = targetToRoot.inverse() * sourceToRoot;
+        return true;
+    }
+
+private:
+    struct Edge
+    {
+        std::string parent;
+        bool isStatic = false;
+        std::vector<TfSample> samples;
+    };
+
+    bool toRoot(const std::string& frame, double time, Eigen::Isometry3d& transform, std::string& root) const
+    {
+        transform.setIdentity();
+        root = frame;
+        for (size_t depth = 0; depth < m_edges.size(); depth++)
+        {
+            auto it = m_edges.find(root);
+            if (it == m_edges.end())
+            {
+                return true;
+            }
+            const Edge& edge = it->second;
+            if (edge.samples.empty())
+            {
+                return false;
+            }
+            transform = interpolate(edge.samples, time) * transform;
+            root = edge.parent;
+        }
+        // The tree contains a cycle
+        return false;
+    }
+
+    /// Linear interpolation of the translation and spherical interpolation of the
+    /// rotation. Times outside of the samples are clamped.
+    static Eigen::Isometry3d interpolate(const std::vector<TfSample>& samples, double time)
+    {
+        auto next = std::lower_bound(samples.begin(), samples.end(), time,
+            [](const TfSample& s, double t) { return s.time < t; });
+
+        TfSample sample;
+        if (next == samples.begin())
+        {
+            sample = samples.front();
+        }
+        else if (next == samples.end())
+        {
+            sample = samples.back();
+        }
+        else
+        {
+            const TfSample& prev = *(next - 1);
+            const double t = next->time > prev.time ? (time - prev.time) / (next->time - prev.time) : 0.0;
+            sample.translation = prev.translation + t * (next->translation - prev.translation);
+            sample.rotation = prev.rotation.slerp(t, next->rotation);
+        }
+
+        Eigen::Isometry3d transform = Eigen::Isometry3d::Identity();
+        transform.linear() = sample.rotation.normalized().toRotationMatrix();
+        transform.translation() = sample.translation;
+        return transform;
+    }
+
+    std::map<std::string, Edge> m_edges;
+};
+
+void readTfMessage(CdrReader& reader, TfBuffer& buffer, bool isStatic)
+{
+    const uint32_t count = reader.read<uint32_t>();
+    for (uint32_t i = 0; i < count; i++)
+    {
+        std::string parent;
+        TfSample sample;
+        sample.time = readHeader(reader, parent);
+        const std::string child = reader.readString();
+        for (int a = 0; a < 3; a++)
+        {
+            sample.translation[a] = reader.read<double>();
+        }
+        const double x = reader.read<double>();
+        const double y = reader.read<double>();
+        const double z = reader.read<double>();
+        const double w = reader.read<double>();
+        sample.rotation = Eigen::Quaterniond(w, x, y, z);
+
+        // tf frame ids must not start with a slash, but some drivers add one
+        auto strip = [](const std::string& id) { return !id.empty() && id[0] == '/' ? id.substr(1) : id; };
+        buffer.add(strip(parent), strip(child), sample, isStatic);
+    }
+}
+
+/// PointField datatypes
+enum FieldType : uint8_t
+{
+    INT8 = 1,
+    UINT8 = 2,
+    INT16 = 3,
+    UINT16 = 4,
+    INT32 = 5,
+    UINT32 = 6,
+    FLOAT32 = 7,
+    FLOAT64 = 8
+};
+
+struct PointField
+{
+    std::string name;
+    uint32_t offset;
+    uint8_t datatype;
+    uint32_t count;
+};
+
+double readField(const uint8_t* data, uint8_t datatype)
+{
+    switch (datatype)
+    {
+    case INT8:    { int8_t v;   std::memcpy(&v, data, 1); return v; }
+    case UINT8:   { uint8_t v;  std::memcpy(&v, data, 1); return v; }
+    case INT16:   { int16_t v;  std::memcpy(&v, data, 2); return v; }
+    case UINT16:  { uint16_t v; std::memcpy(&v, data, 2); return v; }
+    case INT32:   { int32_t v;  std::memcpy(&v, data, 4); return v; }
+    case UINT32:  { uint32_t v; std::memcpy(&v, data, 4); return v; }
+    case FLOAT32: { float v;    std::memcpy(&v, data, 4); return v; }
+    case FLOAT64: { double v;   std::memcpy(&v, data, 8); return v; }
+    default:      return std::numeric_limits<double>::quiet_NaN();
+    }
+}
+
+size_t fieldSize(uint8_t datatype)
+{
+    switch (datatype)
+    {
+    case INT8:
+    case UINT8:   return 1;
+    case INT16:
+    case UINT16:  return 2;
+    case INT32:
+    case UINT32:
+    case FLOAT32: return 4;
+    case FLOAT64: return 8;
+    default:      return 0;
+    }
+}
+
+PointBufferPtr readPointCloud2(CdrReader& reader, TimestampedPointCloud& cloud)
+{
+    cloud.timestamp = readHeader(reader, cloud.frame);
+    if (!cloud.frame.empty() && cloud.frame[0] == '/')
+    {
+        cloud.frame = cloud.frame.substr(1);
+    }
+    const uint32_t height = reader.read<uint32_t>();
+    const uint32_t width = reader.read<uint32_t>();
+
+    std::vector<PointField> fields(reader.read<uint32_t>());
+    for (PointField& field : fields)
+    {
+        field.name = reader.readString();
+        field.offset = reader.read<uint32_t>();
+        field.datatype = reader.read<uint8_t>();
+        field.count = reader.read<uint32_t>();
+    }
+
+    const bool bigEndian = reader.read<uint8_t>() != 0;
+    const uint32_t pointStep = reader.read<uint32_t>();
+    const uint32_t rowStep = reader.read<uint32_t>();
+    const uint32_t dataSize = reader.read<uint32_t>();
+    const uint8_t* data = reader.readBytes(dataSize);
+
+    if (bigEndian)
+    {
+        throw std::runtime_error("[McapIO] Big endian point clouds are not supported");
+    }
+    if (static_cast<size_t>(height) * rowStep > dataSize || static_cast<size_t>(width) * pointStep > rowStep)
+    {
+        throw std::runtime_error("[McapIO] Inconsistent point cloud size");
+    }
+
+    auto find = [&](const std::string& name) -> const PointField*
+    {
+        for (const PointField& field : fields)
+        {
+            if (field.name == name)
+            {
+                return &field;
+            }
+        }
+        return nullptr;
+    };
+    const PointField* x = find("x");
+    const PointField* y = find("y");
+    const PointField* z = find("z");
+    if (!x || !y || !z)
+    {
+        throw std::runtime_error("[McapIO] Point cloud without x, y and z fields");
+    }
+    const PointField* intensity = find("intensity");
+    const PointField* rgb = find("rgb") ? find("rgb") : find("rgba");
+
+    // Per point time: "time" (Velodyne, seconds), "t" (Ouster, nanoseconds)
+    // or "timestamp" (Hesai, seconds). Values are either absolute or relative
+    // to the header stamp.
+    const PointField* time = find("time") ? find("time") : (find("timestamp") ? find("timestamp") : find("t"));
+    const double timeScale = time && time->name == "t" ? 1e-9 : 1.0;
+
+    // All other scalar fields are stored as float channels
+    std::vector<const PointField*> extra;
+    for (const PointField& field : fields)
+    {
+        if (&field != x && &field != y && &field != z && &field != intensity && &field != rgb && &field != time && field.count == 1)
+        {
+            extra.push_back(&field);
+        }
+    }
+
+    // Every field that is read has to lie within a single point
+    auto validate = [&](const PointField* field, size_t size)
+    {
+        if (size == 0)
+        {
+            throw std::runtime_error("[McapIO] Unknown datatype " + std::to_string(field->datatype) + " of field '" + field->name + "'");
+        }
+        if (static_cast<size_t>(field->offset) + size > pointStep)
+        {
+            throw std::runtime_error("[McapIO] Field '" + field->name + "' exceeds the point step");
+        }
+    };
+    for (const PointField* field : {x, y, z, intensity, time})
+    {
+        if (field)
+        {
+            validate(field, fieldSize(field->datatype));
+        }
+    }
+    if (rgb)
+    {
+        validate(rgb, 4);
+    }
+    for (const PointField* field : extra)
+    {
+        validate(field, fieldSize(field->datatype));
+    }
+
+    std::vector<float> points;
+    std::vector<float> intensities;
+    std::vector<unsigned char> colors;
+    std::vector<double> timestamps;
+    std::vector<std::vector<float>> extraValues(extra.size());
+    for (uint32_t row = 0; row < height; row++)
+    {
+        for (uint32_t col = 0; col < width; col++)
+        {
+            const uint8_t* point = data + static_cast<size_t>(row) * rowStep + static_cast<size_t>(col) * pointStep;
+            const double px = readField(point + x->offset, x->datatype);
+            const double py = readField(point + y->offset, y->datatype);
+            const double pz = readField(point + z->offset, z->datatype);
+            if (!std::isfinite(px) || !std::isfinite(py) || !std::isfinite(pz))
+            {
+                continue;
+            }
+            points.insert(points.end(), {(float)px, (float)py, (float)pz});
+
+            if (intensity)
+            {
+                intensities.push_back(readField(point + intensity->offset, intensity->datatype));
+            }
+            if (rgb)
+            {
+                // Packed as 0x00RRGGBB in a float or uint32
+                uint32_t packed;
+                std::memcpy(&packed, point + rgb->offset, 4);
+                colors.insert(colors.end(), {
+                    (unsigned char)((packed >> 16) & 0xff),
+                    (unsigned char)((packed >> 8) & 0xff),
+                    (unsigned char)(packed & 0xff)});
+            }
+            if (time)
+            {
+                // Offsets are small, absolute stamps are seconds since 1970
+                const double t = readField(point + time->offset, time->datatype) * timeScale;
+                timestamps.push_back(t < 1e6 ? cloud.timestamp + t : t);
+            }
+            for (size_t i = 0; i < extra.size(); i++)
+            {
+                extraValues[i].push_back(readField(point + extra[i]->offset, extra[i]->datatype));
+            }
+        }
+    }
+
+    const size_t n = points.size() / 3;
+    PointBufferPtr buffer = std::make_shared<PointBuffer>();
+    if (n == 0)
+    {
+        return buffer;
+    }
+    buffer->setPointArray(Util::convert_vector_to_shared_array(points), n);
+    if (intensity)
+    {
+        buffer->addFloatChannel(Util::convert_vector_to_shared_array(intensities), "intensities", n, 1);
+    }
+    if (rgb)
+    {
+        buffer->setColorArray(Util::convert_vector_to_shared_array(colors), n);
+    }
+    if (time)
+    {
+        buffer->addChannel<double>(Util::convert_vector_to_shared_array(timestamps), "timestamps", n, 1);
+    }
+    for (size_t i = 0; i < extra.size(); i++)
+    {
+        buffer->addFloatChannel(Util::convert_vector_to_shared_array(extraValues[i]), extra[i]->name, n, 1);
+    }
+    return buffer;
+}
+
+void transformPoints(PointBufferPtr buffer, const Eigen::Isometry3d& transform)
+{
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    for (size_t i = 0; i < n; i++)
+    {
+        const Eigen::Vector3d p = transform * Eigen::Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        points[3 * i] = p.x();
+        points[3 * i + 1] = p.y();
+        points[3 * i + 2] = p.z();
+    }
+}
+
+} // anonymous namespace
+
+std::vector<TimestampedPointCloud> readMcapPointClouds(
+    const boost::filesystem::path& file,
+    const McapReadOptions& options)
+{
+    mcap::McapReader reader;
+    const mcap::Status status = reader.open(file.string());
+    if (!status.ok())
+    {
+        throw std::runtime_error("[McapIO] Could not open " + file.string() + ": " + status.message);
+    }
+
+    auto onProblem = [](const mcap::Status& problem)
+    {
+        lvr2::logout::get() << lvr2::warning << "[McapIO] " << problem.message << lvr2::endl;
+    };
+    auto isCdr = [](const mcap::MessageView& view, const std::string& schema)
+    {
+        return view.schema && view.schema->name == schema && view.channel->messageEncoding == "cdr";
+    };
+
+    // Collect all transformations first, so that they can be interpolated
+    TfBuffer tf;
+    if (!options.fixedFrame.empty())
+    {
+        for (const mcap::MessageView& view : reader.readMessages(onProblem))
+        {
+            const std::string& topic = view.channel->topic;
+            if ((topic == "/tf" || topic == "/tf_static") && isCdr(view, "tf2_msgs/msg/TFMessage"))
+            {
+                CdrReader cdr(view.message.data, view.message.dataSize);
+                readTfMessage(cdr, tf, topic == "/tf_static");
+            }
+        }
+        tf.sort();
+    }
+
+    std::vector<TimestampedPointCloud> clouds;
+    size_t index = 0;
+    for (const mcap::MessageView& view : reader.readMessages(onProblem))
+    {
+        const std::string& topic = view.channel->topic;
+        if (!isCdr(view, "sensor_msgs/msg/PointCloud2")
+            || (!options.topics.empty() && std::find(options.topics.begin(), options.topics.end(), topic) == options.topics.end()))
+        {
+            continue;
+        }
+        if (index++ % std::max<size_t>(options.stride, 1) != 0)
+        {
+            continue;
+        }
+
+        TimestampedPointCloud cloud;
+        cloud.topic = topic;
+        try
+        {
+            CdrReader cdr(view.message.data, view.message.dataSize);
+            cloud.points = readPointCloud2(cdr, cloud);
+        }
+        catch (const std::runtime_error& e)
+        {
+            lvr2::logout::get() << lvr2::warning << e.what() << " (topic " << topic << ")" << lvr2::endl;
+            continue;
+        }
+
+        Eigen::Isometry3d pose;
+        if (!options.fixedFrame.empty() && tf.lookup(options.fixedFrame, cloud.frame, cloud.timestamp, pose))
+        {
+            cloud.pose = pose.matrix();
+            cloud.hasPose = true;
+            if (options.transformPoints)
+            {
+                transformPoints(cloud.points, pose);
+            }
+        }
+        clouds.push_back(cloud);
+
+        if (options.maxClouds > 0 && clouds.size() >= options.maxClouds)
+        {
+            break;
+        }
+    }
+    reader.close();
+
+    const size_t withPose = std::count_if(clouds.begin(), clouds.end(),
+        [](const TimestampedPointCloud& c) { return c.hasPose; });
+    lvr2::logout::get() << lvr2::info << "[McapIO] Read " << clouds.size() << " point clouds from " << file.string();
+    if (!options.fixedFrame.empty())
+    {
+        lvr2::logout::get() << ", " << withPose << " with pose in frame " << options.fixedFrame;
+    }
+    lvr2::logout::get() << lvr2::endl;
+    return clouds;
+}
+
+} // namespace lvr2
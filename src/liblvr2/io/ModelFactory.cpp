@@ -39,6 +39,7 @@
 #include "lvr2/io/modelio/LasIO.hpp"
 #include "lvr2/io/modelio/DatIO.hpp"
 #include "lvr2/io/modelio/STLIO.hpp"
+#include "lvr2/io/modelio/VtkIO.hpp"
 #include "lvr2/io/modelio/B3dmIO.hpp"
 
 
@@ -63,6 +64,10 @@
 #include "lvr2/io/modelio/RxpIO.hpp"
 #endif
 
+#ifdef LVR2_USE_DRACO
+#include "lvr2/io/modelio/DrcIO.hpp"
+#endif
+
 #include <boost/filesystem.hpp>
 
 namespace lvr2
@@ -107,6 +112,12 @@ ModelPtr ModelFactory::readModel( std::string filename )
         io = new B3dmIO;
     }
 #endif
+#ifdef LVR2_USE_DRACO
+    else if (extension == ".drc")
+    {
+        io = new DrcIO;
+    }
+#endif
 #ifdef LVR2_USE_RDB
     else if(extension == ".rdbx")
     {
@@ -244,6 +255,14 @@ void ModelFactory::saveModel( ModelPtr m, std::string filename)
     {
         io = new STLIO;
     }
+    else if (extension == ".vtk" || extension == ".vtp" || extension == ".vtu")
+    {
+        io = new VtkIO;
+    }
+    else if (extension == ".las")
+    {
+        io = new LasIO;
+    }
     /**else if (extension == ".rdbx")
     {
         io = new RdbxIO;
@@ -255,6 +274,13 @@ void ModelFactory::saveModel( ModelPtr m, std::string filename)
         io = new B3dmIO;
     }
 #endif
+#ifdef LVR2_USE_DRACO
+    else if (extension == ".drc")
+    {
+        // Lossless meshes, see encodeDraco()
+        io = new DrcIO;
+    }
+#endif
 #ifdef LVR2_USE_PCL
     else if (extension == ".pcd")
     {
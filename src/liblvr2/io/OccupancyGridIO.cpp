@@ -0,0 +1,123 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * OccupancyGridIO.cpp
+ */
+
+#include "lvr2/io/OccupancyGridIO.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+#include <opencv2/imgcodecs.hpp>
+
+#include <algorithm>
+#include <iomanip>
+
+namespace lvr2
+{
+
+bool saveBinvox(const OccupancyGrid& grid, const boost::filesystem::path& filename)
+{
+    boost::filesystem::ofstream out(filename, std::ios::binary);
+    if (!out)
+    {
+        lvr2::logout::get() << lvr2::error << "[OccupancyGridIO] Could not open " << filename << lvr2::endl;
+        return false;
+    }
+
+    const int dim = std::max(1, grid.size.maxCoeff());
+    out << std::setprecision(9);
+    out << "#binvox 1\n";
+    out << "dim " << dim << " " << dim << " " << dim << "\n";
+    out << "translate " << grid.origin.x() << " " << grid.origin.y() << " " << grid.origin.z() << "\n";
+    out << "scale " << dim * grid.resolution << "\n";
+    out << "data\n";
+
+    // binvox stores the voxels with y running fastest, then z, then x,
+    // as pairs of value and run length
+    unsigned char value = 0;
+    int count = 0;
+    auto flush = [&]()
+    {
+        if (count > 0)
+        {
+            out.put(value);
+            out.put(static_cast<unsigned char>(count));
+        }
+    };
+    for (int x = 0; x < dim; x++)
+    {
+        for (int z = 0; z < dim; z++)
+        {
+            for (int y = 0; y < dim; y++)
+            {
+                const bool inside = x < grid.size.x() && y < grid.size.y() && z < grid.size.z();
+                const unsigned char v = inside && grid.occupied(x, y, z) ? 1 : 0;
+                if (v != value || count == 255)
+                {
+                    flush();
+                    value = v;
+                    count = 0;
+                }
+                count++;
+            }
+        }
+    }
+    flush();
+
+    return out.good();
+}
+
+bool saveOccupancyMap(
+    const OccupancyGrid& grid,
+    const boost::filesystem::path& filename,
+    const OccupancyMapOptions& options)
+{
+    if (!cv::imwrite(filename.string(), projectOccupancyGrid(grid, options)))
+    {
+        lvr2::logout::get() << lvr2::error << "[OccupancyGridIO] Could not write " << filename << lvr2::endl;
+        return false;
+    }
+
+    boost::filesystem::path yamlFile = filename;
+    yamlFile.replace_extension(".yaml");
+    boost::filesystem::ofstream out(yamlFile);
+    out << std::setprecision(9);
+    out << "image: " << filename.filename().string() << "\n";
+    out << "resolution: " << grid.resolution << "\n";
+    out << "origin: [" << grid.origin.x() << ", " << grid.origin.y() << ", 0.0]\n";
+    out << "negate: 0\n";
+    out << "occupied_thresh: 0.65\n";
+    out << "free_thresh: 0.196\n";
+
+    return out.good();
+}
+
+} // namespace lvr2
@@ -0,0 +1,558 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * OctreeCompression.cpp
+ *
+ * File layout (little endian):
+ *   magic "LVROCT01", flags (uint8), uncompressed payload size (uint64)
+ *   payload, LZ4 compressed in blocks (uint32 raw size, uint32 size, data)
+ *   if the lz4 flag is set, otherwise stored as is
+ *
+ * Payload layout:
+ *   number of points (uint64), depth (uint8), min corner (3 x double),
+ *   resolution (double), occupancy bytes, points per leaf (varint) if
+ *   all points are kept, number of channels (uint32) and for every channel
+ *   name, type, width and data.
+ */
+
+#include "lvr2/io/OctreeCompression.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <lz4.h>
+
+#include <algorithm>
+#include <cmath>
+#include <cstring>
+#include <fstream>
+#include <iterator>
+#include <limits>
+#include <numeric>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+const char MAGIC[8] = {'L', 'V', 'R', 'O', 'C', 'T', '0', '1'};
+const uint8_t FLAG_LZ4 = 1;
+const uint8_t FLAG_ALL_POINTS = 2;
+const int MAX_DEPTH = 21;
+const size_t LZ4_BLOCK_SIZE = 64 * 1024 * 1024;
+
+enum ChannelType : uint8_t
+{
+    UCHAR = 0,
+    INT = 1,
+    UINT = 2,
+    FLOAT = 3,
+    DOUBLE = 4
+};
+
+class Writer
+{
+public:
+    template<typename T>
+    void put(const T& value)
+    {
+        putBytes(&value, sizeof(T));
+    }
+
+    void putBytes(const void* data, size_t bytes)
+    {
+        const char* c = static_cast<const char*>(data);
+        m_data.insert(m_data.end(), c, c + bytes);
+    }
+
+    void putVarint(uint64_t value)
+    {
+        while (value >= 0x80)
+        {
+            m_data.push_back(static_cast<char>((value & 0x7f) | 0x80));
+            value >>= 7;
+        }
+        m_data.push_back(static_cast<char>(value));
+    }
+
+    void putString(const std::string& s)
+    {
+        put<uint32_t>(s.size());
+        putBytes(s.data(), s.size());
+    }
+
+    std::vector<char>& data() { return m_data; }
+
+private:
+    std::vector<char> m_data;
+};
+
+class Reader
+{
+public:
+    Reader(const char* data, size_t size) : m_data(data), m_size(size) {}
+
+    template<typename T>
+    T get()
+    {
+        T value;
+        getBytes(&value, sizeof(T));
+        return value;
+    }
+
+    void getBytes(void* out, size_t bytes)
+    {
+        if (m_pos + bytes > m_size)
+        {
+            throw std::runtime_error("[OctreeCompression] Unexpected end of data");
+        }
+        std::memcpy(out, m_data + m_pos, bytes);
+        m_pos += bytes;
+    }
+
+    size_t remaining() const
+    {
+        return m_size - m_pos;
+    }
+
+    uint64_t getVarint()
+    {
+        uint64_t value = 0;
+        for (int shift = 0; shift < 64; shift += 7)
+        {
+            uint8_t byte = get<uint8_t>();
+            value |= static_cast<uint64_t>(byte & 0x7f) << shift;
+            if (!(byte & 0x80))
+            {
+                return value;
+            }
+        }
+        throw std::runtime_error("[OctreeCompression] Invalid varint");
+    }
+
+    std::string getString()
+    {
+        std::string s(get<uint32_t>(), '\0');
+        getBytes(&s[0], s.size());
+        return s;
+    }
+
+private:
+    const char* m_data;
+    size_t m_size;
+    size_t m_pos = 0;
+};
+
+/// Spreads the lower 21 bits of v to every third bit
+uint64_t spreadBits(uint64_t v)
+{
+    v &= 0x1fffff;
+    v = (v | v << 32) & 0x1f00000000ffffULL;
+    v = (v | v << 16) & 0x1f0000ff0000ffULL;
+    v = (v | v << 8) & 0x100f00f00f00f00fULL;
+    v = (v | v << 4) & 0x10c30c30c30c30c3ULL;
+    v = (v | v << 2) & 0x1249249249249249ULL;
+    return v;
+}
+
+uint64_t compactBits(uint64_t v)
+{
+    v &= 0x1249249249249249ULL;
+    v = (v ^ (v >> 2)) & 0x10c30c30c30c30c3ULL;
+    v = (v ^ (v >> 4)) & 0x100f00f00f00f00fULL;
+    v = (v ^ (v >> 8)) & 0x1f0000ff0000ffULL;
+    v = (v ^ (v >> 16)) & 0x1f00000000ffffULL;
+    v = (v ^ (v >> 32)) & 0x1fffff;
+    return v;
+}
+
+template<typename T>
+void writeChannels(Writer& out, PointBufferPtr buffer, ChannelType type, const std::vector<size_t>& order, uint32_t& count)
+{
+    for (const std::string& name : buffer->keys<T>())
+    {
+        if (name == "points")
+        {
+            continue;
+        }
+        auto channel = buffer->getChannel<T>(name);
+        if (!channel || channel->numElements() != buffer->numPoints())
+        {
+            lvr2::logout::get() << lvr2::warning << "[OctreeCompression] Skipping channel '" << name
+                                << "', it does not contain one entry per point" << lvr2::endl;
+            continue;
+        }
+
+        const size_t width = channel->width();
+        const T* data = channel->dataPtr().get();
+        out.putString(name);
+        out.put<uint8_t>(type);
+        out.put<uint32_t>(width);
+        for (size_t i : order)
+        {
+            out.putBytes(data + i * width, width * sizeof(T));
+        }
+        count++;
+    }
+}
+
+template<typename T>
+void readChannel(Reader& in, PointBufferPtr buffer, const std::string& name, size_t n)
+{
+    size_t width = in.get<uint32_t>();
+    if (width > 0 && n > in.remaining() / (width * sizeof(T)))
+    {
+        throw std::runtime_error("[OctreeCompression] Channel '" + name + "' exceeds the data");
+    }
+    boost::shared_array<T> data(new T[n * width]);
+    in.getBytes(data.get(), n * width * sizeof(T));
+    buffer->addChannel<T>(data, name, n, width);
+}
+
+} // anonymous namespace
+
+std::vector<char> compressPointCloud(PointBufferPtr buffer, const OctreeCompressionOptions& options)
+{
+    const size_t n = buffer ? buffer->numPoints() : 0;
+    floatArr points = n ? buffer->getPointArray() : floatArr();
+
+    // Bounding box and octree depth
+    Vector3d min = Vector3d::Constant(std::numeric_limits<double>::max());
+    Vector3d max = Vector3d::Constant(std::numeric_limits<double>::lowest());
+    for (size_t i = 0; i < n; i++)
+    {
+        Vector3d p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        min = min.cwiseMin(p);
+        max = max.cwiseMax(p);
+    }
+    if (n == 0)
+    {
+        min = max = Vector3d::Zero();
+    }
+
+    if (!(options.resolution > 0))
+    {
+        throw std::invalid_argument("[OctreeCompression] Resolution has to be positive");
+    }
+
+    double resolution = options.resolution;
+    const double extent = (max - min).maxCoeff();
+    int depth = std::max(1, (int)std::ceil(std::log2(extent / resolution + 1)));
+    if (depth > MAX_DEPTH)
+    {
+        depth = MAX_DEPTH;
+        resolution = extent / ((1 << MAX_DEPTH) - 1);
+        lvr2::logout::get() << lvr2::warning << "[OctreeCompression] Resolution too fine for the extent of the cloud. Using "
+                            << resolution << lvr2::endl;
+    }
+    const uint64_t maxCoord = (1ULL << depth) - 1;
+
+    // Morton codes of the leaves
+    std::vector<uint64_t> codes(n);
+    for (size_t i = 0; i < n; i++)
+    {
+        uint64_t q[3];
+        for (int j = 0; j < 3; j++)
+        {
+            double c = std::floor((points[3 * i + j] - min[j]) / resolution);
+            q[j] = std::min<uint64_t>(std::max(c, 0.0), maxCoord);
+        }
+        codes[i] = spreadBits(q[0]) | spreadBits(q[1]) << 1 | spreadBits(q[2]) << 2;
+    }
+
+    std::vector<size_t> order(n);
+    std::iota(order.begin(), order.end(), 0);
+    std::stable_sort(order.begin(), order.end(), [&](size_t a, size_t b) { return codes[a] < codes[b]; });
+
+    std::vector<uint64_t> leaves;
+    std::vector<uint64_t> leafCounts;
+    std::vector<size_t> kept;
+    for (size_t i : order)
+    {
+        if (leaves.empty() || leaves.back() != codes[i])
+        {
+            leaves.push_back(codes[i]);
+            leafCounts.push_back(0);
+            kept.push_back(i);
+        }
+        else if (options.keepAllPoints)
+        {
+            kept.push_back(i);
+        }
+        leafCounts.back()++;
+    }
+
+    Writer out;
+    out.put<uint64_t>(kept.size());
+    out.put<uint8_t>(depth);
+    out.putBytes(min.data(), 3 * sizeof(double));
+    out.put<double>(resolution);
+
+    // Occupancy bytes, breadth first. Nodes of each level are sorted by
+    // their code, which is the order in which the decoder creates them.
+    for (int level = 0; level < depth && !leaves.empty(); level++)
+    {
+        const int shift = 3 * (depth - level - 1);
+        uint64_t parent = leaves[0] >> (shift + 3);
+        uint8_t occupancy = 0;
+        for (uint64_t leaf : leaves)
+        {
+            uint64_t node = leaf >> shift;
+            if ((node >> 3) != parent)
+            {
+                out.put<uint8_t>(occupancy);
+                parent = node >> 3;
+                occupancy = 0;
+            }
+            occupancy |= 1 << (node & 7);
+        }
+        out.put<uint8_t>(occupancy);
+    }
+
+    if (options.keepAllPoints)
+    {
+        for (uint64_t c : leafCounts)
+        {
+            out.putVarint(c);
+        }
+    }
+
+    // Attributes
+    Writer channels;
+    uint32_t numChannels = 0;
+    if (n > 0)
+    {
+        writeChannels<unsigned char>(channels, buffer, UCHAR, kept, numChannels);
+        writeChannels<int>(channels, buffer, INT, kept, numChannels);
+        writeChannels<unsigned int>(channels, buffer, UINT, kept, numChannels);
+        writeChannels<float>(channels, buffer, FLOAT, kept, numChannels);
+        writeChannels<double>(channels, buffer, DOUBLE, kept, numChannels);
+    }
+    out.put<uint32_t>(numChannels);
+    out.putBytes(channels.data().data(), channels.data().size());
+
+    // Header and (compressed) payload
+    const std::vector<char>& payload = out.data();
+    Writer file;
+    file.putBytes(MAGIC, sizeof(MAGIC));
+    file.put<uint8_t>((options.lz4 ? FLAG_LZ4 : 0) | (options.keepAllPoints ? FLAG_ALL_POINTS : 0));
+    file.put<uint64_t>(payload.size());
+
+    if (!options.lz4)
+    {
+        file.putBytes(payload.data(), payload.size());
+        return std::move(file.data());
+    }
+
+    std::vector<char> block(LZ4_compressBound(LZ4_BLOCK_SIZE));
+    for (size_t offset = 0; offset < payload.size(); offset += LZ4_BLOCK_SIZE)
+    {
+        const int rawSize = std::min(LZ4_BLOCK_SIZE, payload.size() - offset);
+        const int size = LZ4_compress_default(payload.data() + offset, block.data(), rawSize, (int)block.size());
+        file.put<uint32_t>(rawSize);
+        file.put<uint32_t>(size);
+        file.putBytes(block.data(), size);
+    }
+    return std::move(file.data());
+}
+
+PointBufferPtr decompressPointCloud(const std::vector<char>& data)
+{
+    Reader header(data.data(), data.size());
+    char magic[sizeof(MAGIC)];
+    header.getBytes(magic, sizeof(magic));
+    if (std::memcmp(magic, MAGIC, sizeof(MAGIC)) != 0)
+    {
+        throw std::runtime_error("[OctreeCompression] Invalid file header");
+    }
+    const uint8_t flags = header.get<uint8_t>();
+    const uint64_t payloadSize = header.get<uint64_t>();
+
+    // LZ4 compresses at most by a factor of 255, check before allocating
+    const size_t headerSize = sizeof(MAGIC) + sizeof(uint8_t) + sizeof(uint64_t);
+    const uint64_t maxRatio = (flags & FLAG_LZ4) ? 255 : 1;
+    if (payloadSize / maxRatio > header.remaining())
+    {
+        throw std::runtime_error("[OctreeCompression] Payload size exceeds the data");
+    }
+    std::vector<char> payload(payloadSize);
+    if (flags & FLAG_LZ4)
+    {
+        std::vector<char> block;
+        for (size_t offset = 0; offset < payloadSize;)
+        {
+            const uint32_t rawSize = header.get<uint32_t>();
+            const uint32_t size = header.get<uint32_t>();
+            block.resize(size);
+            header.getBytes(block.data(), size);
+            if (offset + rawSize > payloadSize
+                || LZ4_decompress_safe(block.data(), payload.data() + offset, size, rawSize) != (int)rawSize)
+            {
+                throw std::runtime_error("[OctreeCompression] Corrupted LZ4 block");
+            }
+            offset += rawSize;
+        }
+    }
+    else
+    {
+        if (data.size() < headerSize + payloadSize)
+        {
+            throw std::runtime_error("[OctreeCompression] Unexpected end of data");
+        }
+        std::copy(data.begin() + headerSize, data.begin() + headerSize + payloadSize, payload.begin());
+    }
+
+    Reader in(payload.data(), payload.size());
+    const uint64_t n = in.get<uint64_t>();
+    const int depth = in.get<uint8_t>();
+    Vector3d min;
+    in.getBytes(min.data(), 3 * sizeof(double));
+    const double resolution = in.get<double>();
+    if (depth > MAX_DEPTH || !(resolution > 0))
+    {
+        throw std::runtime_error("[OctreeCompression] Invalid octree parameters");
+    }
+
+    // Rebuild the leaf codes level by level
+    std::vector<uint64_t> nodes;
+    if (n > 0)
+    {
+        nodes.push_back(0);
+    }
+    for (int level = 0; level < depth && !nodes.empty(); level++)
+    {
+        std::vector<uint64_t> children;
+        for (uint64_t node : nodes)
+        {
+            uint8_t occupancy = in.get<uint8_t>();
+            for (int c = 0; c < 8; c++)
+            {
+                if (occupancy & (1 << c))
+                {
+                    children.push_back(node << 3 | c);
+                }
+            }
+        }
+        nodes.swap(children);
+    }
+
+    // Read the points per leaf first to validate n before allocating
+    std::vector<uint64_t> counts(nodes.size(), 1);
+    uint64_t total = 0;
+    for (uint64_t& count : counts)
+    {
+        if (flags & FLAG_ALL_POINTS)
+        {
+            count = in.getVarint();
+        }
+        if (count > n - total)
+        {
+            break;
+        }
+        total += count;
+    }
+    if (total != n)
+    {
+        throw std::runtime_error("[OctreeCompression] Number of points does not match the octree");
+    }
+
+    floatArr points(new float[3 * n]);
+    size_t index = 0;
+    for (size_t i = 0; i < nodes.size(); i++)
+    {
+        const uint64_t leaf = nodes[i];
+        const uint64_t count = counts[i];
+        Vector3d p = min + resolution * Vector3d(
+            compactBits(leaf) + 0.5,
+            compactBits(leaf >> 1) + 0.5,
+            compactBits(leaf >> 2) + 0.5
+        );
+        for (uint64_t j = 0; j < count; j++, index++)
+        {
+            points[3 * index] = p.x();
+            points[3 * index + 1] = p.y();
+            points[3 * index + 2] = p.z();
+        }
+    }
+
+    PointBufferPtr buffer(new PointBuffer(points, n));
+    const uint32_t numChannels = in.get<uint32_t>();
+    for (uint32_t i = 0; i < numChannels; i++)
+    {
+        std::string name = in.getString();
+        switch (in.get<uint8_t>())
+        {
+        case UCHAR:
+            readChannel<unsigned char>(in, buffer, name, n);
+            break;
+        case INT:
+            readChannel<int>(in, buffer, name, n);
+            break;
+        case UINT:
+            readChannel<unsigned int>(in, buffer, name, n);
+            break;
+        case FLOAT:
+            readChannel<float>(in, buffer, name, n);
+            break;
+        case DOUBLE:
+            readChannel<double>(in, buffer, name, n);
+            break;
+        default:
+            throw std::runtime_error("[OctreeCompression] Unknown type of channel " + name);
+        }
+    }
+    return buffer;
+}
+
+void saveCompressedPointCloud(
+    PointBufferPtr buffer,
+    const boost::filesystem::path& filename,
+    const OctreeCompressionOptions& options)
+{
+    std::vector<char> data = compressPointCloud(buffer, options);
+    std::ofstream out(filename.string(), std::ios::binary);
+    out.write(data.data(), data.size());
+
+    lvr2::logout::get() << lvr2::info << "[OctreeCompression] Wrote " << buffer->numPoints() << " points with "
+                        << data.size() << " bytes to " << filename << lvr2::endl;
+}
+
+PointBufferPtr loadCompressedPointCloud(const boost::filesystem::path& filename)
+{
+    std::ifstream in(filename.string(), std::ios::binary);
+    if (!in.good())
+    {
+        throw std::runtime_error("[OctreeCompression] Unable to open " + filename.string());
+    }
+    std::vector<char> data((std::istreambuf_iterator<char>(in)), std::istreambuf_iterator<char>());
+    return decompressPointCloud(data);
+}
+
+} // namespace lvr2
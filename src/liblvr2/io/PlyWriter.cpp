@@ -0,0 +1,259 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PlyWriter.cpp
+ */
+
+#include "lvr2/io/PlyWriter.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <cstdint>
+#include <iomanip>
+#include <stdexcept>
+#include <vector>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Width of the element count placeholders in the header
+constexpr int CountWidth = 20;
+
+bool isLittleEndian()
+{
+    const uint16_t value = 1;
+    return *reinterpret_cast<const unsigned char*>(&value) == 1;
+}
+
+template<typename T>
+void writeBinary(std::ostream& out, const T& value)
+{
+    out.write(reinterpret_cast<const char*>(&value), sizeof(T));
+}
+
+void writeCount(std::ostream& out, size_t count)
+{
+    out << std::setw(CountWidth) << std::setfill('0') << count;
+}
+
+} // anonymous namespace
+
+PlyWriter::PlyWriter(const boost::filesystem::path& file, bool normals, bool colors)
+    : m_file(file)
+    , m_faceFile(file.string() + ".faces")
+    , m_normals(normals)
+    , m_colors(colors)
+{
+    m_out.open(m_file.string(), std::ios::in | std::ios::out | std::ios::binary | std::ios::trunc);
+    m_faces.open(m_faceFile.string(), std::ios::in | std::ios::out | std::ios::binary | std::ios::trunc);
+    if (!m_out || !m_faces)
+    {
+        throw std::runtime_error("[PlyWriter] Could not create '" + m_file.string() + "'");
+    }
+
+    m_out << "ply\n";
+    m_out << "format " << (isLittleEndian() ? "binary_little_endian" : "binary_big_endian") << " 1.0\n";
+    m_out << "comment written by lvr2 PlyWriter\n";
+    m_out << "element vertex ";
+    m_vertexCountPos = m_out.tellp();
+    writeCount(m_out, 0);
+    m_out << "\n";
+    m_out << "property float x\n";
+    m_out << "property float y\n";
+    m_out << "property float z\n";
+    if (m_normals)
+    {
+        m_out << "property float nx\n";
+        m_out << "property float ny\n";
+        m_out << "property float nz\n";
+    }
+    if (m_colors)
+    {
+        m_out << "property uchar red\n";
+        m_out << "property uchar green\n";
+        m_out << "property uchar blue\n";
+    }
+    m_out << "element face ";
+    m_faceCountPos = m_out.tellp();
+    writeCount(m_out, 0);
+    m_out << "\n";
+    m_out << "property list uchar uint vertex_indices\n";
+    m_out << "end_header\n";
+}
+
+PlyWriter::~PlyWriter()
+{
+    try
+    {
+        close();
+    }
+    catch (const std::exception& e)
+    {
+        lvr2::logout::get() << lvr2::error << e.what() << lvr2::endl;
+    }
+}
+
+void PlyWriter::checkOpen() const
+{
+    if (m_closed)
+    {
+        throw std::runtime_error("[PlyWriter] '" + m_file.string() + "' is already closed");
+    }
+}
+
+size_t PlyWriter::addVertices(const float* vertices, const float* normals, const unsigned char* colors, size_t n)
+{
+    checkOpen();
+    if ((m_normals && !normals) || (m_colors && !colors))
+    {
+        throw std::invalid_argument("[PlyWriter] Missing vertex normals or colors");
+    }
+    if (m_numVertices + n > MaxMeshBufferVertices)
+    {
+        throw IndexOverflowError(m_numVertices + n);
+    }
+
+    for (size_t i = 0; i < n; i++)
+    {
+        m_out.write(reinterpret_cast<const char*>(vertices + 3 * i), 3 * sizeof(float));
+        if (m_normals)
+        {
+            m_out.write(reinterpret_cast<const char*>(normals + 3 * i), 3 * sizeof(float));
+        }
+        if (m_colors)
+        {
+            m_out.write(reinterpret_cast<const char*>(colors + 3 * i), 3);
+        }
+    }
+
+    const size_t first = m_numVertices;
+    m_numVertices += n;
+    return first;
+}
+
+void PlyWriter::addFaces(const unsigned int* indices, size_t n, size_t offset)
+{
+    checkOpen();
+
+    // Validate the whole batch first, so a failure leaves no partial face in the file
+    for (size_t i = 0; i < 3 * n; i++)
+    {
+        const size_t index = indices[i] + offset;
+        if (index >= m_numVertices)
+        {
+            throw std::out_of_range("[PlyWriter] Face references vertex " + std::to_string(index)
+                + ", but only " + std::to_string(m_numVertices) + " vertices were added");
+        }
+    }
+
+    const unsigned char corners = 3;
+    for (size_t i = 0; i < n; i++)
+    {
+        writeBinary(m_faces, corners);
+        for (size_t j = 0; j < 3; j++)
+        {
+            writeBinary(m_faces, static_cast<uint32_t>(indices[3 * i + j] + offset));
+        }
+    }
+    m_numFaces += n;
+}
+
+size_t PlyWriter::addMesh(MeshBufferPtr mesh)
+{
+    const size_t n = mesh->numVertices();
+
+    size_t colorWidth = 0;
+    floatArr vertices = mesh->getVertices();
+    floatArr normals = mesh->getVertexNormals();
+    ucharArr colors = mesh->getVertexColors(colorWidth);
+
+    // Fill in missing attributes and drop the alpha channel
+    std::vector<float> defaultNormals;
+    if (m_normals && !normals)
+    {
+        defaultNormals.assign(3 * n, 0.0f);
+    }
+    std::vector<unsigned char> rgb;
+    if (m_colors && (!colors || colorWidth != 3))
+    {
+        rgb.assign(3 * n, 255);
+        for (size_t i = 0; colors && i < n; i++)
+        {
+            std::copy(colors.get() + colorWidth * i, colors.get() + colorWidth * i + 3, rgb.begin() + 3 * i);
+        }
+    }
+
+    const size_t offset = addVertices(
+        vertices.get(),
+        normals ? normals.get() : defaultNormals.data(),
+        rgb.empty() ? colors.get() : rgb.data(),
+        n);
+    if (mesh->hasFaces())
+    {
+        addFaces(mesh->getFaceIndices().get(), mesh->numFaces(), offset);
+    }
+    return offset;
+}
+
+void PlyWriter::close()
+{
+    if (m_closed)
+    {
+        return;
+    }
+    m_closed = true;
+
+    // Append the faces and remove the temporary file
+    m_faces.flush();
+    m_faces.seekg(0);
+    if (m_numFaces > 0)
+    {
+        m_out << m_faces.rdbuf();
+    }
+    m_faces.close();
+    boost::filesystem::remove(m_faceFile);
+
+    m_out.seekp(m_vertexCountPos);
+    writeCount(m_out, m_numVertices);
+    m_out.seekp(m_faceCountPos);
+    writeCount(m_out, m_numFaces);
+
+    m_out.close();
+    if (m_out.fail())
+    {
+        throw std::runtime_error("[PlyWriter] Could not write '" + m_file.string() + "'");
+    }
+
+    lvr2::logout::get() << lvr2::info << "[PlyWriter] Wrote " << m_numVertices << " vertices and "
+        << m_numFaces << " faces to " << m_file.string() << lvr2::endl;
+}
+
+} // namespace lvr2
@@ -35,6 +35,11 @@
 #include "lvr2/io/Tiles3dIO.hpp"
 
 #include <Cesium3DTilesWriter/TilesetWriter.h>
+#include <CesiumUtility/JsonValue.h>
+
+#include <Eigen/Dense>
+
+#include <cmath>
 
 extern const char* VIEWER_HTML;
 
@@ -77,7 +82,46 @@ void indexToName(int i, std::string& name, size_t max)
     name += (i < 10 ? '0' + i : 'a' + i - 10);
 }
 
-void writeTileset(Cesium3DTiles::Tileset& tileset, const std::string& outputDir, float scale)
+std::vector<double> geoReferenceTransform(const GeoReference& geo, double scale)
+{
+    // WGS84 ellipsoid
+    constexpr double a = 6378137.0;
+    constexpr double f = 1.0 / 298.257223563;
+    constexpr double e2 = f * (2 - f);
+
+    const double lat = geo.latitude * M_PI / 180.0;
+    const double lon = geo.longitude * M_PI / 180.0;
+    const double sinLat = std::sin(lat), cosLat = std::cos(lat);
+    const double sinLon = std::sin(lon), cosLon = std::cos(lon);
+
+    // Reference point in earth-centered, earth-fixed coordinates
+    const double N = a / std::sqrt(1 - e2 * sinLat * sinLat);
+    Eigen::Vector3d origin(
+        (N + geo.height) * cosLat * cosLon,
+        (N + geo.height) * cosLat * sinLon,
+        (N * (1 - e2) + geo.height) * sinLat
+    );
+
+    // East, north, up
+    Eigen::Matrix3d R;
+    R.col(0) = Eigen::Vector3d(-sinLon, cosLon, 0);
+    R.col(1) = Eigen::Vector3d(-sinLat * cosLon, -sinLat * sinLon, cosLat);
+    R.col(2) = Eigen::Vector3d(cosLat * cosLon, cosLat * sinLon, sinLat);
+    R *= scale;
+
+    Eigen::Vector3d t = origin - R * Eigen::Vector3d(geo.offset[0], geo.offset[1], geo.offset[2]);
+
+    // column major
+    return {
+        R(0, 0), R(1, 0), R(2, 0), 0,
+        R(0, 1), R(1, 1), R(2, 1), 0,
+        R(0, 2), R(1, 2), R(2, 2), 0,
+        t.x(), t.y(), t.z(), 1
+    };
+}
+
+void writeTileset(Cesium3DTiles::Tileset& tileset, const std::string& outputDir, float scale,
+                  const boost::optional<GeoReference>& geoReference)
 {
     tileset.asset.version = "1.0";
     tileset.geometricError = 1e6; // tileset should always be rendered -> set error very high
@@ -86,15 +130,35 @@ void writeTileset(Cesium3DTiles::Tileset& tileset, const std::string& outputDir,
 
     auto& root = tileset.root;
 
-    double minZ = root.boundingVolume.box[2] - root.boundingVolume.box.back(); // see convertBoundingBox: center.z() - halfVector.z()
-    root.transform =
+    if (geoReference)
     {
-        // 4x4 matrix to place the object somewhere on the globe
-        -scale, 0, 0, 0,
-        0, 0, scale, 0,
-        0, scale, 0, 0,
-        0, 6378137 - minZ * scale, 0, 1
-    };
+        root.transform = geoReferenceTransform(*geoReference, scale);
+
+        using CesiumUtility::JsonValue;
+        tileset.extras["geoReference"] = JsonValue(JsonValue::Object{
+            { "latitude", JsonValue(geoReference->latitude) },
+            { "longitude", JsonValue(geoReference->longitude) },
+            { "height", JsonValue(geoReference->height) },
+            { "offset", JsonValue(JsonValue::Array{
+                JsonValue(geoReference->offset[0]),
+                JsonValue(geoReference->offset[1]),
+                JsonValue(geoReference->offset[2])
+            }) },
+            { "epsg", JsonValue(static_cast<int64_t>(geoReference->epsg)) }
+        });
+    }
+    else
+    {
+        double minZ = root.boundingVolume.box[2] - root.boundingVolume.box.back(); // see convertBoundingBox: center.z() - halfVector.z()
+        root.transform =
+        {
+            // 4x4 matrix to place the object somewhere on the globe
+            -scale, 0, 0, 0,
+            0, 0, scale, 0,
+            0, scale, 0, 0,
+            0, 6378137 - minZ * scale, 0, 1
+        };
+    }
 
     Cesium3DTilesWriter::TilesetWriter writer;
     auto result = writer.writeTileset(tileset);
@@ -0,0 +1,129 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * TrajectoryIO.cpp
+ */
+
+#include "lvr2/io/TrajectoryIO.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+#include <Eigen/Geometry>
+
+#include <algorithm>
+#include <iomanip>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+namespace lvr2
+{
+
+Trajectory loadTrajectory(const boost::filesystem::path& file)
+{
+    boost::filesystem::ifstream in(file);
+    if(!in)
+    {
+        throw std::runtime_error("[TrajectoryIO] Unable to open " + file.string());
+    }
+
+    std::vector<TimedPose> poses;
+    std::string line;
+    size_t lineNumber = 0;
+    while(std::getline(in, line))
+    {
+        lineNumber++;
+        std::replace(line.begin(), line.end(), ',', ' ');
+        std::replace(line.begin(), line.end(), '\t', ' ');
+        const size_t first = line.find_first_not_of(" \r");
+        if(first == std::string::npos || line[first] == '#')
+        {
+            continue;
+        }
+
+        std::istringstream ss(line);
+        std::vector<double> values;
+        double v;
+        while(ss >> v)
+        {
+            values.push_back(v);
+        }
+
+        TimedPose p;
+        if(values.size() == 8)
+        {
+            // Eigen expects w, x, y, z
+            const Eigen::Quaterniond q(values[7], values[4], values[5], values[6]);
+            p.pose.block<3, 3>(0, 0) = q.normalized().toRotationMatrix();
+            p.pose.block<3, 1>(0, 3) = Vector3d(values[1], values[2], values[3]);
+        }
+        else if(values.size() == 13)
+        {
+            for(int r = 0; r < 3; r++)
+            {
+                for(int c = 0; c < 4; c++)
+                {
+                    p.pose(r, c) = values[1 + 4 * r + c];
+                }
+            }
+        }
+        else
+        {
+            throw std::runtime_error("[TrajectoryIO] Unexpected number of values in line "
+                + std::to_string(lineNumber) + " of " + file.string());
+        }
+        p.time = values[0];
+        poses.push_back(p);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[TrajectoryIO] Loaded " << poses.size() << " poses from " << file.string() << lvr2::endl;
+    return Trajectory(std::move(poses));
+}
+
+void saveTrajectory(const boost::filesystem::path& file, const Trajectory& trajectory)
+{
+    boost::filesystem::ofstream out(file);
+    if(!out)
+    {
+        throw std::runtime_error("[TrajectoryIO] Unable to open " + file.string());
+    }
+
+    out << "# time x y z qx qy qz qw" << std::endl;
+    out << std::setprecision(17);
+    for(const TimedPose& p : trajectory.poses())
+    {
+        const Eigen::Quaterniond q(Eigen::Matrix3d(p.pose.block<3, 3>(0, 0)));
+        out << p.time << " "
+            << p.pose(0, 3) << " " << p.pose(1, 3) << " " << p.pose(2, 3) << " "
+            << q.x() << " " << q.y() << " " << q.z() << " " << q.w() << std::endl;
+    }
+}
+
+} // namespace lvr2
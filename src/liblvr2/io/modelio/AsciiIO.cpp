@@ -41,13 +41,38 @@ using std::ifstream;
 #include <boost/filesystem.hpp>
 
 #include "lvr2/io/modelio/AsciiIO.hpp"
+#include "lvr2/io/AsciiTokenizer.hpp"
 #include "lvr2/util/Progress.hpp"
 #include "lvr2/util/Timestamp.hpp"
+#include "lvr2/util/Util.hpp"
 
 namespace lvr2
 {
 
 
+namespace
+{
+
+/**
+ * Returns true if the current line contains numeric values in all given
+ * columns. Used to detect header lines, e.g. the point count in .pts files.
+ */
+bool isDataLine(const AsciiTokenizer& tokens, std::initializer_list<int> columns)
+{
+    double value;
+    for (int column : columns)
+    {
+        if (column >= 0 && !tokens.tryDouble(column, value))
+        {
+            return false;
+        }
+    }
+    return true;
+}
+
+} // anonymous namespace
+
+
 ModelPtr AsciiIO::read(
         string filename,
         const int &xPos, const int& yPos, const int& zPos,
@@ -62,52 +87,11 @@ ModelPtr AsciiIO::read(
         cout << "»" << extension << "« is not a valid file extension." << endl;
         return ModelPtr();
     }
-    // Count lines in file to estimate the number of present points
-    size_t lines_in_file = countLines(filename);
-
-    if ( lines_in_file < 2 )
-    {
-        cout << timestamp << "AsciiIO: Too few lines in file (has to be > 2)." << endl;
-        return ModelPtr();
-    }
-
-    // Open file
-    std::ifstream in;
-    in.open(filename.c_str());
-
-    // Read first two lines, ignore the first one
-    char buffer[2048];
-    in.getline(buffer, 2048);
-    in.getline(buffer, 2048);
-
-    // Get number of entries in test line and analize
-    int num_columns  = AsciiIO::getEntriesInLine(filename);
-
-    // Reopen file and read data
-    in.close();
-    in.open(filename.c_str());
 
-    // Again skip first line
-    in.getline(buffer, 2048);
-
-    // Buffer related variables
-    size_t numPoints = 0;
-
-    floatArr points;
-    ucharArr pointColors;
-    floatArr pointIntensities;
-
-    // Alloc memory for points
-    numPoints = lines_in_file - 1;
-    points = floatArr( new float[ numPoints * 3 ] );
-    ModelPtr model(new Model);
-    model->m_pointCloud = PointBufferPtr( new PointBuffer);
-
-    // (Some) sanity checks for given paramters
-    if(rPos > num_columns || gPos > num_columns || bPos > num_columns || iPos > num_columns)
+    std::ifstream in(filename.c_str());
+    if (!in.good())
     {
-        cout << timestamp << "Error: At least one attribute index is larger than the number of columns" << endl;
-        // Retrun empty model
+        cout << timestamp << "AsciiIO: Unable to open »" << filename << "«." << endl;
         return ModelPtr();
     }
 
@@ -115,110 +99,74 @@ ModelPtr AsciiIO::read(
     bool has_color = (rPos > -1 && gPos > -1 && bPos > -1);
     bool has_intensity = (iPos > -1);
 
-    // Alloc buffer memory for additional attributes
-    if ( has_color )
-    {
-        pointColors = ucharArr( new uint8_t[ numPoints * 3 ] );
-    }
+    std::vector<float> points;
+    std::vector<unsigned char> pointColors;
+    std::vector<float> pointIntensities;
 
-    if ( has_intensity )
+    try
     {
-        pointIntensities = floatArr( new float[ numPoints ] );
-    }
+        AsciiTokenizer tokens(in, filename);
 
-    // Read data form file
-    size_t c = 0;
-    while (in.good() && c < numPoints)
-    {
-        float x, y, z, intensity, dummy;
-        unsigned int r, g, b;
-
-        for(int i = 0; i < num_columns; i++)
+        // The first line may be a header, skip it if it contains no point
+        bool firstLine = true;
+        while (tokens.next())
         {
-            if(i == xPos)
-            {
-                in >> x;
-            }
-            else if(i == yPos)
-            {
-                in >> y;
-            }
-            else if(i == zPos)
-            {
-                in >> z;
-            }
-            else if(i == rPos)
-            {
-                in >> r;
-            }
-            else if(i == gPos)
+            if (firstLine)
             {
-                in >> g;
+                firstLine = false;
+                if (!isDataLine(tokens, {xPos, yPos, zPos}))
+                {
+                    continue;
+                }
             }
-            else if(i == bPos)
-            {
-                in >> b;
-            }
-            else if(i == iPos)
+
+            points.push_back(tokens.getFloat(xPos));
+            points.push_back(tokens.getFloat(yPos));
+            points.push_back(tokens.getFloat(zPos));
+
+            if (has_color)
             {
-                in >> intensity;
+                for (int pos : {rPos, gPos, bPos})
+                {
+                    pointColors.push_back(static_cast<unsigned char>(
+                        std::min(std::max(tokens.getFloat(pos), 0.0f), 255.0f)));
+                }
             }
-            else
+
+            if (has_intensity)
             {
-                in >> dummy;
+                pointIntensities.push_back(tokens.getFloat(iPos));
             }
-
-        }
-
-        // Read according to determined format
-        if(has_color)
-        {
-            pointColors[ c * 3     ] = (unsigned char) r;
-            pointColors[ c * 3 + 1 ] = (unsigned char) g;
-            pointColors[ c * 3 + 2 ] = (unsigned char) b;
-
         }
-
-        if (has_intensity)
-        {
-            pointIntensities[c] = intensity;
-
-        }
-
-        points[ c * 3     ] = x;
-        points[ c * 3 + 1 ] = y;
-        points[ c * 3 + 2 ] = z;
-        c++;
+    }
+    catch (const AsciiParseError& e)
+    {
+        cout << timestamp << "AsciiIO: " << e.what() << endl;
+        return ModelPtr();
     }
 
-    // Sanity check
-    if(c != numPoints)
+    if (points.empty())
     {
-        cout << timestamp << "Warning: Point count / line count mismatch: "
-             << numPoints << " / " << c << endl;
+        cout << timestamp << "AsciiIO: No points found in »" << filename << "«." << endl;
+        return ModelPtr();
     }
 
     // Assign buffers
-    size_t numColors = 0;
-    size_t numIntensities = 0;
+    size_t numPoints = points.size() / 3;
+    ModelPtr model(new Model);
+    model->m_pointCloud = PointBufferPtr( new PointBuffer);
+    model->m_pointCloud->setPointArray(Util::convert_vector_to_shared_array(points), numPoints);
 
     if(has_color)
     {
-        numColors = numPoints;
-        model->m_pointCloud->setColorArray(pointColors, numColors);
-
+        model->m_pointCloud->setColorArray(Util::convert_vector_to_shared_array(pointColors), numPoints);
     }
 
     if(has_intensity)
     {
-        numIntensities = numPoints;
-        model->m_pointCloud->addFloatChannel(pointIntensities, "intensities", numIntensities, 1);
-
+        model->m_pointCloud->addFloatChannel(Util::convert_vector_to_shared_array(pointIntensities), "intensities", numPoints, 1);
     }
 
-
-    model->m_pointCloud->setPointArray(points, numPoints);
-
     this->m_model = model;
     return model;
 }
@@ -235,32 +183,13 @@ ModelPtr AsciiIO::read(string filename)
         cout << "»" << extension << "« is not a valid file extension." << endl;
         return ModelPtr();
     }
-    // Count lines in file to estimate the number of present points
-    int lines_in_file = countLines(filename);
-
-    if ( lines_in_file < 2 )
-    {
-        cout << timestamp << "AsciiIO: Too few lines in file (has to be > 2)." << endl;
-        return ModelPtr();
-    }
-    // Open the given file. Skip the first line (as it may
-    // contain meta data in some formats). Then try to guess
-    // the additional data using some heuristics that apply for
-    // most data formats: If 4 values per point are, given
+    // Try to guess the additional data using some heuristics that
+    // apply for most data formats: If 4 values per point are, given
     // the 4th value usually is a reflectence information.
     // Six entries suggest RGB information, seven entries
     // intensity and RGB.
 
-    // Open file
-    std::ifstream in;
-    in.open(filename.c_str());
-
-    // Read first to lines, ignore the first one
-    char buffer[2048];
-    in.getline(buffer, 2048);
-    in.getline(buffer, 2048);
-
-    // Get number of entries in test line and analize
+    // Get number of entries in the first point line and analize
     int num_attributes  = AsciiIO::getEntriesInLine(filename) - 3;
     bool has_color      = (num_attributes == 3) || (num_attributes == 4);
     bool has_intensity  = (num_attributes == 1) || (num_attributes == 4);
@@ -403,27 +332,15 @@ size_t AsciiIO::countLines(string filename)
 
 int AsciiIO::getEntriesInLine(string filename)
 {
-
     std::ifstream in(filename.c_str());
+    AsciiTokenizer tokens(in, filename);
 
-    // Get first line from file and skip it (possibly metadata)
-    char line[1024];
-    in.getline(line, 1024);
-
-    // Get second line -> hopefully point data
-    in.getline(line, 1024);
-
-    in.close();
-
-    // Get number of blanks
-    int c = 0;
-    char* pch = strtok(line, " ");
-    while(pch){
-        c++;
-        pch = strtok(NULL, " ");
+    // Skip a possible header line
+    if (tokens.next() && !isDataLine(tokens, {0, 1, 2}))
+    {
+        tokens.next();
     }
-
-    return c;
+    return tokens.size();
 }
 
 
@@ -37,6 +37,7 @@
 #include "lvr2/io/modelio/B3dmIO.hpp"
 
 #include "lvr2/util/Logging.hpp"
+#include "lvr2/texture/TextureFactory.hpp"
 
 #include "lvr2/io/modelio/DracoEncoder.hpp"
 #include "lvr2/io/modelio/DracoDecoder.hpp"
@@ -135,11 +136,7 @@ float* add_attribute(std::vector<std::byte>& buffer,
 
 void convert_texture(const Texture& texture, std::vector<uint8_t>& output)
 {
-    cv::Mat image(texture.m_height, texture.m_width, CV_8UC3, texture.m_data);
-    cv::Mat bgr;
-    cv::cvtColor(image, bgr, cv::COLOR_RGB2BGR);
-
-    cv::imencode(".webp", bgr, output);
+    TextureFactory::encodeTexture(texture, ".webp", output);
 }
 const char* MIME_TYPE = "image/webp";
 
@@ -192,6 +189,12 @@ void B3dmIO::save(std::string filename)
     MaterialPBRMetallicRoughness pbr;
     pbr.metallicFactor = 0;
     pbr.roughnessFactor = 1;
+    if (!mesh->getMaterials().empty())
+    {
+        const lvr2::Material& m = mesh->getMaterials().front();
+        pbr.metallicFactor = m.m_metallic;
+        pbr.roughnessFactor = m.m_roughness;
+    }
     material.pbrMetallicRoughness = pbr;
 
     auto [ primitive_id, primitive ] = push_id(out_mesh.primitives);
@@ -355,6 +358,12 @@ void B3dmIO::saveCompressed(const std::string& filename)
     material->SetDoubleSided(true);
     material->SetMetallicFactor(0);
     material->SetRoughnessFactor(1);
+    if (!m_model->m_mesh->getMaterials().empty())
+    {
+        const lvr2::Material& m = m_model->m_mesh->getMaterials().front();
+        material->SetMetallicFactor(m.m_metallic);
+        material->SetRoughnessFactor(m.m_roughness);
+    }
 
     if (m_model->m_mesh->getTextures().size() == 1)
     {
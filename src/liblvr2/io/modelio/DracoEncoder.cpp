@@ -28,7 +28,9 @@
  **/
 
 #include "lvr2/io/modelio/DracoEncoder.hpp"
+#include "lvr2/texture/TextureFactory.hpp"
 #include <draco/metadata/geometry_metadata.h>
+#include <algorithm>
 
 namespace lvr2
 {
@@ -125,8 +127,8 @@ void saveTextures(std::vector<int32_t>* textureValue, vector<Texture>& textures)
 {
     for (int i = 0; i < textures.size(); ++i)
     {
-        // get texture attributes
-        Texture lvrTexture = textures[i];
+        // get texture attributes, draco stores RGB data
+        Texture lvrTexture = TextureFactory::toRGB(textures[i]);
         int            index      = lvrTexture.m_index;
         int            height     = lvrTexture.m_height;
         int            width      = lvrTexture.m_width;
@@ -706,4 +708,38 @@ std::unique_ptr<draco::EncoderBuffer> encodeDraco(ModelPtr                   mod
     return std::unique_ptr<draco::EncoderBuffer>(nullptr);
 }
 
+std::unique_ptr<draco::EncoderBuffer> encodeDraco(ModelPtr                   modelPtr,
+                                                  draco::EncodedGeometryType type,
+                                                  const DracoOptions&        options)
+{
+    draco::Encoder encoder;
+    const int speed = 10 - std::max(0, std::min(10, options.compressionLevel));
+    encoder.SetSpeedOptions(speed, speed);
+
+    // Draco stores attributes without quantization if none is set
+    auto quantize = [&](draco::GeometryAttribute::Type attribute, int bits)
+    {
+        if (bits > 0)
+        {
+            encoder.SetAttributeQuantization(attribute, bits);
+        }
+    };
+    quantize(draco::GeometryAttribute::POSITION, options.positionBits);
+    quantize(draco::GeometryAttribute::NORMAL, options.normalBits);
+    quantize(draco::GeometryAttribute::TEX_COORD, options.texCoordBits);
+    quantize(draco::GeometryAttribute::COLOR, options.genericBits);
+    quantize(draco::GeometryAttribute::GENERIC, options.genericBits);
+
+    if (type == draco::TRIANGULAR_MESH)
+    {
+        return encodeMesh(modelPtr, encoder);
+    }
+    else if (type == draco::POINT_CLOUD)
+    {
+        return encodePointCloud(modelPtr, encoder);
+    }
+
+    return std::unique_ptr<draco::EncoderBuffer>(nullptr);
+}
+
 } // namespace lvr
\ No newline at end of file
@@ -98,9 +98,10 @@ void DrcIO::save(string filename)
     }
 
     // encode
+    const draco::EncodedGeometryType type = m_model->m_pointCloud ? draco::EncodedGeometryType::POINT_CLOUD
+                                                                   : draco::EncodedGeometryType::TRIANGULAR_MESH;
     std::unique_ptr<draco::EncoderBuffer> buffer =
-        encodeDraco(m_model, (m_model->m_pointCloud ? draco::EncodedGeometryType::POINT_CLOUD
-                                                    : draco::EncodedGeometryType::TRIANGULAR_MESH));
+        m_useOptions ? encodeDraco(m_model, type, m_options) : encodeDraco(m_model, type);
 
     if (buffer)
     {
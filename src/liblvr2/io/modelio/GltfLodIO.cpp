@@ -0,0 +1,271 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * @file       GltfLodIO.cpp
+ * @brief      Export of level of detail chains as a single glTF binary.
+ */
+
+#include "lvr2/io/modelio/GltfLodIO.hpp"
+
+#include "lvr2/util/Logging.hpp"
+
+#include <CesiumGltf/Model.h>
+#include <CesiumGltfWriter/GltfWriter.h>
+#include <CesiumUtility/JsonValue.h>
+
+#include <algorithm>
+#include <cstring>
+#include <fstream>
+#include <limits>
+#include <stdexcept>
+
+using namespace CesiumGltf;
+using CesiumUtility::JsonValue;
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Appends the given data to the buffer and returns the id of a new buffer view
+int32_t addBufferView(Model& model, std::vector<std::byte>& buffer, const void* data, size_t byteLength, int32_t target)
+{
+    BufferView view;
+    view.buffer = 0;
+    view.byteOffset = buffer.size();
+    view.byteLength = byteLength;
+    view.target = target;
+
+    buffer.resize(buffer.size() + byteLength);
+    std::memcpy(buffer.data() + view.byteOffset, data, byteLength);
+    // Keep all views 4 byte aligned
+    buffer.resize((buffer.size() + 3) & ~size_t(3));
+
+    model.bufferViews.push_back(view);
+    return model.bufferViews.size() - 1;
+}
+
+int32_t addVec3Accessor(Model& model, int32_t bufferView, const float* data, size_t count)
+{
+    Accessor accessor;
+    accessor.bufferView = bufferView;
+    accessor.count = count;
+    accessor.componentType = Accessor::ComponentType::FLOAT;
+    accessor.type = Accessor::Type::VEC3;
+
+    std::vector<double> min(3, std::numeric_limits<double>::max());
+    std::vector<double> max(3, std::numeric_limits<double>::lowest());
+    for (size_t i = 0; i < count; i++)
+    {
+        for (int j = 0; j < 3; j++)
+        {
+            min[j] = std::min(min[j], (double)data[i * 3 + j]);
+            max[j] = std::max(max[j], (double)data[i * 3 + j]);
+        }
+    }
+    accessor.min = min;
+    accessor.max = max;
+
+    model.accessors.push_back(accessor);
+    return model.accessors.size() - 1;
+}
+
+/// True, if the first n * 3 values of the arrays are equal
+bool isPrefix(const floatArr& data, const floatArr& prefix, size_t n)
+{
+    return data && prefix && std::equal(prefix.get(), prefix.get() + n * 3, data.get());
+}
+
+} // namespace
+
+void saveGlbLODs(
+    const std::vector<MeshBufferPtr>& lods,
+    const std::string& filename,
+    const std::vector<float>& screenCoverage,
+    const std::map<std::string, std::string>& metadata)
+{
+    if (lods.empty())
+    {
+        throw std::runtime_error("saveGlbLODs: no levels of detail given");
+    }
+    if (!screenCoverage.empty() && screenCoverage.size() != lods.size())
+    {
+        throw std::runtime_error("saveGlbLODs: number of screen coverage values does not match the number of levels");
+    }
+
+    const MeshBufferPtr& finest = lods.front();
+    bool shared = std::all_of(lods.begin(), lods.end(), [&](const MeshBufferPtr& lod)
+    {
+        return lod->numVertices() <= finest->numVertices()
+            && isPrefix(finest->getVertices(), lod->getVertices(), lod->numVertices());
+    });
+    bool sharedNormals = shared && std::all_of(lods.begin(), lods.end(), [&](const MeshBufferPtr& lod)
+    {
+        return isPrefix(finest->getVertexNormals(), lod->getVertexNormals(), lod->numVertices());
+    });
+
+    Model model;
+    model.asset.generator = "lvr2";
+    model.asset.version = "2.0";
+
+    Material material;
+    material.doubleSided = true;
+    MaterialPBRMetallicRoughness pbr;
+    pbr.metallicFactor = 0;
+    pbr.roughnessFactor = 1;
+    if (!finest->getMaterials().empty())
+    {
+        const lvr2::Material& m = finest->getMaterials().front();
+        pbr.metallicFactor = m.m_metallic;
+        pbr.roughnessFactor = m.m_roughness;
+    }
+    material.pbrMetallicRoughness = pbr;
+    model.materials.push_back(material);
+
+    auto& rawBuffer = model.buffers.emplace_back();
+    auto& buffer = rawBuffer.cesium.data;
+
+    int32_t positionView = -1;
+    int32_t normalView = -1;
+    if (shared)
+    {
+        positionView = addBufferView(model, buffer, finest->getVertices().get(),
+                                     finest->numVertices() * 3 * sizeof(float),
+                                     BufferView::Target::ARRAY_BUFFER);
+    }
+    if (sharedNormals)
+    {
+        normalView = addBufferView(model, buffer, finest->getVertexNormals().get(),
+                                   finest->numVertices() * 3 * sizeof(float),
+                                   BufferView::Target::ARRAY_BUFFER);
+    }
+
+    std::vector<int32_t> nodeIds;
+    for (const MeshBufferPtr& lod : lods)
+    {
+        const size_t numVertices = lod->numVertices();
+        const size_t numFaces = lod->numFaces();
+
+        MeshPrimitive primitive;
+        primitive.mode = MeshPrimitive::Mode::TRIANGLES;
+        primitive.material = 0;
+
+        int32_t positions = shared ? positionView :
+            addBufferView(model, buffer, lod->getVertices().get(), numVertices * 3 * sizeof(float),
+                          BufferView::Target::ARRAY_BUFFER);
+        primitive.attributes["POSITION"] = addVec3Accessor(model, positions, lod->getVertices().get(), numVertices);
+
+        if (lod->hasVertexNormals())
+        {
+            int32_t normals = sharedNormals ? normalView :
+                addBufferView(model, buffer, lod->getVertexNormals().get(), numVertices * 3 * sizeof(float),
+                              BufferView::Target::ARRAY_BUFFER);
+            primitive.attributes["NORMAL"] = addVec3Accessor(model, normals, lod->getVertexNormals().get(), numVertices);
+        }
+
+        Accessor indices;
+        indices.bufferView = addBufferView(model, buffer, lod->getFaceIndices().get(),
+                                           numFaces * 3 * sizeof(uint32_t),
+                                           BufferView::Target::ELEMENT_ARRAY_BUFFER);
+        indices.count = numFaces * 3;
+        indices.componentType = Accessor::ComponentType::UNSIGNED_INT;
+        indices.type = Accessor::Type::SCALAR;
+        indices.min = { 0 };
+        indices.max = { (double)numVertices - 1 };
+        model.accessors.push_back(indices);
+        primitive.indices = model.accessors.size() - 1;
+
+        Mesh mesh;
+        mesh.primitives.push_back(primitive);
+        model.meshes.push_back(mesh);
+
+        Node node;
+        node.mesh = model.meshes.size() - 1;
+        model.nodes.push_back(node);
+        nodeIds.push_back(model.nodes.size() - 1);
+    }
+
+    // The finest level references all coarser levels
+    Node& root = model.nodes[nodeIds.front()];
+    if (lods.size() > 1)
+    {
+        JsonValue::Array ids;
+        for (size_t i = 1; i < nodeIds.size(); i++)
+        {
+            ids.emplace_back(static_cast<int64_t>(nodeIds[i]));
+        }
+        root.extensions.emplace("MSFT_lod", JsonValue(JsonValue::Object{ { "ids", JsonValue(ids) } }));
+        model.extensionsUsed.push_back("MSFT_lod");
+
+        if (!screenCoverage.empty())
+        {
+            JsonValue::Array coverage;
+            for (float c : screenCoverage)
+            {
+                coverage.emplace_back(static_cast<double>(c));
+            }
+            root.extras["MSFT_screencoverage"] = JsonValue(coverage);
+        }
+    }
+
+    for (const auto& entry : metadata)
+    {
+        model.asset.extras[entry.first] = JsonValue(entry.second);
+    }
+
+    Scene scene;
+    scene.nodes.push_back(nodeIds.front());
+    model.scenes.push_back(scene);
+    model.scene = 0;
+
+    rawBuffer.byteLength = buffer.size();
+
+    CesiumGltfWriter::GltfWriter writer;
+    auto glb = writer.writeGlb(model, buffer);
+    for (auto& w : glb.warnings)
+    {
+        lvr2::logout::get() << lvr2::warning << "[GltfLodIO] " << w << lvr2::endl;
+    }
+    if (!glb.errors.empty())
+    {
+        for (auto& e : glb.errors)
+        {
+            lvr2::logout::get() << lvr2::error << "[GltfLodIO] " << e << lvr2::endl;
+        }
+        throw std::runtime_error("Failed to write glb file " + filename);
+    }
+
+    std::ofstream file(filename, std::ios::binary);
+    file.write((const char*)glb.gltfBytes.data(), glb.gltfBytes.size());
+
+    lvr2::logout::get() << lvr2::info << "[GltfLodIO] Wrote " << lods.size() << " levels of detail"
+        << (shared ? " with shared vertices" : "") << " to " << filename << lvr2::endl;
+}
+
+} // namespace lvr2
@@ -37,11 +37,18 @@ using std::cout;
 using std::endl;
 
 #include "lvr2/io/modelio/LasIO.hpp"
+#include "lvr2/util/ColorSpace.hpp"
 #include "lvr2/util/Timestamp.hpp"
 
 #include <lasreader.hpp>
 #include <laswriter.hpp>
 
+#include <algorithm>
+#include <array>
+#include <cmath>
+#include <cstdint>
+#include <limits>
+
 namespace lvr2
 {
 
@@ -63,23 +70,74 @@ ModelPtr LasIO::read(string filename )
         floatArr points ( new float[3 * num_points]);
         floatArr intensities ( new float[num_points]);
         ucharArr colors (new unsigned char[3 * num_points]);
+        ushortArr colors16;
+        if(lasreader->point.have_rgb)
+        {
+            colors16 = ushortArr(new unsigned short[3 * num_points]);
+        }
+        ucharArr classification (new unsigned char[num_points]);
+        doubleArr timestamps;
+        if(lasreader->point.have_gps_time)
+        {
+            timestamps = doubleArr(new double[num_points]);
+        }
+
+        // Optionally store coordinates relative to the header offset to
+        // keep float precision for large (e.g. UTM) coordinates
+        const LASheader& header = lasreader->header;
+        GeoMetadata geo;
+        if(m_relativeCoordinates)
+        {
+            geo.offset = {header.x_offset, header.y_offset, header.z_offset};
+        }
+
+        // Look for a projected or geographic CRS in the GeoTIFF keys
+        if(header.vlr_geo_keys && header.vlr_geo_key_entries)
+        {
+            for(int i = 0; i < header.vlr_geo_keys->number_of_keys; i++)
+            {
+                const LASvlr_key_entry& entry = header.vlr_geo_key_entries[i];
+                // 3072: ProjectedCSTypeGeoKey, 2048: GeographicTypeGeoKey
+                if(entry.tiff_tag_location == 0 && (entry.key_id == 3072 || (entry.key_id == 2048 && geo.epsg == 0)))
+                {
+                    geo.epsg = entry.value_offset;
+                }
+            }
+        }
 
         // Read point data
         for(size_t i = 0; i < num_points; i++)
         {
             size_t buf_pos = 3 * i;
             lasreader->read_point();
-            points[buf_pos]     = lasreader->point.x;
-            points[buf_pos + 1] = lasreader->point.y;
-            points[buf_pos + 2] = lasreader->point.z;
+            points[buf_pos]     = lasreader->get_x() - geo.offset[0];
+            points[buf_pos + 1] = lasreader->get_y() - geo.offset[1];
+            points[buf_pos + 2] = lasreader->get_z() - geo.offset[2];
 
-            // Create fake colors from intensities
-            /// TODO: Check for color attributes if possible...
-            colors[buf_pos] = lasreader->point.intensity;
-            colors[buf_pos + 1] = lasreader->point.intensity;
-            colors[buf_pos + 2] = lasreader->point.intensity;
+            if(lasreader->point.have_rgb)
+            {
+                // LAS stores 16 bit colors
+                colors16[buf_pos] = lasreader->point.get_R();
+                colors16[buf_pos + 1] = lasreader->point.get_G();
+                colors16[buf_pos + 2] = lasreader->point.get_B();
+                colors[buf_pos] = colors16[buf_pos] >> 8;
+                colors[buf_pos + 1] = colors16[buf_pos + 1] >> 8;
+                colors[buf_pos + 2] = colors16[buf_pos + 2] >> 8;
+            }
+            else
+            {
+                // Create fake colors from intensities
+                colors[buf_pos] = lasreader->point.intensity;
+                colors[buf_pos + 1] = lasreader->point.intensity;
+                colors[buf_pos + 2] = lasreader->point.intensity;
+            }
 
             intensities[i] = lasreader->point.intensity;
+            classification[i] = lasreader->point.get_classification();
+            if(timestamps)
+            {
+                timestamps[i] = lasreader->point.get_gps_time();
+            }
 
         }
 
@@ -88,6 +146,17 @@ ModelPtr LasIO::read(string filename )
         p_buffer->setPointArray(points, num_points);
         p_buffer->addFloatChannel(intensities, "intensities", num_points, 1);
         p_buffer->setColorArray(colors, num_points);
+        if(colors16)
+        {
+            // Keep the full color precision in addition to the 8 bit colors
+            p_buffer->addChannel<unsigned short>(colors16, "colors16", num_points, 3);
+        }
+        p_buffer->addUCharChannel(classification, "classification", num_points, 1);
+        if(timestamps)
+        {
+            p_buffer->addChannel<double>(timestamps, "timestamps", num_points, 1);
+        }
+        p_buffer->setGeoMetadata(geo);
 
         ModelPtr m_ptr( new Model(p_buffer));
         m_model = m_ptr;
@@ -106,8 +175,179 @@ ModelPtr LasIO::read(string filename )
 
 void LasIO::save( string filename )
 {
-    /// TODO: Implement LAS output
-    std::cerr << "LASIO: Saving not yet implemented." << endl;
+    PointBufferPtr buffer = m_model ? m_model->m_pointCloud : PointBufferPtr();
+    if(!buffer || buffer->numPoints() == 0)
+    {
+        cout << timestamp << "LasIO::save(): No point cloud to save." << endl;
+        return;
+    }
+
+    const size_t num_points = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+
+    size_t w_color = 0;
+    ucharArr colors = buffer->hasColors() ? buffer->getColorArray(w_color) : ucharArr();
+
+    // Prefer the full precision colors, see read(), as long as the 8 bit
+    // colors were not modified since then
+    size_t n_colors16 = 0;
+    size_t w_colors16 = 0;
+    ushortArr colors16 = buffer->getArray<unsigned short>("colors16", n_colors16, w_colors16);
+    if(colors16 && (n_colors16 != num_points || w_colors16 != 3
+        || !colorsMatch16(colors, w_color, colors16, num_points)))
+    {
+        colors16.reset();
+    }
+    FloatChannelOptional intensities = buffer->getFloatChannel("intensities");
+    UCharChannelOptional classification = buffer->getUCharChannel("classification");
+
+    size_t n_timestamps = 0;
+    size_t w_timestamps = 0;
+    doubleArr timestamps = buffer->getArray<double>("timestamps", n_timestamps, w_timestamps);
+    if(timestamps && (n_timestamps != num_points || w_timestamps != 1))
+    {
+        timestamps.reset();
+    }
+
+    // The stored points are relative to the geo offset (if any)
+    boost::optional<GeoMetadata> geo = buffer->getGeoMetadata();
+    const std::array<double, 3> geoOffset = geo ? geo->offset : std::array<double, 3>{{0.0, 0.0, 0.0}};
+    std::array<double, 3> min;
+    std::array<double, 3> max;
+    min.fill(std::numeric_limits<double>::max());
+    max.fill(std::numeric_limits<double>::lowest());
+    for(size_t i = 0; i < num_points; i++)
+    {
+        for(int j = 0; j < 3; j++)
+        {
+            min[j] = std::min(min[j], (double)points[3 * i + j]);
+            max[j] = std::max(max[j], (double)points[3 * i + j]);
+        }
+    }
+
+    // LAS stores each coordinate as a 32 bit integer multiple of the scale
+    // relative to the header offset. Use the geo offset if one is set, the
+    // rounded centre of the data otherwise, so that absolute coordinates
+    // (e.g. UTM) do not overflow.
+    const bool hasGeoOffset = geoOffset[0] != 0.0 || geoOffset[1] != 0.0 || geoOffset[2] != 0.0;
+    std::array<double, 3> offset = geoOffset;
+    double extent = 0.0;
+    for(int j = 0; j < 3; j++)
+    {
+        if(!hasGeoOffset)
+        {
+            offset[j] = std::round((min[j] + max[j]) / 2);
+        }
+        const double relativeMin = min[j] + geoOffset[j] - offset[j];
+        const double relativeMax = max[j] + geoOffset[j] - offset[j];
+        extent = std::max(extent, std::max(std::abs(relativeMin), std::abs(relativeMax)));
+    }
+
+    // Millimetre precision if possible, coarser if the extent requires it
+    double scale = 0.001;
+    while(extent / scale >= std::numeric_limits<int32_t>::max() && scale < 1.0)
+    {
+        scale *= 10;
+    }
+    if(extent / scale >= std::numeric_limits<int32_t>::max())
+    {
+        cout << timestamp << "LasIO::save(): Coordinates exceed the range of LAS. Unable to save " << filename << endl;
+        return;
+    }
+    if(scale > 0.001)
+    {
+        cout << timestamp << "LasIO::save(): Reduced the precision to " << scale << " to fit the coordinates." << endl;
+    }
+
+    LASheader header;
+    header.x_scale_factor = scale;
+    header.y_scale_factor = scale;
+    header.z_scale_factor = scale;
+    header.x_offset = offset[0];
+    header.y_offset = offset[1];
+    header.z_offset = offset[2];
+    if(geo)
+    {
+        if(geo->epsg > 0)
+        {
+            // 1024: GTModelTypeGeoKey, 3072: ProjectedCSTypeGeoKey, 2048: GeographicTypeGeoKey
+            bool geographic = geo->epsg >= 4000 && geo->epsg < 5000;
+            LASvlr_key_entry keys[2];
+            keys[0].key_id = 1024;
+            keys[0].tiff_tag_location = 0;
+            keys[0].count = 1;
+            keys[0].value_offset = geographic ? 2 : 1;
+            keys[1].key_id = geographic ? 2048 : 3072;
+            keys[1].tiff_tag_location = 0;
+            keys[1].count = 1;
+            keys[1].value_offset = geo->epsg;
+            header.set_geo_keys(2, keys);
+        }
+    }
+
+    // Point formats 2 and 3 contain RGB colors, formats 1 and 3 GPS time
+    const bool have_rgb = colors || colors16;
+    if(timestamps)
+    {
+        header.point_data_format = have_rgb ? 3 : 1;
+        header.point_data_record_length = have_rgb ? 34 : 28;
+    }
+    else
+    {
+        header.point_data_format = have_rgb ? 2 : 0;
+        header.point_data_record_length = have_rgb ? 26 : 20;
+    }
+
+    LASpoint point;
+    point.init(&header, header.point_data_format, header.point_data_record_length, &header);
+
+    LASwriteOpener laswriteopener;
+    laswriteopener.set_file_name(filename.c_str());
+    LASwriter* laswriter = laswriteopener.open(&header);
+    if(!laswriter)
+    {
+        cout << timestamp << "LasIO::save(): Unable to open file " << filename << endl;
+        return;
+    }
+
+    for(size_t i = 0; i < num_points; i++)
+    {
+        point.set_x(points[3 * i] + geoOffset[0]);
+        point.set_y(points[3 * i + 1] + geoOffset[1]);
+        point.set_z(points[3 * i + 2] + geoOffset[2]);
+
+        if(intensities)
+        {
+            point.set_intensity((*intensities)[i][0]);
+        }
+        if(classification)
+        {
+            point.set_classification((*classification)[i][0]);
+        }
+        if(timestamps)
+        {
+            point.set_gps_time(timestamps[i]);
+        }
+        if(colors16)
+        {
+            point.set_R(colors16[3 * i]);
+            point.set_G(colors16[3 * i + 1]);
+            point.set_B(colors16[3 * i + 2]);
+        }
+        else if(colors)
+        {
+            point.set_R(colors[w_color * i] << 8);
+            point.set_G(colors[w_color * i + 1] << 8);
+            point.set_B(colors[w_color * i + 2] << 8);
+        }
+
+        laswriter->write_point(&point);
+        laswriter->update_inventory(&point);
+    }
+
+    laswriter->update_header(&header, TRUE);
+    laswriter->close();
+    delete laswriter;
 }
 
 } /* namespace lvr2 */
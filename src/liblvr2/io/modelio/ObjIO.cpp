@@ -36,11 +36,13 @@
  *  @author Denis Meyer (denmeyer@uos.de)
  */
 
+#include <array>
 #include <climits>
 #include <iostream>
 #include <fstream>
 #include <string.h>
 #include <locale.h>
+#include <map>
 #include <sstream>
 
 #include <boost/filesystem.hpp>
@@ -92,7 +94,8 @@ void ObjIO::parseMtlFile(
     if(in.good())
     {
         char buffer[1024];
-        int matIndex = 0;
+        int matIndex = materials.size();
+        bool hasDiffuse = false;
         while(in.good())
         {
             in.getline(buffer, 1024);
@@ -113,11 +116,13 @@ void ObjIO::parseMtlFile(
                 if(it == matNames.end())
                 {
                     Material m;
+                    m.m_name = matName;
                     m.m_color = boost::optional<RGB8Color>({128, 128, 128});
                     m.m_texture = boost::none;
                     materials.push_back(m);
                     matNames[matName] = matIndex;
                     matIndex++;
+                    hasDiffuse = false;
 
                 }
                 else
@@ -126,10 +131,17 @@ void ObjIO::parseMtlFile(
                     std::cout << "ObjIO::parseMtlFile(): Warning: Duplicate material: " << matName << std::endl;
                 }
             }
-            else if(keyword == "Ka")
+            else if(materials.empty())
             {
+                // Ignore statements before the first newmtl
+                continue;
+            }
+            else if(keyword == "Kd" || (keyword == "Ka" && !hasDiffuse))
+            {
+                // Prefer the diffuse color, use the ambient color as fallback
                 float r, g, b;
                 ss >> r >> g >> b;
+                hasDiffuse |= keyword == "Kd";
                 Material& current = materials.back();
                 current.m_color = boost::optional<RGB8Color>({
                     static_cast<unsigned char>(r * 255 + 0.5),
@@ -137,15 +149,30 @@ void ObjIO::parseMtlFile(
                     static_cast<unsigned char>(b * 255 + 0.5)
                 });
             }
+            else if(keyword == "Pm")
+            {
+                // PBR extension of the MTL format
+                ss >> materials.back().m_metallic;
+            }
+            else if(keyword == "Pr")
+            {
+                ss >> materials.back().m_roughness;
+            }
             else if(keyword == "map_Kd")
             {
+                // The file name is the last argument, options like -s or -o precede it
                 string texname;
-                ss >> texname;
+                while(ss >> texname);
 
                 // Add full path to texture file name
                 boost::filesystem::path tex_file = p / texname;
+                materials.back().m_textureFile = tex_file.string();
 
                 Texture texture = TextureFactory::readTexture(tex_file.string());
+                if(texture.m_data == nullptr)
+                {
+                    continue;
+                }
                 unsigned int tex_idx = textures.size();
                 texture.m_index = tex_idx;
                 textures.push_back(std::move(texture));
@@ -163,6 +190,125 @@ void ObjIO::parseMtlFile(
     }
 }
 
+/**
+ * @brief Converts the face corners (vertex, texture coordinate and normal
+ *        indices) to a single index per corner. A vertex keeps its index
+ *        for the first attribute combination it is used with and is
+ *        duplicated for all others, so files that share positions between
+ *        differently textured faces are represented correctly.
+ *        On return, texcoords and normals contain one entry per vertex.
+ *        Returns false without modifying the lists if a corner references
+ *        a vertex, texture coordinate or normal that does not exist.
+ */
+bool resolveCorners(
+        const std::vector<std::array<int, 3>>& corners,
+        std::vector<uint>& faces,
+        std::vector<float>& vertices,
+        std::vector<float>& texcoords,
+        std::vector<float>& normals,
+        std::vector<unsigned char>& colors)
+{
+    const size_t numVertices = vertices.size() / 3;
+    const size_t counts[3] = {numVertices, texcoords.size() / 2, normals.size() / 3};
+    const char* names[3] = {"vertex", "texture coordinate", "normal"};
+    for(const std::array<int, 3>& c : corners)
+    {
+        for(int j = 0; j < 3; j++)
+        {
+            // Optional texture coordinates and normals are -1 if not referenced
+            if((j == 0 && c[j] < 0) || c[j] < -1 || (c[j] >= 0 && (size_t)c[j] >= counts[j]))
+            {
+                std::cout << timestamp << "ObjIO::read(): Invalid " << names[j] << " reference "
+                          << c[j] + 1 << " (" << counts[j] << " defined)." << std::endl;
+                return false;
+            }
+        }
+    }
+
+    const bool hasTexcoords = !texcoords.empty();
+    const bool hasNormals = !normals.empty();
+    const bool hasColors = colors.size() == vertices.size();
+
+    // Faces without explicit references use the attribute with the same index as the vertex
+    std::vector<float> vertexTexcoords(2 * numVertices, 0.0f);
+    std::vector<float> vertexNormals(3 * numVertices, 0.0f);
+    if(texcoords.size() == vertexTexcoords.size())
+    {
+        vertexTexcoords = texcoords;
+    }
+    if(normals.size() == vertexNormals.size())
+    {
+        vertexNormals = normals;
+    }
+
+    auto setAttributes = [&](size_t v, const std::array<int, 3>& c)
+    {
+        if(c[1] >= 0 && 2 * (size_t)c[1] + 1 < texcoords.size())
+        {
+            vertexTexcoords[2 * v] = texcoords[2 * c[1]];
+            vertexTexcoords[2 * v + 1] = texcoords[2 * c[1] + 1];
+        }
+        if(c[2] >= 0 && 3 * (size_t)c[2] + 2 < normals.size())
+        {
+            std::copy(normals.begin() + 3 * c[2], normals.begin() + 3 * c[2] + 3, vertexNormals.begin() + 3 * v);
+        }
+    };
+
+    std::vector<std::pair<int, int>> slots(numVertices, std::make_pair(INT_MIN, INT_MIN));
+    std::map<std::array<int, 3>, uint> duplicates;
+    std::vector<uint> cornerIndices(corners.size());
+    for(size_t i = 0; i < corners.size(); i++)
+    {
+        const std::array<int, 3>& c = corners[i];
+
+        std::pair<int, int> key(c[1], c[2]);
+        if(slots[c[0]].first == INT_MIN)
+        {
+            slots[c[0]] = key;
+            setAttributes(c[0], c);
+            cornerIndices[i] = c[0];
+        }
+        else if(slots[c[0]] == key)
+        {
+            cornerIndices[i] = c[0];
+        }
+        else
+        {
+            auto it = duplicates.find(c);
+            if(it == duplicates.end())
+            {
+                const uint v = vertices.size() / 3;
+                // Copy first, inserting a range of the same vector is undefined if it reallocates
+                const std::array<float, 3> position = {vertices[3 * c[0]], vertices[3 * c[0] + 1], vertices[3 * c[0] + 2]};
+                vertices.insert(vertices.end(), position.begin(), position.end());
+                if(hasColors)
+                {
+                    const std::array<unsigned char, 3> color = {colors[3 * c[0]], colors[3 * c[0] + 1], colors[3 * c[0] + 2]};
+                    colors.insert(colors.end(), color.begin(), color.end());
+                }
+                vertexTexcoords.insert(vertexTexcoords.end(), 2, 0.0f);
+                vertexNormals.insert(vertexNormals.end(), 3, 0.0f);
+                setAttributes(v, c);
+                it = duplicates.emplace(c, v).first;
+            }
+            cornerIndices[i] = it->second;
+        }
+    }
+
+    for(uint& f : faces)
+    {
+        f = cornerIndices[f];
+    }
+
+    texcoords = hasTexcoords ? vertexTexcoords : std::vector<float>();
+    normals = hasNormals ? vertexNormals : std::vector<float>();
+    if(!hasColors)
+    {
+        colors.clear();
+    }
+    return true;
+}
+
 ModelPtr ObjIO::read(std::string filename)
 {
     // Get path from filename
@@ -178,6 +324,7 @@ ModelPtr ObjIO::read(std::string filename)
     std::vector<float>         texcoords;
     std::vector<uint>          faceMaterials;
     std::vector<uint>          faces;
+    std::vector<std::array<int, 3>> corners;
     std::vector<Material>&     materials = mesh->getMaterials();
     std::vector<Texture>&      textures = mesh->getTextures();
 
@@ -241,25 +388,38 @@ ModelPtr ObjIO::read(std::string filename)
                 if(tokens.size() < 4)
                     continue;
 
-                std::vector<std::string> tokens2;
-                tokenize(tokens.at(1),tokens2,"/");
-                int a = atoi(tokens2.at(0).c_str());
-                tokens2.clear();
-
-                tokenize(tokens.at(2),tokens2,"/");
-                int b = atoi(tokens2.at(0).c_str());
-                tokens2.clear();
-
-                tokenize(tokens.at(3),tokens2,"/");
-                int c = atoi(tokens2.at(0).c_str());
-                tokens2.clear();
+                // Parse v, v/vt, v//vn or v/vt/vn references. Negative
+                // indices are relative to the current end of the lists.
+                std::vector<size_t> polygon;
+                for(size_t i = 1; i < tokens.size(); i++)
+                {
+                    std::array<int, 3> corner = {-1, -1, -1};
+                    const size_t counts[3] = {vertices.size() / 3, texcoords.size() / 2, normals.size() / 3};
+
+                    std::stringstream cs(tokens[i]);
+                    std::string idx;
+                    for(int j = 0; j < 3 && std::getline(cs, idx, '/'); j++)
+                    {
+                        if(!idx.empty())
+                        {
+                            int k = atoi(idx.c_str());
+                            corner[j] = k < 0 ? counts[j] + k : k - 1;
+                        }
+                    }
+                    polygon.push_back(corners.size());
+                    corners.push_back(corner);
+                }
 
-                faces.push_back(a - 1);
-                faces.push_back(b - 1);
-                faces.push_back(c - 1);
+                // Triangulate polygons as a fan
+                for(size_t i = 1; i + 1 < polygon.size(); i++)
+                {
+                    faces.push_back(polygon[0]);
+                    faces.push_back(polygon[i]);
+                    faces.push_back(polygon[i + 1]);
 
-                // Use current material
-                faceMaterials.push_back(currentMat);
+                    // Use current material
+                    faceMaterials.push_back(currentMat);
+                }
             }
             else if(keyword == "usemtl")
             {
@@ -298,10 +458,19 @@ ModelPtr ObjIO::read(std::string filename)
         std::cout << timestamp << "ObjIO::read(): Unable to open file'" << filename << "'." << std::endl;
     }
 
+    if(!resolveCorners(corners, faces, vertices, texcoords, normals, colors))
+    {
+        return ModelPtr();
+    }
+
     mesh->setVertices(Util::convert_vector_to_shared_array(vertices), vertices.size() / 3);
     mesh->setFaceIndices(Util::convert_vector_to_shared_array(faces), faces.size() / 3);
 
-    if(faceMaterials.size() == faces.size() / 3)
+    if(materials.empty())
+    {
+        // No material library, so the indices refer to nothing
+    }
+    else if(faceMaterials.size() == faces.size() / 3)
     {
         mesh->setFaceMaterialIndices(Util::convert_vector_to_shared_array(faceMaterials));
     }
@@ -310,9 +479,18 @@ ModelPtr ObjIO::read(std::string filename)
         std::cout << "ObjIO::read(): Warning: Face material index buffer does not match face number." << std::endl;
     }
 
-    mesh->setTextureCoordinates(Util::convert_vector_to_shared_array(texcoords));
-    mesh->setVertexNormals(Util::convert_vector_to_shared_array(normals));
-    mesh->setVertexColors(Util::convert_vector_to_shared_array(colors));
+    if(!texcoords.empty())
+    {
+        mesh->setTextureCoordinates(Util::convert_vector_to_shared_array(texcoords));
+    }
+    if(!normals.empty())
+    {
+        mesh->setVertexNormals(Util::convert_vector_to_shared_array(normals));
+    }
+    if(!colors.empty())
+    {
+        mesh->setVertexColors(Util::convert_vector_to_shared_array(colors));
+    }
 
     ModelPtr m(new Model(mesh));
     m_model = m;
@@ -583,13 +761,17 @@ void ObjIO::save( string filename )
                 mtlFile << "Kd "
                         << m.m_color->at(0) / 255.0f << " "
                         << m.m_color->at(1) / 255.0f << " "
-                        << m.m_color->at(2) / 255.0f << std::endl << std::endl;
+                        << m.m_color->at(2) / 255.0f << std::endl;
+                mtlFile << "Pm " << m.m_metallic << std::endl;
+                mtlFile << "Pr " << m.m_roughness << std::endl << std::endl;
             }
             else
             {
                 mtlFile << "newmtl texture_"      << m.m_texture->idx() << std::endl;
                 mtlFile << "Ka 1.000 1.000 1.000" << std::endl;
                 mtlFile << "Kd 1.000 1.000 1.000" << std::endl;
+                mtlFile << "Pm " << m.m_metallic << std::endl;
+                mtlFile << "Pr " << m.m_roughness << std::endl;
                 mtlFile << "map_Kd texture_"      << m.m_texture->idx()
                         << textureImageExtension << std::endl << std::endl;
             }
@@ -40,12 +40,19 @@
 
 
 #include "lvr2/io/modelio/PLYIO.hpp"
+#include "lvr2/texture/TextureFactory.hpp"
+#include "lvr2/util/ColorSpace.hpp"
 #include "lvr2/util/Timestamp.hpp"
 
+#include <algorithm>
+#include <clocale>
 #include <cstring>
+#include <iomanip>
+#include <map>
 #include <ctime>
 #include <sstream>
 #include <fstream>
+#include <tuple>
 
 #include <boost/filesystem.hpp>
 #include <opencv2/opencv.hpp>
@@ -53,6 +60,162 @@
 namespace lvr2
 {
 
+namespace
+{
+
+/**
+ * Returns the names of the mesh's texture files relative to the PLY file.
+ * Textures that were loaded from an existing file are referenced directly.
+ * All others, or all if the "mesh_save_textures" atomic is set, are saved
+ * next to the PLY file in the format given by "mesh_texture_image_extension"
+ * (0 = .ppm, 1 = .jpg, 2 = .png, default is .png).
+ */
+std::vector<std::string> textureFileNames( MeshBufferPtr mesh, const std::string& filename )
+{
+    std::vector<Texture>& textures = mesh->getTextures();
+    std::vector<Material>& materials = mesh->getMaterials();
+
+    intOptional saveTexturesOpt = mesh->getIntAtomic("mesh_save_textures");
+    const bool saveTextures = saveTexturesOpt && *saveTexturesOpt != 0;
+
+    std::string extension = ".png";
+    intOptional extensionOpt = mesh->getIntAtomic("mesh_texture_image_extension");
+    if ( extensionOpt )
+    {
+        switch ( *extensionOpt )
+        {
+            case 0: extension = ".ppm"; break;
+            case 1: extension = ".jpg"; break;
+        }
+    }
+
+    const boost::filesystem::path file( filename );
+    const boost::filesystem::path dir = boost::filesystem::absolute( file ).parent_path();
+
+    std::vector<std::string> names( textures.size() );
+    for ( const Material& m : materials )
+    {
+        if ( saveTextures || !m.m_texture || !m.m_textureFile || m.m_texture->idx() >= names.size() )
+        {
+            continue;
+        }
+        const boost::filesystem::path source = boost::filesystem::absolute( *m.m_textureFile );
+        if ( names[m.m_texture->idx()].empty() && boost::filesystem::exists( source ) )
+        {
+            names[m.m_texture->idx()] = boost::filesystem::relative( source, dir ).generic_string();
+        }
+    }
+
+    for ( size_t i = 0; i < names.size(); i++ )
+    {
+        if ( names[i].empty() )
+        {
+            names[i] = file.stem().string() + "_texture_" + std::to_string(i) + extension;
+            TextureFactory::saveTexture( textures[i], ( dir / names[i] ).string() );
+        }
+    }
+    return names;
+}
+
+/**
+ * Converts the per face corner texture coordinates to per vertex ones.
+ * A vertex keeps its index for the first texture coordinate it is used
+ * with and is duplicated for all others. Returns the original index of
+ * each vertex, the face indices are updated in place.
+ */
+std::vector<unsigned int> resolveFaceTexcoords(
+        unsigned int* faces,
+        size_t numFaces,
+        const float* faceTexcoords,
+        size_t numVertices,
+        std::vector<float>& vertexTexcoords )
+{
+    std::vector<unsigned int> origin( numVertices );
+    for ( size_t i = 0; i < numVertices; i++ )
+    {
+        origin[i] = i;
+    }
+    vertexTexcoords.assign( numVertices * 2, 0.0f );
+
+    std::vector<bool> used( numVertices, false );
+    std::map<std::tuple<unsigned int, float, float>, unsigned int> duplicates;
+    for ( size_t i = 0; i < numFaces * 3; i++ )
+    {
+        const unsigned int v = faces[i];
+        if ( v >= numVertices )
+        {
+            continue;
+        }
+
+        /* PLY texture coordinates have their origin at the bottom. */
+        const float u = faceTexcoords[ i * 2 ];
+        const float t = 1.0f - faceTexcoords[ i * 2 + 1 ];
+        if ( !used[v] )
+        {
+            used[v] = true;
+            vertexTexcoords[ v * 2 ] = u;
+            vertexTexcoords[ v * 2 + 1 ] = t;
+        }
+        else if ( vertexTexcoords[ v * 2 ] != u || vertexTexcoords[ v * 2 + 1 ] != t )
+        {
+            auto it = duplicates.find( std::make_tuple( v, u, t ) );
+            if ( it == duplicates.end() )
+            {
+                it = duplicates.emplace( std::make_tuple( v, u, t ), origin.size() ).first;
+                origin.push_back( v );
+                vertexTexcoords.push_back( u );
+                vertexTexcoords.push_back( t );
+            }
+            faces[i] = it->second;
+        }
+    }
+    return origin;
+}
+
+/**
+ * Copies the attributes of the duplicated vertices, see resolveFaceTexcoords().
+ */
+template <typename T>
+void duplicateVertexAttributes( boost::shared_array<T>& arr, size_t width, const std::vector<unsigned int>& origin )
+{
+    if ( !arr )
+    {
+        return;
+    }
+    boost::shared_array<T> out( new T[ origin.size() * width ] );
+    for ( size_t i = 0; i < origin.size(); i++ )
+    {
+        std::copy( arr.get() + origin[i] * width, arr.get() + ( origin[i] + 1 ) * width, out.get() + i * width );
+    }
+    arr = out;
+}
+
+/**
+ * Switches LC_NUMERIC to "C" while it is alive, so rply parses and writes
+ * ASCII PLY numbers with a '.' decimal separator regardless of the
+ * application's locale. The previous locale is restored on destruction.
+ */
+class ClassicNumericLocale
+{
+public:
+    ClassicNumericLocale()
+    {
+        const char* current = std::setlocale( LC_NUMERIC, nullptr );
+        m_previous = current ? current : "C";
+        std::setlocale( LC_NUMERIC, "C" );
+    }
+
+    ~ClassicNumericLocale()
+    {
+        std::setlocale( LC_NUMERIC, m_previous.c_str() );
+    }
+
+private:
+    std::string m_previous;
+};
+
+} // anonymous namespace
+
 
 void PLYIO::save( string filename )
 {
@@ -94,6 +257,18 @@ void PLYIO::save( string filename )
     ucharArr m_vertexColors;
     ucharArr m_pointColors;
     uintArr  m_faceIndices;
+    floatArr m_textureCoordinates;
+    indexArray m_faceMaterialIndices;
+
+    // Optional full precision colors and intensities, see read()
+    ushortArr m_vertexColors16;
+    ushortArr m_pointColors16;
+    uintArr   m_vertexIntensities32;
+    uintArr   m_pointIntensities32;
+    size_t    n_wide                  = 0;
+    size_t    w_wide                  = 0;
+
+    boost::optional<GeoMetadata> geo;
 
     // Get buffers
     if ( m_model->m_pointCloud )
@@ -109,6 +284,20 @@ void PLYIO::save( string filename )
         m_pointColors           = pc->getColorArray(w_point_color);
         m_pointIntensities      = pc->getFloatArray("intensities", m_numPointIntensities, dummy);
         m_pointNormals          = pc->getNormalArray();
+
+        // The 16 bit colors are only used while the 8 bit colors were not modified
+        m_pointColors16 = pc->getArray<unsigned short>("colors16", n_wide, w_wide);
+        if ( m_pointColors16 && ( n_wide != m_numPoints || w_wide != 3
+             || !colorsMatch16( m_pointColors, w_point_color, m_pointColors16, m_numPoints ) ) )
+        {
+            m_pointColors16.reset();
+        }
+        m_pointIntensities32 = pc->getArray<unsigned int>("intensities32", n_wide, w_wide);
+        if ( m_pointIntensities32 && ( n_wide != m_numPoints || w_wide != 1 ) )
+        {
+            m_pointIntensities32.reset();
+        }
+        geo                     = pc->getGeoMetadata();
     }
 
     if ( m_model->m_mesh )
@@ -125,6 +314,24 @@ void PLYIO::save( string filename )
         m_vertexIntensity  = mesh->getFloatArray("vertex_intensities", m_numVertexIntensities, dummy);
         m_vertexNormals    = mesh->getVertexNormals();
         m_faceIndices      = mesh->getFaceIndices();
+        m_textureCoordinates  = mesh->getTextureCoordinates();
+        m_faceMaterialIndices = mesh->getFaceMaterialIndices();
+
+        m_vertexColors16 = mesh->getArray<unsigned short>("vertex_colors16", n_wide, w_wide);
+        if ( m_vertexColors16 && ( n_wide != m_numVertices || w_wide != 3
+             || !colorsMatch16( m_vertexColors, w_vertex_color, m_vertexColors16, m_numVertices ) ) )
+        {
+            m_vertexColors16.reset();
+        }
+        m_vertexIntensities32 = mesh->getArray<unsigned int>("vertex_intensities32", n_wide, w_wide);
+        if ( m_vertexIntensities32 && ( n_wide != m_numVertices || w_wide != 1 ) )
+        {
+            m_vertexIntensities32.reset();
+        }
+        if ( mesh->getGeoMetadata() )
+        {
+            geo = mesh->getGeoMetadata();
+        }
     }
 
 
@@ -162,7 +369,31 @@ void PLYIO::save( string filename )
     bool point_intensity   = false;
     bool point_confidence  = false;
     bool point_normal      = false;
+    bool face_texcoord     = false;
+    bool face_texnumber    = false;
 
+    /* Texture coordinates are stored per face corner, see read(). */
+    std::vector<std::string> textureFiles;
+    std::vector<int> faceTexNumbers;
+    if ( m_faceIndices && m_textureCoordinates )
+    {
+        MeshBufferPtr mesh( m_model->m_mesh );
+        std::vector<Material>& materials = mesh->getMaterials();
+        textureFiles = textureFileNames( mesh, filename );
+
+        faceTexNumbers.resize( m_numFaces, textureFiles.size() == 1 && !m_faceMaterialIndices ? 0 : -1 );
+        for ( size_t i = 0; m_faceMaterialIndices && i < m_numFaces; i++ )
+        {
+            const unsigned int material = m_faceMaterialIndices[i];
+            if ( material < materials.size() && materials[material].m_texture )
+            {
+                faceTexNumbers[i] = materials[material].m_texture->idx();
+            }
+        }
+
+        face_texcoord = true;
+        face_texnumber = textureFiles.size() > 1;
+    }
 
     /* Add vertex element. */
     if ( m_vertices )
@@ -184,9 +415,10 @@ void PLYIO::save( string filename )
             }
             else
             {
-                ply_add_scalar_property( oply, "red",   PLY_UCHAR );
-                ply_add_scalar_property( oply, "green", PLY_UCHAR );
-                ply_add_scalar_property( oply, "blue",  PLY_UCHAR );
+                e_ply_type colorType = m_vertexColors16 ? PLY_USHORT : PLY_UCHAR;
+                ply_add_scalar_property( oply, "red",   colorType );
+                ply_add_scalar_property( oply, "green", colorType );
+                ply_add_scalar_property( oply, "blue",  colorType );
                 vertex_color = true;
             }
         }
@@ -202,7 +434,7 @@ void PLYIO::save( string filename )
             }
             else
             {
-                ply_add_scalar_property( oply, "intensity",  PLY_FLOAT );
+                ply_add_scalar_property( oply, "intensity",  m_vertexIntensities32 ? PLY_UINT : PLY_FLOAT );
                 vertex_intensity = true;
             }
         }
@@ -245,6 +477,14 @@ void PLYIO::save( string filename )
         {
             ply_add_element( oply, "face", m_numFaces );
             ply_add_list_property( oply, "vertex_indices", PLY_UCHAR, PLY_INT );
+            if ( face_texcoord )
+            {
+                ply_add_list_property( oply, "texcoord", PLY_UCHAR, PLY_FLOAT );
+            }
+            if ( face_texnumber )
+            {
+                ply_add_scalar_property( oply, "texnumber", PLY_INT );
+            }
         }
     }
 
@@ -268,9 +508,10 @@ void PLYIO::save( string filename )
             }
             else
             {
-                ply_add_scalar_property( oply, "red",   PLY_UCHAR );
-                ply_add_scalar_property( oply, "green", PLY_UCHAR );
-                ply_add_scalar_property( oply, "blue",  PLY_UCHAR );
+                e_ply_type colorType = m_pointColors16 ? PLY_USHORT : PLY_UCHAR;
+                ply_add_scalar_property( oply, "red",   colorType );
+                ply_add_scalar_property( oply, "green", colorType );
+                ply_add_scalar_property( oply, "blue",  colorType );
                 point_color = true;
             }
         }
@@ -286,7 +527,7 @@ void PLYIO::save( string filename )
             }
             else
             {
-                ply_add_scalar_property( oply, "intensity",  PLY_FLOAT );
+                ply_add_scalar_property( oply, "intensity",  m_pointIntensities32 ? PLY_UINT : PLY_FLOAT );
                 point_intensity = true;
             }
         }
@@ -325,6 +566,43 @@ void PLYIO::save( string filename )
         }
     }
 
+    /* Reference the texture images like MeshLab does. */
+    for ( const std::string& textureFile : textureFiles )
+    {
+        ply_add_comment( oply, ( "TextureFile " + textureFile ).c_str() );
+    }
+
+    /* Embed georeferencing information as comments. */
+    if ( geo )
+    {
+        std::stringstream epsg;
+        epsg << "lvr2 epsg " << geo->epsg;
+        ply_add_comment( oply, epsg.str().c_str() );
+
+        std::stringstream offset;
+        offset << std::setprecision(17) << "lvr2 offset "
+            << geo->offset[0] << " " << geo->offset[1] << " " << geo->offset[2];
+        ply_add_comment( oply, offset.str().c_str() );
+    }
+
+    /* Write metadata as comments and obj_info lines. */
+    for ( const auto& entry : m_model->m_metadata )
+    {
+        std::stringstream lines(entry.second);
+        std::string line;
+        while ( std::getline( lines, line ) )
+        {
+            if ( entry.first == "obj_info" )
+            {
+                ply_add_obj_info( oply, line.c_str() );
+            }
+            else
+            {
+                ply_add_comment( oply, (entry.first + " " + line).c_str() );
+            }
+        }
+    }
+
     /* Write header to file. */
     if ( !ply_write_header( oply ) )
     {
@@ -339,7 +617,13 @@ void PLYIO::save( string filename )
         ply_write( oply, (double) m_vertices[ i * 3     ] ); /* x */
         ply_write( oply, (double) m_vertices[ i * 3 + 1 ] ); /* y */
         ply_write( oply, (double) m_vertices[ i * 3 + 2 ] ); /* z */
-        if ( vertex_color )
+        if ( vertex_color && m_vertexColors16 )
+        {
+            ply_write( oply, m_vertexColors16[ i * 3     ] ); /* red */
+            ply_write( oply, m_vertexColors16[ i * 3 + 1 ] ); /* green */
+            ply_write( oply, m_vertexColors16[ i * 3 + 2 ] ); /* blue */
+        }
+        else if ( vertex_color )
         {
             ply_write( oply, m_vertexColors[ i * w_vertex_color     ] ); /* red */
             ply_write( oply, m_vertexColors[ i * w_vertex_color + 1 ] ); /* green */
@@ -347,7 +631,7 @@ void PLYIO::save( string filename )
         }
         if ( vertex_intensity )
         {
-            ply_write( oply, m_vertexIntensity[ i ] );
+            ply_write( oply, m_vertexIntensities32 ? (double) m_vertexIntensities32[ i ] : (double) m_vertexIntensity[ i ] );
         }
         if ( vertex_confidence )
         {
@@ -370,6 +654,21 @@ void PLYIO::save( string filename )
             ply_write( oply, (double) m_faceIndices[ i * 3     ] );
             ply_write( oply, (double) m_faceIndices[ i * 3 + 1 ] );
             ply_write( oply, (double) m_faceIndices[ i * 3 + 2 ] );
+            if ( face_texcoord )
+            {
+                /* PLY texture coordinates have their origin at the bottom. */
+                ply_write( oply, 6.0 );
+                for ( size_t j = 0; j < 3; j++ )
+                {
+                    const unsigned int v = m_faceIndices[ i * 3 + j ];
+                    ply_write( oply, (double) m_textureCoordinates[ v * 2 ] );
+                    ply_write( oply, 1.0 - m_textureCoordinates[ v * 2 + 1 ] );
+                }
+            }
+            if ( face_texnumber )
+            {
+                ply_write( oply, faceTexNumbers[ i ] );
+            }
         }
     }
 
@@ -378,7 +677,13 @@ void PLYIO::save( string filename )
         ply_write( oply, (double) m_points[ i * 3     ] ); /* x */
         ply_write( oply, (double) m_points[ i * 3 + 1 ] ); /* y */
         ply_write( oply, (double) m_points[ i * 3 + 2 ] ); /* z */
-        if ( point_color )
+        if ( point_color && m_pointColors16 )
+        {
+            ply_write( oply, m_pointColors16[ i * 3     ] ); /* red */
+            ply_write( oply, m_pointColors16[ i * 3 + 1 ] ); /* green */
+            ply_write( oply, m_pointColors16[ i * 3 + 2 ] ); /* blue */
+        }
+        else if ( point_color )
         {
             ply_write( oply, m_pointColors[ i * w_point_color     ] ); /* red */
             ply_write( oply, m_pointColors[ i * w_point_color + 1 ] ); /* green */
@@ -386,7 +691,7 @@ void PLYIO::save( string filename )
         }
         if ( point_intensity )
         {
-            ply_write( oply, m_pointIntensities[ i ] );
+            ply_write( oply, m_pointIntensities32 ? (double) m_pointIntensities32[ i ] : (double) m_pointIntensities[ i ] );
         }
         if ( point_confidence )
         {
@@ -440,6 +745,56 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
         std::cerr << timestamp << "Could not read header." << std::endl;
         return ModelPtr();
     }
+
+    /* Keep the remaining comments and obj_info lines as metadata. */
+    std::map<std::string, std::string> metadata;
+    auto addMetadata = [&metadata]( const std::string& key, const std::string& value )
+    {
+        std::string& entry = metadata[key];
+        entry += entry.empty() ? value : "\n" + value;
+    };
+
+    /* Read georeferencing information written by lvr2 and texture files. */
+    boost::optional<GeoMetadata> geo;
+    std::vector<std::string> textureFiles;
+    const char* comment = NULL;
+    while ( (comment = ply_get_next_comment( ply, comment )) )
+    {
+        std::stringstream ss(comment);
+        std::string prefix, key;
+        ss >> prefix >> key;
+        if ( prefix != "lvr2" || (key != "epsg" && key != "offset") )
+        {
+            std::string value;
+            std::stringstream rest(comment);
+            rest >> prefix;
+            std::getline( rest >> std::ws, value );
+            if ( prefix == "TextureFile" )
+            {
+                textureFiles.push_back( value );
+            }
+            else if ( !prefix.empty() )
+            {
+                addMetadata( prefix, value );
+            }
+            continue;
+        }
+
+        GeoMetadata current = geo ? *geo : GeoMetadata();
+        if ( key == "epsg" && (ss >> current.epsg) )
+        {
+            geo = current;
+        }
+        else if ( key == "offset" && (ss >> current.offset[0] >> current.offset[1] >> current.offset[2]) )
+        {
+            geo = current;
+        }
+    }
+    const char* objInfo = NULL;
+    while ( (objInfo = ply_get_next_obj_info( ply, objInfo )) )
+    {
+        addMetadata( "obj_info", objInfo );
+    }
     //std::cout << timestamp << "Loading »" << filename << "«." << std::endl;
 
     /* Check if there are vertices and get the amount of vertices. */
@@ -451,19 +806,27 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     // Buffer count variables
     size_t numVertices              = 0;
     size_t numVertexColors          = 0;
+    size_t numVertexColors16        = 0;
     size_t numVertexConfidences     = 0;
     size_t numVertexIntensities     = 0;
+    size_t numVertexIntensities32   = 0;
     size_t numVertexNormals         = 0;
     size_t numVertexPanoramaCoords  = 0;
 
     size_t numPoints                = 0;
     size_t numPointColors           = 0;
+    size_t numPointColors16         = 0;
     size_t numPointConfidence       = 0;
     size_t numPointIntensities      = 0;
+    size_t numPointIntensities32    = 0;
     size_t numPointNormals          = 0;
     size_t numPointPanoramaCoords   = 0;
     size_t numPointSpectralChannels = 0;
     size_t numFaces                 = 0;
+    size_t numFaceTexcoords         = 0;
+    size_t numFaceTexNumbers        = 0;
+
+    e_ply_type type;
 
     size_t n_channels               = 0; // Number of spectral channels
 
@@ -477,11 +840,16 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
             p_ply_property prop = NULL;
             while ( ( prop = ply_get_next_property( elem, prop ) ) )
             {
-                ply_get_property_info( prop, &name, NULL, NULL, NULL );
+                ply_get_property_info( prop, &name, &type, NULL, NULL );
                 if ( !strcmp( name, "red" ) && readColor )
                 {
                     /* We have color information */
                     numVertexColors = n;
+                    if ( type == PLY_UINT16 || type == PLY_USHORT )
+                    {
+                        /* Keep the full precision of 16 bit colors */
+                        numVertexColors16 = n;
+                    }
                 }
                 else if ( !strcmp( name, "confidence" ) && readConfidence )
                 {
@@ -492,6 +860,11 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
                 {
                     /* We have intensity information */
                     numVertexIntensities = n;
+                    if ( type == PLY_UIN32 || type == PLY_UINT )
+                    {
+                        /* Floats can not represent all 32 bit values */
+                        numVertexIntensities32 = n;
+                    }
                 }
                 else if ( !strcmp( name, "nx" ) && readNormals )
                 {
@@ -511,11 +884,16 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
             p_ply_property prop = NULL;
             while ( ( prop = ply_get_next_property( elem, prop ) ) )
             {
-                ply_get_property_info( prop, &name, NULL, NULL, NULL );
+                ply_get_property_info( prop, &name, &type, NULL, NULL );
                 if ( !strcmp( name, "red" ) && readColor )
                 {
                     /* We have color information */
                     numPointColors = n;
+                    if ( type == PLY_UINT16 || type == PLY_USHORT )
+                    {
+                        /* Keep the full precision of 16 bit colors */
+                        numPointColors16 = n;
+                    }
                 }
                 else if ( !strcmp( name, "confidence" ) && readConfidence )
                 {
@@ -526,6 +904,11 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
                 {
                     /* We have intensity information */
                     numPointIntensities = n;
+                    if ( type == PLY_UIN32 || type == PLY_UINT )
+                    {
+                        /* Floats can not represent all 32 bit values */
+                        numPointIntensities32 = n;
+                    }
                 }
                 else if ( !strcmp( name, "nx" ) && readNormals )
                 {
@@ -542,6 +925,21 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
         else if ( !strcmp( name, "face" ) && readFaces )
         {
             numFaces = n;
+            p_ply_property prop = NULL;
+            while ( ( prop = ply_get_next_property( elem, prop ) ) )
+            {
+                ply_get_property_info( prop, &name, &type, NULL, NULL );
+                if ( !strcmp( name, "texcoord" ) )
+                {
+                    /* We have texture coordinates */
+                    numFaceTexcoords = n;
+                }
+                else if ( !strcmp( name, "texnumber" ) )
+                {
+                    /* We have texture indices */
+                    numFaceTexNumbers = n;
+                }
+            }
         }
     }
 
@@ -566,10 +964,18 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     ucharArr vertexColors;
     ucharArr pointColors;
 
+    ushortArr vertexColors16;
+    ushortArr pointColors16;
+
+    uintArr  vertexIntensities32;
+    uintArr  pointIntensities32;
+
     shortArr vertexPanoramaCoords;
     shortArr pointPanoramaCoords;
 
     uintArr  faceIndices;
+    floatArr faceTexcoords;
+    intArr   faceTexNumbers;
 
 
     /* Allocate memory. */
@@ -581,6 +987,14 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     {
         vertexColors = ucharArr( new unsigned char[ numVertices * 3 ] );
     }
+    if ( numVertexColors16 )
+    {
+        vertexColors16 = ushortArr( new unsigned short[ numVertices * 3 ] );
+    }
+    if ( numVertexIntensities32 )
+    {
+        vertexIntensities32 = uintArr( new unsigned int[ numVertices ] );
+    }
     if ( numVertexConfidences )
     {
         vertexConfidence = floatArr( new float[ numVertices ] );
@@ -601,6 +1015,14 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     {
         faceIndices = indexArray( new unsigned int[ numFaces * 3 ] );
     }
+    if ( numFaceTexcoords )
+    {
+        faceTexcoords = floatArr( new float[ numFaces * 6 ] );
+    }
+    if ( numFaceTexNumbers )
+    {
+        faceTexNumbers = intArr( new int[ numFaces ] );
+    }
     if ( numPoints )
     {
         points = floatArr( new float[ numPoints * 3 ] );
@@ -609,6 +1031,14 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     {
         pointColors = ucharArr( new unsigned char[ numPoints * 3 ] );
     }
+    if ( numPointColors16 )
+    {
+        pointColors16 = ushortArr( new unsigned short[ numPoints * 3 ] );
+    }
+    if ( numPointIntensities32 )
+    {
+        pointIntensities32 = uintArr( new unsigned int[ numPoints ] );
+    }
     if ( numPointConfidence )
     {
         pointConfidences = floatArr( new float[numPoints] );
@@ -629,15 +1059,21 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
 
     float*          vertex                   = vertices.get();
     uint8_t*        vertex_color             = vertexColors.get();
+    uint16_t*       vertex_color16           = vertexColors16.get();
     float*          vertex_confidence        = vertexConfidence.get();
     float*          vertex_intensity         = vertexIntensity.get();
+    uint32_t*       vertex_intensity32       = vertexIntensities32.get();
     float*          vertex_normal            = vertexNormals.get();
     short*          vertex_panorama_coords   = vertexPanoramaCoords.get();
     unsigned int*   face                     = faceIndices.get();
+    float*          face_texcoord            = faceTexcoords.get();
+    int*            face_texnumber           = faceTexNumbers.get();
     float*          point                    = points.get();
     uint8_t*        point_color              = pointColors.get();
+    uint16_t*       point_color16            = pointColors16.get();
     float*          point_confidence         = pointConfidences.get();
     float*          point_intensity          = pointIntensities.get();
+    uint32_t*       point_intensity32        = pointIntensities32.get();
     float*          point_normal             = pointNormals.get();
     short*          point_panorama_coords    = pointPanoramaCoords.get();
 
@@ -649,7 +1085,13 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
         ply_set_read_cb( ply, "vertex", "y", readVertexCb, &vertex, 0 );
         ply_set_read_cb( ply, "vertex", "z", readVertexCb, &vertex, 1 );
     }
-    if ( vertex_color )
+    if ( vertex_color16 )
+    {
+        ply_set_read_cb( ply, "vertex", "red",   readColor16Cb,  &vertex_color16,  0 );
+        ply_set_read_cb( ply, "vertex", "green", readColor16Cb,  &vertex_color16,  0 );
+        ply_set_read_cb( ply, "vertex", "blue",  readColor16Cb,  &vertex_color16,  1 );
+    }
+    else if ( vertex_color )
     {
         ply_set_read_cb( ply, "vertex", "red",   readColorCb,  &vertex_color,  0 );
         ply_set_read_cb( ply, "vertex", "green", readColorCb,  &vertex_color,  0 );
@@ -659,7 +1101,11 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     {
         ply_set_read_cb( ply, "vertex", "confidence", readVertexCb, &vertex_confidence, 1 );
     }
-    if ( vertex_intensity )
+    if ( vertex_intensity32 )
+    {
+        ply_set_read_cb( ply, "vertex", "intensity", readIntensity32Cb, &vertex_intensity32, 1 );
+    }
+    else if ( vertex_intensity )
     {
         ply_set_read_cb( ply, "vertex", "intensity", readVertexCb, &vertex_intensity, 1 );
     }
@@ -680,6 +1126,14 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
         ply_set_read_cb( ply, "face", "vertex_indices", readFaceCb, &face, 0 );
         ply_set_read_cb( ply, "face", "vertex_index", readFaceCb, &face, 0 );
     }
+    if ( face_texcoord )
+    {
+        ply_set_read_cb( ply, "face", "texcoord", readFaceTexcoordCb, &face_texcoord, 0 );
+    }
+    if ( face_texnumber )
+    {
+        ply_set_read_cb( ply, "face", "texnumber", readTexNumberCb, &face_texnumber, 0 );
+    }
 
     if ( point )
     {
@@ -687,7 +1141,13 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
         ply_set_read_cb( ply, "point", "y", readVertexCb, &point, 0 );
         ply_set_read_cb( ply, "point", "z", readVertexCb, &point, 1 );
     }
-    if ( point_color )
+    if ( point_color16 )
+    {
+        ply_set_read_cb( ply, "point", "red",   readColor16Cb,  &point_color16,  0 );
+        ply_set_read_cb( ply, "point", "green", readColor16Cb,  &point_color16,  0 );
+        ply_set_read_cb( ply, "point", "blue",  readColor16Cb,  &point_color16,  1 );
+    }
+    else if ( point_color )
     {
         ply_set_read_cb( ply, "point", "red",   readColorCb,  &point_color,  0 );
         ply_set_read_cb( ply, "point", "green", readColorCb,  &point_color,  0 );
@@ -697,7 +1157,11 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     {
         ply_set_read_cb( ply, "point", "confidence", readVertexCb, &point_confidence, 1 );
     }
-    if ( point_intensity )
+    if ( point_intensity32 )
+    {
+        ply_set_read_cb( ply, "point", "intensity", readIntensity32Cb, &point_intensity32, 1 );
+    }
+    else if ( point_intensity )
     {
         ply_set_read_cb( ply, "point", "intensity", readVertexCb, &point_intensity, 1 );
     }
@@ -714,12 +1178,36 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
     }
 
     /* Read ply file. */
-    if ( !ply_read( ply ) )
+    bool readOk;
+    {
+        ClassicNumericLocale locale;
+        readOk = ply_read( ply );
+    }
+    if ( !readOk )
     {
         std::cerr << timestamp << "Could not read »" << filename << "«."
             << std::endl;
     }
 
+    /* Derive the 8 bit colors and float intensities from the wide values,
+     * so that all algorithms can use the default channels. */
+    for ( size_t i = 0; vertexColors16 && i < numVertices * 3; i++ )
+    {
+        vertexColors[i] = vertexColors16[i] >> 8;
+    }
+    for ( size_t i = 0; pointColors16 && i < numPoints * 3; i++ )
+    {
+        pointColors[i] = pointColors16[i] >> 8;
+    }
+    for ( size_t i = 0; vertexIntensities32 && i < numVertices; i++ )
+    {
+        vertexIntensity[i] = vertexIntensities32[i];
+    }
+    for ( size_t i = 0; pointIntensities32 && i < numPoints; i++ )
+    {
+        pointIntensities[i] = pointIntensities32[i];
+    }
+
     /* Check if we got only vertices and neither points nor faces. If that is
      * the case then use the vertices as points. */
     if ( vertices && !points && !faceIndices )
@@ -728,30 +1216,40 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
             << "Assuming that vertices are meant to be points." << std::endl;
         points                  = vertices;
         pointColors             = vertexColors;
+        pointColors16           = vertexColors16;
+        pointIntensities32      = vertexIntensities32;
         pointConfidences        = vertexConfidence;
         pointIntensities        = vertexIntensity;
         pointNormals            = vertexNormals;
         pointPanoramaCoords     = vertexPanoramaCoords;
         point                   = points.get();
         point_color             = pointColors.get();
+        point_color16           = pointColors16.get();
+        point_intensity32       = pointIntensities32.get();
         point_confidence        = pointConfidences.get();
         point_intensity         = pointIntensities.get();
         point_normal            = pointNormals.get();
         point_panorama_coords   = pointPanoramaCoords.get();
         numPoints               = numVertices;
         numPointColors          = numVertexColors;
+        numPointColors16        = numVertexColors16;
+        numPointIntensities32   = numVertexIntensities32;
         numPointConfidence      = numVertexConfidences;
         numPointIntensities     = numVertexIntensities;
         numPointNormals         = numVertexNormals;
         numPointPanoramaCoords  = numVertexPanoramaCoords;
         numVertices             = 0;
         numVertexColors         = 0;
+        numVertexColors16       = 0;
+        numVertexIntensities32  = 0;
         numVertexConfidences    = 0;
         numVertexIntensities    = 0;
         numVertexNormals        = 0;
         numVertexPanoramaCoords = 0;
         vertices.reset();
         vertexColors.reset();
+        vertexColors16.reset();
+        vertexIntensities32.reset();
         vertexConfidence.reset();
         vertexIntensity.reset();
         vertexNormals.reset();
@@ -760,6 +1258,35 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
 
     ply_close( ply );
 
+    /* Convert the per face texture coordinates to per vertex ones. */
+    floatArr vertexTexcoords;
+    if ( vertices && faceIndices && faceTexcoords )
+    {
+        std::vector<float> texcoords;
+        std::vector<unsigned int> origin = resolveFaceTexcoords(
+                faceIndices.get(), numFaces, faceTexcoords.get(), numVertices, texcoords );
+        if ( origin.size() > numVertices )
+        {
+            duplicateVertexAttributes( vertices, 3, origin );
+            duplicateVertexAttributes( vertexColors, 3, origin );
+            duplicateVertexAttributes( vertexColors16, 3, origin );
+            duplicateVertexAttributes( vertexConfidence, 1, origin );
+            duplicateVertexAttributes( vertexIntensity, 1, origin );
+            duplicateVertexAttributes( vertexIntensities32, 1, origin );
+            duplicateVertexAttributes( vertexNormals, 3, origin );
+            duplicateVertexAttributes( vertexPanoramaCoords, 2, origin );
+
+            for ( size_t* count : { &numVertexColors, &numVertexColors16, &numVertexConfidences,
+                    &numVertexIntensities, &numVertexIntensities32, &numVertexNormals, &numVertexPanoramaCoords } )
+            {
+                *count = *count ? origin.size() : 0;
+            }
+            numVertices = origin.size();
+        }
+        vertexTexcoords = floatArr( new float[ texcoords.size() ] );
+        std::copy( texcoords.begin(), texcoords.end(), vertexTexcoords.get() );
+    }
+
     // read Panorama Images if we have annotated data
     if (numPointPanoramaCoords)
     {
@@ -775,8 +1302,10 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
                 swap(point_panorama_coords, 2 * i, 2 * numPointPanoramaCoords, 2);
                 swap(point, 3 * i, 3 * numPointPanoramaCoords, 3);
                 if (numPointColors)      swap(point_color,      3*i, 3*numPointPanoramaCoords, 3);
+                if (numPointColors16)    swap(point_color16,    3*i, 3*numPointPanoramaCoords, 3);
                 if (numPointConfidence)  swap(point_confidence,   i,   numPointPanoramaCoords, 1);
                 if (numPointIntensities) swap(point_intensity,    i,   numPointPanoramaCoords, 1);
+                if (numPointIntensities32) swap(point_intensity32, i,  numPointPanoramaCoords, 1);
                 if (numPointNormals)     swap(point_normal,     3*i, 3*numPointPanoramaCoords, 3);
 
                 i--;
@@ -862,6 +1391,16 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
             pc->setColorArray(pointColors, numPointColors);
         }
 
+        if (pointColors16)
+        {
+            pc->addChannel<unsigned short>(pointColors16, "colors16", numPointColors16, 3);
+        }
+
+        if (pointIntensities32)
+        {
+            pc->addChannel<unsigned int>(pointIntensities32, "intensities32", numPointIntensities32, 1);
+        }
+
         if (pointIntensities)
         {
             pc->addFloatChannel(pointIntensities, "intensities", numPointIntensities, 1);
@@ -887,6 +1426,11 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
             pc->addIntAtomic(400 + 4 * n_channels, "spectral_wavelength_max");
             pc->addIntAtomic(n_channels, "num_spectral_channels");
         }
+
+        if (geo)
+        {
+            pc->setGeoMetadata(*geo);
+        }
     }
 
     if(vertices)
@@ -909,6 +1453,16 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
             mesh->setVertexColors(vertexColors);
         }
 
+        if (vertexColors16)
+        {
+            mesh->addChannel<unsigned short>(vertexColors16, "vertex_colors16", numVertexColors16, 3);
+        }
+
+        if (vertexIntensities32)
+        {
+            mesh->addChannel<unsigned int>(vertexIntensities32, "vertex_intensities32", numVertexIntensities32, 1);
+        }
+
         if (vertexIntensity)
         {
             mesh->addFloatChannel(vertexIntensity, "vertex_intensities", numVertexIntensities, 1);
@@ -918,9 +1472,68 @@ ModelPtr PLYIO::read( string filename, bool readColor, bool readConfidence,
         {
             mesh->addFloatChannel(vertexConfidence, "vertex_confidences",  numVertexConfidences, 1);
         }
+
+        if (vertexTexcoords)
+        {
+            mesh->setTextureCoordinates(vertexTexcoords);
+        }
+
+        // One textured material per texture file. Faces without a valid
+        // texture number get an additional untextured material.
+        if (vertexTexcoords && !textureFiles.empty())
+        {
+            std::vector<Texture>& textures = mesh->getTextures();
+            std::vector<Material>& materials = mesh->getMaterials();
+            boost::filesystem::path dir = boost::filesystem::path(filename).parent_path();
+            for (const std::string& textureFile : textureFiles)
+            {
+                Material material;
+                material.m_name = boost::filesystem::path(textureFile).stem().string();
+                material.m_textureFile = (dir / textureFile).string();
+
+                Texture texture = TextureFactory::readTexture(*material.m_textureFile);
+                if (texture.m_data != nullptr)
+                {
+                    texture.m_index = textures.size();
+                    material.m_texture = TextureHandle(textures.size());
+                    textures.push_back(std::move(texture));
+                }
+                else
+                {
+                    std::cerr << timestamp << "Could not read texture »" << *material.m_textureFile << "«." << std::endl;
+                    material.m_color = RGB8Color{255, 255, 255};
+                }
+                materials.push_back(material);
+            }
+
+            const unsigned int numTextureMaterials = materials.size();
+            indexArray faceMaterials( new unsigned int[ numFaces ] );
+            for (size_t i = 0; i < numFaces; i++)
+            {
+                int number = faceTexNumbers ? faceTexNumbers[i] : 0;
+                if (number < 0 || (unsigned int) number >= numTextureMaterials)
+                {
+                    if (materials.size() == numTextureMaterials)
+                    {
+                        Material untextured;
+                        untextured.m_color = RGB8Color{255, 255, 255};
+                        materials.push_back(untextured);
+                    }
+                    number = numTextureMaterials;
+                }
+                faceMaterials[i] = number;
+            }
+            mesh->setFaceMaterialIndices(faceMaterials);
+        }
+
+        if (geo)
+        {
+            mesh->setGeoMetadata(*geo);
+        }
     }
 
     ModelPtr m( new Model( mesh, pc ) );
+    m->m_metadata = metadata;
     m_model = m;
     return m;
 
@@ -950,6 +1563,30 @@ int PLYIO::readColorCb( p_ply_argument argument )
 }
 
 
+int PLYIO::readColor16Cb( p_ply_argument argument )
+{
+
+    uint16_t ** color;
+    ply_get_argument_user_data( argument, (void **) &color, NULL );
+    **color = ply_get_argument_value( argument );
+    (*color)++;
+    return 1;
+
+}
+
+
+int PLYIO::readIntensity32Cb( p_ply_argument argument )
+{
+
+    uint32_t ** intensity;
+    ply_get_argument_user_data( argument, (void **) &intensity, NULL );
+    **intensity = ply_get_argument_value( argument );
+    (*intensity)++;
+    return 1;
+
+}
+
+
 int PLYIO::readFaceCb( p_ply_argument argument )
 {
 
@@ -974,6 +1611,42 @@ int PLYIO::readFaceCb( p_ply_argument argument )
 
 }
 
+int PLYIO::readFaceTexcoordCb( p_ply_argument argument )
+{
+
+    float ** texcoord;
+    long int length, value_index;
+    ply_get_argument_user_data( argument, (void **) &texcoord, NULL );
+    ply_get_argument_property( argument, NULL, &length, &value_index );
+    if ( value_index < 0 )
+    {
+        /* We got info about amount of texture coordinates. */
+        if ( ply_get_argument_value( argument ) == 6 )
+        {
+            return 1;
+        }
+        std::cerr << timestamp << "Texture coordinates do not match a triangle mesh." << std::endl;
+        return 0;
+    }
+    **texcoord = ply_get_argument_value( argument );
+    (*texcoord)++;
+
+    return 1;
+
+}
+
+
+int PLYIO::readTexNumberCb( p_ply_argument argument )
+{
+
+    int ** number;
+    ply_get_argument_user_data( argument, (void **) &number, NULL );
+    **number = ply_get_argument_value( argument );
+    (*number)++;
+    return 1;
+
+}
+
 int PLYIO::readPanoramaCoordCB( p_ply_argument argument )
 {
 
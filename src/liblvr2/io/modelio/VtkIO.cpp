@@ -0,0 +1,352 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * VtkIO.cpp
+ */
+
+#include "lvr2/io/modelio/VtkIO.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <boost/filesystem.hpp>
+
+#include <algorithm>
+#include <fstream>
+#include <functional>
+#include <iomanip>
+#include <set>
+#include <type_traits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// A channel converted to double values together with its VTK type names
+struct VtkArray
+{
+    std::string name;
+    size_t width;
+    std::string legacyType;
+    std::string xmlType;
+    bool integral;
+    std::vector<double> data;
+};
+
+/// Decides whether a channel with the given name and number of elements is written
+using ChannelFilter = std::function<bool(const std::string&, size_t)>;
+
+template<typename T>
+void collectArrays(
+    BaseBuffer& buffer,
+    size_t numElements,
+    const std::set<std::string>& skip,
+    const ChannelFilter& filter,
+    const std::string& legacyType,
+    const std::string& xmlType,
+    std::vector<VtkArray>& out)
+{
+    for (const std::string& key : buffer.keys<T>())
+    {
+        if (numElements == 0 || skip.count(key))
+        {
+            continue;
+        }
+
+        typename Channel<T>::Optional channel = buffer.getChannel<T>(key);
+        if (!channel || channel->numElements() != numElements || !filter(key, channel->numElements()))
+        {
+            continue;
+        }
+
+        VtkArray array;
+        array.name = key;
+        std::replace(array.name.begin(), array.name.end(), ' ', '_');
+        array.width = channel->width();
+        array.legacyType = legacyType;
+        array.xmlType = xmlType;
+        array.integral = std::is_integral<T>::value;
+        array.data.resize(numElements * array.width);
+        const T* ptr = channel->dataPtr().get();
+        for (size_t i = 0; i < array.data.size(); i++)
+        {
+            array.data[i] = static_cast<double>(ptr[i]);
+        }
+        out.push_back(std::move(array));
+    }
+}
+
+std::vector<VtkArray> collectArrays(
+    BaseBuffer& buffer,
+    size_t numElements,
+    const std::set<std::string>& skip,
+    const ChannelFilter& filter = [](const std::string&, size_t) { return true; })
+{
+    std::vector<VtkArray> arrays;
+    collectArrays<float>(buffer, numElements, skip, filter, "float", "Float32", arrays);
+    collectArrays<double>(buffer, numElements, skip, filter, "double", "Float64", arrays);
+    collectArrays<int>(buffer, numElements, skip, filter, "int", "Int32", arrays);
+    collectArrays<unsigned int>(buffer, numElements, skip, filter, "unsigned_int", "UInt32", arrays);
+    collectArrays<unsigned char>(buffer, numElements, skip, filter, "unsigned_char", "UInt8", arrays);
+    return arrays;
+}
+
+void writeValues(std::ostream& out, const VtkArray& array)
+{
+    for (size_t i = 0; i < array.data.size(); i++)
+    {
+        if (array.integral)
+        {
+            out << static_cast<long>(array.data[i]);
+        }
+        else
+        {
+            out << array.data[i];
+        }
+        out << ((i + 1) % array.width == 0 ? "\n" : " ");
+    }
+}
+
+/// The geometry that is written: triangles or single vertex cells
+struct VtkGeometry
+{
+    size_t numPoints = 0;
+    floatArr points;
+    size_t numFaces = 0;
+    indexArray faces;
+    std::vector<VtkArray> pointData;
+    std::vector<VtkArray> cellData;
+
+    size_t numCells() const { return faces ? numFaces : numPoints; }
+    size_t cellSize() const { return faces ? 3 : 1; }
+    size_t cellIndex(size_t cell, size_t j) const { return faces ? faces[3 * cell + j] : cell; }
+};
+
+void writeLegacy(std::ofstream& out, const VtkGeometry& g)
+{
+    out << "# vtk DataFile Version 3.0\n"
+        << "lvr2\n"
+        << "ASCII\n"
+        << "DATASET POLYDATA\n";
+
+    out << "POINTS " << g.numPoints << " float\n";
+    for (size_t i = 0; i < g.numPoints; i++)
+    {
+        out << g.points[3 * i] << " " << g.points[3 * i + 1] << " " << g.points[3 * i + 2] << "\n";
+    }
+
+    out << (g.faces ? "POLYGONS " : "VERTICES ") << g.numCells() << " " << g.numCells() * (g.cellSize() + 1) << "\n";
+    for (size_t i = 0; i < g.numCells(); i++)
+    {
+        out << g.cellSize();
+        for (size_t j = 0; j < g.cellSize(); j++)
+        {
+            out << " " << g.cellIndex(i, j);
+        }
+        out << "\n";
+    }
+
+    auto writeFields = [&](const std::vector<VtkArray>& arrays, const std::string& section, size_t n)
+    {
+        if (arrays.empty())
+        {
+            return;
+        }
+        out << section << " " << n << "\n";
+        out << "FIELD FieldData " << arrays.size() << "\n";
+        for (const VtkArray& array : arrays)
+        {
+            out << array.name << " " << array.width << " " << n << " " << array.legacyType << "\n";
+            writeValues(out, array);
+        }
+    };
+    writeFields(g.pointData, "POINT_DATA", g.numPoints);
+    writeFields(g.cellData, "CELL_DATA", g.numCells());
+}
+
+void writeXml(std::ofstream& out, const VtkGeometry& g, bool unstructured)
+{
+    const std::string type = unstructured ? "UnstructuredGrid" : "PolyData";
+    const std::string cellTag = unstructured ? "Cells" : (g.faces ? "Polys" : "Verts");
+
+    out << "<?xml version=\"1.0\"?>\n"
+        << "<VTKFile type=\"" << type << "\" version=\"0.1\" byte_order=\"LittleEndian\">\n"
+        << "<" << type << ">\n";
+
+    out << "<Piece NumberOfPoints=\"" << g.numPoints << "\"";
+    if (unstructured)
+    {
+        out << " NumberOfCells=\"" << g.numCells() << "\"";
+    }
+    else
+    {
+        out << " NumberOfVerts=\"" << (g.faces ? 0 : g.numCells()) << "\""
+            << " NumberOfLines=\"0\" NumberOfStrips=\"0\""
+            << " NumberOfPolys=\"" << (g.faces ? g.numCells() : 0) << "\"";
+    }
+    out << ">\n";
+
+    auto writeArrays = [&](const std::vector<VtkArray>& arrays, const std::string& tag)
+    {
+        out << "<" << tag << ">\n";
+        for (const VtkArray& array : arrays)
+        {
+            out << "<DataArray type=\"" << array.xmlType << "\" Name=\"" << array.name
+                << "\" NumberOfComponents=\"" << array.width << "\" format=\"ascii\">\n";
+            writeValues(out, array);
+            out << "</DataArray>\n";
+        }
+        out << "</" << tag << ">\n";
+    };
+    writeArrays(g.pointData, "PointData");
+    writeArrays(g.cellData, "CellData");
+
+    out << "<Points>\n<DataArray type=\"Float32\" NumberOfComponents=\"3\" format=\"ascii\">\n";
+    for (size_t i = 0; i < g.numPoints; i++)
+    {
+        out << g.points[3 * i] << " " << g.points[3 * i + 1] << " " << g.points[3 * i + 2] << "\n";
+    }
+    out << "</DataArray>\n</Points>\n";
+
+    out << "<" << cellTag << ">\n";
+    out << "<DataArray type=\"Int64\" Name=\"connectivity\" format=\"ascii\">\n";
+    for (size_t i = 0; i < g.numCells(); i++)
+    {
+        for (size_t j = 0; j < g.cellSize(); j++)
+        {
+            out << g.cellIndex(i, j) << (j + 1 < g.cellSize() ? " " : "\n");
+        }
+    }
+    out << "</DataArray>\n";
+    out << "<DataArray type=\"Int64\" Name=\"offsets\" format=\"ascii\">\n";
+    for (size_t i = 0; i < g.numCells(); i++)
+    {
+        out << (i + 1) * g.cellSize() << "\n";
+    }
+    out << "</DataArray>\n";
+    if (unstructured)
+    {
+        // 5: VTK_TRIANGLE, 1: VTK_VERTEX
+        out << "<DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n";
+        for (size_t i = 0; i < g.numCells(); i++)
+        {
+            out << (g.faces ? 5 : 1) << "\n";
+        }
+        out << "</DataArray>\n";
+    }
+    out << "</" << cellTag << ">\n";
+
+    out << "</Piece>\n</" << type << ">\n</VTKFile>\n";
+}
+
+} // anonymous namespace
+
+ModelPtr VtkIO::read(std::string filename)
+{
+    lvr2::logout::get() << lvr2::warning << "[VtkIO] Reading VTK files is not supported: " << filename << lvr2::endl;
+    return ModelPtr(new Model);
+}
+
+void VtkIO::save(std::string filename)
+{
+    save(m_model, filename);
+}
+
+void VtkIO::save(ModelPtr model, std::string filename)
+{
+    const std::string extension = boost::filesystem::path(filename).extension().string();
+    if (extension != ".vtk" && extension != ".vtp" && extension != ".vtu")
+    {
+        lvr2::logout::get() << lvr2::error << "[VtkIO] Unsupported extension " << extension << lvr2::endl;
+        return;
+    }
+
+    VtkGeometry g;
+    if (model && model->m_mesh && model->m_mesh->numVertices() > 0)
+    {
+        MeshBufferPtr mesh = model->m_mesh;
+        g.numPoints = mesh->numVertices();
+        g.points = mesh->getVertices();
+        g.numFaces = mesh->numFaces();
+        g.faces = mesh->getFaceIndices();
+
+        // Face channels are named "face_...", e.g. "face_normals". Other
+        // channels are only face data if their size can't match the vertices.
+        const size_t numPoints = g.numPoints;
+        auto isFaceChannel = [numPoints](const std::string& key, size_t n)
+        {
+            return key.compare(0, 5, "face_") == 0 || n != numPoints;
+        };
+
+        std::set<std::string> skip = {"vertices", "face_indices"};
+        g.pointData = collectArrays(*mesh, g.numPoints, skip,
+            [&](const std::string& key, size_t n) { return !isFaceChannel(key, n); });
+        if (g.faces)
+        {
+            g.cellData = collectArrays(*mesh, g.numFaces, skip, isFaceChannel);
+        }
+
+        if (model->m_pointCloud)
+        {
+            lvr2::logout::get() << lvr2::warning << "[VtkIO] Model contains a mesh and a point cloud. "
+                << "Only the mesh is written." << lvr2::endl;
+        }
+    }
+    else if (model && model->m_pointCloud)
+    {
+        PointBufferPtr pc = model->m_pointCloud;
+        g.numPoints = pc->numPoints();
+        g.points = pc->getPointArray();
+        g.pointData = collectArrays(*pc, g.numPoints, {"points"});
+    }
+    else
+    {
+        lvr2::logout::get() << lvr2::error << "[VtkIO] Model is empty" << lvr2::endl;
+        return;
+    }
+
+    std::ofstream out(filename);
+    if (!out.good())
+    {
+        lvr2::logout::get() << lvr2::error << "[VtkIO] Could not open " << filename << lvr2::endl;
+        return;
+    }
+    out << std::setprecision(9);
+
+    if (extension == ".vtk")
+    {
+        writeLegacy(out, g);
+    }
+    else
+    {
+        writeXml(out, g, extension == ".vtu");
+    }
+}
+
+} // namespace lvr2
@@ -0,0 +1,150 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PolylineIO.cpp
+ */
+
+#include "lvr2/io/vector/PolylineIO.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <fstream>
+#include <iomanip>
+
+namespace lvr2
+{
+
+namespace
+{
+
+Vector3d globalPosition(const Vector3d& p, const boost::optional<GeoMetadata>& geo)
+{
+    if (!geo)
+    {
+        return p;
+    }
+    return p + Vector3d(geo->offset[0], geo->offset[1], geo->offset[2]);
+}
+
+bool openOutput(std::ofstream& out, const boost::filesystem::path& filename)
+{
+    out.open(filename.string());
+    if (!out.good())
+    {
+        lvr2::logout::get() << lvr2::error << "[PolylineIO] Could not open " << filename.string() << lvr2::endl;
+        return false;
+    }
+    out << std::setprecision(12);
+    return true;
+}
+
+} // anonymous namespace
+
+void saveDXF(
+    const std::vector<Polyline>& polylines,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo)
+{
+    std::ofstream out;
+    if (!openOutput(out, filename))
+    {
+        return;
+    }
+
+    // DXF consists of pairs of lines: group code and value
+    out << "0\nSECTION\n2\nENTITIES\n";
+    for (const Polyline& polyline : polylines)
+    {
+        if (polyline.points.size() < 2)
+        {
+            continue;
+        }
+
+        // Flag 8: 3D polyline, flag 1: closed
+        const int flags = 8 | (polyline.closed ? 1 : 0);
+        out << "0\nPOLYLINE\n8\n" << polyline.layer << "\n66\n1\n70\n" << flags << "\n";
+        for (const Vector3d& p : polyline.points)
+        {
+            Vector3d g = globalPosition(p, geo);
+            out << "0\nVERTEX\n8\n" << polyline.layer << "\n"
+                << "10\n" << g.x() << "\n20\n" << g.y() << "\n30\n" << g.z() << "\n"
+                << "70\n32\n";
+        }
+        out << "0\nSEQEND\n8\n" << polyline.layer << "\n";
+    }
+    out << "0\nENDSEC\n0\nEOF\n";
+}
+
+void saveGeoJSON(
+    const std::vector<Polyline>& polylines,
+    const boost::filesystem::path& filename,
+    const boost::optional<GeoMetadata>& geo)
+{
+    std::ofstream out;
+    if (!openOutput(out, filename))
+    {
+        return;
+    }
+
+    out << "{\n  \"type\": \"FeatureCollection\",\n";
+    if (geo && geo->epsg > 0)
+    {
+        out << "  \"crs\": {\"type\": \"name\", \"properties\": {\"name\": \"urn:ogc:def:crs:EPSG::"
+            << geo->epsg << "\"}},\n";
+    }
+    out << "  \"features\": [";
+
+    bool first = true;
+    for (const Polyline& polyline : polylines)
+    {
+        if (polyline.points.size() < 2)
+        {
+            continue;
+        }
+
+        // Polygon rings have to repeat their first position
+        const bool polygon = polyline.closed && polyline.points.size() > 2;
+        out << (first ? "\n" : ",\n");
+        first = false;
+
+        out << "    {\"type\": \"Feature\", \"properties\": {\"layer\": \"" << polyline.layer << "\"}, "
+            << "\"geometry\": {\"type\": \"" << (polygon ? "Polygon" : "LineString") << "\", \"coordinates\": "
+            << (polygon ? "[[" : "[");
+
+        size_t n = polyline.points.size() + (polygon ? 1 : 0);
+        for (size_t i = 0; i < n; i++)
+        {
+            Vector3d g = globalPosition(polyline.points[i % polyline.points.size()], geo);
+            out << (i ? ", " : "") << "[" << g.x() << ", " << g.y() << ", " << g.z() << "]";
+        }
+        out << (polygon ? "]]" : "]") << "}}";
+    }
+    out << "\n  ]\n}\n";
+}
+
+} // namespace lvr2
@@ -0,0 +1,96 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Benchmark.cpp
+ */
+
+#include "lvr2/reconstruction/Benchmark.hpp"
+
+#include <fstream>
+#include <iomanip>
+#include <sstream>
+
+namespace lvr2
+{
+
+std::string benchmarkTable(const std::vector<BenchmarkResult>& results)
+{
+    size_t nameWidth = 4;
+    for (const BenchmarkResult& r : results)
+    {
+        nameWidth = std::max(nameWidth, r.name.size());
+    }
+
+    std::stringstream ss;
+    ss << std::left << std::setw(nameWidth + 2) << "case"
+       << std::right << std::setw(10) << "points"
+       << std::setw(9) << "threads"
+       << std::setw(11) << "voxel"
+       << std::setw(11) << "min [s]"
+       << std::setw(11) << "mean [s]"
+       << std::setw(11) << "max [s]"
+       << std::setw(11) << "faces" << "\n";
+
+    ss << std::fixed;
+    for (const BenchmarkResult& r : results)
+    {
+        ss << std::left << std::setw(nameWidth + 2) << r.name
+           << std::right << std::setw(10) << r.numPoints
+           << std::setw(9) << r.threads
+           << std::setw(11) << std::setprecision(4) << r.voxelSize;
+        if (r.failure.empty())
+        {
+            ss << std::setprecision(3)
+               << std::setw(11) << r.minSeconds
+               << std::setw(11) << r.meanSeconds
+               << std::setw(11) << r.maxSeconds
+               << std::setw(11) << r.numFaces;
+        }
+        else
+        {
+            ss << "  failed: " << r.failure;
+        }
+        ss << "\n";
+    }
+    return ss.str();
+}
+
+void saveBenchmarkCsv(const std::vector<BenchmarkResult>& results, const boost::filesystem::path& filename)
+{
+    std::ofstream out(filename.string());
+    out << "case,points,threads,voxel_size,min_seconds,mean_seconds,max_seconds,faces,failure\n";
+    for (const BenchmarkResult& r : results)
+    {
+        out << r.name << "," << r.numPoints << "," << r.threads << "," << r.voxelSize << ","
+            << r.minSeconds << "," << r.meanSeconds << "," << r.maxSeconds << ","
+            << r.numFaces << ",\"" << r.failure << "\"\n";
+    }
+}
+
+} // namespace lvr2
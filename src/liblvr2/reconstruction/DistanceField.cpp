@@ -0,0 +1,156 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * DistanceField.cpp
+ */
+
+#include "lvr2/reconstruction/DistanceField.hpp"
+#include "lvr2/reconstruction/FastReconstructionTables.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+DistanceField::DistanceField(float voxelSize, std::unordered_map<Vector3i, CellDistances> cells)
+    : m_voxelSize(voxelSize)
+    , m_cells(std::move(cells))
+{
+}
+
+std::shared_ptr<DistanceField> DistanceField::fromPointBuffer(PointBufferPtr buffer, float voxelSize)
+{
+    FloatChannelOptional centers = buffer->getFloatChannel("points");
+    FloatChannelOptional values = buffer->getFloatChannel("tsdf_values");
+    if (!centers || !values || values->width() != 8 || values->numElements() != centers->numElements())
+    {
+        return nullptr;
+    }
+
+    std::unordered_map<Vector3i, CellDistances> cells;
+    cells.reserve(centers->numElements());
+    for (size_t i = 0; i < centers->numElements(); i++)
+    {
+        Vector3i index(
+            std::floor((*centers)[i][0] / voxelSize),
+            std::floor((*centers)[i][1] / voxelSize),
+            std::floor((*centers)[i][2] / voxelSize));
+        CellDistances distances;
+        for (int j = 0; j < 8; j++)
+        {
+            distances[j] = (*values)[i][j];
+        }
+        cells.emplace(index, distances);
+    }
+    return std::make_shared<DistanceField>(voxelSize, std::move(cells));
+}
+
+PointBufferPtr DistanceField::toPointBuffer() const
+{
+    const size_t n = m_cells.size();
+    floatArr centers(new float[3 * n]);
+    floatArr values(new float[8 * n]);
+    size_t i = 0;
+    for (const auto& cell : m_cells)
+    {
+        for (int a = 0; a < 3; a++)
+        {
+            centers[3 * i + a] = (cell.first[a] + 0.5f) * m_voxelSize;
+        }
+        std::copy(cell.second.begin(), cell.second.end(), values.get() + 8 * i);
+        i++;
+    }
+
+    PointBufferPtr buffer = std::make_shared<PointBuffer>(centers, n);
+    buffer->addFloatChannel(values, "tsdf_values", n, 8);
+    return buffer;
+}
+
+Vector3i DistanceField::cellIndex(const Vector3f& position) const
+{
+    return Vector3i(
+        std::floor(position.x() / m_voxelSize),
+        std::floor(position.y() / m_voxelSize),
+        std::floor(position.z() / m_voxelSize));
+}
+
+float DistanceField::query(const Vector3f& position) const
+{
+    const Vector3i index = cellIndex(position);
+    auto it = m_cells.find(index);
+    if (it == m_cells.end())
+    {
+        return std::numeric_limits<float>::quiet_NaN();
+    }
+
+    // Local coordinates within the cell
+    const Vector3f t = position / m_voxelSize - index.cast<float>();
+
+    float distance = 0;
+    for (int i = 0; i < 8; i++)
+    {
+        float weight = 1;
+        for (int a = 0; a < 3; a++)
+        {
+            weight *= box_creation_table[i][a] > 0 ? t[a] : 1 - t[a];
+        }
+        distance += weight * it->second[i];
+    }
+    // NaN corners propagate to the result
+    return distance;
+}
+
+bool DistanceField::contains(const Vector3f& position) const
+{
+    return std::isfinite(query(position));
+}
+
+std::pair<Vector3f, Vector3f> DistanceField::bounds() const
+{
+    if (m_cells.empty())
+    {
+        return std::make_pair(Vector3f::Zero(), Vector3f::Zero());
+    }
+
+    Vector3i min = m_cells.begin()->first;
+    Vector3i max = min;
+    for (const auto& cell : m_cells)
+    {
+        min = min.cwiseMin(cell.first);
+        max = max.cwiseMax(cell.first);
+    }
+    return std::make_pair(
+        min.cast<float>() * m_voxelSize,
+        (max + Vector3i::Ones()).cast<float>() * m_voxelSize);
+}
+
+} // namespace lvr2
@@ -0,0 +1,185 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * IndexCache.cpp
+ */
+
+#include "lvr2/reconstruction/IndexCache.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <cstring>
+#include <fstream>
+#include <iomanip>
+#include <sstream>
+
+namespace lvr2
+{
+
+namespace
+{
+
+const char GRID_CACHE_MAGIC[8] = {'L', 'V', 'R', 'G', 'R', 'I', 'D', '1'};
+
+} // anonymous namespace
+
+uint64_t hashBytes(const void* data, size_t bytes, uint64_t seed)
+{
+    const unsigned char* p = static_cast<const unsigned char*>(data);
+    uint64_t hash = seed;
+    for (size_t i = 0; i < bytes; i++)
+    {
+        hash ^= p[i];
+        hash *= 1099511628211ULL;
+    }
+    return hash;
+}
+
+uint64_t hashPointBuffer(PointBufferPtr buffer, bool includeNormals)
+{
+    uint64_t n = buffer->numPoints();
+    uint64_t hash = hashValue(n, 14695981039346656037ULL);
+    hash = hashBytes(buffer->getPointArray().get(), 3 * n * sizeof(float), hash);
+
+    if (includeNormals && buffer->hasNormals())
+    {
+        hash = hashBytes(buffer->getNormalArray().get(), 3 * n * sizeof(float), hash);
+    }
+    return hash;
+}
+
+boost::filesystem::path indexCacheFile(
+    const boost::filesystem::path& directory,
+    const std::string& prefix,
+    uint64_t key)
+{
+    std::stringstream ss;
+    ss << prefix << "_" << std::hex << std::setw(16) << std::setfill('0') << key << ".cache";
+    return directory / ss.str();
+}
+
+boost::filesystem::path temporaryCacheFile(const boost::filesystem::path& file)
+{
+    boost::filesystem::path tmp = file;
+    tmp += "." + boost::filesystem::unique_path().string() + ".tmp";
+    return tmp;
+}
+
+bool commitCacheFile(const boost::filesystem::path& tmp, const boost::filesystem::path& file)
+{
+    boost::system::error_code ec;
+    boost::filesystem::rename(tmp, file, ec);
+    if (ec)
+    {
+        lvr2::logout::get() << lvr2::warning << "[IndexCache] Unable to write cache " << file << ": " << ec.message() << lvr2::endl;
+        boost::filesystem::remove(tmp, ec);
+        return false;
+    }
+    return true;
+}
+
+void saveGridCache(PointBufferPtr grid, const boost::filesystem::path& file)
+{
+    if (file.has_parent_path())
+    {
+        boost::filesystem::create_directories(file.parent_path());
+    }
+
+    // Write to a temporary file first, so an interrupted run never leaves
+    // a partially written cache behind
+    const boost::filesystem::path tmp = temporaryCacheFile(file);
+    {
+        std::ofstream out(tmp.string(), std::ios::out | std::ios::binary);
+        if (!out.good())
+        {
+            lvr2::logout::get() << lvr2::warning << "[IndexCache] Unable to write grid cache " << file << lvr2::endl;
+            return;
+        }
+
+        uint64_t n = grid->numPoints();
+        out.write(GRID_CACHE_MAGIC, sizeof(GRID_CACHE_MAGIC));
+        out.write(reinterpret_cast<const char*>(&n), sizeof(n));
+        out.write(reinterpret_cast<const char*>(grid->getPointArray().get()), 3 * n * sizeof(float));
+        out.write(reinterpret_cast<const char*>(grid->getFloatChannel("tsdf_values")->dataPtr().get()), 8 * n * sizeof(float));
+        out.close();
+        if (out.fail())
+        {
+            lvr2::logout::get() << lvr2::warning << "[IndexCache] Unable to write grid cache " << file << lvr2::endl;
+            boost::system::error_code ec;
+            boost::filesystem::remove(tmp, ec);
+            return;
+        }
+    }
+    commitCacheFile(tmp, file);
+}
+
+PointBufferPtr loadGridCache(const boost::filesystem::path& file)
+{
+    if (!boost::filesystem::exists(file))
+    {
+        return nullptr;
+    }
+
+    std::ifstream in(file.string(), std::ios::in | std::ios::binary);
+    char magic[sizeof(GRID_CACHE_MAGIC)];
+    uint64_t n = 0;
+    in.read(magic, sizeof(magic));
+    in.read(reinterpret_cast<char*>(&n), sizeof(n));
+    if (!in.good() || std::memcmp(magic, GRID_CACHE_MAGIC, sizeof(magic)) != 0)
+    {
+        lvr2::logout::get() << lvr2::warning << "[IndexCache] Ignoring invalid grid cache " << file << lvr2::endl;
+        return nullptr;
+    }
+
+    // Check the point count against the file size before allocating anything
+    boost::system::error_code ec;
+    const uint64_t fileSize = boost::filesystem::file_size(file, ec);
+    const uint64_t header = sizeof(GRID_CACHE_MAGIC) + sizeof(n);
+    const uint64_t pointSize = 11 * sizeof(float);
+    if (ec || fileSize < header || (fileSize - header) % pointSize != 0 || n != (fileSize - header) / pointSize)
+    {
+        lvr2::logout::get() << lvr2::warning << "[IndexCache] Ignoring corrupt grid cache " << file << lvr2::endl;
+        return nullptr;
+    }
+
+    floatArr centers(new float[3 * n]);
+    floatArr distances(new float[8 * n]);
+    in.read(reinterpret_cast<char*>(centers.get()), 3 * n * sizeof(float));
+    in.read(reinterpret_cast<char*>(distances.get()), 8 * n * sizeof(float));
+    if (!in.good())
+    {
+        lvr2::logout::get() << lvr2::warning << "[IndexCache] Ignoring truncated grid cache " << file << lvr2::endl;
+        return nullptr;
+    }
+
+    auto grid = std::make_shared<PointBuffer>(centers, n);
+    grid->addFloatChannel(distances, "tsdf_values", n, 8);
+    return grid;
+}
+
+} // namespace lvr2
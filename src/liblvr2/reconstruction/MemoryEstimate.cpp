@@ -0,0 +1,116 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MemoryEstimate.cpp
+ */
+
+#include "lvr2/reconstruction/MemoryEstimate.hpp"
+
+#include <cmath>
+#include <cstdint>
+#include <unordered_set>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Index arrays and nodes of the search tree per point
+constexpr size_t TreeBytesPerPoint = 32;
+
+/// Box with vertex, intersection and neighbor arrays plus the hash map entry
+constexpr size_t BytesPerCell = 360;
+
+/// Query point plus the hash map entry. Neighboring cells share most corners,
+/// so there is roughly one query point per cell.
+constexpr size_t BytesPerQueryPoint = 64;
+
+/// Vertices, half edges and faces of the mesh per occupied cell (about one
+/// vertex and two faces per cell) including normals and other properties
+constexpr size_t MeshBytesPerCell = 300;
+
+/// Extruded grids contain the neighbors of all occupied cells, which
+/// roughly triples the number of cells for surface-like data
+constexpr size_t ExtrudeFactor = 3;
+
+/// Size of the data of a channel in bytes
+struct ChannelBytes : public boost::static_visitor<size_t>
+{
+    template<typename T>
+    size_t operator()(const Channel<T>& channel) const
+    {
+        return channel.numElements() * channel.width() * sizeof(T);
+    }
+};
+
+} // anonymous namespace
+
+MemoryEstimate estimateMemory(PointBufferPtr buffer, const ReconstructionOptions& options)
+{
+    MemoryEstimate estimate;
+    if(!buffer || buffer->numPoints() == 0)
+    {
+        return estimate;
+    }
+
+    const size_t n = buffer->numPoints();
+
+    for(const auto& elem : *buffer)
+    {
+        estimate.points += boost::apply_visitor(ChannelBytes(), elem.second);
+    }
+    if(!buffer->hasNormals())
+    {
+        estimate.points += n * 3 * sizeof(float);
+    }
+
+    estimate.searchTree = n * TreeBytesPerPoint;
+
+    // Count the voxels that contain points
+    FloatChannel points = *buffer->getFloatChannel("points");
+    const double voxelSize = options.voxelSize;
+    std::unordered_set<uint64_t> cells;
+    for(size_t i = 0; i < n; i++)
+    {
+        const uint64_t x = static_cast<int64_t>(std::floor(points[i][0] / voxelSize)) & 0x1fffff;
+        const uint64_t y = static_cast<int64_t>(std::floor(points[i][1] / voxelSize)) & 0x1fffff;
+        const uint64_t z = static_cast<int64_t>(std::floor(points[i][2] / voxelSize)) & 0x1fffff;
+        cells.insert(x | (y << 21) | (z << 42));
+    }
+    estimate.occupiedCells = cells.size();
+
+    const size_t gridCells = estimate.occupiedCells * (options.extrude ? ExtrudeFactor : 1);
+    estimate.grid = gridCells * (BytesPerCell + BytesPerQueryPoint);
+    estimate.mesh = estimate.occupiedCells * MeshBytesPerCell;
+
+    return estimate;
+}
+
+} // namespace lvr2
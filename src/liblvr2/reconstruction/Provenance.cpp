@@ -0,0 +1,201 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Provenance.cpp
+ */
+
+#include "lvr2/reconstruction/Provenance.hpp"
+#include "lvr2/reconstruction/IndexCache.hpp"
+#include "lvr2/util/JsonUtil.hpp"
+
+#include <boost/filesystem/fstream.hpp>
+
+#include <algorithm>
+#include <ctime>
+#include <iomanip>
+#include <sstream>
+#include <vector>
+
+#ifndef LVR2_VERSION_STRING
+#define LVR2_VERSION_STRING "unknown"
+#endif
+
+namespace lvr2
+{
+
+namespace
+{
+
+const char* faceRegionName(FaceRegionType type)
+{
+    switch (type)
+    {
+        case FaceRegionType::Chunk:   return "chunk";
+        case FaceRegionType::Cluster: return "cluster";
+        default:                      return "none";
+    }
+}
+
+const char* normalWeightingName(VertexNormalWeighting weighting)
+{
+    switch (weighting)
+    {
+        case VertexNormalWeighting::Angle: return "angle";
+        case VertexNormalWeighting::Area:  return "area";
+        default:                           return "uniform";
+    }
+}
+
+} // anonymous namespace
+
+std::string lvr2Version()
+{
+    return LVR2_VERSION_STRING;
+}
+
+std::string hashFile(const boost::filesystem::path& file)
+{
+    namespace fs = boost::filesystem;
+
+    // Directories (e.g. scan projects) are hashed file by file in a fixed order
+    std::vector<fs::path> files;
+    boost::system::error_code ec;
+    if (fs::is_directory(file, ec))
+    {
+        for (fs::recursive_directory_iterator it(file, ec), end; !ec && it != end; it.increment(ec))
+        {
+            if (fs::is_regular_file(it->path()))
+            {
+                files.push_back(it->path());
+            }
+        }
+        std::sort(files.begin(), files.end());
+    }
+    else if (fs::is_regular_file(file, ec))
+    {
+        files.push_back(file);
+    }
+    if (files.empty() || ec)
+    {
+        return "";
+    }
+
+    uint64_t hash = 14695981039346656037ULL;
+    std::vector<char> block(1 << 20);
+    for (const fs::path& f : files)
+    {
+        fs::ifstream in(f, std::ios::binary);
+        if (!in)
+        {
+            return "";
+        }
+        if (f != file)
+        {
+            const std::string name = fs::relative(f, file).generic_string();
+            hash = hashBytes(name.data(), name.size(), hash);
+        }
+        while (in)
+        {
+            in.read(block.data(), block.size());
+            hash = hashBytes(block.data(), in.gcount(), hash);
+        }
+    }
+
+    std::stringstream ss;
+    ss << std::hex << std::setw(16) << std::setfill('0') << hash;
+    return ss.str();
+}
+
+std::string reconstructionOptionsToJson(const ReconstructionOptions& options)
+{
+    std::stringstream ss;
+    ss << std::boolalpha << std::setprecision(9);
+    ss << "{"
+       << "\"decomposition\": " << jsonString(options.decomposition)
+       << ", \"voxelSize\": " << options.voxelSize
+       << ", \"extrude\": " << options.extrude
+       << ", \"refinementLevels\": " << options.refinementLevels
+       << ", \"truncationDistance\": " << options.truncationDistance
+       << ", \"maxDistance\": " << options.maxDistance
+       << ", \"searchTree\": " << jsonString(options.searchTree)
+       << ", \"kn\": " << options.kn
+       << ", \"ki\": " << options.ki
+       << ", \"normalWeighting\": " << jsonString(normalWeightingName(options.normalWeighting))
+       << ", \"kd\": " << options.kd
+       << ", \"normalMethod\": " << static_cast<int>(options.normalMethod)
+       << ", \"recalcNormals\": " << options.recalcNormals
+       << ", \"confidenceChannel\": " << jsonString(options.confidenceChannel)
+       << ", \"flipPoint\": [";
+    for (size_t i = 0; i < options.flipPoint.size(); i++)
+    {
+        ss << (i ? ", " : "") << options.flipPoint[i];
+    }
+    ss << "]"
+       << ", \"allowPartial\": " << options.allowPartial
+       << ", \"cacheDirectory\": " << jsonString(options.cacheDirectory)
+       << ", \"trimMinPoints\": " << options.trimMinPoints
+       << ", \"trimRadius\": " << options.trimRadius
+       << ", \"vertexQuality\": " << options.vertexQuality
+       << ", \"memoryBudget\": " << options.memoryBudget
+       << ", \"keepDistanceField\": " << options.keepDistanceField
+       << ", \"faceRegions\": " << jsonString(faceRegionName(options.faceRegions))
+       << ", \"regionChunkSize\": " << options.regionChunkSize
+       << ", \"regionMinSinAngle\": " << options.regionMinSinAngle
+       << "}";
+    return ss.str();
+}
+
+std::map<std::string, std::string> reconstructionProvenance(
+    const ReconstructionOptions& options,
+    const boost::filesystem::path& inputFile)
+{
+    std::map<std::string, std::string> metadata;
+    metadata["generator"] = "lvr2 " + lvr2Version();
+    metadata["lvr2_version"] = lvr2Version();
+
+    std::time_t now = std::time(nullptr);
+    std::stringstream timestamp;
+    timestamp << std::put_time(std::gmtime(&now), "%Y-%m-%dT%H:%M:%SZ");
+    metadata["timestamp"] = timestamp.str();
+
+    metadata["options"] = reconstructionOptionsToJson(options);
+
+    if (!inputFile.empty())
+    {
+        metadata["input_file"] = inputFile.string();
+        const std::string hash = hashFile(inputFile);
+        if (!hash.empty())
+        {
+            metadata["input_hash"] = "fnv1a64:" + hash;
+        }
+    }
+    return metadata;
+}
+
+} // namespace lvr2
@@ -0,0 +1,104 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/**
+ * RunReport.cpp
+ */
+
+#include "lvr2/reconstruction/RunReport.hpp"
+#include "lvr2/util/JsonUtil.hpp"
+
+#include <fstream>
+#include <iomanip>
+#include <sstream>
+#include <stdexcept>
+
+#if defined(__linux__) || defined(__APPLE__)
+#include <sys/resource.h>
+#endif
+
+namespace lvr2
+{
+
+size_t peakMemoryUsage()
+{
+#if defined(__linux__) || defined(__APPLE__)
+    struct rusage usage;
+    if(getrusage(RUSAGE_SELF, &usage) != 0)
+    {
+        return 0;
+    }
+#if defined(__APPLE__)
+    return static_cast<size_t>(usage.ru_maxrss);
+#else
+    // ru_maxrss is given in kilobytes on Linux
+    return static_cast<size_t>(usage.ru_maxrss) * 1024;
+#endif
+#else
+    return 0;
+#endif
+}
+
+std::string RunReport::toJson() const
+{
+    std::stringstream ss;
+    ss << std::setprecision(9);
+    ss << "{\n";
+    ss << "  \"total_seconds\": " << totalSeconds << ",\n";
+    ss << "  \"peak_memory\": " << peakMemory << ",\n";
+    ss << "  \"input_points\": " << inputPoints << ",\n";
+    ss << "  \"output_vertices\": " << outputVertices << ",\n";
+    ss << "  \"output_faces\": " << outputFaces << ",\n";
+    ss << "  \"stages\": [";
+    for(size_t i = 0; i < stages.size(); i++)
+    {
+        const PipelineStageInfo& s = stages[i];
+        ss << (i == 0 ? "\n" : ",\n");
+        ss << "    {"
+           << "\"name\": \"" << escapeJson(s.name) << "\", "
+           << "\"seconds\": " << s.seconds << ", "
+           << "\"peak_memory\": " << s.peakMemory << ", "
+           << "\"elements_in\": " << s.elementsIn << ", "
+           << "\"elements_out\": " << s.elementsOut
+           << "}";
+    }
+    ss << (stages.empty() ? "]\n" : "\n  ]\n");
+    ss << "}\n";
+    return ss.str();
+}
+
+void RunReport::saveJson(const boost::filesystem::path& path) const
+{
+    std::ofstream out(path.string());
+    if(!out.good())
+    {
+        throw std::runtime_error("[RunReport] Could not open " + path.string() + " for writing");
+    }
+    out << toJson();
+}
+
+} // namespace lvr2
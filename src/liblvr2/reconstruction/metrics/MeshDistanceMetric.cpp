@@ -0,0 +1,105 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshDistanceMetric.cpp
+ */
+
+#include "lvr2/reconstruction/metrics/MeshDistanceMetric.hpp"
+#include "lvr2/algorithm/MeshSampling.hpp"
+#include "lvr2/algorithm/pmp/TriangleKdTree.h"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/PMPMesh.hpp"
+
+#include <algorithm>
+#include <cmath>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Distances of the vertices and surface samples of `from` to the surface of `to`
+OneSidedMeshDistance oneSidedDistance(MeshBufferPtr from, MeshBufferPtr to, float density, unsigned int seed)
+{
+    OneSidedMeshDistance result;
+
+    PMPMesh<BaseVector<float>> target(to);
+    pmp::TriangleKdTree tree(target.getSurfaceMesh());
+
+    PointBufferPtr samples = sampleMesh(from, density, seed);
+    floatArr sampleArr = samples->getPointArray();
+    floatArr vertexArr = from->getVertices();
+    const size_t numSamples = samples->numPoints();
+    const size_t numVertices = from->numVertices();
+    const size_t n = numSamples + numVertices;
+    if (n == 0)
+    {
+        return result;
+    }
+
+    double sum = 0.0;
+    double sum2 = 0.0;
+    double max = 0.0;
+
+    #pragma omp parallel for reduction(+:sum,sum2) reduction(max:max)
+    for (size_t i = 0; i < n; i++)
+    {
+        const float* p = i < numSamples ? &sampleArr[3 * i] : &vertexArr[3 * (i - numSamples)];
+        double d = tree.nearest(pmp::Point(p[0], p[1], p[2])).dist;
+        sum += d;
+        sum2 += d * d;
+        max = std::max(max, d);
+    }
+
+    result.numSamples = n;
+    result.mean = sum / n;
+    result.rms = std::sqrt(sum2 / n);
+    result.max = max;
+    return result;
+}
+
+} // anonymous namespace
+
+MeshDistance meshDistance(MeshBufferPtr a, MeshBufferPtr b, float density, unsigned int seed)
+{
+    MeshDistance result;
+    result.aToB = oneSidedDistance(a, b, density, seed);
+    result.bToA = oneSidedDistance(b, a, density, seed + 1);
+    result.hausdorff = std::max(result.aToB.max, result.bToA.max);
+
+    const size_t n = result.aToB.numSamples + result.bToA.numSamples;
+    if (n > 0)
+    {
+        result.mean = (result.aToB.mean * result.aToB.numSamples + result.bToA.mean * result.bToA.numSamples) / n;
+    }
+    return result;
+}
+
+} // namespace lvr2
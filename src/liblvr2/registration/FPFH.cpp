@@ -4,6 +4,7 @@
 #include <lvr2/util/Timestamp.hpp>
 #include <lvr2/geometry/BaseVector.hpp>
 
+#include <algorithm>
 #include <iostream>
 
 namespace lvr2
@@ -44,10 +45,71 @@ static Eigen::Vector4f ComputePairFeatures(const Eigen::Vector3f &p1,
     return result;
 }
 
+/**
+ * @brief Computes the SPFH histogram (33 bins) of a single point from its
+ *        k nearest neighbors. The histogram is zero if the point has no neighbors.
+ */
+static Eigen::VectorXf computeSPFH(const FloatChannel& points, const FloatChannel& normals,
+                                   SearchTreePtr<BaseVector<float>> tree, size_t k, size_t i)
+{
+    Eigen::VectorXf feature = Eigen::VectorXf::Zero(33);
+
+    BaseVector<float> point(points[i][0], points[i][1], points[i][2]);
+    BaseVector<float> normal(normals[i][0], normals[i][1], normals[i][2]);
+
+    std::vector<size_t> indices;
+    std::vector<float> distances;
+    if (tree->kSearch(point, k, indices, distances) > 1)
+    {
+        Eigen::Vector3f query_point(point.x, point.y, point.z);
+        Eigen::Vector3f query_normal(normal.x, normal.y, normal.z);
+
+        // Only compute SPFH feature when a point has neighbors
+        double hist_incr = 100.0 / (float)(indices.size() - 1);
+        for (size_t k = 1; k < indices.size(); k++)
+        {
+            // Skip the point itself, compute histogram
+            size_t current = indices[k];
+            Eigen::Vector3f current_point(
+                points[current][0],
+                points[current][1],
+                points[current][2]);
+
+            Eigen::Vector3f current_normal(
+                normals[current][0],
+                normals[current][1],
+                normals[current][2]);
+
+            auto pf = ComputePairFeatures(query_point, query_normal,
+                                          current_point,
+                                          current_normal);
+            int h_index = (int)(floor(11 * (pf(0) + M_PI) / (2.0 * M_PI)));
+            if (h_index < 0)
+                h_index = 0;
+            if (h_index >= 11)
+                h_index = 10;
+            feature(h_index) += hist_incr;
+            h_index = (int)(floor(11 * (pf(1) + 1.0) * 0.5));
+            if (h_index < 0)
+                h_index = 0;
+            if (h_index >= 11)
+                h_index = 10;
+            feature(h_index + 11) += hist_incr;
+            h_index = (int)(floor(11 * (pf(2) + 1.0) * 0.5));
+            if (h_index < 0)
+                h_index = 0;
+            if (h_index >= 11)
+                h_index = 10;
+            feature(h_index + 22) += hist_incr;
+        }
+    }
+    return feature;
+}
+
 FPFHFeaturePtr computeInitialFeatures(const PointBufferPtr pointBuffer, SearchTreePtr<BaseVector<float>> tree, size_t k)
 {
     auto feature = Eigen::MatrixXf(33, pointBuffer->numPoints());
-
+    feature.setZero();
 
     auto points_opt = pointBuffer->getChannel<float>("points");
     auto normals_opt = pointBuffer->getChannel<float>("normals");
@@ -59,55 +121,7 @@ FPFHFeaturePtr computeInitialFeatures(const PointBufferPtr pointBuffer, SearchTr
 #pragma omp parallel for schedule(static)
         for (int i = 0; i < pointBuffer->numPoints(); i++)
         {
-            BaseVector<float> point(points[i][0], points[i][1], points[i][2]);
-            BaseVector<float> normal(normals[i][0], normals[i][1], normals[i][2]);
-
-            std::vector<size_t> indices;
-            std::vector<float> distances;
-            if (tree->kSearch(point, k, indices, distances) > 1)
-            {
-                Eigen::Vector3f query_point(point.x, point.y, point.z);
-                Eigen::Vector3f query_normal(normal.x, normal.y, normal.z);
-
-                // Only compute SPFH feature when a point has neighbors
-                double hist_incr = 100.0 / (float)(indices.size() - 1);
-                for (size_t k = 1; k < indices.size(); k++)
-                {
-                    // Skip the point itself, compute histogram
-                    size_t current = indices[k];
-                    Eigen::Vector3f current_point(
-                        points[current][0], 
-                        points[current][1], 
-                        points[current][2]);
-
-                    Eigen::Vector3f current_normal(
-                        normals[current][0], 
-                        normals[current][1], 
-                        normals[current][2]);
-
-                    auto pf = ComputePairFeatures(query_point, query_normal,
-                                                  current_point,
-                                                  current_normal);
-                    int h_index = (int)(floor(11 * (pf(0) + M_PI) / (2.0 * M_PI)));
-                    if (h_index < 0)
-                        h_index = 0;
-                    if (h_index >= 11)
-                        h_index = 10;
-                    feature(h_index, i) += hist_incr;
-                    h_index = (int)(floor(11 * (pf(1) + 1.0) * 0.5));
-                    if (h_index < 0)
-                        h_index = 0;
-                    if (h_index >= 11)
-                        h_index = 10;
-                    feature(h_index + 11, i) += hist_incr;
-                    h_index = (int)(floor(11 * (pf(2) + 1.0) * 0.5));
-                    if (h_index < 0)
-                        h_index = 0;
-                    if (h_index >= 11)
-                        h_index = 10;
-                    feature(h_index + 22, i) += hist_incr;
-                }
-            }
+            feature.col(i) = computeSPFH(points, normals, tree, k, i);
         }
     }
     return FPFHFeaturePtr(new Eigen::MatrixXf(feature));
@@ -177,4 +191,78 @@ FPFHFeaturePtr computeFPFHFeatures(const PointBufferPtr pointCloud, size_t k)
 
 }
 
+FPFHFeaturePtr computeFPFHFeatures(const PointBufferPtr pointCloud, const std::vector<size_t>& keypoints, size_t k)
+{
+    FPFHFeaturePtr feature(new Eigen::MatrixXf(33, keypoints.size()));
+    feature->setZero();
+
+    auto points_opt = pointCloud->getChannel<float>("points");
+    auto normals_opt = pointCloud->getChannel<float>("normals");
+    if (!points_opt || !normals_opt)
+    {
+        std::cout << timestamp << "FPFH Failed because input point cloud has no normals" << std::endl;
+        return feature;
+    }
+    auto points = *points_opt;
+    auto normals = *normals_opt;
+
+    SearchTreePtr<BaseVector<float>> tree(new SearchTreeFlann<BaseVector<float>>(pointCloud));
+
+    // The SPFH histograms are only required for the keypoints and their neighbors
+    std::vector<std::vector<size_t>> neighbors(keypoints.size());
+    std::vector<std::vector<float>> distances(keypoints.size());
+    std::vector<size_t> required;
+    for (size_t i = 0; i < keypoints.size(); i++)
+    {
+        BaseVector<float> point(points[keypoints[i]][0], points[keypoints[i]][1], points[keypoints[i]][2]);
+        tree->kSearch(point, k, neighbors[i], distances[i]);
+        required.push_back(keypoints[i]);
+        required.insert(required.end(), neighbors[i].begin(), neighbors[i].end());
+    }
+    std::sort(required.begin(), required.end());
+    required.erase(std::unique(required.begin(), required.end()), required.end());
+
+    Eigen::MatrixXf spfh(33, required.size());
+#pragma omp parallel for schedule(static)
+    for (size_t j = 0; j < required.size(); j++)
+    {
+        spfh.col(j) = computeSPFH(points, normals, tree, k, required[j]);
+    }
+    auto column = [&](size_t index)
+    {
+        return std::lower_bound(required.begin(), required.end(), index) - required.begin();
+    };
+
+    for (size_t i = 0; i < keypoints.size(); i++)
+    {
+        if (neighbors[i].size() <= 1)
+        {
+            continue;
+        }
+        double sum[3] = {0.0, 0.0, 0.0};
+        for (size_t n = 1; n < neighbors[i].size(); n++)
+        {
+            // skip the point itself
+            double dist = distances[i][n];
+            if (dist == 0.0)
+                continue;
+            for (int j = 0; j < 33; j++)
+            {
+                double val = spfh(j, column(neighbors[i][n])) / dist;
+                sum[j / 11] += val;
+                (*feature)(j, i) += val;
+            }
+        }
+        for (int j = 0; j < 3; j++)
+            if (sum[j] != 0.0)
+                sum[j] = 100.0 / sum[j];
+        for (int j = 0; j < 33; j++)
+        {
+            (*feature)(j, i) *= sum[j / 11];
+            (*feature)(j, i) += spfh(j, column(keypoints[i]));
+        }
+    }
+    return feature;
+}
+
 } // namespace lvr2
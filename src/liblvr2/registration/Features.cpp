@@ -0,0 +1,256 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Features.cpp
+ */
+
+#include "lvr2/registration/Features.hpp"
+#include "lvr2/registration/IndexedPointTree.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+constexpr int SHOTAzimuthBins = 8;
+constexpr int SHOTCosineBins = 11;
+constexpr int SHOTSize = SHOTAzimuthBins * 2 * 2 * SHOTCosineBins;
+
+} // anonymous namespace
+
+SHOTFeaturePtr computeSHOTFeatures(const PointBufferPtr pointCloud, const std::vector<size_t>& keypoints, float radius)
+{
+    SHOTFeaturePtr feature(new SHOTFeature(SHOTSize, keypoints.size()));
+    feature->setZero();
+    if (!pointCloud->hasNormals())
+    {
+        lvr2::logout::get() << lvr2::error << "[Features] SHOT requires normals" << lvr2::endl;
+        return feature;
+    }
+
+    const size_t n = pointCloud->numPoints();
+    floatArr points = pointCloud->getPointArray();
+    floatArr normals = pointCloud->getNormalArray();
+    auto tree = buildIndexedPointTree(pointCloud);
+
+    #pragma omp parallel for schedule(dynamic, 16)
+    for (size_t k = 0; k < keypoints.size(); k++)
+    {
+        const size_t i = keypoints[k];
+        const Vector3d p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+
+        std::vector<IndexedPoint3f*> neighbors;
+        std::vector<float> distances;
+        tree->knnSearch(p, n, neighbors, distances, radius);
+        if (neighbors.size() < 5)
+        {
+            continue;
+        }
+
+        // Local reference frame from the distance weighted covariance
+        Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+        double weights = 0;
+        for (size_t j = 0; j < neighbors.size(); j++)
+        {
+            const double w = radius - distances[j];
+            const Vector3d d = neighbors[j]->point.cast<double>() - p;
+            covariance += w * d * d.transpose();
+            weights += w;
+        }
+        if (weights <= 0)
+        {
+            continue;
+        }
+        covariance /= weights;
+        Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> solver(covariance);
+        Vector3d x = solver.eigenvectors().col(2);
+        Vector3d z = solver.eigenvectors().col(0);
+
+        // Disambiguate the signs by the majority of the neighbors
+        int xVotes = 0;
+        int zVotes = 0;
+        for (const IndexedPoint3f* q : neighbors)
+        {
+            const Vector3d d = q->point.cast<double>() - p;
+            xVotes += d.dot(x) >= 0 ? 1 : -1;
+            zVotes += d.dot(z) >= 0 ? 1 : -1;
+        }
+        if (xVotes < 0)
+        {
+            x = -x;
+        }
+        if (zVotes < 0)
+        {
+            z = -z;
+        }
+        const Vector3d y = z.cross(x);
+
+        Eigen::VectorXf histogram = Eigen::VectorXf::Zero(SHOTSize);
+        for (const IndexedPoint3f* q : neighbors)
+        {
+            if (q->index == i)
+            {
+                continue;
+            }
+            const Vector3d d = q->point.cast<double>() - p;
+            const Vector3d local(d.dot(x), d.dot(y), d.dot(z));
+
+            const int radial = local.norm() < radius / 2 ? 0 : 1;
+            const int elevation = local.z() >= 0 ? 1 : 0;
+            const double azimuth = std::atan2(local.y(), local.x()) + M_PI;
+            int azimuthBin = static_cast<int>(azimuth / (2 * M_PI) * SHOTAzimuthBins);
+            azimuthBin = std::min(std::max(azimuthBin, 0), SHOTAzimuthBins - 1);
+            const int volume = (azimuthBin * 2 + elevation) * 2 + radial;
+
+            const size_t qi = q->index;
+            const Vector3d normal(normals[3 * qi], normals[3 * qi + 1], normals[3 * qi + 2]);
+            const double cosine = std::min(std::max(normal.dot(z), -1.0), 1.0);
+
+            // Linear interpolation between the two closest cosine bins
+            const double pos = (cosine + 1) / 2 * SHOTCosineBins - 0.5;
+            const int lower = static_cast<int>(std::floor(pos));
+            const double t = pos - lower;
+            const int base = volume * SHOTCosineBins;
+            histogram[base + std::max(lower, 0)] += 1 - t;
+            histogram[base + std::min(lower + 1, SHOTCosineBins - 1)] += t;
+        }
+
+        const float norm = histogram.norm();
+        if (norm > 0)
+        {
+            feature->col(k) = histogram / norm;
+        }
+    }
+
+    return feature;
+}
+
+std::vector<FeatureCorrespondence> matchFeatures(
+    const Eigen::MatrixXf& source,
+    const Eigen::MatrixXf& target,
+    const FeatureMatchingOptions& options)
+{
+    if (source.rows() != target.rows())
+    {
+        throw std::invalid_argument("[Features] Descriptors have different dimensions");
+    }
+
+    const float inf = std::numeric_limits<float>::infinity();
+    const Eigen::Index numSource = source.cols();
+    const Eigen::Index numTarget = target.cols();
+
+    // Nearest and second nearest target of each source descriptor
+    std::vector<Eigen::Index> nearest(numSource, -1);
+    std::vector<float> best(numSource, inf);
+    std::vector<float> second(numSource, inf);
+
+    // Nearest source of each target descriptor for the mutual check
+    std::vector<Eigen::Index> nearestSource(numTarget, -1);
+
+    #pragma omp parallel for schedule(dynamic, 64)
+    for (Eigen::Index s = 0; s < numSource; s++)
+    {
+        for (Eigen::Index t = 0; t < numTarget; t++)
+        {
+            const float d = (source.col(s) - target.col(t)).squaredNorm();
+            if (d < best[s])
+            {
+                second[s] = best[s];
+                best[s] = d;
+                nearest[s] = t;
+            }
+            else if (d < second[s])
+            {
+                second[s] = d;
+            }
+        }
+    }
+
+    if (options.mutual)
+    {
+        #pragma omp parallel for schedule(dynamic, 64)
+        for (Eigen::Index t = 0; t < numTarget; t++)
+        {
+            float bestDistance = inf;
+            for (Eigen::Index s = 0; s < numSource; s++)
+            {
+                const float d = (source.col(s) - target.col(t)).squaredNorm();
+                if (d < bestDistance)
+                {
+                    bestDistance = d;
+                    nearestSource[t] = s;
+                }
+            }
+        }
+    }
+
+    std::vector<FeatureCorrespondence> matches;
+    for (Eigen::Index s = 0; s < numSource; s++)
+    {
+        if (nearest[s] < 0)
+        {
+            continue;
+        }
+        const float distance = std::sqrt(best[s]);
+        if (options.maxDistance > 0 && distance > options.maxDistance)
+        {
+            continue;
+        }
+        if (options.maxRatio < 1 && second[s] < inf && distance >= options.maxRatio * std::sqrt(second[s]))
+        {
+            continue;
+        }
+        if (options.mutual && nearestSource[nearest[s]] != s)
+        {
+            continue;
+        }
+        matches.push_back({static_cast<size_t>(s), static_cast<size_t>(nearest[s]), distance});
+    }
+
+    std::sort(matches.begin(), matches.end(), [](const FeatureCorrespondence& a, const FeatureCorrespondence& b)
+    {
+        return a.distance < b.distance;
+    });
+
+    lvr2::logout::get() << lvr2::info << "[Features] Matched " << matches.size() << " of "
+        << numSource << " descriptors" << lvr2::endl;
+    return matches;
+}
+
+} // namespace lvr2
@@ -48,6 +48,7 @@ ICPPointAlign::ICPPointAlign(SLAMScanPtr model, SLAMScanPtr data) :
     m_maxDistanceMatch  = 25;
     m_maxIterations     = 50;
     m_epsilon           = 0.00001;
+    m_maxLeafSize       = 20;
     m_verbose           = false;
 
     m_searchTree = model->createKDTree(m_maxLeafSize);
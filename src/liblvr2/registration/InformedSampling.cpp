@@ -0,0 +1,267 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * InformedSampling.cpp
+ */
+
+#include "lvr2/registration/InformedSampling.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <algorithm>
+#include <cmath>
+#include <functional>
+#include <limits>
+#include <numeric>
+#include <random>
+
+namespace lvr2
+{
+
+namespace
+{
+
+bool checkNormals(PointBufferPtr buffer, const char* name)
+{
+    if (!buffer || !buffer->hasNormals())
+    {
+        lvr2::logout::get() << lvr2::warning << "[" << name << "] Point buffer has no normals, "
+                            << "returning all points" << lvr2::endl;
+        return false;
+    }
+    return true;
+}
+
+using Vector6d = Eigen::Matrix<double, 6, 1>;
+
+/**
+ * @brief Computes the point-to-plane constraint vectors of all points.
+ *        Positions are relative to the centroid and scaled by the mean
+ *        distance to it, so rotations and translations are comparable.
+ */
+std::vector<Vector6d> constraintVectors(PointBufferPtr buffer, const std::vector<size_t>& indices)
+{
+    floatArr points = buffer->getPointArray();
+    floatArr normals = buffer->getNormalArray();
+
+    Vector3d centroid = Vector3d::Zero();
+    for (size_t i : indices)
+    {
+        centroid += Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+    }
+    centroid /= std::max<size_t>(indices.size(), 1);
+
+    double scale = 0;
+    for (size_t i : indices)
+    {
+        scale += (Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]) - centroid).norm();
+    }
+    scale = scale > 0 ? indices.size() / scale : 1;
+
+    std::vector<Vector6d> vectors(indices.size());
+    for (size_t j = 0; j < indices.size(); j++)
+    {
+        size_t i = indices[j];
+        Vector3d p = (Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]) - centroid) * scale;
+        Vector3d n(normals[3 * i], normals[3 * i + 1], normals[3 * i + 2]);
+        vectors[j] << p.cross(n), n;
+    }
+    return vectors;
+}
+
+} // anonymous namespace
+
+std::vector<size_t> NormalSpaceSampling::selectIndices() const
+{
+    const size_t n = m_pointBuffer->numPoints();
+    std::vector<size_t> result;
+    if (n <= m_numPoints)
+    {
+        result.resize(n);
+        std::iota(result.begin(), result.end(), 0);
+        return result;
+    }
+
+    floatArr normals = m_pointBuffer->getNormalArray();
+    const size_t bins = std::max<size_t>(m_binsPerAxis, 1);
+
+    // Sort the points into buckets by the spherical coordinates of their normals
+    std::vector<std::vector<size_t>> buckets(bins * bins);
+    for (size_t i = 0; i < n; i++)
+    {
+        Vector3d normal(normals[3 * i], normals[3 * i + 1], normals[3 * i + 2]);
+        if (!normal.allFinite() || normal.squaredNorm() == 0)
+        {
+            continue;
+        }
+        normal.normalize();
+        double azimuth = (std::atan2(normal.y(), normal.x()) + M_PI) / (2 * M_PI);
+        double polar = std::acos(std::max(-1.0, std::min(1.0, normal.z()))) / M_PI;
+        size_t a = std::min<size_t>(azimuth * bins, bins - 1);
+        size_t p = std::min<size_t>(polar * bins, bins - 1);
+        buckets[p * bins + a].push_back(i);
+    }
+
+    std::mt19937 rng(m_seed);
+    for (auto& bucket : buckets)
+    {
+        std::shuffle(bucket.begin(), bucket.end(), rng);
+    }
+    buckets.erase(
+        std::remove_if(buckets.begin(), buckets.end(), [](const std::vector<size_t>& b) { return b.empty(); }),
+        buckets.end()
+    );
+
+    // Draw one point per bucket in turn until enough points are selected
+    result.reserve(m_numPoints);
+    for (size_t round = 0; result.size() < m_numPoints && !buckets.empty(); round++)
+    {
+        bool any = false;
+        for (const auto& bucket : buckets)
+        {
+            if (round < bucket.size() && result.size() < m_numPoints)
+            {
+                result.push_back(bucket[round]);
+                any = true;
+            }
+        }
+        if (!any)
+        {
+            break;
+        }
+    }
+
+    std::sort(result.begin(), result.end());
+    return result;
+}
+
+PointBufferPtr NormalSpaceSampling::getReducedPoints()
+{
+    if (!checkNormals(m_pointBuffer, "NormalSpaceSampling"))
+    {
+        return m_pointBuffer;
+    }
+    return std::make_shared<PointBuffer>(m_pointBuffer->select(selectIndices()));
+}
+
+std::vector<size_t> CovarianceSampling::selectIndices() const
+{
+    const size_t n = m_pointBuffer->numPoints();
+    std::vector<size_t> all(n);
+    std::iota(all.begin(), all.end(), 0);
+    if (n <= m_numPoints)
+    {
+        return all;
+    }
+
+    std::vector<Vector6d> vectors = constraintVectors(m_pointBuffer, all);
+    Eigen::Matrix<double, 6, 6> cov = Eigen::Matrix<double, 6, 6>::Zero();
+    for (const Vector6d& v : vectors)
+    {
+        cov += v * v.transpose();
+    }
+    Eigen::SelfAdjointEigenSolver<Eigen::Matrix<double, 6, 6>> eig(cov);
+    const Eigen::Matrix<double, 6, 6> directions = eig.eigenvectors();
+
+    // For each eigen direction, the points sorted by decreasing leverage
+    std::vector<std::vector<std::pair<double, size_t>>> sorted(6);
+    for (int k = 0; k < 6; k++)
+    {
+        sorted[k].resize(n);
+        for (size_t i = 0; i < n; i++)
+        {
+            double d = vectors[i].dot(directions.col(k));
+            sorted[k][i] = std::make_pair(d * d, i);
+        }
+        std::sort(sorted[k].begin(), sorted[k].end(), std::greater<std::pair<double, size_t>>());
+    }
+
+    // Greedily add the best point of the least constrained direction
+    std::vector<bool> used(n, false);
+    std::vector<size_t> next(6, 0);
+    Vector6d constraint = Vector6d::Zero();
+    std::vector<size_t> result;
+    result.reserve(m_numPoints);
+    while (result.size() < m_numPoints)
+    {
+        int k;
+        constraint.minCoeff(&k);
+        while (next[k] < n && used[sorted[k][next[k]].second])
+        {
+            next[k]++;
+        }
+        if (next[k] == n)
+        {
+            // All points were used, can only happen if numPoints >= n
+            break;
+        }
+
+        size_t i = sorted[k][next[k]].second;
+        used[i] = true;
+        result.push_back(i);
+        for (int j = 0; j < 6; j++)
+        {
+            double d = vectors[i].dot(directions.col(j));
+            constraint[j] += d * d;
+        }
+    }
+
+    std::sort(result.begin(), result.end());
+    return result;
+}
+
+PointBufferPtr CovarianceSampling::getReducedPoints()
+{
+    if (!checkNormals(m_pointBuffer, "CovarianceSampling"))
+    {
+        return m_pointBuffer;
+    }
+    return std::make_shared<PointBuffer>(m_pointBuffer->select(selectIndices()));
+}
+
+double CovarianceSampling::conditionNumber(PointBufferPtr points, const std::vector<size_t>& indices)
+{
+    if (!checkNormals(points, "CovarianceSampling") || indices.empty())
+    {
+        return std::numeric_limits<double>::infinity();
+    }
+
+    Eigen::Matrix<double, 6, 6> cov = Eigen::Matrix<double, 6, 6>::Zero();
+    for (const Vector6d& v : constraintVectors(points, indices))
+    {
+        cov += v * v.transpose();
+    }
+    Eigen::SelfAdjointEigenSolver<Eigen::Matrix<double, 6, 6>> eig(cov, Eigen::EigenvaluesOnly);
+    double smallest = eig.eigenvalues()[0];
+    return smallest > 0 ? eig.eigenvalues()[5] / smallest : std::numeric_limits<double>::infinity();
+}
+
+} // namespace lvr2
@@ -0,0 +1,216 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Keypoints.cpp
+ */
+
+#include "lvr2/registration/Keypoints.hpp"
+#include "lvr2/registration/IndexedPointTree.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <algorithm>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+Vector3f pointAt(const floatArr& points, size_t i)
+{
+    return Vector3f(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+}
+
+/**
+ * @brief Keeps the candidates whose response is maximal within the radius
+ *        and sorts them by decreasing response. Non candidates have a
+ *        response of -infinity.
+ */
+std::vector<size_t> nonMaxSuppression(
+    const IndexedPointTree& tree,
+    const floatArr& points,
+    const std::vector<float>& response,
+    float radius,
+    size_t maxNeighbors)
+{
+    const size_t n = response.size();
+    std::vector<char> isMax(n, 0);
+
+    #pragma omp parallel for schedule(dynamic, 1024)
+    for (size_t i = 0; i < n; i++)
+    {
+        if (response[i] == -std::numeric_limits<float>::infinity())
+        {
+            continue;
+        }
+        std::vector<IndexedPoint3f*> neighbors;
+        tree.knnSearch(pointAt(points, i), maxNeighbors, neighbors, radius);
+        bool localMax = true;
+        for (const IndexedPoint3f* q : neighbors)
+        {
+            // Ties are broken by the index, so plateaus yield a single keypoint
+            const size_t j = q->index;
+            if (response[j] > response[i] || (response[j] == response[i] && j < i))
+            {
+                localMax = false;
+                break;
+            }
+        }
+        isMax[i] = localMax;
+    }
+
+    std::vector<size_t> keypoints;
+    for (size_t i = 0; i < n; i++)
+    {
+        if (isMax[i])
+        {
+            keypoints.push_back(i);
+        }
+    }
+    std::sort(keypoints.begin(), keypoints.end(), [&](size_t a, size_t b)
+    {
+        return response[a] > response[b];
+    });
+    return keypoints;
+}
+
+} // anonymous namespace
+
+std::vector<size_t> detectISSKeypoints(
+    PointBufferPtr buffer,
+    const ISSKeypointOptions& options)
+{
+    const size_t n = buffer->numPoints();
+    if (n == 0)
+    {
+        return std::vector<size_t>();
+    }
+    floatArr points = buffer->getPointArray();
+    IndexedPointTree::Ptr tree = buildIndexedPointTree(buffer);
+
+    std::vector<float> saliency(n, -std::numeric_limits<float>::infinity());
+
+    #pragma omp parallel for schedule(dynamic, 1024)
+    for (size_t i = 0; i < n; i++)
+    {
+        std::vector<IndexedPoint3f*> neighbors;
+        tree->knnSearch(pointAt(points, i), options.maxNeighbors, neighbors, options.salientRadius);
+        if (neighbors.size() < options.minNeighbors)
+        {
+            continue;
+        }
+
+        const Vector3d p = pointAt(points, i).cast<double>();
+        Eigen::Matrix3d scatter = Eigen::Matrix3d::Zero();
+        for (const IndexedPoint3f* q : neighbors)
+        {
+            const Vector3d d = q->point.cast<double>() - p;
+            scatter += d * d.transpose();
+        }
+        scatter /= neighbors.size();
+
+        // Eigenvalues in increasing order
+        const Vector3d ev = Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d>(scatter, Eigen::EigenvaluesOnly).eigenvalues();
+        const double l1 = ev[2];
+        const double l2 = ev[1];
+        const double l3 = ev[0];
+        if (l1 > 0 && l2 > 0 && l2 / l1 < options.gamma21 && l3 / l2 < options.gamma32)
+        {
+            saliency[i] = l3;
+        }
+    }
+
+    std::vector<size_t> keypoints = nonMaxSuppression(*tree, points, saliency, options.nonMaxRadius, options.maxNeighbors);
+    lvr2::logout::get() << lvr2::info << "[Keypoints] Detected " << keypoints.size() << " ISS keypoints" << lvr2::endl;
+    return keypoints;
+}
+
+std::vector<size_t> detectHarrisKeypoints(
+    PointBufferPtr buffer,
+    const HarrisKeypointOptions& options)
+{
+    const size_t n = buffer->numPoints();
+    if (n == 0)
+    {
+        return std::vector<size_t>();
+    }
+    if (!buffer->hasNormals())
+    {
+        lvr2::logout::get() << lvr2::error << "[Keypoints] Harris keypoints require normals" << lvr2::endl;
+        return std::vector<size_t>();
+    }
+    floatArr points = buffer->getPointArray();
+    floatArr normals = buffer->getNormalArray();
+    IndexedPointTree::Ptr tree = buildIndexedPointTree(buffer);
+
+    std::vector<float> response(n, -std::numeric_limits<float>::infinity());
+
+    #pragma omp parallel for schedule(dynamic, 1024)
+    for (size_t i = 0; i < n; i++)
+    {
+        std::vector<IndexedPoint3f*> neighbors;
+        tree->knnSearch(pointAt(points, i), options.maxNeighbors, neighbors, options.radius);
+        if (neighbors.size() < options.minNeighbors)
+        {
+            continue;
+        }
+
+        Vector3d mean = Vector3d::Zero();
+        for (const IndexedPoint3f* q : neighbors)
+        {
+            mean += pointAt(normals, q->index).cast<double>();
+        }
+        mean /= neighbors.size();
+
+        Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+        for (const IndexedPoint3f* q : neighbors)
+        {
+            const Vector3d d = pointAt(normals, q->index).cast<double>() - mean;
+            covariance += d * d.transpose();
+        }
+        covariance /= neighbors.size();
+
+        const double trace = covariance.trace();
+        const double r = covariance.determinant() - options.k * trace * trace;
+        if (r > options.threshold)
+        {
+            response[i] = r;
+        }
+    }
+
+    std::vector<size_t> keypoints = nonMaxSuppression(*tree, points, response, options.nonMaxRadius, options.maxNeighbors);
+    lvr2::logout::get() << lvr2::info << "[Keypoints] Detected " << keypoints.size() << " Harris keypoints" << lvr2::endl;
+    return keypoints;
+}
+
+} // namespace lvr2
@@ -0,0 +1,305 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ObjectLocalization.cpp
+ */
+
+#include "lvr2/registration/ObjectLocalization.hpp"
+#include "lvr2/algorithm/KDTree.hpp"
+#include "lvr2/algorithm/MeshSampling.hpp"
+#include "lvr2/registration/Features.hpp"
+#include "lvr2/registration/ICPPointAlign.hpp"
+#include "lvr2/registration/Keypoints.hpp"
+#include "lvr2/registration/SLAMScanWrapper.hpp"
+#include "lvr2/types/ScanTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Eigenvalues>
+#include <Eigen/Geometry>
+
+#include <algorithm>
+#include <cmath>
+#include <random>
+#include <unordered_set>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Keeps the first point of each voxel
+PointBufferPtr downsample(PointBufferPtr buffer, float voxelSize)
+{
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    std::unordered_set<Vector3i> occupied;
+    std::vector<size_t> indices;
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3i voxel(
+            static_cast<int>(std::floor(points[3 * i] / voxelSize)),
+            static_cast<int>(std::floor(points[3 * i + 1] / voxelSize)),
+            static_cast<int>(std::floor(points[3 * i + 2] / voxelSize)));
+        if (occupied.insert(voxel).second)
+        {
+            indices.push_back(i);
+        }
+    }
+    return std::make_shared<PointBuffer>(buffer->select(indices));
+}
+
+KDTreePtr<Vector3f> buildTree(PointBufferPtr buffer)
+{
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    std::unique_ptr<Vector3f[]> treePoints(new Vector3f[n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        treePoints[i] = Vector3f(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+    }
+    return KDTree<Vector3f>::create(std::move(treePoints), n);
+}
+
+/// PCA normals of the k nearest neighbors, oriented towards the origin
+void estimateNormals(PointBufferPtr buffer, size_t k)
+{
+    const size_t n = buffer->numPoints();
+    floatArr points = buffer->getPointArray();
+    floatArr normals(new float[3 * n]);
+    auto tree = buildTree(buffer);
+
+    #pragma omp parallel for schedule(dynamic, 1024)
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3f p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        std::vector<Vector3f*> neighbors;
+        tree->knnSearch(p, k, neighbors);
+
+        Vector3d centroid = Vector3d::Zero();
+        for (const Vector3f* q : neighbors)
+        {
+            centroid += q->cast<double>();
+        }
+        centroid /= std::max<size_t>(neighbors.size(), 1);
+
+        Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+        for (const Vector3f* q : neighbors)
+        {
+            const Vector3d d = q->cast<double>() - centroid;
+            covariance += d * d.transpose();
+        }
+        Vector3d normal = Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d>(covariance).eigenvectors().col(0);
+        if (normal.dot(p.cast<double>()) > 0)
+        {
+            normal = -normal;
+        }
+        normals[3 * i] = normal.x();
+        normals[3 * i + 1] = normal.y();
+        normals[3 * i + 2] = normal.z();
+    }
+    buffer->setNormalArray(normals, n);
+}
+
+Vector3d pointAt(PointBufferPtr buffer, size_t i)
+{
+    floatArr points = buffer->getPointArray();
+    return Vector3d(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+}
+
+} // anonymous namespace
+
+ObjectLocalizationResult localizeObject(
+    MeshBufferPtr model,
+    PointBufferPtr scene,
+    const ObjectLocalizationOptions& options)
+{
+    ObjectLocalizationResult result;
+    const float voxel = options.voxelSize;
+    const double inlierDistance = options.inlierDistance > 0 ? options.inlierDistance : 2 * voxel;
+    const double maxDistance = options.maxDistance > 0 ? options.maxDistance : 3 * voxel;
+
+    // Sample the model densely enough that every voxel of its surface is hit
+    PointBufferPtr modelSamples = sampleMesh(model, 4.0f / (voxel * voxel), options.seed);
+    PointBufferPtr modelPoints = downsample(modelSamples, voxel);
+    PointBufferPtr scenePoints = downsample(scene, voxel);
+    if (!scenePoints->hasNormals())
+    {
+        estimateNormals(scenePoints, options.kn);
+    }
+
+    // Coarse alignment by descriptor matching
+    ISSKeypointOptions issOptions;
+    issOptions.salientRadius = 6 * voxel;
+    issOptions.nonMaxRadius = 4 * voxel;
+    const std::vector<size_t> modelKeypoints = detectISSKeypoints(modelPoints, issOptions);
+    const std::vector<size_t> sceneKeypoints = detectISSKeypoints(scenePoints, issOptions);
+    if (modelKeypoints.size() < 3 || sceneKeypoints.size() < 3)
+    {
+        lvr2::logout::get() << lvr2::warning << "[ObjectLocalization] Too few keypoints" << lvr2::endl;
+        return result;
+    }
+
+    FPFHFeaturePtr modelFeatures = computeFPFHFeatures(modelPoints, modelKeypoints, options.kf);
+    FPFHFeaturePtr sceneFeatures = computeFPFHFeatures(scenePoints, sceneKeypoints, options.kf);
+    const std::vector<FeatureCorrespondence> matches = matchFeatures(*modelFeatures, *sceneFeatures);
+    result.numCorrespondences = matches.size();
+    if (matches.size() < 3)
+    {
+        lvr2::logout::get() << lvr2::warning << "[ObjectLocalization] Too few correspondences" << lvr2::endl;
+        return result;
+    }
+
+    std::vector<Vector3d> src(matches.size());
+    std::vector<Vector3d> dst(matches.size());
+    for (size_t i = 0; i < matches.size(); i++)
+    {
+        src[i] = pointAt(modelPoints, modelKeypoints[matches[i].source]);
+        dst[i] = pointAt(scenePoints, sceneKeypoints[matches[i].target]);
+    }
+
+    auto inliersOf = [&](const Eigen::Matrix4d& T, std::vector<size_t>& inliers)
+    {
+        inliers.clear();
+        for (size_t i = 0; i < src.size(); i++)
+        {
+            const Vector3d p = T.block<3, 3>(0, 0) * src[i] + T.block<3, 1>(0, 3);
+            if ((p - dst[i]).norm() < inlierDistance)
+            {
+                inliers.push_back(i);
+            }
+        }
+    };
+
+    std::mt19937 rng(options.seed);
+    std::uniform_int_distribution<size_t> pick(0, matches.size() - 1);
+    Eigen::Matrix4d best = Eigen::Matrix4d::Identity();
+    std::vector<size_t> bestInliers;
+    std::vector<size_t> inliers;
+    for (int it = 0; it < options.ransacIterations; it++)
+    {
+        const size_t a = pick(rng);
+        const size_t b = pick(rng);
+        const size_t c = pick(rng);
+        if (a == b || a == c || b == c)
+        {
+            continue;
+        }
+
+        // Rigid transformations preserve the edge lengths of the sample
+        const size_t sample[3] = {a, b, c};
+        bool consistent = true;
+        for (int e = 0; e < 3 && consistent; e++)
+        {
+            const double ls = (src[sample[e]] - src[sample[(e + 1) % 3]]).norm();
+            const double ld = (dst[sample[e]] - dst[sample[(e + 1) % 3]]).norm();
+            consistent = std::abs(ls - ld) < 0.1 * std::max(ls, ld) + inlierDistance;
+        }
+        if (!consistent)
+        {
+            continue;
+        }
+
+        Eigen::Matrix3d S, D;
+        for (int e = 0; e < 3; e++)
+        {
+            S.col(e) = src[sample[e]];
+            D.col(e) = dst[sample[e]];
+        }
+        const Eigen::Matrix4d T = Eigen::umeyama(S, D, false);
+        inliersOf(T, inliers);
+        if (inliers.size() > bestInliers.size())
+        {
+            best = T;
+            bestInliers = inliers;
+        }
+    }
+
+    if (bestInliers.size() < 3)
+    {
+        lvr2::logout::get() << lvr2::warning << "[ObjectLocalization] No consistent coarse pose found" << lvr2::endl;
+        return result;
+    }
+
+    // Re-estimate the coarse pose from all inliers
+    Eigen::Matrix3Xd S(3, bestInliers.size());
+    Eigen::Matrix3Xd D(3, bestInliers.size());
+    for (size_t i = 0; i < bestInliers.size(); i++)
+    {
+        S.col(i) = src[bestInliers[i]];
+        D.col(i) = dst[bestInliers[i]];
+    }
+    best = Eigen::umeyama(S, D, false);
+    result.numInliers = bestInliers.size();
+
+    // ICP refinement of the model points against the scene
+    ScanPtr sceneScan = std::make_shared<Scan>();
+    sceneScan->points = scenePoints;
+    ScanPtr modelScan = std::make_shared<Scan>();
+    modelScan->points = modelPoints;
+    modelScan->poseEstimation = best;
+
+    SLAMScanPtr sceneWrapper = std::make_shared<SLAMScanWrapper>(sceneScan);
+    SLAMScanPtr modelWrapper = std::make_shared<SLAMScanWrapper>(modelScan);
+    ICPPointAlign icp(sceneWrapper, modelWrapper);
+    icp.setMaxMatchDistance(maxDistance);
+    icp.setMaxIterations(options.icpIterations);
+    icp.match();
+    result.pose = modelWrapper->pose();
+
+    // Fit error of all model samples
+    auto tree = buildTree(scenePoints);
+    floatArr samples = modelSamples->getPointArray();
+    const size_t n = modelSamples->numPoints();
+    size_t fitted = 0;
+    double sumSq = 0;
+    #pragma omp parallel for reduction(+:fitted, sumSq)
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3d p = result.pose.block<3, 3>(0, 0) * Vector3d(samples[3 * i], samples[3 * i + 1], samples[3 * i + 2])
+            + result.pose.block<3, 1>(0, 3);
+        Vector3f* neighbor;
+        double distance;
+        if (tree->nnSearch(p, neighbor, distance, maxDistance))
+        {
+            fitted++;
+            sumSq += distance * distance;
+        }
+    }
+    result.fitness = n > 0 ? static_cast<double>(fitted) / n : 0.0;
+    result.rmsError = fitted > 0 ? std::sqrt(sumSq / fitted) : 0.0;
+    result.success = true;
+
+    lvr2::logout::get() << lvr2::info << "[ObjectLocalization] Fitness: " << result.fitness
+        << ", RMS error: " << result.rmsError << ", RANSAC inliers: " << result.numInliers
+        << " of " << result.numCorrespondences << lvr2::endl;
+    return result;
+}
+
+} // namespace lvr2
@@ -0,0 +1,313 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * PoseGraph.cpp
+ */
+
+#include "lvr2/registration/PoseGraph.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Geometry>
+#include <Eigen/SparseCholesky>
+#include <Eigen/SparseCore>
+
+#include <cmath>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Pose for the local increment (translation, axis angle rotation)
+Transformd expIncrement(const Vector6d& delta)
+{
+    Transformd T = Transformd::Identity();
+    const Vector3d w = delta.tail<3>();
+    const double angle = w.norm();
+    if (angle > 0)
+    {
+        T.block<3, 3>(0, 0) = Eigen::AngleAxisd(angle, w / angle).toRotationMatrix();
+    }
+    T.block<3, 1>(0, 3) = delta.head<3>();
+    return T;
+}
+
+/// Residual of an edge: the deviation of the relative pose from the measurement
+Vector6d edgeError(const Transformd& from, const Transformd& to, const Transformd& measurement)
+{
+    const Transformd E = measurement.inverse() * from.inverse() * to;
+    const Eigen::AngleAxisd aa(Eigen::Matrix3d(E.block<3, 3>(0, 0)));
+    Vector6d e;
+    e.head<3>() = E.block<3, 1>(0, 3);
+    e.tail<3>() = aa.angle() * aa.axis();
+    return e;
+}
+
+/// Weight of the information matrix of an edge with the given squared Mahalanobis distance
+double robustWeight(const PoseGraphEdge& edge, double chi2, const PoseGraphOptions& options)
+{
+    if (!edge.loopClosure || options.huberDelta <= 0)
+    {
+        return 1.0;
+    }
+    const double r = std::sqrt(chi2);
+    return r <= options.huberDelta ? 1.0 : options.huberDelta / r;
+}
+
+double robustCost(const std::vector<Transformd>& poses, const std::vector<PoseGraphEdge>& edges, const PoseGraphOptions& options)
+{
+    double cost = 0;
+    for (const PoseGraphEdge& edge : edges)
+    {
+        const Vector6d e = edgeError(poses[edge.from], poses[edge.to], edge.measurement);
+        const double chi2 = e.dot(edge.information * e);
+        const double r = std::sqrt(chi2);
+        if (edge.loopClosure && options.huberDelta > 0 && r > options.huberDelta)
+        {
+            cost += 2 * options.huberDelta * r - options.huberDelta * options.huberDelta;
+        }
+        else
+        {
+            cost += chi2;
+        }
+    }
+    return cost;
+}
+
+} // anonymous namespace
+
+size_t PoseGraph::addPose(const Transformd& pose)
+{
+    m_poses.push_back(pose);
+    m_fixed.push_back(m_poses.size() == 1);
+    return m_poses.size() - 1;
+}
+
+void PoseGraph::addEdge(const PoseGraphEdge& edge)
+{
+    if (edge.from >= m_poses.size() || edge.to >= m_poses.size())
+    {
+        throw std::out_of_range("[PoseGraph] Edge between " + std::to_string(edge.from) + " and "
+            + std::to_string(edge.to) + " refers to a pose that does not exist");
+    }
+    m_edges.push_back(edge);
+}
+
+void PoseGraph::addEdge(size_t from, size_t to, const Transformd& measurement,
+                        const Matrix6d& information, bool loopClosure)
+{
+    PoseGraphEdge edge;
+    edge.from = from;
+    edge.to = to;
+    edge.measurement = measurement;
+    edge.information = information;
+    edge.loopClosure = loopClosure;
+    addEdge(edge);
+}
+
+void PoseGraph::setFixed(size_t index, bool fixed)
+{
+    m_fixed.at(index) = fixed;
+}
+
+double PoseGraph::error() const
+{
+    double error = 0;
+    for (const PoseGraphEdge& edge : m_edges)
+    {
+        const Vector6d e = edgeError(m_poses[edge.from], m_poses[edge.to], edge.measurement);
+        error += e.dot(edge.information * e);
+    }
+    return error;
+}
+
+PoseGraphResult PoseGraph::optimize(const PoseGraphOptions& options)
+{
+    PoseGraphResult result;
+    result.initialError = error();
+    result.finalError = result.initialError;
+
+    // Index of the first variable of each pose, -1 for fixed poses
+    std::vector<int> variable(m_poses.size(), -1);
+    int numVariables = 0;
+    for (size_t i = 0; i < m_poses.size(); i++)
+    {
+        if (!m_fixed[i])
+        {
+            variable[i] = numVariables;
+            numVariables += 6;
+        }
+    }
+    if (numVariables == 0 || m_edges.empty())
+    {
+        result.converged = true;
+        return result;
+    }
+
+    const double h = 1e-6;
+    double cost = robustCost(m_poses, m_edges, options);
+    for (int it = 0; it < options.maxIterations; it++)
+    {
+        std::vector<Eigen::Triplet<double>> triplets;
+        Eigen::VectorXd b = Eigen::VectorXd::Zero(numVariables);
+
+        for (const PoseGraphEdge& edge : m_edges)
+        {
+            const Transformd& Xi = m_poses[edge.from];
+            const Transformd& Xj = m_poses[edge.to];
+            const Vector6d e = edgeError(Xi, Xj, edge.measurement);
+            const Matrix6d W = robustWeight(edge, e.dot(edge.information * e), options) * edge.information;
+
+            // Numerical Jacobians with respect to the local increments
+            Matrix6d Ji, Jj;
+            for (int k = 0; k < 6; k++)
+            {
+                Vector6d d = Vector6d::Zero();
+                d[k] = h;
+                Ji.col(k) = (edgeError(Xi * expIncrement(d), Xj, edge.measurement)
+                    - edgeError(Xi * expIncrement(-d), Xj, edge.measurement)) / (2 * h);
+                Jj.col(k) = (edgeError(Xi, Xj * expIncrement(d), edge.measurement)
+                    - edgeError(Xi, Xj * expIncrement(-d), edge.measurement)) / (2 * h);
+            }
+
+            const int vars[2] = {variable[edge.from], variable[edge.to]};
+            const Matrix6d* J[2] = {&Ji, &Jj};
+            for (int a = 0; a < 2; a++)
+            {
+                if (vars[a] < 0)
+                {
+                    continue;
+                }
+                b.segment<6>(vars[a]) += J[a]->transpose() * W * e;
+                for (int c = 0; c < 2; c++)
+                {
+                    if (vars[c] < 0)
+                    {
+                        continue;
+                    }
+                    const Matrix6d block = J[a]->transpose() * W * (*J[c]);
+                    for (int r = 0; r < 6; r++)
+                    {
+                        for (int s = 0; s < 6; s++)
+                        {
+                            triplets.emplace_back(vars[a] + r, vars[c] + s, block(r, s));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Small damping keeps poses without constraints solvable
+        for (int v = 0; v < numVariables; v++)
+        {
+            triplets.emplace_back(v, v, 1e-9);
+        }
+
+        Eigen::SparseMatrix<double> H(numVariables, numVariables);
+        H.setFromTriplets(triplets.begin(), triplets.end());
+        Eigen::SimplicialLDLT<Eigen::SparseMatrix<double>> solver(H);
+        if (solver.info() != Eigen::Success)
+        {
+            lvr2::logout::get() << lvr2::error << "[PoseGraph] Decomposition of the normal equations failed" << lvr2::endl;
+            break;
+        }
+        const Eigen::VectorXd delta = solver.solve(-b);
+
+        const std::vector<Transformd> previous = m_poses;
+        for (size_t i = 0; i < m_poses.size(); i++)
+        {
+            if (variable[i] >= 0)
+            {
+                m_poses[i] = m_poses[i] * expIncrement(delta.segment<6>(variable[i]));
+            }
+        }
+        result.iterations++;
+
+        const double newCost = robustCost(m_poses, m_edges, options);
+        if (newCost > cost)
+        {
+            // Diverged, keep the previous solution
+            m_poses = previous;
+            result.converged = false;
+            break;
+        }
+        const bool converged = cost - newCost < options.epsilon * cost;
+        cost = newCost;
+        if (converged)
+        {
+            result.converged = true;
+            break;
+        }
+    }
+
+    result.finalError = error();
+    lvr2::logout::get() << lvr2::info << "[PoseGraph] Optimized " << m_poses.size() << " poses with "
+        << m_edges.size() << " edges in " << result.iterations << " iterations. Error: "
+        << result.initialError << " -> " << result.finalError << lvr2::endl;
+    return result;
+}
+
+PoseGraph poseGraphFromScanProject(ScanProjectPtr project, const std::vector<PoseGraphEdge>& loopClosures)
+{
+    PoseGraph graph;
+    for (size_t i = 0; i < project->positions.size(); i++)
+    {
+        graph.addPose(project->positions[i]->transformation);
+        if (i > 0)
+        {
+            const Transformd& prev = project->positions[i - 1]->transformation;
+            const Transformd& cur = project->positions[i]->transformation;
+            graph.addEdge(i - 1, i, prev.inverse() * cur);
+        }
+    }
+    for (const PoseGraphEdge& edge : loopClosures)
+    {
+        graph.addEdge(edge);
+    }
+    return graph;
+}
+
+bool applyPoseGraph(ScanProjectPtr project, const PoseGraph& graph)
+{
+    if (project->positions.size() != graph.numPoses())
+    {
+        lvr2::logout::get() << lvr2::warning << "[PoseGraph] Graph has " << graph.numPoses()
+            << " poses, but the project " << project->positions.size() << " positions" << lvr2::endl;
+        return false;
+    }
+    for (size_t i = 0; i < graph.numPoses(); i++)
+    {
+        project->positions[i]->transformation = graph.poses()[i];
+    }
+    return true;
+}
+
+} // namespace lvr2
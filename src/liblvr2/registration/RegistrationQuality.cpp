@@ -0,0 +1,191 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * RegistrationQuality.cpp
+ */
+
+#include "lvr2/registration/RegistrationQuality.hpp"
+#include "lvr2/algorithm/KDTree.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/ColorGradient.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <Eigen/Eigenvalues>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+RegistrationQualityReport registrationQuality(
+    PointBufferPtr source,
+    PointBufferPtr target,
+    const RegistrationQualityOptions& options)
+{
+    RegistrationQualityReport report;
+    const size_t n = source->numPoints();
+    const size_t m = target->numPoints();
+    report.residuals.assign(n, std::numeric_limits<float>::quiet_NaN());
+    report.regionErrors.assign(n, std::numeric_limits<float>::quiet_NaN());
+    if (n == 0 || m == 0)
+    {
+        return report;
+    }
+
+    floatArr targetPoints = target->getPointArray();
+    std::unique_ptr<Vector3f[]> treePoints(new Vector3f[m]);
+    for (size_t i = 0; i < m; i++)
+    {
+        treePoints[i] = Vector3f(targetPoints[3 * i], targetPoints[3 * i + 1], targetPoints[3 * i + 2]);
+    }
+    auto tree = KDTree<Vector3f>::create(std::move(treePoints), m);
+
+    floatArr sourcePoints = source->getPointArray();
+    const size_t k = std::max<size_t>(options.k, 1);
+    const double searchRadius = 3 * options.maxDistance;
+
+    #pragma omp parallel for schedule(dynamic, 1024)
+    for (size_t i = 0; i < n; i++)
+    {
+        const Vector3f p(sourcePoints[3 * i], sourcePoints[3 * i + 1], sourcePoints[3 * i + 2]);
+        std::vector<Vector3f*> neighbors;
+        std::vector<float> distances;
+        tree->knnSearch(p, k, neighbors, distances, searchRadius);
+        if (neighbors.empty() || distances[0] > options.maxDistance)
+        {
+            continue;
+        }
+
+        float residual = distances[0];
+        if (neighbors.size() >= 3 && options.k >= 3)
+        {
+            Vector3d centroid = Vector3d::Zero();
+            for (const Vector3f* q : neighbors)
+            {
+                centroid += q->cast<double>();
+            }
+            centroid /= neighbors.size();
+
+            Eigen::Matrix3d covariance = Eigen::Matrix3d::Zero();
+            for (const Vector3f* q : neighbors)
+            {
+                const Vector3d d = q->cast<double>() - centroid;
+                covariance += d * d.transpose();
+            }
+            Eigen::SelfAdjointEigenSolver<Eigen::Matrix3d> solver(covariance);
+            const Vector3d normal = solver.eigenvectors().col(0);
+
+            // Fall back to point to point for degenerate (e.g. linear) neighborhoods
+            if (solver.eigenvalues()[1] > 1e-6 * solver.eigenvalues()[2])
+            {
+                residual = std::abs(normal.dot(p.cast<double>() - centroid));
+            }
+        }
+        report.residuals[i] = residual;
+    }
+
+    // Statistics and per region RMS errors
+    std::unordered_map<Vector3i, std::pair<double, size_t>> regions;
+    std::vector<Vector3i> regionOf(n);
+    double sum = 0;
+    double sumSq = 0;
+    for (size_t i = 0; i < n; i++)
+    {
+        regionOf[i] = Vector3i(
+            static_cast<int>(std::floor(sourcePoints[3 * i] / options.regionSize)),
+            static_cast<int>(std::floor(sourcePoints[3 * i + 1] / options.regionSize)),
+            static_cast<int>(std::floor(sourcePoints[3 * i + 2] / options.regionSize)));
+
+        const float r = report.residuals[i];
+        if (std::isnan(r))
+        {
+            continue;
+        }
+        report.numOverlapping++;
+        sum += r;
+        sumSq += r * r;
+        auto& region = regions[regionOf[i]];
+        region.first += r * r;
+        region.second++;
+    }
+
+    for (size_t i = 0; i < n; i++)
+    {
+        auto it = regions.find(regionOf[i]);
+        if (it != regions.end())
+        {
+            report.regionErrors[i] = std::sqrt(it->second.first / it->second.second);
+        }
+    }
+
+    report.overlapRatio = static_cast<double>(report.numOverlapping) / n;
+    if (report.numOverlapping > 0)
+    {
+        report.meanResidual = sum / report.numOverlapping;
+        report.rmsResidual = std::sqrt(sumSq / report.numOverlapping);
+    }
+
+    lvr2::logout::get() << lvr2::info << "[RegistrationQuality] Overlap: " << report.overlapRatio * 100
+        << "%, RMS residual: " << report.rmsResidual << ", mean residual: " << report.meanResidual << lvr2::endl;
+    return report;
+}
+
+void addRegistrationHeatmap(
+    PointBufferPtr source,
+    const RegistrationQualityReport& report,
+    float maxError)
+{
+    const size_t n = source->numPoints();
+    floatArr errors(new float[n]);
+    ucharArr colors(new unsigned char[3 * n]);
+
+    ColorGradient gradient(256);
+    for (size_t i = 0; i < n; i++)
+    {
+        const float e = i < report.regionErrors.size() ? report.regionErrors[i] : std::numeric_limits<float>::quiet_NaN();
+        errors[i] = e;
+        if (std::isnan(e))
+        {
+            std::fill(colors.get() + 3 * i, colors.get() + 3 * i + 3, 128);
+            continue;
+        }
+        const float t = maxError > 0 ? std::min(e / maxError, 1.0f) : 0.0f;
+        RGB8Color color;
+        gradient.getColor(color, static_cast<size_t>(t * 255), ColorGradient::JET);
+        std::copy(color.begin(), color.end(), colors.get() + 3 * i);
+    }
+
+    source->addFloatChannel(errors, "registration_error", n, 1);
+    source->setColorArray(colors, n);
+}
+
+} // namespace lvr2
@@ -0,0 +1,286 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * PhotoTexturing.cpp
+ */
+
+#include "lvr2/texture/PhotoTexturing.hpp"
+#include "lvr2/algorithm/raycasting/BVHRaycaster.hpp"
+#include "lvr2/texture/Material.hpp"
+#include "lvr2/texture/Texture.hpp"
+#include "lvr2/texture/TextureFactory.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Util.hpp"
+
+#include <opencv2/imgcodecs.hpp>
+
+#include <algorithm>
+#include <limits>
+#include <stdexcept>
+#include <unordered_map>
+
+namespace lvr2
+{
+
+size_t PhotoSet::addPhoto(const cv::Mat& image, std::shared_ptr<const CameraModel> model, const Transformd& cameraToWorld)
+{
+    Photo photo;
+    photo.image = image;
+    photo.model = model;
+    photo.cameraToWorld = cameraToWorld;
+    m_photos.push_back(photo);
+    m_worldToCamera.push_back(cameraToWorld.inverse());
+    return m_photos.size() - 1;
+}
+
+size_t PhotoSet::addPhoto(const boost::filesystem::path& file, std::shared_ptr<const CameraModel> model, const Transformd& cameraToWorld)
+{
+    cv::Mat image = cv::imread(file.string(), cv::IMREAD_COLOR);
+    if (image.empty())
+    {
+        throw std::runtime_error("[PhotoTexturing] Could not read image " + file.string());
+    }
+    return addPhoto(image, model, cameraToWorld);
+}
+
+Vector3f PhotoSet::cameraPosition(size_t index) const
+{
+    return m_photos[index].cameraToWorld.block<3, 1>(0, 3).cast<float>();
+}
+
+boost::optional<Vector2f> PhotoSet::project(size_t index, const Vector3f& point, float border) const
+{
+    const Transformd& worldToCamera = m_worldToCamera[index];
+    const Vector3f p = (worldToCamera.block<3, 3>(0, 0) * point.cast<double>() + worldToCamera.block<3, 1>(0, 3)).cast<float>();
+    if (p.z() <= 0)
+    {
+        return boost::none;
+    }
+
+    const cv::Mat& image = m_photos[index].image;
+    const Vector2f pixel = m_photos[index].model->projectPoint(p);
+    if (!pixel.allFinite() || pixel.x() < border || pixel.y() < border
+        || pixel.x() > image.cols - border || pixel.y() > image.rows - border)
+    {
+        return boost::none;
+    }
+    return pixel;
+}
+
+std::vector<int> selectViews(MeshBufferPtr mesh, const PhotoSet& photos, const PhotoTexturingOptions& options)
+{
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+    auto vertex = [&](size_t i)
+    {
+        return Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    std::vector<int> views(numFaces, -1);
+    std::vector<float> bestArea(numFaces, 0);
+    if (numFaces == 0 || photos.size() == 0)
+    {
+        return views;
+    }
+
+    std::unique_ptr<BVHRaycaster<DistInt>> raycaster;
+    if (options.occlusion)
+    {
+        raycaster.reset(new BVHRaycaster<DistInt>(mesh));
+    }
+
+    for (size_t p = 0; p < photos.size(); p++)
+    {
+        const Vector3f eye = photos.cameraPosition(p);
+
+        // Faces that face the camera and project into the image
+        std::vector<size_t> candidates;
+        std::vector<float> areas;
+        for (size_t f = 0; f < numFaces; f++)
+        {
+            const Vector3f a = vertex(faces[3 * f]);
+            const Vector3f b = vertex(faces[3 * f + 1]);
+            const Vector3f c = vertex(faces[3 * f + 2]);
+            const Vector3f normal = (b - a).cross(c - a).normalized();
+            const Vector3f toEye = (eye - (a + b + c) / 3.0f).normalized();
+            if (!(normal.dot(toEye) >= options.minCosAngle))
+            {
+                continue;
+            }
+
+            auto pa = photos.project(p, a, options.border);
+            auto pb = photos.project(p, b, options.border);
+            auto pc = photos.project(p, c, options.border);
+            if (!pa || !pb || !pc)
+            {
+                continue;
+            }
+
+            const Vector2f ab = *pb - *pa;
+            const Vector2f ac = *pc - *pa;
+            const float area = std::abs(ab.x() * ac.y() - ab.y() * ac.x()) / 2;
+            if (area > bestArea[f])
+            {
+                candidates.push_back(f);
+                areas.push_back(area);
+            }
+        }
+
+        std::vector<uint8_t> hits;
+        std::vector<DistInt> intersections;
+        std::vector<float> distances(candidates.size());
+        if (raycaster && !candidates.empty())
+        {
+            std::vector<Vector3f> directions(candidates.size());
+            for (size_t i = 0; i < candidates.size(); i++)
+            {
+                const size_t f = candidates[i];
+                const Vector3f centroid = (vertex(faces[3 * f]) + vertex(faces[3 * f + 1]) + vertex(faces[3 * f + 2])) / 3.0f;
+                distances[i] = (centroid - eye).norm();
+                directions[i] = (centroid - eye) / distances[i];
+            }
+            raycaster->castRays(eye, directions, intersections, hits);
+        }
+
+        for (size_t i = 0; i < candidates.size(); i++)
+        {
+            if (raycaster && hits[i] && intersections[i].dist < distances[i] - options.occlusionTolerance)
+            {
+                continue;
+            }
+            views[candidates[i]] = p;
+            bestArea[candidates[i]] = areas[i];
+        }
+    }
+
+    const size_t textured = std::count_if(views.begin(), views.end(), [](int v) { return v >= 0; });
+    lvr2::logout::get() << lvr2::info << "[PhotoTexturing] Found views for " << textured << " of "
+        << numFaces << " faces" << lvr2::endl;
+    return views;
+}
+
+MeshBufferPtr texturizeFromPhotos(MeshBufferPtr mesh, const PhotoSet& photos, const std::vector<int>& views)
+{
+    const size_t numFaces = mesh->numFaces();
+    const size_t numPhotos = photos.size();
+    floatArr vertices = mesh->getVertices();
+    floatArr normals = mesh->getVertexNormals();
+    indexArray faces = mesh->getFaceIndices();
+
+    // Materials are the used photos in order, followed by the untextured material
+    std::vector<int> materialOfPhoto(numPhotos, -1);
+    std::vector<Material> materials;
+    std::vector<Texture> textures;
+    for (int view : views)
+    {
+        if (view >= 0 && materialOfPhoto[view] < 0)
+        {
+            materialOfPhoto[view] = materials.size();
+            Material material;
+            material.m_texture = TextureHandle(textures.size());
+            materials.push_back(material);
+
+            Texture texture = TextureFactory::fromMat(photos[view].image);
+            texture.m_index = textures.size();
+            textures.push_back(std::move(texture));
+        }
+    }
+    const unsigned int untextured = materials.size();
+    if (std::find(views.begin(), views.end(), -1) != views.end())
+    {
+        Material material;
+        material.m_color = RGB8Color{128, 128, 128};
+        materials.push_back(material);
+    }
+
+    // One vertex per original vertex and photo
+    std::unordered_map<uint64_t, unsigned int> vertexMap;
+    std::vector<float> outVertices;
+    std::vector<float> outNormals;
+    std::vector<float> outTexCoords;
+    std::vector<unsigned int> outFaces(numFaces * 3);
+    std::vector<unsigned int> outMaterials(numFaces);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const int view = views[f];
+        outMaterials[f] = view >= 0 ? materialOfPhoto[view] : untextured;
+        for (int j = 0; j < 3; j++)
+        {
+            const unsigned int v = faces[3 * f + j];
+            const uint64_t key = static_cast<uint64_t>(v) * (numPhotos + 1) + (view >= 0 ? view : numPhotos);
+            auto it = vertexMap.find(key);
+            if (it == vertexMap.end())
+            {
+                it = vertexMap.emplace(key, outVertices.size() / 3).first;
+                const Vector3f position(vertices[3 * v], vertices[3 * v + 1], vertices[3 * v + 2]);
+                outVertices.insert(outVertices.end(), {position.x(), position.y(), position.z()});
+                if (normals)
+                {
+                    outNormals.insert(outNormals.end(), {normals[3 * v], normals[3 * v + 1], normals[3 * v + 2]});
+                }
+
+                Vector2f uv = Vector2f::Zero();
+                if (view >= 0)
+                {
+                    // No border, as the vertex might be close to the image border
+                    auto pixel = photos.project(view, position, -std::numeric_limits<float>::max());
+                    if (pixel)
+                    {
+                        const cv::Mat& image = photos[view].image;
+                        uv = Vector2f(pixel->x() / image.cols, pixel->y() / image.rows);
+                    }
+                }
+                outTexCoords.insert(outTexCoords.end(), {uv.x(), uv.y()});
+            }
+            outFaces[3 * f + j] = it->second;
+        }
+    }
+
+    MeshBufferPtr out(new MeshBuffer);
+    out->setVertices(Util::convert_vector_to_shared_array(outVertices), outVertices.size() / 3);
+    out->setFaceIndices(Util::convert_vector_to_shared_array(outFaces), numFaces);
+    if (normals)
+    {
+        out->setVertexNormals(Util::convert_vector_to_shared_array(outNormals));
+    }
+    out->setTextureCoordinates(Util::convert_vector_to_shared_array(outTexCoords));
+    out->setFaceMaterialIndices(Util::convert_vector_to_shared_array(outMaterials));
+    out->setMaterials(materials);
+    out->setTextures(textures);
+    return out;
+}
+
+MeshBufferPtr texturizeFromPhotos(MeshBufferPtr mesh, const PhotoSet& photos, const PhotoTexturingOptions& options)
+{
+    return texturizeFromPhotos(mesh, photos, selectViews(mesh, photos, options));
+}
+
+} // namespace lvr2
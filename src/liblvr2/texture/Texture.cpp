@@ -36,6 +36,7 @@
  */
 
 #include "lvr2/texture/Texture.hpp"
+#include "lvr2/texture/TextureFactory.hpp"
 #include "lvr2/display/GlTexture.hpp"
 
 #include <opencv2/core/mat.hpp>
@@ -214,21 +215,9 @@ Texture::Texture(
 
 void Texture::save(const std::string& file_name)
 {
-    if (!file_name.empty())
-    {
-        cv::Mat image(m_height, m_width, CV_8UC3, m_data);
-        cv::Mat bgr;
-        cv::cvtColor(image, bgr, cv::COLOR_RGB2BGR);
-        cv::imwrite(file_name, bgr);
-    }
-    else
-    {
-        //write image file
-        std::string name = "texture_" + std::to_string(m_index) + ".ppm";
-        PPMIO pio;
-        pio.setDataArray(this->m_data, m_width, m_height);
-        pio.write(name);
-    }
+    // TextureFactory handles grayscale and RGBA textures
+    const std::string name = file_name.empty() ? "texture_" + std::to_string(m_index) + ".ppm" : file_name;
+    TextureFactory::saveTexture(*this, name);
 }
 
 Texture::~Texture() {
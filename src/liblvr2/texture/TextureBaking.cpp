@@ -0,0 +1,290 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * TextureBaking.cpp
+ */
+
+#include "lvr2/texture/TextureBaking.hpp"
+#include "lvr2/algorithm/NormalAlgorithms.hpp"
+#include "lvr2/algorithm/raycasting/BVHRaycaster.hpp"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/PMPMesh.hpp"
+#include "lvr2/util/Logging.hpp"
+#include "lvr2/util/Timestamp.hpp"
+
+#include <opencv2/imgcodecs.hpp>
+
+#include <algorithm>
+#include <cmath>
+
+namespace lvr2
+{
+
+namespace
+{
+
+using BakeInt = Intersection<intelem::Point, intelem::Distance, intelem::Face>;
+
+Vector3f vertexAt(const floatArr& vertices, unsigned int i)
+{
+    return Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+}
+
+/// Barycentric weights of p with respect to the triangle (a, b, c)
+Vector3f barycentricWeights(const Vector3f& p, const Vector3f& a, const Vector3f& b, const Vector3f& c)
+{
+    Vector3f v0 = b - a;
+    Vector3f v1 = c - a;
+    Vector3f v2 = p - a;
+    float d00 = v0.dot(v0);
+    float d01 = v0.dot(v1);
+    float d11 = v1.dot(v1);
+    float d20 = v2.dot(v0);
+    float d21 = v2.dot(v1);
+    float denom = d00 * d11 - d01 * d01;
+    if (std::abs(denom) < 1e-20f)
+    {
+        return Vector3f(1, 0, 0);
+    }
+    float v = (d11 * d20 - d01 * d21) / denom;
+    float w = (d00 * d21 - d01 * d20) / denom;
+    return Vector3f(1.0f - v - w, v, w);
+}
+
+/// Extends the baked region by one pixel in each iteration
+void dilate(BakedMaps& maps, int iterations)
+{
+    for (int it = 0; it < iterations; it++)
+    {
+        cv::Mat mask = maps.mask.clone();
+        for (int y = 0; y < mask.rows; y++)
+        {
+            for (int x = 0; x < mask.cols; x++)
+            {
+                if (mask.at<uchar>(y, x))
+                {
+                    continue;
+                }
+                for (int k = 0; k < 4; k++)
+                {
+                    int nx = x + (k == 0) - (k == 1);
+                    int ny = y + (k == 2) - (k == 3);
+                    if (nx < 0 || ny < 0 || nx >= mask.cols || ny >= mask.rows || !mask.at<uchar>(ny, nx))
+                    {
+                        continue;
+                    }
+                    maps.normalMap.at<cv::Vec3b>(y, x) = maps.normalMap.at<cv::Vec3b>(ny, nx);
+                    maps.heightMap.at<float>(y, x) = maps.heightMap.at<float>(ny, nx);
+                    maps.mask.at<uchar>(y, x) = 255;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+} // anonymous namespace
+
+BakedMaps bakeMaps(MeshBufferPtr highRes, MeshBufferPtr lowRes, const BakingOptions& options)
+{
+    BakedMaps maps;
+    maps.normalMap = cv::Mat(options.height, options.width, CV_8UC3, cv::Scalar(255, 128, 128));
+    maps.heightMap = cv::Mat(options.height, options.width, CV_32FC1, cv::Scalar(0));
+    maps.mask = cv::Mat(options.height, options.width, CV_8UC1, cv::Scalar(0));
+
+    floatArr texCoords = lowRes->getTextureCoordinates();
+    if (!texCoords)
+    {
+        lvr2::logout::get() << lvr2::error << "[TextureBaking] Simplified mesh has no texture coordinates" << lvr2::endl;
+        return maps;
+    }
+
+    Timestamp ts;
+
+    // Low resolution geometry
+    const size_t numLowVertices = lowRes->numVertices();
+    const size_t numLowFaces = lowRes->numFaces();
+    floatArr lowVertices = lowRes->getVertices();
+    indexArray lowFaces = lowRes->getFaceIndices();
+    std::vector<Vector3f> lowNormals;
+    if (lowRes->hasVertexNormals())
+    {
+        floatArr n = lowRes->getVertexNormals();
+        for (size_t i = 0; i < numLowVertices; i++)
+        {
+            lowNormals.push_back(vertexAt(n, i).normalized());
+        }
+    }
+    else
+    {
+        // PMPMesh keeps the vertex order of the buffer
+        PMPMesh<BaseVector<float>> mesh(lowRes);
+        DenseVertexMap<Normal<float>> normals = calcVertexNormals(mesh, calcFaceNormals(mesh), VertexNormalWeighting::Area);
+        for (size_t i = 0; i < numLowVertices; i++)
+        {
+            const Normal<float>& n = normals[VertexHandle(i)];
+            lowNormals.emplace_back(n.x, n.y, n.z);
+        }
+    }
+
+    // High resolution geometry
+    floatArr highVertices = highRes->getVertices();
+    indexArray highFaces = highRes->getFaceIndices();
+    floatArr highNormals = highRes->hasVertexNormals() ? highRes->getVertexNormals() : floatArr();
+
+    // Rasterize the UV layout: the face and barycentric weights of each pixel
+    std::vector<long> pixelFace(options.width * options.height, -1);
+    std::vector<Vector3f> pixelWeights(options.width * options.height);
+    std::vector<Vector3f> faceTangents(numLowFaces, Vector3f::UnitX());
+    for (size_t f = 0; f < numLowFaces; f++)
+    {
+        const unsigned int* idx = &lowFaces[3 * f];
+        Vector2f uv[3];
+        Vector3f px[3];
+        for (int j = 0; j < 3; j++)
+        {
+            uv[j] = Vector2f(texCoords[2 * idx[j]], texCoords[2 * idx[j] + 1]);
+            // Image row 0 corresponds to v = 1
+            px[j] = Vector3f(uv[j].x() * options.width, (1.0f - uv[j].y()) * options.height, 0);
+        }
+
+        // Tangent along increasing u
+        Vector3f e1 = vertexAt(lowVertices, idx[1]) - vertexAt(lowVertices, idx[0]);
+        Vector3f e2 = vertexAt(lowVertices, idx[2]) - vertexAt(lowVertices, idx[0]);
+        Vector2f d1 = uv[1] - uv[0];
+        Vector2f d2 = uv[2] - uv[0];
+        float det = d1.x() * d2.y() - d2.x() * d1.y();
+        if (std::abs(det) > 1e-12f)
+        {
+            faceTangents[f] = ((e1 * d2.y() - e2 * d1.y()) / det).normalized();
+        }
+
+        int x0 = std::max(0, (int)std::floor(std::min({px[0].x(), px[1].x(), px[2].x()})));
+        int x1 = std::min(options.width - 1, (int)std::ceil(std::max({px[0].x(), px[1].x(), px[2].x()})));
+        int y0 = std::max(0, (int)std::floor(std::min({px[0].y(), px[1].y(), px[2].y()})));
+        int y1 = std::min(options.height - 1, (int)std::ceil(std::max({px[0].y(), px[1].y(), px[2].y()})));
+        for (int y = y0; y <= y1; y++)
+        {
+            for (int x = x0; x <= x1; x++)
+            {
+                Vector3f w = barycentricWeights(Vector3f(x + 0.5f, y + 0.5f, 0), px[0], px[1], px[2]);
+                if (w.minCoeff() < -1e-4f)
+                {
+                    continue;
+                }
+                pixelFace[y * options.width + x] = f;
+                pixelWeights[y * options.width + x] = w;
+            }
+        }
+    }
+
+    BVHRaycaster<BakeInt> raycaster(highRes);
+
+    #pragma omp parallel for schedule(dynamic)
+    for (int y = 0; y < options.height; y++)
+    {
+        for (int x = 0; x < options.width; x++)
+        {
+            const long f = pixelFace[y * options.width + x];
+            if (f < 0)
+            {
+                continue;
+            }
+            const unsigned int* idx = &lowFaces[3 * f];
+            const Vector3f& w = pixelWeights[y * options.width + x];
+
+            Vector3f p = w[0] * vertexAt(lowVertices, idx[0])
+                + w[1] * vertexAt(lowVertices, idx[1])
+                + w[2] * vertexAt(lowVertices, idx[2]);
+            Vector3f n = (w[0] * lowNormals[idx[0]] + w[1] * lowNormals[idx[1]] + w[2] * lowNormals[idx[2]]).normalized();
+
+            // Cast from the outer end of the search range towards the surface
+            BakeInt hit;
+            Vector3f origin = p + n * options.maxDistance;
+            if (!raycaster.castRay(origin, -n, hit) || hit.dist > 2 * options.maxDistance)
+            {
+                continue;
+            }
+
+            // Normal of the high resolution surface at the hit point
+            const unsigned int* hIdx = &highFaces[3 * hit.face_id];
+            Vector3f a = vertexAt(highVertices, hIdx[0]);
+            Vector3f b = vertexAt(highVertices, hIdx[1]);
+            Vector3f c = vertexAt(highVertices, hIdx[2]);
+            Vector3f hn;
+            if (highNormals)
+            {
+                Vector3f hw = barycentricWeights(hit.point, a, b, c);
+                hn = hw[0] * vertexAt(highNormals, hIdx[0]) + hw[1] * vertexAt(highNormals, hIdx[1]) + hw[2] * vertexAt(highNormals, hIdx[2]);
+            }
+            else
+            {
+                hn = (b - a).cross(c - a);
+            }
+            hn.normalize();
+
+            if (options.tangentSpace)
+            {
+                Vector3f t = faceTangents[f] - n * n.dot(faceTangents[f]);
+                if (t.squaredNorm() < 1e-12f)
+                {
+                    t = n.unitOrthogonal();
+                }
+                t.normalize();
+                Vector3f bt = n.cross(t);
+                hn = Vector3f(hn.dot(t), hn.dot(bt), hn.dot(n));
+            }
+
+            auto encode = [](float v) { return (uchar)std::round(std::min(1.0f, std::max(-1.0f, v)) * 127.5f + 127.5f); };
+            maps.normalMap.at<cv::Vec3b>(y, x) = cv::Vec3b(encode(hn.z()), encode(hn.y()), encode(hn.x()));
+            maps.heightMap.at<float>(y, x) = options.maxDistance - hit.dist;
+            maps.mask.at<uchar>(y, x) = 255;
+        }
+    }
+
+    dilate(maps, options.padding);
+
+    lvr2::logout::get() << lvr2::info << "[TextureBaking] Baked " << cv::countNonZero(maps.mask)
+        << " pixels in " << ts.getElapsedTimeInS() << "s" << lvr2::endl;
+
+    return maps;
+}
+
+void saveBakedMaps(const BakedMaps& maps, const std::string& prefix, float maxDistance)
+{
+    cv::imwrite(prefix + "_normal.png", maps.normalMap);
+    cv::imwrite(prefix + "_height.tif", maps.heightMap);
+
+    // Map [-maxDistance, maxDistance] to the full 16 bit range
+    cv::Mat height16;
+    maps.heightMap.convertTo(height16, CV_16UC1, 65535.0 / (2.0 * maxDistance), 32767.5);
+    cv::imwrite(prefix + "_height.png", height16);
+}
+
+} // namespace lvr2
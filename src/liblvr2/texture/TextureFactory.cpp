@@ -44,6 +44,8 @@
 #include <opencv2/imgcodecs.hpp>
 #include <opencv2/imgproc.hpp>
 
+#include <algorithm>
+#include <cctype>
 #include <iostream>
 using std::cout;
 using std::endl;
@@ -51,88 +53,139 @@ using std::endl;
 namespace lvr2
 {
 
-Texture TextureFactory::readTexture(std::string filename)
+namespace
 {
-    // returns 8-bit image with BGR order
-    cv::Mat mat = cv::imread(filename);
-
 
-    // if unable to read file
-    if (mat.data == NULL)
+/// Converts an image with 1, 3 or 4 channels and arbitrary depth to a texture
+Texture matToTexture(cv::Mat mat)
+{
+    if (mat.depth() != CV_8U)
     {
-        cout << timestamp << "TextureFactory: Unable to read file '"
-            << filename << "'. Returning empty Texture." << endl;
+        // e.g. 16 bit PNGs
+        double scale = mat.depth() == CV_16U ? 1.0 / 257.0 : 1.0;
+        mat.convertTo(mat, CV_8U, scale);
+    }
 
-        // return empty Texture
+    // convert it to RGB(A) order
+    if (mat.channels() == 3)
+    {
+        cv::cvtColor(mat, mat, cv::COLOR_BGR2RGB);
+    }
+    else if (mat.channels() == 4)
+    {
+        cv::cvtColor(mat, mat, cv::COLOR_BGRA2RGBA);
+    }
+    else if (mat.channels() != 1)
+    {
         return Texture();
     }
 
-    // convert it to RGB order
-    cv::cvtColor(mat, mat, cv::COLOR_BGR2RGB);
-
-    Texture ret(0, mat.cols, mat.rows, 3, 1, 1.0);
+    Texture ret(0, mat.cols, mat.rows, mat.channels(), 1, 1.0);
     std::copy(mat.datastart, mat.dataend, ret.m_data);
 
     return ret;
 }
 
-void TextureFactory::saveTexture(const Texture& tex, std::string filename)
+/// Converts the texture to an 8 bit BGR(A) or grayscale image
+bool textureToMat(const Texture& tex, cv::Mat& mat, bool keepAlpha, const std::string& name)
 {
     // if Texture has no data to be saved
     if (tex.m_data == NULL || tex.m_width == 0 || tex.m_height == 0 ||
         tex.m_numChannels == 0 || tex.m_numBytesPerChan == 0)
     {
-        cout << timestamp << "TextureFactory: Texture will not be saved to file '"
-            << filename << "' because the texture has no data." << endl;
+        cout << timestamp << "TextureFactory: Texture will not be saved to '"
+            << name << "' because the texture has no data." << endl;
 
-        return;
+        return false;
     }
 
     // TODO convert the data instead of only allowing 1 byte channels
     if (tex.m_numBytesPerChan != 1)
     {
-        cout << timestamp << "TextureFactory: Texture will not be saved to file '"
-            << filename << "' because texture has more than 1 byte \
+        cout << timestamp << "TextureFactory: Texture will not be saved to '"
+            << name << "' because texture has more than 1 byte \
             per channel (currently only 1-byte channels are supported)." << endl;
 
-        return;
+        return false;
     }
 
     if (tex.m_numChannels != 1 && tex.m_numChannels != 3 && tex.m_numChannels != 4)
     {
-        cout << timestamp << "TextureFactory: Texture will not be saved to file '"
-            << filename << "' because the texture has an unsupported amount of channels \
+        cout << timestamp << "TextureFactory: Texture will not be saved to '"
+            << name << "' because the texture has an unsupported amount of channels \
             (currently only 1, 3 and 4 channels per pixel are supported)." << endl;
 
-        return;
+        return false;
     }
 
-    // convert texture data to be saved with OpenCV
-    int mat_type = CV_8UC1;
-    int conversionCode = cv::COLOR_GRAY2BGR;
+    const int type = CV_MAKETYPE(CV_8U, tex.m_numChannels);
+    cv::Mat rgb(tex.m_height, tex.m_width, type, tex.m_data);
 
     if (tex.m_numChannels == 3)
     {
-        mat_type = CV_8UC3;
-        conversionCode = cv::COLOR_RGB2BGR;
+        cv::cvtColor(rgb, mat, cv::COLOR_RGB2BGR);
     }
     else if (tex.m_numChannels == 4)
     {
-        // currently we just ignore the alpha channel but it would be
-        // possible to save the alpha channel with OpenCV for PNG images.
-        mat_type = CV_8UC3;
-        conversionCode = cv::COLOR_RGBA2BGR;
+        cv::cvtColor(rgb, mat, keepAlpha ? cv::COLOR_RGBA2BGRA : cv::COLOR_RGBA2BGR);
+    }
+    else
+    {
+        mat = rgb.clone();
     }
+    return true;
+}
 
-    cv::Mat mat;
-    mat.create(tex.m_height, tex.m_width, mat_type);
+std::string lowerExtension(const std::string& filename)
+{
+    const size_t dot = filename.find_last_of('.');
+    std::string ext = dot == std::string::npos ? "." + filename : filename.substr(dot);
+    std::transform(ext.begin(), ext.end(), ext.begin(), ::tolower);
+    return ext;
+}
+
+std::vector<int> encodeParams(const std::string& ext, int quality)
+{
+    std::vector<int> params;
+    if (quality > 0 && (ext == ".jpg" || ext == ".jpeg"))
+    {
+        params = {cv::IMWRITE_JPEG_QUALITY, quality};
+    }
+    else if (quality > 0 && ext == ".webp")
+    {
+        params = {cv::IMWRITE_WEBP_QUALITY, quality};
+    }
+    return params;
+}
+
+} // anonymous namespace
+
+Texture TextureFactory::readTexture(std::string filename)
+{
+    // keep alpha channels and 16 bit data, BGR(A) order
+    cv::Mat mat = cv::imread(filename, cv::IMREAD_UNCHANGED);
+
+    // if unable to read file
+    if (mat.data == NULL)
+    {
+        cout << timestamp << "TextureFactory: Unable to read file '"
+            << filename << "'. Returning empty Texture." << endl;
+
+        // return empty Texture
+        return Texture();
+    }
 
-    size_t bytesToCopy = tex.m_width * tex.m_height * tex.m_numChannels * tex.m_numBytesPerChan;
-    std::copy(tex.m_data, tex.m_data + bytesToCopy, mat.data);
+    return matToTexture(mat);
+}
+
+void TextureFactory::saveTexture(const Texture& tex, std::string filename)
+{
+    const std::string ext = lowerExtension(filename);
 
-    if (!(mat_type == CV_8UC1))
+    cv::Mat mat;
+    if (!textureToMat(tex, mat, ext == ".png" || ext == ".webp", filename))
     {
-        cv::cvtColor(mat, mat, conversionCode);
+        return;
     }
 
     // todo include params like binary mode for ppm files for example...
@@ -143,4 +196,89 @@ void TextureFactory::saveTexture(const Texture& tex, std::string filename)
     };
 }
 
+bool TextureFactory::encodeTexture(const Texture& tex, const std::string& extension,
+                                   std::vector<uint8_t>& output, int quality)
+{
+    const std::string ext = lowerExtension(extension);
+
+    cv::Mat mat;
+    if (!textureToMat(tex, mat, ext == ".png" || ext == ".webp", "memory (" + ext + ")"))
+    {
+        return false;
+    }
+
+    output.clear();
+    if (!cv::imencode(ext, mat, output, encodeParams(ext, quality)))
+    {
+        cout << timestamp << "TextureFactory: Unable to encode texture as '"
+            << ext << "'." << endl;
+        return false;
+    }
+    return true;
+}
+
+Texture TextureFactory::decodeTexture(const std::vector<uint8_t>& data)
+{
+    cv::Mat mat = cv::imdecode(data, cv::IMREAD_UNCHANGED);
+    if (mat.data == NULL)
+    {
+        cout << timestamp << "TextureFactory: Unable to decode image data. "
+            << "Returning empty Texture." << endl;
+        return Texture();
+    }
+    return matToTexture(mat);
+}
+
+Texture TextureFactory::fromMat(const cv::Mat& image)
+{
+    // matToTexture converts the channel order in place
+    return matToTexture(image.clone());
+}
+
+Texture TextureFactory::toRGB(const Texture& texture)
+{
+    if (texture.m_data == nullptr || (texture.m_numChannels == 3 && texture.m_numBytesPerChan == 1))
+    {
+        return texture;
+    }
+
+    Texture ret(texture.m_index, texture.m_width, texture.m_height, 3, 1,
+                texture.m_texelSize, nullptr, texture.m_layerName);
+    const size_t numPixels = (size_t)texture.m_width * texture.m_height;
+    const size_t stride = texture.m_numChannels * texture.m_numBytesPerChan;
+    for (size_t i = 0; i < numPixels; i++)
+    {
+        const unsigned char* src = texture.m_data + i * stride;
+        for (size_t c = 0; c < 3; c++)
+        {
+            // Grayscale (with or without alpha) is replicated
+            const size_t channel = texture.m_numChannels >= 3 ? c : 0;
+            ret.m_data[i * 3 + c] = src[channel * texture.m_numBytesPerChan];
+        }
+    }
+    return ret;
+}
+
+std::string TextureFactory::mimeType(const std::string& extension)
+{
+    const std::string ext = lowerExtension(extension);
+    if (ext == ".png")
+    {
+        return "image/png";
+    }
+    if (ext == ".jpg" || ext == ".jpeg")
+    {
+        return "image/jpeg";
+    }
+    if (ext == ".webp")
+    {
+        return "image/webp";
+    }
+    if (ext == ".ppm")
+    {
+        return "image/x-portable-pixmap";
+    }
+    return "application/octet-stream";
+}
+
 } // namespace lvr2
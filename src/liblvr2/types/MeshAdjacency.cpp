@@ -0,0 +1,222 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+
+/**
+ * MeshAdjacency.cpp
+ */
+
+#include "lvr2/types/MeshAdjacency.hpp"
+
+#include <algorithm>
+#include <array>
+#include <iterator>
+
+namespace lvr2
+{
+
+MeshAdjacency::MeshAdjacency(const unsigned int* faces, size_t numFaces, size_t numVertices)
+{
+    auto valid = [&](size_t f)
+    {
+        return faces[3 * f] < numVertices
+            && faces[3 * f + 1] < numVertices
+            && faces[3 * f + 2] < numVertices;
+    };
+
+    // Faces of each vertex
+    m_vertexFaceOffsets.assign(numVertices + 1, 0);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        if (valid(f))
+        {
+            for (int i = 0; i < 3; i++)
+            {
+                m_vertexFaceOffsets[faces[3 * f + i] + 1]++;
+            }
+        }
+    }
+    for (size_t v = 0; v < numVertices; v++)
+    {
+        m_vertexFaceOffsets[v + 1] += m_vertexFaceOffsets[v];
+    }
+
+    m_vertexFaces.resize(m_vertexFaceOffsets[numVertices]);
+    std::vector<size_t> fill(m_vertexFaceOffsets.begin(), m_vertexFaceOffsets.end() - 1);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        if (valid(f))
+        {
+            for (int i = 0; i < 3; i++)
+            {
+                m_vertexFaces[fill[faces[3 * f + i]]++] = f;
+            }
+        }
+    }
+
+    // Neighbors of each vertex, gathered from its faces
+    m_vertexNeighborOffsets.reserve(numVertices + 1);
+    m_vertexNeighborOffsets.push_back(0);
+    m_vertexNeighbors.reserve(m_vertexFaces.size() * 2);
+    for (size_t v = 0; v < numVertices; v++)
+    {
+        const size_t start = m_vertexNeighbors.size();
+        for (unsigned int f : vertexFaces(v))
+        {
+            for (int i = 0; i < 3; i++)
+            {
+                if (faces[3 * f + i] != v)
+                {
+                    m_vertexNeighbors.push_back(faces[3 * f + i]);
+                }
+            }
+        }
+        std::sort(m_vertexNeighbors.begin() + start, m_vertexNeighbors.end());
+        m_vertexNeighbors.erase(
+            std::unique(m_vertexNeighbors.begin() + start, m_vertexNeighbors.end()),
+            m_vertexNeighbors.end());
+        m_vertexNeighborOffsets.push_back(m_vertexNeighbors.size());
+    }
+
+    // Faces that share an edge. Sorting the edges groups all faces of an edge.
+    std::vector<std::array<unsigned int, 3>> edges;
+    edges.reserve(numFaces * 3);
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        if (valid(f))
+        {
+            for (int i = 0; i < 3; i++)
+            {
+                unsigned int a = faces[3 * f + i];
+                unsigned int b = faces[3 * f + (i + 1) % 3];
+                if (a != b)
+                {
+                    edges.push_back({std::min(a, b), std::max(a, b), static_cast<unsigned int>(f)});
+                }
+            }
+        }
+    }
+    std::sort(edges.begin(), edges.end());
+
+    std::vector<std::vector<unsigned int>> neighbors(numFaces);
+    for (size_t i = 0; i < edges.size();)
+    {
+        size_t j = i;
+        while (j < edges.size() && edges[j][0] == edges[i][0] && edges[j][1] == edges[i][1])
+        {
+            j++;
+        }
+
+        // A face can contain an edge twice if it is degenerate
+        MeshEdge edge;
+        edge.v1 = edges[i][0];
+        edge.v2 = edges[i][1];
+        edge.numFaces = 0;
+        edge.faces[0] = edge.faces[1] = MeshEdge::NoFace;
+        for (size_t a = i; a < j; a++)
+        {
+            if (a > i && edges[a][2] == edges[a - 1][2])
+            {
+                continue;
+            }
+            if (edge.numFaces < 2)
+            {
+                edge.faces[edge.numFaces] = edges[a][2];
+            }
+            edge.numFaces++;
+        }
+        m_edges.push_back(edge);
+
+        for (size_t a = i; a < j; a++)
+        {
+            for (size_t b = i; b < j; b++)
+            {
+                if (edges[a][2] != edges[b][2])
+                {
+                    neighbors[edges[a][2]].push_back(edges[b][2]);
+                }
+            }
+        }
+        i = j;
+    }
+
+    m_faceNeighborOffsets.reserve(numFaces + 1);
+    m_faceNeighborOffsets.push_back(0);
+    for (auto& n : neighbors)
+    {
+        std::sort(n.begin(), n.end());
+        n.erase(std::unique(n.begin(), n.end()), n.end());
+        m_faceNeighbors.insert(m_faceNeighbors.end(), n.begin(), n.end());
+        m_faceNeighborOffsets.push_back(m_faceNeighbors.size());
+    }
+}
+
+std::vector<unsigned int> MeshAdjacency::edgeFaces(size_t v1, size_t v2) const
+{
+    IndexRange faces1 = vertexFaces(v1);
+    IndexRange faces2 = vertexFaces(v2);
+    std::vector<unsigned int> shared;
+    std::set_intersection(faces1.begin(), faces1.end(), faces2.begin(), faces2.end(), std::back_inserter(shared));
+    return shared;
+}
+
+std::vector<MeshEdge> MeshAdjacency::boundaryEdges() const
+{
+    std::vector<MeshEdge> boundary;
+    for (const MeshEdge& e : m_edges)
+    {
+        if (e.isBoundary())
+        {
+            boundary.push_back(e);
+        }
+    }
+    return boundary;
+}
+
+bool MeshAdjacency::isBoundaryEdge(size_t v1, size_t v2) const
+{
+    return edgeFaces(v1, v2).size() == 1;
+}
+
+bool MeshAdjacency::isBoundaryVertex(size_t vertex) const
+{
+    if (vertexFaces(vertex).empty())
+    {
+        return true;
+    }
+    for (unsigned int n : vertexNeighbors(vertex))
+    {
+        if (isBoundaryEdge(vertex, n))
+        {
+            return true;
+        }
+    }
+    return false;
+}
+
+} // namespace lvr2
@@ -28,6 +28,8 @@
 #include "lvr2/types/MeshBuffer.hpp"
 #include "lvr2/util/Timestamp.hpp"
 
+#include <algorithm>
+#include <cmath>
 #include <iostream>
 using std::cout;
 using std::endl;
@@ -43,10 +45,15 @@ MeshBuffer::MeshBuffer()
 
 void MeshBuffer::setVertices(floatArr vertices, size_t n)
 {
+    if(n > MaxMeshBufferVertices)
+    {
+        throw IndexOverflowError(n);
+    }
     if(n)
     {
         this->addFloatChannel(vertices, "vertices", n, 3);
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setVertexNormals(floatArr normals)
@@ -60,6 +67,7 @@ void MeshBuffer::setVertexNormals(floatArr normals)
         cout << "MeshBuffer::setVertexNormals(): "
              << "Cannot add vertex normals without vertex definitions" << endl;
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setVertexColors(ucharArr colors, size_t w)
@@ -73,6 +81,7 @@ void MeshBuffer::setVertexColors(ucharArr colors, size_t w)
         cout << "MeshBuffer::setVertexColors(): "
              << "Cannot add vertex colors without vertex definitions" << endl;
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setTextureCoordinates(floatArr coordinates)
@@ -86,6 +95,7 @@ void MeshBuffer::setTextureCoordinates(floatArr coordinates)
         cout << "MeshBuffer::setTextureCoordinates(): "
              << "Cannot add vertex colors without vertex definitions" << endl;
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setFaceIndices(indexArray indices, size_t n)
@@ -94,6 +104,7 @@ void MeshBuffer::setFaceIndices(indexArray indices, size_t n)
     {
         this->addIndexChannel(indices, "face_indices", n, 3);
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setFaceMaterialIndices(indexArray indices)
@@ -107,6 +118,7 @@ void MeshBuffer::setFaceMaterialIndices(indexArray indices)
         cout << "MeshBuffer::setFaceMaterialIndices(): "
              << "Cannot add material indices without face definitions" << endl;
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setFaceNormals(floatArr normals)
@@ -120,6 +132,7 @@ void MeshBuffer::setFaceNormals(floatArr normals)
         cout << "MeshBuffer::setFaceMaterialIndices(): "
              << "Cannot add material indices without face definitions" << endl;
     }
+    invalidateAdjacency();
 }
 
 void MeshBuffer::setFaceColors(ucharArr colors, size_t w)
@@ -133,6 +146,7 @@ void MeshBuffer::setFaceColors(ucharArr colors, size_t w)
         cout << "MeshBuffer::setFaceColors(): "
              << "Cannot add face colors without face definitions" << endl;
     }
+    invalidateAdjacency();
 }
 
 size_t MeshBuffer::numVertices() const
@@ -182,6 +196,142 @@ floatArr MeshBuffer::getVertexNormals()
     return this->getFloatArray("vertex_normals", n, w);
 }
 
+MeshAdjacencyPtr MeshBuffer::getAdjacency()
+{
+    indexArray faces = getFaceIndices();
+    if(!faces)
+    {
+        return MeshAdjacencyPtr();
+    }
+
+    // Channels can also be replaced directly, so the cache is checked against
+    // the current face array
+    if(!m_adjacency || faces.get() != m_adjacencyFaces
+        || numFaces() != m_adjacencyNumFaces || numVertices() != m_adjacencyNumVertices)
+    {
+        m_adjacency = std::make_shared<MeshAdjacency>(faces.get(), numFaces(), numVertices());
+        m_adjacencyFaces = faces.get();
+        m_adjacencyNumFaces = numFaces();
+        m_adjacencyNumVertices = numVertices();
+    }
+    return m_adjacency;
+}
+
+MeshEdgeRange MeshBuffer::edges()
+{
+    return MeshEdgeRange(getAdjacency());
+}
+
+std::vector<MeshEdge> MeshBuffer::boundaryEdges()
+{
+    MeshAdjacencyPtr adjacency = getAdjacency();
+    return adjacency ? adjacency->boundaryEdges() : std::vector<MeshEdge>();
+}
+
+void MeshBuffer::invalidateAdjacency()
+{
+    m_adjacency.reset();
+    m_adjacencyFaces = nullptr;
+}
+
+boost::optional<Normal<float>> MeshBuffer::getVertexNormal(size_t i) const
+{
+    const FloatChannelOptional normals = this->getChannel<float>("vertex_normals");
+    if(!normals || i >= normals->numElements())
+    {
+        return boost::none;
+    }
+
+    BaseVector<float> n((*normals)[i][0], (*normals)[i][1], (*normals)[i][2]);
+    const float length = n.length();
+    if(!std::isfinite(length) || length == 0)
+    {
+        return boost::none;
+    }
+    return Normal<float>(n);
+}
+
+bool MeshBuffer::setVertexNormal(size_t i, const BaseVector<float>& normal)
+{
+    FloatChannelOptional normals = this->getChannel<float>("vertex_normals");
+    const float length = normal.length();
+    if(!normals || i >= normals->numElements() || !std::isfinite(length) || length == 0)
+    {
+        return false;
+    }
+
+    Normal<float> n(normal);
+    (*normals)[i][0] = n.getX();
+    (*normals)[i][1] = n.getY();
+    (*normals)[i][2] = n.getZ();
+    return true;
+}
+
+size_t MeshBuffer::normalizeNormals()
+{
+    size_t invalid = 0;
+    for(const char* name : {"vertex_normals", "face_normals"})
+    {
+        FloatChannelOptional normals = this->getChannel<float>(name);
+        if(normals && normals->width() == 3)
+        {
+            invalid += normalizeArray(normals->dataPtr().get(), normals->numElements());
+        }
+    }
+    return invalid;
+}
+
+bool MeshBuffer::computeVertexNormals()
+{
+    MeshAdjacencyPtr adjacency = getAdjacency();
+    if(!adjacency)
+    {
+        return false;
+    }
+
+    const size_t n = numVertices();
+    floatArr vertices = getVertices();
+    indexArray faces = getFaceIndices();
+    auto vertex = [&vertices](unsigned int i)
+    {
+        return BaseVector<float>(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+    };
+
+    floatArr normals(new float[3 * n]);
+    #pragma omp parallel for
+    for(size_t v = 0; v < n; v++)
+    {
+        BaseVector<float> sum(0, 0, 0);
+        for(unsigned int f : adjacency->vertexFaces(v))
+        {
+            const unsigned int* face = faces.get() + 3 * f;
+            const int k = face[0] == v ? 0 : (face[1] == v ? 1 : 2);
+            const BaseVector<float> p = vertex(face[k]);
+            const BaseVector<float> e1 = vertex(face[(k + 1) % 3]) - p;
+            const BaseVector<float> e2 = vertex(face[(k + 2) % 3]) - p;
+            BaseVector<float> normal = e1.cross(e2);
+            const float length = normal.length();
+            const float lengths = e1.length() * e2.length();
+            if(length == 0 || lengths == 0)
+            {
+                continue;
+            }
+
+            // Weight each face with its angle at the vertex
+            const float angle = std::acos(std::max(-1.0f, std::min(1.0f, e1.dot(e2) / lengths)));
+            sum += normal * (angle / length);
+        }
+        sum.normalize();
+        normals[3 * v]     = sum.x;
+        normals[3 * v + 1] = sum.y;
+        normals[3 * v + 2] = sum.z;
+    }
+
+    // Replacing the normals does not change the topology, so keep the cached adjacency
+    this->addFloatChannel(normals, "vertex_normals", n, 3);
+    return true;
+}
+
 floatArr MeshBuffer::getFaceNormals()
 {
     size_t n;
@@ -227,6 +377,31 @@ std::vector<Material>& MeshBuffer::getMaterials()
     return m_materials;
 }
 
+boost::optional<Material&> MeshBuffer::getFaceMaterial(size_t faceIndex)
+{
+    IndexChannelOptional indices = this->getIndexChannel("face_material_indices");
+    if(!indices || faceIndex >= indices->numElements())
+    {
+        return boost::none;
+    }
+
+    const unsigned int materialIndex = (*indices)[faceIndex][0];
+    if(materialIndex >= m_materials.size())
+    {
+        return boost::none;
+    }
+    return m_materials[materialIndex];
+}
+
+boost::optional<Texture&> MeshBuffer::getMaterialTexture(const Material& material)
+{
+    if(!material.m_texture || material.m_texture->idx() >= m_textures.size())
+    {
+        return boost::none;
+    }
+    return m_textures[material.m_texture->idx()];
+}
+
 bool MeshBuffer::hasVertices() const 
 {
     const FloatChannelOptional channel = this->getChannel<float>("vertices");
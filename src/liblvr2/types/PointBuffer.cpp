@@ -26,8 +26,10 @@
  */
 
 #include "lvr2/types/PointBuffer.hpp"
+#include "lvr2/algorithm/BaseBufferManipulators.hpp"
 #include "lvr2/util/Timestamp.hpp"
 
+#include <cmath>
 #include <iostream>
 
 namespace lvr2
@@ -104,6 +106,49 @@ ucharArr PointBuffer::getColorArray(size_t& w)
 }
 
 
+boost::optional<Normal<float>> PointBuffer::getNormal(size_t i) const
+{
+    const FloatChannelOptional normals = getChannel<float>("normals");
+    if(!normals || i >= normals->numElements())
+    {
+        return boost::none;
+    }
+
+    BaseVector<float> n((*normals)[i][0], (*normals)[i][1], (*normals)[i][2]);
+    const float length = n.length();
+    if(!std::isfinite(length) || length == 0)
+    {
+        return boost::none;
+    }
+    return Normal<float>(n);
+}
+
+bool PointBuffer::setNormal(size_t i, const BaseVector<float>& normal)
+{
+    FloatChannelOptional normals = getChannel<float>("normals");
+    const float length = normal.length();
+    if(!normals || i >= normals->numElements() || !std::isfinite(length) || length == 0)
+    {
+        return false;
+    }
+
+    Normal<float> n(normal);
+    (*normals)[i][0] = n.getX();
+    (*normals)[i][1] = n.getY();
+    (*normals)[i][2] = n.getZ();
+    return true;
+}
+
+size_t PointBuffer::normalizeNormals()
+{
+    FloatChannelOptional normals = getChannel<float>("normals");
+    if(!normals || normals->width() != 3)
+    {
+        return 0;
+    }
+    return normalizeArray(normals->dataPtr().get(), normals->numElements());
+}
+
 bool PointBuffer::hasColors() const
 {
    return hasChannel<unsigned char>("colors");
@@ -142,6 +187,43 @@ PointBuffer PointBuffer::clone() const
 
 }
 
+PointBuffer PointBuffer::select(const std::vector<size_t>& indices) const
+{
+    PointBuffer pb;
+    const size_t n = numPoints();
+    manipulators::Select visitor(indices, n);
+
+    for(const auto& elem : *this)
+    {
+        pb.insert({elem.first, boost::apply_visitor(visitor, elem.second)});
+    }
+
+    return pb;
+}
+
+bool PointBuffer::reorder(const std::vector<size_t>& indices)
+{
+    const size_t n = numPoints();
+    if(indices.size() != n)
+    {
+        return false;
+    }
+
+    std::vector<bool> used(n, false);
+    for(size_t index : indices)
+    {
+        if(index >= n || used[index])
+        {
+            return false;
+        }
+        used[index] = true;
+    }
+
+    PointBuffer tmp = select(indices);
+    this->swap(tmp);
+    return true;
+}
+
 
 
 }
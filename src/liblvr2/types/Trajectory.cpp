@@ -0,0 +1,96 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Trajectory.cpp
+ */
+
+#include "lvr2/types/Trajectory.hpp"
+
+#include <Eigen/Geometry>
+
+#include <algorithm>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+Trajectory::Trajectory(std::vector<TimedPose> poses)
+    : m_poses(std::move(poses))
+{
+    std::stable_sort(m_poses.begin(), m_poses.end(),
+        [](const TimedPose& a, const TimedPose& b) { return a.time < b.time; });
+}
+
+void Trajectory::addPose(double time, const Transformd& pose)
+{
+    auto pos = std::upper_bound(m_poses.begin(), m_poses.end(), time,
+        [](double t, const TimedPose& p) { return t < p.time; });
+    TimedPose p;
+    p.time = time;
+    p.pose = pose;
+    m_poses.insert(pos, p);
+}
+
+Transformd Trajectory::interpolate(double time) const
+{
+    if(m_poses.empty())
+    {
+        throw std::out_of_range("[Trajectory] Interpolation in an empty trajectory");
+    }
+
+    auto next = std::lower_bound(m_poses.begin(), m_poses.end(), time,
+        [](const TimedPose& p, double t) { return p.time < t; });
+    if(next == m_poses.begin())
+    {
+        return m_poses.front().pose;
+    }
+    if(next == m_poses.end())
+    {
+        return m_poses.back().pose;
+    }
+
+    const TimedPose& prev = *(next - 1);
+    const double dt = next->time - prev.time;
+    const double t = dt > 0 ? (time - prev.time) / dt : 0.0;
+
+    const Eigen::Quaterniond q0(prev.pose.block<3, 3>(0, 0));
+    const Eigen::Quaterniond q1(next->pose.block<3, 3>(0, 0));
+
+    Transformd pose = Transformd::Identity();
+    pose.block<3, 3>(0, 0) = q0.slerp(t, q1).toRotationMatrix();
+    pose.block<3, 1>(0, 3) = (1 - t) * prev.pose.block<3, 1>(0, 3) + t * next->pose.block<3, 1>(0, 3);
+    return pose;
+}
+
+bool Trajectory::covers(double time) const
+{
+    return !m_poses.empty() && time >= startTime() && time <= endTime();
+}
+
+} // namespace lvr2
@@ -0,0 +1,255 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * AxisConventions.cpp
+ */
+
+#include "lvr2/util/AxisConventions.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <stdexcept>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Length of a unit in metres
+double unitLength(LengthUnit unit)
+{
+    switch (unit)
+    {
+    case LengthUnit::Millimetres:
+        return 0.001;
+    case LengthUnit::Centimetres:
+        return 0.01;
+    case LengthUnit::Inches:
+        return 0.0254;
+    case LengthUnit::Feet:
+        return 0.3048;
+    case LengthUnit::Metres:
+    default:
+        return 1.0;
+    }
+}
+
+void rotate(float* data, size_t n, const Eigen::Matrix3d& rotation)
+{
+    for (size_t i = 0; i < n; i++)
+    {
+        Vector3d v = rotation * Vector3d(data[3 * i], data[3 * i + 1], data[3 * i + 2]);
+        data[3 * i] = v.x();
+        data[3 * i + 1] = v.y();
+        data[3 * i + 2] = v.z();
+    }
+}
+
+void scale(float* data, size_t n, double factor)
+{
+    for (size_t i = 0; i < 3 * n; i++)
+    {
+        data[i] *= factor;
+    }
+}
+
+} // anonymous namespace
+
+LengthUnit parseLengthUnit(const std::string& unit)
+{
+    if (unit == "mm")
+    {
+        return LengthUnit::Millimetres;
+    }
+    if (unit == "cm")
+    {
+        return LengthUnit::Centimetres;
+    }
+    if (unit == "m")
+    {
+        return LengthUnit::Metres;
+    }
+    if (unit == "in")
+    {
+        return LengthUnit::Inches;
+    }
+    if (unit == "ft")
+    {
+        return LengthUnit::Feet;
+    }
+    throw std::invalid_argument("Unknown length unit '" + unit + "'");
+}
+
+double unitScale(LengthUnit from, LengthUnit to)
+{
+    return unitLength(from) / unitLength(to);
+}
+
+Transformd upAxisTransform(UpAxis from, UpAxis to)
+{
+    Transformd transform = Transformd::Identity();
+    if (from == UpAxis::Y && to == UpAxis::Z)
+    {
+        // Rotation by +90 degrees around x
+        transform.block<3, 3>(0, 0) << 1, 0, 0,
+                                       0, 0, -1,
+                                       0, 1, 0;
+    }
+    else if (from == UpAxis::Z && to == UpAxis::Y)
+    {
+        transform.block<3, 3>(0, 0) << 1, 0, 0,
+                                       0, 0, 1,
+                                       0, -1, 0;
+    }
+    return transform;
+}
+
+void convertUpAxis(PointBufferPtr buffer, UpAxis from, UpAxis to)
+{
+    if (!buffer || from == to)
+    {
+        return;
+    }
+    const Eigen::Matrix3d rotation = upAxisTransform(from, to).block<3, 3>(0, 0);
+    rotate(buffer->getPointArray().get(), buffer->numPoints(), rotation);
+    if (buffer->hasNormals())
+    {
+        rotate(buffer->getNormalArray().get(), buffer->numPoints(), rotation);
+    }
+}
+
+void convertUpAxis(MeshBufferPtr mesh, UpAxis from, UpAxis to)
+{
+    if (!mesh || from == to)
+    {
+        return;
+    }
+    const Eigen::Matrix3d rotation = upAxisTransform(from, to).block<3, 3>(0, 0);
+    rotate(mesh->getVertices().get(), mesh->numVertices(), rotation);
+    if (mesh->hasVertexNormals())
+    {
+        rotate(mesh->getVertexNormals().get(), mesh->numVertices(), rotation);
+    }
+    if (mesh->hasFaceNormals())
+    {
+        rotate(mesh->getFaceNormals().get(), mesh->numFaces(), rotation);
+    }
+}
+
+void convertUnits(PointBufferPtr buffer, LengthUnit from, LengthUnit to)
+{
+    if (!buffer || from == to)
+    {
+        return;
+    }
+    scale(buffer->getPointArray().get(), buffer->numPoints(), unitScale(from, to));
+}
+
+void convertUnits(MeshBufferPtr mesh, LengthUnit from, LengthUnit to)
+{
+    if (!mesh || from == to)
+    {
+        return;
+    }
+    scale(mesh->getVertices().get(), mesh->numVertices(), unitScale(from, to));
+}
+
+UpAxis detectUpAxis(const floatArr& points, size_t n, const floatArr& normals)
+{
+    if (normals)
+    {
+        // Count normals that are within ~25 degrees of each axis
+        size_t alignedY = 0;
+        size_t alignedZ = 0;
+        for (size_t i = 0; i < n; i++)
+        {
+            Vector3d normal(normals[3 * i], normals[3 * i + 1], normals[3 * i + 2]);
+            double length = normal.norm();
+            if (!std::isfinite(length) || length == 0)
+            {
+                continue;
+            }
+            alignedY += std::abs(normal.y()) > 0.9 * length;
+            alignedZ += std::abs(normal.z()) > 0.9 * length;
+        }
+        if (alignedY != alignedZ)
+        {
+            return alignedY > alignedZ ? UpAxis::Y : UpAxis::Z;
+        }
+    }
+
+    float minY = std::numeric_limits<float>::max();
+    float maxY = std::numeric_limits<float>::lowest();
+    float minZ = std::numeric_limits<float>::max();
+    float maxZ = std::numeric_limits<float>::lowest();
+    for (size_t i = 0; i < n; i++)
+    {
+        minY = std::min(minY, points[3 * i + 1]);
+        maxY = std::max(maxY, points[3 * i + 1]);
+        minZ = std::min(minZ, points[3 * i + 2]);
+        maxZ = std::max(maxZ, points[3 * i + 2]);
+    }
+    return (maxY - minY) < (maxZ - minZ) ? UpAxis::Y : UpAxis::Z;
+}
+
+UpAxis detectUpAxis(PointBufferPtr buffer)
+{
+    return detectUpAxis(
+        buffer->getPointArray(),
+        buffer->numPoints(),
+        buffer->hasNormals() ? buffer->getNormalArray() : floatArr()
+    );
+}
+
+UpAxis detectUpAxis(MeshBufferPtr mesh)
+{
+    return detectUpAxis(
+        mesh->getVertices(),
+        mesh->numVertices(),
+        mesh->hasVertexNormals() ? mesh->getVertexNormals() : floatArr()
+    );
+}
+
+LengthUnit detectLengthUnit(const floatArr& points, size_t n)
+{
+    Vector3d min = Vector3d::Constant(std::numeric_limits<double>::max());
+    Vector3d max = Vector3d::Constant(std::numeric_limits<double>::lowest());
+    for (size_t i = 0; i < n; i++)
+    {
+        Vector3d p(points[3 * i], points[3 * i + 1], points[3 * i + 2]);
+        min = min.cwiseMin(p);
+        max = max.cwiseMax(p);
+    }
+    return n > 0 && (max - min).norm() > 2000 ? LengthUnit::Millimetres : LengthUnit::Metres;
+}
+
+} // namespace lvr2
@@ -0,0 +1,319 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * ColorSpace.cpp
+ */
+
+#include "lvr2/util/ColorSpace.hpp"
+
+#include <algorithm>
+#include <cmath>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Color data of a buffer channel
+struct ColorView
+{
+    unsigned char* data = nullptr;
+    size_t n = 0;
+    size_t width = 0;
+};
+
+bool getColors(BaseBuffer& buffer, const std::string& name, ColorView& view)
+{
+    UCharChannelOptional channel = buffer.getUCharChannel(name);
+    if (!channel || channel->width() < 3)
+    {
+        return false;
+    }
+    view.data = channel->dataPtr().get();
+    view.n = channel->numElements();
+    view.width = channel->width();
+    return true;
+}
+
+unsigned char clampColor(float v)
+{
+    return (unsigned char)std::min(255.0f, std::max(0.0f, std::round(v)));
+}
+
+void applyGamma(ColorView& view, float gamma)
+{
+    unsigned char lut[256];
+    for (int i = 0; i < 256; i++)
+    {
+        lut[i] = clampColor(255.0f * std::pow(i / 255.0f, 1.0f / gamma));
+    }
+    for (size_t i = 0; i < view.n; i++)
+    {
+        unsigned char* c = view.data + i * view.width;
+        c[0] = lut[c[0]];
+        c[1] = lut[c[1]];
+        c[2] = lut[c[2]];
+    }
+}
+
+void applyExposure(ColorView& view, float targetBrightness)
+{
+    // Rec. 709 luminance
+    double sum = 0.0;
+    for (size_t i = 0; i < view.n; i++)
+    {
+        const unsigned char* c = view.data + i * view.width;
+        sum += 0.2126 * c[0] + 0.7152 * c[1] + 0.0722 * c[2];
+    }
+    const double mean = view.n ? sum / view.n : 0.0;
+    if (mean <= 0.0)
+    {
+        return;
+    }
+
+    const float gain = targetBrightness * 255.0 / mean;
+    for (size_t i = 0; i < view.n; i++)
+    {
+        unsigned char* c = view.data + i * view.width;
+        c[0] = clampColor(c[0] * gain);
+        c[1] = clampColor(c[1] * gain);
+        c[2] = clampColor(c[2] * gain);
+    }
+}
+
+void applyWhiteBalance(ColorView& view)
+{
+    double mean[3] = {0.0, 0.0, 0.0};
+    for (size_t i = 0; i < view.n; i++)
+    {
+        const unsigned char* c = view.data + i * view.width;
+        mean[0] += c[0];
+        mean[1] += c[1];
+        mean[2] += c[2];
+    }
+    if (mean[0] <= 0.0 || mean[1] <= 0.0 || mean[2] <= 0.0)
+    {
+        return;
+    }
+
+    const double gray = (mean[0] + mean[1] + mean[2]) / 3.0;
+    const float gain[3] = {float(gray / mean[0]), float(gray / mean[1]), float(gray / mean[2])};
+    for (size_t i = 0; i < view.n; i++)
+    {
+        unsigned char* c = view.data + i * view.width;
+        c[0] = clampColor(c[0] * gain[0]);
+        c[1] = clampColor(c[1] * gain[1]);
+        c[2] = clampColor(c[2] * gain[2]);
+    }
+}
+
+} // anonymous namespace
+
+Vector3f rgbToHsv(unsigned char r, unsigned char g, unsigned char b)
+{
+    const float rf = r / 255.0f;
+    const float gf = g / 255.0f;
+    const float bf = b / 255.0f;
+    const float max = std::max(rf, std::max(gf, bf));
+    const float min = std::min(rf, std::min(gf, bf));
+    const float delta = max - min;
+
+    float h = 0.0f;
+    if (delta > 0.0f)
+    {
+        if (max == rf)
+        {
+            h = 60.0f * std::fmod((gf - bf) / delta, 6.0f);
+        }
+        else if (max == gf)
+        {
+            h = 60.0f * ((bf - rf) / delta + 2.0f);
+        }
+        else
+        {
+            h = 60.0f * ((rf - gf) / delta + 4.0f);
+        }
+        if (h < 0.0f)
+        {
+            h += 360.0f;
+        }
+    }
+
+    const float s = max > 0.0f ? delta / max : 0.0f;
+    return Vector3f(h, s, max);
+}
+
+void hsvToRgb(const Vector3f& hsv, unsigned char* rgb)
+{
+    const float h = std::fmod(std::fmod(hsv[0], 360.0f) + 360.0f, 360.0f);
+    const float s = std::min(1.0f, std::max(0.0f, hsv[1]));
+    const float v = std::min(1.0f, std::max(0.0f, hsv[2]));
+
+    const float c = v * s;
+    const float x = c * (1.0f - std::abs(std::fmod(h / 60.0f, 2.0f) - 1.0f));
+    const float m = v - c;
+
+    float r, g, b;
+    switch ((int)(h / 60.0f))
+    {
+        case 0:  r = c; g = x; b = 0; break;
+        case 1:  r = x; g = c; b = 0; break;
+        case 2:  r = 0; g = c; b = x; break;
+        case 3:  r = 0; g = x; b = c; break;
+        case 4:  r = x; g = 0; b = c; break;
+        default: r = c; g = 0; b = x; break;
+    }
+
+    rgb[0] = clampColor((r + m) * 255.0f);
+    rgb[1] = clampColor((g + m) * 255.0f);
+    rgb[2] = clampColor((b + m) * 255.0f);
+}
+
+floatArr colorsToHsv(const ucharArr& colors, size_t n, size_t width)
+{
+    floatArr hsv(new float[3 * n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        const unsigned char* c = colors.get() + i * width;
+        Vector3f v = rgbToHsv(c[0], c[1], c[2]);
+        hsv[3 * i] = v[0];
+        hsv[3 * i + 1] = v[1];
+        hsv[3 * i + 2] = v[2];
+    }
+    return hsv;
+}
+
+ucharArr hsvToColors(const floatArr& hsv, size_t n)
+{
+    ucharArr colors(new unsigned char[3 * n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        hsvToRgb(Vector3f(hsv[3 * i], hsv[3 * i + 1], hsv[3 * i + 2]), colors.get() + 3 * i);
+    }
+    return colors;
+}
+
+bool colorsMatch16(const ucharArr& colors, size_t width, const ushortArr& colors16, size_t n)
+{
+    if (!colors || !colors16)
+    {
+        return false;
+    }
+    for (size_t i = 0; i < n; i++)
+    {
+        for (size_t j = 0; j < 3; j++)
+        {
+            if (colors[i * width + j] != (colors16[3 * i + j] >> 8))
+            {
+                return false;
+            }
+        }
+    }
+    return true;
+}
+
+bool gammaCorrect(PointBufferPtr buffer, float gamma)
+{
+    ColorView view;
+    if (!buffer || gamma <= 0.0f || !getColors(*buffer, "colors", view))
+    {
+        return false;
+    }
+    applyGamma(view, gamma);
+    return true;
+}
+
+bool gammaCorrect(MeshBufferPtr mesh, float gamma)
+{
+    ColorView view;
+    if (!mesh || gamma <= 0.0f || !getColors(*mesh, "vertex_colors", view))
+    {
+        return false;
+    }
+    applyGamma(view, gamma);
+    return true;
+}
+
+bool normalizeExposure(PointBufferPtr buffer, float targetBrightness)
+{
+    ColorView view;
+    if (!buffer || !getColors(*buffer, "colors", view))
+    {
+        return false;
+    }
+    applyExposure(view, targetBrightness);
+    return true;
+}
+
+bool normalizeExposure(MeshBufferPtr mesh, float targetBrightness)
+{
+    ColorView view;
+    if (!mesh || !getColors(*mesh, "vertex_colors", view))
+    {
+        return false;
+    }
+    applyExposure(view, targetBrightness);
+    return true;
+}
+
+bool whiteBalance(PointBufferPtr buffer)
+{
+    ColorView view;
+    if (!buffer || !getColors(*buffer, "colors", view))
+    {
+        return false;
+    }
+    applyWhiteBalance(view);
+    return true;
+}
+
+bool whiteBalance(MeshBufferPtr mesh)
+{
+    ColorView view;
+    if (!mesh || !getColors(*mesh, "vertex_colors", view))
+    {
+        return false;
+    }
+    applyWhiteBalance(view);
+    return true;
+}
+
+void whiteBalanceScans(std::vector<PointBufferPtr>& scans)
+{
+    for (PointBufferPtr scan : scans)
+    {
+        if (scan)
+        {
+            whiteBalance(scan);
+        }
+    }
+}
+
+} // namespace lvr2
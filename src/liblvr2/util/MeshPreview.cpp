@@ -0,0 +1,276 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MeshPreview.cpp
+ */
+
+#include "lvr2/util/MeshPreview.hpp"
+#include "lvr2/types/MatrixTypes.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <opencv2/imgcodecs.hpp>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Orthonormal camera frame: image x axis, image y axis (up) and viewing direction
+struct PreviewCamera
+{
+    Vector3f right;
+    Vector3f up;
+    Vector3f dir;
+};
+
+PreviewCamera previewCamera(PreviewView view)
+{
+    Vector3f right;
+    Vector3f dir;
+    switch (view)
+    {
+    case PreviewView::Back:
+        right = Vector3f(-1, 0, 0);
+        dir = Vector3f(0, -1, 0);
+        break;
+    case PreviewView::Left:
+        right = Vector3f(0, -1, 0);
+        dir = Vector3f(1, 0, 0);
+        break;
+    case PreviewView::Right:
+        right = Vector3f(0, 1, 0);
+        dir = Vector3f(-1, 0, 0);
+        break;
+    case PreviewView::Top:
+        right = Vector3f(1, 0, 0);
+        dir = Vector3f(0, 0, -1);
+        break;
+    case PreviewView::Isometric:
+        right = Vector3f(1, 1, 0).normalized();
+        dir = Vector3f(-1, 1, -1).normalized();
+        break;
+    case PreviewView::Front:
+    default:
+        right = Vector3f(1, 0, 0);
+        dir = Vector3f(0, 1, 0);
+        break;
+    }
+    return PreviewCamera{right, right.cross(dir).normalized(), dir};
+}
+
+} // anonymous namespace
+
+std::string previewViewName(PreviewView view)
+{
+    switch (view)
+    {
+    case PreviewView::Front:     return "front";
+    case PreviewView::Back:      return "back";
+    case PreviewView::Left:      return "left";
+    case PreviewView::Right:     return "right";
+    case PreviewView::Top:       return "top";
+    case PreviewView::Isometric: return "isometric";
+    }
+    return "view";
+}
+
+cv::Mat renderPreview(MeshBufferPtr mesh, PreviewView view, const PreviewOptions& options)
+{
+    cv::Mat image(options.height, options.width, CV_8UC3, options.background);
+    if (!mesh || mesh->numVertices() == 0 || mesh->numFaces() == 0)
+    {
+        return image;
+    }
+
+    const size_t numVertices = mesh->numVertices();
+    const size_t numFaces = mesh->numFaces();
+    floatArr vertices = mesh->getVertices();
+    indexArray faces = mesh->getFaceIndices();
+
+    size_t colorWidth = 0;
+    ucharArr colors;
+    if (options.useVertexColors)
+    {
+        colors = mesh->getVertexColors(colorWidth);
+    }
+
+    // Project all vertices into the camera frame relative to the centroid
+    // of the bounding box
+    Vector3f bbMin = Vector3f::Constant(std::numeric_limits<float>::max());
+    Vector3f bbMax = Vector3f::Constant(std::numeric_limits<float>::lowest());
+    for (size_t i = 0; i < numVertices; i++)
+    {
+        Vector3f p(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]);
+        bbMin = bbMin.cwiseMin(p);
+        bbMax = bbMax.cwiseMax(p);
+    }
+    const Vector3f center = (bbMin + bbMax) / 2;
+
+    const PreviewCamera cam = previewCamera(view);
+    std::vector<Vector3f> projected(numVertices);
+    float extentX = 0;
+    float extentY = 0;
+    for (size_t i = 0; i < numVertices; i++)
+    {
+        Vector3f p = Vector3f(vertices[3 * i], vertices[3 * i + 1], vertices[3 * i + 2]) - center;
+        projected[i] = Vector3f(p.dot(cam.right), p.dot(cam.up), p.dot(cam.dir));
+        extentX = std::max(extentX, std::abs(projected[i].x()));
+        extentY = std::max(extentY, std::abs(projected[i].y()));
+    }
+
+    // Fit the projection into the image with a small margin
+    const float margin = 0.9f;
+    float scale = std::numeric_limits<float>::max();
+    if (extentX > 0)
+    {
+        scale = std::min(scale, margin * options.width / (2 * extentX));
+    }
+    if (extentY > 0)
+    {
+        scale = std::min(scale, margin * options.height / (2 * extentY));
+    }
+    if (scale == std::numeric_limits<float>::max())
+    {
+        scale = 1;
+    }
+
+    for (Vector3f& p : projected)
+    {
+        p.x() = options.width / 2.0f + p.x() * scale;
+        p.y() = options.height / 2.0f - p.y() * scale;
+    }
+
+    std::vector<float> depth(options.width * options.height, std::numeric_limits<float>::max());
+    const Vector3f gray(200, 200, 200);
+
+    for (size_t f = 0; f < numFaces; f++)
+    {
+        const unsigned int* idx = &faces[3 * f];
+        const Vector3f& a = projected[idx[0]];
+        const Vector3f& b = projected[idx[1]];
+        const Vector3f& c = projected[idx[2]];
+
+        const float area = (b.x() - a.x()) * (c.y() - a.y()) - (c.x() - a.x()) * (b.y() - a.y());
+        if (std::abs(area) < 1e-12f)
+        {
+            continue;
+        }
+
+        // Two sided headlight shading with the face normal
+        Vector3f va(vertices[3 * idx[0]], vertices[3 * idx[0] + 1], vertices[3 * idx[0] + 2]);
+        Vector3f vb(vertices[3 * idx[1]], vertices[3 * idx[1] + 1], vertices[3 * idx[1] + 2]);
+        Vector3f vc(vertices[3 * idx[2]], vertices[3 * idx[2] + 1], vertices[3 * idx[2] + 2]);
+        Vector3f n = (vb - va).cross(vc - va);
+        const float len = n.norm();
+        const float shade = 0.2f + 0.8f * (len > 0 ? std::abs(n.dot(cam.dir)) / len : 1.0f);
+
+        Vector3f col[3];
+        for (int j = 0; j < 3; j++)
+        {
+            col[j] = colors
+                ? Vector3f(colors[colorWidth * idx[j]], colors[colorWidth * idx[j] + 1], colors[colorWidth * idx[j] + 2])
+                : gray;
+        }
+
+        const int x0 = std::max(0, (int)std::floor(std::min({a.x(), b.x(), c.x()})));
+        const int x1 = std::min(options.width - 1, (int)std::ceil(std::max({a.x(), b.x(), c.x()})));
+        const int y0 = std::max(0, (int)std::floor(std::min({a.y(), b.y(), c.y()})));
+        const int y1 = std::min(options.height - 1, (int)std::ceil(std::max({a.y(), b.y(), c.y()})));
+
+        for (int y = y0; y <= y1; y++)
+        {
+            for (int x = x0; x <= x1; x++)
+            {
+                const float px = x + 0.5f;
+                const float py = y + 0.5f;
+                const float w0 = ((b.x() - px) * (c.y() - py) - (c.x() - px) * (b.y() - py)) / area;
+                const float w1 = ((c.x() - px) * (a.y() - py) - (a.x() - px) * (c.y() - py)) / area;
+                const float w2 = 1.0f - w0 - w1;
+                if (w0 < 0 || w1 < 0 || w2 < 0)
+                {
+                    continue;
+                }
+
+                const float z = w0 * a.z() + w1 * b.z() + w2 * c.z();
+                float& d = depth[y * options.width + x];
+                if (z >= d)
+                {
+                    continue;
+                }
+                d = z;
+
+                Vector3f rgb = (w0 * col[0] + w1 * col[1] + w2 * col[2]) * shade;
+                image.at<cv::Vec3b>(y, x) = cv::Vec3b(
+                    cv::saturate_cast<uchar>(rgb.z()),
+                    cv::saturate_cast<uchar>(rgb.y()),
+                    cv::saturate_cast<uchar>(rgb.x()));
+            }
+        }
+    }
+
+    return image;
+}
+
+cv::Mat renderPreviewSheet(MeshBufferPtr mesh, const PreviewOptions& options)
+{
+    std::vector<cv::Mat> images;
+    for (PreviewView view : options.views)
+    {
+        images.push_back(renderPreview(mesh, view, options));
+    }
+
+    cv::Mat sheet;
+    if (!images.empty())
+    {
+        cv::hconcat(images, sheet);
+    }
+    return sheet;
+}
+
+bool savePreviews(MeshBufferPtr mesh, const std::string& prefix, const PreviewOptions& options)
+{
+    bool ok = true;
+    for (PreviewView view : options.views)
+    {
+        std::string filename = prefix + "_" + previewViewName(view) + ".png";
+        if (!cv::imwrite(filename, renderPreview(mesh, view, options)))
+        {
+            lvr2::logout::get() << lvr2::error << "[MeshPreview] Could not write " << filename << lvr2::endl;
+            ok = false;
+        }
+    }
+    return ok;
+}
+
+} // namespace lvr2
@@ -0,0 +1,175 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * MortonOrder.cpp
+ */
+
+#include "lvr2/util/MortonOrder.hpp"
+
+#include <algorithm>
+#include <limits>
+#include <utility>
+
+namespace lvr2
+{
+
+namespace
+{
+
+/// Spreads the lower 21 bits of v so that two zero bits follow each bit
+uint64_t splitBits(uint32_t v)
+{
+    uint64_t x = v & 0x1fffff;
+    x = (x | x << 32) & 0x1f00000000ffffULL;
+    x = (x | x << 16) & 0x1f0000ff0000ffULL;
+    x = (x | x << 8)  & 0x100f00f00f00f00fULL;
+    x = (x | x << 4)  & 0x10c30c30c30c30c3ULL;
+    x = (x | x << 2)  & 0x1249249249249249ULL;
+    return x;
+}
+
+/// Sorts the points by the code of their cell on a 2^21 grid over the bounding box
+template<typename CodeFunc>
+std::vector<size_t> sortAlongCurve(const FloatChannel& points, CodeFunc code)
+{
+    const size_t n = points.numElements();
+
+    float min[3], max[3];
+    for(int a = 0; a < 3; a++)
+    {
+        min[a] = std::numeric_limits<float>::max();
+        max[a] = std::numeric_limits<float>::lowest();
+    }
+    for(size_t i = 0; i < n; i++)
+    {
+        for(int a = 0; a < 3; a++)
+        {
+            min[a] = std::min(min[a], points[i][a]);
+            max[a] = std::max(max[a], points[i][a]);
+        }
+    }
+
+    // Same scale on all axes, so the cells of the curve are cubes
+    float extent = 0;
+    for(int a = 0; a < 3; a++)
+    {
+        extent = std::max(extent, max[a] - min[a]);
+    }
+    const float maxCell = (1 << 21) - 1;
+    const float scale = extent > 0 ? maxCell / extent : 0;
+
+    std::vector<std::pair<uint64_t, size_t>> codes(n);
+    #pragma omp parallel for
+    for(size_t i = 0; i < n; i++)
+    {
+        uint32_t cell[3];
+        for(int a = 0; a < 3; a++)
+        {
+            cell[a] = static_cast<uint32_t>(std::min(maxCell, (points[i][a] - min[a]) * scale));
+        }
+        codes[i] = std::make_pair(code(cell[0], cell[1], cell[2]), i);
+    }
+
+    std::sort(codes.begin(), codes.end());
+
+    std::vector<size_t> order(n);
+    for(size_t i = 0; i < n; i++)
+    {
+        order[i] = codes[i].second;
+    }
+    return order;
+}
+
+} // anonymous namespace
+
+uint64_t mortonCode(uint32_t x, uint32_t y, uint32_t z)
+{
+    return splitBits(x) | (splitBits(y) << 1) | (splitBits(z) << 2);
+}
+
+uint64_t hilbertCode(uint32_t x, uint32_t y, uint32_t z)
+{
+    // J. Skilling, "Programming the Hilbert curve", 2004: The coordinates
+    // are transformed in place to the transposed Hilbert index.
+    const int n = 3;
+    uint32_t X[3] = { x & 0x1fffff, y & 0x1fffff, z & 0x1fffff };
+    const uint32_t M = 1u << 20;
+
+    // Inverse undo
+    for(uint32_t Q = M; Q > 1; Q >>= 1)
+    {
+        const uint32_t P = Q - 1;
+        for(int i = 0; i < n; i++)
+        {
+            if(X[i] & Q)
+            {
+                X[0] ^= P;
+            }
+            else
+            {
+                const uint32_t t = (X[0] ^ X[i]) & P;
+                X[0] ^= t;
+                X[i] ^= t;
+            }
+        }
+    }
+
+    // Gray encode
+    for(int i = 1; i < n; i++)
+    {
+        X[i] ^= X[i - 1];
+    }
+    uint32_t t = 0;
+    for(uint32_t Q = M; Q > 1; Q >>= 1)
+    {
+        if(X[n - 1] & Q)
+        {
+            t ^= Q - 1;
+        }
+    }
+    for(int i = 0; i < n; i++)
+    {
+        X[i] ^= t;
+    }
+
+    // The most significant bit of each triple is stored in X[0]
+    return splitBits(X[2]) | (splitBits(X[1]) << 1) | (splitBits(X[0]) << 2);
+}
+
+std::vector<size_t> mortonOrder(const FloatChannel& points)
+{
+    return sortAlongCurve(points, mortonCode);
+}
+
+std::vector<size_t> hilbertOrder(const FloatChannel& points)
+{
+    return sortAlongCurve(points, hilbertCode);
+}
+
+} // namespace lvr2
@@ -1,6 +1,7 @@
 #include "lvr2/util/Synthetic.hpp"
 #include <opencv2/imgproc.hpp>
 #include "opencv2/core/utility.hpp"
+#include <boost/optional.hpp>
 #ifndef CV_AA
 #define CV_AA cv::LINE_AA
 #endif
@@ -179,6 +180,210 @@ CameraImagePtr genLVRImage()
     return imgPtr;
 }
 
+Sphere::Sphere(const Vector3f& center, float radius)
+    : m_center(center), m_radius(radius)
+{
+}
+
+float Sphere::sdf(const Vector3f& p) const
+{
+    return (p - m_center).norm() - m_radius;
+}
+
+Vector3f Sphere::sample(std::mt19937& rng, Vector3f& normal) const
+{
+    std::normal_distribution<float> dist(0.0f, 1.0f);
+    do
+    {
+        normal = Vector3f(dist(rng), dist(rng), dist(rng));
+    } while (normal.squaredNorm() < 1e-12f);
+    normal.normalize();
+    return m_center + normal * m_radius;
+}
+
+void Sphere::bounds(Vector3f& min, Vector3f& max) const
+{
+    min = m_center - Vector3f::Constant(m_radius);
+    max = m_center + Vector3f::Constant(m_radius);
+}
+
+Box::Box(const Vector3f& center, const Vector3f& halfExtents)
+    : m_center(center), m_halfExtents(halfExtents)
+{
+}
+
+float Box::sdf(const Vector3f& p) const
+{
+    Vector3f q = (p - m_center).cwiseAbs() - m_halfExtents;
+    return q.cwiseMax(0.0f).norm() + std::min(q.maxCoeff(), 0.0f);
+}
+
+Vector3f Box::sample(std::mt19937& rng, Vector3f& normal) const
+{
+    const Vector3f& e = m_halfExtents;
+    // Areas of the face pairs orthogonal to x, y and z
+    float areas[3] = {e.y() * e.z(), e.x() * e.z(), e.x() * e.y()};
+    std::discrete_distribution<int> axisDist({areas[0], areas[1], areas[2]});
+    std::uniform_real_distribution<float> unit(-1.0f, 1.0f);
+    std::bernoulli_distribution side(0.5);
+
+    int axis = axisDist(rng);
+    Vector3f local(unit(rng) * e.x(), unit(rng) * e.y(), unit(rng) * e.z());
+    float sign = side(rng) ? 1.0f : -1.0f;
+    local[axis] = sign * e[axis];
+
+    normal = Vector3f::Zero();
+    normal[axis] = sign;
+    return m_center + local;
+}
+
+void Box::bounds(Vector3f& min, Vector3f& max) const
+{
+    min = m_center - m_halfExtents;
+    max = m_center + m_halfExtents;
+}
+
+Torus::Torus(const Vector3f& center, float majorRadius, float minorRadius)
+    : m_center(center), m_majorRadius(majorRadius), m_minorRadius(minorRadius)
+{
+}
+
+float Torus::sdf(const Vector3f& p) const
+{
+    Vector3f d = p - m_center;
+    Vector2f q(Vector2f(d.x(), d.y()).norm() - m_majorRadius, d.z());
+    return q.norm() - m_minorRadius;
+}
+
+Vector3f Torus::sample(std::mt19937& rng, Vector3f& normal) const
+{
+    std::uniform_real_distribution<float> angle(0.0f, 2 * M_PI);
+    std::uniform_real_distribution<float> unit(0.0f, 1.0f);
+
+    // Rejection sampling of the tube angle to account for the larger
+    // area on the outside of the torus
+    float u = angle(rng);
+    float v;
+    do
+    {
+        v = angle(rng);
+    } while (unit(rng) > (m_majorRadius + m_minorRadius * std::cos(v)) / (m_majorRadius + m_minorRadius));
+
+    normal = Vector3f(std::cos(v) * std::cos(u), std::cos(v) * std::sin(u), std::sin(v));
+    Vector3f ring(m_majorRadius * std::cos(u), m_majorRadius * std::sin(u), 0.0f);
+    return m_center + ring + normal * m_minorRadius;
+}
+
+void Torus::bounds(Vector3f& min, Vector3f& max) const
+{
+    const float r = m_majorRadius + m_minorRadius;
+    min = m_center - Vector3f(r, r, m_minorRadius);
+    max = m_center + Vector3f(r, r, m_minorRadius);
+}
+
+Plane::Plane(const Vector3f& center, const Vector3f& normal, float size)
+    : m_center(center), m_normal(normal.normalized()), m_size(size)
+{
+    m_u = m_normal.unitOrthogonal();
+    m_v = m_normal.cross(m_u);
+}
+
+float Plane::sdf(const Vector3f& p) const
+{
+    return (p - m_center).dot(m_normal);
+}
+
+Vector3f Plane::sample(std::mt19937& rng, Vector3f& normal) const
+{
+    std::uniform_real_distribution<float> unit(-0.5f, 0.5f);
+    normal = m_normal;
+    return m_center + m_u * (unit(rng) * m_size) + m_v * (unit(rng) * m_size);
+}
+
+void Plane::bounds(Vector3f& min, Vector3f& max) const
+{
+    Vector3f extent = (m_u.cwiseAbs() + m_v.cwiseAbs()) * (m_size / 2);
+    min = m_center - extent;
+    max = m_center + extent;
+}
+
+PointBufferPtr samplePoints(const Shape& shape, size_t numPoints, const NoiseOptions& noise)
+{
+    std::mt19937 rng(noise.seed);
+    // A normal distribution requires a positive standard deviation
+    boost::optional<std::normal_distribution<float>> gaussian;
+    if (noise.gaussian > 0)
+    {
+        gaussian = std::normal_distribution<float>(0.0f, noise.gaussian);
+    }
+    std::normal_distribution<float> direction(0.0f, 1.0f);
+
+    const size_t numOutliers = (size_t)std::round(std::max(noise.outlierRatio, 0.0f) * numPoints);
+    const size_t n = numPoints + numOutliers;
+
+    floatArr points(new float[3 * n]);
+    floatArr normals(new float[3 * n]);
+    ucharArr outliers(new unsigned char[n]);
+
+    for (size_t i = 0; i < numPoints; i++)
+    {
+        Vector3f normal;
+        Vector3f p = shape.sample(rng, normal);
+        if (gaussian)
+        {
+            p += normal * (*gaussian)(rng);
+        }
+        for (int j = 0; j < 3; j++)
+        {
+            points[3 * i + j] = p[j];
+            normals[3 * i + j] = normal[j];
+        }
+        outliers[i] = 0;
+    }
+
+    Vector3f min, max;
+    shape.bounds(min, max);
+    const Vector3f margin = Vector3f::Constant((max - min).norm() * noise.outlierMargin);
+    min -= margin;
+    max += margin;
+    std::uniform_real_distribution<float> ux(min.x(), max.x());
+    std::uniform_real_distribution<float> uy(min.y(), max.y());
+    std::uniform_real_distribution<float> uz(min.z(), max.z());
+
+    for (size_t i = numPoints; i < n; i++)
+    {
+        Vector3f p(ux(rng), uy(rng), uz(rng));
+        Vector3f normal(direction(rng), direction(rng), direction(rng));
+        normal.normalize();
+        for (int j = 0; j < 3; j++)
+        {
+            points[3 * i + j] = p[j];
+            normals[3 * i + j] = normal[j];
+        }
+        outliers[i] = 1;
+    }
+
+    PointBufferPtr ret(new PointBuffer);
+    ret->setPointArray(points, n);
+    ret->setNormalArray(normals, n);
+    ret->addUCharChannel(outliers, "outliers", n, 1);
+    return ret;
+}
+
+void addSignedDistances(const Shape& shape, PointBufferPtr points)
+{
+    const size_t n = points->numPoints();
+    floatArr p = points->getPointArray();
+    floatArr distances(new float[n]);
+    for (size_t i = 0; i < n; i++)
+    {
+        distances[i] = shape.sdf(Vector3f(p[3 * i], p[3 * i + 1], p[3 * i + 2]));
+    }
+
+    points->erase("sdf");
+    points->addFloatChannel(distances, "sdf", n, 1);
+}
+
 } // namespace synthetic
 
 } // namespace lvr2
\ No newline at end of file
@@ -76,6 +76,9 @@ int main(int argc, char** argv)
     float reduction_factor = 0.2f;
     float normal_deviation = -1;
     float scale = 1.0f;
+    std::vector<double> geo_reference;
+    std::vector<double> geo_offset;
+    int epsg = 0;
     std::vector<fs::path> mesh_out_files;
     AllowedMemoryUsage allowedMemUsage = AllowedMemoryUsage::Moderate;
     bool fix_mesh;
@@ -129,6 +132,16 @@ int main(int argc, char** argv)
          "This defaults to 100 because small meshes are hard to navigate in the default html viewer.\n"
          "Only change this if you use a different viewer and/or you have an accurate position on the globe.")
 
+        ("geoReference,g", value<std::vector<double>>(&geo_reference)->multitoken(),
+         "Place the tileset on the globe: <latitude> <longitude> [<height>].\n"
+         "WGS84 coordinates in degrees and meters. The mesh's x, y and z axes are mapped to east, north and up.")
+
+        ("geoOffset", value<std::vector<double>>(&geo_offset)->multitoken(),
+         "Mesh coordinates of the --geoReference point: <x> <y> <z>, e.g. a UTM offset.")
+
+        ("epsg", value<int>(&epsg)->default_value(epsg),
+         "EPSG code of the mesh's coordinate system. Stored as metadata in the tileset.")
+
         ("compress,z", bool_switch(&compress),
          "Compress the output meshes using Draco Compression.\n"
          "This will significantly reduce filesize and improve loading times when remotely viewing the tiles "
@@ -201,6 +214,14 @@ int main(int argc, char** argv)
         {
             throw po::error("reductionFactor must be between 0 and 1");
         }
+        if (!geo_reference.empty() && (geo_reference.size() < 2 || geo_reference.size() > 3))
+        {
+            throw po::error("geoReference expects <latitude> <longitude> [<height>]");
+        }
+        if (!geo_offset.empty() && geo_offset.size() != 3)
+        {
+            throw po::error("geoOffset expects <x> <y> <z>");
+        }
 
         has_chunk_size = variables.count("chunkSize") > 0;
     }
@@ -478,6 +499,19 @@ int main(int argc, char** argv)
     lvr2::logout::get() << lvr2::info << "Creating 3D Tiles" << lvr2::endl;
 
     IO io(output_dir.string());
+    if (!geo_reference.empty())
+    {
+        GeoReference geo;
+        geo.latitude = geo_reference[0];
+        geo.longitude = geo_reference[1];
+        geo.height = geo_reference.size() > 2 ? geo_reference[2] : 0.0;
+        for (size_t i = 0; i < 3 && i < geo_offset.size(); i++)
+        {
+            geo.offset[i] = geo_offset[i];
+        }
+        geo.epsg = epsg;
+        io.setGeoReference(geo);
+    }
     io.write(tree, compress, scale);
 
     tree.reset();
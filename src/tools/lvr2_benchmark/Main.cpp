@@ -0,0 +1,105 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Main.cpp
+ *
+ * Compares search tree backends, decompositions and thread counts on
+ * synthetic spheres of increasing size.
+ */
+
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/reconstruction/Benchmark.hpp"
+
+#include <boost/program_options.hpp>
+
+#include <iostream>
+
+using namespace lvr2;
+
+int main(int argc, char** argv)
+{
+    namespace po = boost::program_options;
+
+    std::vector<size_t> sizes;
+    std::vector<std::string> trees;
+    std::vector<std::string> decompositions;
+    std::vector<int> threads;
+    int repetitions;
+    std::string csv;
+
+    po::options_description descr("lvr2_benchmark options");
+    descr.add_options()
+        ("help", "Produce help message")
+        ("sizes", po::value<std::vector<size_t>>(&sizes)->multitoken()->default_value({10000, 100000}, "10000 100000"), "Numbers of input points")
+        ("searchTree", po::value<std::vector<std::string>>(&trees)->multitoken()->default_value({"FLANN"}, "FLANN"), "Search tree backends to compare")
+        ("decomposition", po::value<std::vector<std::string>>(&decompositions)->multitoken()->default_value({"PMC"}, "PMC"), "Decompositions to compare")
+        ("threads", po::value<std::vector<int>>(&threads)->multitoken()->default_value({0}, "0"), "Thread counts to compare, 0 uses all")
+        ("repetitions", po::value<int>(&repetitions)->default_value(3), "Timed runs per case")
+        ("csv", po::value<std::string>(&csv)->default_value(""), "Write results to this CSV file");
+
+    po::variables_map vm;
+    po::store(po::parse_command_line(argc, argv, descr), vm);
+    po::notify(vm);
+
+    if (vm.count("help"))
+    {
+        std::cout << descr << std::endl;
+        return EXIT_SUCCESS;
+    }
+
+    std::vector<BenchmarkCase> cases;
+    for (const std::string& tree : trees)
+    {
+        for (const std::string& decomposition : decompositions)
+        {
+            for (int t : threads)
+            {
+                BenchmarkCase c;
+                c.name = decomposition + "/" + tree + "/" + (t > 0 ? std::to_string(t) : std::string("all"));
+                c.options.searchTree = tree;
+                c.options.decomposition = decomposition;
+                c.threads = t;
+                cases.push_back(c);
+            }
+        }
+    }
+
+    BenchmarkConfig config;
+    config.sizes = sizes;
+    config.repetitions = repetitions;
+
+    std::vector<BenchmarkResult> results = runBenchmark<BaseVector<float>>(cases, config);
+    std::cout << benchmarkTable(results);
+
+    if (!csv.empty())
+    {
+        saveBenchmarkCsv(results, csv);
+    }
+    return EXIT_SUCCESS;
+}
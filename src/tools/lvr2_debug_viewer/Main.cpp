@@ -0,0 +1,314 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Main.cpp
+ *
+ * Interactive viewer to debug surface reconstructions. Shows the input
+ * points, their normals, the query points of the reconstruction grid
+ * colored by the sign of their distance value and the extracted mesh.
+ * Each layer can be toggled with the number keys 1 - 4.
+ */
+
+#include "Options.hpp"
+
+#include "lvr2/algorithm/FinalizeAlgorithms.hpp"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/PMPMesh.hpp"
+#include "lvr2/io/ModelFactory.hpp"
+#include "lvr2/reconstruction/BilinearFastBox.hpp"
+#include "lvr2/reconstruction/FastBox.hpp"
+#include "lvr2/reconstruction/FastReconstruction.hpp"
+#include "lvr2/reconstruction/PointsetGrid.hpp"
+#include "lvr2/reconstruction/Reconstruction.hpp"
+#include "lvr2/util/Logging.hpp"
+
+#include <vtkActor.h>
+#include <vtkCallbackCommand.h>
+#include <vtkCellArray.h>
+#include <vtkInteractorStyleTrackballCamera.h>
+#include <vtkPointData.h>
+#include <vtkPoints.h>
+#include <vtkPolyData.h>
+#include <vtkPolyDataMapper.h>
+#include <vtkProperty.h>
+#include <vtkRenderWindow.h>
+#include <vtkRenderWindowInteractor.h>
+#include <vtkRenderer.h>
+#include <vtkSmartPointer.h>
+#include <vtkUnsignedCharArray.h>
+
+#include <array>
+
+using namespace lvr2;
+
+using Vec = BaseVector<float>;
+
+namespace
+{
+
+vtkSmartPointer<vtkActor> createActor(vtkSmartPointer<vtkPolyData> data)
+{
+    auto mapper = vtkSmartPointer<vtkPolyDataMapper>::New();
+    mapper->SetInputData(data);
+    auto actor = vtkSmartPointer<vtkActor>::New();
+    actor->SetMapper(mapper);
+    return actor;
+}
+
+vtkSmartPointer<vtkActor> pointActor(PointBufferPtr buffer)
+{
+    auto points = vtkSmartPointer<vtkPoints>::New();
+    auto verts = vtkSmartPointer<vtkCellArray>::New();
+    floatArr p = buffer->getPointArray();
+    for (size_t i = 0; i < buffer->numPoints(); i++)
+    {
+        vtkIdType id = points->InsertNextPoint(p[3 * i], p[3 * i + 1], p[3 * i + 2]);
+        verts->InsertNextCell(1, &id);
+    }
+
+    auto data = vtkSmartPointer<vtkPolyData>::New();
+    data->SetPoints(points);
+    data->SetVerts(verts);
+
+    auto actor = createActor(data);
+    actor->GetProperty()->SetColor(0.2, 0.2, 0.2);
+    actor->GetProperty()->SetPointSize(2);
+    return actor;
+}
+
+vtkSmartPointer<vtkActor> normalActor(PointBufferPtr buffer, float length)
+{
+    auto points = vtkSmartPointer<vtkPoints>::New();
+    auto lines = vtkSmartPointer<vtkCellArray>::New();
+    floatArr p = buffer->getPointArray();
+    floatArr n = buffer->getNormalArray();
+    for (size_t i = 0; n && i < buffer->numPoints(); i++)
+    {
+        vtkIdType ids[2];
+        ids[0] = points->InsertNextPoint(p[3 * i], p[3 * i + 1], p[3 * i + 2]);
+        ids[1] = points->InsertNextPoint(
+            p[3 * i] + length * n[3 * i],
+            p[3 * i + 1] + length * n[3 * i + 1],
+            p[3 * i + 2] + length * n[3 * i + 2]);
+        lines->InsertNextCell(2, ids);
+    }
+
+    auto data = vtkSmartPointer<vtkPolyData>::New();
+    data->SetPoints(points);
+    data->SetLines(lines);
+
+    auto actor = createActor(data);
+    actor->GetProperty()->SetColor(0.1, 0.7, 0.1);
+    return actor;
+}
+
+/// Query points of the grid: red inside (negative distance), blue outside
+template<typename BoxT>
+vtkSmartPointer<vtkActor> gridActor(const PointsetGrid<Vec, BoxT>& grid)
+{
+    auto points = vtkSmartPointer<vtkPoints>::New();
+    auto verts = vtkSmartPointer<vtkCellArray>::New();
+    auto colors = vtkSmartPointer<vtkUnsignedCharArray>::New();
+    colors->SetNumberOfComponents(3);
+    colors->SetName("Colors");
+
+    for (const auto& qp : grid.getQueryPoints())
+    {
+        if (qp.m_invalid)
+        {
+            continue;
+        }
+        vtkIdType id = points->InsertNextPoint(qp.m_position.x, qp.m_position.y, qp.m_position.z);
+        verts->InsertNextCell(1, &id);
+        const unsigned char red[3] = {220, 40, 40};
+        const unsigned char blue[3] = {40, 40, 220};
+        colors->InsertNextTypedTuple(qp.m_distance < 0 ? red : blue);
+    }
+
+    auto data = vtkSmartPointer<vtkPolyData>::New();
+    data->SetPoints(points);
+    data->SetVerts(verts);
+    data->GetPointData()->SetScalars(colors);
+
+    auto actor = createActor(data);
+    actor->GetProperty()->SetPointSize(4);
+    return actor;
+}
+
+vtkSmartPointer<vtkActor> meshActor(MeshBufferPtr mesh)
+{
+    auto points = vtkSmartPointer<vtkPoints>::New();
+    auto polys = vtkSmartPointer<vtkCellArray>::New();
+    floatArr v = mesh->getVertices();
+    indexArray f = mesh->getFaceIndices();
+    for (size_t i = 0; i < mesh->numVertices(); i++)
+    {
+        points->InsertNextPoint(v[3 * i], v[3 * i + 1], v[3 * i + 2]);
+    }
+    for (size_t i = 0; i < mesh->numFaces(); i++)
+    {
+        vtkIdType ids[3] = {f[3 * i], f[3 * i + 1], f[3 * i + 2]};
+        polys->InsertNextCell(3, ids);
+    }
+
+    auto data = vtkSmartPointer<vtkPolyData>::New();
+    data->SetPoints(points);
+    data->SetPolys(polys);
+
+    auto actor = createActor(data);
+    actor->GetProperty()->SetColor(0.8, 0.8, 0.6);
+    // Back faces are shown in a different color to reveal flipped normals
+    auto back = vtkSmartPointer<vtkProperty>::New();
+    back->SetColor(0.8, 0.3, 0.8);
+    actor->SetBackfaceProperty(back);
+    return actor;
+}
+
+/// Reconstructs the mesh and returns the actors of the grid and mesh layers
+template<typename BoxT>
+std::array<vtkSmartPointer<vtkActor>, 2> reconstructLayers(
+    PointsetSurfacePtr<Vec> surface,
+    const ReconstructionOptions& options)
+{
+    auto grid = std::make_shared<PointsetGrid<Vec, BoxT>>(
+        options.voxelSize, surface, surface->getBoundingBox(), true, options.extrude);
+    grid->calcDistanceValues();
+
+    PMPMesh<Vec> mesh;
+    FastReconstruction<Vec, BoxT> reconstruction(grid);
+    reconstruction.getMesh(mesh);
+
+    SimpleFinalizer<Vec> finalizer;
+    MeshBufferPtr buffer = finalizer.apply(mesh);
+
+    lvr2::logout::get() << lvr2::info << "[DebugViewer] Grid with " << grid->getNumberOfCells()
+        << " cells, mesh with " << buffer->numFaces() << " faces" << lvr2::endl;
+
+    return {gridActor(*grid), meshActor(buffer)};
+}
+
+struct LayerToggle
+{
+    std::array<vtkSmartPointer<vtkActor>, 4> actors;
+    vtkRenderWindow* window;
+};
+
+void toggleLayer(vtkObject* caller, unsigned long, void* clientData, void*)
+{
+    auto interactor = static_cast<vtkRenderWindowInteractor*>(caller);
+    auto toggle = static_cast<LayerToggle*>(clientData);
+
+    std::string key = interactor->GetKeySym() ? interactor->GetKeySym() : "";
+    if (key.size() == 1 && key[0] >= '1' && key[0] <= '4')
+    {
+        vtkActor* actor = toggle->actors[key[0] - '1'];
+        actor->SetVisibility(!actor->GetVisibility());
+        toggle->window->Render();
+    }
+}
+
+} // anonymous namespace
+
+int main(int argc, char** argv)
+{
+    debug_viewer::Options options(argc, argv);
+    if (options.printUsage())
+    {
+        return EXIT_SUCCESS;
+    }
+    std::cout << options << std::endl;
+
+    ModelPtr model = ModelFactory::readModel(options.getInputFileName());
+    if (!model || !model->m_pointCloud)
+    {
+        lvr2::logout::get() << lvr2::error << "[DebugViewer] Could not read point cloud from "
+            << options.getInputFileName() << lvr2::endl;
+        return EXIT_FAILURE;
+    }
+    PointBufferPtr buffer = model->m_pointCloud;
+
+    ReconstructionOptions reconstructionOptions;
+    reconstructionOptions.decomposition = options.getDecomposition();
+    reconstructionOptions.voxelSize = options.getVoxelsize();
+    reconstructionOptions.kn = options.getKn();
+    reconstructionOptions.ki = options.getKi();
+    reconstructionOptions.kd = options.getKd();
+
+    PointsetSurfacePtr<Vec> surface = createReconstructionSurface<Vec>(buffer, reconstructionOptions);
+    if (!buffer->hasNormals())
+    {
+        surface->calculateSurfaceNormals();
+    }
+
+    std::array<vtkSmartPointer<vtkActor>, 2> layers;
+    if (options.getDecomposition() == "MC")
+    {
+        layers = reconstructLayers<FastBox<Vec>>(surface, reconstructionOptions);
+    }
+    else
+    {
+        BilinearFastBox<Vec>::m_surface = surface;
+        layers = reconstructLayers<BilinearFastBox<Vec>>(surface, reconstructionOptions);
+    }
+
+    LayerToggle toggle;
+    toggle.actors = {pointActor(buffer), normalActor(buffer, options.getNormalLength()), layers[0], layers[1]};
+
+    auto renderer = vtkSmartPointer<vtkRenderer>::New();
+    renderer->SetBackground(1.0, 1.0, 1.0);
+    for (auto& actor : toggle.actors)
+    {
+        renderer->AddActor(actor);
+    }
+    // Normals and grid are hidden initially
+    toggle.actors[1]->SetVisibility(false);
+    toggle.actors[2]->SetVisibility(false);
+
+    auto window = vtkSmartPointer<vtkRenderWindow>::New();
+    window->AddRenderer(renderer);
+    window->SetSize(1280, 960);
+    window->SetWindowName("lvr2_debug_viewer - 1: points, 2: normals, 3: grid, 4: mesh");
+    toggle.window = window;
+
+    auto interactor = vtkSmartPointer<vtkRenderWindowInteractor>::New();
+    interactor->SetRenderWindow(window);
+    auto style = vtkSmartPointer<vtkInteractorStyleTrackballCamera>::New();
+    interactor->SetInteractorStyle(style);
+
+    auto callback = vtkSmartPointer<vtkCallbackCommand>::New();
+    callback->SetCallback(toggleLayer);
+    callback->SetClientData(&toggle);
+    interactor->AddObserver(vtkCommand::KeyPressEvent, callback);
+
+    renderer->ResetCamera();
+    window->Render();
+    interactor->Start();
+
+    return EXIT_SUCCESS;
+}
@@ -0,0 +1,99 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Options.cpp
+ */
+
+#include "Options.hpp"
+
+namespace debug_viewer
+{
+
+using namespace boost::program_options;
+
+Options::Options(int argc, char** argv) : BaseOption(argc, argv)
+{
+    m_descr.add_options()
+        ("help", "Produce help message")
+        ("inputFile", value<std::vector<std::string>>(), "Input point cloud")
+        ("decomposition,d", value<std::string>(&m_decomposition)->default_value("PMC"), "Decomposition type: MC or PMC")
+        ("voxelsize,v", value<float>(&m_voxelsize)->default_value(10), "Voxel size of the reconstruction grid")
+        ("kn", value<int>(&m_kn)->default_value(10), "Size of k-neighborhood used for normal estimation")
+        ("ki", value<int>(&m_ki)->default_value(10), "Number of normals used for normal interpolation")
+        ("kd", value<int>(&m_kd)->default_value(5), "Number of points used for distance function evaluation")
+        ("normalLength", value<float>(&m_normalLength)->default_value(0), "Length of the displayed normals. Half the voxel size if 0.");
+    setup();
+}
+
+std::string Options::getInputFileName() const
+{
+    return m_variables["inputFile"].as<std::vector<std::string>>()[0];
+}
+
+std::string Options::getDecomposition() const
+{
+    return m_decomposition;
+}
+
+float Options::getVoxelsize() const
+{
+    return m_voxelsize;
+}
+
+int Options::getKn() const
+{
+    return m_kn;
+}
+
+int Options::getKi() const
+{
+    return m_ki;
+}
+
+int Options::getKd() const
+{
+    return m_kd;
+}
+
+float Options::getNormalLength() const
+{
+    return m_normalLength > 0 ? m_normalLength : m_voxelsize / 2;
+}
+
+bool Options::printUsage() const
+{
+    if (m_variables.count("help") || !m_variables.count("inputFile"))
+    {
+        std::cout << std::endl << m_descr << std::endl;
+        return true;
+    }
+    return false;
+}
+
+} // namespace debug_viewer
@@ -0,0 +1,91 @@
+/**
+ * Copyright (c) 2023, University Osnabrück
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are met:
+ *     * Redistributions of source code must retain the above copyright
+ *       notice, this list of conditions and the following disclaimer.
+ *     * Redistributions in binary form must reproduce the above copyright
+ *       notice, this list of conditions and the following disclaimer in the
+ *       documentation and/or other materials provided with the distribution.
+ *     * Neither the name of the University Osnabrück nor the
+ *       names of its contributors may be used to endorse or promote products
+ *       derived from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND
+ * ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+ * WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL University Osnabrück BE LIABLE FOR ANY
+ * DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES
+ * (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES;
+ * LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND
+ * ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+
+/**
+ * Options.hpp
+ */
+
+#ifndef LVR2_DEBUG_VIEWER_OPTIONS_HPP
+#define LVR2_DEBUG_VIEWER_OPTIONS_HPP
+
+#include "lvr2/config/BaseOption.hpp"
+
+#include <boost/program_options.hpp>
+#include <iostream>
+#include <string>
+#include <vector>
+
+namespace debug_viewer
+{
+
+/**
+ * @brief Program options of the reconstruction debug viewer
+ */
+class Options : public lvr2::BaseOption
+{
+public:
+    Options(int argc, char** argv);
+    virtual ~Options() = default;
+
+    /// Input point cloud
+    std::string getInputFileName() const;
+
+    /// Decomposition type: "MC" or "PMC"
+    std::string getDecomposition() const;
+
+    float getVoxelsize() const;
+    int getKn() const;
+    int getKi() const;
+    int getKd() const;
+
+    /// Length of the rendered normals. If 0, half the voxel size is used.
+    float getNormalLength() const;
+
+    bool printUsage() const;
+
+private:
+    std::string m_decomposition;
+    float m_voxelsize;
+    int m_kn;
+    int m_ki;
+    int m_kd;
+    float m_normalLength;
+};
+
+inline std::ostream& operator<<(std::ostream& os, const Options& o)
+{
+    os << "##### Input file \t\t: " << o.getInputFileName() << std::endl;
+    os << "##### Decomposition \t\t: " << o.getDecomposition() << std::endl;
+    os << "##### Voxelsize \t\t: " << o.getVoxelsize() << std::endl;
+    os << "##### kn / ki / kd \t\t: " << o.getKn() << " / " << o.getKi() << " / " << o.getKd() << std::endl;
+    return os;
+}
+
+} // namespace debug_viewer
+
+#endif // LVR2_DEBUG_VIEWER_OPTIONS_HPP
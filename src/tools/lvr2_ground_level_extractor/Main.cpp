@@ -463,6 +463,20 @@ int main(int argc, char* argv[])
     std::cout << timestamp.getElapsedTime() << "Saving Model as obj" << std::endl;
     ModelFactory::saveModel(m,options.getOutputFileName() + ".obj");  
 
+    if(!options.getDemFile().empty())
+    {
+        std::cout << timestamp.getElapsedTime() << "Saving DEM as " << options.getDemFile() << std::endl;
+        boost::optional<GeoMetadata> geo;
+        if(options.getDemEpsg() > 0)
+        {
+            GeoMetadata meta;
+            meta.epsg = options.getDemEpsg();
+            geo = meta;
+        }
+        HeightGrid grid = rasterizeHeightField(buffer, options.getDemCellSize());
+        saveDem(grid, options.getDemFile(), geo);
+    }
+
     if(!options.getInputReferencePairs().empty())
     {
         std::ofstream file;
@@ -31,6 +31,7 @@
 #include "lvr2/types/PointBuffer.hpp"
 #include "lvr2/types/MeshBuffer.hpp"
 #include "lvr2/io/ModelFactory.hpp"
+#include "lvr2/io/DemIO.hpp"
 #include "lvr2/io/modelio/GeoTIFFIO.hpp"
 #include "lvr2/util/ColorGradient.hpp"
 
@@ -51,7 +51,10 @@ Options::Options(int argc, char** argv)
         ("swThreshold", value<float>(&m_swThreshold)->default_value(1),"Threshold for the small window in THM.")
         ("lwSize", value<int>(&m_lwSize)->default_value(3), "Size of the large window (x*x) in THM.")
         ("lwThreshold", value<float>(&m_lwThreshold)->default_value(3),"Threshold for the large window in THM.")
-        ("slopeThreshold", value<float>(&m_slopeThreshold)->default_value(30),"Threshold for the slope's angle in THM.");
+        ("slopeThreshold", value<float>(&m_slopeThreshold)->default_value(30),"Threshold for the slope's angle in THM.")
+        ("demFile", value<string>()->default_value(""), "Additionally export the extracted terrain as raster DEM. Supported formats are ESRI ASCII grid (.asc) and GeoTIFF (.tif).")
+        ("demCellSize", value<float>()->default_value(0), "Cell size of the exported DEM. If 0, the resolution is used.")
+        ("demEpsg", value<int>()->default_value(0), "EPSG code of the coordinate system of the exported DEM.");
 
     setup();
 }
@@ -86,6 +89,22 @@ string Options::getInputReferencePairs() const
     return m_variables["inputReferencePairs"].as<string>();
 }
 
+string Options::getDemFile() const
+{
+    return m_variables["demFile"].as<string>();
+}
+
+float Options::getDemCellSize() const
+{
+    float cellSize = m_variables["demCellSize"].as<float>();
+    return cellSize > 0 ? cellSize : getResolution();
+}
+
+int Options::getDemEpsg() const
+{
+    return m_variables["demEpsg"].as<int>();
+}
+
 int Options::getStartingBand() const
 {
     return m_variables["startingBand"].as<int>();
@@ -64,6 +64,21 @@ public:
      */
     string getInputReferencePairs() const;
 
+    /**
+     * @brief   Returns the output DEM file name, empty if no DEM should be written
+     */
+    string getDemFile() const;
+
+    /**
+     * @brief   Returns the cell size of the output DEM
+     */
+    float getDemCellSize() const;
+
+    /**
+     * @brief   Returns the EPSG code of the output DEM, 0 if unknown
+     */
+    int getDemEpsg() const;
+
     /**
      * @brief   Returns the number of the first band that should be extracted from the GeoTIFF
      */
@@ -200,6 +215,11 @@ inline std::ostream& operator<<(std::ostream& os, const Options &o)
     {
         std::cout << "##### Reference Points File: " << o.getInputReferencePairs() << std::endl;
     }
+    if(!o.getDemFile().empty())
+    {
+        std::cout << "##### DEM File: " << o.getDemFile() << std::endl;
+        std::cout << "##### DEM Cell Size: " << o.getDemCellSize() << std::endl;
+    }
     std::cout << "##### Starting Band: " << o.getStartingBand() << std::endl;
     std::cout << "##### Number of Bands: " << o.getNumberOfBands() << std::endl;
     std::cout << "##### Color Scale: " << o.getColorScale() << std::endl;
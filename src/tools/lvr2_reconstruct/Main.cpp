@@ -27,7 +27,9 @@
 
 
 #include <iostream>
+#include <limits>
 #include <memory>
+#include <stdexcept>
 #include <tuple>
 #include <stdlib.h>
 
@@ -51,6 +53,7 @@
 #include "lvr2/algorithm/ClusterPainter.hpp"
 #include "lvr2/algorithm/ClusterAlgorithms.hpp"
 #include "lvr2/algorithm/CleanupAlgorithms.hpp"
+#include "lvr2/reconstruction/Provenance.hpp"
 #include "lvr2/algorithm/ReductionAlgorithms.hpp"
 #include "lvr2/algorithm/Materializer.hpp"
 #include "lvr2/algorithm/Texturizer.hpp"
@@ -77,10 +80,17 @@
 #include "lvr2/io/PlutoMapIO.hpp"
 #include "lvr2/io/meshio/HDF5IO.hpp"
 #include "lvr2/io/meshio/DirectoryIO.hpp"
+#include "lvr2/io/modelio/GltfLodIO.hpp"
 #include "lvr2/util/Factories.hpp"
 #include "lvr2/algorithm/GeometryAlgorithms.hpp"
 #include "lvr2/algorithm/UtilAlgorithms.hpp"
 #include "lvr2/algorithm/KDTree.hpp"
+#include "lvr2/algorithm/PipeReconstruction.hpp"
+#include "lvr2/algorithm/PointClassification.hpp"
+#include "lvr2/algorithm/PointDeduplication.hpp"
+#include "lvr2/algorithm/TemporalFiltering.hpp"
+#include "lvr2/io/TrajectoryIO.hpp"
+#include "lvr2/util/ColorSpace.hpp"
 #include "lvr2/util/Logging.hpp"
 #include "lvr2/util/ScanProjectUtils.hpp"
 
@@ -113,7 +123,7 @@ using namespace lvr2;
 using Vec = BaseVector<float>;
 using PsSurface = lvr2::PointsetSurface<Vec>;
 
-auto buildCombinedPointCloud(lvr2::ScanProjectPtr& project, lvr2::ReductionAlgorithmPtr reduction_algorithm) -> lvr2::PointBufferPtr
+auto buildCombinedPointCloud(lvr2::ScanProjectPtr& project, lvr2::ReductionAlgorithmPtr reduction_algorithm, bool white_balance) -> lvr2::PointBufferPtr
 {
     // === Build the PointCloud ===
     lvr2::Monitor mon(lvr2::LogLevel::info, "[LVR2 Reconstruct] Loading scan positions", project->positions.size());
@@ -159,6 +169,12 @@ auto buildCombinedPointCloud(lvr2::ScanProjectPtr& project, lvr2::ReductionAlgor
                     scan->load(reduction_algorithm);
                 }
 
+                // Compensate different cameras before merging
+                if (white_balance)
+                {
+                    whiteBalance(scan->points);
+                }
+
                 // Transform the new pointcloud
                 transformPointCloud<float>(
                     std::make_shared<Model>(scan->points),
@@ -235,6 +251,22 @@ auto buildCombinedPointCloud(lvr2::ScanProjectPtr& project, lvr2::ReductionAlgor
     return retval;
 }
 
+NormalEstimationMethod normalEstimationMethod(const reconstruct::Options& options)
+{
+    switch (options.getNormalEstimation())
+    {
+    case 0:
+        return NormalEstimationMethod::PCA;
+    case 1:
+        return NormalEstimationMethod::RANSAC;
+    case 2:
+        return NormalEstimationMethod::Iterative;
+    case 3:
+        return NormalEstimationMethod::IPCAExact;
+    }
+    throw std::invalid_argument("[LVR2 Reconstruct] Unknown normal estimation method " + std::to_string(options.getNormalEstimation()) + ". Choose from {0, 1, 2, 3}.");
+}
+
 template <typename BaseVecT>
 PointsetSurfacePtr<BaseVecT> loadPointCloud(const reconstruct::Options& options)
 {   
@@ -271,13 +303,36 @@ PointsetSurfacePtr<BaseVecT> loadPointCloud(const reconstruct::Options& options)
             project = lvr2::loadScanProject(options.getInputSchema(), options.getInputFileName());
         }
         
-        buffer = buildCombinedPointCloud(project, reduction_algorithm);
+        buffer = buildCombinedPointCloud(project, reduction_algorithm, options.whiteBalance());
     }
     else 
     {
         buffer = model->m_pointCloud;
     }
 
+    vector<unsigned char> classes = options.getClasses();
+    if(!classes.empty() && buffer)
+    {
+        buffer = filterByClassification(buffer, classes);
+    }
+
+    if(!options.getTrajectoryFile().empty() && buffer)
+    {
+        Trajectory trajectory = loadTrajectory(options.getTrajectoryFile());
+        if(!deskewPoints(buffer, trajectory, std::numeric_limits<double>::quiet_NaN()))
+        {
+            lvr2::logout::get() << lvr2::warning << "[LVR2 Reconstruct] Input points were not deskewed" << lvr2::endl;
+        }
+    }
+
+    if(options.getDedupRadius() > 0 && buffer)
+    {
+        DeduplicationOptions dedupOptions;
+        dedupOptions.radius = options.getDedupRadius();
+        dedupOptions.confidenceChannel = options.getConfidenceChannel();
+        removeDuplicatePoints(buffer, dedupOptions);
+    }
+
     // Create a point cloud manager
     string pcm_name = options.getPCM();
     PointsetSurfacePtr<Vec> surface;
@@ -291,13 +346,9 @@ PointsetSurfacePtr<BaseVecT> loadPointCloud(const reconstruct::Options& options)
     else if(pcm_name == "FLANN" || pcm_name == "NANOFLANN" || pcm_name == "LVR2")
     {
         
-        int plane_fit_method = options.getNormalEstimation();
+        NormalEstimationMethod plane_fit_method = normalEstimationMethod(options);
 
-        // plane_fit_method
-        // - 0: PCA
-        // - 1: RANSAC
-        // - 2: Iterative
-        surface = std::make_shared<AdaptiveKSearchSurface<BaseVecT>>(
+        auto adaptiveSurface = std::make_shared<AdaptiveKSearchSurface<BaseVecT>>(
             buffer,
             pcm_name,
             options.getKn(),
@@ -306,6 +357,8 @@ PointsetSurfacePtr<BaseVecT> loadPointCloud(const reconstruct::Options& options)
             plane_fit_method,
             options.getScanPoseFile()
         );
+        adaptiveSurface->setConfidenceChannel(options.getConfidenceChannel());
+        surface = adaptiveSurface;
     }
     else if(pcm_name == "LBVH_CUDA")
     {
@@ -357,7 +410,8 @@ PointsetSurfacePtr<BaseVecT> loadPointCloud(const reconstruct::Options& options)
             #ifdef GPU_FOUND
                 size_t num_points = buffer->numPoints();
                 floatArr points = buffer->getPointArray();
-                floatArr normals = floatArr(new float[ num_points * 3 ]);
+                // Overwrite existing normals in place if present
+                floatArr normals = buffer->hasNormals() ? buffer->getNormalArray() : floatArr(new float[ num_points * 3 ]);
                 lvr2::logout::get() << lvr2::info << "Generating GPU kd-tree" << lvr2::endl;
                 GpuSurface gpu_surface(points, num_points);
                 
@@ -401,6 +455,41 @@ PointsetSurfacePtr<BaseVecT> loadPointCloud(const reconstruct::Options& options)
     return surface;
 }
 
+template <typename BoxT>
+std::shared_ptr<PointsetGrid<Vec, BoxT>> createPointsetGrid(
+    const reconstruct::Options& options,
+    PointsetSurfacePtr<Vec> surface,
+    float resolution,
+    bool useVoxelsize
+)
+{
+    std::shared_ptr<PointsetGrid<Vec, BoxT>> grid;
+    if(options.getRefinementLevels() > 0)
+    {
+        float voxelsize = useVoxelsize ? resolution : surface->getBoundingBox().getLongestSide() / resolution;
+        grid = std::make_shared<PointsetGrid<Vec, BoxT>>(
+            voxelsize,
+            surface,
+            surface->getBoundingBox(),
+            surfaceBandCells(surface, voxelsize, options.getRefinementLevels(), options.extrude())
+        );
+    }
+    else
+    {
+        grid = std::make_shared<PointsetGrid<Vec, BoxT>>(
+            resolution,
+            surface,
+            surface->getBoundingBox(),
+            useVoxelsize,
+            options.extrude()
+        );
+    }
+
+    grid->setTruncationDistance(options.getTruncationDistance());
+    grid->setMaxDistance(options.getMaxDistance());
+    return grid;
+}
+
 std::pair<shared_ptr<GridBase>, unique_ptr<FastReconstructionBase<Vec>>>
     createGridAndReconstruction(
         const reconstruct::Options& options,
@@ -423,13 +512,7 @@ std::pair<shared_ptr<GridBase>, unique_ptr<FastReconstructionBase<Vec>>>
 
     if(decompositionType == "MC")
     {
-        auto grid = std::make_shared<PointsetGrid<Vec, FastBox<Vec>>>(
-            resolution,
-            surface,
-            surface->getBoundingBox(),
-            useVoxelsize,
-            options.extrude()
-        );
+        auto grid = createPointsetGrid<FastBox<Vec>>(options, surface, resolution, useVoxelsize);
 
         grid->calcDistanceValues();
         lvr2::logout::get() << lvr2::info << "[LVR2 Reconstruct] Grid Cells: " << grid->getCells().size() << lvr2::endl;
@@ -439,13 +522,7 @@ std::pair<shared_ptr<GridBase>, unique_ptr<FastReconstructionBase<Vec>>>
     else if(decompositionType == "PMC")
     {
         BilinearFastBox<Vec>::m_surface = surface;
-        auto grid = std::make_shared<PointsetGrid<Vec, BilinearFastBox<Vec>>>(
-            resolution,
-            surface,
-            surface->getBoundingBox(),
-            useVoxelsize,
-            options.extrude()
-        );
+        auto grid = createPointsetGrid<BilinearFastBox<Vec>>(options, surface, resolution, useVoxelsize);
         grid->calcDistanceValues();
         lvr2::logout::get() << lvr2::info << "[LVR2 Reconstruct] Grid Cells: " << grid->getCells().size() << lvr2::endl;
         auto reconstruction = std::make_unique<FastReconstruction<Vec, BilinearFastBox<Vec>>>(grid);
@@ -462,13 +539,7 @@ std::pair<shared_ptr<GridBase>, unique_ptr<FastReconstructionBase<Vec>>>
     // }
     else if(decompositionType == "MT")
     {
-        auto grid = std::make_shared<PointsetGrid<Vec, TetraederBox<Vec>>>(
-            resolution,
-            surface,
-            surface->getBoundingBox(),
-            useVoxelsize,
-            options.extrude()
-        );
+        auto grid = createPointsetGrid<TetraederBox<Vec>>(options, surface, resolution, useVoxelsize);
         grid->calcDistanceValues();
         auto reconstruction = make_unique<FastReconstruction<Vec, TetraederBox<Vec>>>(grid);
         return make_pair(grid, std::move(reconstruction));
@@ -476,13 +547,7 @@ std::pair<shared_ptr<GridBase>, unique_ptr<FastReconstructionBase<Vec>>>
     else if(decompositionType == "SF")
     {
         SharpBox<Vec>::m_surface = surface;
-        auto grid = std::make_shared<PointsetGrid<Vec, SharpBox<Vec>>>(
-            resolution,
-            surface,
-            surface->getBoundingBox(),
-            useVoxelsize,
-            options.extrude()
-        );
+        auto grid = createPointsetGrid<SharpBox<Vec>>(options, surface, resolution, useVoxelsize);
         grid->calcDistanceValues();
         auto reconstruction = make_unique<FastReconstruction<Vec, SharpBox<Vec>>>(grid);
         return make_pair(grid, std::move(reconstruction));
@@ -600,6 +665,13 @@ BaseMeshT reconstructMesh(reconstruct::Options options, PointsetSurfacePtr<BaseV
     // Reconstruct mesh
     reconstruction->getMesh(mesh);
 
+    // Remove faces that are not supported by the point cloud
+    if(options.getTrimDensity() > 0)
+    {
+        float radius = options.getTrimRadius() > 0 ? options.getTrimRadius() : options.getVoxelsize();
+        trimLowDensityFaces(mesh, *surface, radius, options.getTrimDensity());
+    }
+
     // Save grid to file
     if(options.saveGrid() && grid)
     {
@@ -790,6 +862,71 @@ auto loadExistingMesh(reconstruct::Options options)
     return std::make_tuple(std::move(mesh), std::move(surface), std::move(faceNormalMap), std::move(clusterBiMap));
 }
 
+int reconstructPipeRuns(const reconstruct::Options& options, PointBufferPtr buffer)
+{
+    PipeReconstructionOptions pipeOptions;
+    pipeOptions.distanceThreshold = options.getPipeDistance();
+    pipeOptions.minInliers = options.getPipeMinPoints();
+
+    PipeReconstructionResult pipes = reconstructPipes(buffer, pipeOptions);
+    MeshBufferPtr mesh = pipeMesh(pipes);
+
+    boost::filesystem::path outputDir(options.getOutputDirectory());
+    savePipeReport(pipes, outputDir / "pipes.csv");
+
+    ModelPtr model(new Model(mesh));
+    for(const std::string& output_filename : options.getOutputFileNames())
+    {
+        lvr2::logout::get() << lvr2::info << "[LVR2 Reconstruct] Saving pipes to "<< output_filename << "." << lvr2::endl;
+        ModelFactory::saveModel(model, (outputDir / output_filename).string());
+    }
+    return EXIT_SUCCESS;
+}
+
+VertexNormalWeighting normalWeighting(const reconstruct::Options& options)
+{
+    if (options.getNormalWeighting() == "angle")
+    {
+        return VertexNormalWeighting::Angle;
+    }
+    else if (options.getNormalWeighting() == "area")
+    {
+        return VertexNormalWeighting::Area;
+    }
+    else if (options.getNormalWeighting() == "uniform")
+    {
+        return VertexNormalWeighting::Uniform;
+    }
+    throw std::invalid_argument("[LVR2 Reconstruct] Unknown normal weighting '" + options.getNormalWeighting() + "'. Choose from {uniform, angle, area}.");
+}
+
+/// Maps the command line parameters to the library options, so they can be recorded in the provenance
+ReconstructionOptions reconstructionOptions(const reconstruct::Options& options)
+{
+    ReconstructionOptions result;
+    result.decomposition = options.getDecomposition();
+    result.voxelSize = options.getVoxelsize();
+    result.extrude = options.extrude();
+    result.refinementLevels = options.getRefinementLevels();
+    result.truncationDistance = options.getTruncationDistance();
+    result.maxDistance = options.getMaxDistance();
+    result.searchTree = options.getPCM();
+    result.kn = options.getKn();
+    result.ki = options.getKi();
+    result.kd = options.getKd();
+    result.normalWeighting = normalWeighting(options);
+    result.normalMethod = normalEstimationMethod(options);
+    result.recalcNormals = options.recalcNormals();
+    result.confidenceChannel = options.getConfidenceChannel();
+    if (auto flipPoint = options.getFlippoint())
+    {
+        result.flipPoint = *flipPoint;
+    }
+    result.trimMinPoints = options.getTrimDensity();
+    result.trimRadius = options.getTrimRadius();
+    return result;
+}
+
 int main(int argc, char** argv)
 {
     // =======================================================================
@@ -809,6 +946,17 @@ int main(int argc, char** argv)
 
     std::cout << options << std::endl;
 
+    try
+    {
+        normalWeighting(options);
+        normalEstimationMethod(options);
+    }
+    catch (const std::invalid_argument& e)
+    {
+        lvr2::logout::get() << lvr2::error << e.what() << lvr2::endl;
+        return EXIT_FAILURE;
+    }
+
     // =======================================================================
     // Load (and potentially store) point cloud
     // =======================================================================
@@ -834,6 +982,11 @@ int main(int argc, char** argv)
             exit(EXIT_FAILURE);
         }
         
+        if (options.reconstructPipes())
+        {
+            return reconstructPipeRuns(options, surface->pointBuffer());
+        }
+
         lvr2::logout::get() << lvr2::info << "[LVR2 Reconstruct] Pointcloud loaded starting to reconstruct surfaces ..." << lvr2::endl;
 
         // Reconstruct simple mesh
@@ -868,7 +1021,7 @@ int main(int argc, char** argv)
     auto vertexColors = calcColorFromPointCloud(mesh, surface);
 
     // Calc normals for vertices
-    auto vertexNormals = calcVertexNormals(mesh, faceNormals, *surface);
+    auto vertexNormals = calcVertexNormals(mesh, faceNormals, *surface, normalWeighting(options));
 
     // Prepare finalize algorithm
     TextureFinalizer<Vec> finalize(clusterBiMap);
@@ -948,6 +1101,19 @@ int main(int argc, char** argv)
     // Create output model and save to file
     auto m = ModelPtr( new Model(buffer));
 
+    std::stringstream parameters;
+    for(int i = 1; i < argc; i++)
+    {
+        parameters << (i > 1 ? " " : "") << argv[i];
+    }
+    m->setProvenance("lvr2_reconstruct", parameters.str());
+    if(options.embedProvenance())
+    {
+        // Keep the generator and time stamp set above
+        auto provenance = reconstructionProvenance(reconstructionOptions(options), options.getInputFileName());
+        m->m_metadata.insert(provenance.begin(), provenance.end());
+    }
+
     if(options.saveOriginalData())
     {
         m->m_pointCloud = surface->pointBuffer();
@@ -971,12 +1137,21 @@ int main(int argc, char** argv)
 
             mesh_io.saveMesh(
                 options.getMeshName(),
-                buffer
+                buffer,
+                m->m_metadata
                 );
 
             continue;
         }
 
+#ifdef LVR2_USE_3DTILES
+        if (extension == ".glb")
+        {
+            saveGlbLODs({buffer}, outputFile.string(), {}, m->m_metadata);
+            continue;
+        }
+#endif
+
         if (extension == "")
         {
             DirectoryKernelPtr kernel = DirectoryKernelPtr(new DirectoryKernel(outputFile.string()));
@@ -985,7 +1160,8 @@ int main(int argc, char** argv)
 
             mesh_io.saveMesh(
                 options.getMeshName(),
-                buffer
+                buffer,
+                m->m_metadata
                 );
 
             continue;
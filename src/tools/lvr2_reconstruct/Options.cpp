@@ -65,16 +65,21 @@ Options::Options(int argc, char** argv)
         ("inputFile", value< vector<string> >(), "Input file name. Supported formats are ASCII (.pts, .xyz), .ply and .h5")
         ("inputSchema", value<string>(&m_inputSchema),"The ScanProjectSchema to use with the input file. Options are HDF5, HDF5V2, RAW, HYPERLIB, EUROC, RAWPLY, SLAM6D")
         ("outputDirectory", value<string>()->default_value("./"), "Directory where the output files are placed")
-        ("outputFile", value< vector<string> >()->multitoken()->default_value(vector<string>{"triangle_mesh.ply", "triangle_mesh.obj"}), "Output file name. Supported formats are ASCII (.pts, .xyz) and .ply")
+        ("outputFile", value< vector<string> >()->multitoken()->default_value(vector<string>{"triangle_mesh.ply", "triangle_mesh.obj"}), "Output file name. Supported formats are ASCII (.pts, .xyz), .ply and .glb")
         ("voxelsize,v", value<float>(&m_voxelsize)->default_value(10), "Voxelsize of grid used for reconstruction.")
         ("noExtrusion", "Do not extend grid. Can be used  to avoid artefacts in dense data sets but. Disabling will possibly create additional holes in sparse data sets.")
         ("intersections,i", value<int>(&m_intersections)->default_value(-1), "Number of intersections used for reconstruction. If other than -1, voxelsize will calculated automatically.")
+        ("refinementLevels", value<int>()->default_value(0), "Find the cells that are intersected by the surface on a grid with a voxel size of voxelsize * 2^refinementLevels first and refine only those. Faster than evaluating all cells that contain points.")
+        ("truncationDistance", value<float>()->default_value(0), "Clamp the distance function to [-truncationDistance, truncationDistance]. Disabled if <= 0.")
+        ("maxDistance", value<float>()->default_value(0), "Skip grid cells whose corners are farther than this from the nearest points. Defaults to 1.75 * voxelsize if <= 0.")
         ("pcm,p", value<string>(&m_pcm)->default_value("LVR2"), "Point cloud manager used for point handling and normal estimation. Choose from {FLANN, PCL, LVR2, LBVH_CUDA}.")
         ("nem", value<int>(&m_normalEstimation)->default_value(0), "Method for estimating point normals / planes. 0: PCA (default), 1: RANSAC, 2: IPCA ilikebigbits, 3: IPCA exact. Make sure the computing device is supporting the respective method.")
         ("decomposition,d", value<string>(&m_pcm)->default_value("PMC"), "Defines the type of decomposition that is used for the voxels (Standard Marching Cubes (MC), Planar Marching Cubes (PMC), Standard Marching Cubes with sharp feature detection (SF), Dual Marching Cubes with an adaptive Octree (DMC) or Tetraeder (MT) decomposition. Choose from {MC, PMC, MT, SF}")
         ("optimizePlanes,o", "Shift all triangle vertices of a cluster onto their shared plane")
         ("clusterPlanes,c", "Cluster planar regions based on normal threshold, do not shift vertices into regression plane.")
         ("cleanContours", value<int>(&m_cleanContourIterations)->default_value(0), "Remove noise artifacts from contours. Same values are between 2 and 4")
+        ("trimDensity", value<int>()->default_value(0), "Remove faces with fewer than n points within trimRadius of their centroid, e.g. bubbles over sparse areas. Disabled if 0.")
+        ("trimRadius", value<float>()->default_value(0), "Search radius for trimDensity. Defaults to the voxel size if <= 0.")
         ("planeIterations", value<int>(&m_planeIterations)->default_value(3), "Number of iterations for plane optimization")
         ("fillHoles,f", value<int>(&m_fillHoles)->default_value(0), "Maximum size for hole filling")
         ("rda", value<int>(&m_rda)->default_value(0), "Remove dangling artifacts, i.e. remove the clusters with less than n triangles")
@@ -83,10 +88,15 @@ Options::Options(int argc, char** argv)
         ("writeClassificationResult,w", "Write classification results to file 'clusters.clu'")
         ("exportPointNormals,e", "Exports original point cloud data together with normals into a single file called 'pointnormals.ply'")
         ("saveGrid,g", "Writes the generated grid to a file called 'fastgrid.grid. The result can be rendered with qviewer.")
+        ("provenance", "Embed the lvr2 version, a hash of the input data and the reconstruction parameters as JSON in the saved meshes (PLY comments, glTF extras, HDF5 attributes) in addition to the command line parameters.")
         ("saveOriginalData,s", "Save the original points and the estimated normals together with the reconstruction into one file ('triangle_mesh.ply')")
         ("scanPoseFile", value<string>()->default_value(""), "ASCII file containing scan positions that can be used to flip normals")
+        ("trajectory", value<string>()->default_value(""), "ASCII file with time stamped sensor poses (TUM format or time followed by a 3x4 pose matrix). Points with a 'timestamps' channel (e.g. GPS time from LAS files) are transformed with the interpolated pose at their time stamp to remove the motion distortion of mobile mapping data.")
+        ("dedupRadius", value<float>()->default_value(0), "Remove points that are closer than this distance to another point, e.g. in the overlap of merged scans. Of each group of duplicates the point with the highest confidence (see --confidenceChannel) is kept. Disabled if 0.")
+        ("confidenceChannel", value<string>()->default_value("confidence"), "Float channel with per point weights for normal estimation and distance evaluation, e.g. derived from scanner quality or range. Ignored if the input has no such channel.")
         ("kd", value<int>(&m_kd)->default_value(5), "Number of normals used for distance function evaluation")
         ("ki", value<int>(&m_ki)->default_value(10), "Number of normals used in the normal interpolation process")
+        ("normalWeighting", value<string>()->default_value("uniform"), "Weighting of the face normals when they are averaged to vertex normals. Choose from {uniform, angle, area}; other values are rejected.")
         ("kn", value<int>(&m_kn)->default_value(10), "Size of k-neighborhood used for normal estimation")
         ("mp", value<int>(&m_minPlaneSize)->default_value(7), "Minimum value for plane optimzation")
         ("retesselate,t", "Retesselate regions that are in a regression plane. Implies --optimizePlanes.")
@@ -127,6 +137,11 @@ Options::Options(int argc, char** argv)
         ("inputMeshFile", value<string>(&m_inputMeshFile), "The file to load the mesh from")
         ("reduceScan", value<float>(&m_octreeVoxelSize)->default_value(0.0f), "Use Octree reduction algorithm with the given gridsize when after loading the scans")
         ("reduceScanMinPoints", value<size_t>(&m_octreeMinPoints)->default_value(1), "The number of points an octree voxel has to contain to be considered occupied")
+        ("whiteBalance", "White balance the colors of each scan before merging them. Useful if the scans were colored by different cameras.")
+        ("classes", value< vector<int> >()->multitoken(), "Only reconstruct points of the given ASPRS classes, e.g. --classes 2 6 for ground and buildings. Requires a classification channel (LAS input).")
+        ("pipes", "Reconstruct detected pipes as parametric cylinders instead of running marching cubes. Writes a pipes.csv report with the radius and length of every segment to the output directory.")
+        ("pipeDistance", value<float>()->default_value(0.01f), "Maximum distance of a point to a pipe surface when using --pipes")
+        ("pipeMinPoints", value<size_t>()->default_value(200), "Minimum number of points of a pipe segment when using --pipes")
 #ifdef LVR2_USE_EMBREE
         ("useRaycastingTexturizer", "If this flag is set the RaycastingTexturizer is used. This uses raycasting for occlusion testing when generating the textures.")
 #endif
@@ -160,6 +175,11 @@ int Options::getKi() const
     return m_variables["ki"].as<int>();
 }
 
+string Options::getNormalWeighting() const
+{
+    return m_variables["normalWeighting"].as<string>();
+}
+
 int Options::getKd() const
 {
     return m_variables["kd"].as<int>();
@@ -175,6 +195,21 @@ int Options::getIntersections() const
     return m_variables["intersections"].as<int>();
 }
 
+int Options::getRefinementLevels() const
+{
+    return m_variables["refinementLevels"].as<int>();
+}
+
+float Options::getTruncationDistance() const
+{
+    return m_variables["truncationDistance"].as<float>();
+}
+
+float Options::getMaxDistance() const
+{
+    return m_variables["maxDistance"].as<float>();
+}
+
 int Options::getPlaneIterations() const
 {
     return m_variables["planeIterations"].as<int>();
@@ -215,11 +250,26 @@ string Options::getDecomposition() const
     return (m_variables["decomposition"].as< string >());
 }
 
+string Options::getConfidenceChannel() const
+{
+    return m_variables["confidenceChannel"].as<string>();
+}
+
 string Options::getScanPoseFile() const
 {
     return (m_variables["scanPoseFile"].as<string>());
 }
 
+string Options::getTrajectoryFile() const
+{
+    return m_variables["trajectory"].as<string>();
+}
+
+float Options::getDedupRadius() const
+{
+    return m_variables["dedupRadius"].as<float>();
+}
+
 float Options::getEdgeCollapseReductionRatio() const
 {
     return (m_variables["reductionRatio"].as<float>());
@@ -299,6 +349,11 @@ bool Options::saveGrid() const
     return (m_variables.count("saveGrid"));
 }
 
+bool Options::embedProvenance() const
+{
+    return (m_variables.count("provenance"));
+}
+
 bool Options::saveOriginalData() const
 {
     return (m_variables.count("saveOriginalData"));
@@ -357,6 +412,16 @@ int Options::getSmallRegionThreshold() const
     return m_variables["smallRegionThreshold"].as<int>();
 }
 
+int Options::getTrimDensity() const
+{
+    return m_variables["trimDensity"].as<int>();
+}
+
+float Options::getTrimRadius() const
+{
+    return m_variables["trimRadius"].as<float>();
+}
+
 int Options::getCleanContourIterations() const
 {
     return m_variables["cleanContours"].as<int>();
@@ -543,6 +608,39 @@ size_t Options::getOctreeMinPoints() const
     return m_octreeMinPoints;
 }
 
+vector<unsigned char> Options::getClasses() const
+{
+    vector<unsigned char> classes;
+    if(m_variables.count("classes"))
+    {
+        for(int c : m_variables["classes"].as< vector<int> >())
+        {
+            classes.push_back(static_cast<unsigned char>(c));
+        }
+    }
+    return classes;
+}
+
+bool Options::whiteBalance() const
+{
+    return m_variables.count("whiteBalance");
+}
+
+bool Options::reconstructPipes() const
+{
+    return m_variables.count("pipes");
+}
+
+float Options::getPipeDistance() const
+{
+    return m_variables["pipeDistance"].as<float>();
+}
+
+size_t Options::getPipeMinPoints() const
+{
+    return m_variables["pipeMinPoints"].as<size_t>();
+}
+
 bool Options::useRaycastingTexturizer() const
 {
     return m_variables.count("useRaycastingTexturizer");
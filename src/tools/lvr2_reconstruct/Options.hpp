@@ -109,6 +109,12 @@ public:
      */
     bool    saveGrid() const;
 
+    /**
+     * @brief   Returns true if the library version and the input file hash
+     *          should be embedded in the saved meshes
+     */
+    bool    embedProvenance() const;
+
     /**
      * @brief   Returns true if the original points should be stored
      *          together with the reconstruction
@@ -162,6 +168,12 @@ public:
      */
     int     getKi() const;
 
+    /**
+     * @brief   Returns the weighting of the face normals when they are
+     *          averaged to vertex normals: uniform, angle or area
+     */
+    string  getNormalWeighting() const;
+
     /**
      * @brief   Returns the number of neighbors used for
      *          initial normal estimation
@@ -240,6 +252,24 @@ public:
      */
     string  getScanPoseFile() const;
 
+    /**
+     * @brief   Returns the name of the trajectory file used to remove the
+     *          motion distortion of the input points. Empty if not set.
+     */
+    string  getTrajectoryFile() const;
+
+    /**
+     * @brief   Returns the distance below which points are considered
+     *          duplicates. Deduplication is disabled if <= 0.
+     */
+    float   getDedupRadius() const;
+
+    /**
+     * @brief   Returns the name of the channel with per point weights
+     *          for normal estimation and distance evaluation.
+     */
+    string  getConfidenceChannel() const;
+
     /**
      * @brief   Returns the number of intersections. If the return value
      *          is positive it will be used for reconstruction instead of
@@ -247,6 +277,24 @@ public:
      */
     int     getIntersections() const;
 
+    /**
+     * @brief   Returns the number of coarse to fine refinement steps used
+     *          to build the grid. 0 if disabled.
+     */
+    int     getRefinementLevels() const;
+
+    /**
+     * @brief   Returns the distance at which the distance function is
+     *          truncated. Disabled if <= 0.
+     */
+    float   getTruncationDistance() const;
+
+    /**
+     * @brief   Returns the maximum distance of a cell corner to the nearest
+     *          points. 1.75 * voxelsize is used if <= 0.
+     */
+    float   getMaxDistance() const;
+
 
     /**
      * @brief   Returns to number plane optimization iterations
@@ -285,6 +333,17 @@ public:
      */
     int   getCleanContourIterations() const;
 
+    /**
+     * @brief   Minimum number of points within the trim radius of a face.
+     *          Faces with less support are removed. Disabled if 0.
+     */
+    int   getTrimDensity() const;
+
+    /**
+     * @brief   Search radius for density based trimming
+     */
+    float getTrimRadius() const;
+
     /**
      * @brief   Returns the number of dangling artifacts to remove from
      *          a created mesh.
@@ -379,6 +438,18 @@ public:
 
     size_t getOctreeMinPoints() const;
 
+    /// ASPRS classes to reconstruct. Empty if all points should be used.
+    vector<unsigned char> getClasses() const;
+
+    bool whiteBalance() const;
+
+    /// Reconstruct pipes as parametric cylinders instead of a marching cubes mesh
+    bool reconstructPipes() const;
+
+    float getPipeDistance() const;
+
+    size_t getPipeMinPoints() const;
+
     bool useRaycastingTexturizer() const;
 
     const std::string& getInputSchema() const;
@@ -549,6 +620,18 @@ inline ostream& operator<<(ostream& os, const Options &o)
     {
         cout << "##### Voxelsize \t\t: " << o.getVoxelsize() << endl;
     }
+    if(o.getRefinementLevels() > 0)
+    {
+        cout << "##### Refinement levels \t: " << o.getRefinementLevels() << endl;
+    }
+    if(o.getTruncationDistance() > 0)
+    {
+        cout << "##### Truncation distance \t: " << o.getTruncationDistance() << endl;
+    }
+    if(o.getMaxDistance() > 0)
+    {
+        cout << "##### Max distance \t\t: " << o.getMaxDistance() << endl;
+    }
     cout << "##### Number of threads \t: "    << o.getNumThreads()      << endl;
     cout << "##### Point cloud manager \t: " << o.getPCM()             << endl;
     cout << "##### Normal Estimation:  \t: " << o.getNormalEstimation() << endl;
@@ -565,6 +648,7 @@ inline ostream& operator<<(ostream& os, const Options &o)
     }
     cout << "##### k_n \t\t\t: "              << o.getKn()              << endl;
     cout << "##### k_i \t\t\t: "              << o.getKi()              << endl;
+    cout << "##### Normal weighting \t\t: "    << o.getNormalWeighting() << endl;
     cout << "##### k_d \t\t\t: "              << o.getKd()              << endl;
     if(o.getDecomposition() == "SF")
     {
@@ -590,6 +674,10 @@ inline ostream& operator<<(ostream& os, const Options &o)
         cout << "##### Fill holes \t\t: NO" << endl;
     }
 
+    if(o.getTrimDensity() > 0)
+    {
+        cout << "##### Trim density \t\t: " << o.getTrimDensity() << endl;
+    }
     if(o.getDanglingArtifacts())
     {
         cout << "##### Remove DAs \t\t: " << o.getDanglingArtifacts() << endl;
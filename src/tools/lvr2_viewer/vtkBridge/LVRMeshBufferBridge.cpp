@@ -48,6 +48,7 @@
 #include <vtkCellData.h>
 #include <vtkUnsignedCharArray.h>
 
+#include "lvr2/texture/TextureFactory.hpp"
 #include "lvr2/util/Util.hpp"
 
 #include <map>
@@ -587,7 +588,7 @@ namespace lvr2
     {
         vector<Texture> &textures = m_meshBuffer->getTextures();
         size_t n = textures.size();
-        Texture &tex = textures[index];
+        Texture tex = TextureFactory::toRGB(textures[index]);
         int w = tex.m_width;
         int h = tex.m_height;
         unsigned char numChannels = tex.m_numChannels;
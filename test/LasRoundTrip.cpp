@@ -0,0 +1,57 @@
+#include <cmath>
+#include <algorithm>
+#include <cstdlib>
+#include <iostream>
+
+#include <boost/filesystem.hpp>
+
+#include "lvr2/types/Model.hpp"
+#include "lvr2/io/modelio/LasIO.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+using namespace lvr2;
+
+/**
+ * Writes UTM like coordinates (northing around 5.8e6) without a geo offset
+ * and checks that they are read back unchanged.
+ */
+int main()
+{
+    // All values are exactly representable as float
+    const float coordinates[] = {
+        500000.0f, 5800000.0f, 42.0f,
+        500123.5f, 5812345.5f, 57.25f,
+        499876.5f, 5787654.5f, 12.5f
+    };
+    const size_t n = 3;
+
+    floatArr points(new float[3 * n]);
+    std::copy(coordinates, coordinates + 3 * n, points.get());
+    PointBufferPtr buffer(new PointBuffer(points, n));
+
+    const boost::filesystem::path file = boost::filesystem::temp_directory_path()
+        / boost::filesystem::unique_path("lvr2_las_%%%%%%%%.las");
+
+    LasIO io;
+    io.save(ModelPtr(new Model(buffer)), file.string());
+    ModelPtr model = io.read(file.string());
+    boost::filesystem::remove(file);
+
+    if (!model || !model->m_pointCloud || model->m_pointCloud->numPoints() != n)
+    {
+        std::cerr << "Unable to read back the written points" << std::endl;
+        return EXIT_FAILURE;
+    }
+
+    int failures = 0;
+    floatArr read = model->m_pointCloud->getPointArray();
+    for (size_t i = 0; i < 3 * n; i++)
+    {
+        if (std::abs(read[i] - coordinates[i]) > 1e-3)
+        {
+            std::cerr << "Coordinate " << i << " is " << read[i] << " instead of " << coordinates[i] << std::endl;
+            failures++;
+        }
+    }
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}
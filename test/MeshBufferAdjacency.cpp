@@ -0,0 +1,108 @@
+#include <algorithm>
+#include <cmath>
+#include <cstdlib>
+#include <iostream>
+
+#include "lvr2/algorithm/SurfaceClassification.hpp"
+#include "lvr2/types/MeshBuffer.hpp"
+
+using namespace lvr2;
+
+namespace
+{
+
+/**
+ * @brief Builds the unit cube with outward facing triangles. Every quad is
+ *        split along the same diagonal, so the corners are adjacent to
+ *        different numbers of triangles per side.
+ */
+MeshBufferPtr unitCube()
+{
+    floatArr vertices(new float[3 * 8]);
+    for (int i = 0; i < 8; i++)
+    {
+        vertices[3 * i] = i & 1;
+        vertices[3 * i + 1] = (i >> 1) & 1;
+        vertices[3 * i + 2] = (i >> 2) & 1;
+    }
+
+    const unsigned int quads[6][4] = {
+        {0, 2, 3, 1}, {4, 5, 7, 6},
+        {0, 1, 5, 4}, {2, 6, 7, 3},
+        {0, 4, 6, 2}, {1, 3, 7, 5}
+    };
+    indexArray faces(new unsigned int[3 * 12]);
+    for (int q = 0; q < 6; q++)
+    {
+        const unsigned int triangles[6] = {
+            quads[q][0], quads[q][1], quads[q][2],
+            quads[q][0], quads[q][2], quads[q][3]
+        };
+        std::copy(triangles, triangles + 6, faces.get() + 6 * q);
+    }
+
+    MeshBufferPtr mesh(new MeshBuffer);
+    mesh->setVertices(vertices, 8);
+    mesh->setFaceIndices(faces, 12);
+    return mesh;
+}
+
+} // namespace
+
+int main()
+{
+    MeshBufferPtr mesh = unitCube();
+    int failures = 0;
+
+    MeshAdjacencyPtr adjacency = mesh->getAdjacency();
+    if (!adjacency || adjacency != mesh->getAdjacency())
+    {
+        std::cerr << "The adjacency is not cached" << std::endl;
+        failures++;
+    }
+
+    ucharArr colors(new unsigned char[3 * 8]());
+    mesh->setVertexColors(colors);
+    if (adjacency == mesh->getAdjacency())
+    {
+        std::cerr << "The adjacency was not invalidated by a setter" << std::endl;
+        failures++;
+    }
+
+    // Angle weighting is independent of the triangulation, so the normals are the cube diagonals
+    if (!mesh->computeVertexNormals())
+    {
+        std::cerr << "Unable to compute vertex normals" << std::endl;
+        failures++;
+    }
+    floatArr vertices = mesh->getVertices();
+    floatArr normals = mesh->getVertexNormals();
+    const float c = 1.0f / std::sqrt(3.0f);
+    for (size_t i = 0; normals && i < 3 * 8; i++)
+    {
+        const float expected = (vertices[i] * 2 - 1) * c;
+        if (std::abs(normals[i] - expected) > 1e-5)
+        {
+            std::cerr << "Normal coordinate " << i << " is " << normals[i] << " instead of " << expected << std::endl;
+            failures++;
+        }
+    }
+
+    const size_t numRegions = growPlanarRegions(mesh, 10.0);
+    IndexChannelOptional regions = mesh->getIndexChannel("face_regions");
+    if (numRegions != 6 || !regions)
+    {
+        std::cerr << "Found " << numRegions << " planar regions instead of 6" << std::endl;
+        failures++;
+    }
+    for (size_t q = 0; regions && q < 6; q++)
+    {
+        if ((*regions)[2 * q][0] != (*regions)[2 * q + 1][0])
+        {
+            std::cerr << "The triangles of side " << q << " are in different regions" << std::endl;
+            failures++;
+        }
+    }
+
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}
@@ -0,0 +1,75 @@
+#include <cstdlib>
+#include <iostream>
+
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/reconstruction/ReconstructionPipeline.hpp"
+#include "lvr2/util/Synthetic.hpp"
+
+using namespace lvr2;
+
+using Vec = BaseVector<float>;
+
+/**
+ * Runs a simplify stage after the reconstruction and checks that the per
+ * vertex and per face results refer to the elements of the simplified mesh.
+ */
+int main()
+{
+    PointBufferPtr points = samplePoints(Sphere(Vector3f::Zero(), 1.0f), 20000);
+
+    ReconstructionOptions options;
+    options.voxelSize = 0.1;
+    options.vertexQuality = true;
+    options.faceRegions = FaceRegionType::Cluster;
+
+    ReconstructionPipeline<Vec> pipeline;
+    pipeline.reconstruct(options).simplify(0.5);
+    auto result = pipeline.run(points);
+
+    int failures = 0;
+    const size_t numVertices = result.mesh.numVertices();
+    if (numVertices == 0)
+    {
+        std::cerr << "Reconstruction produced an empty mesh" << std::endl;
+        return EXIT_FAILURE;
+    }
+
+    auto check = [&](const char* name, size_t numValues, auto containsKey)
+    {
+        if (numValues != numVertices)
+        {
+            std::cerr << name << " has " << numValues << " values for " << numVertices << " vertices" << std::endl;
+            failures++;
+        }
+        for (auto vH : result.mesh.vertices())
+        {
+            if (!containsKey(vH))
+            {
+                std::cerr << name << " has no value for vertex " << vH.idx() << std::endl;
+                failures++;
+                break;
+            }
+        }
+    };
+    check("vertexNormals", result.vertexNormals.numValues(), [&](VertexHandle vH) { return result.vertexNormals.containsKey(vH); });
+    check("vertexDensity", result.vertexDensity.numValues(), [&](VertexHandle vH) { return result.vertexDensity.containsKey(vH); });
+    check("vertexResidual", result.vertexResidual.numValues(), [&](VertexHandle vH) { return result.vertexResidual.containsKey(vH); });
+
+    if (result.faceRegions.numValues() != result.mesh.numFaces())
+    {
+        std::cerr << "faceRegions has " << result.faceRegions.numValues() << " values for "
+                  << result.mesh.numFaces() << " faces" << std::endl;
+        failures++;
+    }
+    for (auto fH : result.mesh.faces())
+    {
+        if (!result.faceRegions.containsKey(fH))
+        {
+            std::cerr << "faceRegions has no value for face " << fH.idx() << std::endl;
+            failures++;
+            break;
+        }
+    }
+
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}
@@ -0,0 +1,85 @@
+#include <cstdlib>
+#include <iostream>
+#include <random>
+#include <string>
+#include <vector>
+
+#include "lvr2/algorithm/KDTree.hpp"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/reconstruction/SearchTree.hpp"
+#include "lvr2/reconstruction/SearchTreeFlann.hpp"
+#include "lvr2/types/PointBuffer.hpp"
+
+using namespace lvr2;
+
+using Vec = BaseVector<float>;
+
+/**
+ * Checks that kSearchPoints() returns the same neighbors as kSearch()
+ * followed by a lookup of the found indices in the point buffer.
+ */
+int compareWithTwoStepSearch(const std::string& name, const SearchTree<Vec>& tree, floatArr points, size_t n)
+{
+    std::mt19937 rng(7);
+    std::uniform_real_distribution<float> coordinate(-1.0f, 11.0f);
+
+    int failures = 0;
+    std::vector<size_t> indices;
+    std::vector<size_t> combinedIndices;
+    std::vector<Vec> neighbors;
+    for (int q = 0; q < 100; q++)
+    {
+        const Vec query(coordinate(rng), coordinate(rng), coordinate(rng));
+        const int k = 1 + q % 20;
+
+        const int found = tree.kSearch(query, k, indices);
+        const int combinedFound = tree.kSearchPoints(query, k, combinedIndices, neighbors);
+
+        if (found != combinedFound || combinedIndices.size() != static_cast<size_t>(combinedFound)
+            || neighbors.size() != combinedIndices.size())
+        {
+            std::cerr << name << ": query " << q << " found " << combinedFound << " neighbors instead of " << found << std::endl;
+            failures++;
+            continue;
+        }
+
+        for (int i = 0; i < found; i++)
+        {
+            const size_t index = indices[i];
+            if (index >= n)
+            {
+                std::cerr << name << ": query " << q << " found the invalid index " << index << std::endl;
+                failures++;
+                continue;
+            }
+
+            const Vec expected(points[3 * index], points[3 * index + 1], points[3 * index + 2]);
+            if (combinedIndices[i] != index || neighbors[i] != expected)
+            {
+                std::cerr << name << ": query " << q << " neighbor " << i << " does not match the two step search" << std::endl;
+                failures++;
+            }
+        }
+    }
+    return failures;
+}
+
+int main()
+{
+    std::mt19937 rng(42);
+    std::uniform_real_distribution<float> coordinate(0.0f, 10.0f);
+
+    const size_t n = 2000;
+    floatArr points(new float[3 * n]);
+    for (size_t i = 0; i < 3 * n; i++)
+    {
+        points[i] = coordinate(rng);
+    }
+    PointBufferPtr buffer(new PointBuffer(points, n));
+
+    int failures = 0;
+    failures += compareWithTwoStepSearch("SearchTreeFlann", SearchTreeFlann<Vec>(buffer), points, n);
+    failures += compareWithTwoStepSearch("SearchKDTree", SearchKDTree<Vec>(buffer), points, n);
+
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}
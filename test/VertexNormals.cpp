@@ -0,0 +1,89 @@
+#include <cmath>
+#include <cstdlib>
+#include <iostream>
+#include <vector>
+
+#include "lvr2/algorithm/NormalAlgorithms.hpp"
+#include "lvr2/geometry/BaseVector.hpp"
+#include "lvr2/geometry/HalfEdgeMesh.hpp"
+
+using namespace lvr2;
+
+using Vec = BaseVector<float>;
+
+namespace
+{
+
+/**
+ * @brief Builds the unit cube with outward facing triangles. Every quad is
+ *        split along the same diagonal, so the corners are adjacent to
+ *        different numbers of triangles per side.
+ */
+HalfEdgeMesh<Vec> unitCube(std::vector<VertexHandle>& corners)
+{
+    HalfEdgeMesh<Vec> mesh;
+    for (int i = 0; i < 8; i++)
+    {
+        corners.push_back(mesh.addVertex(Vec(i & 1, (i >> 1) & 1, (i >> 2) & 1)));
+    }
+
+    const int quads[6][4] = {
+        {0, 2, 3, 1}, {4, 5, 7, 6},
+        {0, 1, 5, 4}, {2, 6, 7, 3},
+        {0, 4, 6, 2}, {1, 3, 7, 5}
+    };
+    for (const auto& q : quads)
+    {
+        mesh.addFace(corners[q[0]], corners[q[1]], corners[q[2]]);
+        mesh.addFace(corners[q[0]], corners[q[2]], corners[q[3]]);
+    }
+    return mesh;
+}
+
+/// Checks whether the normal of the corner points along the cube diagonal (±1,±1,±1)/√3
+bool isDiagonal(const Normal<float>& normal, const Vec& corner)
+{
+    const float c = 1.0f / std::sqrt(3.0f);
+    const Vec expected((corner.x * 2 - 1) * c, (corner.y * 2 - 1) * c, (corner.z * 2 - 1) * c);
+    return std::abs(normal.x - expected.x) < 1e-5
+        && std::abs(normal.y - expected.y) < 1e-5
+        && std::abs(normal.z - expected.z) < 1e-5;
+}
+
+} // namespace
+
+int main()
+{
+    std::vector<VertexHandle> corners;
+    auto mesh = unitCube(corners);
+    auto faceNormals = calcFaceNormals(mesh);
+
+    int failures = 0;
+    size_t uniformDiagonal = 0;
+    for (auto vH : corners)
+    {
+        auto corner = mesh.getVertexPosition(vH);
+
+        auto angle = interpolatedVertexNormal(mesh, faceNormals, vH, VertexNormalWeighting::Angle);
+        if (!angle || !isDiagonal(*angle, corner))
+        {
+            std::cerr << "Angle weighted normal of corner " << corner << " is not the cube diagonal" << std::endl;
+            failures++;
+        }
+
+        auto uniform = interpolatedVertexNormal(mesh, faceNormals, vH, VertexNormalWeighting::Uniform);
+        if (uniform && isDiagonal(*uniform, corner))
+        {
+            uniformDiagonal++;
+        }
+    }
+
+    // With uneven triangle counts per side, uniform weighting is biased away from the diagonal
+    if (uniformDiagonal == corners.size())
+    {
+        std::cerr << "Uniform weighted normals unexpectedly match the cube diagonals" << std::endl;
+        failures++;
+    }
+
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}